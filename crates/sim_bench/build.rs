@@ -18,8 +18,7 @@ fn main() {
     let dirty = Command::new("git")
         .args(["diff", "--quiet"])
         .status()
-        .map(|s| !s.success())
-        .unwrap_or(true);
+        .map_or(true, |s| !s.success());
 
     println!("cargo:rustc-env=GIT_SHA={sha}");
     println!("cargo:rustc-env=GIT_DIRTY={dirty}");
@@ -291,18 +291,21 @@ fn print_comparison_summary(report: &ComparisonReport) {
 // Orchestration
 // ---------------------------------------------------------------------------
 
-struct ArmConfig<'a> {
-    label: &'a str,
-    content: &'a sim_core::GameContent,
-    seeds: &'a [u64],
-    scenario_name: &'a str,
-    scenario_params: &'a serde_json::Value,
-    base_state: Option<&'a sim_core::GameState>,
-    ticks: u64,
-    metrics_every: u64,
+pub(crate) struct ArmConfig<'a> {
+    pub label: &'a str,
+    pub content: &'a sim_core::GameContent,
+    pub seeds: &'a [u64],
+    pub scenario_name: &'a str,
+    pub scenario_params: &'a serde_json::Value,
+    pub base_state: Option<&'a sim_core::GameState>,
+    pub controller: sim_control::ControllerProfile,
+    pub metrics_format: sim_world::MetricsFormat,
+    pub ticks: u64,
+    pub metrics_every: u64,
+    pub hash_every: Option<u64>,
 }
 
-fn run_arm(config: &ArmConfig<'_>, arm_dir: &Path) -> Result<Vec<runner::SeedResult>> {
+pub(crate) fn run_arm(config: &ArmConfig<'_>, arm_dir: &Path) -> Result<Vec<runner::SeedResult>> {
     println!(
         "Running arm {} ({} seeds)...",
         config.label,
@@ -318,10 +321,13 @@ fn run_arm(config: &ArmConfig<'_>, arm_dir: &Path) -> Result<Vec<runner::SeedRes
                 seed,
                 config.ticks,
                 config.metrics_every,
+                config.hash_every,
                 &seed_dir,
                 config.scenario_name,
                 config.scenario_params,
                 config.base_state,
+                config.controller,
+                config.metrics_format,
             )
         })
         .collect();
@@ -352,7 +358,7 @@ fn load_autopilot_config(path: &str) -> Result<sim_core::AutopilotConfig> {
 }
 
 fn load_base_content(scenario: &scenario::Scenario) -> Result<sim_core::GameContent> {
-    let mut content = sim_world::load_content(&scenario.content_dir)?;
+    let mut content = scenario.load_content()?;
     let non_autopilot_overrides: HashMap<String, serde_json::Value> = scenario
         .overrides
         .iter()
@@ -402,10 +408,7 @@ pub fn run_compare(
     content_b.autopilot = load_autopilot_config(config_b_path)?;
 
     let base_state = if let Some(ref state_path) = scenario.state {
-        let json = std::fs::read_to_string(state_path)
-            .with_context(|| format!("reading state file: {state_path}"))?;
-        let mut loaded: sim_core::GameState = serde_json::from_str(&json)
-            .with_context(|| format!("parsing state file: {state_path}"))?;
+        let mut loaded = crate::scenario::read_state_file(state_path)?;
         loaded.body_cache = sim_core::build_body_cache(&content_a.solar_system.bodies);
         Some(loaded)
     } else {
@@ -415,6 +418,7 @@ pub fn run_compare(
     let scenario_params = serde_json::json!({
         "ticks": scenario.ticks,
         "metrics_every": scenario.metrics_every,
+        "controller": scenario.controller,
     });
 
     let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
@@ -430,8 +434,11 @@ pub fn run_compare(
         scenario_name: &scenario.name,
         scenario_params: &scenario_params,
         base_state: base_state.as_ref(),
+        controller: scenario.controller,
+        metrics_format: scenario.metrics_format,
         ticks: scenario.ticks,
         metrics_every: scenario.metrics_every,
+        hash_every: scenario.hash_every,
     };
 
     let results_a = run_arm(&arm_cfg("A", &content_a), &run_dir.join("arm_a"))?;
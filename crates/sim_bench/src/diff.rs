@@ -0,0 +1,546 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Prefix of the metric names built from `per_element_material_kg`, the
+/// same names the regression gate accepts.
+const ELEMENT_PREFIX: &str = "per_element_material_kg.";
+
+#[derive(Debug, Serialize)]
+pub struct DiffReport {
+    pub batch_a: BatchInfo,
+    pub batch_b: BatchInfo,
+    pub metrics: Vec<MetricDiff>,
+    /// Metrics recorded by only one batch (e.g. an element one side never
+    /// refined).
+    pub only_in_a: Vec<String>,
+    pub only_in_b: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchInfo {
+    pub path: String,
+    pub scenario_name: String,
+    pub seed_count: u64,
+    /// Seeds whose final metrics row was found; the significance test uses
+    /// these.
+    pub seeds_read: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricDiff {
+    pub metric: String,
+    pub mean_a: f64,
+    pub mean_b: f64,
+    /// `mean_b - mean_a`.
+    pub delta: f64,
+    /// `delta / |mean_a|`; `None` when `mean_a` is 0.
+    pub delta_pct: Option<f64>,
+    /// `None` when either batch has fewer than two seeds with the metric.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mann_whitney: Option<MannWhitneyResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MannWhitneyResult {
+    /// U statistic of batch A.
+    pub u_statistic: f64,
+    /// Two-sided p-value: exact without ties, normal approximation with tie
+    /// correction otherwise.
+    pub p_value: f64,
+    pub significant_at_05: bool,
+}
+
+/// One batch directory: its `batch_summary.json` and, per seed, the final
+/// row of the seed's metrics CSV.
+struct Batch {
+    path: PathBuf,
+    summary: serde_json::Value,
+    /// Metric name -> one final value per seed that recorded it.
+    per_seed: BTreeMap<String, Vec<f64>>,
+    seeds_read: usize,
+}
+
+// ---------------------------------------------------------------------------
+// Statistics
+// ---------------------------------------------------------------------------
+
+/// Ranks (1-based) of `values`, with tied values sharing their mean rank.
+fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]].total_cmp(&values[order[start]]).is_eq() {
+            end += 1;
+        }
+        // Positions start..end hold ranks start+1..=end.
+        let mean_rank = (start + end + 1) as f64 / 2.0;
+        for &index in &order[start..end] {
+            ranks[index] = mean_rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// Two-sided Mann-Whitney U test of `a` against `b`.
+/// Returns None if either sample has fewer than 2 values.
+fn mann_whitney(a: &[f64], b: &[f64]) -> Option<MannWhitneyResult> {
+    if a.len() < 2 || b.len() < 2 {
+        return None;
+    }
+    let combined: Vec<f64> = a.iter().chain(b).copied().collect();
+    let ranks = ranks(&combined);
+    let (n_a, n_b) = (a.len() as f64, b.len() as f64);
+    let rank_sum_a: f64 = ranks[..a.len()].iter().sum();
+    let u_statistic = rank_sum_a - n_a * (n_a + 1.0) / 2.0;
+
+    let mut tie_sizes: BTreeMap<u64, f64> = BTreeMap::new();
+    for value in &combined {
+        *tie_sizes.entry(value.to_bits()).or_default() += 1.0;
+    }
+    let tie_term: f64 = tie_sizes.values().map(|t| t * t * t - t).sum();
+
+    let p_value = if tie_term == 0.0 {
+        exact_p_value(u_statistic, a.len(), b.len())
+    } else {
+        let n = n_a + n_b;
+        let mean = n_a * n_b / 2.0;
+        let variance = n_a * n_b / 12.0 * ((n + 1.0) - tie_term / (n * (n - 1.0)));
+        if variance <= 0.0 {
+            // Every value tied: no evidence of a difference.
+            1.0
+        } else {
+            // Continuity correction towards the mean.
+            let z = ((u_statistic - mean).abs() - 0.5).max(0.0) / variance.sqrt();
+            (2.0 * normal_upper_tail(z)).min(1.0)
+        }
+    };
+
+    Some(MannWhitneyResult {
+        u_statistic,
+        p_value,
+        significant_at_05: p_value < 0.05,
+    })
+}
+
+/// Two-sided p-value of `u` under the exact null distribution of U for
+/// samples of `n_a` and `n_b` untied values.
+fn exact_p_value(u: f64, n_a: usize, n_b: usize) -> f64 {
+    // counts[i][u] = number of orderings of i A-values and j B-values with
+    // statistic u, built up one B-value at a time.
+    let max_u = n_a * n_b;
+    let mut counts: Vec<Vec<f64>> = (0..=n_a)
+        .map(|_| {
+            let mut row = vec![0.0; max_u + 1];
+            row[0] = 1.0;
+            row
+        })
+        .collect();
+    for j in 1..=n_b {
+        let mut next = vec![vec![0.0; max_u + 1]; n_a + 1];
+        next[0][0] = 1.0;
+        for i in 1..=n_a {
+            for stat in 0..=i * j {
+                // The largest value is either a B-value (U unchanged) or an
+                // A-value that beats all j B-values.
+                let largest_b = counts[i][stat];
+                let largest_a = if stat >= j {
+                    next[i - 1][stat - j]
+                } else {
+                    0.0
+                };
+                next[i][stat] = largest_b + largest_a;
+            }
+        }
+        counts = next;
+    }
+    let distribution = &counts[n_a];
+    let total: f64 = distribution.iter().sum();
+    let lower: f64 = distribution
+        .iter()
+        .enumerate()
+        .filter(|(stat, _)| (*stat as f64) <= u + 1e-9)
+        .map(|(_, count)| count)
+        .sum();
+    let upper: f64 = distribution
+        .iter()
+        .enumerate()
+        .filter(|(stat, _)| (*stat as f64) >= u - 1e-9)
+        .map(|(_, count)| count)
+        .sum();
+    (2.0 * lower.min(upper) / total).min(1.0)
+}
+
+/// P(Z > z) for a standard normal Z (Abramowitz & Stegun 7.1.26, error
+/// below 1.5e-7).
+fn normal_upper_tail(z: f64) -> f64 {
+    let x = z.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.327_591_1 * x);
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erfc = poly * (-x * x).exp();
+    if z >= 0.0 {
+        erfc / 2.0
+    } else {
+        1.0 - erfc / 2.0
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Loading
+// ---------------------------------------------------------------------------
+
+/// Load a batch from its directory or its `batch_summary.json`.
+fn load_batch(path: &Path) -> Result<Batch> {
+    let (dir, summary_path) = if path.is_dir() {
+        (path.to_path_buf(), path.join("batch_summary.json"))
+    } else {
+        let dir = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf();
+        (dir, path.to_path_buf())
+    };
+    let summary: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(&summary_path)
+            .with_context(|| format!("reading {}", summary_path.display()))?,
+    )
+    .with_context(|| format!("parsing {}", summary_path.display()))?;
+
+    let mut per_seed: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    let mut seeds_read = 0;
+    for seed_dir in seed_dirs(&dir)? {
+        let Some(row) = final_metrics_row(&seed_dir)? else {
+            continue;
+        };
+        seeds_read += 1;
+        for (column, value) in row {
+            let metric = match column.strip_prefix("material_kg_") {
+                Some(element) => format!("{ELEMENT_PREFIX}{element}"),
+                None => column,
+            };
+            per_seed.entry(metric).or_default().push(value);
+        }
+    }
+    Ok(Batch {
+        path: path.to_path_buf(),
+        summary,
+        per_seed,
+        seeds_read,
+    })
+}
+
+/// `seed_*` directories of seeds that finished (wrote `run_result.json`).
+fn seed_dirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    for entry in std::fs::read_dir(dir).with_context(|| format!("reading {}", dir.display()))? {
+        let path = entry?.path();
+        let is_seed = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("seed_"));
+        if is_seed && path.join("run_result.json").is_file() {
+            dirs.push(path);
+        }
+    }
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// Numeric columns of the last row of a seed's last metrics CSV file, which
+/// the runner always ends with the final snapshot.
+fn final_metrics_row(seed_dir: &Path) -> Result<Option<Vec<(String, f64)>>> {
+    let files = crate::runner::metrics_csv_paths(seed_dir)?;
+    let Some(path) = files.last() else {
+        return Ok(None);
+    };
+    let mut reader =
+        csv::Reader::from_path(path).with_context(|| format!("opening {}", path.display()))?;
+    let headers = reader.headers()?.clone();
+    let mut last = None;
+    for record in reader.records() {
+        last = Some(record.with_context(|| format!("reading {}", path.display()))?);
+    }
+    Ok(last.map(|record| {
+        headers
+            .iter()
+            .zip(record.iter())
+            .filter(|(name, _)| !matches!(*name, "tick" | "metrics_version"))
+            .filter_map(|(name, value)| Some((name.to_string(), value.parse().ok()?)))
+            .collect()
+    }))
+}
+
+// ---------------------------------------------------------------------------
+// Report building
+// ---------------------------------------------------------------------------
+
+/// Metric name -> mean from `aggregated_metrics` and
+/// `per_element_material_kg`.
+fn metric_means(summary: &serde_json::Value) -> BTreeMap<String, f64> {
+    let mut means = BTreeMap::new();
+    let sections = [
+        ("", &summary["aggregated_metrics"]),
+        (ELEMENT_PREFIX, &summary["per_element_material_kg"]),
+    ];
+    for (prefix, section) in sections {
+        let Some(section) = section.as_object() else {
+            continue;
+        };
+        for (name, stats) in section {
+            if let Some(mean) = stats["mean"].as_f64() {
+                means.insert(format!("{prefix}{name}"), mean);
+            }
+        }
+    }
+    means
+}
+
+fn batch_info(batch: &Batch) -> BatchInfo {
+    BatchInfo {
+        path: batch.path.display().to_string(),
+        scenario_name: batch.summary["scenario_name"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        seed_count: batch.summary["seed_count"].as_u64().unwrap_or_default(),
+        seeds_read: batch.seeds_read,
+    }
+}
+
+fn build_diff_report(a: &Batch, b: &Batch) -> DiffReport {
+    let a_means = metric_means(&a.summary);
+    let b_means = metric_means(&b.summary);
+    let names: BTreeSet<&String> = a_means.keys().chain(b_means.keys()).collect();
+    let mut report = DiffReport {
+        batch_a: batch_info(a),
+        batch_b: batch_info(b),
+        metrics: Vec::new(),
+        only_in_a: Vec::new(),
+        only_in_b: Vec::new(),
+    };
+    for name in names {
+        let (mean_a, mean_b) = match (a_means.get(name), b_means.get(name)) {
+            (Some(&mean_a), Some(&mean_b)) => (mean_a, mean_b),
+            (Some(_), None) => {
+                report.only_in_a.push(name.clone());
+                continue;
+            }
+            (None, _) => {
+                report.only_in_b.push(name.clone());
+                continue;
+            }
+        };
+        let delta = mean_b - mean_a;
+        let seeds = |batch: &Batch| batch.per_seed.get(name).cloned().unwrap_or_default();
+        report.metrics.push(MetricDiff {
+            metric: name.clone(),
+            mean_a,
+            mean_b,
+            delta,
+            delta_pct: (mean_a != 0.0).then(|| delta / mean_a.abs() * 100.0),
+            mann_whitney: mann_whitney(&seeds(a), &seeds(b)),
+        });
+    }
+    report
+}
+
+fn print_diff(report: &DiffReport, show_all: bool) {
+    println!(
+        "\n=== Batch diff: {} ({} seeds) -> {} ({} seeds) ===",
+        report.batch_a.scenario_name,
+        report.batch_a.seed_count,
+        report.batch_b.scenario_name,
+        report.batch_b.seed_count
+    );
+    println!("  A: {}", report.batch_a.path);
+    println!("  B: {}", report.batch_b.path);
+    println!(
+        "\n{:<40} {:>14} {:>14} {:>14} {:>9} {:>8}",
+        "Metric", "Mean A", "Mean B", "Delta", "Delta %", "p"
+    );
+    println!("{}", "-".repeat(104));
+    let mut unchanged = 0;
+    for metric in &report.metrics {
+        if !show_all && metric.delta == 0.0 {
+            unchanged += 1;
+            continue;
+        }
+        let delta_pct = metric
+            .delta_pct
+            .map_or_else(|| "-".to_string(), |pct| format!("{pct:+.1}%"));
+        let (p_value, marker) = match &metric.mann_whitney {
+            Some(test) if test.significant_at_05 => (format!("{:.4}", test.p_value), " *"),
+            Some(test) => (format!("{:.4}", test.p_value), ""),
+            None => ("-".to_string(), ""),
+        };
+        println!(
+            "{:<40} {:>14.4} {:>14.4} {:>+14.4} {:>9} {:>8}{}",
+            metric.metric, metric.mean_a, metric.mean_b, metric.delta, delta_pct, p_value, marker
+        );
+    }
+    if unchanged > 0 {
+        println!("({unchanged} metrics unchanged; --all lists them)");
+    }
+    println!("* significant at p<0.05 (two-sided Mann-Whitney U across seeds)");
+    for (label, names) in [("A", &report.only_in_a), ("B", &report.only_in_b)] {
+        if !names.is_empty() {
+            println!("Only in {label}: {}", names.join(", "));
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Orchestration
+// ---------------------------------------------------------------------------
+
+/// Align the aggregated metrics of two recorded batches and print each
+/// metric's delta with a Mann-Whitney test over the seeds' final values.
+pub fn run_diff(batch_a: &str, batch_b: &str, show_all: bool, json: bool) -> Result<()> {
+    let a = load_batch(Path::new(batch_a))?;
+    let b = load_batch(Path::new(batch_b))?;
+    let report = build_diff_report(&a, &b);
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).context("serializing diff report")?
+        );
+    } else {
+        print_diff(&report, show_all);
+        for batch in [&report.batch_a, &report.batch_b] {
+            if batch.seeds_read < 2 {
+                eprintln!(
+                    "warning: {} has {} seed metrics files; significance needs at least 2",
+                    batch.path, batch.seeds_read
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_average_ties() {
+        assert_eq!(ranks(&[10.0, 30.0, 20.0, 20.0]), vec![1.0, 4.0, 2.5, 2.5]);
+    }
+
+    #[test]
+    fn mann_whitney_too_few_samples() {
+        assert!(mann_whitney(&[1.0], &[2.0, 3.0]).is_none());
+    }
+
+    #[test]
+    fn mann_whitney_exact_separated_samples() {
+        // Complete separation of 5 vs 5: p = 2 / C(10, 5) = 2 / 252.
+        let result = mann_whitney(&[1.0, 2.0, 3.0, 4.0, 5.0], &[6.0, 7.0, 8.0, 9.0, 10.0]).unwrap();
+        assert!(result.u_statistic.abs() < 1e-10);
+        assert!((result.p_value - 2.0 / 252.0).abs() < 1e-10);
+        assert!(result.significant_at_05);
+
+        // Complete separation of 3 vs 3 cannot reach 0.05: p = 2 / 20.
+        let result = mann_whitney(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]).unwrap();
+        assert!((result.p_value - 0.1).abs() < 1e-10);
+        assert!(!result.significant_at_05);
+    }
+
+    #[test]
+    fn mann_whitney_interleaved_not_significant() {
+        let result = mann_whitney(&[1.0, 4.0, 5.0, 8.0], &[2.0, 3.0, 6.0, 7.0]).unwrap();
+        assert!((result.u_statistic - 8.0).abs() < 1e-10);
+        assert!((result.p_value - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn mann_whitney_ties_use_normal_approximation() {
+        let all_tied = mann_whitney(&[5.0, 5.0, 5.0], &[5.0, 5.0, 5.0]).unwrap();
+        assert!((all_tied.p_value - 1.0).abs() < 1e-10);
+
+        let a = [1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0];
+        let b = [10.0, 10.0, 11.0, 11.0, 12.0, 12.0, 13.0, 13.0];
+        let result = mann_whitney(&a, &b).unwrap();
+        assert!(result.p_value < 0.01, "{}", result.p_value);
+    }
+
+    #[test]
+    fn normal_tail_known_values() {
+        assert!((normal_upper_tail(0.0) - 0.5).abs() < 1e-6);
+        assert!((normal_upper_tail(1.96) - 0.025).abs() < 1e-4);
+        assert!((normal_upper_tail(-1.96) - 0.975).abs() < 1e-4);
+    }
+
+    fn write_batch(dir: &Path, scenario: &str, balances: &[f64], with_fe: bool) {
+        let mean = balances.iter().sum::<f64>() / balances.len() as f64;
+        let mut per_element = serde_json::json!({});
+        if with_fe {
+            per_element["Fe"] = serde_json::json!({"mean": 100.0});
+        }
+        let summary = serde_json::json!({
+            "scenario_name": scenario,
+            "seed_count": balances.len(),
+            "aggregated_metrics": {
+                "balance": {"mean": mean},
+                "techs_unlocked": {"mean": 2.0},
+            },
+            "per_element_material_kg": per_element,
+        });
+        std::fs::write(dir.join("batch_summary.json"), summary.to_string()).unwrap();
+        for (seed, balance) in balances.iter().enumerate() {
+            let seed_dir = dir.join(format!("seed_{seed}"));
+            std::fs::create_dir_all(&seed_dir).unwrap();
+            std::fs::write(seed_dir.join("run_result.json"), "{}").unwrap();
+            std::fs::write(
+                seed_dir.join("metrics_000.csv"),
+                format!("tick,balance,techs_unlocked\n60,0,0\n120,{balance},2\n"),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn diff_aligns_batches_and_tests_per_seed_values() {
+        let dir_a = tempfile::tempdir().unwrap();
+        let dir_b = tempfile::tempdir().unwrap();
+        write_batch(dir_a.path(), "base", &[1.0, 2.0, 3.0, 4.0, 5.0], true);
+        write_batch(dir_b.path(), "variant", &[6.0, 7.0, 8.0, 9.0, 10.0], false);
+
+        let a = load_batch(dir_a.path()).unwrap();
+        let b = load_batch(&dir_b.path().join("batch_summary.json")).unwrap();
+        assert_eq!(a.seeds_read, 5);
+        assert_eq!(a.per_seed["balance"], vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+
+        let report = build_diff_report(&a, &b);
+        assert_eq!(report.only_in_a, vec!["per_element_material_kg.Fe"]);
+        assert!(report.only_in_b.is_empty());
+
+        let balance = &report.metrics[0];
+        assert_eq!(balance.metric, "balance");
+        assert!((balance.delta - 5.0).abs() < 1e-10);
+        assert!((balance.delta_pct.unwrap() - 5.0 / 3.0 * 100.0).abs() < 1e-9);
+        assert!(balance.mann_whitney.as_ref().unwrap().significant_at_05);
+
+        let techs = &report.metrics[1];
+        assert_eq!(techs.metric, "techs_unlocked");
+        assert!(techs.delta.abs() < 1e-10);
+        assert!(!techs.mann_whitney.as_ref().unwrap().significant_at_05);
+    }
+}
@@ -0,0 +1,373 @@
+use crate::run_result;
+use crate::runner::{self, SeedResult};
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+// ---------------------------------------------------------------------------
+// Charts
+// ---------------------------------------------------------------------------
+
+/// Metrics charted per seed over time; a metric missing from the CSVs is
+/// skipped.
+const CHARTED_METRICS: [&str; 10] = [
+    "balance",
+    "total_ore_kg",
+    "total_material_kg",
+    "techs_unlocked",
+    "fleet_idle",
+    "station_storage_used_pct",
+    "avg_module_wear",
+    "power_deficit_kw",
+    "export_revenue_total",
+    "loan_outstanding",
+];
+
+/// Points kept per seed series; longer series are thinned evenly so the file
+/// stays small for `metrics_every: 1` runs.
+const MAX_POINTS: usize = 500;
+
+const CHART_WIDTH: f64 = 640.0;
+const CHART_HEIGHT: f64 = 220.0;
+const MARGIN_LEFT: f64 = 90.0;
+const MARGIN_BOTTOM: f64 = 24.0;
+
+/// Line colours, cycled when there are more seeds.
+const PALETTE: [&str; 8] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#17becf",
+];
+
+/// One seed's `(tick, value)` samples per charted metric.
+type SeedSeries = BTreeMap<&'static str, Vec<(f64, f64)>>;
+
+/// Read every metrics CSV row of a seed, keeping the charted columns.
+fn read_seed_series(seed_dir: &Path) -> Result<SeedSeries> {
+    let mut series = SeedSeries::new();
+    for path in runner::metrics_csv_paths(seed_dir)? {
+        let mut reader =
+            csv::Reader::from_path(&path).with_context(|| format!("opening {}", path.display()))?;
+        let headers = reader.headers()?.clone();
+        let Some(tick_column) = headers.iter().position(|name| name == "tick") else {
+            continue;
+        };
+        let columns: Vec<(&'static str, usize)> = CHARTED_METRICS
+            .iter()
+            .filter_map(|metric| Some((*metric, headers.iter().position(|h| h == *metric)?)))
+            .collect();
+        for record in reader.records() {
+            let record = record.with_context(|| format!("reading {}", path.display()))?;
+            let Some(tick) = record.get(tick_column).and_then(|v| v.parse().ok()) else {
+                continue;
+            };
+            for &(metric, column) in &columns {
+                if let Some(value) = record.get(column).and_then(|v| v.parse().ok()) {
+                    series.entry(metric).or_default().push((tick, value));
+                }
+            }
+        }
+    }
+    for points in series.values_mut() {
+        thin(points);
+    }
+    Ok(series)
+}
+
+/// Keep at most `MAX_POINTS`, always including the last sample.
+fn thin(points: &mut Vec<(f64, f64)>) {
+    if points.len() <= MAX_POINTS {
+        return;
+    }
+    let stride = points.len().div_ceil(MAX_POINTS);
+    let last = points[points.len() - 1];
+    let mut kept: Vec<(f64, f64)> = points.iter().copied().step_by(stride).collect();
+    if kept.last() != Some(&last) {
+        kept.push(last);
+    }
+    *points = kept;
+}
+
+/// An SVG line chart of one metric, one polyline per seed.
+fn chart_svg(metric: &str, lines: &[(u64, &[(f64, f64)])]) -> String {
+    let points = lines.iter().flat_map(|(_, points)| points.iter());
+    let (mut x_min, mut x_max, mut y_min, mut y_max) = (
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+    );
+    for &(x, y) in points {
+        x_min = x_min.min(x);
+        x_max = x_max.max(x);
+        y_min = y_min.min(y);
+        y_max = y_max.max(y);
+    }
+    // A flat series still gets a visible range.
+    if y_max <= y_min {
+        y_min -= 1.0;
+        y_max += 1.0;
+    }
+    if x_max <= x_min {
+        x_max = x_min + 1.0;
+    }
+    let plot_width = CHART_WIDTH - MARGIN_LEFT - 10.0;
+    let plot_height = CHART_HEIGHT - MARGIN_BOTTOM - 10.0;
+    let scale_x = |x: f64| MARGIN_LEFT + (x - x_min) / (x_max - x_min) * plot_width;
+    let scale_y = |y: f64| 10.0 + (y_max - y) / (y_max - y_min) * plot_height;
+
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        r#"<svg viewBox="0 0 {CHART_WIDTH} {CHART_HEIGHT}" width="{CHART_WIDTH}" height="{CHART_HEIGHT}" role="img" aria-label="{metric}">"#
+    );
+    let _ = write!(
+        svg,
+        r##"<rect x="{MARGIN_LEFT}" y="10" width="{plot_width}" height="{plot_height}" fill="none" stroke="#ccc"/>"##
+    );
+    for (value, y) in [(y_max, 10.0), (y_min, 10.0 + plot_height)] {
+        let _ = write!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="end" class="axis">{}</text>"#,
+            MARGIN_LEFT - 4.0,
+            y + 4.0,
+            format_value(value)
+        );
+    }
+    for (tick, anchor) in [(x_min, "start"), (x_max, "end")] {
+        let _ = write!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="{anchor}" class="axis">tick {tick}</text>"#,
+            scale_x(tick),
+            CHART_HEIGHT - 6.0
+        );
+    }
+    for (index, (_, points)) in lines.iter().enumerate() {
+        let coords: Vec<String> = points
+            .iter()
+            .map(|&(x, y)| format!("{:.1},{:.1}", scale_x(x), scale_y(y)))
+            .collect();
+        let _ = write!(
+            svg,
+            r#"<polyline fill="none" stroke="{}" stroke-width="1.5" points="{}"/>"#,
+            PALETTE[index % PALETTE.len()],
+            coords.join(" ")
+        );
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+// ---------------------------------------------------------------------------
+// Page
+// ---------------------------------------------------------------------------
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn format_value(value: f64) -> String {
+    if value.abs() >= 1e6 {
+        format!("{value:.3e}")
+    } else if value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.3}")
+    }
+}
+
+/// `{name: {mean, min, max, stddev}}` as table rows.
+fn stats_rows(section: &serde_json::Value, out: &mut String) {
+    let Some(section) = section.as_object() else {
+        return;
+    };
+    for (name, stats) in section {
+        let cell = |key: &str| {
+            stats[key]
+                .as_f64()
+                .map_or_else(|| "-".to_string(), format_value)
+        };
+        let _ = write!(
+            out,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(name),
+            cell("mean"),
+            cell("min"),
+            cell("max"),
+            cell("stddev")
+        );
+    }
+}
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2em;color:#222}\
+table{border-collapse:collapse;margin-bottom:1.5em}\
+td,th{border:1px solid #ddd;padding:2px 8px;text-align:right}\
+td:first-child,th:first-child{text-align:left}\
+.collapsed{color:#b00;font-weight:bold}\
+.axis{font-size:10px;fill:#555}\
+.legend span{display:inline-block;margin-right:1em}\
+.chart{display:inline-block;margin:0 1em 1em 0}";
+
+fn render(
+    scenario_name: &str,
+    ticks: u64,
+    seeds: &[SeedResult],
+    series: &[(u64, SeedSeries)],
+    batch_summary: &serde_json::Value,
+) -> String {
+    let mut html = String::new();
+    let title = format!("sim_bench: {}", escape(scenario_name));
+    let _ = write!(
+        html,
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{title}</title>\
+         <style>{STYLE}</style></head><body><h1>{title}</h1>"
+    );
+    let collapsed = seeds
+        .iter()
+        .filter(|seed| run_result::detect_collapse(&seed.final_snapshot).0)
+        .count();
+    let _ = write!(
+        html,
+        "<p>{} seeds &times; {ticks} ticks, {collapsed} collapsed. Batch <code>{}</code>.</p>",
+        seeds.len(),
+        escape(batch_summary["batch_id"].as_str().unwrap_or_default())
+    );
+
+    html.push_str(
+        "<h2>Seeds</h2><table><tr><th>Seed</th><th>Composite</th><th>Threshold</th>\
+         <th>Collapse</th></tr>",
+    );
+    for seed in seeds {
+        let collapse = match run_result::detect_collapse(&seed.final_snapshot) {
+            (true, reason) => format!(
+                "<span class=\"collapsed\">{}</span>",
+                escape(reason.as_deref().unwrap_or("collapsed"))
+            ),
+            (false, _) => "-".to_string(),
+        };
+        let _ = write!(
+            html,
+            "<tr><td>{}</td><td>{:.2}</td><td>{}</td><td>{collapse}</td></tr>",
+            seed.seed,
+            seed.final_score.composite,
+            escape(&seed.final_score.threshold)
+        );
+    }
+    html.push_str("</table>");
+
+    html.push_str("<h2>Time series</h2><p class=\"legend\">");
+    for (index, (seed, _)) in series.iter().enumerate() {
+        let _ = write!(
+            html,
+            "<span style=\"color:{}\">&#9632; seed {seed}</span>",
+            PALETTE[index % PALETTE.len()]
+        );
+    }
+    html.push_str("</p>");
+    for metric in CHARTED_METRICS {
+        let lines: Vec<(u64, &[(f64, f64)])> = series
+            .iter()
+            .filter_map(|(seed, seed_series)| {
+                Some((*seed, seed_series.get(metric)?.as_slice()))
+                    .filter(|(_, points)| !points.is_empty())
+            })
+            .collect();
+        if lines.is_empty() {
+            continue;
+        }
+        let _ = write!(
+            html,
+            "<div class=\"chart\"><h3>{metric}</h3>{}</div>",
+            chart_svg(metric, &lines)
+        );
+    }
+
+    let header = "<tr><th>Metric</th><th>Mean</th><th>Min</th><th>Max</th><th>StdDev</th></tr>";
+    let _ = write!(
+        html,
+        "<h2>Aggregated metrics (final tick)</h2><table>{header}"
+    );
+    stats_rows(&batch_summary["aggregated_metrics"], &mut html);
+    let _ = write!(
+        html,
+        "</table><h2>Refined material (kg)</h2><table>{header}"
+    );
+    stats_rows(&batch_summary["per_element_material_kg"], &mut html);
+    html.push_str("</table></body></html>\n");
+    html
+}
+
+// ---------------------------------------------------------------------------
+// Orchestration
+// ---------------------------------------------------------------------------
+
+/// Write a self-contained `report.html` into `run_dir`: per-seed charts read
+/// back from the seeds' metrics CSVs, collapse flags and the batch's
+/// aggregated tables. Needs no network access or scripts to view.
+pub fn write_html_report(
+    run_dir: &Path,
+    scenario_name: &str,
+    ticks: u64,
+    seeds: &[SeedResult],
+    batch_summary: &serde_json::Value,
+) -> Result<std::path::PathBuf> {
+    let series = seeds
+        .iter()
+        .map(|seed| {
+            let seed_dir = run_dir.join(format!("seed_{}", seed.seed));
+            Ok((seed.seed, read_seed_series(&seed_dir)?))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let html = render(scenario_name, ticks, seeds, &series, batch_summary);
+    let path = run_dir.join("report.html");
+    std::fs::write(&path, html).with_context(|| format!("writing {}", path.display()))?;
+    Ok(path)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thin_keeps_last_sample() {
+        let mut points: Vec<(f64, f64)> = (0..1234).map(|i| (f64::from(i), 0.0)).collect();
+        thin(&mut points);
+        assert!(points.len() <= MAX_POINTS + 1);
+        assert_eq!(points.last(), Some(&(1233.0, 0.0)));
+    }
+
+    #[test]
+    fn report_charts_seed_series_and_escapes_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let seed_dir = dir.path().join("seed_7");
+        std::fs::create_dir_all(&seed_dir).unwrap();
+        std::fs::write(
+            seed_dir.join("metrics_000.csv"),
+            "tick,balance,techs_unlocked\n0,100,0\n10,250.5,1\n",
+        )
+        .unwrap();
+
+        let series = read_seed_series(&seed_dir).unwrap();
+        assert_eq!(series["balance"], vec![(0.0, 100.0), (10.0, 250.5)]);
+        assert!(!series.contains_key("total_ore_kg"));
+
+        let summary = serde_json::json!({
+            "batch_id": "b1",
+            "aggregated_metrics": {"balance": {"mean": 250.5, "min": 250.5, "max": 250.5, "stddev": 0.0}},
+            "per_element_material_kg": {},
+        });
+        let html = render("a<b", 10, &[], &[(7, series)], &summary);
+        assert!(html.contains("sim_bench: a&lt;b"));
+        assert!(html.contains("<h3>balance</h3><svg"));
+        assert!(html.contains("<h3>techs_unlocked</h3>"));
+        assert!(!html.contains("<h3>total_ore_kg</h3>"));
+        assert!(html.contains("seed 7"));
+        assert!(html.contains("<td>250.500</td>"));
+    }
+}
@@ -6,12 +6,15 @@ use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 mod compare;
+mod diff;
+mod html_report;
 mod overrides;
-mod parquet_writer;
+mod regression;
 mod run_result;
 mod runner;
 mod scenario;
 mod summary;
+mod sweep;
 
 #[derive(Parser)]
 #[command(
@@ -33,38 +36,126 @@ enum Commands {
         /// Output directory (default: runs/).
         #[arg(long, default_value = "runs")]
         output_dir: String,
+        /// Autopilot profile, overriding the scenario's `controller`.
+        #[arg(long)]
+        controller: Option<sim_control::ControllerProfile>,
+        /// Per-seed metrics files: csv, parquet or both, overriding the
+        /// scenario's `metrics_format` (default both).
+        #[arg(long)]
+        metrics_format: Option<sim_world::MetricsFormat>,
+        #[command(flatten)]
+        experiment: ExperimentArgs,
     },
-    /// Compare two autopilot config files using the same seeds.
+    /// Compare two autopilot config files using the same seeds, or with
+    /// `--baseline`, gate a rerun of the scenario against a recorded batch.
     Compare {
         /// Path to the scenario JSON file (defines seeds, ticks, content).
         #[arg(long)]
         scenario: String,
         /// Path to the first autopilot config JSON file (baseline).
-        #[arg(long)]
-        config_a: String,
+        #[arg(long, required_unless_present = "baseline")]
+        config_a: Option<String>,
         /// Path to the second autopilot config JSON file (variant).
+        #[arg(long, required_unless_present = "baseline")]
+        config_b: Option<String>,
+        /// `batch_summary.json` of an earlier run. Reruns the scenario and
+        /// exits non-zero if a gated metric regressed beyond its tolerance.
+        #[arg(long, conflicts_with_all = ["config_a", "config_b"])]
+        baseline: Option<String>,
+        /// Gate tolerance `METRIC=T`, replacing a default or gating another
+        /// aggregated metric (repeatable; needs --baseline).
+        #[arg(long = "tolerance", requires = "baseline", value_parser = regression::parse_tolerance)]
+        tolerances: Vec<(String, f64)>,
+        /// Output directory (default: runs/).
+        #[arg(long, default_value = "runs")]
+        output_dir: String,
+    },
+    /// Run a scenario once per combination of override values and rank the
+    /// combinations by mean composite score.
+    Sweep {
+        /// Path to the scenario JSON file (defines seeds, ticks, content).
         #[arg(long)]
-        config_b: String,
+        scenario: String,
+        /// Override key and the values to try, e.g.
+        /// `autopilot.weights.mine_distance=0,0.5,1` (repeatable).
+        #[arg(long = "param", value_parser = sweep::parse_param)]
+        params: Vec<sweep::SweepParam>,
         /// Output directory (default: runs/).
         #[arg(long, default_value = "runs")]
         output_dir: String,
     },
+    /// Align the aggregated metrics of two recorded batches and print each
+    /// delta with a Mann-Whitney significance test across seeds.
+    Diff {
+        /// Batch directory (or its `batch_summary.json`) to compare against.
+        batch_a: String,
+        /// Batch directory (or its `batch_summary.json`) with the change.
+        batch_b: String,
+        /// Also list metrics whose means are unchanged.
+        #[arg(long)]
+        all: bool,
+        /// Print the report as JSON instead of a table.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Experiment notebook flags; each overrides the scenario's `experiment`
+/// field of the same name.
+#[derive(clap::Args)]
+struct ExperimentArgs {
+    /// What this batch is meant to show.
+    #[arg(long)]
+    hypothesis: Option<String>,
+    /// Free-form notes.
+    #[arg(long)]
+    notes: Option<String>,
+    /// Content git commit (default: detected from the content directory).
+    #[arg(long)]
+    content_commit: Option<String>,
+}
+
+impl ExperimentArgs {
+    fn into_meta(self) -> sim_world::ExperimentMeta {
+        sim_world::ExperimentMeta {
+            hypothesis: self.hypothesis,
+            notes: self.notes,
+            content_commit: self.content_commit,
+        }
+    }
 }
 
 #[allow(clippy::too_many_lines)]
-fn run(scenario_path: &str, output_dir: &str) -> Result<()> {
-    let scenario = scenario::load_scenario(Path::new(scenario_path))?;
+fn run(
+    scenario_path: &str,
+    output_dir: &str,
+    controller: Option<sim_control::ControllerProfile>,
+    metrics_format: Option<sim_world::MetricsFormat>,
+    experiment: ExperimentArgs,
+) -> Result<()> {
+    let mut scenario = scenario::load_scenario(Path::new(scenario_path))?;
+    if let Some(controller) = controller {
+        scenario.controller = controller;
+    }
+    if let Some(metrics_format) = metrics_format {
+        scenario.metrics_format = metrics_format;
+    }
     let seeds = scenario.seeds.expand();
+    let experiment = experiment
+        .into_meta()
+        .or(scenario.experiment.clone())
+        .with_detected_commit(&scenario.content_dir);
 
     println!(
-        "Loading scenario '{}': {} seeds × {} ticks",
+        "Loading scenario '{}': {} seeds × {} ticks, controller {}",
         scenario.name,
         seeds.len(),
-        scenario.ticks
+        scenario.ticks,
+        scenario.controller
     );
 
     // Load content and apply overrides.
-    let mut content = sim_world::load_content(&scenario.content_dir)?;
+    let mut content = scenario.load_content()?;
     overrides::apply_overrides(&mut content, &scenario.overrides)?;
     // Re-derive tick values after overrides may have changed game-time fields.
     content.constants.derive_tick_values();
@@ -72,11 +163,9 @@ fn run(scenario_path: &str, output_dir: &str) -> Result<()> {
 
     // Load base state file if specified.
     let base_state = if let Some(ref state_path) = scenario.state {
-        let json = std::fs::read_to_string(state_path)
-            .with_context(|| format!("reading state file: {state_path}"))?;
-        let mut loaded: sim_core::GameState = serde_json::from_str(&json)
-            .with_context(|| format!("parsing state file: {state_path}"))?;
+        let mut loaded = crate::scenario::read_state_file(state_path)?;
         loaded.body_cache = sim_core::build_body_cache(&content.solar_system.bodies);
+        sim_world::clear_disabled_subsystems(&mut loaded, &content);
         println!("Using state file: {state_path}");
         Some(loaded)
     } else {
@@ -87,9 +176,13 @@ fn run(scenario_path: &str, output_dir: &str) -> Result<()> {
     let scenario_params = serde_json::json!({
         "ticks": scenario.ticks,
         "metrics_every": scenario.metrics_every,
+        "hash_every": scenario.hash_every,
         "content_dir": scenario.content_dir,
+        "content_packs": scenario.content_packs,
         "state": scenario.state,
         "overrides": scenario.overrides,
+        "controller": scenario.controller,
+        "metrics_format": scenario.metrics_format,
     });
 
     // Create timestamped output directory.
@@ -114,10 +207,13 @@ fn run(scenario_path: &str, output_dir: &str) -> Result<()> {
                 seed,
                 scenario.ticks,
                 scenario.metrics_every,
+                scenario.hash_every,
                 &seed_dir,
                 &scenario.name,
                 &scenario_params,
                 base_state.as_ref(),
+                scenario.controller,
+                scenario.metrics_format,
             )
         })
         .collect();
@@ -166,6 +262,7 @@ fn run(scenario_path: &str, output_dir: &str) -> Result<()> {
     let snapshot_only_refs: Vec<&sim_core::MetricsSnapshot> =
         seed_results.iter().map(|r| &r.final_snapshot).collect();
     let aggregated_metrics = summary::build_aggregated_metrics(&snapshot_only_refs);
+    let per_element_material_kg = summary::build_element_material_metrics(&snapshot_only_refs);
 
     let batch_summary = serde_json::json!({
         "batch_schema_version": 1,
@@ -176,6 +273,8 @@ fn run(scenario_path: &str, output_dir: &str) -> Result<()> {
         "run_ids": run_ids,
         "collapsed_count": collapsed_count,
         "aggregated_metrics": aggregated_metrics,
+        "per_element_material_kg": per_element_material_kg,
+        "experiment": experiment,
     });
 
     let batch_path = run_dir.join("batch_summary.json");
@@ -190,8 +289,17 @@ fn run(scenario_path: &str, output_dir: &str) -> Result<()> {
     batch_file.sync_all()?;
     std::fs::rename(&batch_tmp, &batch_path).context("renaming batch summary")?;
 
+    let report_path = html_report::write_html_report(
+        &run_dir,
+        &scenario.name,
+        scenario.ticks,
+        &seed_results,
+        &batch_summary,
+    )?;
+
     println!("Summary written to {}", summary_path.display());
     println!("Batch summary written to {}", batch_path.display());
+    println!("HTML report written to {}", report_path.display());
     Ok(())
 }
 
@@ -201,13 +309,43 @@ fn main() -> Result<()> {
         Commands::Run {
             scenario,
             output_dir,
-        } => run(&scenario, &output_dir)?,
+            controller,
+            metrics_format,
+            experiment,
+        } => run(
+            &scenario,
+            &output_dir,
+            controller,
+            metrics_format,
+            experiment,
+        )?,
         Commands::Compare {
             scenario,
             config_a,
             config_b,
+            baseline,
+            tolerances,
+            output_dir,
+        } => match (baseline, config_a, config_b) {
+            (Some(baseline), _, _) => {
+                regression::run_regression_gate(&scenario, &baseline, &tolerances, &output_dir)?;
+            }
+            (None, Some(config_a), Some(config_b)) => {
+                compare::run_compare(&scenario, &config_a, &config_b, &output_dir)?;
+            }
+            (None, _, _) => unreachable!("clap requires both configs without --baseline"),
+        },
+        Commands::Sweep {
+            scenario,
+            params,
             output_dir,
-        } => compare::run_compare(&scenario, &config_a, &config_b, &output_dir)?,
+        } => sweep::run_sweep(&scenario, &params, &output_dir)?,
+        Commands::Diff {
+            batch_a,
+            batch_b,
+            all,
+            json,
+        } => diff::run_diff(&batch_a, &batch_b, all, json)?,
     }
     Ok(())
 }
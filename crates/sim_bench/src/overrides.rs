@@ -6,13 +6,25 @@ pub fn apply_overrides(
     content: &mut GameContent,
     overrides: &HashMap<String, serde_json::Value>,
 ) -> Result<()> {
-    // Split overrides into constant, module, autopilot, strategy, and scoring groups.
+    // Split overrides into constant, module, autopilot, strategy, scoring and
+    // subsystem groups.
     let mut constant_overrides = Vec::new();
+    let mut subsystem_overrides = Vec::new();
     let mut autopilot_overrides = Vec::new();
     let mut strategy_overrides = Vec::new();
     let mut scoring_overrides = Vec::new();
     for (key, value) in overrides {
-        if let Some(rest) = key.strip_prefix("module.") {
+        if key == "starting_unlocked" {
+            content.starting_unlocked = serde_json::from_value(value.clone())
+                .context("starting_unlocked must be an array of tech ids")?;
+            sim_world::check_starting_unlocked(content)?;
+        } else if key == "mode" {
+            let mode: sim_core::SimMode = serde_json::from_value(value.clone())
+                .context("mode must be one of full, economy_only, research_only")?;
+            content.subsystems = sim_core::Subsystems::for_mode(mode);
+        } else if let Some(rest) = key.strip_prefix("subsystems.") {
+            subsystem_overrides.push((rest, value, key.as_str()));
+        } else if let Some(rest) = key.strip_prefix("module.") {
             apply_module_override(&mut content.module_defs, rest, key, value)?;
         } else if let Some(rest) = key.strip_prefix("autopilot.") {
             autopilot_overrides.push((rest, value));
@@ -37,6 +49,21 @@ pub fn apply_overrides(
     if !scoring_overrides.is_empty() {
         apply_scoring_overrides(&mut content.scoring, &scoring_overrides)?;
     }
+    // After `mode`, so single switches adjust the preset.
+    for (name, value, full_key) in subsystem_overrides {
+        let subsystem: sim_core::Subsystem = serde_json::from_value(serde_json::Value::String(
+            name.to_string(),
+        ))
+        .with_context(|| {
+            format!(
+                "unknown subsystem in '{full_key}': expected mining, trade, research or industry"
+            )
+        })?;
+        let enabled = value
+            .as_bool()
+            .ok_or_else(|| anyhow::anyhow!("'{full_key}': expected true or false"))?;
+        content.subsystems.set(subsystem, enabled);
+    }
     Ok(())
 }
 
@@ -288,32 +315,37 @@ fn set_nested_path(
 }
 
 /// Apply overrides to `AutopilotConfig` using the same serialize→patch→deserialize
-/// pattern as constant overrides. Keys are bare field names (without the `autopilot.` prefix).
+/// pattern as constant overrides. Keys are field names without the `autopilot.`
+/// prefix; nested fields use dotted paths (`weights.mine_distance`).
 fn apply_autopilot_overrides(
     autopilot: &mut sim_core::AutopilotConfig,
     overrides: &[(&str, &serde_json::Value)],
 ) -> Result<()> {
-    let serde_json::Value::Object(mut map) =
-        serde_json::to_value(&*autopilot).context("failed to serialize AutopilotConfig")?
-    else {
-        unreachable!("AutopilotConfig serializes to JSON object")
-    };
+    let mut root =
+        serde_json::to_value(&*autopilot).context("failed to serialize AutopilotConfig")?;
 
     for &(key, value) in overrides {
-        if !map.contains_key(key) {
-            bail!(
-                "unknown autopilot override key 'autopilot.{key}'. \
-                 Valid keys: {}",
-                map.keys()
-                    .map(String::as_str)
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            );
+        let mut target = &mut root;
+        for segment in key.split('.') {
+            let serde_json::Value::Object(map) = target else {
+                bail!("autopilot override 'autopilot.{key}': '{segment}' is not a nested field");
+            };
+            if !map.contains_key(segment) {
+                bail!(
+                    "unknown autopilot override key 'autopilot.{key}'. \
+                     Valid keys: {}",
+                    map.keys()
+                        .map(String::as_str)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+            target = map.get_mut(segment).expect("key checked above");
         }
-        map.insert(key.to_string(), value.clone());
+        *target = value.clone();
     }
 
-    *autopilot = serde_json::from_value(serde_json::Value::Object(map))
+    *autopilot = serde_json::from_value(root)
         .context("failed to deserialize AutopilotConfig after applying overrides")?;
     Ok(())
 }
@@ -463,6 +495,58 @@ mod tests {
         assert_eq!(content.constants.replenish_target_count, 15);
     }
 
+    #[test]
+    fn test_mode_override_with_subsystem_switch() {
+        use sim_core::Subsystem;
+        let mut content = test_content();
+        let overrides = HashMap::from([
+            ("mode".to_string(), serde_json::json!("research_only")),
+            ("subsystems.trade".to_string(), serde_json::json!(true)),
+        ]);
+        apply_overrides(&mut content, &overrides).unwrap();
+        assert!(!content.subsystems.enabled(Subsystem::Mining));
+        assert!(!content.subsystems.enabled(Subsystem::Industry));
+        assert!(content.subsystems.enabled(Subsystem::Trade));
+        assert!(content.subsystems.enabled(Subsystem::Research));
+
+        let unknown = HashMap::from([("subsystems.farming".to_string(), serde_json::json!(false))]);
+        assert!(apply_overrides(&mut content, &unknown).is_err());
+    }
+
+    #[test]
+    fn test_starting_unlocked_override() {
+        let mut content = test_content();
+        let tech = content
+            .techs
+            .iter()
+            .find(|t| t.prereqs.is_empty())
+            .unwrap()
+            .id
+            .clone();
+        let overrides = HashMap::from([(
+            "starting_unlocked".to_string(),
+            serde_json::json!([tech.0.clone()]),
+        )]);
+        apply_overrides(&mut content, &overrides).unwrap();
+        assert_eq!(content.starting_unlocked, vec![tech]);
+    }
+
+    #[test]
+    fn test_starting_unlocked_override_requires_prereqs() {
+        let mut content = test_content();
+        let tech = content
+            .techs
+            .iter()
+            .find(|t| !t.prereqs.is_empty())
+            .unwrap()
+            .id
+            .clone();
+        let overrides =
+            HashMap::from([("starting_unlocked".to_string(), serde_json::json!([tech.0]))]);
+        let err = apply_overrides(&mut content, &overrides).unwrap_err();
+        assert!(err.to_string().contains("requires"), "{err}");
+    }
+
     #[test]
     fn test_unknown_key_errors() {
         let mut content = test_content();
@@ -829,6 +913,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_autopilot_weight_overrides() {
+        let mut content = test_content();
+        let overrides = HashMap::from([(
+            "autopilot.weights.mine_distance".to_string(),
+            serde_json::json!(0.4),
+        )]);
+        apply_overrides(&mut content, &overrides).unwrap();
+        assert!((content.autopilot.weights.mine_distance - 0.4).abs() < f64::EPSILON);
+        assert!((content.autopilot.weights.mine_value - 1.0).abs() < f64::EPSILON);
+
+        let bad = HashMap::from([(
+            "autopilot.weights.bogus".to_string(),
+            serde_json::json!(1.0),
+        )]);
+        let err = apply_overrides(&mut content, &bad).unwrap_err().to_string();
+        assert!(err.contains("mine_distance"), "{err}");
+    }
+
     #[test]
     fn test_autopilot_override_unknown_key_errors() {
         let mut content = test_content();
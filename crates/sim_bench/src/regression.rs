@@ -0,0 +1,367 @@
+use crate::compare::{run_arm, ArmConfig};
+use crate::overrides;
+use crate::run_result;
+use crate::scenario;
+use crate::summary;
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+// ---------------------------------------------------------------------------
+// Gates
+// ---------------------------------------------------------------------------
+
+/// Collapsed seeds / seeds. Lower is better and its tolerance is absolute.
+const COLLAPSE_RATE: &str = "collapse_rate";
+
+/// Metrics checked when no `--tolerance` names them, with their default
+/// tolerances.
+const DEFAULT_GATES: [(&str, f64); 3] = [
+    ("per_element_material_kg.Fe", 0.05),
+    ("techs_unlocked", 0.0),
+    (COLLAPSE_RATE, 0.0),
+];
+
+/// Parse `METRIC=TOLERANCE`.
+pub fn parse_tolerance(spec: &str) -> Result<(String, f64), String> {
+    let (metric, tolerance) = spec
+        .split_once('=')
+        .ok_or_else(|| format!("expected METRIC=TOLERANCE, got '{spec}'"))?;
+    let tolerance: f64 = tolerance
+        .parse()
+        .map_err(|_| format!("tolerance for '{metric}' must be a number, got '{tolerance}'"))?;
+    if metric.is_empty() || tolerance.is_nan() || tolerance < 0.0 {
+        return Err(format!(
+            "expected METRIC=TOLERANCE with TOLERANCE >= 0, got '{spec}'"
+        ));
+    }
+    Ok((metric.to_string(), tolerance))
+}
+
+/// Default gates with `tolerances` replacing or adding to them.
+fn gates(tolerances: &[(String, f64)]) -> BTreeMap<String, f64> {
+    let mut gates: BTreeMap<String, f64> = DEFAULT_GATES
+        .iter()
+        .map(|(metric, tolerance)| ((*metric).to_string(), *tolerance))
+        .collect();
+    gates.extend(tolerances.iter().cloned());
+    gates
+}
+
+/// Mean of `metric` in a batch summary: `collapse_rate`, an
+/// `aggregated_metrics` name, or `per_element_material_kg.<element>`.
+fn metric_value(batch: &serde_json::Value, metric: &str) -> Option<f64> {
+    if metric == COLLAPSE_RATE {
+        let seeds = batch["seed_count"].as_f64().filter(|n| *n > 0.0)?;
+        return Some(batch["collapsed_count"].as_f64()? / seeds);
+    }
+    let stats = match metric.strip_prefix("per_element_material_kg.") {
+        Some(element) => &batch["per_element_material_kg"][element],
+        None => &batch["aggregated_metrics"][metric],
+    };
+    stats["mean"].as_f64()
+}
+
+#[derive(Debug, Serialize)]
+pub struct GateResult {
+    pub metric: String,
+    /// `None` when the baseline does not record the metric; the gate is
+    /// skipped.
+    pub baseline: Option<f64>,
+    pub current: f64,
+    pub tolerance: f64,
+    pub regressed: bool,
+}
+
+/// A higher-is-better metric regresses when its mean drops by more than
+/// `tolerance` × the baseline; `collapse_rate` when it rises by more than
+/// `tolerance`.
+fn check(metric: &str, baseline: f64, current: f64, tolerance: f64) -> bool {
+    if metric == COLLAPSE_RATE {
+        current > baseline + tolerance
+    } else {
+        current < baseline - baseline.abs() * tolerance
+    }
+}
+
+fn evaluate(
+    baseline: &serde_json::Value,
+    current: &serde_json::Value,
+    gates: &BTreeMap<String, f64>,
+) -> Result<Vec<GateResult>> {
+    gates
+        .iter()
+        .map(|(metric, &tolerance)| {
+            let Some(current_value) = metric_value(current, metric) else {
+                bail!("unknown gate metric '{metric}'");
+            };
+            let baseline_value = metric_value(baseline, metric);
+            Ok(GateResult {
+                metric: metric.clone(),
+                baseline: baseline_value,
+                current: current_value,
+                tolerance,
+                regressed: baseline_value
+                    .is_some_and(|base| check(metric, base, current_value, tolerance)),
+            })
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Report
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Serialize)]
+pub struct RegressionReport {
+    pub scenario_name: String,
+    pub baseline_path: String,
+    pub ticks: u64,
+    pub passed: bool,
+    pub gates: Vec<GateResult>,
+    /// This run in the `batch_summary.json` shape, usable as the next
+    /// baseline.
+    pub current: serde_json::Value,
+}
+
+fn print_gate_summary(report: &RegressionReport) {
+    println!(
+        "\n=== Regression gate: {} ({} ticks) vs {} ===",
+        report.scenario_name, report.ticks, report.baseline_path
+    );
+    println!(
+        "{:<32} {:>14} {:>14} {:>10}  Result",
+        "Metric", "Baseline", "Current", "Tolerance"
+    );
+    println!("{}", "-".repeat(82));
+    for gate in &report.gates {
+        let (baseline, result) = match gate.baseline {
+            Some(value) if gate.regressed => (format!("{value:.4}"), "REGRESSED"),
+            Some(value) => (format!("{value:.4}"), "ok"),
+            None => ("-".to_string(), "skipped (not in baseline)"),
+        };
+        println!(
+            "{:<32} {:>14} {:>14.4} {:>10.4}  {}",
+            gate.metric, baseline, gate.current, gate.tolerance, result
+        );
+    }
+}
+
+fn write_report_atomic(report: &RegressionReport, path: &Path) -> Result<()> {
+    let tmp_path = path.with_extension("json.tmp");
+    let json = serde_json::to_string_pretty(report).context("serializing regression report")?;
+    let mut file = std::fs::File::create(&tmp_path)
+        .with_context(|| format!("creating {}", tmp_path.display()))?;
+    file.write_all(json.as_bytes())
+        .context("writing regression report")?;
+    file.sync_all()?;
+    std::fs::rename(&tmp_path, path).context("renaming regression report")
+}
+
+// ---------------------------------------------------------------------------
+// Orchestration
+// ---------------------------------------------------------------------------
+
+/// Rerun the scenario and compare its aggregated metrics with a recorded
+/// `batch_summary.json`. Errors (so the process exits non-zero) when any
+/// gate regresses.
+pub fn run_regression_gate(
+    scenario_path: &str,
+    baseline_path: &str,
+    tolerances: &[(String, f64)],
+    output_dir: &str,
+) -> Result<()> {
+    let scenario = scenario::load_scenario(Path::new(scenario_path))?;
+    let seeds = scenario.seeds.expand();
+    let baseline: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(baseline_path)
+            .with_context(|| format!("reading baseline: {baseline_path}"))?,
+    )
+    .with_context(|| format!("parsing baseline: {baseline_path}"))?;
+    if baseline["seed_count"].as_u64() != Some(seeds.len() as u64) {
+        eprintln!(
+            "warning: baseline has {} seeds, scenario runs {}",
+            baseline["seed_count"],
+            seeds.len()
+        );
+    }
+
+    println!(
+        "Regression gate: '{}' | {} seeds x {} ticks | baseline {}",
+        scenario.name,
+        seeds.len(),
+        scenario.ticks,
+        baseline_path
+    );
+
+    let mut content = scenario.load_content()?;
+    overrides::apply_overrides(&mut content, &scenario.overrides)?;
+    content.constants.derive_tick_values();
+    sim_core::derive_module_tick_values(&mut content.module_defs, &content.constants);
+
+    let base_state = if let Some(ref state_path) = scenario.state {
+        let mut loaded = crate::scenario::read_state_file(state_path)?;
+        loaded.body_cache = sim_core::build_body_cache(&content.solar_system.bodies);
+        sim_world::clear_disabled_subsystems(&mut loaded, &content);
+        Some(loaded)
+    } else {
+        None
+    };
+
+    let scenario_params = serde_json::json!({
+        "ticks": scenario.ticks,
+        "metrics_every": scenario.metrics_every,
+        "overrides": scenario.overrides,
+        "controller": scenario.controller,
+    });
+
+    let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
+    let run_dir = PathBuf::from(output_dir).join(format!("gate_{}_{}", scenario.name, timestamp));
+    std::fs::create_dir_all(&run_dir)
+        .with_context(|| format!("creating output directory: {}", run_dir.display()))?;
+
+    let results = run_arm(
+        &ArmConfig {
+            label: "current",
+            content: &content,
+            seeds: &seeds,
+            scenario_name: &scenario.name,
+            scenario_params: &scenario_params,
+            base_state: base_state.as_ref(),
+            controller: scenario.controller,
+            metrics_format: scenario.metrics_format,
+            ticks: scenario.ticks,
+            metrics_every: scenario.metrics_every,
+            hash_every: scenario.hash_every,
+        },
+        &run_dir,
+    )?;
+
+    let snapshots: Vec<&sim_core::MetricsSnapshot> =
+        results.iter().map(|r| &r.final_snapshot).collect();
+    let collapsed_count = results
+        .iter()
+        .filter(|r| run_result::detect_collapse(&r.final_snapshot).0)
+        .count();
+    let current = serde_json::json!({
+        "batch_schema_version": 1,
+        "scenario_name": scenario.name,
+        "scenario_params": scenario_params,
+        "seed_count": results.len(),
+        "collapsed_count": collapsed_count,
+        "aggregated_metrics": summary::build_aggregated_metrics(&snapshots),
+        "per_element_material_kg": summary::build_element_material_metrics(&snapshots),
+    });
+
+    let gate_results = evaluate(&baseline, &current, &gates(tolerances))?;
+    let regressed: Vec<String> = gate_results
+        .iter()
+        .filter(|gate| gate.regressed)
+        .map(|gate| gate.metric.clone())
+        .collect();
+    let report = RegressionReport {
+        scenario_name: scenario.name.clone(),
+        baseline_path: baseline_path.to_string(),
+        ticks: scenario.ticks,
+        passed: regressed.is_empty(),
+        gates: gate_results,
+        current,
+    };
+    print_gate_summary(&report);
+
+    let report_path = run_dir.join("regression_report.json");
+    write_report_atomic(&report, &report_path)?;
+    println!("\nReport: {}", report_path.display());
+    if !report.passed {
+        bail!("regression gate failed: {}", regressed.join(", "));
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch(fe: f64, techs: f64, collapsed: u64) -> serde_json::Value {
+        serde_json::json!({
+            "seed_count": 4,
+            "collapsed_count": collapsed,
+            "aggregated_metrics": { "techs_unlocked": { "mean": techs } },
+            "per_element_material_kg": { "Fe": { "mean": fe } },
+        })
+    }
+
+    fn regressed(results: &[GateResult]) -> Vec<&str> {
+        results
+            .iter()
+            .filter(|gate| gate.regressed)
+            .map(|gate| gate.metric.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn drops_within_tolerance_pass() {
+        let baseline = batch(10_000.0, 5.0, 1);
+        let current = batch(9_600.0, 5.0, 1);
+        let results = evaluate(&baseline, &current, &gates(&[])).unwrap();
+        assert!(regressed(&results).is_empty());
+    }
+
+    #[test]
+    fn regressions_beyond_tolerance_fail() {
+        let baseline = batch(10_000.0, 5.0, 0);
+        let current = batch(9_000.0, 4.5, 1);
+        let results = evaluate(&baseline, &current, &gates(&[])).unwrap();
+        assert_eq!(
+            regressed(&results),
+            [
+                "collapse_rate",
+                "per_element_material_kg.Fe",
+                "techs_unlocked"
+            ]
+        );
+
+        let loose = gates(&[
+            ("per_element_material_kg.Fe".to_string(), 0.2),
+            ("techs_unlocked".to_string(), 0.2),
+            ("collapse_rate".to_string(), 0.25),
+        ]);
+        let results = evaluate(&baseline, &current, &loose).unwrap();
+        assert!(regressed(&results).is_empty());
+    }
+
+    #[test]
+    fn metrics_missing_from_the_baseline_are_skipped() {
+        let mut baseline = batch(10_000.0, 5.0, 0);
+        baseline
+            .as_object_mut()
+            .unwrap()
+            .remove("per_element_material_kg");
+        let current = batch(1.0, 5.0, 0);
+        let results = evaluate(&baseline, &current, &gates(&[])).unwrap();
+        assert!(regressed(&results).is_empty());
+        assert!(results
+            .iter()
+            .any(|gate| gate.metric == "per_element_material_kg.Fe" && gate.baseline.is_none()));
+
+        let unknown = gates(&[("bogus_metric".to_string(), 0.1)]);
+        assert!(evaluate(&baseline, &current, &unknown).is_err());
+    }
+
+    #[test]
+    fn parse_tolerance_rejects_bad_specs() {
+        assert_eq!(
+            parse_tolerance("techs_unlocked=0.1").unwrap(),
+            ("techs_unlocked".to_string(), 0.1)
+        );
+        assert!(parse_tolerance("techs_unlocked").is_err());
+        assert!(parse_tolerance("techs_unlocked=-1").is_err());
+        assert!(parse_tolerance("techs_unlocked=abc").is_err());
+    }
+}
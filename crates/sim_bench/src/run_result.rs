@@ -4,6 +4,8 @@ use std::collections::HashMap;
 use std::io::Write;
 use std::path::Path;
 
+pub use sim_world::report::detect_collapse;
+
 #[derive(Debug, Serialize)]
 pub struct RunResult {
     pub run_schema_version: u32,
@@ -60,6 +62,9 @@ pub struct SummaryMetrics {
     pub thruster_count: u32,
     pub export_revenue_total: f64,
     pub export_count: u32,
+    pub loan_outstanding: f64,
+    pub loan_missed_payments: u32,
+    pub loan_defaulted: u32,
     pub power_generated_kw: f64,
     pub power_consumed_kw: f64,
     pub power_deficit_kw: f64,
@@ -98,6 +103,9 @@ impl SummaryMetrics {
             thruster_count: snapshot.thruster_count,
             export_revenue_total: snapshot.export_revenue_total,
             export_count: snapshot.export_count,
+            loan_outstanding: snapshot.loan_outstanding,
+            loan_missed_payments: snapshot.loan_missed_payments,
+            loan_defaulted: snapshot.loan_defaulted,
             power_generated_kw: f64::from(snapshot.power_generated_kw),
             power_consumed_kw: f64::from(snapshot.power_consumed_kw),
             power_deficit_kw: f64::from(snapshot.power_deficit_kw),
@@ -146,20 +154,6 @@ impl RunResult {
     }
 }
 
-/// Detect collapse: processor starved > 0 AND `fleet_idle` == `fleet_total`.
-pub fn detect_collapse(snapshot: &MetricsSnapshot) -> (bool, Option<String>) {
-    let processor_starved = snapshot
-        .per_module_metrics
-        .get("processor")
-        .map_or(0, |m| m.starved);
-    let collapsed = processor_starved > 0 && snapshot.fleet_idle == snapshot.fleet_total;
-    if collapsed {
-        (true, Some("refinery_starved + fleet_idle".to_string()))
-    } else {
-        (false, None)
-    }
-}
-
 pub fn git_sha() -> String {
     env!("GIT_SHA").to_string()
 }
@@ -229,6 +223,9 @@ mod tests {
             thruster_count: 0,
             export_revenue_total: 0.0,
             export_count: 0,
+            loan_outstanding: 0.0,
+            loan_missed_payments: 0,
+            loan_defaulted: 0,
             power_generated_kw: 0.0,
             power_consumed_kw: 0.0,
             power_deficit_kw: 0.0,
@@ -244,6 +241,7 @@ mod tests {
             transfer_count: 0,
             milestones_completed: 0,
             game_phase: 0,
+            power_brownout_kw: 0.0,
         }
     }
 
@@ -365,6 +363,15 @@ mod tests {
         assert!(reason.is_some());
     }
 
+    #[test]
+    fn test_collapse_detection_loan_default() {
+        let mut snapshot = sample_snapshot();
+        snapshot.loan_defaulted = 1;
+        let (collapsed, reason) = detect_collapse(&snapshot);
+        assert!(collapsed);
+        assert_eq!(reason.as_deref(), Some("loan_default"));
+    }
+
     #[test]
     fn test_git_sha_not_empty() {
         // Build-time env vars should be set
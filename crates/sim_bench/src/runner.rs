@@ -1,12 +1,11 @@
-use crate::parquet_writer::ParquetMetricsWriter;
 use crate::run_result::{self, RunResult, SummaryMetrics};
 use anyhow::{Context, Result};
 use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
-use sim_control::{AutopilotController, CommandSource};
+use sim_control::{AutopilotController, CommandSource, ControllerProfile};
 use sim_core::{GameContent, GameState, MetricsSnapshot, TickTimings};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 use uuid::Uuid;
 
@@ -25,10 +24,13 @@ pub fn run_seed(
     seed: u64,
     ticks: u64,
     metrics_every: u64,
+    hash_every: Option<u64>,
     seed_dir: &Path,
     scenario_name: &str,
     scenario_params: &serde_json::Value,
     base_state: Option<&GameState>,
+    controller: ControllerProfile,
+    metrics_format: sim_world::MetricsFormat,
 ) -> Result<SeedResult> {
     let run_id = Uuid::new_v4().to_string();
     let start = Instant::now();
@@ -39,14 +41,23 @@ pub fn run_seed(
         cloned.meta.seed = seed;
         cloned
     } else {
-        sim_world::build_initial_state(content, seed, &mut rng)
+        sim_world::build_initial_state(content, seed, &mut sim_world::worldgen_rng(seed))
     };
-    let mut autopilot = AutopilotController::new();
+    let mut autopilot = AutopilotController::with_profile(controller);
     autopilot.enable_decision_logging();
     let mut next_command_id = 0u64;
 
-    let (mut metrics_writer, mut parquet_writer, mut decisions_writer) =
-        create_seed_writers(seed_dir, seed, ticks, content, metrics_every)?;
+    let (mut metrics_writer, mut decisions_writer) = create_seed_writers(
+        seed_dir,
+        seed,
+        ticks,
+        content,
+        metrics_every,
+        metrics_format,
+    )?;
+    let mut hash_writer = hash_every
+        .map(|every| sim_world::StateHashWriter::create(seed_dir, every))
+        .transpose()?;
 
     #[allow(clippy::cast_possible_truncation)]
     let mut all_timings: Vec<TickTimings> = Vec::with_capacity(ticks as usize);
@@ -61,14 +72,13 @@ pub fn run_seed(
         let mut timings = TickTimings::default();
         sim_core::tick(&mut state, &commands, content, &mut rng, Some(&mut timings));
         all_timings.push(timings);
+        if let Some(ref mut writer) = hash_writer {
+            writer.observe(&state)?;
+        }
 
         if state.meta.tick % metrics_every == 0 {
             let snapshot = sim_core::compute_metrics(&state, content);
-            let score = sim_core::compute_run_score(&snapshot, &state, content);
-            parquet_writer
-                .write_row(&snapshot, &score)
-                .context("Parquet row")?;
-            metrics_writer.write_row(&snapshot).context("CSV row")?;
+            metrics_writer.write_row(&snapshot, &state, content)?;
         }
     }
 
@@ -76,16 +86,13 @@ pub fn run_seed(
     let final_snapshot = sim_core::compute_metrics(&state, content);
     let final_score = sim_core::compute_run_score(&final_snapshot, &state, content);
     if state.meta.tick % metrics_every != 0 {
-        metrics_writer
-            .write_row(&final_snapshot)
-            .context("final CSV row")?;
-        parquet_writer
-            .write_row(&final_snapshot, &final_score)
-            .context("final Parquet row")?;
+        metrics_writer.write_row(&final_snapshot, &state, content)?;
     }
-    metrics_writer.flush().context("flushing CSV")?;
-    parquet_writer.finish().context("finishing Parquet")?;
+    metrics_writer.finish()?;
     decisions_writer.flush().context("flushing decisions")?;
+    if let Some(ref mut writer) = hash_writer {
+        writer.flush()?;
+    }
 
     #[allow(clippy::cast_possible_truncation)]
     let wall_time_ms = start.elapsed().as_millis() as u64;
@@ -120,18 +127,32 @@ pub fn run_seed(
     })
 }
 
-/// Create the CSV, Parquet, and decision writers for a seed run.
+/// A seed's `metrics_NNN.csv` files in write order.
+pub fn metrics_csv_paths(seed_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(seed_dir)
+        .with_context(|| format!("reading {}", seed_dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "csv")
+                && path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .is_some_and(|stem| stem.starts_with("metrics_"))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Create the metrics and decision writers for a seed run.
 fn create_seed_writers(
     seed_dir: &Path,
     seed: u64,
     ticks: u64,
     content: &GameContent,
     metrics_every: u64,
-) -> Result<(
-    sim_core::MetricsFileWriter,
-    ParquetMetricsWriter,
-    csv::Writer<std::fs::File>,
-)> {
+    metrics_format: sim_world::MetricsFormat,
+) -> Result<(sim_world::MetricsWriter, csv::Writer<std::fs::File>)> {
     std::fs::create_dir_all(seed_dir)
         .with_context(|| format!("creating seed directory: {}", seed_dir.display()))?;
     let decisions_writer =
@@ -143,22 +164,11 @@ fn create_seed_writers(
         &content.content_version,
         metrics_every,
         serde_json::json!({"runner": "sim_bench", "ticks": ticks}),
+        // Experiment metadata lives on the batch, not on each seed.
+        &sim_world::ExperimentMeta::default(),
     )?;
-    let element_ids = sim_core::content_element_ids(content);
-    let behavior_types = sim_core::content_behavior_types(content);
-    let metrics_writer = sim_core::MetricsFileWriter::new(
-        seed_dir.to_path_buf(),
-        element_ids.clone(),
-        behavior_types.clone(),
-    )
-    .with_context(|| format!("opening metrics CSV in {}", seed_dir.display()))?;
-    let parquet_writer = ParquetMetricsWriter::new(
-        &seed_dir.join("metrics.parquet"),
-        element_ids,
-        behavior_types,
-    )
-    .with_context(|| format!("opening metrics Parquet in {}", seed_dir.display()))?;
-    Ok((metrics_writer, parquet_writer, decisions_writer))
+    let metrics_writer = sim_world::MetricsWriter::new(seed_dir, metrics_format, content)?;
+    Ok((metrics_writer, decisions_writer))
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -249,10 +259,13 @@ mod tests {
             42,
             120,
             60,
+            None,
             &seed_dir,
             "test_scenario",
             &params,
             None,
+            ControllerProfile::Balanced,
+            sim_world::MetricsFormat::Both,
         )
         .unwrap();
 
@@ -308,10 +321,13 @@ mod tests {
             42,
             120,
             60,
+            Some(30),
             &dir1.path().join("seed_42"),
             "test",
             &params,
             None,
+            ControllerProfile::Balanced,
+            sim_world::MetricsFormat::Both,
         )
         .unwrap();
         let result2 = run_seed(
@@ -319,10 +335,13 @@ mod tests {
             42,
             120,
             60,
+            Some(30),
             &dir2.path().join("seed_42"),
             "test",
             &params,
             None,
+            ControllerProfile::Balanced,
+            sim_world::MetricsFormat::Both,
         )
         .unwrap();
 
@@ -335,6 +354,16 @@ mod tests {
             result1.final_snapshot.fleet_total,
             result2.final_snapshot.fleet_total
         );
+        let hashes = |dir: &TempDir| {
+            std::fs::read_to_string(
+                dir.path()
+                    .join("seed_42")
+                    .join(sim_world::STATE_HASHES_FILE),
+            )
+            .unwrap()
+        };
+        assert_eq!(hashes(&dir1).lines().count(), 5);
+        assert_eq!(hashes(&dir1), hashes(&dir2));
     }
 
     #[test]
@@ -352,10 +381,13 @@ mod tests {
             99,
             120,
             60,
+            None,
             &seed_dir,
             "state_test",
             &params,
             Some(&base_state),
+            ControllerProfile::Balanced,
+            sim_world::MetricsFormat::Both,
         )
         .unwrap();
 
@@ -9,21 +9,77 @@ pub struct Scenario {
     pub ticks: u64,
     #[serde(default = "default_metrics_every")]
     pub metrics_every: u64,
+    /// Write each seed's `GameState::stable_hash` every N ticks to
+    /// `state_hashes.csv`, for comparing runs tick by tick.
+    #[serde(default)]
+    pub hash_every: Option<u64>,
     pub seeds: SeedSpec,
     #[serde(default = "default_content_dir")]
     pub content_dir: String,
+    /// Content packs layered over `content_dir` in order, for variants that
+    /// only tweak a few defs. See `sim_world::load_content_packs`.
+    #[serde(default)]
+    pub content_packs: Vec<String>,
     /// Optional path to a state JSON file. If set, this state is loaded instead
     /// of calling `build_initial_state()`.
     #[serde(default)]
     pub state: Option<String>,
     #[serde(default)]
     pub overrides: HashMap<String, serde_json::Value>,
+    /// Autopilot profile every seed runs with (`sim_bench run --controller`
+    /// overrides it).
+    #[serde(default)]
+    pub controller: sim_control::ControllerProfile,
+    /// Per-seed metrics files (`sim_bench run --metrics-format` overrides
+    /// it). `diff` and `report.html` read the CSVs.
+    #[serde(default = "default_metrics_format")]
+    pub metrics_format: sim_world::MetricsFormat,
+    /// Experiment notebook metadata recorded in `batch_summary.json`.
+    #[serde(default)]
+    pub experiment: sim_world::ExperimentMeta,
+}
+
+impl Scenario {
+    /// `content_dir` followed by `content_packs`.
+    pub fn content_dirs(&self) -> Vec<&str> {
+        std::iter::once(self.content_dir.as_str())
+            .chain(self.content_packs.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// Load and validate the scenario's content, printing any validation
+    /// warnings.
+    pub fn load_content(&self) -> Result<sim_core::GameContent> {
+        let (content, warnings) =
+            sim_world::load_content_packs_with_warnings(&self.content_dirs())?;
+        for warning in &warnings {
+            eprintln!("warning: {warning}");
+        }
+        Ok(content)
+    }
+}
+
+/// Read and parse the scenario `state` file at `path`, printing any load
+/// warnings (e.g. a schema migration).
+pub fn read_state_file(path: &str) -> Result<sim_core::GameState> {
+    let json =
+        std::fs::read_to_string(path).with_context(|| format!("reading state file: {path}"))?;
+    let (state, warnings) = sim_world::parse_state_with_warnings(&json)
+        .with_context(|| format!("parsing state file: {path}"))?;
+    for warning in &warnings {
+        eprintln!("warning: {warning}");
+    }
+    Ok(state)
 }
 
 fn default_metrics_every() -> u64 {
     60
 }
 
+fn default_metrics_format() -> sim_world::MetricsFormat {
+    sim_world::MetricsFormat::Both
+}
+
 fn default_content_dir() -> String {
     "./content".to_string()
 }
@@ -55,6 +111,9 @@ pub fn load_scenario(path: &Path) -> Result<Scenario> {
     if scenario.ticks == 0 {
         bail!("scenario 'ticks' must be > 0");
     }
+    if scenario.hash_every == Some(0) {
+        bail!("scenario 'hash_every' must be > 0");
+    }
     let seeds = scenario.seeds.expand();
     if seeds.is_empty() {
         bail!("scenario 'seeds' must produce at least one seed");
@@ -149,6 +208,27 @@ mod tests {
         );
         let scenario = load_scenario(file.path()).unwrap();
         assert!(scenario.state.is_none());
+        assert_eq!(
+            scenario.controller,
+            sim_control::ControllerProfile::Balanced
+        );
+    }
+
+    #[test]
+    fn test_load_scenario_with_controller() {
+        let file = write_temp_scenario(
+            r#"{
+            "name": "miners",
+            "ticks": 100,
+            "seeds": [1],
+            "controller": "aggressive-miner"
+        }"#,
+        );
+        let scenario = load_scenario(file.path()).unwrap();
+        assert_eq!(
+            scenario.controller,
+            sim_control::ControllerProfile::AggressiveMiner
+        );
     }
 
     #[test]
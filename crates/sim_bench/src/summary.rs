@@ -1,5 +1,7 @@
 use serde::Serialize;
 use sim_core::{MetricsSnapshot, RunScore};
+pub use sim_world::report::build_aggregated_metrics;
+use std::collections::BTreeSet;
 
 #[derive(Debug, Serialize)]
 pub struct SummaryStats {
@@ -121,55 +123,51 @@ pub fn compute_summary(
     }
 }
 
-fn compute_metric_summary(name: &str, values: &[f64]) -> MetricSummary {
-    let count = values.len() as f64;
-    let mean = values.iter().sum::<f64>() / count;
-    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
-    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
-    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count;
-    let stddev = variance.sqrt();
-
-    MetricSummary {
-        name: name.to_string(),
-        mean,
-        min,
-        max,
-        stddev,
-    }
-}
-
-/// Build aggregated metrics in the contract format:
-/// `{ "key": { "mean": ..., "min": ..., "max": ..., "stddev": ... }, ... }`
-///
-/// Auto-generates entries for all fixed scalar fields (except `tick` and `metrics_version`)
-/// using [`MetricsSnapshot::fixed_field_values`]. New fields added to `MetricsSnapshot`
-/// automatically appear in the aggregated output.
-pub fn build_aggregated_metrics(snapshots: &[&MetricsSnapshot]) -> serde_json::Value {
-    let descriptors = MetricsSnapshot::fixed_field_descriptors();
-    // Pre-extract field values once per snapshot to avoid O(fields * snapshots) allocations.
-    let all_values: Vec<Vec<(&str, sim_core::MetricValue)>> =
-        snapshots.iter().map(|s| s.fixed_field_values()).collect();
-
+/// Per-element refined material kg (`per_element_material_kg`) aggregated
+/// like `build_aggregated_metrics`. A seed without an element counts as 0.
+pub fn build_element_material_metrics(snapshots: &[&MetricsSnapshot]) -> serde_json::Value {
+    let elements: BTreeSet<&String> = snapshots
+        .iter()
+        .flat_map(|s| s.per_element_material_kg.keys())
+        .collect();
     let mut map = serde_json::Map::new();
-    for (index, (name, _)) in descriptors.iter().enumerate() {
-        if matches!(*name, "tick" | "metrics_version") {
-            continue;
-        }
-        let values: Vec<f64> = all_values.iter().map(|fv| fv[index].1.as_f64()).collect();
-        let summary = compute_metric_summary(name, &values);
+    for element in elements {
+        let values: Vec<f64> = snapshots
+            .iter()
+            .map(|s| {
+                f64::from(
+                    s.per_element_material_kg
+                        .get(element)
+                        .copied()
+                        .unwrap_or(0.0),
+                )
+            })
+            .collect();
+        let stats = sim_world::report::summarize(&values);
         map.insert(
-            name.to_string(),
+            element.clone(),
             serde_json::json!({
-                "mean": summary.mean,
-                "min": summary.min,
-                "max": summary.max,
-                "stddev": summary.stddev,
+                "mean": stats.mean,
+                "min": stats.min,
+                "max": stats.max,
+                "stddev": stats.stddev,
             }),
         );
     }
     serde_json::Value::Object(map)
 }
 
+fn compute_metric_summary(name: &str, values: &[f64]) -> MetricSummary {
+    let stats = sim_world::report::summarize(values);
+    MetricSummary {
+        name: name.to_string(),
+        mean: stats.mean,
+        min: stats.min,
+        max: stats.max,
+        stddev: stats.stddev,
+    }
+}
+
 pub fn print_summary(scenario_name: &str, ticks: u64, stats: &SummaryStats) {
     let tick_display = if ticks >= 1000 {
         format!("{}k", ticks / 1000)
@@ -262,6 +260,9 @@ mod tests {
             thruster_count: 0,
             export_revenue_total: 0.0,
             export_count: 0,
+            loan_outstanding: 0.0,
+            loan_missed_payments: 0,
+            loan_defaulted: 0,
             power_generated_kw: 0.0,
             power_consumed_kw: 0.0,
             power_deficit_kw: 0.0,
@@ -277,6 +278,7 @@ mod tests {
             transfer_count: 0,
             milestones_completed: 0,
             game_phase: 0,
+            power_brownout_kw: 0.0,
         }
     }
 
@@ -0,0 +1,274 @@
+use crate::compare::{run_arm, ArmConfig};
+use crate::overrides;
+use crate::run_result;
+use crate::scenario;
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// One swept override key and the values it takes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepParam {
+    pub key: String,
+    pub values: Vec<serde_json::Value>,
+}
+
+/// Parse `KEY=V1,V2,...`. Each value is read as JSON, falling back to a
+/// string, so `autopilot.weights.mine_distance=0,0.5,1` sweeps numbers and
+/// `strategy.mode=Balanced,Expand` sweeps strings.
+pub fn parse_param(spec: &str) -> Result<SweepParam, String> {
+    let (key, values) = spec
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=V1,V2,..., got '{spec}'"))?;
+    if key.is_empty() || values.is_empty() {
+        return Err(format!("expected KEY=V1,V2,..., got '{spec}'"));
+    }
+    let values = values
+        .split(',')
+        .map(|raw| {
+            serde_json::from_str(raw).unwrap_or_else(|_| serde_json::Value::String(raw.to_string()))
+        })
+        .collect();
+    Ok(SweepParam {
+        key: key.to_string(),
+        values,
+    })
+}
+
+#[derive(Debug, Serialize)]
+pub struct SweepReport {
+    pub scenario_name: String,
+    pub seed_count: usize,
+    pub ticks: u64,
+    /// Best mean composite score first.
+    pub arms: Vec<ArmSummary>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArmSummary {
+    pub label: String,
+    /// The overrides this arm applied on top of the scenario's.
+    pub params: serde_json::Map<String, serde_json::Value>,
+    pub composite: sim_world::report::MetricStats,
+    pub collapsed_count: usize,
+    pub seed_count: usize,
+}
+
+// ---------------------------------------------------------------------------
+// Grid
+// ---------------------------------------------------------------------------
+
+/// Every combination of the swept values, first parameter varying slowest.
+fn grid(params: &[SweepParam]) -> Vec<Vec<(String, serde_json::Value)>> {
+    let mut combos = vec![Vec::new()];
+    for param in params {
+        combos = combos
+            .into_iter()
+            .flat_map(|combo| {
+                param.values.iter().map(move |value| {
+                    let mut next = combo.clone();
+                    next.push((param.key.clone(), value.clone()));
+                    next
+                })
+            })
+            .collect();
+    }
+    combos
+}
+
+fn arm_label(combo: &[(String, serde_json::Value)]) -> String {
+    combo
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn summarize_arm(
+    label: String,
+    combo: &[(String, serde_json::Value)],
+    results: &[crate::runner::SeedResult],
+) -> ArmSummary {
+    let composites: Vec<f64> = results.iter().map(|r| r.final_score.composite).collect();
+    let collapsed_count = results
+        .iter()
+        .filter(|r| run_result::detect_collapse(&r.final_snapshot).0)
+        .count();
+    ArmSummary {
+        label,
+        params: combo.iter().cloned().collect(),
+        composite: sim_world::report::summarize(&composites),
+        collapsed_count,
+        seed_count: results.len(),
+    }
+}
+
+fn print_sweep_summary(report: &SweepReport) {
+    println!(
+        "\n=== Sweep: {} ({} arms, {} seeds, {} ticks) ===",
+        report.scenario_name,
+        report.arms.len(),
+        report.seed_count,
+        report.ticks
+    );
+    println!(
+        "{:>10} {:>8} {:>9}  Params",
+        "Composite", "StdDev", "Collapsed"
+    );
+    println!("{}", "-".repeat(60));
+    for arm in &report.arms {
+        println!(
+            "{:>10.2} {:>8.2} {:>6}/{:<2}  {}",
+            arm.composite.mean,
+            arm.composite.stddev,
+            arm.collapsed_count,
+            arm.seed_count,
+            arm.label
+        );
+    }
+}
+
+fn write_report_atomic(report: &SweepReport, path: &Path) -> Result<()> {
+    let tmp_path = path.with_extension("json.tmp");
+    let json = serde_json::to_string_pretty(report).context("serializing sweep report")?;
+    let mut file = std::fs::File::create(&tmp_path)
+        .with_context(|| format!("creating {}", tmp_path.display()))?;
+    file.write_all(json.as_bytes())
+        .context("writing sweep report")?;
+    file.sync_all()?;
+    std::fs::rename(&tmp_path, path).context("renaming sweep report")
+}
+
+// ---------------------------------------------------------------------------
+// Orchestration
+// ---------------------------------------------------------------------------
+
+/// Run the scenario once per combination of `params` (applied on top of the
+/// scenario's overrides, so any override key can be swept) and rank the
+/// combinations by mean composite score.
+pub fn run_sweep(scenario_path: &str, params: &[SweepParam], output_dir: &str) -> Result<()> {
+    if params.is_empty() {
+        bail!("sweep needs at least one --param KEY=V1,V2,...");
+    }
+    let scenario = scenario::load_scenario(Path::new(scenario_path))?;
+    let seeds = scenario.seeds.expand();
+    let combos = grid(params);
+
+    println!(
+        "Sweep: '{}' | {} arms x {} seeds x {} ticks",
+        scenario.name,
+        combos.len(),
+        seeds.len(),
+        scenario.ticks
+    );
+
+    let loaded = scenario.load_content()?;
+    let base_state = if let Some(ref state_path) = scenario.state {
+        let mut state = crate::scenario::read_state_file(state_path)?;
+        state.body_cache = sim_core::build_body_cache(&loaded.solar_system.bodies);
+        Some(state)
+    } else {
+        None
+    };
+
+    let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
+    let run_dir = PathBuf::from(output_dir).join(format!("sweep_{}_{}", scenario.name, timestamp));
+    std::fs::create_dir_all(&run_dir)
+        .with_context(|| format!("creating output directory: {}", run_dir.display()))?;
+
+    let mut arms = Vec::new();
+    for (index, combo) in combos.iter().enumerate() {
+        let mut arm_overrides: HashMap<String, serde_json::Value> = scenario.overrides.clone();
+        arm_overrides.extend(combo.iter().cloned());
+        let mut content = loaded.clone();
+        overrides::apply_overrides(&mut content, &arm_overrides)?;
+        content.constants.derive_tick_values();
+        sim_core::derive_module_tick_values(&mut content.module_defs, &content.constants);
+        let mut arm_state = base_state.clone();
+        if let Some(ref mut state) = arm_state {
+            sim_world::clear_disabled_subsystems(state, &content);
+        }
+
+        let label = arm_label(combo);
+        let scenario_params = serde_json::json!({
+            "ticks": scenario.ticks,
+            "metrics_every": scenario.metrics_every,
+            "controller": scenario.controller,
+            "overrides": arm_overrides,
+        });
+        let config = ArmConfig {
+            label: &label,
+            content: &content,
+            seeds: &seeds,
+            scenario_name: &scenario.name,
+            scenario_params: &scenario_params,
+            base_state: arm_state.as_ref(),
+            controller: scenario.controller,
+            metrics_format: scenario.metrics_format,
+            ticks: scenario.ticks,
+            metrics_every: scenario.metrics_every,
+            hash_every: scenario.hash_every,
+        };
+        let results = run_arm(&config, &run_dir.join(format!("arm_{index:03}")))?;
+        arms.push(summarize_arm(label, combo, &results));
+    }
+    arms.sort_by(|a, b| b.composite.mean.total_cmp(&a.composite.mean));
+
+    let report = SweepReport {
+        scenario_name: scenario.name.clone(),
+        seed_count: seeds.len(),
+        ticks: scenario.ticks,
+        arms,
+    };
+    print_sweep_summary(&report);
+
+    let report_path = run_dir.join("sweep_report.json");
+    write_report_atomic(&report, &report_path)?;
+    println!("\nReport: {}", report_path.display());
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_param_reads_json_values_with_string_fallback() {
+        let param = parse_param("autopilot.weights.mine_distance=0,0.5,1").unwrap();
+        assert_eq!(param.key, "autopilot.weights.mine_distance");
+        assert_eq!(
+            param.values,
+            [
+                serde_json::json!(0),
+                serde_json::json!(0.5),
+                serde_json::json!(1)
+            ]
+        );
+        let modes = parse_param("strategy.mode=Balanced,Expand").unwrap();
+        assert_eq!(modes.values[1], serde_json::json!("Expand"));
+        assert!(parse_param("no_values").is_err());
+    }
+
+    #[test]
+    fn grid_covers_every_combination_first_param_slowest() {
+        let params = [
+            parse_param("a=1,2").unwrap(),
+            parse_param("b=3,4,5").unwrap(),
+        ];
+        let labels: Vec<String> = grid(&params).iter().map(|c| arm_label(c)).collect();
+        assert_eq!(labels.len(), 6);
+        assert_eq!(labels[0], "a=1 b=3");
+        assert_eq!(labels[1], "a=1 b=4");
+        assert_eq!(labels[5], "a=2 b=5");
+    }
+}
@@ -0,0 +1,174 @@
+//! Shareable save bundles for bug reports and community runs.
+//!
+//! A bundle wraps a `GameState` with the fingerprint of the content it was
+//! produced against, so `sim_cli import` can refuse saves that would silently
+//! behave differently on the recipient's content.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sim_core::{GameContent, GameState};
+
+/// Bumped when the bundle envelope changes shape.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// zstd frame magic, used to auto-detect compressed bundles on import.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShareBundle {
+    pub bundle_version: u32,
+    pub content_version: String,
+    pub content_hash: String,
+    pub schema_version: u32,
+    pub tick: u64,
+    /// Original run seed. `None` when exported with `--strip-rng`.
+    pub seed: Option<u64>,
+    pub state: GameState,
+}
+
+/// What to remove from the state before sharing.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StripOptions {
+    /// Zero the RNG seed so the recipient cannot replay the original run.
+    pub rng: bool,
+    /// Drop free-text and narrative history: ship display names and the
+    /// fired sim-event log.
+    pub notes: bool,
+}
+
+/// Build a bundle from a loaded save.
+pub fn build_bundle(
+    mut state: GameState,
+    content: &GameContent,
+    strip: StripOptions,
+) -> Result<ShareBundle> {
+    let seed = if strip.rng {
+        state.meta.seed = 0;
+        None
+    } else {
+        Some(state.meta.seed)
+    };
+    if strip.notes {
+        for ship in state.ships.values_mut() {
+            ship.name.clear();
+        }
+        state.events.history.clear();
+    }
+    Ok(ShareBundle {
+        bundle_version: BUNDLE_VERSION,
+        content_version: content.content_version.clone(),
+        content_hash: sim_world::content_hash(content)?,
+        schema_version: state.meta.schema_version,
+        tick: state.meta.tick,
+        seed,
+        state,
+    })
+}
+
+/// Serialize a bundle, optionally zstd-compressed.
+pub fn encode_bundle(bundle: &ShareBundle, compress: bool) -> Result<Vec<u8>> {
+    let json = serde_json::to_vec(bundle).context("serializing bundle")?;
+    if compress {
+        zstd::encode_all(json.as_slice(), 0).context("compressing bundle")
+    } else {
+        Ok(json)
+    }
+}
+
+/// Parse a bundle, decompressing it first if it is a zstd frame.
+pub fn decode_bundle(bytes: &[u8]) -> Result<ShareBundle> {
+    let json = if bytes.starts_with(&ZSTD_MAGIC) {
+        zstd::decode_all(bytes).context("decompressing bundle")?
+    } else {
+        bytes.to_vec()
+    };
+    serde_json::from_slice(&json).context("parsing bundle")
+}
+
+/// Check a bundle against the local engine and content. Returns the state
+/// with runtime caches rebuilt, ready to run.
+pub fn validate_bundle(bundle: ShareBundle, content: &GameContent) -> Result<GameState> {
+    anyhow::ensure!(
+        bundle.bundle_version == BUNDLE_VERSION,
+        "bundle version {} is not supported (expected {BUNDLE_VERSION})",
+        bundle.bundle_version,
+    );
+    let expected_schema = sim_core::CURRENT_SCHEMA_VERSION;
+    anyhow::ensure!(
+        bundle.schema_version == expected_schema,
+        "bundle has schema version {}, but the engine expects version {expected_schema}",
+        bundle.schema_version,
+    );
+    let local_hash = sim_world::content_hash(content)?;
+    anyhow::ensure!(
+        bundle.content_hash == local_hash,
+        "bundle was exported against content {} ({}), but local content is {} ({local_hash})",
+        bundle.content_version,
+        bundle.content_hash,
+        content.content_version,
+    );
+
+    restore_state(bundle.state, content).context("bundle state")
+}
+
+/// Rebuild the runtime caches a serialized state drops and validate it
+/// against `content`.
+pub fn restore_state(mut state: GameState, content: &GameContent) -> Result<GameState> {
+    state.body_cache = sim_core::build_body_cache(&content.solar_system.bodies);
+    for station in state.stations.values_mut() {
+        station.rebuild_module_index(content);
+        station.init_module_efficiency(content);
+    }
+    sim_world::validate_state(&state, content)?;
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim_core::test_fixtures::{base_content, base_state};
+
+    #[test]
+    fn compressed_bundle_roundtrips_and_validates() {
+        let content = base_content();
+        let state = base_state(&content);
+        let bundle = build_bundle(state, &content, StripOptions::default()).unwrap();
+        let bytes = encode_bundle(&bundle, true).unwrap();
+        assert!(bytes.starts_with(&ZSTD_MAGIC));
+
+        let decoded = decode_bundle(&bytes).unwrap();
+        assert_eq!(decoded.seed, bundle.seed);
+        let restored = validate_bundle(decoded, &content).unwrap();
+        assert_eq!(restored.meta.tick, bundle.tick);
+    }
+
+    #[test]
+    fn strip_options_remove_seed_and_notes() {
+        let content = base_content();
+        let mut state = base_state(&content);
+        state.meta.seed = 42;
+        for ship in state.ships.values_mut() {
+            ship.name = "Captain's Pride".to_string();
+        }
+        let strip = StripOptions {
+            rng: true,
+            notes: true,
+        };
+        let bundle = build_bundle(state, &content, strip).unwrap();
+        assert_eq!(bundle.seed, None);
+        assert_eq!(bundle.state.meta.seed, 0);
+        assert!(bundle.state.ships.values().all(|s| s.name.is_empty()));
+    }
+
+    #[test]
+    fn import_rejects_content_mismatch() {
+        let content = base_content();
+        let state = base_state(&content);
+        let bundle = build_bundle(state, &content, StripOptions::default()).unwrap();
+
+        let mut other = base_content();
+        other.constants.mining_rate_kg_per_minute += 1.0;
+        let err = validate_bundle(bundle, &other).unwrap_err();
+        assert!(err.to_string().contains("local content"), "{err}");
+    }
+}
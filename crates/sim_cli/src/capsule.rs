@@ -0,0 +1,250 @@
+//! Bug capsules: everything needed to replay a tick that panicked.
+//!
+//! `sim_cli run` records the inputs of every tick with a [`TickRecorder`].
+//! If `sim_core::tick` panics, the inputs of the failing tick (pre-tick
+//! state, command batch, RNG position) are written to `capsule_tick_<N>.json`
+//! together with the content fingerprint, and `sim_cli reproduce` runs that
+//! one tick again against the same content.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use sim_core::{CommandEnvelope, EventEnvelope, GameContent, GameState, RngCheckpoint};
+
+/// Bumped when the capsule format changes shape.
+pub const CAPSULE_VERSION: u32 = 1;
+
+/// Ticks between state checkpoints in a [`TickRecorder`].
+const CHECKPOINT_INTERVAL: usize = 100;
+
+/// The inputs of one tick.
+pub struct TickInputs {
+    state: GameState,
+    commands: Vec<CommandEnvelope>,
+    rng: RngCheckpoint,
+}
+
+impl TickInputs {
+    pub fn tick(&self) -> u64 {
+        self.state.meta.tick
+    }
+}
+
+struct RecordedTick {
+    /// Controller faults are numbered into the event counter between ticks,
+    /// so the counter is restored rather than replayed.
+    next_event_id: u64,
+    commands: Vec<CommandEnvelope>,
+}
+
+/// Rolling record of the ticks a run has started.
+///
+/// Cloning the state before every tick is too slow for long runs, so this
+/// keeps a checkpoint every `CHECKPOINT_INTERVAL` ticks plus the command
+/// batches since, and rebuilds the failing tick's pre-tick state by
+/// replaying from the checkpoint.
+pub struct TickRecorder {
+    checkpoint: GameState,
+    checkpoint_rng: RngCheckpoint,
+    since_checkpoint: Vec<RecordedTick>,
+}
+
+impl TickRecorder {
+    pub fn new(state: &GameState, rng: &ChaCha8Rng) -> Self {
+        Self {
+            checkpoint: state.clone(),
+            checkpoint_rng: RngCheckpoint::capture(rng),
+            since_checkpoint: Vec::new(),
+        }
+    }
+
+    /// Record a tick that is about to run with `commands`.
+    pub fn record(&mut self, state: &GameState, commands: &[CommandEnvelope], rng: &ChaCha8Rng) {
+        if self.since_checkpoint.len() >= CHECKPOINT_INTERVAL {
+            *self = Self::new(state, rng);
+        }
+        self.since_checkpoint.push(RecordedTick {
+            next_event_id: state.counters.next_event_id,
+            commands: commands.to_vec(),
+        });
+    }
+
+    /// The inputs of the last recorded tick.
+    pub fn last_tick(&self, content: &GameContent) -> TickInputs {
+        let mut state = self.checkpoint.clone();
+        let mut rng = self.checkpoint_rng.restore();
+        let (last, earlier) = self
+            .since_checkpoint
+            .split_last()
+            .expect("a tick is recorded before it runs");
+        for recorded in earlier {
+            state.counters.next_event_id = recorded.next_event_id;
+            sim_core::tick(&mut state, &recorded.commands, content, &mut rng, None);
+        }
+        state.counters.next_event_id = last.next_event_id;
+        TickInputs {
+            state,
+            commands: last.commands.clone(),
+            rng: RngCheckpoint::capture(&rng),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BugCapsule {
+    pub capsule_version: u32,
+    pub content_version: String,
+    pub content_hash: String,
+    pub schema_version: u32,
+    /// Tick that panicked; `state` is the state just before it.
+    pub tick: u64,
+    pub panic_message: String,
+    pub rng: RngCheckpoint,
+    pub commands: Vec<CommandEnvelope>,
+    pub state: GameState,
+}
+
+pub fn build_capsule(
+    inputs: TickInputs,
+    content: &GameContent,
+    panic_message: String,
+) -> Result<BugCapsule> {
+    Ok(BugCapsule {
+        capsule_version: CAPSULE_VERSION,
+        content_version: content.content_version.clone(),
+        content_hash: sim_world::content_hash(content)?,
+        schema_version: inputs.state.meta.schema_version,
+        tick: inputs.state.meta.tick,
+        panic_message,
+        rng: inputs.rng,
+        commands: inputs.commands,
+        state: inputs.state,
+    })
+}
+
+/// Write `capsule` into `dir` and return the file path.
+pub fn write_capsule(capsule: &BugCapsule, dir: &Path) -> Result<PathBuf> {
+    let path = dir.join(format!("capsule_tick_{}.json", capsule.tick));
+    let json = serde_json::to_vec(capsule).context("serializing bug capsule")?;
+    std::fs::write(&path, json)
+        .with_context(|| format!("writing bug capsule: {}", path.display()))?;
+    Ok(path)
+}
+
+pub fn read_capsule(path: &str) -> Result<BugCapsule> {
+    let bytes = std::fs::read(path).with_context(|| format!("reading bug capsule: {path}"))?;
+    serde_json::from_slice(&bytes).with_context(|| format!("parsing bug capsule: {path}"))
+}
+
+/// Check a capsule against the local engine and content, and return the
+/// pre-tick state and RNG ready to replay.
+pub fn prepare_replay(
+    capsule: BugCapsule,
+    content: &GameContent,
+) -> Result<(GameState, ChaCha8Rng, Vec<CommandEnvelope>)> {
+    anyhow::ensure!(
+        capsule.capsule_version == CAPSULE_VERSION,
+        "capsule version {} is not supported (expected {CAPSULE_VERSION})",
+        capsule.capsule_version,
+    );
+    let expected_schema = sim_core::CURRENT_SCHEMA_VERSION;
+    anyhow::ensure!(
+        capsule.schema_version == expected_schema,
+        "capsule has schema version {}, but the engine expects version {expected_schema}",
+        capsule.schema_version,
+    );
+    let local_hash = sim_world::content_hash(content)?;
+    anyhow::ensure!(
+        capsule.content_hash == local_hash,
+        "capsule was captured against content {} ({}), but local content is {} ({local_hash})",
+        capsule.content_version,
+        capsule.content_hash,
+        content.content_version,
+    );
+    let state = crate::bundle::restore_state(capsule.state, content).context("capsule state")?;
+    Ok((state, capsule.rng.restore(), capsule.commands))
+}
+
+/// Run one tick, turning a panic into its message. The state is left
+/// half-updated after a panic and must not be used further.
+pub fn tick_guarded(
+    state: &mut GameState,
+    commands: &[CommandEnvelope],
+    content: &GameContent,
+    rng: &mut ChaCha8Rng,
+) -> Result<Vec<EventEnvelope>, String> {
+    catch_unwind(AssertUnwindSafe(|| {
+        sim_core::tick(state, commands, content, rng, None)
+    }))
+    .map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|s| (*s).to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "non-string panic payload".to_string())
+    })
+}
+
+/// The replayed tick, unguarded so a debugger stops at the panic. Kept out
+/// of line as a stable breakpoint (`break sim_cli::capsule::replay_tick`).
+#[inline(never)]
+pub fn replay_tick(
+    state: &mut GameState,
+    commands: &[CommandEnvelope],
+    content: &GameContent,
+    rng: &mut ChaCha8Rng,
+) -> Vec<EventEnvelope> {
+    sim_core::tick(state, commands, content, rng, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use sim_core::test_fixtures::{base_content, base_state};
+
+    #[test]
+    fn replayed_capsule_matches_the_original_tick() {
+        let content = base_content();
+        let mut state = base_state(&content);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let mut recorder = TickRecorder::new(&state, &rng);
+        // Crosses a checkpoint, with a fault-style counter bump between ticks.
+        for tick in 0..=CHECKPOINT_INTERVAL + 5 {
+            if tick == CHECKPOINT_INTERVAL + 2 {
+                state.counters.next_event_id += 1;
+            }
+            recorder.record(&state, &[], &rng);
+            tick_guarded(&mut state, &[], &content, &mut rng).unwrap();
+        }
+
+        let capsule =
+            build_capsule(recorder.last_tick(&content), &content, "test".to_string()).unwrap();
+        assert_eq!(capsule.tick, state.meta.tick - 1);
+        let json = serde_json::to_vec(&capsule).unwrap();
+
+        let capsule: BugCapsule = serde_json::from_slice(&json).unwrap();
+        let (mut replayed, mut replay_rng, commands) = prepare_replay(capsule, &content).unwrap();
+        replay_tick(&mut replayed, &commands, &content, &mut replay_rng);
+        assert_eq!(replayed.stable_hash(), state.stable_hash());
+    }
+
+    #[test]
+    fn replay_rejects_content_mismatch() {
+        let content = base_content();
+        let state = base_state(&content);
+        let rng = ChaCha8Rng::seed_from_u64(3);
+        let mut recorder = TickRecorder::new(&state, &rng);
+        recorder.record(&state, &[], &rng);
+        let capsule =
+            build_capsule(recorder.last_tick(&content), &content, "test".to_string()).unwrap();
+
+        let mut other = base_content();
+        other.constants.mining_rate_kg_per_minute += 1.0;
+        let err = prepare_replay(capsule, &other).unwrap_err();
+        assert!(err.to_string().contains("local content"), "{err}");
+    }
+}
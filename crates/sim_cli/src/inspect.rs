@@ -0,0 +1,279 @@
+//! Human-readable views of a saved `GameState` for `sim_cli inspect`.
+//!
+//! Each view renders one collection as a plain-text table, in id order.
+//! `--node` keeps the entities whose position is on that body, the same
+//! `parent_body` the `node` query field matches.
+
+use std::fmt::Write;
+
+use sim_core::{inventory_mass_kg, GameState, Position};
+
+/// A table rendered after the summary.
+#[derive(Debug, Clone, Copy)]
+pub enum View {
+    Ships,
+    Stations,
+    Asteroids,
+    Research,
+}
+
+/// The summary followed by each view, separated by blank lines.
+pub fn render(state: &GameState, views: &[View], node: Option<&str>) -> String {
+    let mut sections = vec![summary(state)];
+    for view in views {
+        sections.push(match view {
+            View::Ships => ships(state, node),
+            View::Stations => stations(state, node),
+            View::Asteroids => asteroids(state, node),
+            View::Research => research(state),
+        });
+    }
+    sections.join("\n")
+}
+
+fn summary(state: &GameState) -> String {
+    let mut table = Table::new(&["", ""]);
+    let rows = [
+        ("tick", state.meta.tick.to_string()),
+        ("seed", state.meta.seed.to_string()),
+        ("schema_version", state.meta.schema_version.to_string()),
+        ("balance", format!("{:.0}", state.balance)),
+        ("ships", state.ships.len().to_string()),
+        ("stations", state.stations.len().to_string()),
+        ("asteroids", state.asteroids.len().to_string()),
+        ("scan_sites", state.scan_sites.len().to_string()),
+        ("techs_unlocked", state.research.unlocked.len().to_string()),
+    ];
+    for (name, value) in rows {
+        table.row(vec![name.to_string(), value]);
+    }
+    table.render_body()
+}
+
+/// An empty inventory sums to -0.0; print it as 0.0.
+fn kg(mass: f32) -> String {
+    format!("{:.1}", mass + 0.0)
+}
+
+fn on_node(position: &Position, node: Option<&str>) -> bool {
+    node.is_none_or(|node| position.parent_body.0 == node)
+}
+
+fn ships(state: &GameState, node: Option<&str>) -> String {
+    let mut table = Table::new(&[
+        "SHIP",
+        "OWNER",
+        "HULL",
+        "NODE",
+        "TASK",
+        "ETA",
+        "CARGO_KG",
+        "PROPELLANT_KG",
+        "WEAR",
+    ]);
+    for ship in state
+        .ships
+        .values()
+        .filter(|ship| on_node(&ship.position, node))
+    {
+        let (task, eta) = ship.task.as_ref().map_or(("Idle", None), |task| {
+            (task.kind.label(), Some(task.eta_tick))
+        });
+        table.row(vec![
+            ship.id.0.clone(),
+            ship.owner.0.clone(),
+            ship.hull_id.0.clone(),
+            ship.position.parent_body.0.clone(),
+            task.to_string(),
+            eta.map_or_else(|| "-".to_string(), |tick| tick.to_string()),
+            kg(inventory_mass_kg(&ship.inventory)),
+            format!(
+                "{:.1}/{:.1}",
+                ship.propellant_kg, ship.propellant_capacity_kg
+            ),
+            format!("{:.2}", ship.wear.wear),
+        ]);
+    }
+    table.render("ships")
+}
+
+fn stations(state: &GameState, node: Option<&str>) -> String {
+    let mut table = Table::new(&[
+        "STATION", "OWNER", "NODE", "FRAME", "MODULES", "CARGO_KG", "CREW",
+    ]);
+    for station in state
+        .stations
+        .values()
+        .filter(|station| on_node(&station.position, node))
+    {
+        let modules = &station.core.modules;
+        let enabled = modules.iter().filter(|module| module.enabled).count();
+        table.row(vec![
+            station.id.0.clone(),
+            station.owner.0.clone(),
+            station.position.parent_body.0.clone(),
+            station
+                .frame_id
+                .as_ref()
+                .map_or_else(|| "-".to_string(), |frame| frame.0.clone()),
+            format!("{enabled}/{}", modules.len()),
+            kg(inventory_mass_kg(&station.core.inventory)),
+            station.core.crew.values().sum::<u32>().to_string(),
+        ]);
+    }
+    table.render("stations")
+}
+
+fn asteroids(state: &GameState, node: Option<&str>) -> String {
+    let mut table = Table::new(&["ASTEROID", "NODE", "MASS_KG", "SCANNED", "TAGS"]);
+    for asteroid in state
+        .asteroids
+        .values()
+        .filter(|asteroid| on_node(&asteroid.position, node))
+    {
+        let tags: Vec<&str> = asteroid
+            .anomaly_tags
+            .iter()
+            .map(|tag| tag.0.as_str())
+            .collect();
+        table.row(vec![
+            asteroid.id.0.clone(),
+            asteroid.position.parent_body.0.clone(),
+            format!("{:.0}", asteroid.mass_kg),
+            if asteroid.knowledge.composition.is_some() {
+                "yes"
+            } else {
+                "no"
+            }
+            .to_string(),
+            if tags.is_empty() {
+                "-".to_string()
+            } else {
+                tags.join(",")
+            },
+        ]);
+    }
+    table.render("asteroids")
+}
+
+/// Techs with evidence, unlocked or queued, then the data pool.
+fn research(state: &GameState) -> String {
+    let research = &state.research;
+    let mut techs: Vec<&sim_core::TechId> = research
+        .unlocked
+        .iter()
+        .chain(research.evidence.keys())
+        .chain(&research.queue)
+        .collect();
+    techs.sort_by(|a, b| a.0.cmp(&b.0));
+    techs.dedup();
+
+    let mut table = Table::new(&["TECH", "STATUS", "EVIDENCE"]);
+    for tech in techs {
+        let status = if research.unlocked.contains(tech) {
+            "unlocked".to_string()
+        } else if let Some(position) = research.queue.iter().position(|queued| queued == tech) {
+            format!("queued #{}", position + 1)
+        } else {
+            "-".to_string()
+        };
+        let evidence: f32 = research
+            .evidence
+            .get(tech)
+            .map_or(0.0, |progress| progress.points.values().sum());
+        table.row(vec![tech.0.clone(), status, format!("{evidence:.1}")]);
+    }
+
+    let mut pool: Vec<_> = research.data_pool.iter().collect();
+    pool.sort_by(|a, b| a.0 .0.cmp(&b.0 .0));
+    let mut data = Table::new(&["DATA", "AMOUNT"]);
+    for (kind, amount) in pool {
+        data.row(vec![kind.to_string(), format!("{amount:.1}")]);
+    }
+    format!("{}\n{}", table.render("techs"), data.render("data kinds"))
+}
+
+/// Columns padded to their widest cell.
+struct Table {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    fn new(header: &[&str]) -> Self {
+        Self {
+            header: header.iter().map(|column| (*column).to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    fn row(&mut self, cells: Vec<String>) {
+        self.rows.push(cells);
+    }
+
+    /// Header, rows and a `N <noun>` count line.
+    fn render(&self, noun: &str) -> String {
+        let mut out = self.format(std::iter::once(&self.header).chain(&self.rows));
+        let _ = writeln!(out, "{} {noun}", self.rows.len());
+        out
+    }
+
+    /// Rows only, for key/value tables.
+    fn render_body(&self) -> String {
+        self.format(self.rows.iter())
+    }
+
+    fn format<'a>(&self, lines: impl Iterator<Item = &'a Vec<String>> + Clone) -> String {
+        let mut widths = vec![0; self.header.len()];
+        for line in lines.clone() {
+            for (width, cell) in widths.iter_mut().zip(line) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let mut out = String::new();
+        for line in lines {
+            let cells: Vec<String> = line
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect();
+            out.push_str(cells.join("  ").trim_end());
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim_core::test_fixtures::{base_content, base_state};
+
+    #[test]
+    fn views_render_tables_filtered_by_node() {
+        let content = base_content();
+        let state = base_state(&content);
+        let ship = state.ships.values().next().unwrap();
+        let node = ship.position.parent_body.0.clone();
+
+        let all = render(
+            &state,
+            &[View::Ships, View::Stations, View::Asteroids, View::Research],
+            None,
+        );
+        assert!(all.contains("tick"), "{all}");
+        assert!(all.contains("SHIP"), "{all}");
+        assert!(all.contains(&ship.id.0), "{all}");
+        assert!(all.contains(&format!("{} ships\n", state.ships.len())));
+        assert!(all.contains("STATION"));
+        assert!(all.contains("ASTEROID"));
+        assert!(all.contains("TECH"));
+
+        let elsewhere = render(&state, &[View::Ships], Some("no_such_body"));
+        assert!(elsewhere.contains("0 ships\n"), "{elsewhere}");
+        assert!(!elsewhere.contains(&ship.id.0));
+
+        let here = render(&state, &[View::Ships], Some(&node));
+        assert!(here.contains(&ship.id.0), "{here}");
+    }
+}
@@ -1,8 +1,19 @@
+mod bundle;
+mod capsule;
+mod inspect;
+mod run_log;
+
+use std::path::Path;
+use std::time::Duration;
+
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
-use sim_control::{AutopilotController, CommandSource};
+use clap::{Args, Parser, Subcommand};
+use sim_control::{
+    AutopilotController, CommandSource, ControllerFault, ControllerProfile, HaulerController,
+    SandboxedController, ScriptController,
+};
 use sim_core::GameState;
-use sim_world::RunSetupBuilder;
+use sim_world::{OrphanedModulePolicy, RunSetupBuilder};
 
 // ---------------------------------------------------------------------------
 // CLI definition
@@ -27,8 +38,16 @@ enum Commands {
         /// Load initial `GameState` from a JSON file. Mutually exclusive with --seed.
         #[arg(long = "state", conflicts_with = "seed")]
         state_file: Option<String>,
+        /// How to handle saved modules whose def no longer exists in content:
+        /// error, scrap (refund to balance), or quarantine (disable).
+        #[arg(long, default_value = "quarantine")]
+        orphaned_modules: OrphanedModulePolicy,
         #[arg(long, default_value = "./content")]
         content_dir: String,
+        /// Content pack layered over --content-dir (repeatable; later packs
+        /// override earlier ones, merging defs by id).
+        #[arg(long = "content-pack")]
+        content_packs: Vec<String>,
         #[arg(long, default_value_t = 100)]
         print_every: u64,
         /// Sample metrics every N ticks (default 60).
@@ -37,37 +56,312 @@ enum Commands {
         /// Disable automatic metrics collection to runs/ directory.
         #[arg(long)]
         no_metrics: bool,
+        /// Metrics files in the run directory: csv, parquet or both.
+        #[arg(long, default_value = "csv", conflicts_with = "no_metrics")]
+        metrics_format: sim_world::MetricsFormat,
+        /// Record `GameState::stable_hash` every N ticks to `state_hashes.csv`
+        /// in the run directory (printed instead with --no-metrics).
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        hash_every: Option<u64>,
+        /// Write the initial state and every tick's commands, events and
+        /// state hash to the run directory, for `sim_cli replay`.
+        #[arg(long, conflicts_with = "no_metrics")]
+        tick_log: bool,
+        /// Autopilot profile: balanced, aggressive-miner, research-first, or
+        /// trade-focused.
+        #[arg(long, default_value = "balanced", conflicts_with = "script")]
+        controller: ControllerProfile,
+        /// Dedicate up to N ships to hauling refined material between
+        /// stations and to the export hub; the autopilot runs the rest.
+        #[arg(long, default_value_t = 0, conflicts_with = "script")]
+        haulers: usize,
+        /// Drive the run with a Rhai script (`fn decide(state, content)`)
+        /// instead of the autopilot.
+        #[arg(long)]
+        script: Option<String>,
+        /// Wall-clock limit per `decide` call, in milliseconds. Over-limit
+        /// calls are discarded and reported as controller faults.
+        #[arg(long, default_value_t = 1000)]
+        script_time_limit_ms: u64,
+        #[command(flatten)]
+        experiment: ExperimentArgs,
+    },
+    /// Package a save file as a shareable bundle (state + content hash + run info).
+    Export {
+        /// Save file (`GameState` JSON) to export.
+        save: String,
+        /// Output path. Defaults to `<save>.bundle.json` (`.bundle.json.zst` with --compress).
+        #[arg(long)]
+        out: Option<String>,
+        #[arg(long, default_value = "./content")]
+        content_dir: String,
+        /// Zero the RNG seed so the original run cannot be replayed.
+        #[arg(long)]
+        strip_rng: bool,
+        /// Drop ship display names and the sim-event history.
+        #[arg(long)]
+        strip_notes: bool,
+        /// zstd-compress the bundle.
+        #[arg(long)]
+        compress: bool,
+    },
+    /// Validate a shared bundle against local content and write its state as a save file.
+    Import {
+        /// Bundle produced by `sim_cli export` (plain or zstd-compressed).
+        bundle: String,
+        /// Output save path. Defaults to `<bundle>.state.json`.
+        #[arg(long)]
+        out: Option<String>,
+        #[arg(long, default_value = "./content")]
+        content_dir: String,
+    },
+    /// Replay the tick recorded in a bug capsule, without catching its panic.
+    Reproduce {
+        /// Capsule written by `sim_cli run` when a tick panicked.
+        capsule: String,
+        #[arg(long, default_value = "./content")]
+        content_dir: String,
+        /// Content pack the run used (repeatable, in the run's order).
+        #[arg(long = "content-pack")]
+        content_packs: Vec<String>,
+        /// Write the post-tick state here if the tick completes.
+        #[arg(long)]
+        out: Option<String>,
+    },
+    /// Re-run a recorded run from its tick log and report where it diverges.
+    Replay {
+        /// Run directory written by `sim_cli run` (holds `initial_state.json`
+        /// and `ticks.jsonl`).
+        #[arg(long)]
+        run_dir: String,
+        #[arg(long, default_value = "./content")]
+        content_dir: String,
+        /// Content pack the run used (repeatable, in the run's order).
+        #[arg(long = "content-pack")]
+        content_packs: Vec<String>,
+        /// Stop after this many divergent ticks.
+        #[arg(long, default_value_t = 10)]
+        max_divergences: usize,
+    },
+    /// Check a content directory (and packs) for errors and dead content.
+    Validate {
+        #[arg(long, default_value = "./content")]
+        content_dir: String,
+        /// Content pack layered over --content-dir (repeatable, in order).
+        #[arg(long = "content-pack")]
+        content_packs: Vec<String>,
+        /// Print the report as JSON (`{ content_version, errors, warnings }`).
+        #[arg(long)]
+        json: bool,
+        /// Fail on warnings as well as errors.
+        #[arg(long)]
+        deny_warnings: bool,
+    },
+    /// Print a save file's summary and tables of its ships, stations,
+    /// asteroids or research.
+    Inspect {
+        /// Save file (`GameState` JSON) to inspect.
+        save: String,
+        #[arg(long)]
+        ships: bool,
+        #[arg(long)]
+        stations: bool,
+        /// Unlocked, queued and in-progress techs, and the data pool.
+        #[arg(long)]
+        research: bool,
+        #[arg(long)]
+        asteroids: bool,
+        /// Only list ships, stations and asteroids on this body.
+        #[arg(long)]
+        node: Option<String>,
+    },
+    /// Filter a save file's ships, stations, asteroids or scan sites.
+    Query {
+        /// Save file (`GameState` JSON) to query.
+        save: String,
+        /// Query expression, e.g. `ships[task=Idle & cargo_kg>0]`.
+        expr: String,
+        /// Print the matching entities as JSON instead of their ids.
+        #[arg(long)]
+        json: bool,
     },
+    /// Inspect past run and benchmark batch directories.
+    Runs {
+        #[command(subcommand)]
+        action: RunsAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum RunsAction {
+    /// List runs and batches with their experiment notes.
+    List {
+        /// Directory holding run and batch directories.
+        #[arg(long, default_value = "runs")]
+        dir: String,
+    },
+}
+
+/// Experiment notebook flags, recorded in `run_info.json`.
+#[derive(Args)]
+struct ExperimentArgs {
+    /// What this run is meant to show.
+    #[arg(long)]
+    hypothesis: Option<String>,
+    /// Free-form notes.
+    #[arg(long)]
+    notes: Option<String>,
+    /// Content git commit (default: detected from --content-dir).
+    #[arg(long)]
+    content_commit: Option<String>,
+}
+
+impl ExperimentArgs {
+    fn into_meta(self, content_dir: &str) -> sim_world::ExperimentMeta {
+        sim_world::ExperimentMeta {
+            hypothesis: self.hypothesis,
+            notes: self.notes,
+            content_commit: self.content_commit,
+        }
+        .with_detected_commit(content_dir)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Content loading
+// ---------------------------------------------------------------------------
+
+fn print_warnings(warnings: &[String]) {
+    for warning in warnings {
+        eprintln!("warning: {warning}");
+    }
+}
+
+/// Parse a save file, printing any load warnings (e.g. a schema migration).
+fn load_save(data: &str) -> Result<sim_core::GameState> {
+    let (state, warnings) =
+        sim_world::parse_state_with_warnings(data).context("parsing save file")?;
+    print_warnings(&warnings);
+    Ok(state)
+}
+
+/// Load and validate content packs, printing any validation warnings.
+fn load_content(content_dirs: &[&str]) -> Result<sim_core::GameContent> {
+    let (content, warnings) = sim_world::load_content_packs_with_warnings(content_dirs)?;
+    print_warnings(&warnings);
+    Ok(content)
 }
 
 // ---------------------------------------------------------------------------
 // Run loop
 // ---------------------------------------------------------------------------
 
-#[allow(clippy::too_many_arguments, clippy::needless_pass_by_value)]
+/// Command source for `sim_cli run`. Scripts run sandboxed: a script that
+/// errors, panics or overruns its time limit issues nothing that tick and
+/// is reported as a `ControllerFault`, and the run carries on.
+enum Controller {
+    Autopilot(Box<AutopilotController>),
+    Hauler(Box<HaulerController>),
+    Script(Box<SandboxedController<ScriptController>>),
+}
+
+impl Controller {
+    /// The controller `run`'s `--controller`, `--haulers` and `--script`
+    /// flags select.
+    fn from_flags(
+        profile: ControllerProfile,
+        haulers: usize,
+        script: Option<String>,
+        script_time_limit_ms: u64,
+    ) -> Result<Self> {
+        Ok(match script {
+            Some(path) => Self::Script(Box::new(SandboxedController::new(
+                ScriptController::from_file(Path::new(&path))
+                    .with_context(|| format!("loading script {path}"))?,
+                path,
+                Duration::from_millis(script_time_limit_ms),
+            ))),
+            None if haulers > 0 => Self::Hauler(Box::new(HaulerController::new(
+                AutopilotController::with_profile(profile),
+                haulers,
+            ))),
+            None => Self::Autopilot(Box::new(AutopilotController::with_profile(profile))),
+        })
+    }
+
+    fn generate_commands(
+        &mut self,
+        state: &GameState,
+        content: &sim_core::GameContent,
+        next_command_id: &mut u64,
+    ) -> Vec<sim_core::CommandEnvelope> {
+        match self {
+            Self::Autopilot(autopilot) => {
+                autopilot.generate_commands(state, content, next_command_id)
+            }
+            Self::Hauler(hauler) => hauler.generate_commands(state, content, next_command_id),
+            Self::Script(script) => script.generate_commands(state, content, next_command_id),
+        }
+    }
+
+    /// Recorded as `controller` in the run's metrics params.
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Autopilot(autopilot) => autopilot.profile().name(),
+            Self::Hauler(hauler) => hauler.autopilot().profile().name(),
+            Self::Script(_) => "script",
+        }
+    }
+
+    fn take_faults(&mut self) -> Vec<ControllerFault> {
+        match self {
+            Self::Autopilot(_) | Self::Hauler(_) => Vec::new(),
+            Self::Script(script) => script.take_faults(),
+        }
+    }
+}
+
+#[allow(
+    clippy::too_many_arguments,
+    clippy::needless_pass_by_value,
+    clippy::too_many_lines
+)] // One tick loop feeding every optional output
 fn run(
     ticks: u64,
     seed: Option<u64>,
     state_file: Option<String>,
-    content_dir: &str,
+    orphaned_modules: OrphanedModulePolicy,
+    content_dirs: &[&str],
     print_every: u64,
     metrics_every: u64,
     no_metrics: bool,
+    metrics_format: sim_world::MetricsFormat,
+    hash_every: Option<u64>,
+    tick_log: bool,
+    experiment: sim_world::ExperimentMeta,
+    mut controller: Controller,
 ) -> Result<()> {
-    let mut builder = RunSetupBuilder::from_content_dir(content_dir)?
+    let mut builder = RunSetupBuilder::from_content_packs(content_dirs)?
         .seed(seed)
-        .state_file(state_file);
+        .state_file(state_file)
+        .orphan_policy(orphaned_modules);
     if !no_metrics {
-        builder = builder.metrics(
-            metrics_every,
-            serde_json::json!({
-                "runner": "sim_cli",
-                "ticks": ticks,
-                "print_every": print_every,
-            }),
-        );
+        builder = builder
+            .metrics(
+                metrics_every,
+                serde_json::json!({
+                    "runner": "sim_cli",
+                    "ticks": ticks,
+                    "print_every": print_every,
+                    "controller": controller.name(),
+                    "metrics_format": metrics_format,
+                }),
+            )
+            .metrics_format(metrics_format)
+            .experiment(experiment);
     }
     let setup = builder.build()?;
+    print_warnings(&setup.warnings);
     let content = setup.content;
     let mut state = setup.game_state;
     let mut rng = setup.rng;
@@ -77,8 +371,25 @@ fn run(
         println!("Run directory: {}", dir.display());
     }
 
-    let mut autopilot = AutopilotController::new();
     let mut next_command_id = 0u64;
+    // Only runs with a run directory get a report.
+    let mut reporter = setup
+        .run_dir
+        .as_ref()
+        .map(|_| sim_world::RunReporter::new(&state));
+    let mut hash_writer = match (hash_every, &setup.run_dir) {
+        (Some(every), Some(dir)) => Some(sim_world::StateHashWriter::create(dir, every)?),
+        _ => None,
+    };
+
+    // Tick inputs, written out as a bug capsule if a tick panics.
+    let mut recorder = capsule::TickRecorder::new(&state, &rng);
+    let mut tick_log_writer = setup
+        .run_dir
+        .as_deref()
+        .filter(|_| tick_log)
+        .map(|dir| run_log::TickLogWriter::create(dir, &state, &rng, &content))
+        .transpose()?;
 
     println!(
         "Starting simulation: ticks={ticks} seed={} sites={} content_version={}",
@@ -89,16 +400,42 @@ fn run(
     println!("{}", "-".repeat(80));
 
     for _ in 0..ticks {
-        let commands = autopilot.generate_commands(&state, &content, &mut next_command_id);
+        let commands = controller.generate_commands(&state, &content, &mut next_command_id);
+        let mut events: Vec<_> = controller
+            .take_faults()
+            .iter()
+            .map(|fault| fault.to_event(&mut state))
+            .collect();
 
-        let events = sim_core::tick(&mut state, &commands, &content, &mut rng, None);
+        recorder.record(&state, &commands, &rng);
+        let next_event_id = state.counters.next_event_id;
+        match capsule::tick_guarded(&mut state, &commands, &content, &mut rng) {
+            Ok(tick_events) => {
+                if let Some(ref mut log) = tick_log_writer {
+                    log.write_tick(next_event_id, &commands, &tick_events, &state)?;
+                }
+                events.extend(tick_events);
+            }
+            Err(message) => {
+                let dir = setup.run_dir.as_deref().unwrap_or(Path::new("."));
+                return Err(tick_panic_error(&recorder, &content, &message, dir));
+            }
+        }
 
-        // Print notable events regardless of print_every.
-        for event in &events {
-            if let sim_core::Event::TechUnlocked { tech_id } = &event.event {
+        if let Some(ref mut reporter) = reporter {
+            reporter.observe_events(&events);
+        }
+
+        print_notable_events(&events, state.meta.tick);
+
+        if let Some(ref mut writer) = hash_writer {
+            writer.observe(&state)?;
+        } else if let Some(every) = hash_every {
+            if state.meta.tick % every == 0 {
                 println!(
-                    "*** TECH UNLOCKED: {tech_id} at tick={:04} ***",
-                    state.meta.tick
+                    "state hash tick={:04} {:016x}",
+                    state.meta.tick,
+                    state.stable_hash()
                 );
             }
         }
@@ -110,7 +447,10 @@ fn run(
         if let Some(ref mut writer) = metrics_writer {
             if state.meta.tick % metrics_every == 0 {
                 let snapshot = sim_core::compute_metrics(&state, &content);
-                writer.write_row(&snapshot).context("writing metrics row")?;
+                writer.write_row(&snapshot, &state, &content)?;
+                if let Some(ref mut reporter) = reporter {
+                    reporter.observe_metrics(&snapshot);
+                }
             }
         }
     }
@@ -119,11 +459,279 @@ fn run(
     println!("Done. Final state at tick {}:", state.meta.tick);
     print_status(&state, &content.constants);
 
-    if let Some(ref mut writer) = metrics_writer {
-        writer.flush().context("final metrics flush")?;
+    if let Some(writer) = metrics_writer.take() {
+        writer.finish().context("final metrics flush")?;
         println!("Metrics written to runs/ directory.");
     }
+    if let Some(ref mut writer) = hash_writer {
+        writer.flush()?;
+    }
+    if let Some(ref mut log) = tick_log_writer {
+        log.flush()?;
+    }
+    if let (Some(reporter), Some(dir)) = (reporter, &setup.run_dir) {
+        reporter.finish(&state, &content).write(dir)?;
+        println!("Report written to {}.", dir.join("report.md").display());
+    }
+
+    Ok(())
+}
+
+/// Print notable events regardless of `print_every`.
+fn print_notable_events(events: &[sim_core::EventEnvelope], tick: u64) {
+    for event in events {
+        match &event.event {
+            sim_core::Event::TechUnlocked { tech_id } => {
+                println!("*** TECH UNLOCKED: {tech_id} at tick={tick:04} ***");
+            }
+            sim_core::Event::ControllerFault {
+                controller,
+                message,
+            } => eprintln!(
+                "*** CONTROLLER FAULT ({controller}) at tick={:04}: {message} ***",
+                event.tick
+            ),
+            _ => {}
+        }
+    }
+}
+
+/// Write a bug capsule for the tick that just panicked into `dir`, and
+/// return the error that ends the run.
+fn tick_panic_error(
+    recorder: &capsule::TickRecorder,
+    content: &sim_core::GameContent,
+    message: &str,
+    dir: &Path,
+) -> anyhow::Error {
+    let inputs = recorder.last_tick(content);
+    let tick = inputs.tick();
+    let written = capsule::build_capsule(inputs, content, message.to_string())
+        .and_then(|capsule| capsule::write_capsule(&capsule, dir));
+    match written {
+        Ok(path) => anyhow::anyhow!(
+            "tick {tick} panicked: {message}\nbug capsule written to {} \
+             (replay with `sim_cli reproduce {}`)",
+            path.display(),
+            path.display(),
+        ),
+        Err(err) => err.context(format!(
+            "tick {tick} panicked: {message}; writing its bug capsule failed"
+        )),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Export / import
+// ---------------------------------------------------------------------------
+
+fn export(
+    save: &str,
+    out: Option<String>,
+    content_dir: &str,
+    strip: bundle::StripOptions,
+    compress: bool,
+) -> Result<()> {
+    let content = load_content(&[content_dir])?;
+    let json = std::fs::read_to_string(save).with_context(|| format!("reading save: {save}"))?;
+    let state: GameState =
+        serde_json::from_str(&json).with_context(|| format!("parsing save: {save}"))?;
+    let bundle = bundle::build_bundle(state, &content, strip)?;
+    let bytes = bundle::encode_bundle(&bundle, compress)?;
+
+    let extension = if compress {
+        "bundle.json.zst"
+    } else {
+        "bundle.json"
+    };
+    let out = out.unwrap_or_else(|| {
+        std::path::Path::new(save)
+            .with_extension(extension)
+            .display()
+            .to_string()
+    });
+    std::fs::write(&out, bytes).with_context(|| format!("writing bundle: {out}"))?;
+    println!(
+        "Exported tick {} ({} content {}) to {out}",
+        bundle.tick, bundle.content_version, bundle.content_hash,
+    );
+    Ok(())
+}
+
+fn import(bundle_path: &str, out: Option<String>, content_dir: &str) -> Result<()> {
+    let content = load_content(&[content_dir])?;
+    let bytes =
+        std::fs::read(bundle_path).with_context(|| format!("reading bundle: {bundle_path}"))?;
+    let bundle = bundle::decode_bundle(&bytes)?;
+    let tick = bundle.tick;
+    let state = bundle::validate_bundle(bundle, &content)?;
+
+    let out = out.unwrap_or_else(|| {
+        std::path::Path::new(bundle_path)
+            .with_extension("state.json")
+            .display()
+            .to_string()
+    });
+    let json = serde_json::to_string_pretty(&state).context("serializing state")?;
+    std::fs::write(&out, json).with_context(|| format!("writing save: {out}"))?;
+    println!("Imported tick {tick} to {out} (run it with `sim_cli run --state {out}`)");
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Reproduce
+// ---------------------------------------------------------------------------
+
+fn reproduce(capsule_path: &str, content_dirs: &[&str], out: Option<String>) -> Result<()> {
+    let content = load_content(content_dirs)?;
+    let capsule = capsule::read_capsule(capsule_path)?;
+    let tick = capsule.tick;
+    println!(
+        "Replaying tick {tick} ({} command(s)); originally panicked with: {}",
+        capsule.commands.len(),
+        capsule.panic_message,
+    );
+    let (mut state, mut rng, commands) = capsule::prepare_replay(capsule, &content)?;
+    let events = capsule::replay_tick(&mut state, &commands, &content, &mut rng);
+    println!(
+        "Tick {tick} completed with {} event(s); the panic did not reproduce.",
+        events.len()
+    );
+    if let Some(out) = out {
+        let json = serde_json::to_string_pretty(&state).context("serializing state")?;
+        std::fs::write(&out, json).with_context(|| format!("writing state: {out}"))?;
+        println!("Post-tick state written to {out}");
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Replay
+// ---------------------------------------------------------------------------
+
+fn replay(run_dir: &str, content_dirs: &[&str], max_divergences: usize) -> Result<()> {
+    let content = load_content(content_dirs)?;
+    let summary = run_log::replay(Path::new(run_dir), &content, max_divergences.max(1))?;
+    for mismatch in &summary.divergences {
+        println!("DIVERGED {mismatch}");
+    }
+    if summary.divergences.is_empty() {
+        println!(
+            "Replayed {} tick(s) of {run_dir}; all match.",
+            summary.ticks
+        );
+        return Ok(());
+    }
+    let stopped = if summary.stopped_early {
+        " (stopped at --max-divergences)"
+    } else {
+        ""
+    };
+    anyhow::bail!(
+        "{} of {} replayed tick(s) diverged{stopped}",
+        summary.divergences.len(),
+        summary.ticks,
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Validate
+// ---------------------------------------------------------------------------
+
+/// Print the content's validation errors and warnings plus the lint
+/// warnings, and fail if there are errors (or any warning with
+/// `deny_warnings`).
+fn validate(content_dirs: &[&str], json: bool, deny_warnings: bool) -> Result<()> {
+    let (content, mut report) = sim_world::load_content_packs_unvalidated(content_dirs)?;
+    report.merge(sim_world::content_report(&content));
+    report
+        .warnings
+        .extend(sim_world::lint_content(&content).warnings);
+    if json {
+        let output = serde_json::json!({
+            "content_version": content.content_version,
+            "errors": report.errors,
+            "warnings": report.warnings,
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output).context("serializing report")?
+        );
+    } else {
+        println!(
+            "content {} (version {}): {report}",
+            content_dirs.join(" + "),
+            content.content_version
+        );
+    }
+    let failed = report.has_errors() || deny_warnings && !report.warnings.is_empty();
+    anyhow::ensure!(!failed, "content validation failed");
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Inspect
+// ---------------------------------------------------------------------------
+
+fn inspect(save: &str, views: &[inspect::View], node: Option<&str>) -> Result<()> {
+    let data = std::fs::read_to_string(save).with_context(|| format!("reading save: {save}"))?;
+    let state = load_save(&data)?;
+    print!("{}", inspect::render(&state, views, node));
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Query
+// ---------------------------------------------------------------------------
+
+fn query(save: &str, expr: &str, json: bool) -> Result<()> {
+    let query = sim_core::query::Query::parse(expr).context("parsing query")?;
+    let data = std::fs::read_to_string(save).with_context(|| format!("reading save: {save}"))?;
+    let state = load_save(&data)?;
+    let results = query.run(&state);
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&results).context("serializing results")?
+        );
+        return Ok(());
+    }
+    for id in results.ids() {
+        println!("{id}");
+    }
+    eprintln!("{} match(es) at tick {}", results.len(), state.meta.tick);
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Runs
+// ---------------------------------------------------------------------------
 
+fn list_runs(dir: &str) -> Result<()> {
+    let listings = sim_world::list_runs(std::path::Path::new(dir))?;
+    if listings.is_empty() {
+        println!("No runs in {dir}");
+        return Ok(());
+    }
+    for listing in listings {
+        let dir_name = listing
+            .dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let (kind, seeds) = match listing.kind {
+            sim_world::RunKind::Run => ("run", format!("seed={}", listing.seeds)),
+            sim_world::RunKind::Batch => ("batch", format!("seeds={}", listing.seeds)),
+        };
+        let commit = listing.experiment.content_commit.as_deref().unwrap_or("-");
+        println!("{dir_name}  {kind}  {seeds}  content={commit}");
+        if let Some(hypothesis) = &listing.experiment.hypothesis {
+            println!("    hypothesis: {hypothesis}");
+        }
+        if let Some(notes) = &listing.experiment.notes {
+            println!("    notes: {notes}");
+        }
+    }
     Ok(())
 }
 
@@ -171,6 +779,14 @@ fn print_status(state: &GameState, constants: &sim_core::Constants) {
 // Entry point
 // ---------------------------------------------------------------------------
 
+/// `--content-dir` followed by each `--content-pack`, in order.
+fn content_dirs<'a>(content_dir: &'a str, content_packs: &'a [String]) -> Vec<&'a str> {
+    std::iter::once(content_dir)
+        .chain(content_packs.iter().map(String::as_str))
+        .collect()
+}
+
+#[allow(clippy::too_many_lines)] // One arm per subcommand
 fn main() -> Result<()> {
     let cli = Cli::parse();
     match cli.command {
@@ -178,21 +794,113 @@ fn main() -> Result<()> {
             ticks,
             seed,
             state_file,
+            orphaned_modules,
             content_dir,
+            content_packs,
             print_every,
             metrics_every,
             no_metrics,
+            metrics_format,
+            hash_every,
+            tick_log,
+            controller,
+            haulers,
+            script,
+            script_time_limit_ms,
+            experiment,
         } => {
+            let experiment = experiment.into_meta(&content_dir);
+            let controller =
+                Controller::from_flags(controller, haulers, script, script_time_limit_ms)?;
+            let content_dirs = content_dirs(&content_dir, &content_packs);
             run(
                 ticks,
                 seed,
                 state_file,
-                &content_dir,
+                orphaned_modules,
+                &content_dirs,
                 print_every,
                 metrics_every,
                 no_metrics,
+                metrics_format,
+                hash_every,
+                tick_log,
+                experiment,
+                controller,
             )?;
         }
+        Commands::Export {
+            save,
+            out,
+            content_dir,
+            strip_rng,
+            strip_notes,
+            compress,
+        } => {
+            let strip = bundle::StripOptions {
+                rng: strip_rng,
+                notes: strip_notes,
+            };
+            export(&save, out, &content_dir, strip, compress)?;
+        }
+        Commands::Import {
+            bundle,
+            out,
+            content_dir,
+        } => {
+            import(&bundle, out, &content_dir)?;
+        }
+        Commands::Reproduce {
+            capsule,
+            content_dir,
+            content_packs,
+            out,
+        } => {
+            let content_dirs = content_dirs(&content_dir, &content_packs);
+            reproduce(&capsule, &content_dirs, out)?;
+        }
+        Commands::Replay {
+            run_dir,
+            content_dir,
+            content_packs,
+            max_divergences,
+        } => {
+            let content_dirs = content_dirs(&content_dir, &content_packs);
+            replay(&run_dir, &content_dirs, max_divergences)?;
+        }
+        Commands::Validate {
+            content_dir,
+            content_packs,
+            json,
+            deny_warnings,
+        } => validate(
+            &content_dirs(&content_dir, &content_packs),
+            json,
+            deny_warnings,
+        )?,
+        Commands::Inspect {
+            save,
+            ships,
+            stations,
+            research,
+            asteroids,
+            node,
+        } => {
+            let views: Vec<inspect::View> = [
+                (ships, inspect::View::Ships),
+                (stations, inspect::View::Stations),
+                (asteroids, inspect::View::Asteroids),
+                (research, inspect::View::Research),
+            ]
+            .into_iter()
+            .filter_map(|(wanted, view)| wanted.then_some(view))
+            .collect();
+            inspect(&save, &views, node.as_deref())?;
+        }
+        Commands::Query { save, expr, json } => query(&save, &expr, json)?,
+        Commands::Runs {
+            action: RunsAction::List { dir },
+        } => list_runs(&dir)?,
     }
     Ok(())
 }
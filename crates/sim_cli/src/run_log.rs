@@ -0,0 +1,247 @@
+//! Tick logs: everything needed to re-run a finished run tick by tick.
+//!
+//! `sim_cli run --tick-log` writes `initial_state.json` to the run directory
+//! (the state and RNG position before the first tick, with the content
+//! fingerprint) and appends one line per tick to `ticks.jsonl`: the command
+//! batch, the event counter going into the tick, the tick's events and the
+//! hash of the state after it. `sim_cli replay --run-dir` re-runs those ticks
+//! against local content and reports every tick whose events or state hash
+//! differ.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use sim_core::replay::{RecordedTick, ReplayMismatch};
+use sim_core::{CommandEnvelope, EventEnvelope, GameContent, GameState, RngCheckpoint};
+
+pub const INITIAL_STATE_FILE: &str = "initial_state.json";
+pub const TICK_LOG_FILE: &str = "ticks.jsonl";
+
+/// Bumped when either file changes shape.
+pub const TICK_LOG_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InitialState {
+    pub tick_log_version: u32,
+    pub content_version: String,
+    pub content_hash: String,
+    pub rng: RngCheckpoint,
+    pub state: GameState,
+}
+
+/// One line of `ticks.jsonl`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LoggedTick {
+    /// Controller faults are numbered into the event counter between ticks,
+    /// so the counter is restored rather than replayed.
+    pub next_event_id: u64,
+    #[serde(flatten)]
+    pub recorded: RecordedTick,
+}
+
+/// Appends each tick of a run to its tick log.
+pub struct TickLogWriter {
+    out: BufWriter<File>,
+}
+
+impl TickLogWriter {
+    /// Write `dir/initial_state.json` and start `dir/ticks.jsonl`.
+    pub fn create(
+        dir: &Path,
+        state: &GameState,
+        rng: &ChaCha8Rng,
+        content: &GameContent,
+    ) -> Result<Self> {
+        let initial = InitialState {
+            tick_log_version: TICK_LOG_VERSION,
+            content_version: content.content_version.clone(),
+            content_hash: sim_world::content_hash(content)?,
+            rng: RngCheckpoint::capture(rng),
+            state: state.clone(),
+        };
+        let path = dir.join(INITIAL_STATE_FILE);
+        let json = serde_json::to_vec(&initial).context("serializing initial state")?;
+        std::fs::write(&path, json).with_context(|| format!("writing {}", path.display()))?;
+
+        let path = dir.join(TICK_LOG_FILE);
+        let file = File::create(&path).with_context(|| format!("creating {}", path.display()))?;
+        Ok(Self {
+            out: BufWriter::new(file),
+        })
+    }
+
+    /// Log one tick. `next_event_id` is the counter just before the tick
+    /// ran and `state` is the state after it.
+    pub fn write_tick(
+        &mut self,
+        next_event_id: u64,
+        commands: &[CommandEnvelope],
+        events: &[EventEnvelope],
+        state: &GameState,
+    ) -> Result<()> {
+        let line = LoggedTick {
+            next_event_id,
+            recorded: RecordedTick {
+                tick: state.meta.tick.saturating_sub(1),
+                commands: commands.to_vec(),
+                events: events.to_vec(),
+                state_hash: sim_core::replay::state_hash(state),
+            },
+        };
+        serde_json::to_writer(&mut self.out, &line).context("writing tick log")?;
+        self.out.write_all(b"\n").context("writing tick log")
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.out.flush().context("flushing tick log")
+    }
+}
+
+/// How a replay went.
+#[derive(Debug, Default)]
+pub struct ReplaySummary {
+    pub ticks: usize,
+    /// At most one mismatch per divergent tick, oldest first.
+    pub divergences: Vec<ReplayMismatch>,
+    /// The replay stopped at the divergence limit before the log's end.
+    pub stopped_early: bool,
+}
+
+/// Read a run directory's initial state and check it against the local
+/// engine and content. Returns the pre-run state and RNG.
+pub fn load_initial(run_dir: &Path, content: &GameContent) -> Result<(GameState, ChaCha8Rng)> {
+    let path = run_dir.join(INITIAL_STATE_FILE);
+    let bytes = std::fs::read(&path).with_context(|| {
+        format!(
+            "reading {} (record one with `sim_cli run --tick-log`)",
+            path.display()
+        )
+    })?;
+    let initial: InitialState =
+        serde_json::from_slice(&bytes).with_context(|| format!("parsing {}", path.display()))?;
+    anyhow::ensure!(
+        initial.tick_log_version == TICK_LOG_VERSION,
+        "tick log version {} is not supported (expected {TICK_LOG_VERSION})",
+        initial.tick_log_version,
+    );
+    let expected_schema = sim_core::CURRENT_SCHEMA_VERSION;
+    anyhow::ensure!(
+        initial.state.meta.schema_version == expected_schema,
+        "run has schema version {}, but the engine expects version {expected_schema}",
+        initial.state.meta.schema_version,
+    );
+    let local_hash = sim_world::content_hash(content)?;
+    anyhow::ensure!(
+        initial.content_hash == local_hash,
+        "run used content {} ({}), but local content is {} ({local_hash})",
+        initial.content_version,
+        initial.content_hash,
+        content.content_version,
+    );
+    let state = crate::bundle::restore_state(initial.state, content).context("initial state")?;
+    Ok((state, initial.rng.restore()))
+}
+
+/// Re-run every logged tick of `run_dir`, carrying on from the replayed
+/// state after a divergence until `max_divergences` ticks have diverged.
+pub fn replay(
+    run_dir: &Path,
+    content: &GameContent,
+    max_divergences: usize,
+) -> Result<ReplaySummary> {
+    let (mut state, mut rng) = load_initial(run_dir, content)?;
+    let path = run_dir.join(TICK_LOG_FILE);
+    let file = File::open(&path).with_context(|| format!("reading {}", path.display()))?;
+    let mut summary = ReplaySummary::default();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        if summary.divergences.len() >= max_divergences {
+            summary.stopped_early = true;
+            break;
+        }
+        let line = line.with_context(|| format!("reading {}", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let logged: LoggedTick = serde_json::from_str(&line)
+            .with_context(|| format!("parsing {} line {}", path.display(), index + 1))?;
+        state.counters.next_event_id = logged.next_event_id;
+        let result = sim_core::replay::replay_tick(&mut state, &logged.recorded, content, &mut rng);
+        match result {
+            // The log skips or repeats a tick; nothing after it lines up.
+            Err(mismatch @ ReplayMismatch::Tick { .. }) => return Err(mismatch.into()),
+            Err(mismatch) => summary.divergences.push(mismatch),
+            Ok(()) => {}
+        }
+        summary.ticks += 1;
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use sim_core::test_fixtures::{base_content, base_state};
+
+    fn record_run(dir: &Path, ticks: u64) -> GameContent {
+        let content = base_content();
+        let mut state = base_state(&content);
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        let mut log = TickLogWriter::create(dir, &state, &rng, &content).unwrap();
+        for tick in 0..ticks {
+            // A fault-style counter bump between ticks.
+            if tick == 2 {
+                state.counters.next_event_id += 1;
+            }
+            let next_event_id = state.counters.next_event_id;
+            let events = sim_core::tick(&mut state, &[], &content, &mut rng, None);
+            log.write_tick(next_event_id, &[], &events, &state).unwrap();
+        }
+        log.flush().unwrap();
+        content
+    }
+
+    #[test]
+    fn replay_matches_a_logged_run_and_reports_divergent_ticks() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = record_run(dir.path(), 6);
+
+        let summary = replay(dir.path(), &content, 10).unwrap();
+        assert_eq!(summary.ticks, 6);
+        assert!(summary.divergences.is_empty());
+
+        // Tamper with the recorded hash of tick 3.
+        let path = dir.path().join(TICK_LOG_FILE);
+        let mut lines: Vec<String> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect();
+        let mut logged: LoggedTick = serde_json::from_str(&lines[3]).unwrap();
+        logged.recorded.state_hash ^= 1;
+        lines[3] = serde_json::to_string(&logged).unwrap();
+        std::fs::write(&path, lines.join("\n")).unwrap();
+
+        let summary = replay(dir.path(), &content, 1).unwrap();
+        assert_eq!(summary.ticks, 4);
+        assert!(summary.stopped_early);
+        assert!(matches!(
+            summary.divergences.as_slice(),
+            [ReplayMismatch::StateHash { tick: 3, .. }]
+        ));
+    }
+
+    #[test]
+    fn replay_rejects_content_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        record_run(dir.path(), 1);
+        let mut other = base_content();
+        other.constants.mining_rate_kg_per_minute += 1.0;
+        let err = replay(dir.path(), &other, 1).unwrap_err();
+        assert!(err.to_string().contains("local content"), "{err}");
+    }
+}
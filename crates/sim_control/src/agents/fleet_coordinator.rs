@@ -22,11 +22,11 @@ use crate::objectives::ShipObjective;
 
 /// A planned inter-station resource transfer.
 #[derive(Debug)]
-struct TransferPlan {
-    source: StationId,
-    target: StationId,
-    item: TradeItemSpec,
-    priority: u8,
+pub(crate) struct TransferPlan {
+    pub(crate) source: StationId,
+    pub(crate) target: StationId,
+    pub(crate) item: TradeItemSpec,
+    pub(crate) priority: u8,
 }
 
 /// Per-station inventory snapshot for a specific resource.
@@ -143,7 +143,7 @@ fn match_surplus_to_deficit(
 }
 
 /// Build transfer plans for all resource types, sorted by priority.
-fn build_transfer_plans(state: &GameState, content: &GameContent) -> Vec<TransferPlan> {
+pub(crate) fn build_transfer_plans(state: &GameState, content: &GameContent) -> Vec<TransferPlan> {
     let mut plans: Vec<TransferPlan> = Vec::new();
 
     // --- Propellant element (LH2): highest priority ---
@@ -328,6 +328,8 @@ mod tests {
                 bonuses: vec![],
                 required_tech: None,
                 tags: vec!["mining".to_string()],
+                hold_partitions: std::collections::BTreeMap::new(),
+                replacement_value: 0.0,
             },
         );
         content.hulls.insert(
@@ -343,6 +345,8 @@ mod tests {
                 bonuses: vec![],
                 required_tech: None,
                 tags: vec!["logistics".to_string()],
+                hold_partitions: std::collections::BTreeMap::new(),
+                replacement_value: 0.0,
             },
         );
         content
@@ -384,9 +388,15 @@ mod tests {
                 module_type_index: Default::default(),
                 module_id_index: Default::default(),
                 power_budget_cache: Default::default(),
+                production_windows: std::collections::BTreeMap::new(),
+                life_support_failing: false,
             },
             frame_id: None,
             leaders: vec![],
+            owner: PrincipalId("principal_autopilot".to_string()),
+            expansions: sim_core::StationExpansions::default(),
+            power_policy: sim_core::PowerPolicy::default(),
+            history: std::collections::VecDeque::new(),
         };
         state.stations.insert(station_b(), target);
 
@@ -409,6 +419,10 @@ mod tests {
                 crew: BTreeMap::new(),
                 leaders: vec![],
                 home_station: None,
+                name: String::new(),
+                class_id: None,
+                role: None,
+                wear: sim_core::WearState::default(),
             },
         );
 
@@ -623,6 +637,10 @@ mod tests {
             crew: BTreeMap::new(),
             leaders: vec![],
             home_station: None,
+            name: String::new(),
+            class_id: None,
+            role: None,
+            wear: sim_core::WearState::default(),
         };
         state.ships.insert(ship_id(), mining_ship);
 
@@ -662,6 +680,10 @@ mod tests {
                 crew: BTreeMap::new(),
                 leaders: vec![],
                 home_station: None,
+                name: String::new(),
+                class_id: None,
+                role: None,
+                wear: sim_core::WearState::default(),
             },
         );
 
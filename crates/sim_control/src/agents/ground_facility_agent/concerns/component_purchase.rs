@@ -1,6 +1,7 @@
 use sim_core::{trade, Command, CommandEnvelope, ComponentId, InventoryItem, TradeItemSpec};
 
 use crate::behaviors::make_cmd;
+use crate::scoring;
 
 use super::super::{GroundFacilityConcern, GroundFacilityContext};
 
@@ -51,12 +52,11 @@ impl GroundFacilityConcern for ComponentPurchase {
                 count: needed,
             };
 
-            let Some(cost) =
-                trade::compute_import_cost(&item_spec, &ctx.content.pricing, ctx.content)
+            let Some(cost) = trade::compute_import_cost(&item_spec, &ctx.state.market, ctx.content)
             else {
                 continue;
             };
-            if cost > ctx.state.balance * ctx.state.strategy_config.budget_cap_fraction {
+            if cost > scoring::spend_limit(ctx.state, &ctx.content.autopilot.weights, 1.0) {
                 continue;
             }
 
@@ -4,6 +4,7 @@ use sim_core::{
 };
 
 use crate::behaviors::make_cmd;
+use crate::scoring;
 
 use super::super::{GroundFacilityConcern, GroundFacilityContext};
 
@@ -147,7 +148,7 @@ fn try_launch_satellite(
     // Check budget: base launch cost + fuel cost.
     let fuel_cost = f64::from(rocket_def.fuel_kg) * ctx.content.constants.launch_fuel_cost_per_kg;
     let total_cost = rocket_def.base_launch_cost + fuel_cost;
-    if total_cost > ctx.state.balance * ctx.state.strategy_config.budget_cap_fraction {
+    if total_cost > scoring::spend_limit(ctx.state, &ctx.content.autopilot.weights, 1.0) {
         return Vec::new();
     }
 
@@ -217,11 +218,10 @@ fn try_import_satellite_component(
         count: 1,
     };
 
-    let Some(cost) = trade::compute_import_cost(&item_spec, &ctx.content.pricing, ctx.content)
-    else {
+    let Some(cost) = trade::compute_import_cost(&item_spec, &ctx.state.market, ctx.content) else {
         return Vec::new();
     };
-    if cost > ctx.state.balance * ctx.state.strategy_config.budget_cap_fraction {
+    if cost > scoring::spend_limit(ctx.state, &ctx.content.autopilot.weights, 1.0) {
         return Vec::new();
     }
 
@@ -1,6 +1,7 @@
 use sim_core::{trade, Command, CommandEnvelope, ModuleBehaviorDef, TradeItemSpec};
 
 use crate::behaviors::make_cmd;
+use crate::scoring;
 
 use super::super::{GroundFacilityConcern, GroundFacilityContext};
 
@@ -61,18 +62,14 @@ impl GroundFacilityConcern for SensorPurchase {
             let item_spec = TradeItemSpec::Module {
                 module_def_id: sensor_def_id.clone(),
             };
-            let Some(cost) =
-                trade::compute_import_cost(&item_spec, &ctx.content.pricing, ctx.content)
+            let Some(cost) = trade::compute_import_cost(&item_spec, &ctx.state.market, ctx.content)
             else {
                 continue;
             };
-            // Scale budget willingness by research priority — higher research
+            // Research priority is the purchase's urgency — higher research
             // weight = more willing to invest in sensors (VIO-609).
-            let research_scale =
-                0.5 + 0.5 * f64::from(ctx.state.strategy_config.priorities.research);
-            if cost
-                > ctx.state.balance * ctx.state.strategy_config.budget_cap_fraction * research_scale
-            {
+            let urgency = f64::from(ctx.state.strategy_config.priorities.research);
+            if cost > scoring::spend_limit(ctx.state, &ctx.content.autopilot.weights, urgency) {
                 continue;
             }
 
@@ -1,9 +1,10 @@
 use rand::SeedableRng;
 use sim_core::{
+    internals::ModuleTypeIndex,
     test_fixtures::{base_content, ModuleDefBuilder},
     Command, DataKind, Event, FacilityCore, GroundFacilityId, GroundFacilityState,
-    ModuleBehaviorDef, ModuleKindState, ModuleState, ModuleTypeIndex, SensorArrayDef,
-    SensorArrayState, TradeItemSpec, WearState,
+    ModuleBehaviorDef, ModuleKindState, ModuleState, SensorArrayDef, SensorArrayState,
+    TradeItemSpec, WearState,
 };
 
 use crate::CommandSource;
@@ -58,6 +59,8 @@ fn ground_content() -> sim_core::GameContent {
             importable: true,
             exportable: false,
             category: "module".to_string(),
+            royalty_per_unit: 0.0,
+            elasticity: 0.0,
         },
     );
     content.pricing.items.insert(
@@ -67,6 +70,8 @@ fn ground_content() -> sim_core::GameContent {
             importable: true,
             exportable: false,
             category: "module".to_string(),
+            royalty_per_unit: 0.0,
+            elasticity: 0.0,
         },
     );
 
@@ -373,6 +378,7 @@ fn autopilot_managed_ground_facility_discovers_within_500_ticks() {
             scan_site_weight: 1,
             implicit_comm_tier: None,
         }),
+        security: 1.0,
     });
 
     content.constants.replenish_target_count = 100;
@@ -436,6 +442,8 @@ fn component_purchase_buys_when_budget_allows() {
             importable: true,
             exportable: false,
             category: "component".to_string(),
+            royalty_per_unit: 0.0,
+            elasticity: 0.0,
         },
     );
     content.pricing.items.insert(
@@ -445,6 +453,8 @@ fn component_purchase_buys_when_budget_allows() {
             importable: true,
             exportable: false,
             category: "component".to_string(),
+            royalty_per_unit: 0.0,
+            elasticity: 0.0,
         },
     );
     let state = ground_state(&content);
@@ -479,6 +489,8 @@ fn component_purchase_skips_when_insufficient_budget() {
             importable: true,
             exportable: false,
             category: "component".to_string(),
+            royalty_per_unit: 0.0,
+            elasticity: 0.0,
         },
     );
     let mut state = ground_state(&content);
@@ -615,6 +627,8 @@ fn satellite_content() -> sim_core::GameContent {
             importable: true,
             exportable: false,
             category: "component".to_string(),
+            royalty_per_unit: 0.0,
+            elasticity: 0.0,
         },
     );
     content.pricing.items.insert(
@@ -624,6 +638,8 @@ fn satellite_content() -> sim_core::GameContent {
             importable: true,
             exportable: false,
             category: "component".to_string(),
+            royalty_per_unit: 0.0,
+            elasticity: 0.0,
         },
     );
 
@@ -3,6 +3,7 @@ pub(crate) mod ground_facility_agent;
 pub(crate) mod module_delivery;
 pub(crate) mod ship_agent;
 pub(crate) mod station_agent;
+pub(crate) mod trip_planner;
 
 use sim_core::{CommandEnvelope, GameContent, GameState, PrincipalId};
 
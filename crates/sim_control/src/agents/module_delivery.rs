@@ -112,6 +112,7 @@ fn find_spare_module<'a>(
             InventoryItem::Module {
                 item_id,
                 module_def_id,
+                ..
             } => Some((item_id, module_def_id.as_str())),
             _ => None,
         })
@@ -330,10 +331,12 @@ mod tests {
                     SlotDef {
                         slot_type: utility,
                         label: "Utility 1".to_string(),
+                        adjacent: Vec::new(),
                     },
                     SlotDef {
                         slot_type: research,
                         label: "Research 1".to_string(),
+                        adjacent: Vec::new(),
                     },
                 ],
                 bonuses: vec![],
@@ -355,6 +358,7 @@ mod tests {
             station.core.inventory.push(InventoryItem::Module {
                 item_id: sim_core::ModuleItemId("item_solar_1".to_string()),
                 module_def_id: "module_solar_panel".to_string(),
+                wear: 0.0,
             });
         }
 
@@ -380,9 +384,15 @@ mod tests {
                 module_type_index: Default::default(),
                 module_id_index: Default::default(),
                 power_budget_cache: Default::default(),
+                production_windows: std::collections::BTreeMap::new(),
+                life_support_failing: false,
             },
             frame_id: Some(FrameId("frame_outpost".to_string())),
             leaders: vec![],
+            owner: PrincipalId("principal_autopilot".to_string()),
+            expansions: sim_core::StationExpansions::default(),
+            power_policy: sim_core::PowerPolicy::default(),
+            history: std::collections::VecDeque::new(),
         };
         state.stations.insert(target_id, target);
 
@@ -403,6 +413,10 @@ mod tests {
             crew: BTreeMap::new(),
             leaders: vec![],
             home_station: None,
+            name: String::new(),
+            class_id: None,
+            role: None,
+            wear: sim_core::WearState::default(),
         };
         state.ships.insert(ship_id(), ship);
 
@@ -480,6 +494,7 @@ mod tests {
         target.core.inventory.push(InventoryItem::Module {
             item_id: sim_core::ModuleItemId("item_solar_local".to_string()),
             module_def_id: "module_solar_panel".to_string(),
+            wear: 0.0,
         });
 
         let mut ship_agents = BTreeMap::new();
@@ -512,6 +527,7 @@ mod tests {
         source.core.inventory.push(InventoryItem::Module {
             item_id: sim_core::ModuleItemId("item_lab_1".to_string()),
             module_def_id: "module_lab".to_string(),
+            wear: 0.0,
         });
 
         // Only ONE idle ship — must pick the higher-priority module (power).
@@ -593,10 +609,12 @@ mod tests {
             SlotDef {
                 slot_type: sim_core::SlotType("utility".to_string()),
                 label: "Utility 1".to_string(),
+                adjacent: Vec::new(),
             },
             SlotDef {
                 slot_type: sim_core::SlotType("utility".to_string()),
                 label: "Utility 2".to_string(),
+                adjacent: Vec::new(),
             },
         ];
         let mut state = delivery_state(&content);
@@ -605,6 +623,7 @@ mod tests {
         source.core.inventory.push(InventoryItem::Module {
             item_id: sim_core::ModuleItemId("item_solar_2".to_string()),
             module_def_id: "module_solar_panel".to_string(),
+            wear: 0.0,
         });
 
         // Two idle ships.
@@ -627,6 +646,10 @@ mod tests {
                 crew: BTreeMap::new(),
                 leaders: vec![],
                 home_station: None,
+                name: String::new(),
+                class_id: None,
+                role: None,
+                wear: sim_core::WearState::default(),
             },
         );
 
@@ -656,6 +679,7 @@ mod tests {
         target.core.inventory.push(InventoryItem::Module {
             item_id: sim_core::ModuleItemId("item_solar_local".to_string()),
             module_def_id: "module_solar_panel".to_string(),
+            wear: 0.0,
         });
         // Give the frame an extra utility slot (total: 2 utility + 1 research).
         let frame = content
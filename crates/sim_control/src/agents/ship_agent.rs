@@ -1,8 +1,14 @@
+use std::collections::VecDeque;
+
 use sim_core::{
-    Command, CommandEnvelope, GameContent, GameState, PrincipalId, ShipId, ShipState, TaskKind,
+    AsteroidId, Command, CommandEnvelope, GameContent, GameState, PrincipalId, ShipId, ShipState,
+    SiteId, TaskKind,
 };
 
-use crate::behaviors::{deposit_priority, make_cmd, maybe_transit, should_opportunistic_refuel};
+use crate::agents::trip_planner::hold_has_room;
+use crate::behaviors::{
+    deposit_priority, make_cmd, maybe_transit, repair_priority, should_opportunistic_refuel,
+};
 use crate::objectives::ShipObjective;
 
 use super::Agent;
@@ -29,12 +35,22 @@ fn make_ship_task_cmd(
 /// A ship-level agent that converts a `ShipObjective` into tactical commands.
 ///
 /// The ship agent handles the "how" of executing an objective: transit routing,
-/// opportunistic refueling, deposit priority, and objective invalidation.
+/// opportunistic refueling, deposit priority, repair trips, and objective
+/// invalidation.
 /// It does NOT pick its own target — the station layer (or assignment bridge)
 /// assigns objectives.
 pub(crate) struct ShipAgent {
     pub(crate) ship_id: ShipId,
     pub(crate) objective: Option<ShipObjective>,
+    /// Remaining scan sites at the same node as the current `Survey`
+    /// objective. Each becomes the next objective once the previous survey
+    /// completes, so one ship sweeps a node instead of several ships
+    /// crossing the map to neighbouring sites.
+    pub(crate) survey_batch: VecDeque<SiteId>,
+    /// Asteroids on the current multi-asteroid mining trip, current leg
+    /// first. While non-empty the ship keeps mining instead of depositing
+    /// until the hold is full or the legs run out.
+    pub(crate) mining_trip: VecDeque<AsteroidId>,
 }
 
 impl ShipAgent {
@@ -42,6 +58,58 @@ impl ShipAgent {
         Self {
             ship_id,
             objective: None,
+            survey_batch: VecDeque::new(),
+            mining_trip: VecDeque::new(),
+        }
+    }
+
+    /// True when the agent has no objective and no queued surveys or
+    /// mining legs.
+    pub(crate) fn is_unassigned(&self) -> bool {
+        self.objective.is_none() && self.survey_batch.is_empty() && self.mining_trip.is_empty()
+    }
+
+    /// Once the current leg's asteroid is depleted, move on to the next
+    /// still-mineable asteroid of the trip.
+    fn advance_mining_trip(&mut self, state: &GameState) {
+        if self.objective.is_some() || self.mining_trip.is_empty() {
+            return;
+        }
+        self.mining_trip.pop_front();
+        while let Some(asteroid_id) = self.mining_trip.front() {
+            if state
+                .asteroids
+                .get(asteroid_id)
+                .is_some_and(|a| a.mass_kg > 0.0)
+            {
+                self.objective = Some(ShipObjective::Mine {
+                    asteroid_id: asteroid_id.clone(),
+                });
+                return;
+            }
+            self.mining_trip.pop_front();
+        }
+    }
+
+    /// True while a mining trip has legs left and the hold has room, in
+    /// which case the deposit-first rule is deferred.
+    fn continuing_mining_trip(&self, ship: &ShipState, content: &GameContent) -> bool {
+        !self.mining_trip.is_empty()
+            && matches!(self.objective, Some(ShipObjective::Mine { .. }))
+            && hold_has_room(ship, content)
+    }
+
+    /// Promote the next still-unsurveyed site in the batch to the current
+    /// objective. Sites surveyed by someone else in the meantime are skipped.
+    fn advance_survey_batch(&mut self, state: &GameState) {
+        if self.objective.is_some() {
+            return;
+        }
+        while let Some(site_id) = self.survey_batch.pop_front() {
+            if state.scan_sites.iter().any(|s| s.id == site_id) {
+                self.objective = Some(ShipObjective::Survey { site_id });
+                return;
+            }
         }
     }
 
@@ -176,6 +244,8 @@ impl Agent for ShipAgent {
 
         // Validate current objective — clear if target is gone
         self.validate_objective(state);
+        self.advance_survey_batch(state);
+        self.advance_mining_trip(state);
 
         // Opportunistic refuel takes precedence over everything
         if should_opportunistic_refuel(ship, state, content) {
@@ -190,8 +260,24 @@ impl Agent for ShipAgent {
             }
         }
 
-        // Deposit priority: if ship has cargo, deposit first regardless of objective
-        if let Some(task_kind) = deposit_priority(ship, state, content) {
+        // Deposit priority: if ship has cargo, deposit first regardless of
+        // objective — unless a mining trip still has room to fill.
+        if !self.continuing_mining_trip(ship, content) {
+            if let Some(task_kind) = deposit_priority(ship, state, content) {
+                self.mining_trip.clear();
+                return make_ship_task_cmd(
+                    ship,
+                    &self.ship_id,
+                    state.meta.tick,
+                    next_id,
+                    task_kind,
+                );
+            }
+        }
+
+        // Worn ships dock for repair before their next job; the objective
+        // is picked up again once they are repaired.
+        if let Some(task_kind) = repair_priority(ship, state, content) {
             return make_ship_task_cmd(ship, &self.ship_id, state.meta.tick, next_id, task_kind);
         }
 
@@ -273,6 +359,10 @@ mod tests {
             crew: BTreeMap::new(),
             leaders: vec![],
             home_station: None,
+            name: String::new(),
+            class_id: None,
+            role: None,
+            wear: sim_core::WearState::default(),
         };
         state.ships.insert(test_ship_id(), ship);
         (state, content)
@@ -297,11 +387,83 @@ mod tests {
                 knowledge: AsteroidKnowledge {
                     tag_beliefs: vec![],
                     composition,
+                    layers: None,
+                    composition_sigma: None,
+                    deep_scan_count: 0,
                 },
+                layers: None,
             },
         );
     }
 
+    #[test]
+    fn test_survey_batch_advances_after_site_surveyed() {
+        let (mut state, content) = setup_state_with_ship();
+        for id in ["site_b", "site_c"] {
+            state.scan_sites.push(sim_core::ScanSite {
+                id: SiteId(id.to_string()),
+                position: test_position(),
+                template_id: "tmpl_iron_rich".to_string(),
+            });
+        }
+        let owner = PrincipalId("principal_autopilot".to_string());
+        let mut agent = ShipAgent::new(test_ship_id());
+        // site_a was just surveyed (no longer in scan_sites); site_b is next.
+        agent.objective = Some(ShipObjective::Survey {
+            site_id: SiteId("site_a".to_string()),
+        });
+        agent.survey_batch =
+            VecDeque::from([SiteId("site_b".to_string()), SiteId("site_c".to_string())]);
+        let mut next_id = 1;
+
+        let commands = agent.generate(&state, &content, &owner, &mut next_id, None);
+
+        assert!(matches!(
+            &agent.objective,
+            Some(ShipObjective::Survey { site_id }) if site_id.0 == "site_b"
+        ));
+        assert_eq!(agent.survey_batch.len(), 1);
+        assert_eq!(commands.len(), 1);
+    }
+
+    #[test]
+    fn test_mining_trip_continues_to_next_leg_before_depositing() {
+        let (mut state, content) = setup_state_with_ship();
+        let leg_a = AsteroidId("asteroid_a".to_string());
+        let leg_b = AsteroidId("asteroid_b".to_string());
+        add_asteroid(&mut state, leg_a.clone(), 0.0, true); // just depleted
+        add_asteroid(&mut state, leg_b.clone(), 1_000.0, true);
+        state
+            .ships
+            .get_mut(&test_ship_id())
+            .unwrap()
+            .inventory
+            .push(InventoryItem::Ore {
+                lot_id: LotId("lot_a".to_string()),
+                asteroid_id: leg_a.clone(),
+                kg: 1_000.0,
+                composition: std::collections::HashMap::from([("Fe".to_string(), 0.8)]),
+            });
+        let owner = PrincipalId("principal_autopilot".to_string());
+        let mut agent = ShipAgent::new(test_ship_id());
+        agent.objective = Some(ShipObjective::Mine {
+            asteroid_id: leg_a.clone(),
+        });
+        agent.mining_trip = VecDeque::from([leg_a, leg_b.clone()]);
+        let mut next_id = 1;
+
+        let commands = agent.generate(&state, &content, &owner, &mut next_id, None);
+
+        assert_eq!(commands.len(), 1);
+        assert!(matches!(
+            &commands[0].command,
+            Command::AssignShipTask {
+                task_kind: TaskKind::Mine { asteroid, .. },
+                ..
+            } if *asteroid == leg_b
+        ));
+    }
+
     #[test]
     fn test_no_objective_produces_no_commands() {
         let (state, content) = setup_state_with_ship();
@@ -558,6 +720,63 @@ mod tests {
         assert!(agent.objective.is_some());
     }
 
+    #[test]
+    fn test_worn_ship_docks_for_repair_before_mining() {
+        let (mut state, mut content) = setup_state_with_ship();
+        content.module_defs.insert(
+            "module_maintenance_bay".to_string(),
+            sim_core::test_fixtures::ModuleDefBuilder::new("module_maintenance_bay")
+                .behavior(sim_core::ModuleBehaviorDef::Maintenance(
+                    sim_core::MaintenanceDef {
+                        repair_interval_minutes: 60,
+                        repair_interval_ticks: 1,
+                        wear_reduction_per_run: 0.2,
+                        repair_kit_cost: 1,
+                        repair_threshold: 0.0,
+                        maintenance_component_id: "repair_kit".to_string(),
+                    },
+                ))
+                .build(),
+        );
+        let station = state.stations.get_mut(&test_station_id()).unwrap();
+        station
+            .core
+            .modules
+            .push(sim_core::test_fixtures::test_module(
+                "module_maintenance_bay",
+                sim_core::ModuleKindState::Maintenance(sim_core::MaintenanceState {
+                    ticks_since_last_run: 0,
+                }),
+            ));
+        station.core.inventory.push(InventoryItem::Component {
+            component_id: sim_core::ComponentId("repair_kit".to_string()),
+            count: 2,
+            quality: 1.0,
+        });
+        add_asteroid(&mut state, test_asteroid_id(), 5000.0, true);
+        state.ships.get_mut(&test_ship_id()).unwrap().wear.wear =
+            content.autopilot.ship_repair_wear_threshold;
+
+        let owner = PrincipalId("principal_autopilot".to_string());
+        let mut agent = ShipAgent::new(test_ship_id());
+        agent.objective = Some(ShipObjective::Mine {
+            asteroid_id: test_asteroid_id(),
+        });
+        let mut next_id = 1;
+        let commands = agent.generate(&state, &content, &owner, &mut next_id, None);
+
+        assert_eq!(commands.len(), 1);
+        assert!(matches!(
+            &commands[0].command,
+            Command::AssignShipTask {
+                task_kind: TaskKind::DockForRepair { station },
+                ..
+            } if *station == test_station_id()
+        ));
+        // The mining objective waits for the repair.
+        assert!(matches!(agent.objective, Some(ShipObjective::Mine { .. })));
+    }
+
     #[test]
     fn test_non_idle_ship_produces_no_commands() {
         let (mut state, content) = setup_state_with_ship();
@@ -624,9 +843,15 @@ mod tests {
                 module_type_index: Default::default(),
                 module_id_index: Default::default(),
                 power_budget_cache: Default::default(),
+                production_windows: std::collections::BTreeMap::new(),
+                life_support_failing: false,
             },
             frame_id: None,
             leaders: vec![],
+            owner: PrincipalId("principal_autopilot".to_string()),
+            expansions: sim_core::StationExpansions::default(),
+            power_policy: sim_core::PowerPolicy::default(),
+            history: std::collections::VecDeque::new(),
         };
         state.stations.insert(to.clone(), target);
         agent.objective = Some(ShipObjective::Transfer {
@@ -0,0 +1,97 @@
+//! Cold-start bootstrap sequencing for a station agent.
+//!
+//! A fresh station otherwise installs every module, sets thresholds, assigns
+//! labs and sends ships out in the same tick. The bootstrap walks it through
+//! power → refinery → labs → exploration instead: each step only unlocks its
+//! own actions, and the next step starts once the station state shows the
+//! current one is done. A station with nothing left to install falls straight
+//! through to `Exploration` on its first tick.
+
+use sim_core::{GameContent, InventoryItem, ModuleBehaviorDef, StationState};
+
+/// A step that has not completed after this many ticks is skipped, so a
+/// module with no free slot cannot wedge the sequence.
+const STEP_TIMEOUT_TICKS: u64 = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum BootstrapStep {
+    /// Install solar arrays and batteries.
+    Power,
+    /// Install processors and support modules; set processor thresholds.
+    Refinery,
+    /// Install labs and assign them to techs.
+    Labs,
+    /// Bootstrap finished: ships may be sent out.
+    Exploration,
+}
+
+impl BootstrapStep {
+    fn next(self) -> Self {
+        match self {
+            Self::Power => Self::Refinery,
+            Self::Refinery => Self::Labs,
+            Self::Labs | Self::Exploration => Self::Exploration,
+        }
+    }
+
+    /// Whether a module with this behavior may be installed during the step.
+    pub(crate) fn allows_install(self, behavior: &ModuleBehaviorDef) -> bool {
+        match behavior {
+            ModuleBehaviorDef::SolarArray(_) | ModuleBehaviorDef::Battery(_) => true,
+            ModuleBehaviorDef::Lab(_) => self >= Self::Labs,
+            _ => self >= Self::Refinery,
+        }
+    }
+}
+
+pub(crate) struct Bootstrap {
+    step: BootstrapStep,
+    step_started_tick: Option<u64>,
+}
+
+impl Bootstrap {
+    pub(crate) fn new() -> Self {
+        Self {
+            step: BootstrapStep::Power,
+            step_started_tick: None,
+        }
+    }
+
+    pub(crate) fn finished(&self) -> bool {
+        self.step == BootstrapStep::Exploration
+    }
+
+    /// Advance past every step whose completion check passes (or that has
+    /// timed out) against the current state. Returns the current step.
+    pub(crate) fn advance(
+        &mut self,
+        station: &StationState,
+        content: &GameContent,
+        tick: u64,
+    ) -> BootstrapStep {
+        while self.step != BootstrapStep::Exploration {
+            let started = *self.step_started_tick.get_or_insert(tick);
+            let timed_out = tick.saturating_sub(started) >= STEP_TIMEOUT_TICKS;
+            if !timed_out && !step_complete(self.step, station, content) {
+                break;
+            }
+            self.step = self.step.next();
+            self.step_started_tick = Some(tick);
+        }
+        self.step
+    }
+}
+
+/// True once the station holds no module the step may install. Processor
+/// thresholds are issued during the refinery step but do not hold it open.
+fn step_complete(step: BootstrapStep, station: &StationState, content: &GameContent) -> bool {
+    !station.core.inventory.iter().any(|item| {
+        let InventoryItem::Module { module_def_id, .. } = item else {
+            return false;
+        };
+        content
+            .module_defs
+            .get(module_def_id)
+            .is_some_and(|def| step.allows_install(&def.behavior))
+    })
+}
@@ -1,9 +1,10 @@
 use sim_core::{
     trade, Command, CommandEnvelope, ComponentId, InputAmount, InputFilter, InventoryItem,
-    ModuleBehaviorDef, TechId, TradeItemSpec,
+    ModuleBehaviorDef, StationState, TechId, TradeItemSpec,
 };
 
 use crate::behaviors::make_cmd;
+use crate::scoring;
 
 use super::super::{StationConcern, StationContext};
 
@@ -86,19 +87,7 @@ impl StationConcern for ComponentImport {
             return Vec::new();
         }
 
-        let component_count: u32 = station
-            .core
-            .inventory
-            .iter()
-            .filter_map(|item| match item {
-                InventoryItem::Component {
-                    component_id,
-                    count,
-                    ..
-                } if component_id.0 == *import_component => Some(*count),
-                _ => None,
-            })
-            .sum();
+        let component_count = stocked_and_inbound_count(ctx, station, import_component);
         if component_count >= required_components {
             return Vec::new();
         }
@@ -109,11 +98,11 @@ impl StationConcern for ComponentImport {
             count: needed,
         };
 
-        let Some(cost) = trade::compute_import_cost(&item_spec, &ctx.content.pricing, ctx.content)
+        let Some(cost) = trade::compute_import_cost(&item_spec, &ctx.state.market, ctx.content)
         else {
             return Vec::new();
         };
-        if cost > ctx.state.balance * ctx.state.strategy_config.budget_cap_fraction {
+        if cost > scoring::spend_limit(ctx.state, &ctx.content.autopilot.weights, 1.0) {
             return Vec::new();
         }
 
@@ -128,3 +117,32 @@ impl StationConcern for ComponentImport {
         )]
     }
 }
+
+/// Count components on hand plus those in in-transit import orders, so the
+/// station doesn't re-order while a delivery is pending.
+fn stocked_and_inbound_count(
+    ctx: &StationContext,
+    station: &StationState,
+    component_id: &str,
+) -> u32 {
+    let in_transit = ctx
+        .state
+        .import_orders
+        .iter()
+        .filter(|order| order.station_id == *ctx.station_id)
+        .flat_map(|order| order.items.iter());
+    station
+        .core
+        .inventory
+        .iter()
+        .chain(in_transit)
+        .filter_map(|item| match item {
+            InventoryItem::Component {
+                component_id: id,
+                count,
+                ..
+            } if id.0 == component_id => Some(*count),
+            _ => None,
+        })
+        .sum()
+}
@@ -0,0 +1,136 @@
+use sim_core::{Command, CommandEnvelope, Contract, ContractKind, InventoryItem, StationState};
+
+use crate::behaviors::make_cmd;
+
+use super::super::{StationConcern, StationContext};
+
+/// 6b. Take contracts ahead of exports: delivery offers this station can
+/// fill from stock, and a survey offer while the owner holds none. Material
+/// promised to contracts is held back from `MaterialExport`.
+pub(in crate::agents) struct ContractFulfilment;
+
+impl StationConcern for ContractFulfilment {
+    fn name(&self) -> &'static str {
+        "contract_fulfilment"
+    }
+    fn should_run(&self, ctx: &StationContext) -> bool {
+        !ctx.state.contracts.is_empty()
+    }
+    fn generate(&mut self, ctx: &mut StationContext) -> Vec<CommandEnvelope> {
+        let Some(station) = ctx.state.stations.get(ctx.station_id) else {
+            return Vec::new();
+        };
+        let mut accept: Vec<&Contract> = fillable_offers(ctx, station);
+        if let Some(survey) = survey_offer(ctx) {
+            accept.push(survey);
+        }
+        accept
+            .into_iter()
+            .map(|contract| {
+                make_cmd(
+                    ctx.owner,
+                    ctx.state.meta.tick,
+                    ctx.next_id,
+                    Command::AcceptContract {
+                        contract_id: contract.id,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+fn station_kg(station: &StationState, element: &str) -> f32 {
+    station
+        .core
+        .inventory
+        .iter()
+        .filter_map(|item| match item {
+            InventoryItem::Material {
+                element: el, kg, ..
+            } if el == element => Some(*kg),
+            _ => None,
+        })
+        .sum()
+}
+
+/// kg of `element` the owner's held delivery contracts still need.
+fn held_kg(ctx: &StationContext, element: &str) -> f32 {
+    ctx.state
+        .contracts
+        .iter()
+        .filter(|contract| contract.holder.as_ref() == Some(ctx.owner))
+        .filter_map(|contract| match &contract.kind {
+            ContractKind::Deliver { element: el, kg } if el == element => Some(*kg),
+            _ => None,
+        })
+        .sum()
+}
+
+/// Open delivery offers this station can fill from stock after covering
+/// held contracts, in offer order, each drawing on what the earlier ones
+/// left.
+fn fillable_offers<'a>(ctx: &StationContext<'a>, station: &StationState) -> Vec<&'a Contract> {
+    let mut promised: Vec<(&str, f32)> = Vec::new();
+    let mut offers = Vec::new();
+    for contract in &ctx.state.contracts {
+        let ContractKind::Deliver { element, kg } = &contract.kind else {
+            continue;
+        };
+        if contract.holder.is_some() {
+            continue;
+        }
+        let already: f32 = promised
+            .iter()
+            .filter(|(el, _)| *el == element.as_str())
+            .map(|(_, kg)| kg)
+            .sum();
+        if station_kg(station, element) - held_kg(ctx, element) - already >= *kg {
+            promised.push((element, *kg));
+            offers.push(contract);
+        }
+    }
+    offers
+}
+
+/// The first open survey offer, taken by the owner's first station while
+/// the owner has ships and holds no survey contract.
+fn survey_offer<'a>(ctx: &StationContext<'a>) -> Option<&'a Contract> {
+    let first_station = ctx
+        .state
+        .stations
+        .values()
+        .find(|station| station.owner == *ctx.owner)?;
+    if first_station.id != *ctx.station_id
+        || !ctx
+            .state
+            .ships
+            .values()
+            .any(|ship| ship.owner == *ctx.owner)
+        || ctx.state.contracts.iter().any(|contract| {
+            contract.holder.as_ref() == Some(ctx.owner)
+                && matches!(contract.kind, ContractKind::Survey { .. })
+        })
+    {
+        return None;
+    }
+    ctx.state.contracts.iter().find(|contract| {
+        contract.holder.is_none() && matches!(contract.kind, ContractKind::Survey { .. })
+    })
+}
+
+/// kg of `element` this station keeps back from export: what the owner's
+/// held deliveries need plus the offers it is taking this tick.
+pub(in crate::agents) fn contract_reserve_kg(ctx: &StationContext, element: &str) -> f32 {
+    let Some(station) = ctx.state.stations.get(ctx.station_id) else {
+        return 0.0;
+    };
+    let taking: f32 = fillable_offers(ctx, station)
+        .iter()
+        .filter_map(|contract| match &contract.kind {
+            ContractKind::Deliver { element: el, kg } if el == element => Some(*kg),
+            _ => None,
+        })
+        .sum();
+    held_kg(ctx, element) + taking
+}
@@ -2,6 +2,7 @@ use sim_core::{trade, Command, CommandEnvelope, TradeItemSpec};
 
 use crate::agents::DecisionRecord;
 use crate::behaviors::make_cmd;
+use crate::scoring;
 
 use super::super::{has_unsatisfied_crew_need, StationConcern, StationContext};
 
@@ -51,12 +52,11 @@ impl StationConcern for CrewRecruitment {
                 role: role.clone(),
                 count: shortfall,
             };
-            let Some(cost) =
-                trade::compute_import_cost(&item_spec, &ctx.content.pricing, ctx.content)
+            let Some(cost) = trade::compute_import_cost(&item_spec, &ctx.state.market, ctx.content)
             else {
                 continue;
             };
-            let budget_cap = ctx.state.balance * ctx.state.strategy_config.budget_cap_fraction;
+            let budget_cap = scoring::spend_limit(ctx.state, &ctx.content.autopilot.weights, 1.0);
             if cost > budget_cap {
                 continue;
             }
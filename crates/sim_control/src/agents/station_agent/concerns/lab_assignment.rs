@@ -6,7 +6,9 @@ use sim_core::{
 
 use crate::agents::DecisionRecord;
 use crate::behaviors::{compute_sufficiency, make_cmd};
+use crate::scoring;
 
+use super::super::BootstrapStep;
 use super::super::StationConcern;
 use super::super::StationContext;
 
@@ -30,12 +32,51 @@ pub(crate) struct LabAssignment {
     cache: LabAssignmentCache,
 }
 
+/// Score eligible techs by evidence sufficiency and by how many locked techs
+/// they unblock, weighted by `AutopilotWeights.research_progress` and
+/// `research_urgency`.
+fn score_candidates(ctx: &StationContext, eligible: &[TechId]) -> Vec<(TechId, f64)> {
+    let research = &ctx.state.research;
+    let dependents = |tech_id: &TechId| {
+        ctx.content
+            .techs
+            .iter()
+            .filter(|tech| !research.unlocked.contains(&tech.id))
+            .filter(|tech| tech.prereqs.contains(tech_id))
+            .count()
+    };
+    let terms: Vec<(TechId, f32, usize)> = eligible
+        .iter()
+        .filter(|tid| !research.unlocked.contains(tid))
+        .filter_map(|tid| {
+            let tech = ctx.content.techs.iter().find(|t| t.id == *tid)?;
+            let sufficiency = compute_sufficiency(tech, research.evidence.get(tid));
+            Some((tid.clone(), sufficiency, dependents(tid)))
+        })
+        .collect();
+    #[allow(clippy::cast_precision_loss)]
+    let max_dependents = terms.iter().map(|(_, _, count)| *count).max().unwrap_or(0) as f64;
+    let weights = &ctx.content.autopilot.weights;
+    terms
+        .into_iter()
+        .map(|(tech_id, sufficiency, dependents)| {
+            #[allow(clippy::cast_precision_loss)]
+            let urgency = scoring::normalized(dependents as f64, max_dependents);
+            let score = scoring::weighted_score(&[
+                (weights.research_progress, f64::from(sufficiency)),
+                (weights.research_urgency, urgency),
+            ]);
+            (tech_id, score)
+        })
+        .collect()
+}
+
 impl StationConcern for LabAssignment {
     fn name(&self) -> &'static str {
         "lab_assignment"
     }
-    fn should_run(&self, _ctx: &StationContext) -> bool {
-        true
+    fn should_run(&self, ctx: &StationContext) -> bool {
+        ctx.bootstrap >= BootstrapStep::Labs
     }
     fn generate(&mut self, ctx: &mut StationContext) -> Vec<CommandEnvelope> {
         // Rebuild eligible tech cache when unlocked set changes.
@@ -93,16 +134,7 @@ impl StationConcern for LabAssignment {
                 .cached_eligible
                 .get(&lab_def.domain)
                 .map_or(&[][..], |v| v.as_slice());
-            let mut candidates: Vec<(TechId, f32)> = eligible
-                .iter()
-                .filter(|tid| !ctx.state.research.unlocked.contains(tid))
-                .filter_map(|tid| {
-                    let tech = ctx.content.techs.iter().find(|t| t.id == *tid)?;
-                    let sufficiency =
-                        compute_sufficiency(tech, ctx.state.research.evidence.get(tid));
-                    Some((tid.clone(), sufficiency))
-                })
-                .collect();
+            let mut candidates = score_candidates(ctx, eligible);
             candidates.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0 .0.cmp(&b.0 .0)));
 
             if let Some((tech_id, score)) = candidates.first() {
@@ -123,19 +155,19 @@ impl StationConcern for LabAssignment {
                         concern: "lab_assignment".to_string(),
                         decision_type: "pick_tech".to_string(),
                         chosen_id: tech_id.0.clone(),
-                        chosen_score: f64::from(*score),
+                        chosen_score: *score,
                         alt_1_id: candidates
                             .get(1)
                             .map_or_else(String::new, |(t, _)| t.0.clone()),
-                        alt_1_score: candidates.get(1).map_or(0.0, |(_, s)| f64::from(*s)),
+                        alt_1_score: candidates.get(1).map_or(0.0, |(_, s)| *s),
                         alt_2_id: candidates
                             .get(2)
                             .map_or_else(String::new, |(t, _)| t.0.clone()),
-                        alt_2_score: candidates.get(2).map_or(0.0, |(_, s)| f64::from(*s)),
+                        alt_2_score: candidates.get(2).map_or(0.0, |(_, s)| *s),
                         alt_3_id: candidates
                             .get(3)
                             .map_or_else(String::new, |(t, _)| t.0.clone()),
-                        alt_3_score: candidates.get(3).map_or(0.0, |(_, s)| f64::from(*s)),
+                        alt_3_score: candidates.get(3).map_or(0.0, |(_, s)| *s),
                         context_json: format!(
                             "{{\"domain\":\"{}\",\"module\":\"{}\"}}",
                             lab_def.domain, module.id.0,
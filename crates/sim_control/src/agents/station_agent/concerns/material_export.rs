@@ -1,7 +1,9 @@
-use sim_core::{trade, Command, CommandEnvelope};
+use sim_core::{trade, Command, CommandEnvelope, TradeItemSpec};
 
 use crate::behaviors::{build_export_candidates, make_cmd};
 
+use super::contract_reserve_kg;
+
 use super::super::{StationConcern, StationContext};
 
 /// 7. Export surplus materials for revenue.
@@ -25,12 +27,20 @@ impl StationConcern for MaterialExport {
 
         let candidates = build_export_candidates(station, &ctx.content.autopilot, batch_size_kg);
         for candidate in candidates {
-            if trade::compute_export_revenue(&candidate, &ctx.content.pricing, ctx.content)
+            if trade::compute_export_revenue(&candidate, &ctx.state.market, ctx.content)
                 .is_none_or(|rev| rev < min_revenue)
             {
                 continue;
             }
-            if !trade::has_enough_for_export(&station.core.inventory, &candidate) {
+            // Contracts pay better than the market; keep their material.
+            let needed = match &candidate {
+                TradeItemSpec::Material { element, kg } => TradeItemSpec::Material {
+                    element: element.clone(),
+                    kg: kg + contract_reserve_kg(ctx, element),
+                },
+                other => other.clone(),
+            };
+            if !trade::has_enough_for_export(&station.core.inventory, &needed) {
                 continue;
             }
             commands.push(make_cmd(
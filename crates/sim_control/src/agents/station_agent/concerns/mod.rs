@@ -1,19 +1,23 @@
 mod component_import;
+mod contract_fulfilment;
 mod crew_assignment;
 mod crew_recruitment;
 mod lab_assignment;
 mod material_export;
 mod module_management;
+mod processor_thresholds;
 mod propellant_management;
 mod ship_fitting;
 mod slag_jettison;
 
 pub(in crate::agents) use component_import::ComponentImport;
+pub(in crate::agents) use contract_fulfilment::{contract_reserve_kg, ContractFulfilment};
 pub(in crate::agents) use crew_assignment::CrewAssignment;
 pub(in crate::agents) use crew_recruitment::CrewRecruitment;
 pub(crate) use lab_assignment::LabAssignment;
 pub(in crate::agents) use material_export::MaterialExport;
 pub(crate) use module_management::ModuleManagement;
+pub(crate) use processor_thresholds::ProcessorThresholds;
 pub(crate) use propellant_management::PropellantManagement;
 pub(in crate::agents) use ship_fitting::ShipFitting;
 pub(in crate::agents) use slag_jettison::SlagJettison;
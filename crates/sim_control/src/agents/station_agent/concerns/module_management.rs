@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use sim_core::{Command, CommandEnvelope, InventoryItem, ModuleKindState, SlotType};
+use sim_core::{Command, CommandEnvelope, InventoryItem, SlotType};
 
 use crate::behaviors::make_cmd;
 
@@ -8,7 +8,8 @@ use super::super::StationConcern;
 use super::super::StationContext;
 
 /// 1. Install modules from inventory, re-enable disabled modules (except
-///    propellant-role and max-wear), set processor thresholds.
+///    propellant-role and max-wear). Processor thresholds are set by
+///    `ProcessorThresholds`.
 pub(crate) struct ModuleManagement;
 
 /// Shed load during power deficit or re-enable modules during surplus.
@@ -73,17 +74,17 @@ fn manage_power(
         let mut available_headroom =
             station.core.power.generated_kw - station.core.power.consumed_kw;
         for module in &station.core.modules {
+            // Modules without a def were quarantined at load; leave them off.
+            let Some(def) = ctx.content.module_defs.get(&module.def_id) else {
+                continue;
+            };
             if !module.enabled
                 && module.wear.wear < 1.0
                 && !ctx
                     .content
                     .module_has_role(&module.def_id, &ctx.content.autopilot.propellant_role)
             {
-                let power_cost = ctx
-                    .content
-                    .module_defs
-                    .get(&module.def_id)
-                    .map_or(0.0, |d| d.power_consumption_per_run);
+                let power_cost = def.power_consumption_per_run;
                 if !has_power_gen || power_cost <= available_headroom || power_cost <= 0.0 {
                     commands.push(make_cmd(
                         ctx.owner,
@@ -111,7 +112,15 @@ fn enqueue_frameless_installs(
     commands: &mut Vec<CommandEnvelope>,
 ) {
     for item in &station.core.inventory {
-        if let InventoryItem::Module { item_id, .. } = item {
+        if let InventoryItem::Module {
+            item_id,
+            module_def_id,
+            ..
+        } = item
+        {
+            if !install_allowed(ctx, module_def_id) {
+                continue;
+            }
             commands.push(make_cmd(
                 ctx.owner,
                 ctx.state.meta.tick,
@@ -163,6 +172,7 @@ fn enqueue_framed_installs(
         let InventoryItem::Module {
             item_id,
             module_def_id,
+            ..
         } = item
         else {
             continue;
@@ -170,6 +180,9 @@ fn enqueue_framed_installs(
         let Some(def) = ctx.content.module_defs.get(module_def_id) else {
             continue;
         };
+        if !ctx.bootstrap.allows_install(&def.behavior) {
+            continue;
+        }
         let Some(slot_idx) = find_free_slot(&frame.slots, &def.compatible_slots, &claimed) else {
             // No compatible free slot this tick — wait for one to open up.
             continue;
@@ -188,6 +201,15 @@ fn enqueue_framed_installs(
     }
 }
 
+/// Whether the bootstrap step lets this module be installed yet. Modules
+/// without a def are left to the handler, as before.
+fn install_allowed(ctx: &StationContext, module_def_id: &str) -> bool {
+    ctx.content
+        .module_defs
+        .get(module_def_id)
+        .is_none_or(|def| ctx.bootstrap.allows_install(&def.behavior))
+}
+
 /// Return the first slot index that is compatible with `compatible_slots`
 /// and not already in `claimed`. Matches the handler's first-fit policy so
 /// the autopilot and handler agree on slot assignments.
@@ -241,23 +263,6 @@ impl StationConcern for ModuleManagement {
 
         manage_power(station, ctx, &mut commands);
 
-        for module in &station.core.modules {
-            if let ModuleKindState::Processor(processor_state) = &module.kind_state {
-                if processor_state.threshold_kg == 0.0 {
-                    commands.push(make_cmd(
-                        ctx.owner,
-                        ctx.state.meta.tick,
-                        ctx.next_id,
-                        Command::SetModuleThreshold {
-                            facility_id: ctx.station_id.clone().into(),
-                            module_id: module.id.clone(),
-                            threshold_kg: ctx.state.strategy_config.refinery_threshold_kg,
-                        },
-                    ));
-                }
-            }
-        }
-
         commands
     }
 }
@@ -0,0 +1,187 @@
+//! Throughput-based processor thresholds.
+//!
+//! A fixed refinery threshold either dribbles small batches when ore pours
+//! in or leaves ore sitting forever once a belt is depleted and inflow can
+//! no longer reach it. Instead, the concern tracks the station's recent ore
+//! inflow and aims each ore processor's threshold at
+//! `refinery_batch_ticks` worth of inflow. The target never drops below one
+//! full recipe batch and never rises above half of what the station could
+//! hold (current ore plus free space), so it stays reachable without
+//! blocking deposits.
+
+use sim_core::{
+    inventory_volume_m3, Command, CommandEnvelope, GameContent, InputAmount, InputFilter,
+    InventoryItem, ItemKind, ModuleBehaviorDef, ModuleKindState, StationState,
+};
+
+use crate::behaviors::make_cmd;
+
+use super::super::{BootstrapStep, StationConcern, StationContext};
+
+/// Time constant (ticks) of the inflow moving average.
+const INFLOW_SMOOTHING_TICKS: f32 = 48.0;
+/// Relative change a new target needs before a threshold is re-issued.
+const RETARGET_TOLERANCE: f32 = 0.25;
+/// Share of the station's reachable ore capacity a threshold may use.
+const HEADROOM_SHARE: f32 = 0.5;
+
+/// Exponential moving average of ore arriving at the station, from
+/// increases in station ore between passes.
+#[derive(Default)]
+struct OreInflow {
+    /// (tick, station ore kg) at the previous pass.
+    last: Option<(u64, f32)>,
+    first_tick: Option<u64>,
+    rate_kg_per_tick: f32,
+}
+
+impl OreInflow {
+    #[allow(clippy::cast_precision_loss)]
+    fn observe(&mut self, tick: u64, ore_kg: f32) {
+        match self.last {
+            Some((last_tick, last_kg)) if tick > last_tick => {
+                let elapsed = (tick - last_tick) as f32;
+                let sample = (ore_kg - last_kg).max(0.0) / elapsed;
+                let alpha = (elapsed / INFLOW_SMOOTHING_TICKS).min(1.0);
+                self.rate_kg_per_tick += alpha * (sample - self.rate_kg_per_tick);
+            }
+            Some(_) => {}
+            None => self.first_tick = Some(tick),
+        }
+        self.last = Some((tick, ore_kg));
+    }
+
+    fn observed_ticks(&self, tick: u64) -> u64 {
+        self.first_tick
+            .map_or(0, |first| tick.saturating_sub(first))
+    }
+}
+
+/// 1b. Set processor thresholds: the configured default on first sight,
+///     then retargeted from ore inflow once `refinery_batch_ticks` have
+///     been observed.
+#[derive(Default)]
+pub(crate) struct ProcessorThresholds {
+    inflow: OreInflow,
+}
+
+impl StationConcern for ProcessorThresholds {
+    fn name(&self) -> &'static str {
+        "processor_thresholds"
+    }
+    fn should_run(&self, ctx: &StationContext) -> bool {
+        ctx.bootstrap >= BootstrapStep::Refinery
+    }
+    fn generate(&mut self, ctx: &mut StationContext) -> Vec<CommandEnvelope> {
+        let Some(station) = ctx.state.stations.get(ctx.station_id) else {
+            return Vec::new();
+        };
+        let tick = ctx.state.meta.tick;
+        let ore_kg = station_ore_kg(station);
+        self.inflow.observe(tick, ore_kg);
+
+        let batch_ticks = ctx.state.strategy_config.refinery_batch_ticks;
+        let dynamic = batch_ticks > 0 && self.inflow.observed_ticks(tick) >= batch_ticks;
+        #[allow(clippy::cast_precision_loss)]
+        let inflow_target = self.inflow.rate_kg_per_tick * batch_ticks as f32;
+        let ceiling = HEADROOM_SHARE * reachable_ore_kg(station, ore_kg, ctx.content);
+
+        let mut commands = Vec::new();
+        for module in &station.core.modules {
+            let ModuleKindState::Processor(processor_state) = &module.kind_state else {
+                continue;
+            };
+            let current = processor_state.threshold_kg;
+            let batch_kg = ctx
+                .content
+                .module_defs
+                .get(&module.def_id)
+                .and_then(|def| ore_batch_kg(&def.behavior, ctx.content));
+            let threshold_kg = match batch_kg {
+                Some(batch_kg) if dynamic => {
+                    let target = inflow_target.min(ceiling).max(batch_kg);
+                    if current > 0.0 && (target - current).abs() <= RETARGET_TOLERANCE * current {
+                        continue;
+                    }
+                    target
+                }
+                _ if current == 0.0 => ctx.state.strategy_config.refinery_threshold_kg,
+                _ => continue,
+            };
+            commands.push(make_cmd(
+                ctx.owner,
+                tick,
+                ctx.next_id,
+                Command::SetModuleThreshold {
+                    facility_id: ctx.station_id.clone().into(),
+                    module_id: module.id.clone(),
+                    threshold_kg,
+                },
+            ));
+        }
+        commands
+    }
+}
+
+fn station_ore_kg(station: &StationState) -> f32 {
+    station
+        .core
+        .inventory
+        .iter()
+        .filter(|item| matches!(item, InventoryItem::Ore { .. }))
+        .map(InventoryItem::mass_kg)
+        .sum()
+}
+
+/// Ore the station could hold if all free cargo space filled with ore.
+fn reachable_ore_kg(station: &StationState, ore_kg: f32, content: &GameContent) -> f32 {
+    let used_m3 = station
+        .core
+        .cached_inventory_volume_m3
+        .unwrap_or_else(|| inventory_volume_m3(&station.core.inventory, content));
+    let free_m3 = (station.core.cargo_capacity_m3 - used_m3).max(0.0);
+    let density = content
+        .density_map
+        .get(sim_core::ELEMENT_ORE)
+        .copied()
+        .unwrap_or(0.0);
+    ore_kg + free_m3 * density
+}
+
+/// Per-run ore intake of a processor whose recipes take ore, or `None` for
+/// processors fed by something else (electrolysis, smelting materials).
+fn ore_batch_kg(behavior: &ModuleBehaviorDef, content: &GameContent) -> Option<f32> {
+    let ModuleBehaviorDef::Processor(processor_def) = behavior else {
+        return None;
+    };
+    processor_def
+        .recipes
+        .iter()
+        .filter_map(|recipe_id| content.recipes.get(recipe_id)?.inputs.first())
+        .filter(|input| matches!(input.filter, InputFilter::ItemKind(ItemKind::Ore)))
+        .find_map(|input| match input.amount {
+            InputAmount::Kg(kg) => Some(kg),
+            InputAmount::Count(_) => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inflow_converges_on_steady_arrivals_and_ignores_draws() {
+        let mut inflow = OreInflow::default();
+        let mut ore_kg = 0.0;
+        for tick in 0..=400 {
+            inflow.observe(tick, ore_kg);
+            ore_kg += 100.0;
+        }
+        assert_eq!(inflow.observed_ticks(400), 400);
+        assert!((inflow.rate_kg_per_tick - 100.0).abs() < 1.0);
+
+        // A refinery run draws ore down; that is not negative inflow.
+        inflow.observe(401, 0.0);
+        assert!(inflow.rate_kg_per_tick > 95.0);
+    }
+}
@@ -1,3 +1,4 @@
+mod bootstrap;
 pub(crate) mod concerns;
 mod objectives;
 #[cfg(test)]
@@ -7,9 +8,11 @@ use sim_core::{CommandEnvelope, GameContent, GameState, PrincipalId, StationId};
 
 use super::Agent;
 use super::DecisionRecord;
+use bootstrap::{Bootstrap, BootstrapStep};
 use concerns::{
-    ComponentImport, CrewAssignment, CrewRecruitment, LabAssignment, MaterialExport,
-    ModuleManagement, PropellantManagement, ShipFitting, SlagJettison,
+    ComponentImport, ContractFulfilment, CrewAssignment, CrewRecruitment, LabAssignment,
+    MaterialExport, ModuleManagement, ProcessorThresholds, PropellantManagement, ShipFitting,
+    SlagJettison,
 };
 
 /// Returns true if any enabled module has an unsatisfied crew requirement.
@@ -36,6 +39,9 @@ pub(crate) struct StationContext<'a> {
     pub next_id: &'a mut u64,
     pub trade_import_unlocked: bool,
     pub trade_export_unlocked: bool,
+    /// Current cold-start step; concerns hold back actions it has not
+    /// reached yet.
+    pub bootstrap: BootstrapStep,
     pub decisions: Option<&'a mut Vec<DecisionRecord>>,
 }
 
@@ -56,24 +62,29 @@ pub(crate) trait StationConcern: Send {
 /// Per-station agent that composes ordered concerns.
 ///
 /// Execution order is determined by `default_concerns()`:
-/// modules → labs → crew → recruit → import → slag → exports →
-/// propellant → ship fitting.
+/// modules → processor thresholds → labs → crew → recruit → import → slag → contracts →
+/// exports → propellant → ship fitting. On a fresh station the `Bootstrap` sequence
+/// gates installs, lab assignment and ship objectives (power → refinery →
+/// labs → exploration).
 ///
 /// Created per `StationState`; removed when the station is removed from state.
 pub(crate) struct StationAgent {
     pub(crate) station_id: StationId,
     concerns: Vec<Box<dyn StationConcern>>,
+    bootstrap: Bootstrap,
 }
 
 /// Default concern set for a station agent, in execution order.
 fn default_concerns() -> Vec<Box<dyn StationConcern>> {
     vec![
         Box::new(ModuleManagement),
+        Box::new(ProcessorThresholds::default()),
         Box::new(LabAssignment::default()),
         Box::new(CrewAssignment),
         Box::new(CrewRecruitment),
         Box::new(ComponentImport),
         Box::new(SlagJettison),
+        Box::new(ContractFulfilment),
         Box::new(MaterialExport),
         Box::new(PropellantManagement),
         Box::new(ShipFitting),
@@ -81,10 +92,17 @@ fn default_concerns() -> Vec<Box<dyn StationConcern>> {
 }
 
 impl StationAgent {
+    /// Whether the cold-start bootstrap has reached exploration, so the
+    /// station's ships may be given objectives.
+    pub(crate) fn bootstrapped(&self) -> bool {
+        self.bootstrap.finished()
+    }
+
     pub(crate) fn new(station_id: StationId) -> Self {
         Self {
             station_id,
             concerns: default_concerns(),
+            bootstrap: Bootstrap::new(),
         }
     }
 
@@ -110,6 +128,7 @@ impl StationAgent {
             trade_export_unlocked: state
                 .progression
                 .trade_tier_unlocked(sim_core::TradeTier::Export),
+            bootstrap: BootstrapStep::Exploration,
             decisions: None,
         };
         let mut concern = PropellantManagement;
@@ -130,9 +149,10 @@ impl Agent for StationAgent {
         next_id: &mut u64,
         mut decisions: Option<&mut Vec<DecisionRecord>>,
     ) -> Vec<CommandEnvelope> {
-        if !state.stations.contains_key(&self.station_id) {
+        let Some(station) = state.stations.get(&self.station_id) else {
             return Vec::new();
-        }
+        };
+        let bootstrap = self.bootstrap.advance(station, content, state.meta.tick);
 
         let trade_import_unlocked = state
             .progression
@@ -153,6 +173,7 @@ impl Agent for StationAgent {
                 next_id,
                 trade_import_unlocked,
                 trade_export_unlocked,
+                bootstrap,
                 #[allow(clippy::option_as_ref_deref)] // Need &mut Vec, not &mut [T]
                 decisions: decisions.as_mut().map(|v| &mut **v),
             };
@@ -1,24 +1,29 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
+use sim_core::asteroid_ranking::{
+    asteroid_candidates, AsteroidCandidate, AsteroidQuery, AsteroidSort, CompositionFilter,
+};
 use sim_core::{
-    compute_entity_absolute, AsteroidId, ConcernPriorities, GameContent, GameState, ShipId, SiteId,
-    StationId, TechId,
+    compute_entity_absolute, AbsolutePos, AsteroidId, BodyId, ConcernPriorities, GameContent,
+    GameState, Position, ShipId, SiteId, StationId, TechId,
 };
 
 use crate::agents::ship_agent::ShipAgent;
+use crate::agents::trip_planner::plan_mining_trip;
 use crate::agents::DecisionRecord;
 use crate::behaviors::{
-    collect_deep_scan_candidates, deposit_priority, element_mining_value,
-    should_opportunistic_refuel, station_has_module_with_role, total_element_inventory,
+    collect_deep_scan_candidates, deposit_priority, should_opportunistic_refuel,
+    station_has_module_with_role, total_element_inventory,
 };
 use crate::objectives::ShipObjective;
+use crate::scoring;
 
 use super::StationAgent;
 
 /// Iterator state for ship objective candidate lists.
 struct ObjectiveCandidates<'a> {
     mine: &'a std::slice::Iter<'a, AsteroidId>,
-    site: &'a std::slice::Iter<'a, SiteId>,
+    survey: &'a [SurveyCluster],
     deep_scan: &'a std::slice::Iter<'a, AsteroidId>,
 }
 
@@ -49,10 +54,10 @@ fn log_objective_decision(
             .map(|id| id.0.clone())
             .collect(),
         "Survey" => candidates
-            .site
-            .clone()
+            .survey
+            .iter()
             .take(3)
-            .map(|id| id.0.clone())
+            .map(|cluster| cluster.sites[0].0.clone())
             .collect(),
         _ => candidates
             .deep_scan
@@ -77,39 +82,122 @@ fn log_objective_decision(
         context_json: format!(
             "{{\"mine_remaining\":{},\"survey_remaining\":{},\"deep_scan_remaining\":{}}}",
             candidates.mine.clone().count(),
-            candidates.site.clone().count(),
+            candidates
+                .survey
+                .iter()
+                .map(|cluster| cluster.sites.len())
+                .sum::<usize>(),
             candidates.deep_scan.clone().count(),
         ),
     });
 }
 
-/// Survey sites sorted by distance from reference position (nearest first).
-pub(in crate::agents) fn collect_survey_candidates(
+/// Unsurveyed scan sites at one node, handed to a single ship as a batch.
+pub(in crate::agents) struct SurveyCluster {
+    /// Absolute position of the first site, used to pick the nearest
+    /// cluster for each ship.
+    anchor: AbsolutePos,
+    /// Site ids, nearest to the station first. Never empty.
+    sites: Vec<SiteId>,
+}
+
+/// Scan sites not yet claimed by any ship, grouped by parent body and split
+/// into batches of at most `batch_size`. Within a node, sites are ordered by
+/// distance from `reference_pos`; clusters are ordered by the distance of
+/// their first site (nearest first).
+pub(in crate::agents) fn collect_survey_clusters(
     state: &GameState,
     reference_pos: &sim_core::Position,
-) -> Vec<SiteId> {
+    claimed: &BTreeSet<SiteId>,
+    batch_size: usize,
+) -> Vec<SurveyCluster> {
     if state.scan_sites.is_empty() {
         return Vec::new();
     }
     let ref_abs = compute_entity_absolute(reference_pos, &state.body_cache);
-    let mut decorated: Vec<(u128, SiteId)> = state
-        .scan_sites
-        .iter()
-        .map(|site| {
-            let dist = ref_abs
-                .distance_squared(compute_entity_absolute(&site.position, &state.body_cache));
-            (dist, site.id.clone())
-        })
-        .collect();
-    decorated.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1 .0.cmp(&b.1 .0)));
-    decorated.into_iter().map(|(_, id)| id).collect()
+    let mut by_node: BTreeMap<&BodyId, Vec<(u128, AbsolutePos, SiteId)>> = BTreeMap::new();
+    for site in &state.scan_sites {
+        if claimed.contains(&site.id) {
+            continue;
+        }
+        let abs = compute_entity_absolute(&site.position, &state.body_cache);
+        by_node
+            .entry(&site.position.parent_body)
+            .or_default()
+            .push((ref_abs.distance_squared(abs), abs, site.id.clone()));
+    }
+
+    let mut clusters: Vec<(u128, SurveyCluster)> = Vec::new();
+    for mut sites in by_node.into_values() {
+        sites.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.2 .0.cmp(&b.2 .0)));
+        for chunk in sites.chunks(batch_size.max(1)) {
+            let (dist, anchor, _) = &chunk[0];
+            clusters.push((
+                *dist,
+                SurveyCluster {
+                    anchor: *anchor,
+                    sites: chunk.iter().map(|(_, _, id)| id.clone()).collect(),
+                },
+            ));
+        }
+    }
+    clusters.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then_with(|| a.1.sites[0].0.cmp(&b.1.sites[0].0))
+    });
+    clusters.into_iter().map(|(_, cluster)| cluster).collect()
+}
+
+/// Remove the cluster whose anchor is nearest to `ship_pos` and turn it
+/// into a `Survey` objective for its first site. The remaining sites are
+/// written to `rest` for the ship agent's survey batch.
+fn take_nearest_cluster(
+    clusters: &mut Vec<SurveyCluster>,
+    ship_pos: &sim_core::Position,
+    state: &GameState,
+    rest: &mut Vec<SiteId>,
+) -> Option<ShipObjective> {
+    if clusters.is_empty() {
+        return None;
+    }
+    // Ships at an uncached body fall back to station-distance order.
+    let idx = if state.body_cache.contains_key(&ship_pos.parent_body) {
+        let ship_abs = compute_entity_absolute(ship_pos, &state.body_cache);
+        clusters
+            .iter()
+            .enumerate()
+            .min_by_key(|(idx, cluster)| (ship_abs.distance_squared(cluster.anchor), *idx))
+            .map_or(0, |(idx, _)| idx)
+    } else {
+        0
+    };
+    let mut sites = clusters.remove(idx).sites.into_iter();
+    let site_id = sites.next()?;
+    rest.extend(sites);
+    Some(ShipObjective::Survey { site_id })
 }
 
-/// Mine candidates sorted by mining value (mass * element fraction), descending.
-/// Volatile detection determines which element to prioritize.
+/// Scan sites already targeted by a ship agent, either as the current
+/// `Survey` objective or queued in its survey batch.
+fn claimed_survey_sites(ship_agents: &BTreeMap<ShipId, ShipAgent>) -> BTreeSet<SiteId> {
+    let mut claimed = BTreeSet::new();
+    for agent in ship_agents.values() {
+        if let Some(ShipObjective::Survey { site_id }) = &agent.objective {
+            claimed.insert(site_id.clone());
+        }
+        claimed.extend(agent.survey_batch.iter().cloned());
+    }
+    claimed
+}
+
+/// Mine candidates best first, scored by mining value (mass * element
+/// fraction) against distance from `reference_pos` with
+/// `AutopilotWeights.mine_value`/`mine_distance`. Volatile detection
+/// determines which element to value.
 pub(in crate::agents) fn collect_mine_candidates(
     state: &GameState,
     content: &GameContent,
+    reference_pos: &Position,
 ) -> Vec<AsteroidId> {
     let propellant_role = &content.autopilot.propellant_role;
     let support_role = &content.autopilot.propellant_support_role;
@@ -129,14 +217,71 @@ pub(in crate::agents) fn collect_mine_candidates(
     } else {
         primary_element
     };
-    let mut decorated: Vec<(f32, AsteroidId)> = state
-        .asteroids
-        .values()
-        .filter(|a| a.mass_kg > 0.0 && a.knowledge.composition.is_some())
-        .map(|a| (element_mining_value(a, sort_element), a.id.clone()))
+    let query = AsteroidQuery {
+        composition: CompositionFilter::Known,
+        near: Some(reference_pos.clone()),
+        sort: AsteroidSort::ElementValue(sort_element.clone()),
+        ..AsteroidQuery::default()
+    };
+    let candidates = asteroid_candidates(state, &query);
+    let value = |candidate: &AsteroidCandidate| f64::from(candidate.element_kg.unwrap_or(0.0));
+    #[allow(clippy::cast_precision_loss)]
+    let distance = |candidate: &AsteroidCandidate| candidate.distance_au_um.unwrap_or(0) as f64;
+    let max_value = candidates.iter().map(value).fold(0.0, f64::max);
+    let max_distance = candidates.iter().map(distance).fold(0.0, f64::max);
+    let weights = &content.autopilot.weights;
+    let mut scored: Vec<(AsteroidId, f64)> = candidates
+        .iter()
+        .map(|candidate| {
+            let score = scoring::weighted_score(&[
+                (
+                    weights.mine_value,
+                    scoring::normalized(value(candidate), max_value),
+                ),
+                (
+                    weights.mine_distance,
+                    -scoring::normalized(distance(candidate), max_distance),
+                ),
+            ]);
+            (candidate.id.clone(), score)
+        })
         .collect();
-    decorated.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1 .0.cmp(&b.1 .0)));
-    decorated.into_iter().map(|(_, id)| id).collect()
+    scoring::rank_by_score(&mut scored);
+    scored.into_iter().map(|(id, _)| id).collect()
+}
+
+/// VIO-487: Only consider ships homed to this station (pre-partitioned by
+/// the controller). Filter to idle, unassigned, non-logistics.
+fn assignable_ships(
+    ship_agents: &BTreeMap<ShipId, ShipAgent>,
+    state: &GameState,
+    content: &GameContent,
+    home_ships: &[ShipId],
+) -> Vec<ShipId> {
+    home_ships
+        .iter()
+        .filter(|id| {
+            let Some(ship) = state.ships.get(*id) else {
+                return false;
+            };
+            let is_idle = ship
+                .task
+                .as_ref()
+                .is_none_or(|t| matches!(t.kind, sim_core::TaskKind::Idle));
+            is_idle
+                && ship_agents.get(*id).is_some_and(ShipAgent::is_unassigned)
+                && !crate::behaviors::ship_has_hull_tag(ship, "logistics", content)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Asteroids already on another ship's mining trip.
+fn claimed_trip_asteroids(ship_agents: &BTreeMap<ShipId, ShipAgent>) -> BTreeSet<AsteroidId> {
+    ship_agents
+        .values()
+        .flat_map(|agent| agent.mining_trip.iter().cloned())
+        .collect()
 }
 
 impl StationAgent {
@@ -144,7 +289,10 @@ impl StationAgent {
     ///
     /// Ships can be at any position — the ship agent will generate Transit
     /// tasks to reach assigned targets. Uses shared-iterator deduplication (AD1)
-    /// so no two ships target the same asteroid or scan site.
+    /// so no two ships target the same asteroid. Surveys are assigned per node:
+    /// each ship takes the nearest unclaimed cluster of scan sites and queues
+    /// the rest of it in its survey batch. Mine objectives get a planned
+    /// multi-asteroid trip when one asteroid will not fill the hold.
     ///
     /// Called separately from `generate()` because it mutates ship agents,
     /// not the command buffer.
@@ -161,25 +309,7 @@ impl StationAgent {
             return;
         };
 
-        // VIO-487: Only consider ships homed to this station (pre-partitioned
-        // by the controller). Filter to idle, no current objective, non-logistics.
-        let assignable: Vec<ShipId> = home_ships
-            .iter()
-            .filter(|id| {
-                let Some(ship) = state.ships.get(*id) else {
-                    return false;
-                };
-                let is_idle = ship
-                    .task
-                    .as_ref()
-                    .is_none_or(|t| matches!(t.kind, sim_core::TaskKind::Idle));
-                is_idle
-                    && ship_agents.get(*id).is_some_and(|a| a.objective.is_none())
-                    && !crate::behaviors::ship_has_hull_tag(ship, "logistics", content)
-            })
-            .cloned()
-            .collect();
-
+        let assignable = assignable_ships(ship_agents, state, content, home_ships);
         if assignable.is_empty() {
             return;
         }
@@ -193,11 +323,16 @@ impl StationAgent {
 
         // Pre-compute sorted candidate lists (Schwartzian transforms)
         let deep_scan_candidates = collect_deep_scan_candidates(state, content, reference_pos);
-        let survey_candidates = collect_survey_candidates(state, reference_pos);
-        let mine_candidates = collect_mine_candidates(state, content);
+        let mut survey_clusters = collect_survey_clusters(
+            state,
+            reference_pos,
+            &claimed_survey_sites(ship_agents),
+            content.autopilot.survey_batch_size,
+        );
+        let mine_candidates = collect_mine_candidates(state, content, reference_pos);
+        let mut claimed_asteroids = claimed_trip_asteroids(ship_agents);
 
         let mut next_deep_scan = deep_scan_candidates.iter();
-        let mut next_site = survey_candidates.iter();
         let mut next_mine = mine_candidates.iter();
 
         // Weighted priority halving (DFHack labormanager pattern).
@@ -231,25 +366,34 @@ impl StationAgent {
                 if *weight <= 0.0 {
                     break; // All remaining weights are zero — skip assignment
                 }
+                // Surveys claim a whole node cluster: the first site becomes
+                // the objective, the rest queue on the ship agent.
+                let mut survey_rest = Vec::new();
                 let objective = match priority {
-                    "Mine" => next_mine.next().map(|id| ShipObjective::Mine {
-                        asteroid_id: id.clone(),
-                    }),
+                    // Ship classes with no mining rate are never sent to mine.
+                    "Mine" if sim_core::mining_rate_kg_per_tick(ship, content) > 0.0 => next_mine
+                        .find(|id| !claimed_asteroids.contains(*id))
+                        .map(|id| ShipObjective::Mine {
+                            asteroid_id: id.clone(),
+                        }),
                     "DeepScan" if deep_scan_unlocked => {
                         next_deep_scan.next().map(|id| ShipObjective::DeepScan {
                             asteroid_id: id.clone(),
                         })
                     }
-                    "Survey" => next_site.next().map(|id| ShipObjective::Survey {
-                        site_id: id.clone(),
-                    }),
+                    "Survey" => take_nearest_cluster(
+                        &mut survey_clusters,
+                        &ship.position,
+                        state,
+                        &mut survey_rest,
+                    ),
                     _ => None,
                 };
                 if let Some(obj) = objective {
                     if let Some(ref mut log) = decisions {
                         let cands = ObjectiveCandidates {
                             mine: &next_mine,
-                            site: &next_site,
+                            survey: &survey_clusters,
                             deep_scan: &next_deep_scan,
                         };
                         log_objective_decision(
@@ -262,7 +406,18 @@ impl StationAgent {
                         );
                     }
                     if let Some(agent) = ship_agents.get_mut(&ship_id) {
+                        if let ShipObjective::Mine { asteroid_id } = &obj {
+                            agent.mining_trip = plan_mining_trip(
+                                ship,
+                                asteroid_id,
+                                state,
+                                content,
+                                &claimed_asteroids,
+                            );
+                            claimed_asteroids.extend(agent.mining_trip.iter().cloned());
+                        }
                         agent.objective = Some(obj);
+                        agent.survey_batch = survey_rest.into();
                     }
                     *weight *= 0.5; // Halve after assignment
                     break;
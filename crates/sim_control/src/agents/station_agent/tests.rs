@@ -7,28 +7,33 @@ use sim_core::{
 };
 
 use crate::agents::ship_agent::ShipAgent;
+use crate::agents::station_agent::BootstrapStep;
 use crate::agents::Agent;
 use crate::objectives::ShipObjective;
 
-use super::concerns::{CrewRecruitment, ModuleManagement, SlagJettison};
+use super::concerns::{
+    ContractFulfilment, CrewRecruitment, ModuleManagement, ProcessorThresholds, SlagJettison,
+};
 use super::{StationAgent, StationConcern, StationContext};
 
 #[test]
 fn new_agent_has_default_concerns() {
     let agent = StationAgent::new(StationId("test_station".to_string()));
     assert_eq!(agent.station_id, StationId("test_station".to_string()));
-    assert_eq!(agent.concerns.len(), 9);
+    assert_eq!(agent.concerns.len(), 11);
     // Verify concern ordering matches expected sequence
     let names: Vec<&str> = agent.concerns.iter().map(|c| c.name()).collect();
     assert_eq!(
         names,
         vec![
             "module_management",
+            "processor_thresholds",
             "lab_assignment",
             "crew_assignment",
             "crew_recruitment",
             "component_import",
             "slag_jettison",
+            "contract_fulfilment",
             "material_export",
             "propellant_management",
             "ship_fitting",
@@ -79,6 +84,7 @@ fn manage_modules_installs_from_inventory() {
         .push(InventoryItem::Module {
             item_id: sim_core::ModuleItemId("item_1".to_string()),
             module_def_id: "mod_def_test".to_string(),
+            wear: 0.0,
         });
 
     let mut concern = ModuleManagement;
@@ -92,6 +98,7 @@ fn manage_modules_installs_from_inventory() {
         trade_import_unlocked: false,
         trade_export_unlocked: false,
         decisions: None,
+        bootstrap: BootstrapStep::Exploration,
     };
 
     let commands = concern.generate(&mut ctx);
@@ -134,6 +141,7 @@ fn jettison_slag_fires_above_threshold() {
         trade_import_unlocked: false,
         trade_export_unlocked: false,
         decisions: None,
+        bootstrap: BootstrapStep::Exploration,
     };
 
     let commands = concern.generate(&mut ctx);
@@ -145,6 +153,72 @@ fn jettison_slag_fires_above_threshold() {
     ));
 }
 
+#[test]
+fn contract_fulfilment_takes_only_offers_in_stock() {
+    let content = base_content();
+    let mut state = base_state(&content);
+    let station_id = state.stations.keys().next().unwrap().clone();
+    let owner = state.stations[&station_id].owner.clone();
+    state
+        .stations
+        .get_mut(&station_id)
+        .unwrap()
+        .core
+        .inventory
+        .push(InventoryItem::Material {
+            element: "Fe".to_string(),
+            kg: 1000.0,
+            quality: 0.8,
+            thermal: None,
+        });
+    let offer = |id: u64, element: &str, kg: f32| sim_core::Contract {
+        id: sim_core::ContractId(id),
+        kind: sim_core::ContractKind::Deliver {
+            element: element.to_string(),
+            kg,
+        },
+        reward: 1.0,
+        penalty: 0.0,
+        offered_tick: 0,
+        deadline_tick: 100,
+        holder: None,
+        progress: 0,
+    };
+    // The second Fe offer no longer fits once the first is taken.
+    state.contracts = vec![
+        offer(0, "Fe", 600.0),
+        offer(1, "Fe", 600.0),
+        offer(2, "Si", 1.0),
+    ];
+
+    let mut concern = ContractFulfilment;
+    let mut next_id = 1;
+    let mut ctx = StationContext {
+        station_id: &station_id,
+        state: &state,
+        content: &content,
+        owner: &owner,
+        next_id: &mut next_id,
+        trade_import_unlocked: false,
+        trade_export_unlocked: true,
+        decisions: None,
+        bootstrap: BootstrapStep::Exploration,
+    };
+
+    let commands = concern.generate(&mut ctx);
+    assert_eq!(commands.len(), 1);
+    assert!(matches!(
+        commands[0].command,
+        Command::AcceptContract {
+            contract_id: sim_core::ContractId(0)
+        }
+    ));
+    assert!(
+        (super::concerns::contract_reserve_kg(&ctx, "Fe") - 600.0).abs() < f32::EPSILON,
+        "material for the taken offer is kept from export"
+    );
+}
+
 #[test]
 fn recruit_crew_skips_when_salary_would_bankrupt() {
     use sim_core::test_fixtures::ModuleDefBuilder;
@@ -168,6 +242,8 @@ fn recruit_crew_skips_when_salary_would_bankrupt() {
             importable: true,
             exportable: false,
             category: String::new(),
+            royalty_per_unit: 0.0,
+            elasticity: 0.0,
         },
     );
     // Module def requiring an engineer
@@ -212,6 +288,7 @@ fn recruit_crew_skips_when_salary_would_bankrupt() {
         trade_import_unlocked: true,
         trade_export_unlocked: true,
         decisions: None,
+        bootstrap: BootstrapStep::Exploration,
     };
 
     let commands = concern.generate(&mut ctx);
@@ -259,10 +336,12 @@ fn sf06_framed_setup() -> (sim_core::GameContent, sim_core::GameState, StationId
                 SlotDef {
                     slot_type: SlotType("industrial".to_string()),
                     label: "I1".to_string(),
+                    adjacent: Vec::new(),
                 },
                 SlotDef {
                     slot_type: SlotType("research".to_string()),
                     label: "R1".to_string(),
+                    adjacent: Vec::new(),
                 },
             ],
             bonuses: vec![],
@@ -285,6 +364,7 @@ fn manage_modules_framed_picks_compatible_slot() {
     station.core.inventory.push(InventoryItem::Module {
         item_id: sim_core::ModuleItemId("item_ind".to_string()),
         module_def_id: "sf06_industrial".to_string(),
+        wear: 0.0,
     });
 
     let owner = PrincipalId("principal_autopilot".to_string());
@@ -299,6 +379,7 @@ fn manage_modules_framed_picks_compatible_slot() {
         trade_import_unlocked: false,
         trade_export_unlocked: false,
         decisions: None,
+        bootstrap: BootstrapStep::Exploration,
     };
     let commands = concern.generate(&mut ctx);
 
@@ -335,6 +416,7 @@ fn manage_modules_framed_skips_when_no_compatible_slot() {
     station.core.inventory.push(InventoryItem::Module {
         item_id: sim_core::ModuleItemId("item_research".to_string()),
         module_def_id: "sf06_research".to_string(),
+        wear: 0.0,
     });
 
     let owner = PrincipalId("principal_autopilot".to_string());
@@ -349,6 +431,7 @@ fn manage_modules_framed_skips_when_no_compatible_slot() {
         trade_import_unlocked: false,
         trade_export_unlocked: false,
         decisions: None,
+        bootstrap: BootstrapStep::Exploration,
     };
     let commands = concern.generate(&mut ctx);
 
@@ -370,10 +453,12 @@ fn manage_modules_framed_avoids_double_booking_same_tick() {
     station.core.inventory.push(InventoryItem::Module {
         item_id: sim_core::ModuleItemId("item_ind_a".to_string()),
         module_def_id: "sf06_industrial".to_string(),
+        wear: 0.0,
     });
     station.core.inventory.push(InventoryItem::Module {
         item_id: sim_core::ModuleItemId("item_ind_b".to_string()),
         module_def_id: "sf06_industrial".to_string(),
+        wear: 0.0,
     });
 
     let owner = PrincipalId("principal_autopilot".to_string());
@@ -388,6 +473,7 @@ fn manage_modules_framed_avoids_double_booking_same_tick() {
         trade_import_unlocked: false,
         trade_export_unlocked: false,
         decisions: None,
+        bootstrap: BootstrapStep::Exploration,
     };
     let commands = concern.generate(&mut ctx);
 
@@ -449,6 +535,10 @@ fn add_idle_ship(
         crew: std::collections::BTreeMap::new(),
         leaders: vec![],
         home_station: state.stations.keys().next().cloned(),
+        name: String::new(),
+        class_id: None,
+        role: None,
+        wear: sim_core::WearState::default(),
     };
     state.ships.insert(ship_id.clone(), ship);
     agents.insert(ship_id.clone(), ShipAgent::new(ship_id));
@@ -479,7 +569,11 @@ fn add_mineable_asteroid(
                     composition.insert("Fe".to_string(), fe_fraction);
                     composition
                 }),
+                layers: None,
+                composition_sigma: None,
+                deep_scan_count: 0,
             },
+            layers: None,
         },
     );
 }
@@ -509,7 +603,9 @@ fn assign_no_idle_ships_no_assignments() {
 
 #[test]
 fn assign_two_ships_two_asteroids_no_double_assignment() {
-    let (mut state, content, mut ship_agents) = assignment_setup();
+    let (mut state, mut content, mut ship_agents) = assignment_setup();
+    // One asteroid per ship; trip planning would chain both onto ship_a.
+    content.autopilot.mining_trip_max_stops = 1;
     let station_id = station_id_from_state(&state);
 
     let ship_a = make_ship_id("ship_a");
@@ -563,6 +659,46 @@ fn assign_two_ships_two_asteroids_no_double_assignment() {
     assert_eq!(id_b, asteroid_2);
 }
 
+#[test]
+fn assign_mine_trip_claims_follow_on_asteroids() {
+    let (mut state, content, mut ship_agents) = assignment_setup();
+    let station_id = station_id_from_state(&state);
+
+    let ship_a = make_ship_id("ship_a");
+    let ship_b = make_ship_id("ship_b");
+    add_idle_ship(&mut state, &mut ship_agents, ship_a.clone());
+    add_idle_ship(&mut state, &mut ship_agents, ship_b.clone());
+    add_mineable_asteroid(&mut state, make_asteroid_id("asteroid_1"), 0.8);
+    add_mineable_asteroid(&mut state, make_asteroid_id("asteroid_2"), 0.5);
+
+    let priorities = ConcernPriorities {
+        mining: 1.0,
+        survey: 0.0,
+        deep_scan: 0.0,
+        ..ConcernPriorities::default()
+    };
+    let agent = StationAgent::new(station_id);
+    let home_ships = all_ship_ids(&ship_agents);
+    agent.assign_ship_objectives(
+        &mut ship_agents,
+        &state,
+        &content,
+        &priorities,
+        &home_ships,
+        None,
+    );
+
+    // Two 1000 kg rocks don't fill ship_a's hold, so both go on its trip
+    // and ship_b is left for other work.
+    let trip: Vec<&str> = ship_agents[&ship_a]
+        .mining_trip
+        .iter()
+        .map(|id| id.0.as_str())
+        .collect();
+    assert_eq!(trip, vec!["asteroid_1", "asteroid_2"]);
+    assert!(ship_agents[&ship_b].objective.is_none());
+}
+
 #[test]
 fn assign_ship_with_cargo_skipped_no_iterator_consumption() {
     let (mut state, content, mut ship_agents) = assignment_setup();
@@ -611,6 +747,52 @@ fn assign_ship_with_cargo_skipped_no_iterator_consumption() {
     ));
 }
 
+#[test]
+fn assign_skips_mining_for_ship_class_that_cannot_mine() {
+    let (mut state, mut content, mut ship_agents) = assignment_setup();
+    let station_id = station_id_from_state(&state);
+    content.ship_classes.insert(
+        "class_courier".to_string(),
+        sim_core::ShipClassDef {
+            id: "class_courier".to_string(),
+            name: "Courier".to_string(),
+            cargo_capacity_m3: None,
+            propellant_capacity_kg: None,
+            speed_multiplier: 1.0,
+            mining_rate_multiplier: 0.0,
+        },
+    );
+
+    let ship_a = make_ship_id("ship_a");
+    let ship_b = make_ship_id("ship_b");
+    add_idle_ship(&mut state, &mut ship_agents, ship_a.clone());
+    add_idle_ship(&mut state, &mut ship_agents, ship_b.clone());
+    state.ships.get_mut(&ship_a).unwrap().class_id = Some("class_courier".to_string());
+
+    let asteroid_1 = make_asteroid_id("asteroid_1");
+    add_mineable_asteroid(&mut state, asteroid_1.clone(), 0.8);
+
+    let agent = StationAgent::new(station_id);
+    let home_ships = all_ship_ids(&ship_agents);
+    agent.assign_ship_objectives(
+        &mut ship_agents,
+        &state,
+        &content,
+        &ConcernPriorities::default(),
+        &home_ships,
+        None,
+    );
+
+    assert!(!matches!(
+        ship_agents[&ship_a].objective,
+        Some(ShipObjective::Mine { .. })
+    ));
+    assert!(matches!(
+        ship_agents[&ship_b].objective,
+        Some(ShipObjective::Mine { ref asteroid_id }) if *asteroid_id == asteroid_1
+    ));
+}
+
 #[test]
 fn assign_busy_ship_not_assigned() {
     let (mut state, content, mut ship_agents) = assignment_setup();
@@ -673,7 +855,11 @@ fn assign_deep_scan_when_tech_unlocked() {
             knowledge: AsteroidKnowledge {
                 tag_beliefs: vec![(sim_core::AnomalyTag("IronRich".to_string()), 0.9)],
                 composition: None,
+                layers: None,
+                composition_sigma: None,
+                deep_scan_count: 0,
             },
+            layers: None,
         },
     );
 
@@ -720,7 +906,11 @@ fn assign_deep_scan_skipped_without_tech() {
             knowledge: AsteroidKnowledge {
                 tag_beliefs: vec![(sim_core::AnomalyTag("IronRich".to_string()), 0.9)],
                 composition: None,
+                layers: None,
+                composition_sigma: None,
+                deep_scan_count: 0,
             },
+            layers: None,
         },
     );
 
@@ -877,6 +1067,85 @@ fn assign_survey_when_no_mine_candidates() {
     ));
 }
 
+#[test]
+fn assign_survey_batches_sites_at_same_node() {
+    let (mut state, mut content, mut ship_agents) = assignment_setup();
+    content.autopilot.survey_batch_size = 3;
+    let station_id = station_id_from_state(&state);
+
+    let ship_a = make_ship_id("ship_a");
+    let ship_b = make_ship_id("ship_b");
+    add_idle_ship(&mut state, &mut ship_agents, ship_a.clone());
+    add_idle_ship(&mut state, &mut ship_agents, ship_b.clone());
+
+    state.scan_sites.clear();
+    for idx in 1..=4 {
+        state.scan_sites.push(sim_core::ScanSite {
+            id: sim_core::SiteId(format!("site_{idx}")),
+            position: test_position(),
+            template_id: "template_default".to_string(),
+        });
+    }
+
+    let agent = StationAgent::new(station_id);
+    let home_ships = all_ship_ids(&ship_agents);
+    agent.assign_ship_objectives(
+        &mut ship_agents,
+        &state,
+        &content,
+        &ConcernPriorities::default(),
+        &home_ships,
+        None,
+    );
+
+    // ship_a claims a full batch at the node; ship_b gets the remainder.
+    let claimed = |id: &ShipId| {
+        let agent = &ship_agents[id];
+        let mut sites: Vec<String> = agent.survey_batch.iter().map(|s| s.0.clone()).collect();
+        if let Some(ShipObjective::Survey { site_id }) = &agent.objective {
+            sites.insert(0, site_id.0.clone());
+        }
+        sites
+    };
+    assert_eq!(claimed(&ship_a), vec!["site_1", "site_2", "site_3"]);
+    assert_eq!(claimed(&ship_b), vec!["site_4"]);
+}
+
+#[test]
+fn assign_survey_skips_sites_queued_on_other_ships() {
+    let (mut state, content, mut ship_agents) = assignment_setup();
+    let station_id = station_id_from_state(&state);
+
+    let busy = make_ship_id("ship_busy");
+    let idle = make_ship_id("ship_idle");
+    add_idle_ship(&mut state, &mut ship_agents, busy.clone());
+    add_idle_ship(&mut state, &mut ship_agents, idle.clone());
+
+    state.scan_sites.clear();
+    state.scan_sites.push(sim_core::ScanSite {
+        id: sim_core::SiteId("site_1".to_string()),
+        position: test_position(),
+        template_id: "template_default".to_string(),
+    });
+    ship_agents
+        .get_mut(&busy)
+        .unwrap()
+        .survey_batch
+        .push_back(sim_core::SiteId("site_1".to_string()));
+
+    let agent = StationAgent::new(station_id);
+    agent.assign_ship_objectives(
+        &mut ship_agents,
+        &state,
+        &content,
+        &ConcernPriorities::default(),
+        std::slice::from_ref(&idle),
+        None,
+    );
+
+    assert!(ship_agents[&idle].objective.is_none());
+}
+
 #[test]
 fn assign_no_candidates_no_objective() {
     let (mut state, content, mut ship_agents) = assignment_setup();
@@ -922,6 +1191,7 @@ fn manage_modules_sheds_load_during_power_deficit() {
                     assembly_interval_ticks: 1,
                     max_stock: HashMap::new(),
                     recipes: vec![],
+                    parallel_slots: 1,
                 },
             ))
             .build(),
@@ -940,6 +1210,7 @@ fn manage_modules_sheds_load_during_power_deficit() {
                     assembly_interval_ticks: 1,
                     max_stock: HashMap::new(),
                     recipes: vec![],
+                    parallel_slots: 1,
                 },
             ))
             .build(),
@@ -961,6 +1232,9 @@ fn manage_modules_sheds_load_during_power_deficit() {
             capped: false,
             cap_override: HashMap::new(),
             selected_recipe: None,
+            extra_slots: Vec::new(),
+            job: None,
+            queue: Vec::new(),
         }),
         wear: sim_core::WearState::default(),
         thermal: None,
@@ -981,6 +1255,9 @@ fn manage_modules_sheds_load_during_power_deficit() {
             capped: false,
             cap_override: HashMap::new(),
             selected_recipe: None,
+            extra_slots: Vec::new(),
+            job: None,
+            queue: Vec::new(),
         }),
         wear: sim_core::WearState::default(),
         thermal: None,
@@ -1012,6 +1289,7 @@ fn manage_modules_sheds_load_during_power_deficit() {
         trade_import_unlocked: false,
         trade_export_unlocked: false,
         decisions: None,
+        bootstrap: BootstrapStep::Exploration,
     };
 
     let commands = concern.generate(&mut ctx);
@@ -1076,3 +1354,144 @@ fn decision_logging_captures_ship_objective() {
     assert_eq!(decisions[0].decision_type, "assign_mine");
     assert_eq!(decisions[0].chosen_id, "asteroid_1");
 }
+
+// --- Processor thresholds -----------------------------------------------
+
+/// Base state with one ore refinery (1,000 kg per run) at threshold
+/// 2,000 kg.
+fn refinery_station() -> (sim_core::GameContent, sim_core::GameState) {
+    use sim_core::test_fixtures::{insert_recipe, test_iron_recipe, test_module, ModuleDefBuilder};
+
+    let mut content = base_content();
+    let recipe_id = insert_recipe(&mut content, test_iron_recipe());
+    let def = ModuleDefBuilder::new("module_test_refinery")
+        .behavior(sim_core::ModuleBehaviorDef::Processor(
+            sim_core::ProcessorDef {
+                processing_interval_minutes: 1,
+                processing_interval_ticks: 1,
+                recipes: vec![recipe_id],
+            },
+        ))
+        .build();
+    content.module_defs.insert(def.id.clone(), def);
+    let mut state = base_state(&content);
+    let station = state.stations.values_mut().next().unwrap();
+    station.core.modules.push(test_module(
+        "module_test_refinery",
+        sim_core::ModuleKindState::Processor(sim_core::ProcessorState {
+            threshold_kg: 2000.0,
+            ticks_since_last_run: 0,
+            stalled: false,
+            selected_recipe: None,
+        }),
+    ));
+    (content, state)
+}
+
+/// Drive `ProcessorThresholds` for ticks `0..=ticks` with `inflow_kg` of
+/// ore arriving after each pass, applying every threshold it issues.
+/// Returns the refinery's final threshold.
+fn run_processor_thresholds(
+    content: &sim_core::GameContent,
+    state: &mut sim_core::GameState,
+    ticks: u64,
+    inflow_kg: f32,
+) -> f32 {
+    let owner = PrincipalId("principal_autopilot".to_string());
+    let station_id = state.stations.keys().next().unwrap().clone();
+    let mut concern = ProcessorThresholds::default();
+    let mut next_id = 1;
+    for tick in 0..=ticks {
+        state.meta.tick = tick;
+        let mut ctx = StationContext {
+            station_id: &station_id,
+            state,
+            content,
+            owner: &owner,
+            next_id: &mut next_id,
+            trade_import_unlocked: false,
+            trade_export_unlocked: false,
+            decisions: None,
+            bootstrap: BootstrapStep::Exploration,
+        };
+        let commands = concern.generate(&mut ctx);
+        let station = state.stations.get_mut(&station_id).unwrap();
+        for command in commands {
+            if let Command::SetModuleThreshold { threshold_kg, .. } = command.command {
+                if let sim_core::ModuleKindState::Processor(processor) =
+                    &mut station.core.modules[0].kind_state
+                {
+                    processor.threshold_kg = threshold_kg;
+                }
+            }
+        }
+        if inflow_kg > 0.0 {
+            station.core.inventory.push(InventoryItem::Ore {
+                lot_id: LotId(format!("lot_inflow_{tick}")),
+                asteroid_id: AsteroidId("asteroid_test".to_string()),
+                kg: inflow_kg,
+                composition: HashMap::from([("Fe".to_string(), 1.0)]),
+            });
+        }
+    }
+    match &state.stations[&station_id].core.modules[0].kind_state {
+        sim_core::ModuleKindState::Processor(processor) => processor.threshold_kg,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn processor_threshold_falls_to_one_batch_without_inflow() {
+    let (content, mut state) = refinery_station();
+    let station = state.stations.values_mut().next().unwrap();
+    station.core.inventory.push(InventoryItem::Ore {
+        lot_id: LotId("lot_leftover".to_string()),
+        asteroid_id: AsteroidId("asteroid_test".to_string()),
+        kg: 1500.0,
+        composition: HashMap::from([("Fe".to_string(), 1.0)]),
+    });
+
+    let threshold = run_processor_thresholds(&content, &mut state, 24, 0.0);
+
+    // 1,500 kg of leftover ore can no longer wait for 2,000 kg.
+    assert!((threshold - 1000.0).abs() < 1e-3, "threshold {threshold}");
+}
+
+#[test]
+fn processor_threshold_rises_with_ore_inflow() {
+    let (content, mut state) = refinery_station();
+
+    let threshold = run_processor_thresholds(&content, &mut state, 200, 500.0);
+
+    // ~500 kg/tick over a 24-tick batch window.
+    assert!(threshold > 9_000.0, "threshold {threshold}");
+    assert!(threshold <= 12_000.0, "threshold {threshold}");
+}
+
+#[test]
+fn processor_threshold_capped_by_storage_headroom() {
+    let (content, mut state) = refinery_station();
+    state.strategy_config.refinery_batch_ticks = 200;
+    // 40 m³ of ore-only storage holds 120,000 kg; half of it is the cap.
+    state
+        .stations
+        .values_mut()
+        .next()
+        .unwrap()
+        .core
+        .cargo_capacity_m3 = 40.0;
+
+    let threshold = run_processor_thresholds(&content, &mut state, 200, 500.0);
+
+    assert!((threshold - 60_000.0).abs() < 1.0, "threshold {threshold}");
+}
+
+#[test]
+fn processor_threshold_fixed_when_batch_ticks_zero() {
+    let (content, mut state) = refinery_station();
+    state.strategy_config.refinery_batch_ticks = 0;
+
+    let threshold = run_processor_thresholds(&content, &mut state, 100, 500.0);
+
+    assert!((threshold - 2000.0).abs() < 1e-3);
+}
@@ -0,0 +1,228 @@
+//! Multi-asteroid mining trips.
+//!
+//! A single Mine task stops when the asteroid is depleted, so small rocks
+//! send ships home with a part-empty hold. When the station agent assigns a
+//! Mine objective, the planner chains further known asteroids at the same
+//! node until the projected ore volume fills the hold (or the space ore may
+//! use in a partitioned hold). The ship agent works through the legs and
+//! only deposits once the hold is full or the plan is exhausted.
+
+use std::collections::{BTreeSet, VecDeque};
+
+use sim_core::{
+    compute_entity_absolute, AsteroidId, AsteroidState, GameContent, GameState, HoldPartition,
+    HoldSpace, ShipState,
+};
+
+/// Ore volume per kg, from the content density map.
+fn ore_m3_per_kg(content: &GameContent) -> f32 {
+    content
+        .density_map
+        .get(sim_core::ELEMENT_ORE)
+        .map_or(0.0, |density| 1.0 / density)
+}
+
+/// Free ore bay volume (the whole free hold when it is undivided).
+fn ore_free_m3(ship: &ShipState, content: &GameContent) -> f32 {
+    HoldSpace::of_ship(ship, content).free_for(HoldPartition::OreBay)
+}
+
+/// True when the hold can take at least one more tick of mining.
+pub(crate) fn hold_has_room(ship: &ShipState, content: &GameContent) -> bool {
+    ore_free_m3(ship, content)
+        >= sim_core::mining_rate_kg_per_tick(ship, content) * ore_m3_per_kg(content)
+}
+
+/// Plan follow-on legs after `first` for a ship about to mine it.
+///
+/// Candidates are known, non-depleted asteroids at the same parent body,
+/// not in `claimed`, taken nearest-to-previous-leg first. Stops when the
+/// projected hold volume reaches capacity or the trip has
+/// `autopilot.mining_trip_max_stops` legs. Returns the whole trip with
+/// `first` at the front, or an empty trip when one asteroid already fills
+/// the hold.
+pub(crate) fn plan_mining_trip(
+    ship: &ShipState,
+    first: &AsteroidId,
+    state: &GameState,
+    content: &GameContent,
+    claimed: &BTreeSet<AsteroidId>,
+) -> VecDeque<AsteroidId> {
+    let max_stops = content.autopilot.mining_trip_max_stops;
+    let m3_per_kg = ore_m3_per_kg(content);
+    let Some(first_asteroid) = state.asteroids.get(first) else {
+        return VecDeque::new();
+    };
+    if max_stops <= 1 || m3_per_kg <= 0.0 {
+        return VecDeque::new();
+    }
+
+    let free_m3 = ore_free_m3(ship, content);
+    let mut projected_m3 = first_asteroid.mass_kg * m3_per_kg;
+    let mut legs = Vec::new();
+    let mut last = first_asteroid;
+    while projected_m3 < free_m3 && legs.len() + 1 < max_stops {
+        let Some(next) = nearest_leg(last, first, &legs, state, claimed) else {
+            break;
+        };
+        projected_m3 += next.mass_kg * m3_per_kg;
+        legs.push(next.id.clone());
+        last = next;
+    }
+    if legs.is_empty() {
+        return VecDeque::new();
+    }
+    std::iter::once(first.clone()).chain(legs).collect()
+}
+
+/// Nearest eligible asteroid to `from` at the same node.
+fn nearest_leg<'a>(
+    from: &AsteroidState,
+    first: &AsteroidId,
+    legs: &[AsteroidId],
+    state: &'a GameState,
+    claimed: &BTreeSet<AsteroidId>,
+) -> Option<&'a AsteroidState> {
+    // Legs share `from`'s body, so an uncached body just falls back to id order.
+    let from_abs = state
+        .body_cache
+        .contains_key(&from.position.parent_body)
+        .then(|| compute_entity_absolute(&from.position, &state.body_cache));
+    state
+        .asteroids
+        .values()
+        .filter(|a| {
+            a.mass_kg > 0.0
+                && a.knowledge.composition.is_some()
+                && a.position.parent_body == from.position.parent_body
+                && a.id != *first
+                && !legs.contains(&a.id)
+                && !claimed.contains(&a.id)
+        })
+        .min_by_key(|a| {
+            let dist = from_abs.map_or(0, |abs| {
+                abs.distance_squared(compute_entity_absolute(&a.position, &state.body_cache))
+            });
+            (dist, a.id.0.clone())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim_core::test_fixtures::{base_content, base_state, test_position};
+    use sim_core::{AsteroidKnowledge, HullId, PrincipalId, ShipId};
+
+    fn small_hold_ship(cargo_capacity_m3: f32) -> ShipState {
+        ShipState {
+            id: ShipId("ship_trip".to_string()),
+            owner: PrincipalId("principal_autopilot".to_string()),
+            position: test_position(),
+            inventory: vec![],
+            task: None,
+            hull_id: HullId("hull_general_purpose".to_string()),
+            fitted_modules: vec![],
+            modifiers: sim_core::modifiers::ModifierSet::default(),
+            propellant_kg: 0.0,
+            propellant_capacity_kg: 0.0,
+            cargo_capacity_m3,
+            speed_ticks_per_au: None,
+            crew: std::collections::BTreeMap::new(),
+            leaders: vec![],
+            home_station: None,
+            name: String::new(),
+            class_id: None,
+            role: None,
+            wear: sim_core::WearState::default(),
+        }
+    }
+
+    fn add_known_asteroid(state: &mut GameState, id: &str, mass_kg: f32) {
+        let asteroid_id = AsteroidId(id.to_string());
+        state.asteroids.insert(
+            asteroid_id.clone(),
+            AsteroidState {
+                id: asteroid_id,
+                position: test_position(),
+                true_composition: std::collections::HashMap::new(),
+                anomaly_tags: vec![],
+                mass_kg,
+                knowledge: AsteroidKnowledge {
+                    tag_beliefs: vec![],
+                    composition: Some(std::collections::HashMap::from([("Fe".to_string(), 0.8)])),
+                    layers: None,
+                    composition_sigma: None,
+                    deep_scan_count: 0,
+                },
+                layers: None,
+            },
+        );
+    }
+
+    #[test]
+    fn small_asteroids_chain_until_hold_full() {
+        let content = base_content();
+        let mut state = base_state(&content);
+        state.asteroids.clear();
+        for id in ["asteroid_a", "asteroid_b", "asteroid_c"] {
+            add_known_asteroid(&mut state, id, 1_000.0);
+        }
+        // Ore is 3000 kg/m³: a 0.5 m³ hold takes 1500 kg, i.e. two 1000 kg rocks.
+        let ship = small_hold_ship(0.5);
+
+        let trip = plan_mining_trip(
+            &ship,
+            &AsteroidId("asteroid_a".to_string()),
+            &state,
+            &content,
+            &BTreeSet::new(),
+        );
+
+        let ids: Vec<&str> = trip.iter().map(|id| id.0.as_str()).collect();
+        assert_eq!(ids, vec!["asteroid_a", "asteroid_b"]);
+    }
+
+    #[test]
+    fn trip_skips_claimed_asteroids_and_respects_stop_limit() {
+        let mut content = base_content();
+        content.autopilot.mining_trip_max_stops = 2;
+        let mut state = base_state(&content);
+        state.asteroids.clear();
+        for id in ["asteroid_a", "asteroid_b", "asteroid_c", "asteroid_d"] {
+            add_known_asteroid(&mut state, id, 100.0);
+        }
+        let ship = small_hold_ship(10.0);
+        let claimed = BTreeSet::from([AsteroidId("asteroid_b".to_string())]);
+
+        let trip = plan_mining_trip(
+            &ship,
+            &AsteroidId("asteroid_a".to_string()),
+            &state,
+            &content,
+            &claimed,
+        );
+
+        let ids: Vec<&str> = trip.iter().map(|id| id.0.as_str()).collect();
+        assert_eq!(ids, vec!["asteroid_a", "asteroid_c"]);
+    }
+
+    #[test]
+    fn asteroid_that_fills_hold_needs_no_trip() {
+        let content = base_content();
+        let mut state = base_state(&content);
+        state.asteroids.clear();
+        add_known_asteroid(&mut state, "asteroid_big", 50_000.0);
+        add_known_asteroid(&mut state, "asteroid_small", 1_000.0);
+        let ship = small_hold_ship(1.0);
+
+        let trip = plan_mining_trip(
+            &ship,
+            &AsteroidId("asteroid_big".to_string()),
+            &state,
+            &content,
+            &BTreeSet::new(),
+        );
+
+        assert!(trip.is_empty());
+    }
+}
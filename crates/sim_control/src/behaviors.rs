@@ -1,7 +1,10 @@
+use sim_core::asteroid_ranking::{
+    asteroid_candidates, AsteroidQuery, AsteroidSort, CompositionFilter,
+};
 use sim_core::{
-    compute_entity_absolute, is_co_located, travel_ticks, AsteroidId, AsteroidState, Command,
-    CommandEnvelope, CommandId, ComponentId, DomainProgress, GameContent, GameState, InventoryItem,
-    Position, PrincipalId, ShipId, ShipState, StationState, TaskKind, TechDef, TradeItemSpec,
+    compute_entity_absolute, is_co_located, travel_ticks, AsteroidId, Command, CommandEnvelope,
+    CommandId, ComponentId, DomainProgress, GameContent, GameState, InventoryItem, Position,
+    PrincipalId, ShipId, ShipState, StationState, TaskKind, TechDef, TradeItemSpec,
 };
 
 pub(crate) const AUTOPILOT_OWNER: &str = "principal_autopilot";
@@ -175,30 +178,28 @@ pub(crate) fn collect_deep_scan_candidates(
     content: &GameContent,
     reference_pos: &Position,
 ) -> Vec<AsteroidId> {
-    if state.asteroids.is_empty() {
-        return Vec::new();
-    }
     let targets = &content.autopilot.deep_scan_targets;
-    let ref_abs = compute_entity_absolute(reference_pos, &state.body_cache);
-    let mut candidates: Vec<(AsteroidId, u128)> = state
-        .asteroids
-        .values()
-        .filter(|asteroid| {
-            asteroid.knowledge.composition.is_none()
-                && asteroid.knowledge.tag_beliefs.iter().any(|(tag, conf)| {
+    let query = AsteroidQuery {
+        composition: CompositionFilter::Unknown,
+        near: Some(reference_pos.clone()),
+        sort: AsteroidSort::Distance,
+        ..AsteroidQuery::default()
+    };
+    asteroid_candidates(state, &query)
+        .into_iter()
+        .filter(|candidate| {
+            state.asteroids[&candidate.id]
+                .knowledge
+                .tag_beliefs
+                .iter()
+                .any(|(tag, conf)| {
                     targets
                         .iter()
                         .any(|target| tag.0 == target.tag && *conf > target.min_confidence)
                 })
         })
-        .map(|a| {
-            let dist =
-                ref_abs.distance_squared(compute_entity_absolute(&a.position, &state.body_cache));
-            (a.id.clone(), dist)
-        })
-        .collect();
-    candidates.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0 .0.cmp(&b.0 .0)));
-    candidates.into_iter().map(|(id, _)| id).collect()
+        .map(|candidate| candidate.id)
+        .collect()
 }
 
 /// Check if any station has a module with the given role installed.
@@ -224,18 +225,6 @@ pub(crate) fn total_element_inventory(state: &GameState, element: &str) -> f32 {
         .sum()
 }
 
-/// Mining value for sorting: `mass_kg × element_fraction`.
-pub(crate) fn element_mining_value(asteroid: &AsteroidState, element: &str) -> f32 {
-    asteroid.mass_kg
-        * asteroid
-            .knowledge
-            .composition
-            .as_ref()
-            .and_then(|c| c.get(element))
-            .copied()
-            .unwrap_or(0.0)
-}
-
 /// Priority 1: if ship has ore, return a Deposit (or Transit→Deposit) task to the nearest station.
 pub(crate) fn deposit_priority(
     ship: &ShipState,
@@ -267,6 +256,62 @@ pub(crate) fn deposit_priority(
     ))
 }
 
+/// Units of `component_id` in a station's inventory.
+fn station_component_count(station: &StationState, component_id: &str) -> u32 {
+    station
+        .core
+        .inventory
+        .iter()
+        .filter_map(|item| match item {
+            InventoryItem::Component {
+                component_id: id,
+                count,
+                ..
+            } if id.0 == component_id => Some(*count),
+            _ => None,
+        })
+        .sum()
+}
+
+/// Once a ship's wear reaches `autopilot.ship_repair_wear_threshold`, a
+/// `DockForRepair` (or Transit→`DockForRepair`) task at the nearest of the
+/// owner's stations whose maintenance bay has enough kits for one repair.
+pub(crate) fn repair_priority(
+    ship: &ShipState,
+    state: &GameState,
+    content: &GameContent,
+) -> Option<TaskKind> {
+    if ship.wear.wear <= 0.0 || ship.wear.wear < content.autopilot.ship_repair_wear_threshold {
+        return None;
+    }
+    let ship_abs = compute_entity_absolute(&ship.position, &state.body_cache);
+    let station = state
+        .stations
+        .values()
+        .filter(|station| station.owner == ship.owner)
+        .filter(|station| {
+            sim_core::repair_bay(station, content).is_some_and(|bay| {
+                bay.wear_reduction_per_run > 0.0
+                    && station_component_count(station, &bay.maintenance_component_id)
+                        >= bay.repair_kit_cost
+            })
+        })
+        .min_by_key(|station| {
+            let station_abs = compute_entity_absolute(&station.position, &state.body_cache);
+            ship_abs.distance_squared(station_abs)
+        })?;
+    Some(maybe_transit(
+        TaskKind::DockForRepair {
+            station: station.id.clone(),
+        },
+        &ship.position,
+        &station.position,
+        ship.ticks_per_au(content.constants.ticks_per_au),
+        state,
+        content,
+    ))
+}
+
 /// Geometric mean of per-domain ratios (accumulated / required), clamped to [0, 1].
 pub(crate) fn compute_sufficiency(tech: &TechDef, progress: Option<&DomainProgress>) -> f32 {
     if tech.domain_requirements.is_empty() {
@@ -296,19 +341,7 @@ pub(crate) fn build_export_candidates(
 
     // 1. Export component surplus above reserve
     let export_comp = &autopilot.export_component;
-    let comp_count: u32 = station
-        .core
-        .inventory
-        .iter()
-        .filter_map(|item| match item {
-            InventoryItem::Component {
-                component_id,
-                count,
-                ..
-            } if component_id.0 == export_comp.component_id => Some(*count),
-            _ => None,
-        })
-        .sum();
+    let comp_count = station_component_count(station, &export_comp.component_id);
     if comp_count > export_comp.reserve {
         candidates.push(TradeItemSpec::Component {
             component_id: ComponentId(export_comp.component_id.clone()),
@@ -0,0 +1,468 @@
+//! Hauler logistics controller.
+//!
+//! `HaulerController` splits the autopilot's fleet into miners and haulers.
+//! Miners stay with the wrapped `AutopilotController`; haulers are reserved
+//! from it (`AutopilotController::set_reserved_ships`) and driven here. An
+//! idle hauler takes the first transfer plan no other hauler is already
+//! running (a station with a surplus another station lacks, as the fleet
+//! coordinator sees it). Failing that, once exports are unlocked, it carries
+//! an outlying station's export surplus to the export hub, and when that
+//! haul ends the delivered goods are exported from the hub. Roles and open
+//! hauls persist across `generate_commands` calls.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use sim_core::{
+    trade, Command, CommandEnvelope, GameContent, GameState, PrincipalId, ShipId, ShipState,
+    StationId, TaskKind, TradeItemSpec, TradeTier,
+};
+
+use crate::agents::fleet_coordinator::build_transfer_plans;
+use crate::behaviors::{
+    build_export_candidates, make_cmd, ship_has_hull_tag, try_refuel, AUTOPILOT_OWNER,
+};
+use crate::{AutopilotController, CommandSource};
+
+/// What a controller ship is used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FleetRole {
+    /// Left to the autopilot: surveys, mining and deposits.
+    Miner,
+    /// Moves refined material between stations and to the export hub.
+    Hauler,
+}
+
+/// A `TransferItems` run a hauler is on.
+#[derive(Debug, Clone)]
+struct Haul {
+    from_station: StationId,
+    to_station: StationId,
+    items: Vec<TradeItemSpec>,
+    /// Export `items` from `to_station` once the haul ends.
+    export: bool,
+}
+
+/// `CommandSource` running the autopilot for miners and hauling for up to
+/// `hauler_count` dedicated ships.
+pub struct HaulerController {
+    autopilot: AutopilotController,
+    hauler_count: usize,
+    /// `None` = the station most of the fleet is homed at.
+    export_hub: Option<StationId>,
+    owner: PrincipalId,
+    roles: BTreeMap<ShipId, FleetRole>,
+    hauls: BTreeMap<ShipId, Haul>,
+}
+
+impl HaulerController {
+    pub fn new(autopilot: AutopilotController, hauler_count: usize) -> Self {
+        Self {
+            autopilot,
+            hauler_count,
+            export_hub: None,
+            owner: PrincipalId(AUTOPILOT_OWNER.to_string()),
+            roles: BTreeMap::new(),
+            hauls: BTreeMap::new(),
+        }
+    }
+
+    /// Deliver export hauls to `station_id`.
+    #[must_use]
+    pub fn with_export_hub(mut self, station_id: StationId) -> Self {
+        self.export_hub = Some(station_id);
+        self
+    }
+
+    #[must_use]
+    pub fn autopilot(&self) -> &AutopilotController {
+        &self.autopilot
+    }
+
+    /// The role a ship was given, once the controller has seen it.
+    #[must_use]
+    pub fn role(&self, ship_id: &ShipId) -> Option<FleetRole> {
+        self.roles.get(ship_id).copied()
+    }
+
+    /// Keep roles in step with the fleet. Idle miners are promoted until
+    /// there are `hauler_count` haulers, preferring ships built with the
+    /// `"hauler"` role hint, then logistics hulls; ships hinted `"miner"`
+    /// are never promoted, and at least one miner is always kept.
+    fn assign_roles(&mut self, state: &GameState, content: &GameContent) {
+        self.roles.retain(|id, _| state.ships.contains_key(id));
+        self.hauls.retain(|id, _| state.ships.contains_key(id));
+        let owned: Vec<&ShipState> = state
+            .ships
+            .values()
+            .filter(|ship| ship.owner == self.owner)
+            .collect();
+        let haulers = self
+            .roles
+            .values()
+            .filter(|role| **role == FleetRole::Hauler)
+            .count();
+        let mut candidates: Vec<&ShipState> = owned
+            .iter()
+            .copied()
+            .filter(|ship| {
+                self.roles.get(&ship.id) != Some(&FleetRole::Hauler)
+                    && ship.role.as_deref() != Some("miner")
+                    && is_idle(ship)
+            })
+            .collect();
+        // Stable sort: ties stay in ship id order.
+        candidates.sort_by_key(|ship| {
+            (
+                ship.role.as_deref() != Some("hauler"),
+                !ship_has_hull_tag(ship, "logistics", content),
+            )
+        });
+        // Always leave at least one miner.
+        let wanted = self.hauler_count.min(owned.len().saturating_sub(1));
+        for ship in candidates.into_iter().take(wanted.saturating_sub(haulers)) {
+            self.roles.insert(ship.id.clone(), FleetRole::Hauler);
+        }
+        for ship in owned {
+            self.roles
+                .entry(ship.id.clone())
+                .or_insert(FleetRole::Miner);
+        }
+    }
+
+    fn hub(&self, state: &GameState) -> Option<StationId> {
+        if let Some(hub) = &self.export_hub {
+            return state.stations.contains_key(hub).then(|| hub.clone());
+        }
+        let mut homed: BTreeMap<&StationId, usize> =
+            state.stations.keys().map(|id| (id, 0)).collect();
+        for ship in state.ships.values().filter(|s| s.owner == self.owner) {
+            if let Some(count) = ship.home_station.as_ref().and_then(|h| homed.get_mut(h)) {
+                *count += 1;
+            }
+        }
+        // `max_by_key` keeps the last maximum; iterate in reverse so ties
+        // go to the lowest id.
+        homed
+            .into_iter()
+            .rev()
+            .max_by_key(|(_, count)| *count)
+            .map(|(id, _)| id.clone())
+    }
+
+    fn next_haul(
+        &self,
+        state: &GameState,
+        content: &GameContent,
+        hub: Option<&StationId>,
+    ) -> Option<Haul> {
+        let running = |from: &StationId, to: &StationId| {
+            self.hauls
+                .values()
+                .any(|haul| haul.from_station == *from && haul.to_station == *to)
+        };
+        if let Some(plan) = build_transfer_plans(state, content)
+            .into_iter()
+            .find(|plan| !running(&plan.source, &plan.target))
+        {
+            return Some(Haul {
+                from_station: plan.source,
+                to_station: plan.target,
+                items: vec![plan.item],
+                export: false,
+            });
+        }
+        let hub = hub?;
+        if !state.progression.trade_tier_unlocked(TradeTier::Export) {
+            return None;
+        }
+        let batch_size_kg = state.strategy_config.export_batch_size_kg;
+        state
+            .stations
+            .iter()
+            .filter(|(id, _)| *id != hub && !running(id, hub))
+            .find_map(|(id, station)| {
+                let items = build_export_candidates(station, &content.autopilot, batch_size_kg);
+                (!items.is_empty()).then(|| Haul {
+                    from_station: id.clone(),
+                    to_station: hub.clone(),
+                    items,
+                    export: true,
+                })
+            })
+    }
+
+    /// Export what an ended hub haul brought, capped at the hub's surplus
+    /// over its reserves.
+    fn export_delivery(
+        &self,
+        haul: &Haul,
+        state: &GameState,
+        content: &GameContent,
+        next_id: &mut u64,
+    ) -> Vec<CommandEnvelope> {
+        let Some(hub) = state.stations.get(&haul.to_station) else {
+            return Vec::new();
+        };
+        let surplus = build_export_candidates(hub, &content.autopilot, f32::MAX);
+        haul.items
+            .iter()
+            .filter_map(|item| capped_to_surplus(item, &surplus))
+            .filter(|item| trade::has_enough_for_export(&hub.core.inventory, item))
+            .map(|item_spec| {
+                make_cmd(
+                    &self.owner,
+                    state.meta.tick,
+                    next_id,
+                    Command::Export {
+                        facility_id: haul.to_station.clone().into(),
+                        item_spec,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+fn is_idle(ship: &ShipState) -> bool {
+    ship.task
+        .as_ref()
+        .is_none_or(|task| matches!(task.kind, TaskKind::Idle))
+}
+
+/// `item` shrunk to the matching surplus entry, if there is one.
+fn capped_to_surplus(item: &TradeItemSpec, surplus: &[TradeItemSpec]) -> Option<TradeItemSpec> {
+    surplus
+        .iter()
+        .find_map(|available| match (item, available) {
+            (
+                TradeItemSpec::Material { element, kg },
+                TradeItemSpec::Material {
+                    element: available_element,
+                    kg: available_kg,
+                },
+            ) if element == available_element => Some(TradeItemSpec::Material {
+                element: element.clone(),
+                kg: kg.min(*available_kg),
+            }),
+            (
+                TradeItemSpec::Component {
+                    component_id,
+                    count,
+                },
+                TradeItemSpec::Component {
+                    component_id: available_id,
+                    count: available_count,
+                },
+            ) if component_id == available_id => Some(TradeItemSpec::Component {
+                component_id: component_id.clone(),
+                count: (*count).min(*available_count),
+            }),
+            _ => None,
+        })
+}
+
+impl CommandSource for HaulerController {
+    fn generate_commands(
+        &mut self,
+        state: &GameState,
+        content: &GameContent,
+        next_command_id: &mut u64,
+    ) -> Vec<CommandEnvelope> {
+        self.assign_roles(state, content);
+        let haulers: BTreeSet<ShipId> = self
+            .roles
+            .iter()
+            .filter(|(_, role)| **role == FleetRole::Hauler)
+            .map(|(id, _)| id.clone())
+            .collect();
+        self.autopilot.set_reserved_ships(haulers.clone());
+        let mut commands = self
+            .autopilot
+            .generate_commands(state, content, next_command_id);
+
+        let hub = self.hub(state);
+        for ship_id in haulers {
+            let Some(ship) = state.ships.get(&ship_id) else {
+                continue;
+            };
+            if !is_idle(ship) {
+                continue;
+            }
+            if let Some(haul) = self.hauls.remove(&ship_id) {
+                if haul.export {
+                    commands.extend(self.export_delivery(&haul, state, content, next_command_id));
+                }
+            }
+            let command = if let Some(haul) = self.next_haul(state, content, hub.as_ref()) {
+                let command = Command::TransferItems {
+                    ship_id: ship_id.clone(),
+                    from_station: haul.from_station.clone(),
+                    to_station: haul.to_station.clone(),
+                    items: haul.items.clone(),
+                };
+                self.hauls.insert(ship_id, haul);
+                command
+            } else if let Some(task_kind) = try_refuel(ship, state, content) {
+                Command::AssignShipTask { ship_id, task_kind }
+            } else {
+                continue;
+            };
+            commands.push(make_cmd(
+                &self.owner,
+                state.meta.tick,
+                next_command_id,
+                command,
+            ));
+        }
+        commands
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim_core::test_fixtures::{base_content, base_state, test_ship_id, test_station_id};
+    use sim_core::{InventoryItem, Position, RadiusAuMicro};
+
+    fn outpost_id() -> StationId {
+        StationId("station_belt_outpost".to_string())
+    }
+
+    fn fe(kg: f32) -> InventoryItem {
+        InventoryItem::Material {
+            element: "Fe".to_string(),
+            kg,
+            quality: 0.8,
+            thermal: None,
+        }
+    }
+
+    fn hauler_content() -> GameContent {
+        let mut content = base_content();
+        content.techs.clear();
+        content
+    }
+
+    /// The base station plus an empty outpost, and a second idle ship.
+    fn hauler_state(content: &GameContent) -> GameState {
+        let mut state = base_state(content);
+        state.scan_sites.clear();
+        let mut outpost = state.stations[&test_station_id()].clone();
+        outpost.id = outpost_id();
+        outpost.position = Position {
+            radius_au_um: RadiusAuMicro(2_000_000),
+            ..outpost.position
+        };
+        outpost.core.inventory.clear();
+        state.stations.insert(outpost_id(), outpost);
+        let mut second = state.ships[&test_ship_id()].clone();
+        second.id = ShipId("ship_0002".to_string());
+        state.ships.insert(second.id.clone(), second);
+        state
+    }
+
+    fn transfers(commands: &[CommandEnvelope]) -> Vec<(&ShipId, &StationId, &StationId)> {
+        commands
+            .iter()
+            .filter_map(|cmd| match &cmd.command {
+                Command::TransferItems {
+                    ship_id,
+                    from_station,
+                    to_station,
+                    ..
+                } => Some((ship_id, from_station, to_station)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn roles_prefer_hauler_hints_and_keep_a_miner() {
+        let content = hauler_content();
+        let mut state = hauler_state(&content);
+        state
+            .ships
+            .get_mut(&ShipId("ship_0002".to_string()))
+            .unwrap()
+            .role = Some("hauler".to_string());
+        let mut controller = HaulerController::new(AutopilotController::new(), 5);
+
+        controller.generate_commands(&state, &content, &mut 0);
+
+        assert_eq!(
+            controller.role(&ShipId("ship_0002".to_string())),
+            Some(FleetRole::Hauler)
+        );
+        assert_eq!(controller.role(&test_ship_id()), Some(FleetRole::Miner));
+    }
+
+    #[test]
+    fn hauler_moves_surplus_to_a_station_that_lacks_it() {
+        let content = hauler_content();
+        let mut state = hauler_state(&content);
+        let base = state.stations.get_mut(&test_station_id()).unwrap();
+        base.core.inventory.push(fe(30_000.0));
+        let mut controller = HaulerController::new(AutopilotController::new(), 1);
+
+        let commands = controller.generate_commands(&state, &content, &mut 0);
+
+        // No role hints: the first idle ship by id is promoted.
+        let hauler = test_ship_id();
+        assert_eq!(
+            transfers(&commands),
+            vec![(&hauler, &test_station_id(), &outpost_id())]
+        );
+        assert!(
+            commands
+                .iter()
+                .all(|cmd| cmd.command.ship_id().is_none_or(|id| *id == hauler)),
+            "the autopilot leaves the hauler alone and has nothing for the miner"
+        );
+    }
+
+    #[test]
+    fn hub_hauls_are_exported_when_they_end() {
+        let content = hauler_content();
+        let mut state = hauler_state(&content);
+        state.progression.trade_tier = TradeTier::Export;
+        // Over the 12 t reserve at the outpost, but not enough for a transfer.
+        let outpost = state.stations.get_mut(&outpost_id()).unwrap();
+        outpost.core.inventory.push(fe(15_000.0));
+        let mut controller =
+            HaulerController::new(AutopilotController::new(), 1).with_export_hub(test_station_id());
+
+        let commands = controller.generate_commands(&state, &content, &mut 0);
+        let hauler = test_ship_id();
+        assert_eq!(
+            transfers(&commands),
+            vec![(&hauler, &outpost_id(), &test_station_id())]
+        );
+
+        // The haul ends with one export batch at the hub, on top of its reserve.
+        let batch_kg = state.strategy_config.export_batch_size_kg;
+        let outpost = state.stations.get_mut(&outpost_id()).unwrap();
+        outpost.core.inventory = vec![fe(15_000.0 - batch_kg)];
+        let hub = state.stations.get_mut(&test_station_id()).unwrap();
+        hub.core.inventory.push(fe(12_000.0 + batch_kg));
+        let commands = controller.generate_commands(&state, &content, &mut 0);
+
+        let exported: Vec<&TradeItemSpec> = commands
+            .iter()
+            .filter_map(|cmd| match &cmd.command {
+                Command::Export {
+                    facility_id,
+                    item_spec,
+                } if *facility_id == test_station_id().into() => Some(item_spec),
+                _ => None,
+            })
+            .collect();
+        assert!(
+            matches!(
+                exported.as_slice(),
+                [TradeItemSpec::Material { element, kg }]
+                    if element == "Fe" && (*kg - batch_kg).abs() < 1e-3
+            ),
+            "{exported:?}"
+        );
+    }
+}
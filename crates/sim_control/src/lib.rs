@@ -1,9 +1,15 @@
 mod agents;
 mod behaviors;
+mod hauler;
 mod objectives;
+mod profile;
+mod sandbox;
+mod scoring;
+#[cfg(feature = "scripting")]
+mod script;
 mod strategy_interpreter;
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use agents::ground_facility_agent::GroundFacilityAgent;
 use agents::ship_agent::ShipAgent;
@@ -11,6 +17,11 @@ use agents::station_agent::StationAgent;
 use agents::Agent;
 pub use agents::DecisionRecord;
 use behaviors::AUTOPILOT_OWNER;
+pub use hauler::{FleetRole, HaulerController};
+pub use profile::ControllerProfile;
+pub use sandbox::{ControllerFault, SandboxedController};
+#[cfg(feature = "scripting")]
+pub use script::{ScriptController, ScriptError};
 use sim_core::{
     CommandEnvelope, ConcernPriorities, GameContent, GameState, GroundFacilityId, PrincipalId,
     ShipId, StationId,
@@ -24,13 +35,24 @@ pub trait CommandSource {
         content: &GameContent,
         next_command_id: &mut u64,
     ) -> Vec<CommandEnvelope>;
+
+    /// Wall-clock deadline for the next `generate_commands`. Controllers
+    /// that can interrupt themselves (scripts) should give up past it and
+    /// report a fault. `None` clears it.
+    fn set_deadline(&mut self, _deadline: Option<std::time::Instant>) {}
+
+    /// Error raised by the last `generate_commands`, if any.
+    fn take_fault(&mut self) -> Option<String> {
+        None
+    }
 }
 
 /// Pure agent-based autopilot controller.
 ///
 /// Station agents handle per-station decisions (modules, labs, crew, trade,
 /// ship objectives). Ship agents handle tactical execution (transit, mine,
-/// deposit, refuel).
+/// deposit, refuel). A [`ControllerProfile`] tilts the strategy toward one
+/// playstyle.
 pub struct AutopilotController {
     station_agents: BTreeMap<StationId, StationAgent>,
     ground_facility_agents: BTreeMap<GroundFacilityId, GroundFacilityAgent>,
@@ -44,10 +66,20 @@ pub struct AutopilotController {
     /// the last evaluation tick, the dirty flag, and per-concern last-serviced
     /// ticks for temporal bias.
     strategy_runtime: StrategyRuntimeState,
+    profile: ControllerProfile,
+    /// Ships another controller drives (e.g. `HaulerController` haulers).
+    /// They get no ship agent, so no objectives or tasks from the autopilot,
+    /// and while any are reserved the fleet coordinator leaves inter-station
+    /// transfers to them.
+    reserved_ships: BTreeSet<ShipId>,
 }
 
 impl AutopilotController {
     pub fn new() -> Self {
+        Self::with_profile(ControllerProfile::default())
+    }
+
+    pub fn with_profile(profile: ControllerProfile) -> Self {
         Self {
             station_agents: BTreeMap::new(),
             ground_facility_agents: BTreeMap::new(),
@@ -55,9 +87,21 @@ impl AutopilotController {
             owner: PrincipalId(AUTOPILOT_OWNER.to_string()),
             decision_log: None,
             strategy_runtime: StrategyRuntimeState::default(),
+            profile,
+            reserved_ships: BTreeSet::new(),
         }
     }
 
+    #[must_use]
+    pub fn profile(&self) -> ControllerProfile {
+        self.profile
+    }
+
+    /// Replace the set of ships the autopilot leaves to another controller.
+    pub fn set_reserved_ships(&mut self, ships: BTreeSet<ShipId>) {
+        self.reserved_ships = ships;
+    }
+
     /// Enable structured decision logging. Zero overhead when not called.
     pub fn enable_decision_logging(&mut self) {
         self.decision_log = Some(Vec::new());
@@ -104,11 +148,13 @@ impl AutopilotController {
 impl AutopilotController {
     /// VIO-607/608: Emit a `SetStrategyConfig` command if the game phase changed
     /// and no manual `mode_override` is set. Applies per-phase priority presets
-    /// from `content.phase_presets` when available. Updates `runtime.last_phase`
-    /// so we only fire once per transition.
+    /// from `content.phase_presets` when available, then the controller
+    /// profile (even under an override). Updates `runtime.last_phase` so we
+    /// only fire once per transition.
     fn maybe_switch_mode(
         runtime: &mut StrategyRuntimeState,
         owner: &PrincipalId,
+        profile: ControllerProfile,
         state: &GameState,
         content: &GameContent,
         next_id: &mut u64,
@@ -119,22 +165,17 @@ impl AutopilotController {
             return;
         }
         runtime.last_phase = Some(current_phase);
-        if state.strategy_config.mode_override.is_some() {
-            return;
+        let mut new_config = state.strategy_config.clone();
+        if state.strategy_config.mode_override.is_none() {
+            new_config.mode = sim_core::StrategyMode::for_phase(current_phase);
+            if let Some(preset) = content.phase_presets.get(&current_phase) {
+                new_config.priorities = *preset;
+            }
         }
-        let target_mode = sim_core::StrategyMode::for_phase(current_phase);
-        let preset_priorities = content.phase_presets.get(&current_phase).copied();
-        let mode_changed = state.strategy_config.mode != target_mode;
-        let priorities_changed =
-            preset_priorities.is_some_and(|preset| preset != state.strategy_config.priorities);
-        if !mode_changed && !priorities_changed {
+        profile.tailor(&mut new_config);
+        if new_config == state.strategy_config {
             return;
         }
-        let mut new_config = state.strategy_config.clone();
-        new_config.mode = target_mode;
-        if let Some(preset) = preset_priorities {
-            new_config.priorities = preset;
-        }
         commands.push(behaviors::make_cmd(
             owner,
             state.meta.tick,
@@ -170,6 +211,24 @@ fn partition_ships_by_station(
     map
 }
 
+/// Give each owned, unreserved ship an agent and drop the agents of ships
+/// that are gone or reserved.
+fn sync_ship_agents(
+    ship_agents: &mut BTreeMap<ShipId, ShipAgent>,
+    state: &GameState,
+    owner: &PrincipalId,
+    reserved: &BTreeSet<ShipId>,
+) {
+    for (ship_id, ship) in &state.ships {
+        if ship.owner == *owner && !reserved.contains(ship_id) {
+            ship_agents
+                .entry(ship_id.clone())
+                .or_insert_with(|| ShipAgent::new(ship_id.clone()));
+        }
+    }
+    ship_agents.retain(|id, _| state.ships.contains_key(id) && !reserved.contains(id));
+}
+
 impl CommandSource for AutopilotController {
     fn generate_commands(
         &mut self,
@@ -190,6 +249,7 @@ impl CommandSource for AutopilotController {
         Self::maybe_switch_mode(
             &mut self.strategy_runtime,
             &self.owner,
+            self.profile,
             state,
             content,
             next_command_id,
@@ -203,7 +263,8 @@ impl CommandSource for AutopilotController {
             ship_agents,
             owner,
             decision_log,
-            strategy_runtime: _,
+            reserved_ships,
+            ..
         } = self;
 
         // 1. Sync agent lifecycle — create for new entities, remove for deleted
@@ -224,12 +285,7 @@ impl CommandSource for AutopilotController {
         }
         ground_facility_agents.retain(|id, _| state.ground_facilities.contains_key(id));
 
-        for (ship_id, ship) in &state.ships {
-            if ship.owner == *owner && !ship_agents.contains_key(ship_id) {
-                ship_agents.insert(ship_id.clone(), ShipAgent::new(ship_id.clone()));
-            }
-        }
-        ship_agents.retain(|id, _| state.ships.contains_key(id));
+        sync_ship_agents(ship_agents, state, owner, reserved_ships);
 
         // 2. Station agents generate commands (modules, labs, crew, trade)
         //    in BTreeMap order (deterministic by StationId)
@@ -257,14 +313,17 @@ impl CommandSource for AutopilotController {
 
         // 3.5a. FleetCoordinator: global supply/demand evaluation (VIO-598).
         // Evaluates per-station inventory levels, matches surpluses to deficits,
-        // and assigns Transfer objectives to idle ships.
-        agents::fleet_coordinator::evaluate_and_assign(
-            ship_agents,
-            state,
-            content,
-            owner,
-            decision_log.as_mut(),
-        );
+        // and assigns Transfer objectives to idle ships. Skipped while other
+        // controllers' ships are reserved: those haulers run the transfers.
+        if reserved_ships.is_empty() {
+            agents::fleet_coordinator::evaluate_and_assign(
+                ship_agents,
+                state,
+                content,
+                owner,
+                decision_log.as_mut(),
+            );
+        }
 
         // 3.5b. Module delivery: cross-station module transfers for empty stations (VIO-596).
         // Runs after fleet coordinator so material transfers are assigned first.
@@ -282,7 +341,11 @@ impl CommandSource for AutopilotController {
 
         // Station agents assign objectives only to their home ships.
         // Priorities drive weighted task selection with priority halving (VIO-481).
+        // Ships stay home until their station has finished bootstrapping.
         for station_agent in station_agents.values() {
+            if !station_agent.bootstrapped() {
+                continue;
+            }
             let home_ships = ships_by_station
                 .get(&station_agent.station_id)
                 .map_or(&[][..], |v| v.as_slice());
@@ -397,7 +460,11 @@ mod tests {
                 knowledge: AsteroidKnowledge {
                     tag_beliefs: vec![],
                     composition: Some(HashMap::from([("Fe".to_string(), 1.0)])),
+                    layers: None,
+                    composition_sigma: None,
+                    deep_scan_count: 0,
                 },
+                layers: None,
             },
         );
 
@@ -469,6 +536,7 @@ mod tests {
             .push(sim_core::InventoryItem::Module {
                 item_id: sim_core::ModuleItemId("module_item_0001".to_string()),
                 module_def_id: "module_basic_iron_refinery".to_string(),
+                wear: 0.0,
             });
 
         let mut autopilot = AutopilotController::new();
@@ -485,7 +553,11 @@ mod tests {
 
     #[test]
     fn test_autopilot_enables_disabled_module() {
-        let content = autopilot_content();
+        let mut content = autopilot_content();
+        content.module_defs.insert(
+            "module_basic_iron_refinery".to_string(),
+            ModuleDefBuilder::new("module_basic_iron_refinery").build(),
+        );
         let mut state = autopilot_state(&content);
 
         let station_id = sim_core::StationId("station_earth_orbit".to_string());
@@ -562,6 +634,7 @@ mod tests {
             .push(sim_core::InventoryItem::Module {
                 item_id: sim_core::ModuleItemId("module_item_maint".to_string()),
                 module_def_id: "module_maintenance_bay".to_string(),
+                wear: 0.0,
             });
 
         let mut autopilot = AutopilotController::new();
@@ -653,7 +726,9 @@ mod tests {
 
     #[test]
     fn test_autopilot_multiple_ships_get_different_assignments() {
-        let content = autopilot_content();
+        let mut content = autopilot_content();
+        // One site per ship; node batching would hand both sites to one ship.
+        content.autopilot.survey_batch_size = 1;
         let mut state = autopilot_state(&content);
 
         let owner = PrincipalId("principal_autopilot".to_string());
@@ -679,6 +754,10 @@ mod tests {
                 crew: Default::default(),
                 leaders: Vec::new(),
                 home_station: Some(station_id),
+                name: String::new(),
+                class_id: None,
+                role: None,
+                wear: sim_core::WearState::default(),
             },
         );
 
@@ -885,6 +964,7 @@ mod tests {
             .push(sim_core::InventoryItem::Module {
                 item_id: sim_core::ModuleItemId("module_item_lab_001".to_string()),
                 module_def_id: "module_materials_lab".to_string(),
+                wear: 0.0,
             });
 
         let mut autopilot = AutopilotController::new();
@@ -899,6 +979,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bootstrap_installs_power_before_labs() {
+        let (mut content, mut state) = lab_content_and_state();
+        content.module_defs.insert(
+            "module_solar".to_string(),
+            ModuleDefBuilder::new("module_solar")
+                .behavior(sim_core::ModuleBehaviorDef::SolarArray(
+                    sim_core::SolarArrayDef {
+                        base_output_kw: 20.0,
+                    },
+                ))
+                .build(),
+        );
+        let station_id = StationId("station_earth_orbit".to_string());
+        let inventory = &mut state.stations.get_mut(&station_id).unwrap().core.inventory;
+        inventory.push(InventoryItem::Module {
+            item_id: sim_core::ModuleItemId("module_item_lab_001".to_string()),
+            module_def_id: "module_materials_lab".to_string(),
+            wear: 0.0,
+        });
+        inventory.push(InventoryItem::Module {
+            item_id: sim_core::ModuleItemId("module_item_solar_001".to_string()),
+            module_def_id: "module_solar".to_string(),
+            wear: 0.0,
+        });
+
+        let installs = |commands: &[CommandEnvelope]| -> Vec<String> {
+            commands
+                .iter()
+                .filter_map(|cmd| match &cmd.command {
+                    Command::InstallModule { module_item_id, .. } => Some(module_item_id.0.clone()),
+                    _ => None,
+                })
+                .collect()
+        };
+        let mut autopilot = AutopilotController::new();
+        let mut next_id = 0u64;
+
+        let commands = autopilot.generate_commands(&state, &content, &mut next_id);
+        assert_eq!(installs(&commands), vec!["module_item_solar_001"]);
+
+        // The solar array has been installed: the lab follows.
+        state.meta.tick += 1;
+        state
+            .stations
+            .get_mut(&station_id)
+            .unwrap()
+            .core
+            .inventory
+            .retain(|item| !matches!(item, InventoryItem::Module { module_def_id, .. } if module_def_id == "module_solar"));
+        let commands = autopilot.generate_commands(&state, &content, &mut next_id);
+        assert_eq!(installs(&commands), vec!["module_item_lab_001"]);
+    }
+
     #[test]
     fn test_autopilot_assigns_lab_to_eligible_tech() {
         let (content, mut state) = lab_content_and_state();
@@ -1276,7 +1410,11 @@ mod tests {
                 knowledge: AsteroidKnowledge {
                     tag_beliefs: vec![],
                     composition: Some(HashMap::from([("Fe".to_string(), 1.0)])),
+                    layers: None,
+                    composition_sigma: None,
+                    deep_scan_count: 0,
                 },
+                layers: None,
             },
         );
         // autopilot_state clears scan_sites — add enough to saturate survey urgency
@@ -1390,6 +1528,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_profile_tailors_strategy_even_under_mode_override() {
+        let content = autopilot_content();
+        let mut state = autopilot_state(&content);
+        state.strategy_config.mode_override = Some(sim_core::StrategyMode::Consolidate);
+        state.strategy_config.mode = sim_core::StrategyMode::Consolidate;
+
+        let mut autopilot = AutopilotController::with_profile(ControllerProfile::ResearchFirst);
+        let mut next_id = 0u64;
+        let commands = autopilot.generate_commands(&state, &content, &mut next_id);
+
+        let config = commands
+            .iter()
+            .find_map(|cmd| match &cmd.command {
+                sim_core::Command::SetStrategyConfig { config } => Some(config),
+                _ => None,
+            })
+            .expect("profile should emit SetStrategyConfig on the first pass");
+        assert_eq!(config.mode, sim_core::StrategyMode::Consolidate);
+        assert!(config.priorities.research >= 0.9);
+        assert!(config.priorities.fleet_expansion <= 0.3);
+    }
+
     #[test]
     fn test_phase_transition_applies_preset_priorities() {
         let mut content = autopilot_content();
@@ -1459,6 +1620,8 @@ mod tests {
                 bonuses: vec![],
                 required_tech: None,
                 tags: vec![],
+                hold_partitions: std::collections::BTreeMap::new(),
+                replacement_value: 0.0,
             },
         );
 
@@ -1477,6 +1640,9 @@ mod tests {
             ],
             outputs: vec![sim_core::OutputSpec::Ship {
                 hull_id: sim_core::HullId("hull_test_ship".to_string()),
+                class_id: None,
+                role: None,
+                owner: sim_core::ShipOwnerRule::StationOwner,
             }],
             efficiency: 1.0,
             thermal_req: None,
@@ -1500,6 +1666,7 @@ mod tests {
                         assembly_interval_ticks: 1440,
                         recipes: vec![sim_core::RecipeId("recipe_test_ship".to_string())],
                         max_stock: HashMap::new(),
+                        parallel_slots: 1,
                     },
                 ))
                 .roles(vec!["shipyard"])
@@ -1552,6 +1719,9 @@ mod tests {
                 capped: false,
                 cap_override: HashMap::new(),
                 selected_recipe: None,
+                extra_slots: Vec::new(),
+                job: None,
+                queue: Vec::new(),
             }),
             wear: sim_core::WearState::default(),
             power_stalled: false,
@@ -1706,6 +1876,10 @@ mod tests {
                     crew: std::collections::BTreeMap::new(),
                     leaders: vec![],
                     home_station: None,
+                    name: String::new(),
+                    class_id: None,
+                    role: None,
+                    wear: sim_core::WearState::default(),
                 },
             );
         }
@@ -1784,6 +1958,8 @@ mod tests {
             boiloff_rate_per_day_at_293k: None,
             boiling_point_mk: None,
             boiloff_curve: None,
+            volatile: false,
+            quality_decay_per_day: None,
         });
         content.init_caches(); // Rebuild density_map with He
                                // Add component def for repair_kit (needed for mass calculation)
@@ -2089,7 +2265,11 @@ mod tests {
                         ("Fe".to_string(), 0.8),
                         ("Si".to_string(), 0.2),
                     ])),
+                    layers: None,
+                    composition_sigma: None,
+                    deep_scan_count: 0,
                 },
+                layers: None,
             },
         );
 
@@ -2108,7 +2288,11 @@ mod tests {
                         ("H2O".to_string(), 0.5),
                         ("Fe".to_string(), 0.1),
                     ])),
+                    layers: None,
+                    composition_sigma: None,
+                    deep_scan_count: 0,
                 },
+                layers: None,
             },
         );
 
@@ -2162,7 +2346,11 @@ mod tests {
                         ("Fe".to_string(), 0.8),
                         ("Si".to_string(), 0.2),
                     ])),
+                    layers: None,
+                    composition_sigma: None,
+                    deep_scan_count: 0,
                 },
+                layers: None,
             },
         );
 
@@ -2181,7 +2369,11 @@ mod tests {
                         ("H2O".to_string(), 0.5),
                         ("Fe".to_string(), 0.1),
                     ])),
+                    layers: None,
+                    composition_sigma: None,
+                    deep_scan_count: 0,
                 },
+                layers: None,
             },
         );
 
@@ -2229,8 +2421,12 @@ mod tests {
                 mass_kg: 2000.0,
                 knowledge: AsteroidKnowledge {
                     tag_beliefs: vec![(AnomalyTag::new("VolatileRich"), 0.9)],
-                    composition: None, // Not deep-scanned yet
+                    composition: None, // Not deep-scanned yet,
+                    layers: None,
+                    composition_sigma: None,
+                    deep_scan_count: 0,
                 },
+                layers: None,
             },
         );
 
@@ -2258,6 +2454,8 @@ mod tests {
             boiloff_rate_per_day_at_293k: None,
             boiling_point_mk: None,
             boiloff_curve: None,
+            volatile: false,
+            quality_decay_per_day: None,
         });
         let mut state = autopilot_state(&content);
 
@@ -2311,7 +2509,11 @@ mod tests {
                         ("Fe".to_string(), 0.8),
                         ("Si".to_string(), 0.2),
                     ])),
+                    layers: None,
+                    composition_sigma: None,
+                    deep_scan_count: 0,
                 },
+                layers: None,
             },
         );
 
@@ -2330,7 +2532,11 @@ mod tests {
                         ("H2O".to_string(), 0.5),
                         ("Fe".to_string(), 0.1),
                     ])),
+                    layers: None,
+                    composition_sigma: None,
+                    deep_scan_count: 0,
                 },
+                layers: None,
             },
         );
 
@@ -2652,10 +2858,13 @@ mod tests {
                 slots: vec![SlotDef {
                     slot_type: SlotType("utility".to_string()),
                     label: "Utility 1".to_string(),
+                    adjacent: Vec::new(),
                 }],
                 bonuses: vec![],
                 required_tech: None,
                 tags: vec![],
+                hold_partitions: std::collections::BTreeMap::new(),
+                replacement_value: 0.0,
             },
         );
         // Add equipment module def
@@ -2685,6 +2894,7 @@ mod tests {
             station.core.inventory.push(InventoryItem::Module {
                 item_id: ModuleItemId("mod_item_fit_test".to_string()),
                 module_def_id: "module_cargo_expander".to_string(),
+                wear: 0.0,
             });
         }
 
@@ -2735,10 +2945,13 @@ mod tests {
                 slots: vec![SlotDef {
                     slot_type: SlotType("utility".to_string()),
                     label: "Utility 1".to_string(),
+                    adjacent: Vec::new(),
                 }],
                 bonuses: vec![],
                 required_tech: None,
                 tags: vec![],
+                hold_partitions: std::collections::BTreeMap::new(),
+                replacement_value: 0.0,
             },
         );
         content.module_defs.insert(
@@ -2774,6 +2987,7 @@ mod tests {
             station.core.inventory.push(InventoryItem::Module {
                 item_id: ModuleItemId("mod_item_skip".to_string()),
                 module_def_id: "module_cargo_expander".to_string(),
+                wear: 0.0,
             });
         }
 
@@ -2812,7 +3026,7 @@ mod tests {
 
         let mut rng = ChaCha8Rng::seed_from_u64(42);
         let mut state = sim_world::build_initial_state(&content, 42, &mut rng);
-        sim_world::validate_state(&state, &content);
+        sim_world::validate_state(&state, &content).unwrap();
 
         // VIO-481: Verify all 13 StrategyConfig thresholds match AutopilotConfig defaults
         // so the migration from content.autopilot to state.strategy_config is behavioral-equivalent.
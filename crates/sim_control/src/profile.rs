@@ -0,0 +1,132 @@
+//! Autopilot controller profiles.
+//!
+//! A profile tilts the autopilot toward one playstyle by tailoring the run's
+//! `StrategyConfig`: it raises or caps priority weights and adjusts a few
+//! operational knobs. The autopilot re-applies its profile whenever it
+//! updates the config for a phase change (see `maybe_switch_mode`), after the
+//! phase preset, so a profile holds across presets. Every adjustment is a
+//! floor or a cap, so tailoring an already tailored config changes nothing.
+
+use serde::{Deserialize, Serialize};
+use sim_core::StrategyConfig;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ControllerProfile {
+    /// Leaves the strategy config as content and phase presets set it.
+    #[default]
+    Balanced,
+    /// Mines and surveys hard and grows the fleet; research waits.
+    AggressiveMiner,
+    /// Favours labs, sensors and deep scans over fleet growth.
+    ResearchFirst,
+    /// Exports early and often in smaller batches.
+    TradeFocused,
+}
+
+impl ControllerProfile {
+    pub const ALL: [Self; 4] = [
+        Self::Balanced,
+        Self::AggressiveMiner,
+        Self::ResearchFirst,
+        Self::TradeFocused,
+    ];
+
+    /// Name accepted by `--controller`.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Balanced => "balanced",
+            Self::AggressiveMiner => "aggressive-miner",
+            Self::ResearchFirst => "research-first",
+            Self::TradeFocused => "trade-focused",
+        }
+    }
+
+    /// Apply this profile's adjustments to `config`.
+    pub fn tailor(self, config: &mut StrategyConfig) {
+        let priorities = &mut config.priorities;
+        match self {
+            Self::Balanced => {}
+            Self::AggressiveMiner => {
+                raise(&mut priorities.mining, 0.9);
+                raise(&mut priorities.survey, 0.8);
+                raise(&mut priorities.fleet_expansion, 0.8);
+                cap(&mut priorities.research, 0.4);
+                config.fleet_size_target = config.fleet_size_target.max(5);
+                config.budget_cap_fraction = config.budget_cap_fraction.max(0.1);
+            }
+            Self::ResearchFirst => {
+                raise(&mut priorities.research, 0.9);
+                raise(&mut priorities.deep_scan, 0.7);
+                cap(&mut priorities.mining, 0.5);
+                cap(&mut priorities.fleet_expansion, 0.3);
+            }
+            Self::TradeFocused => {
+                raise(&mut priorities.export, 0.9);
+                raise(&mut priorities.mining, 0.8);
+                cap(&mut priorities.fleet_expansion, 0.4);
+                config.export_batch_size_kg = config.export_batch_size_kg.min(250.0);
+                config.export_min_revenue = config.export_min_revenue.min(500.0);
+            }
+        }
+    }
+}
+
+fn raise(weight: &mut f32, floor: f32) {
+    *weight = weight.max(floor);
+}
+
+fn cap(weight: &mut f32, ceiling: f32) {
+    *weight = weight.min(ceiling);
+}
+
+impl std::fmt::Display for ControllerProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for ControllerProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|profile| profile.name() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown controller profile '{s}' (expected balanced, aggressive-miner, \
+                     research-first, or trade-focused)"
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profiles_round_trip_through_their_names() {
+        for profile in ControllerProfile::ALL {
+            assert_eq!(profile.name().parse(), Ok(profile));
+            let json = serde_json::to_string(&profile).unwrap();
+            assert_eq!(json, format!("\"{}\"", profile.name()));
+        }
+        assert!("greedy".parse::<ControllerProfile>().is_err());
+    }
+
+    #[test]
+    fn tailoring_is_idempotent_and_balanced_is_a_no_op() {
+        let base = StrategyConfig::default();
+        for profile in ControllerProfile::ALL {
+            let mut once = base.clone();
+            profile.tailor(&mut once);
+            let mut twice = once.clone();
+            profile.tailor(&mut twice);
+            assert_eq!(once, twice, "{profile}");
+            assert_eq!(once == base, profile == ControllerProfile::Balanced);
+        }
+    }
+}
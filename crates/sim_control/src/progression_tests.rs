@@ -268,6 +268,7 @@ fn production_like_content() -> GameContent {
                 assembly_interval_ticks: 6,
                 recipes: vec![RecipeId("recipe_basic_repair_kit".to_string())],
                 max_stock: HashMap::from([(ComponentId("repair_kit".to_string()), 50)]),
+                parallel_slots: 1,
             }))
             .build(),
     );
@@ -318,34 +319,42 @@ fn production_like_state(content: &GameContent) -> GameState {
         InventoryItem::Module {
             item_id: ModuleItemId("mi_001".to_string()),
             module_def_id: "module_sensor_array".to_string(),
+            wear: 0.0,
         },
         InventoryItem::Module {
             item_id: ModuleItemId("mi_002".to_string()),
             module_def_id: "module_exploration_lab".to_string(),
+            wear: 0.0,
         },
         InventoryItem::Module {
             item_id: ModuleItemId("mi_003".to_string()),
             module_def_id: "module_materials_lab".to_string(),
+            wear: 0.0,
         },
         InventoryItem::Module {
             item_id: ModuleItemId("mi_004".to_string()),
             module_def_id: "module_engineering_lab".to_string(),
+            wear: 0.0,
         },
         InventoryItem::Module {
             item_id: ModuleItemId("mi_008".to_string()),
             module_def_id: "module_manufacturing_lab".to_string(),
+            wear: 0.0,
         },
         InventoryItem::Module {
             item_id: ModuleItemId("mi_005".to_string()),
             module_def_id: "module_basic_iron_refinery".to_string(),
+            wear: 0.0,
         },
         InventoryItem::Module {
             item_id: ModuleItemId("mi_006".to_string()),
             module_def_id: "module_basic_assembler".to_string(),
+            wear: 0.0,
         },
         InventoryItem::Module {
             item_id: ModuleItemId("mi_007".to_string()),
             module_def_id: "module_maintenance_bay".to_string(),
+            wear: 0.0,
         },
         InventoryItem::Component {
             component_id: ComponentId("repair_kit".to_string()),
@@ -568,6 +577,8 @@ fn ships_built_after_tech_unlock_and_trade_available() {
             bonuses: vec![],
             required_tech: None,
             tags: vec![],
+            hold_partitions: std::collections::BTreeMap::new(),
+            replacement_value: 0.0,
         },
     );
 
@@ -586,6 +597,9 @@ fn ships_built_after_tech_unlock_and_trade_available() {
         ],
         outputs: vec![OutputSpec::Ship {
             hull_id: HullId("hull_test_ship".to_string()),
+            class_id: None,
+            role: None,
+            owner: sim_core::ShipOwnerRule::StationOwner,
         }],
         efficiency: 1.0,
         thermal_req: None,
@@ -609,6 +623,7 @@ fn ships_built_after_tech_unlock_and_trade_available() {
                 assembly_interval_ticks: 336, // 20160 / 60
                 recipes: vec![RecipeId("recipe_basic_mining_shuttle".to_string())],
                 max_stock: HashMap::new(),
+                parallel_slots: 1,
             }))
             .roles(vec!["shipyard"])
             .build(),
@@ -662,6 +677,7 @@ fn ships_built_after_tech_unlock_and_trade_available() {
     station.core.inventory.push(InventoryItem::Module {
         item_id: ModuleItemId("mi_shipyard".to_string()),
         module_def_id: "module_shipyard".to_string(),
+        wear: 0.0,
     });
 
     // Pre-stock Fe so shipyard has materials
@@ -1047,9 +1063,15 @@ fn manufacturing_pipeline_ore_to_cast_part() {
                     module_type_index: Default::default(),
                     module_id_index: HashMap::new(),
                     power_budget_cache: Default::default(),
+                    production_windows: std::collections::BTreeMap::new(),
+                    life_support_failing: false,
                 },
                 leaders: Vec::new(),
                 frame_id: None,
+                owner: PrincipalId("principal_autopilot".to_string()),
+                expansions: sim_core::StationExpansions::default(),
+                power_policy: sim_core::PowerPolicy::default(),
+                history: std::collections::VecDeque::new(),
             },
         )]
         .into_iter()
@@ -1061,8 +1083,10 @@ fn manufacturing_pipeline_ore_to_cast_part() {
             data_pool: sim_core::AHashMap::default(),
             evidence: sim_core::AHashMap::default(),
             action_counts: sim_core::AHashMap::default(),
+            queue: Vec::new(),
         },
         balance: 1_000_000.0,
+        loan: sim_core::LoanState::default(),
         export_revenue_total: 0.0,
         export_count: 0,
         counters: Counters {
@@ -1080,7 +1104,20 @@ fn manufacturing_pipeline_ore_to_cast_part() {
         transfer_count: 0,
         progression: Default::default(),
         strategy_config: Default::default(),
+        import_orders: Vec::new(),
+        freight_orders: Vec::new(),
+        freight_routes: Vec::new(),
+        market: sim_core::MarketState::default(),
+        contracts: Vec::new(),
         body_cache: sim_core::build_body_cache(&content.solar_system.bodies),
+        jettisoned_slag_kg: std::collections::BTreeMap::new(),
+        solar_storms: std::collections::BTreeMap::new(),
+        claims: std::collections::BTreeMap::new(),
+        hotspots: sim_core::internals::HotspotWindow::default(),
+        projects: Vec::new(),
+        station_builds: Vec::new(),
+        belts: std::collections::BTreeMap::new(),
+        pending_commands: Vec::new(),
     };
     // Rebuild indices
     for station in state.stations.values_mut() {
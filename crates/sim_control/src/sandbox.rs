@@ -0,0 +1,183 @@
+//! Fault isolation for untrusted controllers.
+//!
+//! `SandboxedController` wraps a scripted or remote `CommandSource` so that
+//! a hang, an error or a panic costs that controller one tick instead of the
+//! run. Each call gets a wall-clock deadline (see
+//! `CommandSource::set_deadline`) and runs under `catch_unwind`. On a panic,
+//! a reported fault or an overrun, the tick's commands are discarded and a
+//! `ControllerFault` is recorded for the driver to log and emit as an event.
+//! The built-in autopilot is trusted and not wrapped.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use sim_core::{CommandEnvelope, Event, EventEnvelope, EventId, GameContent, GameState};
+
+use crate::CommandSource;
+
+/// One failed `generate_commands` call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ControllerFault {
+    pub controller: String,
+    pub tick: u64,
+    pub message: String,
+}
+
+impl ControllerFault {
+    /// The fault as a `ControllerFault` event, numbered from `state`'s
+    /// event counter.
+    pub fn to_event(&self, state: &mut GameState) -> EventEnvelope {
+        let id = EventId(state.counters.next_event_id);
+        state.counters.next_event_id += 1;
+        EventEnvelope {
+            id,
+            tick: self.tick,
+            event: Event::ControllerFault {
+                controller: self.controller.clone(),
+                message: self.message.clone(),
+            },
+        }
+    }
+}
+
+pub struct SandboxedController<C> {
+    inner: C,
+    name: String,
+    time_limit: Duration,
+    faults: Vec<ControllerFault>,
+}
+
+impl<C: CommandSource> SandboxedController<C> {
+    pub fn new(inner: C, name: impl Into<String>, time_limit: Duration) -> Self {
+        Self {
+            inner,
+            name: name.into(),
+            time_limit,
+            faults: Vec::new(),
+        }
+    }
+
+    /// Drain the faults recorded since the last call.
+    pub fn take_faults(&mut self) -> Vec<ControllerFault> {
+        std::mem::take(&mut self.faults)
+    }
+
+    fn run(
+        &mut self,
+        state: &GameState,
+        content: &GameContent,
+        next_command_id: &mut u64,
+    ) -> Result<Vec<CommandEnvelope>, String> {
+        let started = Instant::now();
+        self.inner.set_deadline(Some(started + self.time_limit));
+        // Ids are only consumed when the commands are kept.
+        let mut ids = *next_command_id;
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            self.inner.generate_commands(state, content, &mut ids)
+        }));
+        self.inner.set_deadline(None);
+        let commands = result.map_err(|payload| panic_message(payload.as_ref()))?;
+        if let Some(message) = self.inner.take_fault() {
+            return Err(message);
+        }
+        let elapsed = started.elapsed();
+        if elapsed > self.time_limit {
+            return Err(format!(
+                "took {elapsed:?}, over the {:?} limit",
+                self.time_limit
+            ));
+        }
+        *next_command_id = ids;
+        Ok(commands)
+    }
+}
+
+impl<C: CommandSource> CommandSource for SandboxedController<C> {
+    fn generate_commands(
+        &mut self,
+        state: &GameState,
+        content: &GameContent,
+        next_command_id: &mut u64,
+    ) -> Vec<CommandEnvelope> {
+        match self.run(state, content, next_command_id) {
+            Ok(commands) => commands,
+            Err(message) => {
+                self.faults.push(ControllerFault {
+                    controller: self.name.clone(),
+                    tick: state.meta.tick,
+                    message,
+                });
+                Vec::new()
+            }
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    let detail = payload
+        .downcast_ref::<&str>()
+        .map(|s| (*s).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "non-string payload".to_string());
+    format!("panicked: {detail}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim_core::test_fixtures::{base_content, base_state};
+
+    /// Panics on its first call, then sleeps past any deadline, then works.
+    struct Flaky {
+        calls: u32,
+    }
+
+    impl CommandSource for Flaky {
+        fn generate_commands(
+            &mut self,
+            state: &GameState,
+            _content: &GameContent,
+            next_command_id: &mut u64,
+        ) -> Vec<CommandEnvelope> {
+            self.calls += 1;
+            match self.calls {
+                1 => panic!("boom"),
+                2 => std::thread::sleep(Duration::from_millis(30)),
+                _ => {}
+            }
+            let id = *next_command_id;
+            *next_command_id += 1;
+            vec![CommandEnvelope {
+                id: sim_core::CommandId(id),
+                issued_by: sim_core::PrincipalId("principal_test".to_string()),
+                issued_tick: state.meta.tick,
+                execute_at_tick: state.meta.tick,
+                command: sim_core::Command::JettisonSlag {
+                    station_id: state.stations.keys().next().unwrap().clone(),
+                },
+            }]
+        }
+    }
+
+    #[test]
+    fn faults_skip_the_tick_and_the_controller_recovers() {
+        let content = base_content();
+        let state = base_state(&content);
+        let mut sandbox =
+            SandboxedController::new(Flaky { calls: 0 }, "flaky", Duration::from_millis(10));
+        let mut next_id = 0;
+
+        let first = sandbox.generate_commands(&state, &content, &mut next_id);
+        let second = sandbox.generate_commands(&state, &content, &mut next_id);
+        let third = sandbox.generate_commands(&state, &content, &mut next_id);
+
+        assert!(first.is_empty() && second.is_empty());
+        assert_eq!(third.len(), 1);
+        assert_eq!(third[0].id.0, 0, "discarded calls consume no ids");
+        let faults = sandbox.take_faults();
+        assert_eq!(faults.len(), 2);
+        assert_eq!(faults[0].message, "panicked: boom");
+        assert!(faults[1].message.contains("over the"));
+        assert!(sandbox.take_faults().is_empty());
+    }
+}
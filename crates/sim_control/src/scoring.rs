@@ -0,0 +1,72 @@
+//! Weighted candidate scoring for the autopilot.
+//!
+//! Decisions that pick among candidates (which asteroid to mine, which tech
+//! a lab works on) describe each candidate by a few terms normalized to
+//! 0..1 and rank by the weighted sum, with the weights taken from
+//! `AutopilotConfig.weights`. Purchases are gated by one budget cap,
+//! optionally scaled by the purchase's urgency. Because every knob is a
+//! config field, `sim_bench sweep` can tune them instead of editing
+//! constants.
+
+use sim_core::{AutopilotWeights, GameState};
+
+/// Sum of `weight × term`. Pass terms that count against a candidate
+/// negated.
+pub(crate) fn weighted_score(terms: &[(f64, f64)]) -> f64 {
+    terms.iter().map(|(weight, term)| weight * term).sum()
+}
+
+/// `value` relative to the largest among the candidates, 0 when that is 0.
+pub(crate) fn normalized(value: f64, max: f64) -> f64 {
+    if max > 0.0 {
+        value / max
+    } else {
+        0.0
+    }
+}
+
+/// Sort best first. The sort is stable, so equal scores keep their
+/// incoming order (callers pass candidates in a deterministic order).
+pub(crate) fn rank_by_score<T>(candidates: &mut [(T, f64)]) {
+    candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+}
+
+/// Most the autopilot spends on one purchase: `budget_cap_fraction` of the
+/// balance, scaled down by `weights.budget_urgency` for a purchase whose
+/// `urgency` (0..1) is below 1.
+pub(crate) fn spend_limit(state: &GameState, weights: &AutopilotWeights, urgency: f64) -> f64 {
+    let scale = 1.0 - weights.budget_urgency + weights.budget_urgency * urgency;
+    state.balance * state.strategy_config.budget_cap_fraction * scale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rank_keeps_incoming_order_for_ties() {
+        let mut ranked = vec![("a", 0.5), ("b", 0.9), ("c", 0.5)];
+        rank_by_score(&mut ranked);
+        let order: Vec<&str> = ranked.iter().map(|(id, _)| *id).collect();
+        assert_eq!(order, ["b", "a", "c"]);
+    }
+
+    #[test]
+    fn distance_weight_can_outrank_value() {
+        let near_poor = weighted_score(&[(1.0, normalized(400.0, 1000.0)), (0.8, -0.1)]);
+        let far_rich = weighted_score(&[(1.0, normalized(1000.0, 1000.0)), (0.8, -1.0)]);
+        assert!(near_poor > far_rich);
+        assert!((normalized(5.0, 0.0)).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn full_urgency_gets_the_whole_cap() {
+        let content = sim_core::test_fixtures::base_content();
+        let mut state = sim_core::test_fixtures::base_state(&content);
+        state.balance = 1_000_000.0;
+        state.strategy_config.budget_cap_fraction = 0.05;
+        let weights = AutopilotWeights::default();
+        assert!((spend_limit(&state, &weights, 1.0) - 50_000.0).abs() < 1e-6);
+        assert!((spend_limit(&state, &weights, 0.0) - 25_000.0).abs() < 1e-6);
+    }
+}
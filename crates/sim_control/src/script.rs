@@ -0,0 +1,348 @@
+//! Rhai-scripted controller (`scripting` feature).
+//!
+//! A script defines `fn decide(state, content)` returning an array of
+//! commands. `state` and `content` are copies of `GameState` /
+//! `GameContent` in their JSON shape, so writes to them have no effect.
+//! `this` inside `decide` is a map that persists across calls for the
+//! script's own memory. Commands are maps in `Command`'s wire format
+//! (`#{ JettisonSlag: #{ station_id: "station_0001" } }`), built by hand or
+//! with the registered builders:
+//!
+//! - `command(name, fields)` — any `Command` variant by name
+//! - `assign_task(ship_id, task)` — `task` in `TaskKind`'s wire format
+//! - `survey(ship_id, site_id)`, `deep_scan(ship_id, asteroid_id)`
+//! - `set_module_enabled(station_id, module_id, enabled)`
+//! - `set_module_threshold(station_id, module_id, threshold_kg)`
+//! - `jettison_slag(station_id)`
+//!
+//! Top-level statements run once when the script is loaded. Commands are
+//! issued as the autopilot principal, so ownership checks match
+//! `AutopilotController`. A deadline set through `CommandSource::set_deadline`
+//! terminates a long-running `decide` with an error.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use rhai::{Array, CallFnOptions, Dynamic, Engine, EvalAltResult, Map, Scope, AST};
+use sim_core::{
+    Command, CommandEnvelope, FacilityId, GameContent, GameState, ModuleInstanceId, PrincipalId,
+    StationId,
+};
+
+use crate::behaviors::{make_cmd, AUTOPILOT_OWNER};
+use crate::CommandSource;
+
+/// Name of the entry point every script must define.
+const DECIDE_FN: &str = "decide";
+
+/// Script operations between deadline checks.
+const DEADLINE_CHECK_OPS: u64 = 1024;
+
+#[derive(Debug)]
+pub enum ScriptError {
+    Io(std::io::Error),
+    Parse(rhai::ParseError),
+    /// The script raised an error or returned something that is not an
+    /// array of commands.
+    Eval(String),
+    /// `decide` ran past the deadline set with `set_deadline`.
+    TimedOut,
+}
+
+impl std::fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "reading script: {err}"),
+            Self::Parse(err) => write!(f, "parsing script: {err}"),
+            Self::Eval(message) => write!(f, "running script: {message}"),
+            Self::TimedOut => write!(f, "running script: time limit exceeded"),
+        }
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+/// `CommandSource` backed by a Rhai script.
+pub struct ScriptController {
+    engine: Engine,
+    ast: AST,
+    scope: Scope<'static>,
+    /// The script's `this` in `decide`.
+    memory: Dynamic,
+    /// `content` as passed to the script, converted once on first use.
+    content_view: Option<Dynamic>,
+    owner: PrincipalId,
+    /// First error since the last `take_error`; `decide` is not called
+    /// again until it is taken.
+    error: Option<ScriptError>,
+    /// Shared with the engine's progress callback.
+    deadline: Arc<Mutex<Option<Instant>>>,
+}
+
+impl ScriptController {
+    pub fn from_file(path: &Path) -> Result<Self, ScriptError> {
+        let source = std::fs::read_to_string(path).map_err(ScriptError::Io)?;
+        Self::from_source(&source)
+    }
+
+    pub fn from_source(source: &str) -> Result<Self, ScriptError> {
+        let deadline = Arc::new(Mutex::new(None));
+        let mut engine = script_engine();
+        let progress_deadline = Arc::clone(&deadline);
+        engine.on_progress(move |ops| {
+            if ops % DEADLINE_CHECK_OPS != 0 {
+                return None;
+            }
+            let deadline = *progress_deadline.lock().ok()?;
+            deadline
+                .is_some_and(|deadline| Instant::now() > deadline)
+                .then_some(Dynamic::UNIT)
+        });
+        let ast = engine.compile(source).map_err(ScriptError::Parse)?;
+        if !ast.iter_functions().any(|f| f.name == DECIDE_FN) {
+            return Err(ScriptError::Eval(format!(
+                "script does not define `fn {DECIDE_FN}(state, content)`"
+            )));
+        }
+        let mut scope = Scope::new();
+        engine
+            .run_ast_with_scope(&mut scope, &ast)
+            .map_err(|err| ScriptError::Eval(err.to_string()))?;
+        Ok(Self {
+            engine,
+            ast,
+            scope,
+            memory: Dynamic::from_map(Map::new()),
+            content_view: None,
+            owner: PrincipalId(AUTOPILOT_OWNER.to_string()),
+            error: None,
+            deadline,
+        })
+    }
+
+    /// Take the pending script error, if any, and resume calling `decide`.
+    pub fn take_error(&mut self) -> Option<ScriptError> {
+        self.error.take()
+    }
+
+    fn decide(
+        &mut self,
+        state: &GameState,
+        content: &GameContent,
+    ) -> Result<Vec<Command>, ScriptError> {
+        if self.content_view.is_none() {
+            self.content_view = Some(rhai::serde::to_dynamic(content).map_err(eval_error)?);
+        }
+        let content_view = self.content_view.clone().unwrap_or_default();
+        let state_view = rhai::serde::to_dynamic(state).map_err(eval_error)?;
+        let options = CallFnOptions::new()
+            .eval_ast(false)
+            .bind_this_ptr(&mut self.memory);
+        let result: Array = self
+            .engine
+            .call_fn_with_options(
+                options,
+                &mut self.scope,
+                &self.ast,
+                DECIDE_FN,
+                (state_view, content_view),
+            )
+            .map_err(eval_error)?;
+        result
+            .iter()
+            .map(|value| rhai::serde::from_dynamic::<Command>(value).map_err(eval_error))
+            .collect()
+    }
+}
+
+impl CommandSource for ScriptController {
+    fn generate_commands(
+        &mut self,
+        state: &GameState,
+        content: &GameContent,
+        next_command_id: &mut u64,
+    ) -> Vec<CommandEnvelope> {
+        if self.error.is_some() {
+            return Vec::new();
+        }
+        match self.decide(state, content) {
+            Ok(commands) => commands
+                .into_iter()
+                .map(|command| make_cmd(&self.owner, state.meta.tick, next_command_id, command))
+                .collect(),
+            Err(err) => {
+                self.error = Some(err);
+                Vec::new()
+            }
+        }
+    }
+
+    fn set_deadline(&mut self, deadline: Option<Instant>) {
+        if let Ok(mut slot) = self.deadline.lock() {
+            *slot = deadline;
+        }
+    }
+
+    fn take_fault(&mut self) -> Option<String> {
+        self.take_error().map(|err| err.to_string())
+    }
+}
+
+#[allow(clippy::needless_pass_by_value, clippy::boxed_local)] // map_err hands over the boxed error
+fn eval_error(err: Box<EvalAltResult>) -> ScriptError {
+    match *err {
+        // Only the deadline check terminates scripts.
+        EvalAltResult::ErrorTerminated(..) => ScriptError::TimedOut,
+        err => ScriptError::Eval(err.to_string()),
+    }
+}
+
+type BuilderResult = Result<Dynamic, Box<EvalAltResult>>;
+
+fn to_command(command: &Command) -> BuilderResult {
+    rhai::serde::to_dynamic(command)
+}
+
+fn script_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.register_fn("command", |name: &str, fields: Map| -> BuilderResult {
+        let mut wrapper = Map::new();
+        wrapper.insert(name.into(), Dynamic::from_map(fields));
+        let command: Command = rhai::serde::from_dynamic(&Dynamic::from_map(wrapper))?;
+        to_command(&command)
+    });
+    engine.register_fn(
+        "assign_task",
+        |ship_id: &str, task: Dynamic| -> BuilderResult {
+            to_command(&Command::AssignShipTask {
+                ship_id: sim_core::ShipId(ship_id.to_string()),
+                task_kind: rhai::serde::from_dynamic(&task)?,
+            })
+        },
+    );
+    engine.register_fn("survey", |ship_id: &str, site_id: &str| {
+        to_command(&Command::AssignShipTask {
+            ship_id: sim_core::ShipId(ship_id.to_string()),
+            task_kind: sim_core::TaskKind::Survey {
+                site: sim_core::SiteId(site_id.to_string()),
+            },
+        })
+    });
+    engine.register_fn("deep_scan", |ship_id: &str, asteroid_id: &str| {
+        to_command(&Command::AssignShipTask {
+            ship_id: sim_core::ShipId(ship_id.to_string()),
+            task_kind: sim_core::TaskKind::DeepScan {
+                asteroid: sim_core::AsteroidId(asteroid_id.to_string()),
+            },
+        })
+    });
+    engine.register_fn(
+        "set_module_enabled",
+        |station_id: &str, module_id: &str, enabled: bool| {
+            to_command(&Command::SetModuleEnabled {
+                facility_id: station_facility(station_id),
+                module_id: ModuleInstanceId(module_id.to_string()),
+                enabled,
+            })
+        },
+    );
+    engine.register_fn(
+        "set_module_threshold",
+        |station_id: &str, module_id: &str, threshold_kg: f64| {
+            #[allow(clippy::cast_possible_truncation)]
+            to_command(&Command::SetModuleThreshold {
+                facility_id: station_facility(station_id),
+                module_id: ModuleInstanceId(module_id.to_string()),
+                threshold_kg: threshold_kg as f32,
+            })
+        },
+    );
+    engine.register_fn("jettison_slag", |station_id: &str| {
+        to_command(&Command::JettisonSlag {
+            station_id: StationId(station_id.to_string()),
+        })
+    });
+    engine
+}
+
+fn station_facility(station_id: &str) -> FacilityId {
+    FacilityId::Station(StationId(station_id.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim_core::test_fixtures::{base_content, base_state};
+
+    #[test]
+    fn script_issues_commands_and_keeps_memory() {
+        let content = base_content();
+        let state = base_state(&content);
+        let station_id = state.stations.keys().next().unwrap().0.clone();
+        let mut controller = ScriptController::from_source(
+            r"
+            fn decide(state, content) {
+                this.calls = (this.calls ?? 0) + 1;
+                if this.calls != 2 { return []; }
+                state.stations.keys().map(|id| jettison_slag(id))
+            }
+            ",
+        )
+        .unwrap();
+
+        let mut next_id = 0;
+        assert!(controller
+            .generate_commands(&state, &content, &mut next_id)
+            .is_empty());
+        let commands = controller.generate_commands(&state, &content, &mut next_id);
+
+        assert!(controller.take_error().is_none());
+        assert_eq!(commands.len(), 1);
+        assert!(matches!(
+            &commands[0].command,
+            Command::JettisonSlag { station_id: id } if id.0 == station_id
+        ));
+        assert_eq!(commands[0].issued_by.0, AUTOPILOT_OWNER);
+        assert_eq!(next_id, 1);
+    }
+
+    #[test]
+    fn invalid_command_is_reported() {
+        let content = base_content();
+        let state = base_state(&content);
+        let mut controller = ScriptController::from_source(
+            r#"fn decide(state, content) { [command("NoSuchCommand", #{})] }"#,
+        )
+        .unwrap();
+
+        let mut next_id = 0;
+        assert!(controller
+            .generate_commands(&state, &content, &mut next_id)
+            .is_empty());
+        assert!(matches!(
+            controller.take_error(),
+            Some(ScriptError::Eval(_))
+        ));
+    }
+
+    #[test]
+    fn runaway_script_stops_at_deadline() {
+        let content = base_content();
+        let state = base_state(&content);
+        let mut controller =
+            ScriptController::from_source("fn decide(state, content) { loop {} }").unwrap();
+
+        controller.set_deadline(Some(Instant::now() + std::time::Duration::from_millis(20)));
+        let commands = controller.generate_commands(&state, &content, &mut 0);
+
+        assert!(commands.is_empty());
+        let fault = controller.take_fault().expect("deadline should fault");
+        assert!(fault.contains("time limit exceeded"), "{fault}");
+    }
+
+    #[test]
+    fn script_without_decide_is_rejected() {
+        assert!(ScriptController::from_source("let x = 1;").is_err());
+    }
+}
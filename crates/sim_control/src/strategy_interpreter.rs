@@ -177,7 +177,7 @@ fn combine_weights(config: &StrategyConfig, urgency: &PriorityWeights) -> Priori
 fn apply_hysteresis(scores: &mut PriorityWeights, previous: Option<&ConcernPriorities>) {
     let Some(prev) = previous else { return };
     let prev_vec = prev.to_vec();
-    for (score, prev_value) in scores.fields_mut().into_iter().zip(prev_vec.into_iter()) {
+    for (score, prev_value) in scores.fields_mut().into_iter().zip(prev_vec) {
         if prev_value >= CONCERN_ACTIVE_THRESHOLD {
             *score += HYSTERESIS_BONUS;
         }
@@ -190,7 +190,7 @@ fn refresh_last_serviced(
     scores: &PriorityWeights,
     current_tick: u64,
 ) {
-    for (slot, score) in last_serviced.iter_mut().zip(scores.to_vec().into_iter()) {
+    for (slot, score) in last_serviced.iter_mut().zip(scores.to_vec()) {
         if score >= CONCERN_ACTIVE_THRESHOLD {
             *slot = Some(current_tick);
         }
@@ -0,0 +1,225 @@
+//! Ranked asteroid queries.
+//!
+//! Where [`crate::query`] filters snapshot collections with hand-written
+//! expressions, these are typed queries for callers that ask the same
+//! question every tick, such as "the ten most valuable Fe asteroids near
+//! `belt_inner`". Results are fully ordered (ties broken by asteroid id),
+//! so the autopilot ranks its mining and deep-scan targets with them
+//! without giving up determinism. The daemon serves the same queries at
+//! `GET /api/v1/queries/asteroids`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{compute_entity_absolute, AsteroidId, AsteroidState, BodyId, GameState, Position};
+
+/// Filter on whether an asteroid's composition has been deep-scanned.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompositionFilter {
+    #[default]
+    Any,
+    Known,
+    Unknown,
+}
+
+/// Result order. Every order falls back to ascending asteroid id.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum AsteroidSort {
+    /// Most kg of the element first (`mass_kg` × known fraction; unknown
+    /// compositions count as none).
+    ElementValue(String),
+    /// Heaviest first.
+    #[default]
+    Mass,
+    /// Nearest to [`AsteroidQuery::near`] first. Without `near`, id order.
+    Distance,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AsteroidQuery {
+    /// Only asteroids orbiting this body.
+    pub body: Option<BodyId>,
+    pub composition: CompositionFilter,
+    /// Reference point for distances.
+    pub near: Option<Position>,
+    pub sort: AsteroidSort,
+    /// Return at most this many results.
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AsteroidCandidate {
+    pub id: AsteroidId,
+    pub body: BodyId,
+    pub mass_kg: f32,
+    /// Kg of the sort element, for [`AsteroidSort::ElementValue`].
+    pub element_kg: Option<f32>,
+    /// Distance from [`AsteroidQuery::near`] in micro-AU, when given.
+    pub distance_au_um: Option<u64>,
+}
+
+/// Mining value: `mass_kg` × the element's known fraction.
+pub fn element_mining_value(asteroid: &AsteroidState, element: &str) -> f32 {
+    asteroid.mass_kg
+        * asteroid
+            .knowledge
+            .composition
+            .as_ref()
+            .and_then(|composition| composition.get(element))
+            .copied()
+            .unwrap_or(0.0)
+}
+
+/// Asteroids matching `query`, best first. Depleted asteroids are never
+/// returned.
+pub fn asteroid_candidates(state: &GameState, query: &AsteroidQuery) -> Vec<AsteroidCandidate> {
+    let near = query
+        .near
+        .as_ref()
+        .filter(|near| state.body_cache.contains_key(&near.parent_body))
+        .map(|near| compute_entity_absolute(near, &state.body_cache));
+    let mut ranked: Vec<(Option<u128>, AsteroidCandidate)> = state
+        .asteroids
+        .values()
+        .filter(|asteroid| asteroid.mass_kg > 0.0)
+        .filter(|asteroid| {
+            query
+                .body
+                .as_ref()
+                .is_none_or(|body| asteroid.position.parent_body == *body)
+        })
+        .filter(|asteroid| match query.composition {
+            CompositionFilter::Any => true,
+            CompositionFilter::Known => asteroid.knowledge.composition.is_some(),
+            CompositionFilter::Unknown => asteroid.knowledge.composition.is_none(),
+        })
+        .map(|asteroid| {
+            // Squared distance sorts exactly; the reported distance is rounded.
+            let distance_sq = near
+                .filter(|_| {
+                    state
+                        .body_cache
+                        .contains_key(&asteroid.position.parent_body)
+                })
+                .map(|near| {
+                    near.distance_squared(compute_entity_absolute(
+                        &asteroid.position,
+                        &state.body_cache,
+                    ))
+                });
+            let element_kg = match &query.sort {
+                AsteroidSort::ElementValue(element) => {
+                    Some(element_mining_value(asteroid, element))
+                }
+                AsteroidSort::Mass | AsteroidSort::Distance => None,
+            };
+            let candidate = AsteroidCandidate {
+                id: asteroid.id.clone(),
+                body: asteroid.position.parent_body.clone(),
+                mass_kg: asteroid.mass_kg,
+                element_kg,
+                distance_au_um: distance_sq.map(crate::spatial::integer_sqrt),
+            };
+            (distance_sq, candidate)
+        })
+        .collect();
+
+    ranked.sort_by(|(a_distance, a), (b_distance, b)| {
+        let by_key = match query.sort {
+            AsteroidSort::ElementValue(_) => b
+                .element_kg
+                .unwrap_or(0.0)
+                .total_cmp(&a.element_kg.unwrap_or(0.0)),
+            AsteroidSort::Mass => b.mass_kg.total_cmp(&a.mass_kg),
+            // Asteroids without a distance go last.
+            AsteroidSort::Distance => a_distance
+                .is_none()
+                .cmp(&b_distance.is_none())
+                .then(a_distance.cmp(b_distance)),
+        };
+        by_key.then_with(|| a.id.0.cmp(&b.id.0))
+    });
+    if let Some(limit) = query.limit {
+        ranked.truncate(limit);
+    }
+    ranked.into_iter().map(|(_, candidate)| candidate).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{base_content, base_state, test_position};
+    use crate::{AsteroidKnowledge, CompositionVec, RadiusAuMicro};
+
+    fn add_asteroid(state: &mut GameState, id: &str, mass_kg: f32, fe: Option<f32>) {
+        let composition = fe.map(|fe| CompositionVec::from([("Fe".to_string(), fe)]));
+        state.asteroids.insert(
+            AsteroidId(id.to_string()),
+            AsteroidState {
+                id: AsteroidId(id.to_string()),
+                position: test_position(),
+                true_composition: CompositionVec::from([("Fe".to_string(), fe.unwrap_or(0.5))]),
+                anomaly_tags: vec![],
+                mass_kg,
+                knowledge: AsteroidKnowledge {
+                    tag_beliefs: vec![],
+                    composition,
+                    layers: None,
+                    composition_sigma: None,
+                    deep_scan_count: 0,
+                },
+                layers: None,
+            },
+        );
+    }
+
+    fn ids(candidates: &[AsteroidCandidate]) -> Vec<&str> {
+        candidates.iter().map(|c| c.id.0.as_str()).collect()
+    }
+
+    #[test]
+    fn element_value_ranks_known_asteroids_with_id_tiebreak() {
+        let content = base_content();
+        let mut state = base_state(&content);
+        add_asteroid(&mut state, "a", 1000.0, Some(0.5));
+        add_asteroid(&mut state, "b", 2000.0, Some(0.1));
+        add_asteroid(&mut state, "c", 500.0, Some(1.0));
+        add_asteroid(&mut state, "d", 9000.0, None);
+
+        let query = AsteroidQuery {
+            composition: CompositionFilter::Known,
+            sort: AsteroidSort::ElementValue("Fe".to_string()),
+            limit: Some(2),
+            ..AsteroidQuery::default()
+        };
+        let top = asteroid_candidates(&state, &query);
+
+        assert_eq!(ids(&top), ["a", "c"]);
+        assert_eq!(top[0].element_kg, Some(500.0));
+    }
+
+    #[test]
+    fn distance_sort_puts_nearest_first() {
+        let content = base_content();
+        let mut state = base_state(&content);
+        add_asteroid(&mut state, "far", 100.0, None);
+        add_asteroid(&mut state, "near", 100.0, None);
+        state
+            .asteroids
+            .get_mut(&AsteroidId("far".to_string()))
+            .unwrap()
+            .position
+            .radius_au_um = RadiusAuMicro(50_000);
+
+        let query = AsteroidQuery {
+            near: Some(test_position()),
+            sort: AsteroidSort::Distance,
+            ..AsteroidQuery::default()
+        };
+        let ranked = asteroid_candidates(&state, &query);
+
+        assert_eq!(ids(&ranked), ["near", "far"]);
+        assert_eq!(ranked[0].distance_au_um, Some(0));
+        assert_eq!(ranked[1].distance_au_um, Some(50_000));
+    }
+}
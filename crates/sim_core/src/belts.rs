@@ -0,0 +1,102 @@
+//! Per-node asteroid belts.
+//!
+//! Each `BeltDef` gives a zone body a shared pool of scan sites. The pool
+//! starts at `initial_budget` and every `respawn_interval_minutes` gains
+//! `respawn_amount`, up to `max_budget`. On each replenish check the belt
+//! spends its pool on new scan sites until it has `max_sites` unsurveyed
+//! sites open at its body. Surveying a site frees a slot, so a belt keeps
+//! producing for as long as it refills, rather than running dry. When any
+//! belt is defined the global `replenish_*` top-up is switched off.
+
+use rand::Rng;
+
+use crate::{AsteroidTemplateDef, BeltDef, BeltState, EventEnvelope, GameContent, GameState};
+
+/// Refill belt pools on their cadence and, on replenish ticks, turn pool
+/// budget into scan sites.
+pub(crate) fn tick_belts(
+    state: &mut GameState,
+    content: &GameContent,
+    rng: &mut impl Rng,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let tick = state.meta.tick;
+    for belt in &content.belts {
+        let respawn_ticks = content
+            .constants
+            .game_minutes_to_ticks(belt.respawn_interval_minutes);
+        let pool = state
+            .belts
+            .entry(belt.body.clone())
+            .or_insert_with(|| BeltState {
+                budget: belt.initial_budget,
+            });
+        if respawn_ticks > 0 && tick > 0 && tick.is_multiple_of(respawn_ticks) {
+            pool.budget = pool
+                .budget
+                .saturating_add(belt.respawn_amount)
+                .min(belt.max_budget);
+        }
+    }
+
+    let interval = content.constants.replenish_check_interval_ticks;
+    if interval > 0 && !tick.is_multiple_of(interval) {
+        return;
+    }
+    for belt in &content.belts {
+        spawn_from_belt(state, content, belt, rng, events);
+    }
+}
+
+fn spawn_from_belt(
+    state: &mut GameState,
+    content: &GameContent,
+    belt: &BeltDef,
+    rng: &mut impl Rng,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let Some(body) = content
+        .solar_system
+        .bodies
+        .iter()
+        .find(|b| b.id == belt.body && b.zone.is_some())
+    else {
+        return;
+    };
+    let open = u32::try_from(
+        state
+            .scan_sites
+            .iter()
+            .filter(|site| site.position.parent_body == belt.body)
+            .count(),
+    )
+    .unwrap_or(u32::MAX);
+    let budget = state.belts.get(&belt.body).map_or(0, |pool| pool.budget);
+    let count = budget.min(belt.max_sites.saturating_sub(open));
+    if count == 0 {
+        return;
+    }
+
+    let filtered: Vec<AsteroidTemplateDef>;
+    let templates = if belt.templates.is_empty() {
+        &content.asteroid_templates[..]
+    } else {
+        filtered = content
+            .asteroid_templates
+            .iter()
+            .filter(|t| belt.templates.contains(&t.id))
+            .cloned()
+            .collect();
+        &filtered[..]
+    };
+    if templates.is_empty() {
+        return;
+    }
+
+    for _ in 0..count {
+        crate::engine::spawn_scan_site(state, body, templates, rng, events);
+    }
+    if let Some(pool) = state.belts.get_mut(&belt.body) {
+        pool.budget -= count;
+    }
+}
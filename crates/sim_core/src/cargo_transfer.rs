@@ -0,0 +1,280 @@
+//! Partial cargo moves between a ship and a co-located station or ship.
+//!
+//! `Command::TransferCargo` assigns the ship a `TransferCargo` task lasting
+//! `deposit_ticks`. When it completes, each `CargoSpec` is taken from the
+//! giving side in order, bounded by the free volume left at the receiving
+//! side (in the cargo's partition, for a partitioned ship hold). Ore lots
+//! can be split; the moved share gets a fresh lot id. If the counterparty
+//! has gone or drifted out of docking range, nothing moves.
+
+use crate::tasks::{inventory_volume_m3, item_volume_m3, set_ship_idle, take_spec_from_inventory};
+use crate::{
+    CargoDirection, CargoHolder, CargoSpec, Event, EventEnvelope, GameContent, GameState,
+    HoldPartition, HoldSpace, InventoryItem, LotId, Position, ShipId, TaskKind,
+};
+
+/// Validate a `TransferCargo` command and queue the ship's task. Rejected
+/// for unknown or foreign ships, empty item lists, a missing counterparty,
+/// a ship transferring with itself, or a counterparty out of docking range.
+pub(crate) fn handle_transfer_cargo(
+    state: &GameState,
+    content: &GameContent,
+    ship_id: &ShipId,
+    task_kind: TaskKind,
+    issued_by: &crate::PrincipalId,
+    assignments: &mut Vec<(ShipId, TaskKind)>,
+) -> Result<(), String> {
+    let TaskKind::TransferCargo {
+        counterparty,
+        items,
+        ..
+    } = &task_kind
+    else {
+        return Err("not a cargo transfer task".to_string());
+    };
+    let Some(ship) = state.ships.get(ship_id) else {
+        return Err(format!("unknown ship '{}'", ship_id.0));
+    };
+    if ship.owner != *issued_by || items.is_empty() {
+        return Err("ship is not owned by the issuer or no items given".to_string());
+    }
+    if matches!(counterparty, CargoHolder::Ship(other) if other == ship_id) {
+        return Err("a ship cannot transfer cargo with itself".to_string());
+    }
+    let Some(position) = holder_position(state, counterparty) else {
+        return Err("unknown counterparty".to_string());
+    };
+    if !crate::is_co_located(
+        &ship.position,
+        position,
+        &state.body_cache,
+        content.constants.docking_range_au_um,
+    ) {
+        return Err("counterparty is out of docking range".to_string());
+    }
+    assignments.push((ship_id.clone(), task_kind));
+    Ok(())
+}
+
+/// Finish a `TransferCargo` task: move what fits, emit `CargoTransferred`,
+/// and idle the ship.
+pub(crate) fn resolve_transfer_cargo(
+    state: &mut GameState,
+    ship_id: &ShipId,
+    counterparty: &CargoHolder,
+    direction: CargoDirection,
+    specs: &[CargoSpec],
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let current_tick = state.meta.tick;
+    let ship_holder = CargoHolder::Ship(ship_id.clone());
+    let (from, to) = match direction {
+        CargoDirection::Load => (counterparty, &ship_holder),
+        CargoDirection::Unload => (&ship_holder, counterparty),
+    };
+
+    let in_range = match (
+        state.ships.get(ship_id),
+        holder_position(state, counterparty),
+    ) {
+        (Some(ship), Some(position)) => crate::is_co_located(
+            &ship.position,
+            position,
+            &state.body_cache,
+            content.constants.docking_range_au_um,
+        ),
+        _ => false,
+    };
+
+    let mut moved = Vec::new();
+    if in_range {
+        let mut space = hold_space(state, to, content);
+        let mut next_lot_id = state.counters.next_lot_id;
+        if let Some(inventory) = holder_inventory(state, from) {
+            for spec in specs {
+                let partition = HoldPartition::of_cargo(spec);
+                let free_m3 = space.free_for(partition);
+                if free_m3 <= 0.0 {
+                    continue;
+                }
+                let taken = take_cargo(inventory, spec, free_m3, &mut next_lot_id, content);
+                space.fill(partition, inventory_volume_m3(&taken, content));
+                moved.extend(taken);
+            }
+        }
+        state.counters.next_lot_id = next_lot_id;
+        if !moved.is_empty() {
+            if let Some(inventory) = holder_inventory(state, to) {
+                inventory.extend(moved.clone());
+            }
+            for holder in [from, to] {
+                if let CargoHolder::Station(station_id) = holder {
+                    if let Some(station) = state.stations.get_mut(station_id) {
+                        station.invalidate_volume_cache();
+                    }
+                }
+            }
+        }
+    }
+
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::CargoTransferred {
+            ship_id: ship_id.clone(),
+            counterparty: counterparty.clone(),
+            direction,
+            items: moved,
+        },
+    ));
+    set_ship_idle(state, ship_id, current_tick);
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::TaskCompleted {
+            ship_id: ship_id.clone(),
+            task_kind: "TransferCargo".to_string(),
+            target: Some(counterparty_id(counterparty)),
+        },
+    ));
+}
+
+fn counterparty_id(holder: &CargoHolder) -> String {
+    match holder {
+        CargoHolder::Ship(ship_id) => ship_id.0.clone(),
+        CargoHolder::Station(station_id) => station_id.0.clone(),
+    }
+}
+
+fn holder_position<'a>(state: &'a GameState, holder: &CargoHolder) -> Option<&'a Position> {
+    match holder {
+        CargoHolder::Ship(ship_id) => state.ships.get(ship_id).map(|ship| &ship.position),
+        CargoHolder::Station(station_id) => state
+            .stations
+            .get(station_id)
+            .map(|station| &station.position),
+    }
+}
+
+fn holder_inventory<'a>(
+    state: &'a mut GameState,
+    holder: &CargoHolder,
+) -> Option<&'a mut Vec<InventoryItem>> {
+    match holder {
+        CargoHolder::Ship(ship_id) => state.ships.get_mut(ship_id).map(|ship| &mut ship.inventory),
+        CargoHolder::Station(station_id) => state
+            .stations
+            .get_mut(station_id)
+            .map(|station| &mut station.core.inventory),
+    }
+}
+
+fn hold_space(state: &mut GameState, holder: &CargoHolder, content: &GameContent) -> HoldSpace {
+    match holder {
+        CargoHolder::Ship(ship_id) => state.ships.get(ship_id).map_or_else(
+            || HoldSpace::undivided(0.0),
+            |ship| HoldSpace::of_ship(ship, content),
+        ),
+        CargoHolder::Station(station_id) => {
+            HoldSpace::undivided(state.stations.get_mut(station_id).map_or(0.0, |station| {
+                station.core.cargo_capacity_m3 - station.used_volume_m3(content)
+            }))
+        }
+    }
+}
+
+/// Remove the cargo `spec` selects from `inventory`, bounded by `free_m3`.
+fn take_cargo(
+    inventory: &mut Vec<InventoryItem>,
+    spec: &CargoSpec,
+    free_m3: f32,
+    next_lot_id: &mut u64,
+    content: &GameContent,
+) -> Vec<InventoryItem> {
+    match spec {
+        CargoSpec::Ore { lot_id, kg } => {
+            take_ore(inventory, lot_id, *kg, free_m3, next_lot_id, content)
+        }
+        CargoSpec::Slag { kg } => take_slag(inventory, *kg, free_m3, content),
+        CargoSpec::Item(trade_spec) => {
+            take_spec_from_inventory(inventory, trade_spec, free_m3, content)
+        }
+    }
+}
+
+fn take_ore(
+    inventory: &mut Vec<InventoryItem>,
+    lot_id: &LotId,
+    requested_kg: Option<f32>,
+    free_m3: f32,
+    next_lot_id: &mut u64,
+    content: &GameContent,
+) -> Vec<InventoryItem> {
+    let Some(index) = inventory
+        .iter()
+        .position(|item| matches!(item, InventoryItem::Ore { lot_id: id, .. } if id == lot_id))
+    else {
+        return Vec::new();
+    };
+    let lot_volume = item_volume_m3(&inventory[index], content);
+    let InventoryItem::Ore {
+        kg: lot_kg,
+        asteroid_id,
+        composition,
+        ..
+    } = &mut inventory[index]
+    else {
+        return Vec::new();
+    };
+    let fit_kg = if lot_volume > 0.0 {
+        *lot_kg * (free_m3 / lot_volume).min(1.0)
+    } else {
+        *lot_kg
+    };
+    let take_kg = requested_kg.unwrap_or(*lot_kg).min(*lot_kg).min(fit_kg);
+    if take_kg < content.constants.min_meaningful_kg {
+        return Vec::new();
+    }
+    if *lot_kg - take_kg < content.constants.min_meaningful_kg {
+        return vec![inventory.remove(index)];
+    }
+
+    crate::fixed::add_kg(lot_kg, -take_kg);
+    let split = InventoryItem::Ore {
+        lot_id: LotId(format!("lot_{:04}", *next_lot_id)),
+        asteroid_id: asteroid_id.clone(),
+        kg: take_kg,
+        composition: composition.clone(),
+    };
+    *next_lot_id += 1;
+    vec![split]
+}
+
+fn take_slag(
+    inventory: &mut Vec<InventoryItem>,
+    requested_kg: f32,
+    free_m3: f32,
+    content: &GameContent,
+) -> Vec<InventoryItem> {
+    let density = crate::tasks::element_density(content, crate::ELEMENT_SLAG);
+    let mut remaining = requested_kg.min(free_m3 * density);
+    let mut taken = Vec::new();
+    for item in inventory.iter_mut() {
+        if remaining < content.constants.min_meaningful_kg {
+            break;
+        }
+        if let InventoryItem::Slag { kg, composition } = item {
+            let take = kg.min(remaining);
+            crate::fixed::add_kg(kg, -take);
+            remaining -= take;
+            taken.push(InventoryItem::Slag {
+                kg: take,
+                composition: composition.clone(),
+            });
+        }
+    }
+    let min_kg = content.constants.min_meaningful_kg;
+    inventory.retain(|item| !matches!(item, InventoryItem::Slag { kg, .. } if *kg < min_kg));
+    taken
+}
@@ -0,0 +1,192 @@
+//! Body claims.
+//!
+//! A principal claims a body with `ClaimBody`, which grants it exclusive
+//! mining rights there: `AssignShipTask` refuses `Mine` on an asteroid at a
+//! body claimed by someone other than the ship's owner and emits
+//! `MiningRightsDenied`. A claim is backed either by a station the claimant
+//! has at the body, which is free and lasts as long as the station, or by
+//! `claim_cost` credits, which lasts `claim_duration_minutes` (0 = forever)
+//! and can be renewed by claiming again. A held claim can only be taken
+//! over by a claimant with a station at the body when the holder has none
+//! there. Claims lapse with `ClaimExpired`; `ReleaseClaim` gives one up
+//! without a refund.
+
+use crate::{
+    BodyClaim, BodyId, BodyType, ClaimBasis, Event, EventEnvelope, GameContent, GameState,
+    PrincipalId,
+};
+
+/// The principal whose claim keeps `owner` from mining at `body`, if any.
+pub fn mining_blocked_by<'a>(
+    state: &'a GameState,
+    owner: &PrincipalId,
+    body: &BodyId,
+) -> Option<&'a PrincipalId> {
+    state
+        .claims
+        .get(body)
+        .map(|claim| &claim.owner)
+        .filter(|holder| *holder != owner)
+}
+
+/// Whether `owner` has a station at `body`.
+pub fn has_station_at(state: &GameState, owner: &PrincipalId, body: &BodyId) -> bool {
+    state
+        .stations
+        .values()
+        .any(|station| station.owner == *owner && station.position.parent_body == *body)
+}
+
+/// Why a claim was turned down, or `None` if it may go ahead.
+fn claim_refusal(
+    state: &GameState,
+    content: &GameContent,
+    claimant: &PrincipalId,
+    body_id: &BodyId,
+    basis: ClaimBasis,
+) -> Option<String> {
+    match content
+        .solar_system
+        .bodies
+        .iter()
+        .find(|body| body.id == *body_id)
+    {
+        None => return Some("unknown body".to_string()),
+        Some(body) if body.body_type == BodyType::Star => {
+            return Some("stars cannot be claimed".to_string());
+        }
+        Some(_) => {}
+    }
+    if let Some(claim) = state.claims.get(body_id) {
+        let contestable =
+            basis == ClaimBasis::Station && !has_station_at(state, &claim.owner, body_id);
+        if claim.owner != *claimant && !contestable {
+            return Some(format!("held by {}", claim.owner.0));
+        }
+    }
+    if basis == ClaimBasis::Credits && state.balance < content.constants.claim_cost {
+        return Some("insufficient credits".to_string());
+    }
+    None
+}
+
+pub(crate) fn handle_claim_body(
+    state: &mut GameState,
+    content: &GameContent,
+    body_id: &BodyId,
+    claimant: &PrincipalId,
+    current_tick: u64,
+    events: &mut Vec<EventEnvelope>,
+) -> Result<(), String> {
+    let basis = if has_station_at(state, claimant, body_id) {
+        ClaimBasis::Station
+    } else {
+        ClaimBasis::Credits
+    };
+    if let Some(reason) = claim_refusal(state, content, claimant, body_id, basis) {
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            Event::ClaimRejected {
+                body_id: body_id.clone(),
+                principal: claimant.clone(),
+                reason: reason.clone(),
+            },
+        ));
+        return Err(reason);
+    }
+
+    let (cost, expires_tick) = match basis {
+        ClaimBasis::Station => (0.0, None),
+        ClaimBasis::Credits => {
+            let duration = content.constants.claim_duration_ticks;
+            (
+                content.constants.claim_cost,
+                (duration > 0).then_some(current_tick + duration),
+            )
+        }
+    };
+    crate::fixed::add_credits(&mut state.balance, -cost);
+    let previous = state.claims.insert(
+        body_id.clone(),
+        BodyClaim {
+            owner: claimant.clone(),
+            claimed_tick: current_tick,
+            expires_tick,
+            basis,
+        },
+    );
+    let contested_from = previous
+        .map(|claim| claim.owner)
+        .filter(|owner| owner != claimant);
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::BodyClaimed {
+            body_id: body_id.clone(),
+            owner: claimant.clone(),
+            basis,
+            cost,
+            expires_tick,
+            contested_from,
+        },
+    ));
+    Ok(())
+}
+
+/// Only the holder can release a claim; anyone else is rejected.
+pub(crate) fn handle_release_claim(
+    state: &mut GameState,
+    body_id: &BodyId,
+    issued_by: &PrincipalId,
+    current_tick: u64,
+    events: &mut Vec<EventEnvelope>,
+) -> Result<(), String> {
+    if state
+        .claims
+        .get(body_id)
+        .is_none_or(|claim| claim.owner != *issued_by)
+    {
+        return Err(format!("{} holds no claim on '{}'", issued_by.0, body_id.0));
+    }
+    state.claims.remove(body_id);
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::ClaimReleased {
+            body_id: body_id.clone(),
+            owner: issued_by.clone(),
+        },
+    ));
+    Ok(())
+}
+
+/// Drop credit claims past their expiry and station claims whose owner no
+/// longer has a station at the body.
+pub(crate) fn tick_claims(state: &mut GameState, events: &mut Vec<EventEnvelope>) {
+    let current_tick = state.meta.tick;
+    let lapsed: Vec<BodyId> = state
+        .claims
+        .iter()
+        .filter(|(body_id, claim)| match claim.basis {
+            ClaimBasis::Credits => claim
+                .expires_tick
+                .is_some_and(|expires| expires <= current_tick),
+            ClaimBasis::Station => !has_station_at(state, &claim.owner, body_id),
+        })
+        .map(|(body_id, _)| body_id.clone())
+        .collect();
+    for body_id in lapsed {
+        let Some(claim) = state.claims.remove(&body_id) else {
+            continue;
+        };
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            Event::ClaimExpired {
+                body_id,
+                owner: claim.owner,
+            },
+        ));
+    }
+}
@@ -1,8 +1,8 @@
 //! Command handler functions for `apply_commands`.
 //!
-//! Each public function handles one `Command` variant. Handlers return `false`
-//! when the command should be skipped (invalid target, insufficient resources,
-//! etc.) — the caller uses this to `continue` the command loop.
+//! Each public function handles one `Command` variant. Handlers return
+//! `Err(reason)` when the command is rejected (invalid target, insufficient
+//! resources, etc.); the caller reports it as `CommandRejected`.
 
 use crate::tasks::{deep_scan_enabled, inventory_volume_m3};
 use crate::{
@@ -12,28 +12,98 @@ use crate::{
 use rand::Rng;
 
 /// Validate an `AssignShipTask` command and collect it into the assignments vec
-/// for deferred processing. Returns `false` if the command should be skipped.
+/// for deferred processing. Returns `Err` if the command is rejected.
 pub(crate) fn handle_assign_ship_task(
-    state: &GameState,
+    state: &mut GameState,
     content: &GameContent,
     ship_id: &ShipId,
     task_kind: &TaskKind,
     issued_by: &crate::PrincipalId,
     assignments: &mut Vec<(ShipId, TaskKind)>,
-) -> bool {
+    events: &mut Vec<EventEnvelope>,
+) -> Result<(), String> {
     let Some(ship) = state.ships.get(ship_id) else {
-        return false;
+        return Err(format!("unknown ship '{}'", ship_id.0));
     };
     if ship.owner != *issued_by {
-        return false;
+        return Err(format!(
+            "ship '{}' is not owned by {}",
+            ship_id.0, issued_by.0
+        ));
     }
     if matches!(task_kind, TaskKind::DeepScan { .. })
         && !deep_scan_enabled(&state.research, content)
     {
-        return false;
+        return Err("deep scan is not unlocked".to_string());
+    }
+    if is_mining_task(task_kind) && !content.subsystems.enabled(crate::Subsystem::Mining) {
+        return Err("mining subsystem is disabled".to_string());
+    }
+    if let Some(violation) = crate::range::check_task_range(state, content, ship, task_kind) {
+        let refused = content.constants.mission_range_refuse;
+        let current_tick = state.meta.tick;
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            crate::Event::TaskOutOfRange {
+                ship_id: ship_id.clone(),
+                target_body: violation.target_body,
+                hops: violation.hops,
+                range_hops: violation.range_hops,
+                refused,
+            },
+        ));
+        if refused {
+            return Err("target is out of mission range".to_string());
+        }
+    }
+    if let TaskKind::Escort { ship: charge_id } = task_kind {
+        // Escorts guard a co-located ship of the same owner.
+        let escortable = charge_id != ship_id
+            && state.ships.get(charge_id).is_some_and(|charge| {
+                charge.owner == *issued_by
+                    && charge.position.parent_body == ship.position.parent_body
+            });
+        if !escortable {
+            return Err(
+                "escort charge must be another co-located ship of the same owner".to_string(),
+            );
+        }
+    }
+    // `issued_by` owns the ship, checked above.
+    if let TaskKind::Mine { asteroid, .. } = task_kind {
+        let body = state
+            .asteroids
+            .get(asteroid)
+            .map(|asteroid| asteroid.position.parent_body.clone());
+        if let Some(body_id) = body {
+            if let Some(holder) = crate::claims::mining_blocked_by(state, issued_by, &body_id) {
+                let reason = format!("mining rights at the body are held by {}", holder.0);
+                let event = crate::Event::MiningRightsDenied {
+                    ship_id: ship_id.clone(),
+                    body_id,
+                    holder: holder.clone(),
+                };
+                let current_tick = state.meta.tick;
+                events.push(crate::emit(&mut state.counters, current_tick, event));
+                return Err(reason);
+            }
+        }
     }
     assignments.push((ship_id.clone(), task_kind.clone()));
-    true
+    Ok(())
+}
+
+/// Survey, deep scan, resurvey or mine, directly or at the end of a transit.
+fn is_mining_task(task_kind: &TaskKind) -> bool {
+    match task_kind {
+        TaskKind::Transit { then, .. } => is_mining_task(then),
+        TaskKind::Survey { .. }
+        | TaskKind::DeepScan { .. }
+        | TaskKind::Resurvey { .. }
+        | TaskKind::Mine { .. } => true,
+        _ => false,
+    }
 }
 
 /// Build the default `ModuleKindState`, `BehaviorType`, and optional `ThermalState` for a module.
@@ -63,36 +133,35 @@ pub(crate) fn handle_install_module(
     requested_slot: Option<usize>,
     current_tick: u64,
     events: &mut Vec<EventEnvelope>,
-) -> bool {
+) -> Result<(), String> {
     let Some(station) = state.stations.get_mut(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     let item_pos = station.core.inventory.iter().position(
         |i| matches!(i, InventoryItem::Module { item_id, .. } if item_id == module_item_id),
     );
-    let Some(pos) = item_pos else { return false };
+    let Some(pos) = item_pos else {
+        return Err(format!(
+            "module item '{}' is not in station inventory",
+            module_item_id.0
+        ));
+    };
+    let item = station.core.inventory.remove(pos);
     let InventoryItem::Module {
         item_id,
         module_def_id,
-    } = station.core.inventory.remove(pos)
+        wear,
+    } = item.clone()
     else {
-        return false;
+        return Err("inventory item is not a module".to_string());
     };
     station.invalidate_volume_cache();
 
     let Some(def) = content.module_defs.get(&module_def_id) else {
-        return false;
+        return Err(format!("unknown module def '{module_def_id}'"));
     };
-    if !tech_gate_passed(
-        state,
-        station_id,
-        def,
-        item_id.clone(),
-        module_def_id.clone(),
-        current_tick,
-        events,
-    ) {
-        return false;
+    if !tech_gate_passed(state, station_id, def, item.clone(), current_tick, events) {
+        return Err("module awaits its required tech".to_string());
     }
     // Safe to re-borrow after tech gate check released the mutable borrow.
     let station = state.stations.get_mut(station_id).expect("station exists");
@@ -110,12 +179,12 @@ pub(crate) fn handle_install_module(
                 return_module_on_slot_failure(
                     state,
                     station_id,
-                    item_id,
+                    item,
                     module_def_id,
                     current_tick,
                     events,
                 );
-                return false;
+                return Err("no compatible free slot".to_string());
             }
         };
 
@@ -125,14 +194,14 @@ pub(crate) fn handle_install_module(
     let (kind_state, behavior_type, thermal) = default_module_state(def, content);
 
     let Some(station) = state.stations.get_mut(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     station.core.modules.push(crate::ModuleState {
         id: module_id.clone(),
         def_id: module_def_id.clone(),
         enabled: false,
         kind_state,
-        wear: crate::WearState::default(),
+        wear: crate::WearState { wear },
         thermal,
         slot_index: resolved_slot,
         power_stalled: false,
@@ -160,7 +229,7 @@ pub(crate) fn handle_install_module(
             slot_index: resolved_slot,
         },
     ));
-    true
+    Ok(())
 }
 
 /// Check the tech gate for an install. Returns `true` if the install may
@@ -171,8 +240,7 @@ fn tech_gate_passed(
     state: &mut GameState,
     station_id: &crate::StationId,
     def: &crate::ModuleDef,
-    item_id: crate::ModuleItemId,
-    module_def_id: String,
+    item: InventoryItem,
     current_tick: u64,
     events: &mut Vec<EventEnvelope>,
 ) -> bool {
@@ -185,10 +253,7 @@ fn tech_gate_passed(
     let Some(station) = state.stations.get_mut(station_id) else {
         return false;
     };
-    station.core.inventory.push(InventoryItem::Module {
-        item_id,
-        module_def_id,
-    });
+    station.core.inventory.push(item);
     station.invalidate_volume_cache();
     let module_id = crate::ModuleInstanceId(format!("pending_{}", tech_id.0));
     events.push(crate::emit(
@@ -208,7 +273,7 @@ fn tech_gate_passed(
 fn return_module_on_slot_failure(
     state: &mut GameState,
     station_id: &crate::StationId,
-    item_id: crate::ModuleItemId,
+    item: InventoryItem,
     module_def_id: String,
     current_tick: u64,
     events: &mut Vec<EventEnvelope>,
@@ -216,10 +281,7 @@ fn return_module_on_slot_failure(
     let Some(station) = state.stations.get_mut(station_id) else {
         return;
     };
-    station.core.inventory.push(InventoryItem::Module {
-        item_id,
-        module_def_id: module_def_id.clone(),
-    });
+    station.core.inventory.push(item);
     station.invalidate_volume_cache();
     events.push(crate::emit(
         &mut state.counters,
@@ -245,10 +307,12 @@ enum SlotResolution {
 ///
 /// - Frameless station → `Frameless` (no validation).
 /// - `requested_slot: Some(idx)` → validate index is in range, slot type
-///   is in the module's `compatible_slots`, and no existing module is
-///   in that slot. Any failure returns `NoCompatibleSlot`.
+///   is in the module's `compatible_slots`, no existing module is in that
+///   slot, and no `forbidden` adjacency rule rejects the neighbours. Any
+///   failure returns `NoCompatibleSlot`.
 /// - `requested_slot: None` → return the first slot whose type matches
-///   `compatible_slots` and is not occupied. None available → `NoCompatibleSlot`.
+///   `compatible_slots`, is not occupied, and passes the adjacency rules.
+///   None available → `NoCompatibleSlot`.
 fn resolve_install_slot(
     station: &crate::StationState,
     def: &crate::ModuleDef,
@@ -272,8 +336,15 @@ fn resolve_install_slot(
         .filter_map(|m| m.slot_index)
         .collect();
 
+    // Slots added by construction projects follow the frame's own.
+    let slots: Vec<&crate::SlotDef> = frame
+        .slots
+        .iter()
+        .chain(&station.expansions.slots)
+        .collect();
+
     if let Some(idx) = requested_slot {
-        let Some(slot) = frame.slots.get(idx) else {
+        let Some(slot) = slots.get(idx) else {
             return SlotResolution::NoCompatibleSlot;
         };
         if !def.compatible_slots.contains(&slot.slot_type) {
@@ -282,13 +353,18 @@ fn resolve_install_slot(
         if occupied.contains(&idx) {
             return SlotResolution::NoCompatibleSlot;
         }
+        if crate::station::layout::placement_forbidden(station, frame, def, idx, content) {
+            return SlotResolution::NoCompatibleSlot;
+        }
         return SlotResolution::Slot(idx);
     }
-    for (idx, slot) in frame.slots.iter().enumerate() {
+    for (idx, slot) in slots.iter().enumerate() {
         if occupied.contains(&idx) {
             continue;
         }
-        if def.compatible_slots.contains(&slot.slot_type) {
+        if def.compatible_slots.contains(&slot.slot_type)
+            && !crate::station::layout::placement_forbidden(station, frame, def, idx, content)
+        {
             return SlotResolution::Slot(idx);
         }
     }
@@ -301,7 +377,7 @@ fn resolve_install_slot(
 /// standard 60 minutes-per-tick), scaled by kit mass so heavier frames
 /// take longer. The formula is deliberately simple — a future balance
 /// pass can move these knobs into content/constants.json.
-fn assembly_ticks_for_kit(kit_def: &crate::ComponentDef) -> u64 {
+pub(crate) fn assembly_ticks_for_kit(kit_def: &crate::ComponentDef) -> u64 {
     // ~1 tick per 300 kg, clamped to [48, 168].
     #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
     let raw = (kit_def.mass_kg / 300.0) as u64;
@@ -403,16 +479,16 @@ pub(crate) fn handle_deploy_station(
     target_position: &crate::Position,
     current_tick: u64,
     events: &mut Vec<EventEnvelope>,
-) -> bool {
+) -> Result<(), String> {
     let Some(kit_info) = validate_deploy_inputs(state, content, ship_id, kit_item_index) else {
-        return false;
+        return Err("no deployable station kit at that cargo index".to_string());
     };
 
     let Some(ship_mut) = state.ships.get_mut(ship_id) else {
-        return false;
+        return Err(format!("unknown ship '{}'", ship_id.0));
     };
     if !consume_kit_from_ship(ship_mut, kit_item_index) {
-        return false;
+        return Err("no deployable station kit at that cargo index".to_string());
     }
 
     let travel_ticks = {
@@ -440,7 +516,7 @@ pub(crate) fn handle_deploy_station(
     };
 
     let Some(ship_mut) = state.ships.get_mut(ship_id) else {
-        return false;
+        return Err(format!("unknown ship '{}'", ship_id.0));
     };
     ship_mut.task = Some(crate::TaskState {
         kind: final_task.clone(),
@@ -478,15 +554,14 @@ pub(crate) fn handle_deploy_station(
             },
         ));
     }
-    true
+    Ok(())
 }
 
 /// VIO-595: Handle a `TransferItems` command. Validates the ship and
 /// both stations, pre-deducts fuel for BOTH transit legs (ship→src and
 /// src→dst), and assigns a chained task
 /// `Transit(src) → Pickup → Transit(dst) → Deposit` to the ship.
-/// Returns `true` on successful assignment. Ignores `Crew` item specs
-/// (use Import/Export trade commands for crew).
+/// Ignores `Crew` item specs (use Import/Export trade commands for crew).
 ///
 /// Why pre-deduct both legs: `resolve_transit` does not deduct fuel on
 /// chained hand-off (only the initial `apply_ship_assignments` call
@@ -503,7 +578,7 @@ pub(crate) fn handle_transfer_items(
     items: &[crate::TradeItemSpec],
     current_tick: u64,
     events: &mut Vec<EventEnvelope>,
-) -> bool {
+) -> Result<(), String> {
     // Filter out Crew specs — unsupported by this command.
     let filtered_items: Vec<crate::TradeItemSpec> = items
         .iter()
@@ -511,17 +586,17 @@ pub(crate) fn handle_transfer_items(
         .cloned()
         .collect();
     if filtered_items.is_empty() || from_station == to_station {
-        return false;
+        return Err("nothing to transfer between distinct stations".to_string());
     }
 
     let Some(src_position) = state.stations.get(from_station).map(|s| s.position.clone()) else {
-        return false;
+        return Err(format!("unknown station '{}'", from_station.0));
     };
     let Some(dst_position) = state.stations.get(to_station).map(|s| s.position.clone()) else {
-        return false;
+        return Err(format!("unknown station '{}'", to_station.0));
     };
     let Some(ship) = state.ships.get(ship_id) else {
-        return false;
+        return Err(format!("unknown ship '{}'", ship_id.0));
     };
 
     // Pre-compute both travel legs and their fuel costs.
@@ -549,7 +624,7 @@ pub(crate) fn handle_transfer_items(
                 destination: dst_position,
             },
         ));
-        return false;
+        return Err("insufficient propellant".to_string());
     }
 
     let final_task = build_transfer_task_chain(
@@ -566,7 +641,7 @@ pub(crate) fn handle_transfer_items(
     let target = final_task.target();
 
     let Some(ship_mut) = state.ships.get_mut(ship_id) else {
-        return false;
+        return Err(format!("unknown ship '{}'", ship_id.0));
     };
     if content.constants.fuel_cost_per_au > 0.0 && total_fuel > 0.0 {
         ship_mut.propellant_kg -= total_fuel;
@@ -600,7 +675,7 @@ pub(crate) fn handle_transfer_items(
             target,
         },
     ));
-    true
+    Ok(())
 }
 
 /// Compute total fuel required for a two-leg transfer (ship→src +
@@ -722,12 +797,12 @@ pub(crate) fn handle_uninstall_module(
     module_id: &crate::ModuleInstanceId,
     current_tick: u64,
     events: &mut Vec<EventEnvelope>,
-) -> bool {
+) -> Result<(), String> {
     let Some(station) = state.stations.get_mut(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     let Some(pos) = station.module_index_by_id(module_id) else {
-        return false;
+        return Err(format!("unknown module '{}'", module_id.0));
     };
     let module = station.core.modules.remove(pos);
 
@@ -738,11 +813,12 @@ pub(crate) fn handle_uninstall_module(
     state.counters.next_module_instance_id += 1;
 
     let Some(station) = state.stations.get_mut(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     station.core.inventory.push(InventoryItem::Module {
         item_id: item_id.clone(),
         module_def_id: module.def_id.clone(),
+        wear: module.wear.wear,
     });
     station.invalidate_volume_cache();
     station.rebuild_module_index(content);
@@ -757,7 +833,189 @@ pub(crate) fn handle_uninstall_module(
             module_item_id: item_id,
         },
     ));
-    true
+    Ok(())
+}
+
+/// Destroy an installed module. Its mass, scaled by
+/// `module_scrap_recovery_fraction` and what wear has left, goes into
+/// station inventory as `module_scrap_element`.
+pub(crate) fn handle_scrap_module(
+    state: &mut GameState,
+    content: &GameContent,
+    station_id: &crate::StationId,
+    module_id: &crate::ModuleInstanceId,
+    current_tick: u64,
+    events: &mut Vec<EventEnvelope>,
+) -> Result<(), String> {
+    let Some(station) = state.stations.get_mut(station_id) else {
+        return Err(format!("unknown station '{}'", station_id.0));
+    };
+    let Some(pos) = station.module_index_by_id(module_id) else {
+        return Err(format!("unknown module '{}'", module_id.0));
+    };
+    let module = station.core.modules.remove(pos);
+
+    let mass_kg = content
+        .module_defs
+        .get(&module.def_id)
+        .map_or(0.0, |def| def.mass_kg);
+    let element = content.constants.module_scrap_element.clone();
+    let kg = mass_kg
+        * content.constants.module_scrap_recovery_fraction
+        * (1.0 - module.wear.wear).clamp(0.0, 1.0);
+    if kg > 0.0 {
+        crate::composition::merge_material_lot(
+            &mut station.core.inventory,
+            element.clone(),
+            kg,
+            1.0,
+            None,
+        );
+    }
+    station.invalidate_volume_cache();
+    station.rebuild_module_index(content);
+    station.invalidate_power_cache();
+
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        crate::Event::ModuleScrapped {
+            station_id: station_id.clone(),
+            module_id: module_id.clone(),
+            module_def_id: module.def_id,
+            element,
+            kg,
+        },
+    ));
+    Ok(())
+}
+
+/// Replace an installed module with the next tier of its upgrade chain.
+/// Rejected when the module's def has no `upgrade_to`, the next tier's
+/// tech is locked, it does not fit the module's frame slot, or the station
+/// lacks any of the upgrade's materials or components. The module keeps
+/// its id, slot, enabled flag, priority and crew, and
+/// `module_upgrade_wear_carryover` of its wear; the rest of its state
+/// starts fresh, as on install.
+pub(crate) fn handle_upgrade_module(
+    state: &mut GameState,
+    content: &GameContent,
+    station_id: &crate::StationId,
+    module_id: &crate::ModuleInstanceId,
+    current_tick: u64,
+    events: &mut Vec<EventEnvelope>,
+) -> Result<(), String> {
+    let Some(station) = state.stations.get_mut(station_id) else {
+        return Err(format!("unknown station '{}'", station_id.0));
+    };
+    let Some(pos) = station.module_index_by_id(module_id) else {
+        return Err(format!("unknown module '{}'", module_id.0));
+    };
+    let Some(upgrade) = content
+        .module_defs
+        .get(&station.core.modules[pos].def_id)
+        .and_then(|def| def.upgrade_to.as_ref())
+    else {
+        return Err("module has no upgrade".to_string());
+    };
+    let Some(next_def) = content.module_defs.get(&upgrade.module_def_id) else {
+        return Err(format!(
+            "unknown upgrade module def '{}'",
+            upgrade.module_def_id
+        ));
+    };
+    if next_def
+        .required_tech
+        .as_ref()
+        .is_some_and(|tech| !state.research.unlocked.contains(tech))
+    {
+        return Err("upgrade requires a tech not yet unlocked".to_string());
+    }
+    if !upgrade_fits_slot(
+        station,
+        next_def,
+        station.core.modules[pos].slot_index,
+        content,
+    ) {
+        return Err("upgraded module does not fit its slot".to_string());
+    }
+    let costs: Vec<crate::TradeItemSpec> = upgrade
+        .materials
+        .iter()
+        .map(|(element, kg)| crate::TradeItemSpec::Material {
+            element: element.clone(),
+            kg: *kg,
+        })
+        .chain(upgrade.components.iter().map(|(component_id, count)| {
+            crate::TradeItemSpec::Component {
+                component_id: component_id.clone(),
+                count: *count,
+            }
+        }))
+        .collect();
+    if !costs
+        .iter()
+        .all(|cost| crate::trade::has_enough_for_export(&station.core.inventory, cost))
+    {
+        return Err("insufficient upgrade materials".to_string());
+    }
+    for cost in &costs {
+        crate::trade::remove_inventory_items(&mut station.core.inventory, cost);
+    }
+
+    let (kind_state, _, thermal) = default_module_state(next_def, content);
+    let module = &mut station.core.modules[pos];
+    let from_def_id = std::mem::replace(&mut module.def_id, next_def.id.clone());
+    module.kind_state = kind_state;
+    module.thermal = thermal;
+    module.wear.wear *= content.constants.module_upgrade_wear_carryover;
+    module.efficiency = if next_def.crew_requirement.is_empty() {
+        1.0
+    } else {
+        0.0
+    };
+    module.prev_crew_satisfied = next_def.crew_requirement.is_empty();
+    let wear = module.wear.wear;
+    station.invalidate_volume_cache();
+    station.rebuild_module_index(content);
+    station.invalidate_power_cache();
+
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        crate::Event::ModuleUpgraded {
+            station_id: station_id.clone(),
+            module_id: module_id.clone(),
+            from_def_id,
+            to_def_id: next_def.id.clone(),
+            wear,
+        },
+    ));
+    Ok(())
+}
+
+/// Whether `def` may take over `slot_index` on the station's frame.
+/// Frameless stations and unslotted modules always fit.
+fn upgrade_fits_slot(
+    station: &crate::StationState,
+    def: &crate::ModuleDef,
+    slot_index: Option<usize>,
+    content: &GameContent,
+) -> bool {
+    let (Some(idx), Some(frame)) = (
+        slot_index,
+        station
+            .frame_id
+            .as_ref()
+            .and_then(|frame_id| content.frames.get(frame_id)),
+    ) else {
+        return true;
+    };
+    let Some(slot) = frame.slots.iter().chain(&station.expansions.slots).nth(idx) else {
+        return false;
+    };
+    def.compatible_slots.contains(&slot.slot_type)
+        && !crate::station::layout::placement_forbidden(station, frame, def, idx, content)
 }
 
 /// Toggle the enabled flag on a module.
@@ -768,12 +1026,12 @@ pub(crate) fn handle_set_module_enabled(
     enabled: bool,
     current_tick: u64,
     events: &mut Vec<EventEnvelope>,
-) -> bool {
+) -> Result<(), String> {
     let Some(station) = state.stations.get_mut(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     let Some(module) = station.core.modules.iter_mut().find(|m| &m.id == module_id) else {
-        return false;
+        return Err(format!("unknown module '{}'", module_id.0));
     };
     module.enabled = enabled;
     station.invalidate_power_cache();
@@ -786,7 +1044,7 @@ pub(crate) fn handle_set_module_enabled(
             enabled,
         },
     ));
-    true
+    Ok(())
 }
 
 /// Set the `threshold_kg` on a processor module.
@@ -797,12 +1055,12 @@ pub(crate) fn handle_set_module_threshold(
     threshold_kg: f32,
     current_tick: u64,
     events: &mut Vec<EventEnvelope>,
-) -> bool {
+) -> Result<(), String> {
     let Some(station) = state.stations.get_mut(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     let Some(module) = station.core.modules.iter_mut().find(|m| &m.id == module_id) else {
-        return false;
+        return Err(format!("unknown module '{}'", module_id.0));
     };
     if let crate::ModuleKindState::Processor(ps) = &mut module.kind_state {
         ps.threshold_kg = threshold_kg;
@@ -816,7 +1074,7 @@ pub(crate) fn handle_set_module_threshold(
             threshold_kg,
         },
     ));
-    true
+    Ok(())
 }
 
 /// Assign a tech to a lab module.
@@ -825,17 +1083,17 @@ pub(crate) fn handle_assign_lab_tech(
     station_id: &crate::StationId,
     module_id: &crate::ModuleInstanceId,
     tech_id: Option<&crate::TechId>,
-) -> bool {
+) -> Result<(), String> {
     let Some(station) = state.stations.get_mut(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     let Some(module) = station.core.modules.iter_mut().find(|m| &m.id == module_id) else {
-        return false;
+        return Err(format!("unknown module '{}'", module_id.0));
     };
     if let crate::ModuleKindState::Lab(ls) = &mut module.kind_state {
         ls.assigned_tech = tech_id.cloned();
     }
-    true
+    Ok(())
 }
 
 /// Select a recipe on a processor or assembler module.
@@ -845,36 +1103,84 @@ pub(crate) fn handle_select_recipe(
     station_id: &crate::StationId,
     module_id: &crate::ModuleInstanceId,
     recipe_id: &crate::RecipeId,
-) -> bool {
+) -> Result<(), String> {
     // Recipe must exist in the catalog
     if !content.recipes.contains_key(recipe_id) {
-        return false;
+        return Err(format!("unknown recipe '{}'", recipe_id.0));
     }
     let Some(station) = state.stations.get_mut(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     let Some(module) = station.core.modules.iter_mut().find(|m| &m.id == module_id) else {
-        return false;
+        return Err(format!("unknown module '{}'", module_id.0));
     };
     let Some(def) = content.module_defs.get(&module.def_id) else {
-        return false;
+        return Err(format!("unknown module def '{}'", module.def_id));
     };
     match (&mut module.kind_state, &def.behavior) {
         (crate::ModuleKindState::Processor(ps), crate::ModuleBehaviorDef::Processor(proc_def)) => {
             if !proc_def.recipes.contains(recipe_id) {
-                return false;
+                return Err(format!(
+                    "recipe '{}' is not available on this processor",
+                    recipe_id.0
+                ));
             }
             ps.selected_recipe = Some(recipe_id.clone());
         }
         (crate::ModuleKindState::Assembler(asmb), crate::ModuleBehaviorDef::Assembler(asm_def)) => {
             if !asm_def.recipes.contains(recipe_id) {
-                return false;
+                return Err(format!(
+                    "recipe '{}' is not available on this assembler",
+                    recipe_id.0
+                ));
             }
             asmb.selected_recipe = Some(recipe_id.clone());
         }
-        _ => return false,
+        _ => return Err("module does not take recipes".to_string()),
     }
-    true
+    Ok(())
+}
+
+/// Append an order to an assembler's job queue. Rejected for a zero
+/// count or a recipe the module's def does not list.
+pub(crate) fn handle_queue_assembler_job(
+    state: &mut GameState,
+    content: &GameContent,
+    station_id: &crate::StationId,
+    module_id: &crate::ModuleInstanceId,
+    recipe_id: &crate::RecipeId,
+    count: u32,
+) -> Result<(), String> {
+    if count == 0 || !content.recipes.contains_key(recipe_id) {
+        return Err(format!("unknown recipe '{}'", recipe_id.0));
+    }
+    let Some(station) = state.stations.get_mut(station_id) else {
+        return Err(format!("unknown station '{}'", station_id.0));
+    };
+    let Some(module) = station.core.modules.iter_mut().find(|m| &m.id == module_id) else {
+        return Err(format!("unknown module '{}'", module_id.0));
+    };
+    let Some(def) = content.module_defs.get(&module.def_id) else {
+        return Err(format!("unknown module def '{}'", module.def_id));
+    };
+    let (crate::ModuleKindState::Assembler(asmb), crate::ModuleBehaviorDef::Assembler(asm_def)) =
+        (&mut module.kind_state, &def.behavior)
+    else {
+        return Err("module is not an assembler".to_string());
+    };
+    if !asm_def.recipes.contains(recipe_id) {
+        return Err(format!(
+            "recipe '{}' is not available on this assembler",
+            recipe_id.0
+        ));
+    }
+    asmb.queue.push(crate::AssemblerQueueEntry {
+        recipe_id: recipe_id.clone(),
+        count,
+        started: 0,
+        completed: 0,
+    });
+    Ok(())
 }
 
 /// Set the cap override on an assembler module.
@@ -884,47 +1190,50 @@ pub(crate) fn handle_set_assembler_cap(
     module_id: &crate::ModuleInstanceId,
     component_id: &crate::ComponentId,
     max_stock: u32,
-) -> bool {
+) -> Result<(), String> {
     let Some(station) = state.stations.get_mut(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     let Some(module) = station.core.modules.iter_mut().find(|m| &m.id == module_id) else {
-        return false;
+        return Err(format!("unknown module '{}'", module_id.0));
     };
     if let crate::ModuleKindState::Assembler(asmb) = &mut module.kind_state {
         asmb.cap_override.insert(component_id.clone(), max_stock);
     }
-    true
+    Ok(())
 }
 
 /// Import items into a station via trade.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn handle_import(
     state: &mut GameState,
     content: &GameContent,
     station_id: &crate::StationId,
+    issued_by: &crate::PrincipalId,
     item_spec: &crate::TradeItemSpec,
     current_tick: u64,
     rng: &mut impl Rng,
     events: &mut Vec<EventEnvelope>,
-) -> bool {
-    if !state
-        .progression
-        .trade_tier_unlocked(crate::TradeTier::BasicImport)
+) -> Result<(), String> {
+    if !content.subsystems.enabled(crate::Subsystem::Trade)
+        || !state
+            .progression
+            .trade_tier_unlocked(crate::TradeTier::BasicImport)
     {
-        return false;
+        return Err("imports are not available".to_string());
     }
     if !state.stations.contains_key(station_id) {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     }
     // Zone must have comm relay coverage for trade.
     let zone_id = &state.stations[station_id].position.parent_body.0;
     if crate::satellite::zone_comm_tier(zone_id, state, content) < crate::CommTier::Basic {
-        return false;
+        return Err("no comm relay coverage for trade".to_string());
     }
 
     // Look up pricing and compute cost
-    let Some(cost) = trade::compute_import_cost(item_spec, &content.pricing, content) else {
-        return false; // not importable or unknown item
+    let Some(cost) = trade::compute_import_cost(item_spec, &state.market, content) else {
+        return Err("item cannot be imported".to_string()); // not importable or unknown item
     };
 
     // Check balance
@@ -939,14 +1248,15 @@ pub(crate) fn handle_import(
                 available: state.balance,
             },
         ));
-        return false;
+        return Err("insufficient funds".to_string());
     }
 
     // Crew import: add to station crew roster (no inventory/cargo involved)
     if let crate::TradeItemSpec::Crew { role, count } = item_spec {
-        state.balance -= cost;
+        crate::fixed::add_credits(&mut state.balance, -cost);
+        crate::market::record_trade(&mut state.market, content, item_spec, 1.0);
         let Some(station) = state.stations.get_mut(station_id) else {
-            return false;
+            return Err(format!("unknown station '{}'", station_id.0));
         };
         *station.core.crew.entry(role.clone()).or_insert(0) += count;
         events.push(crate::emit(
@@ -959,25 +1269,42 @@ pub(crate) fn handle_import(
                 balance_after: state.balance,
             },
         ));
-        return true;
+        return Ok(());
     }
 
-    // Check cargo capacity
+    // Check cargo capacity, counting space reserved by in-transit orders
     let new_items = trade::create_inventory_items(item_spec, rng);
     let new_volume = inventory_volume_m3(&new_items, content);
+    let reserved_volume = reserved_inbound_volume_m3(state, station_id, content);
     let Some(station) = state.stations.get_mut(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     let current_volume = station.used_volume_m3(content);
     let cargo_cap = station.core.cargo_capacity_m3;
-    if current_volume + new_volume > cargo_cap {
-        return false; // no room
+    if current_volume + reserved_volume + new_volume > cargo_cap {
+        return Err("not enough free cargo volume".to_string()); // no room
     }
 
     // Execute import
-    state.balance -= cost;
+    crate::fixed::add_credits(&mut state.balance, -cost);
+    crate::market::record_trade(&mut state.market, content, item_spec, 1.0);
+    let delivery_ticks = trade::import_delivery_ticks(item_spec, content);
+    if delivery_ticks > 0 {
+        let order = crate::ImportOrder {
+            id: crate::ImportOrderId(state.counters.next_import_order_id),
+            station_id: station_id.clone(),
+            issued_by: issued_by.clone(),
+            item_spec: item_spec.clone(),
+            items: new_items,
+            cost,
+            placed_tick: current_tick,
+            arrival_tick: current_tick + delivery_ticks,
+        };
+        place_import_order(state, order, events);
+        return Ok(());
+    }
     let Some(station) = state.stations.get_mut(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     trade::merge_into_inventory(&mut station.core.inventory, new_items);
     station.invalidate_volume_cache();
@@ -992,7 +1319,263 @@ pub(crate) fn handle_import(
             balance_after: state.balance,
         },
     ));
-    true
+    Ok(())
+}
+
+/// Cargo volume already promised to a station by in-transit imports and freight.
+fn reserved_inbound_volume_m3(
+    state: &GameState,
+    station_id: &crate::StationId,
+    content: &GameContent,
+) -> f32 {
+    let imports = state
+        .import_orders
+        .iter()
+        .filter(|order| &order.station_id == station_id)
+        .map(|order| inventory_volume_m3(&order.items, content));
+    let freight = state
+        .freight_orders
+        .iter()
+        .filter(|order| &order.to_station == station_id)
+        .map(|order| inventory_volume_m3(&order.items, content));
+    imports.chain(freight).sum()
+}
+
+/// Record a paid import as in transit. Delivered by `resolve_import_deliveries`.
+fn place_import_order(
+    state: &mut GameState,
+    order: crate::ImportOrder,
+    events: &mut Vec<EventEnvelope>,
+) {
+    state.counters.next_import_order_id += 1;
+    events.push(crate::emit(
+        &mut state.counters,
+        order.placed_tick,
+        crate::Event::ImportOrderPlaced {
+            order_id: order.id,
+            station_id: order.station_id.clone(),
+            item_spec: order.item_spec.clone(),
+            cost: order.cost,
+            balance_after: state.balance,
+            arrival_tick: order.arrival_tick,
+        },
+    ));
+    state.import_orders.push(order);
+}
+
+/// Cancel an in-transit import order. Only its issuer may cancel it; part of
+/// the cost is refunded and the order's volume comes off the market again.
+pub(crate) fn handle_cancel_import_order(
+    state: &mut GameState,
+    content: &GameContent,
+    order_id: crate::ImportOrderId,
+    issued_by: &crate::PrincipalId,
+    current_tick: u64,
+    events: &mut Vec<EventEnvelope>,
+) -> Result<(), String> {
+    let Some(index) = state
+        .import_orders
+        .iter()
+        .position(|order| order.id == order_id)
+    else {
+        return Err(format!("unknown import order {}", order_id.0));
+    };
+    if state.import_orders[index].issued_by != *issued_by {
+        return Err(format!(
+            "import order {} was not placed by {}",
+            order_id.0, issued_by.0
+        ));
+    }
+    let order = state.import_orders.remove(index);
+    crate::market::record_trade(&mut state.market, content, &order.item_spec, -1.0);
+    let refund = order.cost
+        * content
+            .constants
+            .import_cancel_refund_fraction
+            .clamp(0.0, 1.0);
+    crate::fixed::add_credits(&mut state.balance, refund);
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        crate::Event::ImportOrderCancelled {
+            order_id,
+            station_id: order.station_id,
+            item_spec: order.item_spec,
+            refund,
+            balance_after: state.balance,
+        },
+    ));
+    Ok(())
+}
+
+/// Hold a command for its later `execute_at_tick`. `pending_commands` stays
+/// sorted by execution tick, and commands for the same tick keep arrival
+/// order.
+pub(crate) fn schedule_command(state: &mut GameState, envelope: crate::CommandEnvelope) {
+    let index = state
+        .pending_commands
+        .partition_point(|pending| pending.execute_at_tick <= envelope.execute_at_tick);
+    state.pending_commands.insert(index, envelope);
+}
+
+/// Take the scheduled commands due at or before `tick`, in order.
+pub(crate) fn take_due_commands(state: &mut GameState, tick: u64) -> Vec<crate::CommandEnvelope> {
+    let due = state
+        .pending_commands
+        .partition_point(|pending| pending.execute_at_tick <= tick);
+    state.pending_commands.drain(..due).collect()
+}
+
+/// Drop a scheduled command. Only its issuer may cancel it.
+pub(crate) fn handle_cancel_command(
+    state: &mut GameState,
+    command_id: crate::CommandId,
+    issued_by: &crate::PrincipalId,
+    current_tick: u64,
+    events: &mut Vec<EventEnvelope>,
+) -> Result<(), String> {
+    let Some(index) = state
+        .pending_commands
+        .iter()
+        .position(|pending| pending.id == command_id && pending.issued_by == *issued_by)
+    else {
+        return Err(format!(
+            "no scheduled command {} from {}",
+            command_id.0, issued_by.0
+        ));
+    };
+    state.pending_commands.remove(index);
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        crate::Event::CommandCancelled {
+            command_id,
+            issued_by: issued_by.clone(),
+        },
+    ));
+    Ok(())
+}
+
+/// Ship goods between two stations with the NPC freight service. Items leave
+/// the origin immediately; the destination's free space is reserved until
+/// delivery. Crew cannot be shipped.
+pub(crate) fn handle_schedule_freight(
+    state: &mut GameState,
+    content: &GameContent,
+    from_station: &crate::StationId,
+    to_station: &crate::StationId,
+    item_spec: &crate::TradeItemSpec,
+    current_tick: u64,
+    events: &mut Vec<EventEnvelope>,
+) -> Result<(), String> {
+    if from_station == to_station || matches!(item_spec, crate::TradeItemSpec::Crew { .. }) {
+        return Err("freight needs distinct stations and cannot carry crew".to_string());
+    }
+    let (Some(from), Some(to)) = (
+        state.stations.get(from_station),
+        state.stations.get(to_station),
+    ) else {
+        return Err("unknown origin or destination station".to_string());
+    };
+    if !trade::has_enough_for_export(&from.core.inventory, item_spec) {
+        return Err("insufficient stock at the origin".to_string());
+    }
+    let Some(mass_kg) = item_spec.compute_mass(content) else {
+        return Err("unknown item mass".to_string());
+    };
+    let from_abs = crate::compute_entity_absolute(&from.position, &state.body_cache);
+    let to_abs = crate::compute_entity_absolute(&to.position, &state.body_cache);
+    let cost = trade::compute_freight_cost(mass_kg, from_abs, to_abs, &content.constants);
+    if state.balance < cost {
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            crate::Event::InsufficientFunds {
+                station_id: from_station.clone(),
+                action: format!("freight {}", item_spec.pricing_key()),
+                required: cost,
+                available: state.balance,
+            },
+        ));
+        return Err("insufficient funds".to_string());
+    }
+
+    let reserved = reserved_inbound_volume_m3(state, to_station, content);
+    let Some(to) = state.stations.get_mut(to_station) else {
+        return Err(format!("unknown station '{}'", to_station.0));
+    };
+    let free_volume = (to.core.cargo_capacity_m3 - to.used_volume_m3(content) - reserved).max(0.0);
+    let Some(items) = take_freight_items(state, content, from_station, item_spec, free_volume)
+    else {
+        return Err("destination cannot hold the full shipment".to_string()); // destination can't hold the full shipment
+    };
+
+    crate::fixed::add_credits(&mut state.balance, -cost);
+    let order_id = crate::FreightOrderId(state.counters.next_freight_order_id);
+    state.counters.next_freight_order_id += 1;
+    let arrival_tick =
+        current_tick + trade::freight_delivery_ticks(from_abs, to_abs, &content.constants);
+    state.freight_orders.push(crate::FreightOrder {
+        id: order_id,
+        from_station: from_station.clone(),
+        to_station: to_station.clone(),
+        item_spec: item_spec.clone(),
+        items,
+        cost,
+        placed_tick: current_tick,
+        arrival_tick,
+    });
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        crate::Event::FreightScheduled {
+            order_id,
+            from_station: from_station.clone(),
+            to_station: to_station.clone(),
+            item_spec: item_spec.clone(),
+            cost,
+            balance_after: state.balance,
+            arrival_tick,
+        },
+    ));
+    Ok(())
+}
+
+/// Remove the full `item_spec` from a station's inventory, limited to
+/// `max_volume_m3`. All-or-nothing: a partial take is put back and `None`
+/// is returned.
+fn take_freight_items(
+    state: &mut GameState,
+    content: &GameContent,
+    station_id: &crate::StationId,
+    item_spec: &crate::TradeItemSpec,
+    max_volume_m3: f32,
+) -> Option<Vec<InventoryItem>> {
+    let station = state.stations.get_mut(station_id)?;
+    let items = crate::tasks::take_items_for_spec(station, item_spec, max_volume_m3, content);
+    let complete = match item_spec {
+        crate::TradeItemSpec::Material { kg, .. } => {
+            crate::inventory_mass_kg(&items) >= kg - content.constants.min_meaningful_kg
+        }
+        crate::TradeItemSpec::Component { count, .. } => {
+            let taken: u32 = items
+                .iter()
+                .map(|item| match item {
+                    InventoryItem::Component { count, .. } => *count,
+                    _ => 0,
+                })
+                .sum();
+            taken == *count
+        }
+        crate::TradeItemSpec::Module { .. } => !items.is_empty(),
+        crate::TradeItemSpec::Crew { .. } => false,
+    };
+    if !complete {
+        trade::merge_into_inventory(&mut station.core.inventory, items);
+        return None;
+    }
+    station.invalidate_volume_cache();
+    Some(items)
 }
 
 /// Export items from a station via trade.
@@ -1003,43 +1586,45 @@ pub(crate) fn handle_export(
     item_spec: &crate::TradeItemSpec,
     current_tick: u64,
     events: &mut Vec<EventEnvelope>,
-) -> bool {
-    if !state
-        .progression
-        .trade_tier_unlocked(crate::TradeTier::Export)
+) -> Result<(), String> {
+    if !content.subsystems.enabled(crate::Subsystem::Trade)
+        || !state
+            .progression
+            .trade_tier_unlocked(crate::TradeTier::Export)
     {
-        return false;
+        return Err("exports are not available".to_string());
     }
     let Some(station) = state.stations.get(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     // Zone must have comm relay coverage for trade.
     if crate::satellite::zone_comm_tier(&station.position.parent_body.0, state, content)
         < crate::CommTier::Basic
     {
-        return false;
+        return Err("no comm relay coverage for trade".to_string());
     }
 
     // Look up pricing and compute revenue
-    let Some(revenue) = trade::compute_export_revenue(item_spec, &content.pricing, content) else {
-        return false; // not exportable or unknown item
+    let Some(revenue) = trade::compute_export_revenue(item_spec, &state.market, content) else {
+        return Err("item cannot be exported".to_string()); // not exportable or unknown item
     };
 
     // Check station has items
     if !trade::has_enough_for_export(&station.core.inventory, item_spec) {
-        return false;
+        return Err("insufficient stock to export".to_string());
     }
 
     // Execute export
     let Some(station) = state.stations.get_mut(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     if !trade::remove_inventory_items(&mut station.core.inventory, item_spec) {
-        return false;
+        return Err("insufficient stock to export".to_string());
     }
     station.invalidate_volume_cache();
-    state.balance += revenue;
-    state.export_revenue_total += revenue;
+    crate::fixed::add_credits(&mut state.balance, revenue);
+    crate::market::record_trade(&mut state.market, content, item_spec, -1.0);
+    crate::fixed::add_credits(&mut state.export_revenue_total, revenue);
     state.export_count += 1;
 
     events.push(crate::emit(
@@ -1052,7 +1637,7 @@ pub(crate) fn handle_export(
             balance_after: state.balance,
         },
     ));
-    true
+    Ok(())
 }
 
 /// Import items into a ground facility. Bypasses milestone trade gating
@@ -1065,13 +1650,13 @@ pub(crate) fn handle_ground_import(
     current_tick: u64,
     rng: &mut impl Rng,
     events: &mut Vec<EventEnvelope>,
-) -> bool {
+) -> Result<(), String> {
     if !state.ground_facilities.contains_key(gf_id) {
-        return false;
+        return Err(format!("unknown ground facility '{}'", gf_id.0));
     }
 
-    let Some(cost) = trade::compute_import_cost(item_spec, &content.pricing, content) else {
-        return false;
+    let Some(cost) = trade::compute_import_cost(item_spec, &state.market, content) else {
+        return Err("item cannot be imported".to_string());
     };
 
     if state.balance < cost {
@@ -1085,14 +1670,15 @@ pub(crate) fn handle_ground_import(
                 available: state.balance,
             },
         ));
-        return false;
+        return Err("insufficient funds".to_string());
     }
 
     // Crew import
     if let crate::TradeItemSpec::Crew { role, count } = item_spec {
-        state.balance -= cost;
+        crate::fixed::add_credits(&mut state.balance, -cost);
+        crate::market::record_trade(&mut state.market, content, item_spec, 1.0);
         let Some(gf) = state.ground_facilities.get_mut(gf_id) else {
-            return false;
+            return Err(format!("unknown ground facility '{}'", gf_id.0));
         };
         *gf.core.crew.entry(role.clone()).or_insert(0) += count;
         events.push(crate::emit(
@@ -1105,23 +1691,24 @@ pub(crate) fn handle_ground_import(
                 balance_after: state.balance,
             },
         ));
-        return true;
+        return Ok(());
     }
 
     // Check cargo capacity
     let new_items = trade::create_inventory_items(item_spec, rng);
     let new_volume = inventory_volume_m3(&new_items, content);
     let Some(gf) = state.ground_facilities.get_mut(gf_id) else {
-        return false;
+        return Err(format!("unknown ground facility '{}'", gf_id.0));
     };
     let current_volume = gf.core.used_volume_m3(content);
     if current_volume + new_volume > gf.core.cargo_capacity_m3 {
-        return false;
+        return Err("not enough free cargo volume".to_string());
     }
 
-    state.balance -= cost;
+    crate::fixed::add_credits(&mut state.balance, -cost);
+    crate::market::record_trade(&mut state.market, content, item_spec, 1.0);
     let Some(gf) = state.ground_facilities.get_mut(gf_id) else {
-        return false;
+        return Err(format!("unknown ground facility '{}'", gf_id.0));
     };
     trade::merge_into_inventory(&mut gf.core.inventory, new_items);
     gf.core.invalidate_volume_cache();
@@ -1136,7 +1723,7 @@ pub(crate) fn handle_ground_import(
             balance_after: state.balance,
         },
     ));
-    true
+    Ok(())
 }
 
 /// Export items from a ground facility. Bypasses milestone trade gating.
@@ -1147,28 +1734,29 @@ pub(crate) fn handle_ground_export(
     item_spec: &crate::TradeItemSpec,
     current_tick: u64,
     events: &mut Vec<EventEnvelope>,
-) -> bool {
+) -> Result<(), String> {
     let Some(gf) = state.ground_facilities.get(gf_id) else {
-        return false;
+        return Err(format!("unknown ground facility '{}'", gf_id.0));
     };
 
-    let Some(revenue) = trade::compute_export_revenue(item_spec, &content.pricing, content) else {
-        return false;
+    let Some(revenue) = trade::compute_export_revenue(item_spec, &state.market, content) else {
+        return Err("item cannot be exported".to_string());
     };
 
     if !trade::has_enough_for_export(&gf.core.inventory, item_spec) {
-        return false;
+        return Err("insufficient stock to export".to_string());
     }
 
     let Some(gf) = state.ground_facilities.get_mut(gf_id) else {
-        return false;
+        return Err(format!("unknown ground facility '{}'", gf_id.0));
     };
     if !trade::remove_inventory_items(&mut gf.core.inventory, item_spec) {
-        return false;
+        return Err("insufficient stock to export".to_string());
     }
     gf.core.invalidate_volume_cache();
-    state.balance += revenue;
-    state.export_revenue_total += revenue;
+    crate::fixed::add_credits(&mut state.balance, revenue);
+    crate::market::record_trade(&mut state.market, content, item_spec, -1.0);
+    crate::fixed::add_credits(&mut state.export_revenue_total, revenue);
     state.export_count += 1;
 
     events.push(crate::emit(
@@ -1181,7 +1769,7 @@ pub(crate) fn handle_ground_export(
             balance_after: state.balance,
         },
     ));
-    true
+    Ok(())
 }
 
 /// Install a module from ground facility inventory into the facility's active modules.
@@ -1192,37 +1780,44 @@ pub(crate) fn handle_ground_install_module(
     module_item_id: &crate::ModuleItemId,
     current_tick: u64,
     events: &mut Vec<EventEnvelope>,
-) -> bool {
+) -> Result<(), String> {
     let Some(gf) = state.ground_facilities.get_mut(gf_id) else {
-        return false;
+        return Err(format!("unknown ground facility '{}'", gf_id.0));
     };
     let item_pos = gf.core.inventory.iter().position(
         |i| matches!(i, InventoryItem::Module { item_id, .. } if item_id == module_item_id),
     );
-    let Some(pos) = item_pos else { return false };
+    let Some(pos) = item_pos else {
+        return Err(format!(
+            "module item '{}' is not in facility inventory",
+            module_item_id.0
+        ));
+    };
     let InventoryItem::Module {
         item_id,
         module_def_id,
+        wear,
     } = gf.core.inventory.remove(pos)
     else {
-        return false;
+        return Err("inventory item is not a module".to_string());
     };
     gf.core.invalidate_volume_cache();
 
     let Some(def) = content.module_defs.get(&module_def_id) else {
-        return false;
+        return Err(format!("unknown module def '{module_def_id}'"));
     };
     if let Some(ref tech_id) = def.required_tech {
         if !state.research.unlocked.contains(tech_id) {
             let Some(gf) = state.ground_facilities.get_mut(gf_id) else {
-                return false;
+                return Err(format!("unknown ground facility '{}'", gf_id.0));
             };
             gf.core.inventory.push(InventoryItem::Module {
                 item_id,
                 module_def_id,
+                wear,
             });
             gf.core.invalidate_volume_cache();
-            return false;
+            return Err("module awaits its required tech".to_string());
         }
     }
 
@@ -1232,7 +1827,7 @@ pub(crate) fn handle_ground_install_module(
     let (kind_state, behavior_type, thermal) = default_module_state(def, content);
 
     let Some(gf) = state.ground_facilities.get_mut(gf_id) else {
-        return false;
+        return Err(format!("unknown ground facility '{}'", gf_id.0));
     };
     // Ground facility modules auto-enable and auto-assign crew on install.
     let crew_satisfied = def
@@ -1244,7 +1839,7 @@ pub(crate) fn handle_ground_install_module(
         def_id: module_def_id.clone(),
         enabled: true,
         kind_state,
-        wear: crate::WearState::default(),
+        wear: crate::WearState { wear },
         thermal,
         slot_index: None,
         power_stalled: false,
@@ -1268,7 +1863,7 @@ pub(crate) fn handle_ground_install_module(
             slot_index: None,
         },
     ));
-    true
+    Ok(())
 }
 
 /// Toggle the enabled flag on a ground facility module.
@@ -1279,12 +1874,12 @@ pub(crate) fn handle_ground_set_module_enabled(
     enabled: bool,
     current_tick: u64,
     events: &mut Vec<EventEnvelope>,
-) -> bool {
+) -> Result<(), String> {
     let Some(gf) = state.ground_facilities.get_mut(gf_id) else {
-        return false;
+        return Err(format!("unknown ground facility '{}'", gf_id.0));
     };
     let Some(module) = gf.core.modules.iter_mut().find(|m| &m.id == module_id) else {
-        return false;
+        return Err(format!("unknown module '{}'", module_id.0));
     };
     module.enabled = enabled;
     gf.core.invalidate_power_cache();
@@ -1297,7 +1892,7 @@ pub(crate) fn handle_ground_set_module_enabled(
             enabled,
         },
     ));
-    true
+    Ok(())
 }
 
 /// Consume fuel from a ground facility's inventory.
@@ -1310,7 +1905,7 @@ fn consume_fuel(core: &mut crate::FacilityCore, fuel_element: &str, fuel_kg: f32
         if let InventoryItem::Material { element, kg, .. } = item {
             if element == fuel_element {
                 let consumed = kg.min(remaining);
-                *kg -= consumed;
+                crate::fixed::add_kg(kg, -consumed);
                 remaining -= consumed;
             }
         }
@@ -1436,36 +2031,36 @@ pub(crate) fn handle_launch(
     destination: &crate::Position,
     current_tick: u64,
     events: &mut Vec<EventEnvelope>,
-) -> bool {
+) -> Result<(), String> {
     // Look up rocket definition.
     let Some(rocket_def) = content.rocket_defs.get(rocket_def_id) else {
-        return false;
+        return Err(format!("unknown rocket def '{rocket_def_id}'"));
     };
 
     // Check tech gate.
     if let Some(ref tech_id) = rocket_def.required_tech {
         if !state.research.unlocked.contains(tech_id) {
-            return false;
+            return Err("rocket requires a tech not yet unlocked".to_string());
         }
     }
 
     let Some(facility) = state.ground_facilities.get(facility_id) else {
-        return false;
+        return Err(format!("unknown ground facility '{}'", facility_id.0));
     };
 
     let Some((pad_index, recovery_ticks)) =
         find_available_pad(facility, content, rocket_def.payload_capacity_kg)
     else {
-        return false;
+        return Err("no launch pad available for the payload".to_string());
     };
 
     if !validate_satellite_payload(payload, facility, state, content) {
-        return false;
+        return Err("invalid satellite payload".to_string());
     }
 
     let payload_mass_kg = compute_payload_mass(payload, content);
     if payload_mass_kg > rocket_def.payload_capacity_kg {
-        return false;
+        return Err("payload exceeds rocket capacity".to_string());
     }
 
     // Check fuel availability in facility inventory.
@@ -1480,20 +2075,20 @@ pub(crate) fn handle_launch(
         })
         .sum();
     if available_fuel < rocket_def.fuel_kg {
-        return false;
+        return Err("insufficient launch fuel".to_string());
     }
 
     // Compute total cost: base + fuel.
     let fuel_cost = f64::from(rocket_def.fuel_kg) * content.constants.launch_fuel_cost_per_kg;
     let total_cost = rocket_def.base_launch_cost + fuel_cost;
     if state.balance < total_cost {
-        return false;
+        return Err("insufficient funds".to_string());
     }
 
     // Commit: deduct cost and consume fuel.
-    state.balance -= total_cost;
+    crate::fixed::add_credits(&mut state.balance, -total_cost);
     let Some(facility) = state.ground_facilities.get_mut(facility_id) else {
-        return false;
+        return Err(format!("unknown ground facility '{}'", facility_id.0));
     };
     consume_fuel(&mut facility.core, fuel_element, rocket_def.fuel_kg);
 
@@ -1509,7 +2104,7 @@ pub(crate) fn handle_launch(
     let arrival_tick = current_tick + transit_ticks;
 
     let Some(facility) = state.ground_facilities.get_mut(facility_id) else {
-        return false;
+        return Err(format!("unknown ground facility '{}'", facility_id.0));
     };
     if let crate::ModuleKindState::LaunchPad(ref mut pad_state) =
         facility.core.modules[pad_index].kind_state
@@ -1539,7 +2134,7 @@ pub(crate) fn handle_launch(
             arrival_tick,
         },
     ));
-    true
+    Ok(())
 }
 
 /// Deploy a satellite from an orbital station's inventory.
@@ -1553,36 +2148,36 @@ pub(crate) fn handle_deploy_satellite(
     current_tick: u64,
     rng: &mut impl rand::Rng,
     events: &mut Vec<EventEnvelope>,
-) -> bool {
+) -> Result<(), String> {
     // Validate satellite def exists.
     let Some(sat_def) = content.satellite_defs.get(satellite_def_id) else {
-        return false;
+        return Err(format!("unknown satellite def '{satellite_def_id}'"));
     };
 
     // Validate tech requirement.
     if let Some(ref required_tech) = sat_def.required_tech {
         if !state.research.unlocked.contains(required_tech) {
-            return false;
+            return Err("satellite requires a tech not yet unlocked".to_string());
         }
     }
 
     // Validate station exists and has the satellite component.
     let Some(station) = state.stations.get(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     let has_component = station.core.inventory.iter().any(|item| {
         matches!(item, InventoryItem::Component { component_id, count, .. }
             if component_id.0 == satellite_def_id && *count > 0)
     });
     if !has_component {
-        return false;
+        return Err("satellite component is not in station inventory".to_string());
     }
 
     let position = station.position.clone();
 
     // Remove component from inventory.
     let Some(station) = state.stations.get_mut(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     remove_component(&mut station.core, satellite_def_id, 1);
 
@@ -1594,7 +2189,7 @@ pub(crate) fn handle_deploy_satellite(
         content,
         rng,
     ) else {
-        return false;
+        return Err("satellite could not be created".to_string());
     };
     let satellite_id = satellite.id.clone();
     let satellite_type = satellite.satellite_type.clone();
@@ -1609,18 +2204,19 @@ pub(crate) fn handle_deploy_satellite(
             satellite_type,
         },
     ));
-    true
+    Ok(())
 }
 
 /// Jettison all slag from a station's inventory.
 pub(crate) fn handle_jettison_slag(
     state: &mut GameState,
+    content: &GameContent,
     station_id: &crate::StationId,
     current_tick: u64,
     events: &mut Vec<EventEnvelope>,
-) -> bool {
+) -> Result<(), String> {
     let Some(station) = state.stations.get_mut(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     let jettisoned_kg: f32 = station
         .core
@@ -1639,6 +2235,7 @@ pub(crate) fn handle_jettison_slag(
         .inventory
         .retain(|i| !matches!(i, InventoryItem::Slag { .. }));
     station.invalidate_volume_cache();
+    let body_id = station.position.parent_body.clone();
     if jettisoned_kg > 0.0 {
         events.push(crate::emit(
             &mut state.counters,
@@ -1648,8 +2245,16 @@ pub(crate) fn handle_jettison_slag(
                 kg: jettisoned_kg,
             },
         ));
+        crate::pollution::record_jettison(
+            state,
+            &body_id,
+            jettisoned_kg,
+            content,
+            current_tick,
+            events,
+        );
     }
-    true
+    Ok(())
 }
 
 /// Set the priority on a module. Controls inventory consumption order, crew
@@ -1659,15 +2264,27 @@ pub(crate) fn handle_set_module_priority(
     station_id: &crate::StationId,
     module_id: &crate::ModuleInstanceId,
     priority: u32,
-) -> bool {
+) -> Result<(), String> {
     let Some(station) = state.stations.get_mut(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     let Some(module) = station.core.modules.iter_mut().find(|m| &m.id == module_id) else {
-        return false;
+        return Err(format!("unknown module '{}'", module_id.0));
     };
     module.module_priority = priority;
-    true
+    Ok(())
+}
+
+pub(crate) fn handle_set_power_policy(
+    state: &mut GameState,
+    station_id: &crate::StationId,
+    policy: crate::PowerPolicy,
+) -> Result<(), String> {
+    let Some(station) = state.stations.get_mut(station_id) else {
+        return Err(format!("unknown station '{}'", station_id.0));
+    };
+    station.power_policy = policy;
+    Ok(())
 }
 
 /// Assign crew of a given role to a module. Validates available crew, role requirement, and cap.
@@ -1679,23 +2296,23 @@ pub(crate) fn handle_assign_crew(
     role: &crate::CrewRole,
     count: u32,
     events: &mut Vec<crate::EventEnvelope>,
-) -> bool {
+) -> Result<(), String> {
     if count == 0 {
-        return false;
+        return Err("crew count must be positive".to_string());
     }
     let current_tick = state.meta.tick;
     let Some(station) = state.stations.get(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     let Some(module_index) = station.module_index_by_id(module_id) else {
-        return false;
+        return Err(format!("unknown module '{}'", module_id.0));
     };
     let def_id = &station.core.modules[module_index].def_id;
     let Some(def) = content.module_defs.get(def_id) else {
-        return false;
+        return Err(format!("unknown module def '{def_id}'"));
     };
     let Some(&needed) = def.crew_requirement.get(role) else {
-        return false;
+        return Err("module does not need that crew role".to_string());
     };
     // Cap: don't assign more than the requirement
     let already_assigned = station.core.modules[module_index]
@@ -1706,12 +2323,12 @@ pub(crate) fn handle_assign_crew(
     let max_assignable = needed.saturating_sub(already_assigned);
     let actual_count = count.min(max_assignable);
     if actual_count == 0 {
-        return false;
+        return Err("module already has its full crew of that role".to_string());
     }
     // Check available crew
     let available = station.available_crew(role);
     if available < actual_count {
-        return false;
+        return Err("not enough available crew".to_string());
     }
     let count = actual_count;
 
@@ -1752,7 +2369,7 @@ pub(crate) fn handle_assign_crew(
             },
         ));
     }
-    true
+    Ok(())
 }
 
 /// Unassign crew of a given role from a module.
@@ -1764,20 +2381,20 @@ pub(crate) fn handle_unassign_crew(
     role: &crate::CrewRole,
     count: u32,
     events: &mut Vec<crate::EventEnvelope>,
-) -> bool {
+) -> Result<(), String> {
     if count == 0 {
-        return false;
+        return Err("crew count must be positive".to_string());
     }
     let current_tick = state.meta.tick;
     let Some(station) = state.stations.get_mut(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     let Some(module) = station.core.modules.iter_mut().find(|m| &m.id == module_id) else {
-        return false;
+        return Err(format!("unknown module '{}'", module_id.0));
     };
     let assigned = module.assigned_crew.get(role).copied().unwrap_or(0);
     if assigned < count {
-        return false;
+        return Err("fewer crew assigned than requested".to_string());
     }
     let def_id = module.def_id.clone();
     let was_satisfied = content
@@ -1815,7 +2432,7 @@ pub(crate) fn handle_unassign_crew(
             },
         ));
     }
-    true
+    Ok(())
 }
 
 /// Apply frame bonuses to a station via the modifier pipeline, mirroring
@@ -1825,7 +2442,8 @@ pub(crate) fn handle_unassign_crew(
 ///   `station.core.modifiers` so the set matches the current frame exactly.
 /// - If the station has a frame, copies the frame's `bonuses` into the
 ///   modifier set tagged with `ModifierSource::Frame(frame_id)` and recomputes
-///   cached base stats (currently just `cargo_capacity_m3`).
+///   cached base stats (currently just `cargo_capacity_m3`, which also
+///   includes `station.expansions.cargo_capacity_m3`).
 /// - Frameless stations (`frame_id == None`) are left alone — no frame
 ///   modifiers, no stat recompute. Their `cargo_capacity_m3` remains whatever
 ///   the caller set directly.
@@ -1856,14 +2474,17 @@ pub fn recompute_station_stats(station: &mut crate::StationState, content: &Game
         station.core.modifiers.add(modifier);
     }
 
-    // Recompute cached cargo capacity from the frame base plus modifiers.
+    // Recompute cached cargo capacity from the frame base plus modifiers,
+    // then add any project expansions on top.
     station.core.cargo_capacity_m3 = station
         .core
         .modifiers
-        .resolve_f32(StatId::CargoCapacity, frame.base_cargo_capacity_m3);
+        .resolve_f32(StatId::CargoCapacity, frame.base_cargo_capacity_m3)
+        + station.expansions.cargo_capacity_m3;
 }
 
-/// Recompute ship cached stats (cargo, speed, propellant capacity) from hull + fitted modules.
+/// Recompute ship cached stats (cargo, speed, propellant capacity) from hull,
+/// ship class and fitted modules.
 pub fn recompute_ship_stats(ship: &mut crate::ShipState, content: &GameContent) {
     use crate::modifiers::{ModifierSource, StatId};
 
@@ -1899,21 +2520,32 @@ pub fn recompute_ship_stats(ship: &mut crate::ShipState, content: &GameContent)
         }
     }
 
-    // Recompute cached stats
+    // Recompute cached stats from the hull, or the ship class where it
+    // overrides the hull.
+    let class = ship.class(content);
+    let base_cargo_m3 = class
+        .and_then(|class| class.cargo_capacity_m3)
+        .unwrap_or(hull.cargo_capacity_m3);
+    let base_propellant_kg = class
+        .and_then(|class| class.propellant_capacity_kg)
+        .unwrap_or(hull.base_propellant_capacity_kg);
+    #[allow(clippy::cast_precision_loss)]
+    let base_ticks_per_au = hull.base_speed_ticks_per_au as f64
+        / f64::from(class.map_or(1.0, |class| class.speed_multiplier));
     ship.cargo_capacity_m3 = ship
         .modifiers
-        .resolve_f32(StatId::CargoCapacity, hull.cargo_capacity_m3);
+        .resolve_f32(StatId::CargoCapacity, base_cargo_m3);
     #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // clamp guards
     {
         ship.speed_ticks_per_au = Some(
             ship.modifiers
-                .resolve(StatId::ShipSpeed, hull.base_speed_ticks_per_au as f64)
+                .resolve(StatId::ShipSpeed, base_ticks_per_au)
                 .clamp(0.0, u64::MAX as f64) as u64,
         );
     }
     ship.propellant_capacity_kg = ship
         .modifiers
-        .resolve_f32(StatId::PropellantCapacity, hull.base_propellant_capacity_kg);
+        .resolve_f32(StatId::PropellantCapacity, base_propellant_kg);
     ship.propellant_kg = ship.propellant_kg.min(ship.propellant_capacity_kg);
 }
 
@@ -1926,16 +2558,16 @@ pub(crate) fn handle_fit_ship_module(
     module_def_id: &ModuleDefId,
     station_id: &StationId,
     events: &mut Vec<EventEnvelope>,
-) -> bool {
+) -> Result<(), String> {
     // Ship must exist and be at the same station location
     let Some(ship) = state.ships.get(ship_id) else {
-        return false;
+        return Err(format!("unknown ship '{}'", ship_id.0));
     };
     let Some(station) = state.stations.get(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     if ship.position != station.position {
-        return false;
+        return Err("ship is not docked at the station".to_string());
     }
     // Ship must be idle (None = freshly constructed, Some(Idle) = completed task)
     if ship
@@ -1943,14 +2575,14 @@ pub(crate) fn handle_fit_ship_module(
         .as_ref()
         .is_some_and(|t| !matches!(t.kind, crate::TaskKind::Idle))
     {
-        return false;
+        return Err("ship is busy".to_string());
     }
     // Hull must exist and slot_index must be valid
     let Some(hull) = content.hulls.get(&ship.hull_id) else {
-        return false;
+        return Err(format!("unknown hull '{}'", ship.hull_id.0));
     };
     let Some(slot_def) = hull.slots.get(slot_index) else {
-        return false;
+        return Err(format!("hull has no slot {slot_index}"));
     };
     // Slot must not already be occupied
     if ship
@@ -1958,31 +2590,36 @@ pub(crate) fn handle_fit_ship_module(
         .iter()
         .any(|fm| fm.slot_index == slot_index)
     {
-        return false;
+        return Err(format!("slot {slot_index} is already fitted"));
     }
     // Module def must exist and be compatible with the slot type
     let Some(module_def) = content.module_defs.get(&module_def_id.0) else {
-        return false;
+        return Err(format!("unknown module def '{}'", module_def_id.0));
     };
     if !module_def.compatible_slots.contains(&slot_def.slot_type) {
-        return false;
+        return Err("module does not fit the slot type".to_string());
     }
     // Station must have an InventoryItem::Module with matching module_def_id
     let item_pos = station.core.inventory.iter().position(|item| {
         matches!(item, InventoryItem::Module { module_def_id: def_id, .. } if *def_id == module_def_id.0)
     });
-    let Some(pos) = item_pos else { return false };
+    let Some(pos) = item_pos else {
+        return Err(format!(
+            "module '{}' is not in station inventory",
+            module_def_id.0
+        ));
+    };
 
     // Execute: remove module from station inventory
     let Some(station) = state.stations.get_mut(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     station.core.inventory.remove(pos);
     station.invalidate_volume_cache();
 
     // Add FittedModule to ship
     let Some(ship) = state.ships.get_mut(ship_id) else {
-        return false;
+        return Err(format!("unknown ship '{}'", ship_id.0));
     };
     ship.fitted_modules.push(FittedModule {
         slot_index,
@@ -2000,7 +2637,7 @@ pub(crate) fn handle_fit_ship_module(
             station_id: station_id.clone(),
         },
     ));
-    true
+    Ok(())
 }
 
 /// Unfit a ship module from a hull slot, returning it to station inventory.
@@ -2012,16 +2649,16 @@ pub(crate) fn handle_unfit_ship_module(
     station_id: &StationId,
     current_tick: u64,
     events: &mut Vec<EventEnvelope>,
-) -> bool {
+) -> Result<(), String> {
     // Ship must exist and be at the same station location
     let Some(ship) = state.ships.get(ship_id) else {
-        return false;
+        return Err(format!("unknown ship '{}'", ship_id.0));
     };
     let Some(station) = state.stations.get(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     if ship.position != station.position {
-        return false;
+        return Err("ship is not docked at the station".to_string());
     }
     // Ship must be idle (None = freshly constructed, Some(Idle) = completed task)
     if ship
@@ -2029,11 +2666,11 @@ pub(crate) fn handle_unfit_ship_module(
         .as_ref()
         .is_some_and(|t| !matches!(t.kind, crate::TaskKind::Idle))
     {
-        return false;
+        return Err("ship is busy".to_string());
     }
     // Hull must exist in content
     if !content.hulls.contains_key(&ship.hull_id) {
-        return false;
+        return Err(format!("unknown hull '{}'", ship.hull_id.0));
     }
     // Slot must have a fitted module
     let Some(fitted_pos) = ship
@@ -2041,12 +2678,12 @@ pub(crate) fn handle_unfit_ship_module(
         .iter()
         .position(|fm| fm.slot_index == slot_index)
     else {
-        return false;
+        return Err(format!("no module fitted in slot {slot_index}"));
     };
 
     // Execute: remove FittedModule from ship
     let Some(ship) = state.ships.get_mut(ship_id) else {
-        return false;
+        return Err(format!("unknown ship '{}'", ship_id.0));
     };
     let removed = ship.fitted_modules.remove(fitted_pos);
     recompute_ship_stats(ship, content);
@@ -2059,11 +2696,12 @@ pub(crate) fn handle_unfit_ship_module(
     state.counters.next_module_instance_id += 1;
 
     let Some(station) = state.stations.get_mut(station_id) else {
-        return false;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
     station.core.inventory.push(InventoryItem::Module {
         item_id: item_id.clone(),
         module_def_id: removed.module_def_id.0.clone(),
+        wear: 0.0,
     });
     station.invalidate_volume_cache();
 
@@ -2077,7 +2715,7 @@ pub(crate) fn handle_unfit_ship_module(
             station_id: station_id.clone(),
         },
     ));
-    true
+    Ok(())
 }
 
 /// Create a thermal link between two module ports on a station.
@@ -2087,25 +2725,25 @@ pub(crate) fn handle_create_thermal_link(
     link: &crate::ThermalLink,
     station_id: &StationId,
     events: &mut Vec<EventEnvelope>,
-) {
+) -> Result<(), String> {
     let Some(station) = state.stations.get(station_id) else {
-        return;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
 
     // Validate both modules exist and look up their defs
     let Some(from_idx) = station.module_index_by_id(&link.from_module_id) else {
-        return;
+        return Err(format!("unknown module '{}'", link.from_module_id.0));
     };
     let Some(to_idx) = station.module_index_by_id(&link.to_module_id) else {
-        return;
+        return Err(format!("unknown module '{}'", link.to_module_id.0));
     };
     let from_module = &station.core.modules[from_idx];
     let to_module = &station.core.modules[to_idx];
     let Some(from_def) = content.module_defs.get(&from_module.def_id) else {
-        return;
+        return Err(format!("unknown module def '{}'", from_module.def_id));
     };
     let Some(to_def) = content.module_defs.get(&to_module.def_id) else {
-        return;
+        return Err(format!("unknown module def '{}'", to_module.def_id));
     };
 
     // Validate ports exist and have correct directions
@@ -2115,7 +2753,7 @@ pub(crate) fn handle_create_thermal_link(
         (Some(fp), Some(tp))
             if fp.direction == crate::PortDirection::Output
                 && tp.direction == crate::PortDirection::Input => {}
-        _ => return,
+        _ => return Err("link must join an output port to an input port".to_string()),
     }
 
     // Check for duplicate
@@ -2124,7 +2762,7 @@ pub(crate) fn handle_create_thermal_link(
         .get_mut(station_id)
         .expect("station verified above");
     if station.core.thermal_links.contains(link) {
-        return;
+        return Err("thermal link already exists".to_string());
     }
 
     station.core.thermal_links.push(link.clone());
@@ -2139,6 +2777,7 @@ pub(crate) fn handle_create_thermal_link(
             to_port_id: link.to_port_id.clone(),
         },
     ));
+    Ok(())
 }
 
 /// Remove a thermal link between two module ports on a station.
@@ -2147,26 +2786,28 @@ pub(crate) fn handle_remove_thermal_link(
     link: &crate::ThermalLink,
     station_id: &StationId,
     events: &mut Vec<EventEnvelope>,
-) {
+) -> Result<(), String> {
     let Some(station) = state.stations.get_mut(station_id) else {
-        return;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
 
     let before_len = station.core.thermal_links.len();
     station.core.thermal_links.retain(|l| l != link);
-    if station.core.thermal_links.len() < before_len {
-        events.push(crate::emit(
-            &mut state.counters,
-            state.meta.tick,
-            crate::Event::ThermalLinkRemoved {
-                station_id: station_id.clone(),
-                from_module_id: link.from_module_id.clone(),
-                from_port_id: link.from_port_id.clone(),
-                to_module_id: link.to_module_id.clone(),
-                to_port_id: link.to_port_id.clone(),
-            },
-        ));
+    if station.core.thermal_links.len() == before_len {
+        return Err("no such thermal link".to_string());
     }
+    events.push(crate::emit(
+        &mut state.counters,
+        state.meta.tick,
+        crate::Event::ThermalLinkRemoved {
+            station_id: station_id.clone(),
+            from_module_id: link.from_module_id.clone(),
+            from_port_id: link.from_port_id.clone(),
+            to_module_id: link.to_module_id.clone(),
+            to_port_id: link.to_port_id.clone(),
+        },
+    ));
+    Ok(())
 }
 
 /// Transfer molten material between two thermal container modules along a link.
@@ -2180,13 +2821,13 @@ pub(crate) fn handle_transfer_molten(
     element: &str,
     kg: f32,
     events: &mut Vec<EventEnvelope>,
-) {
+) -> Result<(), String> {
     if kg <= 0.0 {
-        return;
+        return Err("transfer amount must be positive".to_string());
     }
 
     let Some(station) = state.stations.get(station_id) else {
-        return;
+        return Err(format!("unknown station '{}'", station_id.0));
     };
 
     // Verify a thermal link exists between these modules
@@ -2195,7 +2836,7 @@ pub(crate) fn handle_transfer_molten(
             link.from_module_id == *from_module_id && link.to_module_id == *to_module_id
         });
     if !has_link {
-        return;
+        return Err("no thermal link between the modules".to_string());
     }
 
     // Find source and destination module indices
@@ -2203,7 +2844,7 @@ pub(crate) fn handle_transfer_molten(
         station.module_index_by_id(from_module_id),
         station.module_index_by_id(to_module_id),
     ) else {
-        return;
+        return Err("unknown source or destination module".to_string());
     };
 
     // Verify both are thermal containers
@@ -2216,7 +2857,7 @@ pub(crate) fn handle_transfer_molten(
         crate::ModuleKindState::ThermalContainer(_)
     );
     if !is_from_container || !is_to_container {
-        return;
+        return Err("both modules must be thermal containers".to_string());
     }
 
     // Check destination capacity
@@ -2239,7 +2880,7 @@ pub(crate) fn handle_transfer_molten(
     let crate::ModuleKindState::ThermalContainer(ref mut from_container) =
         station.core.modules[from_idx].kind_state
     else {
-        return;
+        return Err("both modules must be thermal containers".to_string());
     };
 
     // Find liquid material of the requested element
@@ -2254,7 +2895,7 @@ pub(crate) fn handle_transfer_molten(
         )
     });
     let Some(item_idx) = item_idx else {
-        return; // no liquid material of this element
+        return Err(format!("no liquid {element} in the source container")); // no liquid material of this element
     };
 
     // Extract the transfer amount from the source container
@@ -2266,7 +2907,7 @@ pub(crate) fn handle_transfer_molten(
             thermal,
             ..
         } => (*source_kg, *quality, thermal.clone()),
-        _ => return,
+        _ => return Err("source item is not a material".to_string()),
     };
 
     let transfer_kg = kg.min(source_kg_val);
@@ -2285,7 +2926,7 @@ pub(crate) fn handle_transfer_molten(
         kg: ref mut src_kg, ..
     } = from_container.held_items[item_idx]
     {
-        *src_kg -= transfer_kg;
+        crate::fixed::add_kg(src_kg, -transfer_kg);
     }
 
     let actual_kg = transfer_kg;
@@ -2316,7 +2957,7 @@ pub(crate) fn handle_transfer_molten(
         let crate::ModuleKindState::ThermalContainer(ref mut from_container) =
             station.core.modules[from_idx].kind_state
         else {
-            return;
+            return Err("both modules must be thermal containers".to_string());
         };
         from_container.held_items.push(transferred_item);
         events.push(crate::emit(
@@ -2329,14 +2970,14 @@ pub(crate) fn handle_transfer_molten(
                 element: element.to_string(),
             },
         ));
-        return;
+        return Ok(());
     }
 
     // Check destination capacity
     let crate::ModuleKindState::ThermalContainer(ref dest_container) =
         station.core.modules[to_idx].kind_state
     else {
-        return;
+        return Err("both modules must be thermal containers".to_string());
     };
     let current_dest_kg: f32 = crate::tasks::inventory_mass_kg(&dest_container.held_items);
     if current_dest_kg + actual_kg > capacity_kg {
@@ -2344,17 +2985,17 @@ pub(crate) fn handle_transfer_molten(
         let crate::ModuleKindState::ThermalContainer(ref mut from_container) =
             station.core.modules[from_idx].kind_state
         else {
-            return;
+            return Err("both modules must be thermal containers".to_string());
         };
         from_container.held_items.push(transferred_item);
-        return;
+        return Err("destination container is full".to_string());
     }
 
     // Place in destination
     let crate::ModuleKindState::ThermalContainer(ref mut dest_container) =
         station.core.modules[to_idx].kind_state
     else {
-        return;
+        return Err("both modules must be thermal containers".to_string());
     };
     dest_container.held_items.push(transferred_item);
 
@@ -2369,6 +3010,7 @@ pub(crate) fn handle_transfer_molten(
             kg: actual_kg,
         },
     ));
+    Ok(())
 }
 
 /// Apply deferred ship task assignments collected during the command loop.
@@ -2502,15 +3144,19 @@ mod tests {
                     SlotDef {
                         slot_type: SlotType("utility".to_string()),
                         label: "Utility 1".to_string(),
+                        adjacent: Vec::new(),
                     },
                     SlotDef {
                         slot_type: SlotType("industrial".to_string()),
                         label: "Industrial 1".to_string(),
+                        adjacent: Vec::new(),
                     },
                 ],
                 bonuses: vec![],
                 required_tech: None,
                 tags: vec![],
+                hold_partitions: std::collections::BTreeMap::new(),
+                replacement_value: 0.0,
             },
         );
         content.hulls = hulls;
@@ -2540,6 +3186,7 @@ mod tests {
         station.core.inventory.push(InventoryItem::Module {
             item_id: ModuleItemId("mod_item_0001".to_string()),
             module_def_id: "module_cargo_expander".to_string(),
+            wear: 0.0,
         });
         state
     }
@@ -2698,7 +3345,7 @@ mod tests {
             &mut events,
         );
 
-        assert!(result);
+        assert!(result.is_ok());
         let ship = state.ships.get(&ship_id).unwrap();
         assert_eq!(ship.fitted_modules.len(), 1);
         assert_eq!(ship.fitted_modules[0].slot_index, 0);
@@ -2739,7 +3386,7 @@ mod tests {
             &mut events,
         );
 
-        assert!(!result);
+        assert!(result.is_err());
         assert!(events.is_empty());
     }
 
@@ -2769,7 +3416,7 @@ mod tests {
             &mut events,
         );
 
-        assert!(!result);
+        assert!(result.is_err());
     }
 
     #[test]
@@ -2799,7 +3446,7 @@ mod tests {
             &mut events,
         );
 
-        assert!(result);
+        assert!(result.is_ok());
         let ship = state.ships.get(&ship_id).unwrap();
         assert!(ship.fitted_modules.is_empty());
         // Stats reverted to hull base
@@ -2831,10 +3478,12 @@ mod tests {
                     SlotDef {
                         slot_type: SlotType("industrial".to_string()),
                         label: "Industrial 1".to_string(),
+                        adjacent: Vec::new(),
                     },
                     SlotDef {
                         slot_type: SlotType("utility".to_string()),
                         label: "Utility 1".to_string(),
+                        adjacent: Vec::new(),
                     },
                 ],
                 bonuses: vec![crate::modifiers::Modifier::pct_mult(
@@ -2844,6 +3493,8 @@ mod tests {
                 )],
                 required_tech: None,
                 tags: vec![],
+                hold_partitions: std::collections::BTreeMap::new(),
+                replacement_value: 0.0,
             },
         );
         // Add a mining laser equipment module
@@ -2884,6 +3535,7 @@ mod tests {
         station.core.inventory.push(InventoryItem::Module {
             item_id: ModuleItemId("mod_item_laser".to_string()),
             module_def_id: "module_mining_laser".to_string(),
+            wear: 0.0,
         });
 
         // Fit mining laser
@@ -2896,7 +3548,8 @@ mod tests {
             &ModuleDefId("module_mining_laser".to_string()),
             &station_id,
             &mut events,
-        );
+        )
+        .unwrap();
         let ship = state.ships.get(&ship_id).unwrap();
         let mining_rate = ship.modifiers.resolve(StatId::MiningRate, 1.0);
         // Both hull (+25%) and module (+20%) should stack: 1.0 * 1.25 * 1.2 = 1.5
@@ -2915,7 +3568,8 @@ mod tests {
             &station_id,
             2,
             &mut events,
-        );
+        )
+        .unwrap();
         let ship = state.ships.get(&ship_id).unwrap();
         let mining_rate = ship.modifiers.resolve(StatId::MiningRate, 1.0);
         // Hull bonus should still be active after unfit
@@ -2925,6 +3579,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ship_class_overrides_hull_stats() {
+        let mut content = content_with_hull();
+        content.ship_classes.insert(
+            "class_courier".to_string(),
+            crate::ShipClassDef {
+                id: "class_courier".to_string(),
+                name: "Courier".to_string(),
+                cargo_capacity_m3: Some(20.0),
+                propellant_capacity_kg: None,
+                speed_multiplier: 2.0,
+                mining_rate_multiplier: 0.5,
+            },
+        );
+        let mut state = base_state(&content);
+        let ship = state
+            .ships
+            .get_mut(&crate::ShipId("ship_0001".to_string()))
+            .unwrap();
+        ship.class_id = Some("class_courier".to_string());
+        recompute_ship_stats(ship, &content);
+
+        assert!((ship.cargo_capacity_m3 - 20.0).abs() < 0.1);
+        assert_eq!(ship.speed_ticks_per_au, Some(60));
+        assert!((ship.propellant_capacity_kg - 10000.0).abs() < 0.1);
+        assert!(
+            (crate::mining_rate_kg_per_tick(ship, &content)
+                - content.constants.mining_rate_kg_per_tick * 0.5)
+                .abs()
+                < 1e-3
+        );
+    }
+
     #[test]
     fn modifier_source_hull_serialization_roundtrip() {
         let source = ModifierSource::Hull(HullId("hull_test".to_string()));
@@ -134,7 +134,7 @@ pub(crate) fn merge_material_lot(
             thermal.as_ref(),
             kg,
         );
-        *existing_kg += kg;
+        crate::fixed::add_kg(existing_kg, kg);
         *existing_thermal = blended;
     } else {
         inventory.push(InventoryItem::Material {
@@ -0,0 +1,260 @@
+//! Contracts.
+//!
+//! Every `contract_offer_interval_ticks`, while fewer than
+//! `contract_max_offers` wait, a new contract goes on offer: deliver some
+//! kg of a tradeable material, or discover some asteroids. A principal
+//! takes one with `AcceptContract`. A `Deliver` contract completes as soon
+//! as one of the holder's stations holds the full amount, which is handed
+//! over; a `Survey` contract counts the asteroids the holder's ships
+//! discover after accepting it. Meeting the terms by `deadline_tick` pays
+//! `reward`; `AbandonContract` or a missed deadline charges `penalty`
+//! instead. Offers nobody accepted are withdrawn at their deadline.
+//!
+//! Offers draw from a stream derived from the run seed and the tick, not
+//! from the shared run RNG, so turning contracts on or off does not shift
+//! any other subsystem's draws.
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    Contract, ContractId, ContractKind, Event, EventEnvelope, GameContent, GameState, PrincipalId,
+    ShipId, TradeItemSpec,
+};
+
+/// Take an open offer. Rejected when the contract is unknown, already held
+/// or past its deadline.
+pub(crate) fn handle_accept_contract(
+    state: &mut GameState,
+    contract_id: ContractId,
+    principal: &PrincipalId,
+    current_tick: u64,
+    events: &mut Vec<EventEnvelope>,
+) -> Result<(), String> {
+    let Some(contract) = state.contracts.iter_mut().find(|contract| {
+        contract.id == contract_id
+            && contract.holder.is_none()
+            && contract.deadline_tick >= current_tick
+    }) else {
+        return Err(format!("contract {} is not on offer", contract_id.0));
+    };
+    contract.holder = Some(principal.clone());
+    contract.progress = 0;
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::ContractAccepted {
+            contract_id,
+            holder: principal.clone(),
+        },
+    ));
+    Ok(())
+}
+
+/// Only the holder can abandon a contract; anyone else is rejected.
+pub(crate) fn handle_abandon_contract(
+    state: &mut GameState,
+    contract_id: ContractId,
+    principal: &PrincipalId,
+    current_tick: u64,
+    events: &mut Vec<EventEnvelope>,
+) -> Result<(), String> {
+    let Some(index) = state.contracts.iter().position(|contract| {
+        contract.id == contract_id && contract.holder.as_ref() == Some(principal)
+    }) else {
+        return Err(format!(
+            "{} does not hold contract {}",
+            principal.0, contract_id.0
+        ));
+    };
+    let contract = state.contracts.remove(index);
+    fail(state, contract, true, current_tick, events);
+    Ok(())
+}
+
+/// Count a discovery by `ship_id` toward its owner's survey contracts.
+/// Called when a survey reveals an asteroid.
+pub(crate) fn record_survey(state: &mut GameState, ship_id: &ShipId) {
+    let Some(owner) = state.ships.get(ship_id).map(|ship| &ship.owner) else {
+        return;
+    };
+    for contract in &mut state.contracts {
+        if matches!(contract.kind, ContractKind::Survey { .. })
+            && contract.holder.as_ref() == Some(owner)
+        {
+            contract.progress += 1;
+        }
+    }
+}
+
+/// Settle held contracts, withdraw stale offers, then make a new offer on
+/// offer ticks.
+pub(crate) fn tick_contracts(
+    state: &mut GameState,
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let current_tick = state.meta.tick;
+    if !state.contracts.is_empty() {
+        let contracts = std::mem::take(&mut state.contracts);
+        let mut remaining = Vec::with_capacity(contracts.len());
+        for contract in contracts {
+            if contract.holder.is_none() {
+                if contract.deadline_tick >= current_tick {
+                    remaining.push(contract);
+                }
+            } else if try_complete(state, &contract) {
+                let Some(holder) = contract.holder else {
+                    continue;
+                };
+                crate::fixed::add_credits(&mut state.balance, contract.reward);
+                events.push(crate::emit(
+                    &mut state.counters,
+                    current_tick,
+                    Event::ContractCompleted {
+                        contract_id: contract.id,
+                        holder,
+                        reward: contract.reward,
+                    },
+                ));
+            } else if contract.deadline_tick < current_tick {
+                fail(state, contract, false, current_tick, events);
+            } else {
+                remaining.push(contract);
+            }
+        }
+        state.contracts = remaining;
+    }
+
+    let interval = content.constants.contract_offer_interval_ticks;
+    if interval == 0 || current_tick == 0 || !current_tick.is_multiple_of(interval) {
+        return;
+    }
+    let open_offers = state
+        .contracts
+        .iter()
+        .filter(|contract| contract.holder.is_none())
+        .count();
+    if open_offers >= content.constants.contract_max_offers {
+        return;
+    }
+    let mut rng = ChaCha8Rng::seed_from_u64(crate::derive_seed(
+        crate::derive_seed(state.meta.seed, "contracts"),
+        &current_tick.to_string(),
+    ));
+    let Some((kind, reward)) = roll_offer(state, content, &mut rng) else {
+        return;
+    };
+    let contract = Contract {
+        id: ContractId(state.counters.next_contract_id),
+        kind,
+        reward,
+        penalty: reward * content.constants.contract_penalty_fraction,
+        offered_tick: current_tick,
+        deadline_tick: current_tick + content.constants.contract_duration_ticks,
+        holder: None,
+        progress: 0,
+    };
+    state.counters.next_contract_id += 1;
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::ContractOffered {
+            contract_id: contract.id,
+            kind: contract.kind.clone(),
+            reward: contract.reward,
+            penalty: contract.penalty,
+            deadline_tick: contract.deadline_tick,
+        },
+    ));
+    state.contracts.push(contract);
+}
+
+/// Whether the holder has met the terms. A met `Deliver` contract takes
+/// the goods from the first of the holder's stations that has them all.
+fn try_complete(state: &mut GameState, contract: &Contract) -> bool {
+    let Some(holder) = &contract.holder else {
+        return false;
+    };
+    match &contract.kind {
+        ContractKind::Survey { count } => contract.progress >= *count,
+        ContractKind::Deliver { element, kg } => {
+            let item_spec = TradeItemSpec::Material {
+                element: element.clone(),
+                kg: *kg,
+            };
+            let Some(station) = state.stations.values_mut().find(|station| {
+                station.owner == *holder
+                    && crate::trade::has_enough_for_export(&station.core.inventory, &item_spec)
+            }) else {
+                return false;
+            };
+            crate::trade::remove_inventory_items(&mut station.core.inventory, &item_spec);
+            station.invalidate_volume_cache();
+            true
+        }
+    }
+}
+
+fn fail(
+    state: &mut GameState,
+    contract: Contract,
+    abandoned: bool,
+    current_tick: u64,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let Some(holder) = contract.holder else {
+        return;
+    };
+    crate::fixed::add_credits(&mut state.balance, -contract.penalty);
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::ContractFailed {
+            contract_id: contract.id,
+            holder,
+            penalty: contract.penalty,
+            abandoned,
+        },
+    ));
+}
+
+/// Terms and reward of a new offer: a delivery of an exportable material,
+/// or a survey. `None` when neither is possible.
+fn roll_offer(
+    state: &GameState,
+    content: &GameContent,
+    rng: &mut impl Rng,
+) -> Option<(ContractKind, f64)> {
+    let constants = &content.constants;
+    // Sorted so the pick does not depend on hash order.
+    let mut materials: Vec<&String> = content
+        .pricing
+        .items
+        .iter()
+        .filter(|(_, entry)| entry.exportable && entry.category == "material")
+        .map(|(key, _)| key)
+        .collect();
+    materials.sort();
+    let survey = constants.contract_survey_count_max > 0;
+
+    if !materials.is_empty() && (!survey || rng.gen_bool(0.5)) {
+        let element = materials[rng.gen_range(0..materials.len())].clone();
+        let kg_min = constants.contract_deliver_kg_min;
+        let kg = if constants.contract_deliver_kg_max > kg_min {
+            rng.gen_range(kg_min..=constants.contract_deliver_kg_max)
+        } else {
+            kg_min
+        }
+        .round();
+        let price = crate::market::current_price(content, &state.market, &element)?;
+        let reward = price * f64::from(kg) * constants.contract_reward_premium;
+        return Some((ContractKind::Deliver { element, kg }, reward));
+    }
+    if survey {
+        let count = rng.gen_range(1..=constants.contract_survey_count_max);
+        let reward = f64::from(count) * constants.contract_reward_per_survey;
+        return Some((ContractKind::Survey { count }, reward));
+    }
+    None
+}
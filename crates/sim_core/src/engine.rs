@@ -15,14 +15,24 @@ pub fn trade_unlock_tick(constants: &crate::Constants) -> u64 {
 ///
 /// Order of operations:
 /// 1. Apply commands scheduled for this tick.
+///    1.4. Deduct crew salaries; accrue interest on and collect repayments of the loan.
+///    1.5. Start and end solar storms; lapse expired body claims; reprice the market.
 /// 2. Resolve ship tasks whose eta has arrived.
-/// 3. Tick station modules (processors, assemblers, sensors, labs, maintenance, thermal, boiloff).
+///    2.4. Release escorts whose charge is gone.
+///    2.5. Dispatch idle ships on due freight routes.
+/// 3. Tick station modules (processors, assemblers, sensors, labs, maintenance, thermal, boiloff,
+///    production reports, idle diagnoses).
 ///    3.5. Tick ground facility modules (same pipeline via proxy-station pattern).
+///    3.55. Resolve launch transits and in-transit import/freight deliveries.
+///    3.57. Evaporate volatiles from unrefrigerated ore lots; decay stored material quality.
+///    3.58. Advance station construction projects.
 ///    3.6. Tick satellites (survey discovery, science data, zone effect caches).
+///    3.65. Settle held contracts, withdraw stale offers and make new ones.
 /// 4. Advance research on all eligible techs.
 ///    4.5. Evaluate milestones (content-driven progression).
 ///    4.6. Evaluate sim events (content-driven random events).
 /// 5. Replenish scan sites if below threshold.
+///    5.5. Record significant events into each station's history buffer.
 /// 6. Increment tick counter.
 ///
 /// Returns all events produced this tick.
@@ -47,6 +57,11 @@ pub fn tick(
         apply_commands(state, commands, content, rng, &mut events)
     );
     deduct_crew_salaries(state, content, &mut events);
+    crate::loan::tick_loan(state, content, &mut events);
+    // Storms start and end before ships and stations act on them.
+    crate::storms::tick_solar_storms(state, content, rng, &mut events);
+    crate::claims::tick_claims(state, &mut events);
+    crate::market::tick_market(state, content, &mut events);
     // Ongoing tasks (Refuel) run every tick, before scheduled task resolution.
     timed!(
         timings,
@@ -58,11 +73,16 @@ pub fn tick(
         resolve_ship_tasks,
         resolve_ship_tasks(state, content, rng, &mut events)
     );
+    crate::threats::release_orphaned_escorts(state);
+    crate::freight_routes::tick_freight_routes(state, content, &mut events);
     timed!(
         timings,
         tick_stations,
         tick_stations(state, content, rng, &mut events, timings.as_deref_mut())
     );
+    if timings.is_some() {
+        crate::instrumentation::report_hotspots(state, content, &mut events);
+    }
     // Deduct per-module operating costs for ground facilities.
     deduct_operating_costs(state, content, &mut events);
     timed!(
@@ -70,15 +90,23 @@ pub fn tick(
         tick_ground_facilities,
         tick_ground_facilities(state, content, rng, &mut events)
     );
-    // Launch transit resolution + pad recovery. Not separately timed —
-    // O(facilities × transits), negligible vs station/ground ticking.
+    // Launch transit resolution + pad recovery + import/freight arrivals.
+    // Not separately timed — O(facilities × transits + orders), negligible
+    // vs station/ground ticking.
     resolve_launch_transits(state, content, rng, &mut events);
     tick_launch_pad_recovery(state, content);
+    resolve_import_deliveries(state, &mut events);
+    resolve_freight_deliveries(state, &mut events);
+    crate::spoilage::tick_ore_spoilage(state, content, &mut events);
+    crate::spoilage::tick_material_decay(state, content);
+    crate::projects::tick_projects(state, content, &mut events);
+    crate::station_build::tick_station_builds(state, content, &mut events);
     timed!(
         timings,
         tick_satellites,
         tick_satellites(state, content, rng, &mut events)
     );
+    crate::contracts::tick_contracts(state, content, &mut events);
     timed!(
         timings,
         advance_research,
@@ -107,6 +135,7 @@ pub fn tick(
         replenish_scan_sites,
         replenish_scan_sites(state, content, rng, &mut events)
     );
+    crate::station::history::record_station_history(state, content, &events);
 
     // Debug-only: verify cached ship stats match fresh recomputation.
     #[cfg(debug_assertions)]
@@ -152,7 +181,7 @@ fn deduct_operating_costs(
             .sum();
 
         if total_cost > 0.0 {
-            state.balance -= total_cost;
+            crate::fixed::add_credits(&mut state.balance, -total_cost);
             events.push(crate::emit(
                 &mut state.counters,
                 current_tick,
@@ -166,7 +195,6 @@ fn deduct_operating_costs(
     }
 }
 
-#[allow(clippy::too_many_lines)] // Thin dispatcher — all logic in commands.rs
 fn apply_commands(
     state: &mut GameState,
     commands: &[CommandEnvelope],
@@ -178,381 +206,556 @@ fn apply_commands(
 
     let current_tick = state.meta.tick;
 
+    // Commands for a later tick wait in `pending_commands`; those already
+    // due run ahead of this tick's arrivals. Stale commands are rejected.
+    for envelope in commands {
+        if envelope.execute_at_tick > current_tick {
+            commands::schedule_command(state, envelope.clone());
+        } else if envelope.execute_at_tick < current_tick {
+            events.push(crate::emit(
+                &mut state.counters,
+                current_tick,
+                crate::Event::CommandRejected {
+                    command_id: envelope.id,
+                    reason: format!(
+                        "execute_at_tick {} is before the current tick {current_tick}",
+                        envelope.execute_at_tick
+                    ),
+                },
+            ));
+        }
+    }
+    let due = commands::take_due_commands(state, current_tick);
+
     // Validate and collect assignments first to avoid split borrows.
     let mut assignments: Vec<(ShipId, TaskKind)> = Vec::new();
 
-    for envelope in commands {
-        if envelope.execute_at_tick != current_tick {
-            continue;
+    for envelope in due.iter().chain(
+        commands
+            .iter()
+            .filter(|envelope| envelope.execute_at_tick == current_tick),
+    ) {
+        if let Err(reason) =
+            dispatch_command(state, content, rng, envelope, &mut assignments, events)
+        {
+            events.push(crate::emit(
+                &mut state.counters,
+                current_tick,
+                crate::Event::CommandRejected {
+                    command_id: envelope.id,
+                    reason,
+                },
+            ));
         }
-        match &envelope.command {
-            Command::AssignShipTask { ship_id, task_kind } => {
-                commands::handle_assign_ship_task(
-                    state,
-                    content,
-                    ship_id,
-                    task_kind,
-                    &envelope.issued_by,
-                    &mut assignments,
-                );
-            }
-            Command::DeployStation {
-                ship_id,
-                kit_item_index,
-                target_position,
-            } => {
-                commands::handle_deploy_station(
-                    state,
-                    content,
-                    ship_id,
-                    *kit_item_index,
-                    target_position,
-                    current_tick,
-                    events,
-                );
-            }
-            Command::InstallModule {
-                facility_id,
+    }
+
+    commands::apply_ship_assignments(state, content, assignments, current_tick, events);
+}
+
+/// Route one command to its handler. An `Err` carries the reason the
+/// command was dropped.
+#[allow(clippy::too_many_lines)] // Thin dispatcher — all logic in commands.rs
+fn dispatch_command(
+    state: &mut GameState,
+    content: &GameContent,
+    rng: &mut impl Rng,
+    envelope: &CommandEnvelope,
+    assignments: &mut Vec<(ShipId, TaskKind)>,
+    events: &mut Vec<crate::EventEnvelope>,
+) -> Result<(), String> {
+    use crate::commands;
+
+    let current_tick = state.meta.tick;
+
+    match &envelope.command {
+        Command::AssignShipTask { ship_id, task_kind } => commands::handle_assign_ship_task(
+            state,
+            content,
+            ship_id,
+            task_kind,
+            &envelope.issued_by,
+            assignments,
+            events,
+        ),
+        Command::DeployStation {
+            ship_id,
+            kit_item_index,
+            target_position,
+        } => commands::handle_deploy_station(
+            state,
+            content,
+            ship_id,
+            *kit_item_index,
+            target_position,
+            current_tick,
+            events,
+        ),
+        Command::InstallModule {
+            facility_id,
+            module_item_id,
+            slot_index,
+        } => match facility_id {
+            crate::FacilityId::Station(station_id) => commands::handle_install_module(
+                state,
+                content,
+                station_id,
                 module_item_id,
-                slot_index,
-            } => match facility_id {
-                crate::FacilityId::Station(station_id) => {
-                    commands::handle_install_module(
-                        state,
-                        content,
-                        station_id,
-                        module_item_id,
-                        *slot_index,
-                        current_tick,
-                        events,
-                    );
-                }
-                crate::FacilityId::Ground(gf_id) => {
-                    // Ground facilities don't use frames — the slot_index
-                    // field is ignored on the ground install path.
-                    commands::handle_ground_install_module(
-                        state,
-                        content,
-                        gf_id,
-                        module_item_id,
-                        current_tick,
-                        events,
-                    );
-                }
-            },
-            Command::UninstallModule {
-                facility_id,
-                module_id,
-            } => {
-                let crate::FacilityId::Station(station_id) = facility_id else {
-                    continue;
-                };
-                commands::handle_uninstall_module(
+                *slot_index,
+                current_tick,
+                events,
+            ),
+            crate::FacilityId::Ground(gf_id) => {
+                // Ground facilities don't use frames — the slot_index
+                // field is ignored on the ground install path.
+                commands::handle_ground_install_module(
                     state,
                     content,
-                    station_id,
-                    module_id,
-                    current_tick,
-                    events,
-                );
-            }
-            Command::SetModuleEnabled {
-                facility_id,
-                module_id,
-                enabled,
-            } => match facility_id {
-                crate::FacilityId::Station(station_id) => {
-                    commands::handle_set_module_enabled(
-                        state,
-                        station_id,
-                        module_id,
-                        *enabled,
-                        current_tick,
-                        events,
-                    );
-                }
-                crate::FacilityId::Ground(gf_id) => {
-                    commands::handle_ground_set_module_enabled(
-                        state,
-                        gf_id,
-                        module_id,
-                        *enabled,
-                        current_tick,
-                        events,
-                    );
-                }
-            },
-            Command::SetModuleThreshold {
-                facility_id,
-                module_id,
-                threshold_kg,
-            } => {
-                let crate::FacilityId::Station(station_id) = facility_id else {
-                    continue;
-                };
-                commands::handle_set_module_threshold(
-                    state,
-                    station_id,
-                    module_id,
-                    *threshold_kg,
+                    gf_id,
+                    module_item_id,
                     current_tick,
                     events,
-                );
+                )
             }
-            Command::AssignLabTech {
+        },
+        Command::UninstallModule {
+            facility_id,
+            module_id,
+        } => {
+            let crate::FacilityId::Station(station_id) = facility_id else {
+                return Err("ground facilities do not support this command".to_string());
+            };
+            commands::handle_uninstall_module(
+                state,
+                content,
                 station_id,
                 module_id,
-                tech_id,
-            } => {
-                commands::handle_assign_lab_tech(state, station_id, module_id, tech_id.as_ref());
-            }
-            Command::SetAssemblerCap {
+                current_tick,
+                events,
+            )
+        }
+        Command::ScrapModule {
+            facility_id,
+            module_id,
+        } => {
+            let crate::FacilityId::Station(station_id) = facility_id else {
+                return Err("ground facilities do not support this command".to_string());
+            };
+            commands::handle_scrap_module(
+                state,
+                content,
                 station_id,
                 module_id,
-                component_id,
-                max_stock,
-            } => {
-                commands::handle_set_assembler_cap(
-                    state,
-                    station_id,
-                    module_id,
-                    component_id,
-                    *max_stock,
-                );
-            }
-            Command::Import {
-                facility_id,
-                item_spec,
-            } => match facility_id {
-                crate::FacilityId::Station(station_id) => {
-                    commands::handle_import(
-                        state,
-                        content,
-                        station_id,
-                        item_spec,
-                        current_tick,
-                        rng,
-                        events,
-                    );
-                }
-                crate::FacilityId::Ground(gf_id) => {
-                    commands::handle_ground_import(
-                        state,
-                        content,
-                        gf_id,
-                        item_spec,
-                        current_tick,
-                        rng,
-                        events,
-                    );
-                }
-            },
-            Command::Export {
-                facility_id,
-                item_spec,
-            } => match facility_id {
-                crate::FacilityId::Station(station_id) => {
-                    commands::handle_export(
-                        state,
-                        content,
-                        station_id,
-                        item_spec,
-                        current_tick,
-                        events,
-                    );
-                }
-                crate::FacilityId::Ground(gf_id) => {
-                    commands::handle_ground_export(
-                        state,
-                        content,
-                        gf_id,
-                        item_spec,
-                        current_tick,
-                        events,
-                    );
-                }
-            },
-            Command::JettisonSlag { station_id } => {
-                commands::handle_jettison_slag(state, station_id, current_tick, events);
-            }
-            Command::SelectRecipe {
+                current_tick,
+                events,
+            )
+        }
+        Command::UpgradeModule {
+            facility_id,
+            module_id,
+        } => {
+            let crate::FacilityId::Station(station_id) = facility_id else {
+                return Err("ground facilities do not support this command".to_string());
+            };
+            commands::handle_upgrade_module(
+                state,
+                content,
                 station_id,
                 module_id,
-                recipe_id,
-            } => {
-                commands::handle_select_recipe(state, content, station_id, module_id, recipe_id);
-            }
-            Command::SetModulePriority {
+                current_tick,
+                events,
+            )
+        }
+        Command::SetModuleEnabled {
+            facility_id,
+            module_id,
+            enabled,
+        } => match facility_id {
+            crate::FacilityId::Station(station_id) => commands::handle_set_module_enabled(
+                state,
                 station_id,
                 module_id,
-                priority,
-            } => {
-                commands::handle_set_module_priority(state, station_id, module_id, *priority);
-            }
-            Command::FitShipModule {
-                ship_id,
-                slot_index,
-                module_def_id,
-                station_id,
-            } => {
-                commands::handle_fit_ship_module(
-                    state,
-                    content,
-                    ship_id,
-                    *slot_index,
-                    module_def_id,
-                    station_id,
-                    events,
-                );
-            }
-            Command::UnfitShipModule {
-                ship_id,
-                slot_index,
-                station_id,
-            } => {
-                commands::handle_unfit_ship_module(
-                    state,
-                    content,
-                    ship_id,
-                    *slot_index,
-                    station_id,
-                    current_tick,
-                    events,
-                );
-            }
-            Command::AssignCrew {
-                station_id,
+                *enabled,
+                current_tick,
+                events,
+            ),
+            crate::FacilityId::Ground(gf_id) => commands::handle_ground_set_module_enabled(
+                state,
+                gf_id,
                 module_id,
-                role,
-                count,
-            } => {
-                commands::handle_assign_crew(
-                    state, content, station_id, module_id, role, *count, events,
-                );
-            }
-            Command::UnassignCrew {
+                *enabled,
+                current_tick,
+                events,
+            ),
+        },
+        Command::SetModuleThreshold {
+            facility_id,
+            module_id,
+            threshold_kg,
+        } => {
+            let crate::FacilityId::Station(station_id) = facility_id else {
+                return Err("ground facilities do not support this command".to_string());
+            };
+            commands::handle_set_module_threshold(
+                state,
                 station_id,
                 module_id,
-                role,
-                count,
-            } => {
-                commands::handle_unassign_crew(
-                    state, content, station_id, module_id, role, *count, events,
-                );
-            }
-            Command::CreateThermalLink {
-                station_id,
-                from_module_id,
-                from_port_id,
-                to_module_id,
-                to_port_id,
-            } => {
-                let link = crate::ThermalLink {
-                    from_module_id: from_module_id.clone(),
-                    from_port_id: from_port_id.clone(),
-                    to_module_id: to_module_id.clone(),
-                    to_port_id: to_port_id.clone(),
-                };
-                commands::handle_create_thermal_link(state, content, &link, station_id, events);
-            }
-            Command::RemoveThermalLink {
-                station_id,
-                from_module_id,
-                from_port_id,
-                to_module_id,
-                to_port_id,
-            } => {
-                let link = crate::ThermalLink {
-                    from_module_id: from_module_id.clone(),
-                    from_port_id: from_port_id.clone(),
-                    to_module_id: to_module_id.clone(),
-                    to_port_id: to_port_id.clone(),
-                };
-                commands::handle_remove_thermal_link(state, &link, station_id, events);
-            }
-            Command::TransferMolten {
-                station_id,
-                from_module_id,
-                to_module_id,
-                element,
-                kg,
-            } => {
-                commands::handle_transfer_molten(
-                    state,
-                    content,
-                    station_id,
-                    from_module_id,
-                    to_module_id,
-                    element,
-                    *kg,
-                    events,
-                );
-            }
-            Command::Launch {
-                facility_id,
-                rocket_def_id,
-                payload,
-                destination,
-            } => {
-                commands::handle_launch(
-                    state,
-                    content,
-                    facility_id,
-                    rocket_def_id,
-                    payload,
-                    destination,
-                    current_tick,
-                    events,
-                );
-            }
-            Command::DeploySatellite {
+                *threshold_kg,
+                current_tick,
+                events,
+            )
+        }
+        Command::AssignLabTech {
+            station_id,
+            module_id,
+            tech_id,
+        } => commands::handle_assign_lab_tech(state, station_id, module_id, tech_id.as_ref()),
+        Command::SetAssemblerCap {
+            station_id,
+            module_id,
+            component_id,
+            max_stock,
+        } => commands::handle_set_assembler_cap(
+            state,
+            station_id,
+            module_id,
+            component_id,
+            *max_stock,
+        ),
+        Command::Import {
+            facility_id,
+            item_spec,
+        } => match facility_id {
+            crate::FacilityId::Station(station_id) => commands::handle_import(
+                state,
+                content,
                 station_id,
-                satellite_def_id,
-            } => {
-                commands::handle_deploy_satellite(
-                    state,
-                    content,
-                    station_id,
-                    satellite_def_id,
-                    current_tick,
-                    rng,
-                    events,
-                );
-            }
-            Command::SetStrategyConfig { config } => {
-                // Full replacement — not merge. The interpreter cache (if
-                // anyone's listening) observes the change on the next
-                // `AutopilotController::generate_commands` pass because the
-                // runtime owns its own dirty flag; the authoritative
-                // strategy config lives on `GameState`.
-                state.strategy_config = config.clone();
-                events.push(crate::emit(
-                    &mut state.counters,
-                    current_tick,
-                    crate::Event::StrategyConfigChanged {},
-                ));
-            }
-            Command::TransferItems {
-                ship_id,
-                from_station,
-                to_station,
-                items,
-            } => {
-                commands::handle_transfer_items(
-                    state,
-                    content,
-                    ship_id,
-                    from_station,
-                    to_station,
-                    items,
-                    current_tick,
-                    events,
-                );
+                &envelope.issued_by,
+                item_spec,
+                current_tick,
+                rng,
+                events,
+            ),
+            crate::FacilityId::Ground(gf_id) => commands::handle_ground_import(
+                state,
+                content,
+                gf_id,
+                item_spec,
+                current_tick,
+                rng,
+                events,
+            ),
+        },
+        Command::Export {
+            facility_id,
+            item_spec,
+        } => match facility_id {
+            crate::FacilityId::Station(station_id) => {
+                commands::handle_export(state, content, station_id, item_spec, current_tick, events)
             }
+            crate::FacilityId::Ground(gf_id) => commands::handle_ground_export(
+                state,
+                content,
+                gf_id,
+                item_spec,
+                current_tick,
+                events,
+            ),
+        },
+        Command::CancelImportOrder { order_id } => commands::handle_cancel_import_order(
+            state,
+            content,
+            *order_id,
+            &envelope.issued_by,
+            current_tick,
+            events,
+        ),
+        Command::ScheduleFreight {
+            from_station,
+            to_station,
+            item_spec,
+        } => commands::handle_schedule_freight(
+            state,
+            content,
+            from_station,
+            to_station,
+            item_spec,
+            current_tick,
+            events,
+        ),
+        Command::CreateFreightRoute {
+            from_station,
+            to_station,
+            item_spec,
+            interval_ticks,
+        } => crate::freight_routes::handle_create_freight_route(
+            state,
+            content,
+            &envelope.issued_by,
+            from_station,
+            to_station,
+            item_spec,
+            *interval_ticks,
+            current_tick,
+            events,
+        ),
+        Command::StartProject {
+            station_id,
+            project_def_id,
+        } => crate::projects::handle_start_project(
+            state,
+            content,
+            station_id,
+            project_def_id,
+            current_tick,
+            events,
+        ),
+        Command::BuildStation {
+            station_id,
+            kit_component_id,
+            position,
+        } => crate::station_build::handle_build_station(
+            state,
+            content,
+            station_id,
+            kit_component_id,
+            position,
+            current_tick,
+            events,
+        ),
+        Command::CancelProject { project_id } => crate::projects::handle_cancel_project(
+            state,
+            content,
+            *project_id,
+            current_tick,
+            events,
+        ),
+        Command::ClaimBody { body_id } => crate::claims::handle_claim_body(
+            state,
+            content,
+            body_id,
+            &envelope.issued_by,
+            current_tick,
+            events,
+        ),
+        Command::CancelCommand { command_id } => commands::handle_cancel_command(
+            state,
+            *command_id,
+            &envelope.issued_by,
+            current_tick,
+            events,
+        ),
+        Command::ReleaseClaim { body_id } => crate::claims::handle_release_claim(
+            state,
+            body_id,
+            &envelope.issued_by,
+            current_tick,
+            events,
+        ),
+        Command::AcceptContract { contract_id } => crate::contracts::handle_accept_contract(
+            state,
+            *contract_id,
+            &envelope.issued_by,
+            current_tick,
+            events,
+        ),
+        Command::AbandonContract { contract_id } => crate::contracts::handle_abandon_contract(
+            state,
+            *contract_id,
+            &envelope.issued_by,
+            current_tick,
+            events,
+        ),
+        Command::JettisonSlag { station_id } => {
+            commands::handle_jettison_slag(state, content, station_id, current_tick, events)
+        }
+        Command::SelectRecipe {
+            station_id,
+            module_id,
+            recipe_id,
+        } => commands::handle_select_recipe(state, content, station_id, module_id, recipe_id),
+        Command::QueueAssemblerJob {
+            station_id,
+            module_id,
+            recipe_id,
+            count,
+        } => commands::handle_queue_assembler_job(
+            state, content, station_id, module_id, recipe_id, *count,
+        ),
+        Command::SetModulePriority {
+            station_id,
+            module_id,
+            priority,
+        } => commands::handle_set_module_priority(state, station_id, module_id, *priority),
+        Command::SetPowerPolicy { station_id, policy } => {
+            commands::handle_set_power_policy(state, station_id, *policy)
+        }
+        Command::FitShipModule {
+            ship_id,
+            slot_index,
+            module_def_id,
+            station_id,
+        } => commands::handle_fit_ship_module(
+            state,
+            content,
+            ship_id,
+            *slot_index,
+            module_def_id,
+            station_id,
+            events,
+        ),
+        Command::UnfitShipModule {
+            ship_id,
+            slot_index,
+            station_id,
+        } => commands::handle_unfit_ship_module(
+            state,
+            content,
+            ship_id,
+            *slot_index,
+            station_id,
+            current_tick,
+            events,
+        ),
+        Command::AssignCrew {
+            station_id,
+            module_id,
+            role,
+            count,
+        } => commands::handle_assign_crew(
+            state, content, station_id, module_id, role, *count, events,
+        ),
+        Command::UnassignCrew {
+            station_id,
+            module_id,
+            role,
+            count,
+        } => commands::handle_unassign_crew(
+            state, content, station_id, module_id, role, *count, events,
+        ),
+        Command::CreateThermalLink {
+            station_id,
+            from_module_id,
+            from_port_id,
+            to_module_id,
+            to_port_id,
+        } => {
+            let link = crate::ThermalLink {
+                from_module_id: from_module_id.clone(),
+                from_port_id: from_port_id.clone(),
+                to_module_id: to_module_id.clone(),
+                to_port_id: to_port_id.clone(),
+            };
+            commands::handle_create_thermal_link(state, content, &link, station_id, events)
+        }
+        Command::RemoveThermalLink {
+            station_id,
+            from_module_id,
+            from_port_id,
+            to_module_id,
+            to_port_id,
+        } => {
+            let link = crate::ThermalLink {
+                from_module_id: from_module_id.clone(),
+                from_port_id: from_port_id.clone(),
+                to_module_id: to_module_id.clone(),
+                to_port_id: to_port_id.clone(),
+            };
+            commands::handle_remove_thermal_link(state, &link, station_id, events)
         }
+        Command::TransferMolten {
+            station_id,
+            from_module_id,
+            to_module_id,
+            element,
+            kg,
+        } => commands::handle_transfer_molten(
+            state,
+            content,
+            station_id,
+            from_module_id,
+            to_module_id,
+            element,
+            *kg,
+            events,
+        ),
+        Command::Launch {
+            facility_id,
+            rocket_def_id,
+            payload,
+            destination,
+        } => commands::handle_launch(
+            state,
+            content,
+            facility_id,
+            rocket_def_id,
+            payload,
+            destination,
+            current_tick,
+            events,
+        ),
+        Command::DeploySatellite {
+            station_id,
+            satellite_def_id,
+        } => commands::handle_deploy_satellite(
+            state,
+            content,
+            station_id,
+            satellite_def_id,
+            current_tick,
+            rng,
+            events,
+        ),
+        Command::SetStrategyConfig { config } => {
+            // Full replacement — not merge. The interpreter cache (if
+            // anyone's listening) observes the change on the next
+            // `AutopilotController::generate_commands` pass because the
+            // runtime owns its own dirty flag; the authoritative
+            // strategy config lives on `GameState`.
+            state.strategy_config = config.clone();
+            events.push(crate::emit(
+                &mut state.counters,
+                current_tick,
+                crate::Event::StrategyConfigChanged {},
+            ));
+            Ok(())
+        }
+        Command::SetResearchQueue { tech_ids } => {
+            crate::research::handle_set_research_queue(state, content, tech_ids);
+            Ok(())
+        }
+        Command::TransferItems {
+            ship_id,
+            from_station,
+            to_station,
+            items,
+        } => commands::handle_transfer_items(
+            state,
+            content,
+            ship_id,
+            from_station,
+            to_station,
+            items,
+            current_tick,
+            events,
+        ),
+        Command::TransferCargo {
+            ship_id,
+            counterparty,
+            direction,
+            items,
+        } => crate::cargo_transfer::handle_transfer_cargo(
+            state,
+            content,
+            ship_id,
+            TaskKind::TransferCargo {
+                counterparty: counterparty.clone(),
+                direction: *direction,
+                items: items.clone(),
+            },
+            &envelope.issued_by,
+            assignments,
+        ),
     }
-
-    commands::apply_ship_assignments(state, content, assignments, current_tick, events);
 }
 
 /// Resolve completed launch transits — deliver payloads that have arrived.
@@ -598,6 +801,79 @@ fn resolve_launch_transits(
     }
 }
 
+/// Deliver station imports whose `arrival_tick` has been reached. Space was
+/// reserved when the order was placed, so arrivals are merged unconditionally.
+fn resolve_import_deliveries(state: &mut GameState, events: &mut Vec<crate::EventEnvelope>) {
+    let current_tick = state.meta.tick;
+    if !state
+        .import_orders
+        .iter()
+        .any(|order| current_tick >= order.arrival_tick)
+    {
+        return;
+    }
+    let (arrived, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut state.import_orders)
+        .into_iter()
+        .partition(|order| current_tick >= order.arrival_tick);
+    state.import_orders = pending;
+
+    for order in arrived {
+        // Destination no longer exists — goods are lost.
+        let Some(station) = state.stations.get_mut(&order.station_id) else {
+            continue;
+        };
+        crate::trade::merge_into_inventory(&mut station.core.inventory, order.items);
+        station.invalidate_volume_cache();
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            crate::Event::ImportDelivered {
+                order_id: order.id,
+                station_id: order.station_id,
+                item_spec: order.item_spec,
+            },
+        ));
+    }
+}
+
+/// Deliver freight shipments whose `arrival_tick` has been reached. Space was
+/// reserved at scheduling, so arrivals are merged unconditionally.
+fn resolve_freight_deliveries(state: &mut GameState, events: &mut Vec<crate::EventEnvelope>) {
+    let current_tick = state.meta.tick;
+    if !state
+        .freight_orders
+        .iter()
+        .any(|order| current_tick >= order.arrival_tick)
+    {
+        return;
+    }
+    let (arrived, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut state.freight_orders)
+        .into_iter()
+        .partition(|order| current_tick >= order.arrival_tick);
+    state.freight_orders = pending;
+
+    for order in arrived {
+        // Destination no longer exists — goods are lost.
+        let Some(station) = state.stations.get_mut(&order.to_station) else {
+            continue;
+        };
+        crate::trade::merge_into_inventory(&mut station.core.inventory, order.items);
+        station.invalidate_volume_cache();
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            crate::Event::FreightDelivered {
+                order_id: order.id,
+                from_station: order.from_station,
+                to_station: order.to_station,
+                item_spec: order.item_spec,
+                route_id: None,
+                ship_id: None,
+            },
+        ));
+    }
+}
+
 /// Resolve a single completed launch transit payload — deliver supplies, deploy
 /// station, or create satellite.
 fn resolve_transit_payload(
@@ -644,6 +920,10 @@ fn resolve_transit_payload(
                 },
                 frame_id: None,
                 leaders: Vec::new(),
+                owner: crate::PrincipalId("principal_autopilot".to_string()),
+                expansions: crate::StationExpansions::default(),
+                power_policy: crate::PowerPolicy::default(),
+                history: std::collections::VecDeque::new(),
             };
             state.stations.insert(station_id.clone(), station);
             state.counters.stations_deployed += 1;
@@ -770,8 +1050,11 @@ fn resolve_ship_tasks(
         .values()
         .filter(|ship| {
             matches!(&ship.task, Some(task)
-                if task.eta_tick == current_tick
-                && !matches!(task.kind, TaskKind::Idle | TaskKind::Refuel { .. }))
+            if task.eta_tick == current_tick
+            && !matches!(
+                task.kind,
+                TaskKind::Idle | TaskKind::Refuel { .. } | TaskKind::Escort { .. }
+            ))
         })
         .map(|ship| ship.id.clone())
         .collect();
@@ -797,6 +1080,14 @@ fn replenish_scan_sites(
     rng: &mut impl Rng,
     events: &mut Vec<crate::EventEnvelope>,
 ) {
+    if !content.subsystems.enabled(crate::Subsystem::Mining) {
+        return;
+    }
+    // Belts own their spawning when any are defined.
+    if !content.belts.is_empty() {
+        crate::belts::tick_belts(state, content, rng, events);
+        return;
+    }
     // Interval gating: only check on the configured tick interval.
     let interval = content.constants.replenish_check_interval_ticks;
     if interval > 0 && !state.meta.tick.is_multiple_of(interval) {
@@ -821,36 +1112,47 @@ fn replenish_scan_sites(
         return;
     }
 
-    let current_tick = state.meta.tick;
     let deficit = target - state.scan_sites.len();
     let batch = deficit.min(content.constants.replenish_batch_size);
 
     for _ in 0..batch {
         let body = crate::pick_zone_weighted(&zone_bodies, rng);
-        let zone_class = body.zone.as_ref().expect("zone body").resource_class;
-        let template = crate::pick_template_biased(templates, zone_class, rng);
-        let position = crate::random_position_in_zone(body, rng);
-        let uuid = crate::generate_uuid(rng);
-        let site_id = SiteId(format!("site_{uuid}"));
-
-        state.scan_sites.push(ScanSite {
-            id: site_id.clone(),
-            position: position.clone(),
-            template_id: template.id.clone(),
-        });
-
-        events.push(crate::emit(
-            &mut state.counters,
-            current_tick,
-            crate::Event::ScanSiteSpawned {
-                site_id,
-                position,
-                template_id: template.id.clone(),
-            },
-        ));
+        spawn_scan_site(state, body, templates, rng, events);
     }
 }
 
+/// Spawn one scan site in `body`'s zone from `templates`, biased toward the
+/// zone's resource class, and emit `ScanSiteSpawned`.
+pub(crate) fn spawn_scan_site(
+    state: &mut GameState,
+    body: &crate::OrbitalBodyDef,
+    templates: &[crate::AsteroidTemplateDef],
+    rng: &mut impl Rng,
+    events: &mut Vec<crate::EventEnvelope>,
+) {
+    let zone_class = body.zone.as_ref().expect("zone body").resource_class;
+    let template = crate::pick_template_biased(templates, zone_class, rng);
+    let position = crate::random_position_in_zone(body, rng);
+    let uuid = crate::generate_uuid(rng);
+    let site_id = SiteId(format!("site_{uuid}"));
+
+    state.scan_sites.push(ScanSite {
+        id: site_id.clone(),
+        position: position.clone(),
+        template_id: template.id.clone(),
+    });
+
+    events.push(crate::emit(
+        &mut state.counters,
+        state.meta.tick,
+        crate::Event::ScanSiteSpawned {
+            site_id,
+            position,
+            template_id: template.id.clone(),
+        },
+    ));
+}
+
 /// Deduct crew salaries from the balance. Emits `StationBankrupt` on the
 /// zero-crossing transition (once, not every tick while bankrupt).
 fn deduct_crew_salaries(
@@ -875,7 +1177,7 @@ fn deduct_crew_salaries(
 
     if total_salary > 0.0 {
         let was_positive = state.balance >= 0.0;
-        state.balance -= total_salary;
+        crate::fixed::add_credits(&mut state.balance, -total_salary);
         if was_positive && state.balance < 0.0 {
             // Emit bankrupt event for the first station (balance is global)
             if let Some(station_id) = station_ids.first() {
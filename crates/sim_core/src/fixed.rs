@@ -0,0 +1,114 @@
+//! Fixed-point accumulation behind the `fixed-point` feature.
+//!
+//! Balances, research evidence and inventory masses are sums of many small
+//! float deltas, and the rounding in those sums can differ between
+//! platforms and compilers, which breaks replay and digest comparison
+//! between machines. With the feature on, each of these accumulators adds
+//! in whole `i64` units of a fixed quantum and converts back, so every sum
+//! lands on the same grid everywhere. State still stores floats (the API is
+//! unchanged); only the arithmetic is. Without the feature these are plain
+//! float additions and runs are bit-identical to before.
+
+/// Credits are kept to 1/10,000 of a credit.
+pub const CREDIT_SCALE: f64 = 10_000.0;
+/// Inventory masses are kept to the gram.
+pub const MASS_SCALE: f64 = 1_000.0;
+/// Research points are kept to a millionth of a point.
+pub const EVIDENCE_SCALE: f64 = 1_000_000.0;
+
+/// Add `delta` (negative to spend) to a balance.
+pub(crate) fn add_credits(balance: &mut f64, delta: f64) {
+    add_f64(balance, delta, CREDIT_SCALE);
+}
+
+/// Add `delta` kg (negative to remove) to an inventory mass.
+pub(crate) fn add_kg(kg: &mut f32, delta: f32) {
+    add_f32(kg, delta, MASS_SCALE);
+}
+
+/// Add `delta` kg to a run-long mass total.
+pub(crate) fn add_total_kg(total: &mut f64, delta: f64) {
+    add_f64(total, delta, MASS_SCALE);
+}
+
+/// Add research points to a domain's evidence.
+pub(crate) fn add_points(points: &mut f32, delta: f32) {
+    add_f32(points, delta, EVIDENCE_SCALE);
+}
+
+#[cfg(not(feature = "fixed-point"))]
+fn add_f64(total: &mut f64, delta: f64, _scale: f64) {
+    *total += delta;
+}
+
+#[cfg(not(feature = "fixed-point"))]
+fn add_f32(total: &mut f32, delta: f32, _scale: f64) {
+    *total += delta;
+}
+
+#[cfg(feature = "fixed-point")]
+fn add_f64(total: &mut f64, delta: f64, scale: f64) {
+    *total = from_units(
+        to_units(*total, scale).saturating_add(to_units(delta, scale)),
+        scale,
+    );
+}
+
+#[cfg(feature = "fixed-point")]
+#[allow(clippy::cast_possible_truncation)]
+fn add_f32(total: &mut f32, delta: f32, scale: f64) {
+    let units =
+        to_units(f64::from(*total), scale).saturating_add(to_units(f64::from(delta), scale));
+    *total = from_units(units, scale) as f32;
+}
+
+/// `as` saturates out-of-range values and maps NaN to 0, the same on every
+/// target.
+#[cfg(feature = "fixed-point")]
+#[allow(clippy::cast_possible_truncation)]
+pub fn to_units(value: f64, scale: f64) -> i64 {
+    (value * scale).round() as i64
+}
+
+#[cfg(feature = "fixed-point")]
+#[allow(clippy::cast_precision_loss)]
+pub fn from_units(units: i64, scale: f64) -> f64 {
+    units as f64 / scale
+}
+
+#[cfg(all(test, feature = "fixed-point"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sums_land_on_the_quantum_grid() {
+        let mut balance = 0.0;
+        for _ in 0..10 {
+            add_credits(&mut balance, 0.1);
+        }
+        assert_eq!(to_units(balance, CREDIT_SCALE), 10_000);
+        assert!((balance - 1.0).abs() < f64::EPSILON);
+
+        let mut kg = 1.0_f32;
+        add_kg(&mut kg, 0.000_4);
+        assert_eq!(
+            to_units(f64::from(kg), MASS_SCALE),
+            1_000,
+            "sub-gram deltas round away"
+        );
+    }
+
+    #[test]
+    fn order_of_additions_does_not_matter() {
+        let deltas = [1e8, 0.123_45, -1e8, 0.000_07, 3.5];
+        let mut forward = 0.0;
+        for delta in deltas {
+            add_credits(&mut forward, delta);
+        }
+        let mut backward = 0.0;
+        for delta in deltas.iter().rev() {
+            add_credits(&mut backward, *delta);
+        }
+        assert_eq!(forward.to_bits(), backward.to_bits());
+    }
+}
@@ -0,0 +1,209 @@
+//! Recurring ship-run freight between stations.
+//!
+//! `Command::CreateFreightRoute` records a `FreightRoute` owned by the
+//! issuer. Each tick after ship tasks resolve, a route that is due, has no
+//! run in progress and whose origin holds the full `item_spec` sends the
+//! owner's idle ship nearest the origin on the same
+//! `Transit → Pickup → Transit → Deposit` chain as `TransferItems`. The
+//! next run may start `interval_ticks` after dispatch. When the ship's
+//! deposit at the destination lands, `FreightDelivered` reports the run.
+//! A run whose ship is lost or reassigned is dropped, freeing the route.
+
+use crate::{
+    Event, EventEnvelope, FreightRoute, FreightTrip, GameContent, GameState, PrincipalId, ShipId,
+    StationId, TaskKind, TradeItemSpec,
+};
+
+/// Record a new route. Rejected for unknown or identical stations, either
+/// station not owned by `owner`, crew or unknown items, and a zero interval.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn handle_create_freight_route(
+    state: &mut GameState,
+    content: &GameContent,
+    owner: &PrincipalId,
+    from_station: &StationId,
+    to_station: &StationId,
+    item_spec: &TradeItemSpec,
+    interval_ticks: u64,
+    current_tick: u64,
+    events: &mut Vec<EventEnvelope>,
+) -> Result<(), String> {
+    if from_station == to_station
+        || interval_ticks == 0
+        || matches!(item_spec, TradeItemSpec::Crew { .. })
+        || item_spec.compute_mass(content).is_none()
+        || !state.stations.contains_key(from_station)
+        || !state.stations.contains_key(to_station)
+    {
+        return Err("invalid freight route".to_string());
+    }
+    for station_id in [from_station, to_station] {
+        if state.stations[station_id].owner != *owner {
+            return Err(format!(
+                "station '{}' is not owned by {}",
+                station_id.0, owner.0
+            ));
+        }
+    }
+    let route_id = crate::FreightRouteId(state.counters.next_freight_route_id);
+    state.counters.next_freight_route_id += 1;
+    state.freight_routes.push(FreightRoute {
+        id: route_id,
+        owner: owner.clone(),
+        from_station: from_station.clone(),
+        to_station: to_station.clone(),
+        item_spec: item_spec.clone(),
+        interval_ticks,
+        next_dispatch_tick: current_tick,
+        active_trip: None,
+    });
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::FreightRouteCreated {
+            route_id,
+            owner: owner.clone(),
+            from_station: from_station.clone(),
+            to_station: to_station.clone(),
+            item_spec: item_spec.clone(),
+            interval_ticks,
+        },
+    ));
+    Ok(())
+}
+
+/// Drop runs whose ship left the route, then dispatch due routes in
+/// creation order.
+pub(crate) fn tick_freight_routes(
+    state: &mut GameState,
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+) {
+    if state.freight_routes.is_empty() {
+        return;
+    }
+    let current_tick = state.meta.tick;
+    let mut routes = std::mem::take(&mut state.freight_routes);
+    for route in &mut routes {
+        if let Some(trip) = &route.active_trip {
+            let still_running = state
+                .ships
+                .get(&trip.ship_id)
+                .and_then(|ship| ship.task.as_ref())
+                .is_some_and(|task| runs_route(&task.kind, route));
+            if !still_running {
+                route.active_trip = None;
+            }
+        }
+        if route.active_trip.is_none() && current_tick >= route.next_dispatch_tick {
+            dispatch(state, content, route, current_tick, events);
+        }
+    }
+    state.freight_routes = routes;
+}
+
+/// Send the owner's idle ship nearest the origin. Waits a tick when no ship
+/// is free or the origin is short, and a full interval when the ship
+/// refuses the run (not enough propellant).
+fn dispatch(
+    state: &mut GameState,
+    content: &GameContent,
+    route: &mut FreightRoute,
+    current_tick: u64,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let Some(origin) = state.stations.get(&route.from_station) else {
+        return;
+    };
+    if !state.stations.contains_key(&route.to_station)
+        || !crate::trade::has_enough_for_export(&origin.core.inventory, &route.item_spec)
+    {
+        return;
+    }
+    let origin_abs = crate::compute_entity_absolute(&origin.position, &state.body_cache);
+    let Some(ship_id) = state
+        .ships
+        .values()
+        .filter(|ship| {
+            ship.owner == route.owner
+                && ship
+                    .task
+                    .as_ref()
+                    .is_none_or(|task| matches!(task.kind, TaskKind::Idle))
+        })
+        .min_by_key(|ship| {
+            crate::compute_entity_absolute(&ship.position, &state.body_cache).distance(origin_abs)
+        })
+        .map(|ship| ship.id.clone())
+    else {
+        return;
+    };
+
+    if crate::commands::handle_transfer_items(
+        state,
+        content,
+        &ship_id,
+        &route.from_station,
+        &route.to_station,
+        std::slice::from_ref(&route.item_spec),
+        current_tick,
+        events,
+    )
+    .is_err()
+    {
+        route.next_dispatch_tick = current_tick + route.interval_ticks;
+        return;
+    }
+    let order_id = crate::FreightOrderId(state.counters.next_freight_order_id);
+    state.counters.next_freight_order_id += 1;
+    route.active_trip = Some(FreightTrip {
+        ship_id,
+        order_id,
+        dispatched_tick: current_tick,
+    });
+    route.next_dispatch_tick = current_tick + route.interval_ticks;
+}
+
+/// Whether `kind` is (a step of) the route's pickup-and-deliver chain.
+fn runs_route(kind: &TaskKind, route: &FreightRoute) -> bool {
+    match kind {
+        TaskKind::Transit { then, .. } => runs_route(then, route),
+        TaskKind::Pickup {
+            from_station, then, ..
+        } => *from_station == route.from_station && runs_route(then, route),
+        TaskKind::Deposit { station, .. } => *station == route.to_station,
+        _ => false,
+    }
+}
+
+/// Close the run `ship_id` was making to `station_id`, if any. Called
+/// when a deposit lands.
+pub(crate) fn record_delivery(
+    state: &mut GameState,
+    ship_id: &ShipId,
+    station_id: &StationId,
+    current_tick: u64,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let Some(route) = state.freight_routes.iter_mut().find(|route| {
+        route.to_station == *station_id
+            && route
+                .active_trip
+                .as_ref()
+                .is_some_and(|trip| trip.ship_id == *ship_id)
+    }) else {
+        return;
+    };
+    let Some(trip) = route.active_trip.take() else {
+        return;
+    };
+    let event = Event::FreightDelivered {
+        order_id: trip.order_id,
+        from_station: route.from_station.clone(),
+        to_station: route.to_station.clone(),
+        item_spec: route.item_spec.clone(),
+        route_id: Some(route.id),
+        ship_id: Some(trip.ship_id),
+    };
+    events.push(crate::emit(&mut state.counters, current_tick, event));
+}
@@ -0,0 +1,206 @@
+//! Ship hold partitions.
+//!
+//! A hull can reserve part of its hold for one kind of cargo
+//! (`HullDef::hold_partitions`), so a locker full of repair kits cannot
+//! crowd out ore. Every path that puts cargo into a ship asks [`HoldSpace`]
+//! how much of the cargo's partition is still free.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::tasks::item_volume_m3;
+use crate::{GameContent, HoldPartition, ShipState};
+
+/// Free volume left in a hold, per partition.
+#[derive(Debug, Clone)]
+pub struct HoldSpace {
+    /// Free volume of the whole hold.
+    total: f32,
+    /// Free volume shared by cargo of unlisted partitions.
+    shared: f32,
+    /// Free volume of each partition the hull reserves.
+    reserved: BTreeMap<HoldPartition, f32>,
+}
+
+impl HoldSpace {
+    /// A hold without partitions, such as a station's storage.
+    pub fn undivided(free_m3: f32) -> Self {
+        Self {
+            total: free_m3,
+            shared: free_m3,
+            reserved: BTreeMap::new(),
+        }
+    }
+
+    pub fn of_ship(ship: &ShipState, content: &GameContent) -> Self {
+        let usage = hold_usage(ship, content);
+        let mut space = Self::undivided(ship.cargo_capacity_m3 - usage.used_m3);
+        for partition in usage.partitions {
+            let free = partition.capacity_m3 - partition.used_m3;
+            match partition.partition {
+                Some(reserved) => {
+                    space.reserved.insert(reserved, free);
+                }
+                None => space.shared = free,
+            }
+        }
+        space
+    }
+
+    /// Volume that cargo of `partition` can still take up.
+    pub fn free_for(&self, partition: HoldPartition) -> f32 {
+        self.reserved
+            .get(&partition)
+            .copied()
+            .unwrap_or(self.shared)
+            .min(self.total)
+            .max(0.0)
+    }
+
+    /// Record `volume_m3` of `partition` cargo as loaded.
+    pub fn fill(&mut self, partition: HoldPartition, volume_m3: f32) {
+        self.total -= volume_m3;
+        match self.reserved.get_mut(&partition) {
+            Some(free) => *free -= volume_m3,
+            None => self.shared -= volume_m3,
+        }
+    }
+}
+
+/// Occupancy of one section of a ship hold.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartitionUsage {
+    /// `None` is the shared space left over after the reserved partitions.
+    pub partition: Option<HoldPartition>,
+    pub capacity_m3: f32,
+    pub used_m3: f32,
+}
+
+/// Occupancy of a ship hold as a whole and by section.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HoldUsage {
+    pub capacity_m3: f32,
+    pub used_m3: f32,
+    /// Reserved partitions in `HoldPartition` order, then the shared space.
+    /// Empty for an undivided hold.
+    pub partitions: Vec<PartitionUsage>,
+}
+
+pub fn hold_usage(ship: &ShipState, content: &GameContent) -> HoldUsage {
+    let reserved = content
+        .hulls
+        .get(&ship.hull_id)
+        .map(|hull| &hull.hold_partitions)
+        .filter(|partitions| !partitions.is_empty());
+    let mut usage = HoldUsage {
+        capacity_m3: ship.cargo_capacity_m3,
+        used_m3: 0.0,
+        partitions: Vec::new(),
+    };
+    let Some(reserved) = reserved else {
+        usage.used_m3 = crate::inventory_volume_m3(&ship.inventory, content);
+        return usage;
+    };
+    let mut shared = PartitionUsage {
+        partition: None,
+        capacity_m3: (ship.cargo_capacity_m3 - reserved.values().sum::<f32>()).max(0.0),
+        used_m3: 0.0,
+    };
+    usage.partitions = reserved
+        .iter()
+        .map(|(partition, capacity_m3)| PartitionUsage {
+            partition: Some(*partition),
+            capacity_m3: *capacity_m3,
+            used_m3: 0.0,
+        })
+        .collect();
+    for item in &ship.inventory {
+        let volume = item_volume_m3(item, content);
+        usage.used_m3 += volume;
+        let partition = HoldPartition::of_item(item);
+        let section = usage
+            .partitions
+            .iter_mut()
+            .find(|section| section.partition == Some(partition))
+            .unwrap_or(&mut shared);
+        section.used_m3 += volume;
+    }
+    usage.partitions.push(shared);
+    usage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{base_content, base_state, test_ship_id};
+    use crate::{ComponentId, HullDef, HullId, InventoryItem};
+
+    fn content_with_hull(partitions: &[(HoldPartition, f32)]) -> GameContent {
+        let mut content = base_content();
+        let id = HullId("hull_general_purpose".to_string());
+        content.hulls.insert(
+            id.clone(),
+            HullDef {
+                id,
+                name: "General Purpose".to_string(),
+                mass_kg: 5000.0,
+                cargo_capacity_m3: 50.0,
+                base_speed_ticks_per_au: 2133,
+                base_propellant_capacity_kg: 10_000.0,
+                slots: vec![],
+                bonuses: vec![],
+                required_tech: None,
+                tags: vec![],
+                hold_partitions: partitions.iter().copied().collect(),
+                replacement_value: 0.0,
+            },
+        );
+        content
+    }
+
+    fn ship_with_kits(content: &GameContent, count: u32) -> ShipState {
+        let mut ship = base_state(content).ships[&test_ship_id()].clone();
+        ship.inventory = vec![InventoryItem::Component {
+            component_id: ComponentId("repair_kit".to_string()),
+            count,
+            quality: 1.0,
+        }];
+        ship
+    }
+
+    #[test]
+    fn reserved_locker_leaves_the_rest_of_the_hold_to_ore() {
+        let content = content_with_hull(&[(HoldPartition::ComponentLocker, 3.0)]);
+        let ship = ship_with_kits(&content, 2);
+
+        let space = HoldSpace::of_ship(&ship, &content);
+        let usage = hold_usage(&ship, &content);
+
+        assert!((space.free_for(HoldPartition::ComponentLocker) - 1.0).abs() < 1e-4);
+        let ore_free = ship.cargo_capacity_m3 - 3.0;
+        assert!((space.free_for(HoldPartition::OreBay) - ore_free).abs() < 1e-4);
+        assert!((usage.used_m3 - 2.0).abs() < 1e-4);
+        assert_eq!(usage.partitions.len(), 2);
+        assert_eq!(
+            usage.partitions[0].partition,
+            Some(HoldPartition::ComponentLocker)
+        );
+        assert!((usage.partitions[0].used_m3 - 2.0).abs() < 1e-4);
+        assert_eq!(usage.partitions[1].partition, None);
+        assert!(usage.partitions[1].used_m3.abs() < 1e-6);
+    }
+
+    #[test]
+    fn undivided_hold_is_shared_by_all_cargo() {
+        let content = content_with_hull(&[]);
+        let ship = ship_with_kits(&content, 2);
+
+        let space = HoldSpace::of_ship(&ship, &content);
+
+        assert!(hold_usage(&ship, &content).partitions.is_empty());
+        let free = ship.cargo_capacity_m3 - 2.0;
+        assert!((space.free_for(HoldPartition::ComponentLocker) - free).abs() < 1e-4);
+        assert!((space.free_for(HoldPartition::OreBay) - free).abs() < 1e-4);
+    }
+}
@@ -1,5 +1,8 @@
+use std::collections::BTreeMap;
 use std::time::Duration;
 
+use crate::{Event, EventEnvelope, GameContent, GameState, StationId, TickHotspot};
+
 /// Per-step timing data for a single tick.
 ///
 /// 17 duration fields: 9 top-level tick steps + 8 station sub-steps.
@@ -57,6 +60,94 @@ impl TickTimings {
     }
 }
 
+impl TickTimings {
+    /// Station sub-step durations (aggregated so far this tick), in
+    /// `iter_fields` order.
+    pub(crate) fn station_phases(&self) -> [(&'static str, Duration); 8] {
+        [
+            ("power_budget", self.power_budget),
+            ("processors", self.processors),
+            ("assemblers", self.assemblers),
+            ("sensors", self.sensors),
+            ("labs", self.labs),
+            ("maintenance", self.maintenance),
+            ("thermal", self.thermal),
+            ("boiloff", self.boiloff),
+        ]
+    }
+}
+
+/// Per-station, per-phase time accumulated since the last `TickHotspots`
+/// report. Only filled while `constants.hotspot_report_interval_ticks > 0`
+/// and the caller passes `TickTimings`.
+#[derive(Debug, Clone, Default)]
+pub struct HotspotWindow {
+    pub ticks: u64,
+    pub totals: BTreeMap<(StationId, &'static str), Duration>,
+}
+
+impl HotspotWindow {
+    /// Add the time one station spent in each phase, given the station
+    /// sub-step timings before and after its turn.
+    pub(crate) fn record_station(
+        &mut self,
+        station_id: &StationId,
+        before: &[(&'static str, Duration); 8],
+        after: &[(&'static str, Duration); 8],
+    ) {
+        for ((phase, start), (_, end)) in before.iter().zip(after) {
+            let spent = end.saturating_sub(*start);
+            if spent > Duration::ZERO {
+                *self.totals.entry((station_id.clone(), phase)).or_default() += spent;
+            }
+        }
+    }
+}
+
+/// Close the current hotspot window every `hotspot_report_interval_ticks`
+/// and emit `TickHotspots` with the costliest station phases. Wall-clock
+/// based, so only runs when reporting is enabled in content.
+pub(crate) fn report_hotspots(
+    state: &mut GameState,
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let interval = content.constants.hotspot_report_interval_ticks;
+    if interval == 0 {
+        return;
+    }
+    state.hotspots.ticks += 1;
+    if !state.meta.tick.is_multiple_of(interval) {
+        return;
+    }
+    let window = std::mem::take(&mut state.hotspots);
+    let mut ranked: Vec<((StationId, &'static str), Duration)> =
+        window.totals.into_iter().collect();
+    // Costliest first; key order breaks ties so the report is stable.
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked.truncate(content.constants.hotspot_report_top_n);
+    if ranked.is_empty() {
+        return;
+    }
+    let hotspots = ranked
+        .into_iter()
+        .map(|((station_id, phase), spent)| TickHotspot {
+            station_id,
+            phase: phase.to_string(),
+            total_us: u64::try_from(spent.as_micros()).unwrap_or(u64::MAX),
+        })
+        .collect();
+    let current_tick = state.meta.tick;
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::TickHotspots {
+            window_ticks: window.ticks,
+            hotspots,
+        },
+    ));
+}
+
 /// Summary statistics for a single tick step.
 #[derive(Debug, Clone)]
 pub struct StepStats {
@@ -210,6 +301,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hotspot_window_records_per_station_phase_deltas() {
+        let station_id = StationId("station_a".to_string());
+        let before = TickTimings::default();
+        let mut after = before.clone();
+        after.processors = Duration::from_micros(300);
+        after.thermal = Duration::from_micros(20);
+
+        let mut window = HotspotWindow::default();
+        window.record_station(
+            &station_id,
+            &before.station_phases(),
+            &after.station_phases(),
+        );
+        window.record_station(
+            &station_id,
+            &before.station_phases(),
+            &after.station_phases(),
+        );
+
+        assert_eq!(window.totals.len(), 2, "untouched phases are not recorded");
+        assert_eq!(
+            window.totals[&(station_id, "processors")],
+            Duration::from_micros(600)
+        );
+    }
+
+    #[test]
+    fn report_hotspots_emits_top_entries_on_interval() {
+        let mut content = crate::test_fixtures::base_content();
+        content.constants.hotspot_report_interval_ticks = 2;
+        content.constants.hotspot_report_top_n = 1;
+        let mut state = crate::test_fixtures::base_state(&content);
+        let slow = StationId("station_slow".to_string());
+        let fast = StationId("station_fast".to_string());
+        state
+            .hotspots
+            .totals
+            .insert((fast, "labs"), Duration::from_micros(10));
+        state
+            .hotspots
+            .totals
+            .insert((slow.clone(), "processors"), Duration::from_micros(900));
+
+        let mut events = Vec::new();
+        state.meta.tick = 1;
+        report_hotspots(&mut state, &content, &mut events);
+        assert!(events.is_empty(), "no report off-interval");
+
+        state.meta.tick = 2;
+        report_hotspots(&mut state, &content, &mut events);
+        let Some(Event::TickHotspots {
+            window_ticks,
+            hotspots,
+        }) = events.first().map(|e| &e.event)
+        else {
+            panic!("expected TickHotspots, got {events:?}");
+        };
+        assert_eq!(*window_ticks, 2);
+        assert_eq!(
+            hotspots,
+            &vec![TickHotspot {
+                station_id: slow,
+                phase: "processors".to_string(),
+                total_us: 900,
+            }]
+        );
+        assert!(
+            state.hotspots.totals.is_empty(),
+            "window resets after report"
+        );
+    }
+
     #[test]
     fn compute_step_stats_empty_input() {
         let stats = compute_step_stats(&[]);
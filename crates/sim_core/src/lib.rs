@@ -1,51 +1,96 @@
 //! `sim_core` — deterministic simulation tick.
 //!
 //! No IO, no network. All randomness via the passed-in Rng.
+//!
+//! # Stable API
+//!
+//! [`prelude`] re-exports the facade external drivers should build on:
+//! [`tick`], [`Command`]/[`CommandEnvelope`], [`Event`]/[`EventEnvelope`],
+//! and [`GameState`] with its read accessors (`current_tick`, `ship`,
+//! `station`, `asteroid`, `is_unlocked`). Runtime caches and low-level
+//! helpers live in [`internals`] behind the `internals` feature and may
+//! change shape between versions.
 #![cfg_attr(not(test), deny(clippy::unwrap_used))]
 
+pub mod asteroid_ranking;
+mod belts;
+mod cargo_transfer;
+pub mod claims;
 pub(crate) mod commands;
 mod composition;
+mod contracts;
 mod engine;
+pub mod fixed;
+mod freight_routes;
+mod hold;
 mod id;
 pub mod instrumentation;
+mod loan;
+pub mod market;
 pub mod metrics;
+pub mod migrations;
 pub mod milestone;
 pub mod modifiers;
+mod pollution;
+pub mod prelude;
+mod projects;
 pub mod propulsion;
+pub mod query;
+pub mod range;
+pub mod replay;
 mod research;
+pub mod rng;
 pub(crate) mod satellite;
 pub mod scoring;
+mod seed;
+mod ship_loss;
 pub mod sim_events;
 pub mod spatial;
+mod spoilage;
+mod stable_hash;
 mod station;
+mod station_build;
+mod storms;
+pub mod subsystems;
+mod survey;
 pub(crate) mod tasks;
 pub mod thermal;
+mod threats;
 pub mod trade;
 mod types;
 pub mod wear;
 
-pub use commands::{recompute_ship_stats, recompute_station_stats};
+pub(crate) use commands::recompute_station_stats;
 pub use engine::{tick, trade_unlock_tick};
-pub use id::generate_uuid;
+pub use hold::{hold_usage, HoldSpace, HoldUsage, PartitionUsage};
+pub(crate) use id::generate_uuid;
+pub(crate) use instrumentation::HotspotWindow;
 pub use instrumentation::{compute_step_stats, StepStats, TickTimings};
 pub use metrics::{
     append_metrics_row, compute_metrics, content_behavior_types, content_element_ids,
     write_metrics_csv, write_metrics_header, MetricType, MetricValue, MetricsFileWriter,
     MetricsSnapshot, ModuleStatusMetrics, OreElementStats, METRICS_VERSION,
 };
+pub use replay::{state_hash, ReplayMismatch, RunRecording};
+pub use research::grant_tech;
+pub use rng::RngCheckpoint;
 pub use satellite::{zone_comm_tier, zone_nav_bonus};
+pub use seed::derive_seed;
 pub use spatial::{
-    build_body_cache, compute_entity_absolute, integer_sqrt, is_co_located, pick_template_biased,
-    pick_zone_weighted, polar_to_cart, random_angle_in_span, random_position_in_zone,
-    random_radius_in_band, travel_ticks, AbsolutePos, AngleMilliDeg, BodyCache, EntityCache,
-    Position, RadiusAuMicro, ResourceClass, FULL_CIRCLE, METERS_PER_AU, METERS_PER_MICRO_AU,
+    build_body_cache, compute_entity_absolute, is_co_located, pick_template_biased,
+    pick_zone_weighted, random_angle_in_span, random_position_in_zone, random_radius_in_band,
+    travel_ticks, AbsolutePos, AngleMilliDeg, Position, RadiusAuMicro, ResourceClass, FULL_CIRCLE,
+    METERS_PER_AU, METERS_PER_MICRO_AU, MICRO_AU_PER_AU,
 };
-pub use tasks::{inventory_mass_kg, inventory_volume_m3, mine_duration};
+pub use station::why_not_running;
+pub use subsystems::{SimMode, Subsystem, Subsystems};
+pub use tasks::{inventory_mass_kg, inventory_volume_m3, mine_duration, mining_rate_kg_per_tick};
 // -- types: ID newtypes --
 pub use types::{
-    AsteroidId, BodyId, CommandId, ComponentId, CrewRole, EventId, FacilityId, FrameId,
-    GroundFacilityId, HullId, LeaderId, LotId, ModuleDefId, ModuleInstanceId, ModuleItemId, NodeId,
-    PrincipalId, RecipeId, SatelliteId, ShipId, SiteId, SlotType, StationId, TechId,
+    AsteroidId, BodyId, CommandId, ComponentId, ContractId, CrewRole, EventId, FacilityId, FrameId,
+    FreightOrderId, FreightRouteId, GroundFacilityId, HullId, ImportOrderId, LeaderId, LotId,
+    ModuleDefId, ModuleInstanceId, ModuleItemId, NodeId, PrincipalId, ProjectId, RecipeId,
+    SatelliteId, ShipId, SiteId, SlotType, StationId, TechId,
 };
 // -- types: type aliases & constants --
 pub use types::{
@@ -60,16 +105,19 @@ pub use types::{
 };
 // -- types: game state --
 pub use types::{
-    AsteroidKnowledge, AsteroidState, Counters, FacilityCore, GameState, GroundFacilityState,
-    LaunchPayload, LaunchTransitState, MetaState, ModuleTypeIndex, PowerBudgetCache, PowerState,
-    ResearchState, SatelliteState, ScanSite, StationState, TaskState, ThermalLink,
+    AsteroidKnowledge, AsteroidLayer, AsteroidState, BeltState, BodyClaim, ClaimBasis,
+    CompositionLayers, ConstructionProject, ConsumedMaterial, Contract, ContractKind, Counters,
+    FacilityCore, FreightOrder, FreightRoute, FreightTrip, GameState, GroundFacilityState,
+    ImportOrder, LaunchPayload, LaunchTransitState, LoanState, MarketItem, MarketState, MetaState,
+    PowerPolicy, PowerState, ProductionWindow, ResearchState, SatelliteState, ScanSite, SolarStorm,
+    StationBuild, StationExpansions, StationState, TaskState, ThermalLink,
 };
 // -- types: ship state --
 pub use types::{FittedModule, ShipState, TaskKind};
 // -- types: module state --
 pub use types::{
-    compute_crew_factor, compute_module_efficiency, is_crew_satisfied, AssemblerState,
-    BatteryState, LabState, LaunchPadState, MaintenanceState, ModuleKindState, ModuleState,
+    AssemblerJob, AssemblerQueueEntry, AssemblerSlotState, AssemblerState, BatteryState,
+    DroneBayState, LabState, LaunchPadState, MaintenanceState, ModuleKindState, ModuleState,
     ProcessorState, RadiatorState, SensorArrayState, SolarArrayState, ThermalContainerState,
     WearState,
 };
@@ -77,18 +125,21 @@ pub use types::{
 pub use types::{MaterialThermalProps, ThermalState};
 // -- types: content definitions --
 pub use types::{
-    AlertRuleDef, AlertRuleType, AsteroidTemplateDef, AutopilotConfig, BodyType, BoiloffCurveDef,
-    ComponentDef, CrewRoleDef, DeepScanTargetConfig, ElementDef, ExportComponentConfig,
-    ExportElementConfig, FrameDef, GameContent, HullDef, InitialComponent, InitialMaterial,
-    InitialStationDef, NodeDef, OrbitalBodyDef, RocketDef, SatelliteDef, ShipSetupDef, SlotDef,
-    SolarSystemDef, StationSetupDef, TechDef, TechEffect, ThermalDef, ZoneDef,
+    AdjacencyBonusDef, AdjacencyRuleDef, AlertRuleDef, AlertRuleType, AsteroidTemplateDef,
+    AutopilotConfig, AutopilotWeights, BeltDef, BodyType, BoiloffCurveDef, ComponentDef,
+    CrewRoleDef, DeepScanTargetConfig, ElementDef, ExportComponentConfig, ExportElementConfig,
+    FrameDef, GameContent, HullDef, InitialComponent, InitialMaterial, InitialStationDef, NodeDef,
+    OrbitalBodyDef, ProjectDef, ProjectOutcome, RocketDef, SatelliteDef, ShipClassDef,
+    ShipSetupDef, SlotDef, SolarSystemDef, StationSetupDef, TechDef, TechEffect, ThermalDef,
+    ZoneDef,
 };
 // -- types: module & recipe definitions --
 pub use types::{
-    AssemblerDef, BatteryDef, InputAmount, InputFilter, LabDef, LaunchPadDef, MaintenanceDef,
-    ModuleBehaviorDef, ModuleDef, ModulePort, OutputSpec, PortDirection, PortFilter, ProcessorDef,
-    QualityFormula, RadiatorDef, RecipeDef, RecipeInput, RecipeThermalReq, SensorArrayDef,
-    SolarArrayDef, ThermalContainerDef, YieldFormula,
+    AssemblerDef, BatteryDef, DroneBayDef, HabitatDef, InputAmount, InputFilter, LabDef,
+    LaunchPadDef, MaintenanceDef, ModuleBehaviorDef, ModuleDef, ModulePort, ModuleUpgradeDef,
+    OutputSpec, PortDirection, PortFilter, ProcessorDef, QualityFormula, RadiatorDef, RecipeDef,
+    RecipeInput, RecipeThermalReq, SensorArrayDef, ShipOwnerRule, SolarArrayDef,
+    ThermalContainerDef, YieldFormula,
 };
 // -- types: progression --
 pub use types::{
@@ -96,9 +147,15 @@ pub use types::{
     TradeTier,
 };
 // -- types: commands & events --
-pub use types::{Command, CommandEnvelope, Event, EventEnvelope};
+pub use types::{
+    Command, CommandEnvelope, Event, EventEnvelope, EventLevel, IdleReason, ShipLossCause,
+    TickHotspot, COMMAND_SCHEMA_VERSION,
+};
 // -- types: inventory & trade --
-pub use types::{InventoryItem, PricingEntry, PricingTable, TradeItemSpec};
+pub use types::{
+    CargoDirection, CargoHolder, CargoSpec, HoldPartition, InventoryItem, PricingEntry,
+    PricingTable, TradeItemSpec,
+};
 // -- types: constants & functions --
 pub use milestone::KNOWN_COUNTERS;
 pub use scoring::{
@@ -108,8 +165,32 @@ pub use scoring::{
 };
 pub use types::{boiloff_rate_per_tick, derive_module_tick_values, Constants};
 // -- types: strategy --
+pub use ship_loss::ship_value;
+pub use survey::sensor_quality;
 pub use types::{ConcernPriorities, PriorityWeights, StrategyConfig, StrategyMode};
-pub use wear::wear_efficiency;
+pub use wear::{repair_bay, wear_efficiency};
+// -- runtime caches & helpers: crate-internal, see `internals` --
+pub(crate) use spatial::BodyCache;
+#[cfg(any(test, feature = "test-support"))]
+pub(crate) use types::ModuleTypeIndex;
+pub(crate) use types::{compute_module_efficiency, is_crew_satisfied, PowerBudgetCache};
+
+/// Runtime caches and low-level helpers that are not part of the stable API.
+///
+/// World builders and test harnesses need these to assemble a `GameState`
+/// by hand; everything else should go through [`prelude`] and the
+/// `GameState` accessors. Shapes here may change without notice.
+#[cfg(feature = "internals")]
+pub mod internals {
+    pub use crate::commands::{recompute_ship_stats, recompute_station_stats};
+    pub use crate::id::generate_uuid;
+    pub use crate::instrumentation::HotspotWindow;
+    pub use crate::spatial::{integer_sqrt, polar_to_cart, BodyCache, EntityCache};
+    pub use crate::types::{
+        compute_crew_factor, compute_module_efficiency, is_crew_satisfied, ModuleTypeIndex,
+        PowerBudgetCache,
+    };
+}
 
 pub(crate) fn emit(counters: &mut Counters, tick: u64, event: Event) -> EventEnvelope {
     let id = EventId(counters.next_event_id);
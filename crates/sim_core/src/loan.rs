@@ -0,0 +1,57 @@
+//! Starting loan.
+//!
+//! World generation lends `starting_loan` credits, which become the starting
+//! balance and `GameState.loan.outstanding`. Every tick the outstanding sum
+//! grows by `loan_interest_rate_per_tick`, and every
+//! `loan_repayment_interval_ticks` a repayment of `loan_repayment_amount`
+//! (capped at what is owed) is taken from the balance. A repayment the
+//! balance cannot cover is missed instead; after `loan_default_after_missed`
+//! misses in a row the loan defaults and `StationBankrupt` is emitted. A
+//! repayment made in time clears the run of misses.
+
+use crate::{Event, EventEnvelope, GameContent, GameState};
+
+pub(crate) fn tick_loan(
+    state: &mut GameState,
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let constants = &content.constants;
+    let current_tick = state.meta.tick;
+    let loan = &mut state.loan;
+    if loan.defaulted || loan.outstanding <= 0.0 {
+        return;
+    }
+    let interest = loan.outstanding * constants.loan_interest_rate_per_tick;
+    crate::fixed::add_credits(&mut loan.outstanding, interest);
+
+    let interval = constants.loan_repayment_interval_ticks;
+    if interval == 0 || current_tick == 0 || !current_tick.is_multiple_of(interval) {
+        return;
+    }
+    let due = constants.loan_repayment_amount.min(loan.outstanding);
+    if due <= 0.0 {
+        return;
+    }
+    if state.balance >= due {
+        crate::fixed::add_credits(&mut state.balance, -due);
+        crate::fixed::add_credits(&mut loan.outstanding, -due);
+        loan.missed_payments = 0;
+        return;
+    }
+    loan.missed_payments += 1;
+    if loan.missed_payments < constants.loan_default_after_missed {
+        return;
+    }
+    loan.defaulted = true;
+    // Balance is global, so the first station stands for the company.
+    if let Some(station_id) = state.stations.keys().next() {
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            Event::StationBankrupt {
+                station_id: station_id.clone(),
+            },
+        ));
+    }
+}
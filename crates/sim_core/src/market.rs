@@ -0,0 +1,113 @@
+//! Drifting trade prices.
+//!
+//! `PricingTable` holds base prices; `GameState.market` holds how far each
+//! item's price has moved from its base. Every import adds its quantity to
+//! the item's net trade volume and every export subtracts it. Every
+//! `market_update_interval_ticks` each tradeable item's log price is set to
+//! `elasticity × net_volume` plus a seeded random walk of steps up to
+//! `market_volatility`, and the resulting multiplier is clamped to
+//! `market_price_min_mult..=market_price_max_mult`. Items whose price moved
+//! emit `PriceChanged`. With the interval at 0 (the default) nothing is
+//! recorded and prices stay at base.
+//!
+//! The noise comes from a stream derived from the run seed and the update
+//! tick, not from the shared run RNG, so turning the market on or off does
+//! not shift any other subsystem's draws.
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{Event, EventEnvelope, GameContent, GameState, MarketState, TradeItemSpec};
+
+/// Add a completed trade to the item's net volume. `direction` is 1 for
+/// imports and -1 for exports.
+pub(crate) fn record_trade(
+    market: &mut MarketState,
+    content: &GameContent,
+    item_spec: &TradeItemSpec,
+    direction: f64,
+) {
+    if content.constants.market_update_interval_ticks == 0 {
+        return;
+    }
+    let item = market
+        .items
+        .entry(item_spec.pricing_key().to_string())
+        .or_default();
+    item.net_volume += direction * item_spec.quantity();
+}
+
+/// Current unit price of a pricing key: base price times market drift.
+/// `None` for unknown keys.
+pub fn current_price(
+    content: &GameContent,
+    market: &MarketState,
+    pricing_key: &str,
+) -> Option<f64> {
+    let entry = content.pricing.items.get(pricing_key)?;
+    Some(entry.base_price_per_unit * market.multiplier(pricing_key))
+}
+
+/// Reprice every tradeable item on update ticks.
+pub(crate) fn tick_market(
+    state: &mut GameState,
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let constants = &content.constants;
+    let interval = constants.market_update_interval_ticks;
+    let current_tick = state.meta.tick;
+    if interval == 0 || current_tick == 0 || !current_tick.is_multiple_of(interval) {
+        return;
+    }
+    // Sorted so the noise draws do not depend on hash order.
+    let mut keys: Vec<&String> = content
+        .pricing
+        .items
+        .iter()
+        .filter(|(_, entry)| entry.importable || entry.exportable)
+        .map(|(key, _)| key)
+        .collect();
+    keys.sort();
+    let mut rng = ChaCha8Rng::seed_from_u64(crate::derive_seed(
+        crate::derive_seed(state.meta.seed, "market"),
+        &current_tick.to_string(),
+    ));
+
+    for key in keys {
+        let entry = &content.pricing.items[key];
+        if entry.elasticity == 0.0 && constants.market_volatility <= 0.0 {
+            continue;
+        }
+        let item = state.market.items.entry(key.clone()).or_default();
+        if constants.market_volatility > 0.0 {
+            // Kept within the price bounds so a long walk can turn back.
+            item.noise = (item.noise
+                + rng.gen_range(-constants.market_volatility..=constants.market_volatility))
+            .clamp(
+                constants.market_price_min_mult.ln(),
+                constants.market_price_max_mult.ln(),
+            );
+        }
+        let multiplier = (entry.elasticity * item.net_volume + item.noise)
+            .exp()
+            .clamp(
+                constants.market_price_min_mult,
+                constants.market_price_max_mult,
+            );
+        if (multiplier - item.multiplier).abs() < 1e-9 {
+            continue;
+        }
+        let old_price = entry.base_price_per_unit * item.multiplier;
+        item.multiplier = multiplier;
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            Event::PriceChanged {
+                item_id: key.clone(),
+                old_price,
+                new_price: entry.base_price_per_unit * multiplier,
+            },
+        ));
+    }
+}
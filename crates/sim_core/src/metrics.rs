@@ -15,7 +15,8 @@ use std::io::Write;
 
 /// Current schema version — bump when fields are added/removed/reordered.
 /// v11: Replace per-module-type fields with dynamic `per_module_metrics` `BTreeMap`.
-pub const METRICS_VERSION: u32 = 14;
+/// v15: Add the starting loan's `loan_*` fields.
+pub const METRICS_VERSION: u32 = 15;
 
 /// A typed metric value extracted from a [`MetricsSnapshot`] field.
 #[derive(Clone, Copy, Debug)]
@@ -150,11 +151,19 @@ pub struct MetricsSnapshot {
     pub thruster_count: u32,
     pub export_revenue_total: f64,
     pub export_count: u32,
+    /// Starting-loan principal and interest not yet repaid.
+    pub loan_outstanding: f64,
+    /// Loan repayments missed in a row.
+    pub loan_missed_payments: u32,
+    /// 1 once the loan has defaulted, else 0.
+    pub loan_defaulted: u32,
 
     // Power
     pub power_generated_kw: f32,
     pub power_consumed_kw: f32,
     pub power_deficit_kw: f32,
+    /// Run draw shed by station brownouts (part of `power_deficit_kw`).
+    pub power_brownout_kw: f32,
     pub battery_charge_pct: f32,
 
     // (Propellant totals are in per_element_material_kg: H2O, LH2, LOX)
@@ -280,6 +289,9 @@ impl MetricsSnapshot {
             ("thruster_count", U32(self.thruster_count)),
             ("export_revenue_total", F64(self.export_revenue_total)),
             ("export_count", U32(self.export_count)),
+            ("loan_outstanding", F64(self.loan_outstanding)),
+            ("loan_missed_payments", U32(self.loan_missed_payments)),
+            ("loan_defaulted", U32(self.loan_defaulted)),
         ]
     }
 
@@ -289,6 +301,7 @@ impl MetricsSnapshot {
             ("power_generated_kw", F32(self.power_generated_kw)),
             ("power_consumed_kw", F32(self.power_consumed_kw)),
             ("power_deficit_kw", F32(self.power_deficit_kw)),
+            ("power_brownout_kw", F32(self.power_brownout_kw)),
             ("battery_charge_pct", F32(self.battery_charge_pct)),
         ]
     }
@@ -337,10 +350,14 @@ impl MetricsSnapshot {
             ("thruster_count", U32),
             ("export_revenue_total", F64),
             ("export_count", U32),
+            ("loan_outstanding", F64),
+            ("loan_missed_payments", U32),
+            ("loan_defaulted", U32),
             // Power
             ("power_generated_kw", F32),
             ("power_consumed_kw", F32),
             ("power_deficit_kw", F32),
+            ("power_brownout_kw", F32),
             ("battery_charge_pct", F32),
             // Thermal
             ("station_max_temp_mk", U32),
@@ -513,6 +530,7 @@ struct MetricsAccumulator {
     power_generated_kw: f32,
     power_consumed_kw: f32,
     power_deficit_kw: f32,
+    power_brownout_kw: f32,
     battery_stored_kwh: f32,
     battery_capacity_kwh: f32,
 
@@ -576,6 +594,7 @@ impl MetricsAccumulator {
         self.power_generated_kw += station.core.power.generated_kw;
         self.power_consumed_kw += station.core.power.consumed_kw;
         self.power_deficit_kw += station.core.power.deficit_kw;
+        self.power_brownout_kw += station.core.power.brownout_kw;
         self.battery_stored_kwh += station.core.power.battery_stored_kwh;
 
         for module in &station.core.modules {
@@ -653,9 +672,12 @@ impl MetricsAccumulator {
         if module.kind_state.is_stalled() {
             entry.stalled += 1;
         }
-        // Starved is Processor-specific: ore supply below threshold.
+        // Starved is Processor-specific: ore supply below threshold. Runs
+        // without mining have no ore supply to wait on, so nothing starves.
         if let ModuleKindState::Processor(ps) = &module.kind_state {
-            if total_ore_at_station < ps.threshold_kg {
+            if total_ore_at_station < ps.threshold_kg
+                && content.subsystems.enabled(crate::Subsystem::Mining)
+            {
                 entry.starved += 1;
             }
         }
@@ -719,19 +741,31 @@ impl MetricsAccumulator {
             Some(TaskKind::Mine { .. }) => self.fleet_mining += 1,
             // Station construction counts as transiting-in-place for fleet
             // aggregation. It's infrequent (large tier 3 operation) so a
-            // dedicated fleet counter is not yet justified.
-            Some(TaskKind::Transit { .. } | TaskKind::ConstructStation { .. }) => {
+            // dedicated fleet counter is not yet justified. Escorts fly
+            // with their charge.
+            Some(
+                TaskKind::Transit { .. }
+                | TaskKind::ConstructStation { .. }
+                | TaskKind::Escort { .. },
+            ) => {
                 self.fleet_transiting += 1;
             }
-            Some(TaskKind::Survey { .. } | TaskKind::DeepScan { .. }) => {
+            Some(
+                TaskKind::Survey { .. } | TaskKind::DeepScan { .. } | TaskKind::Resurvey { .. },
+            ) => {
                 self.fleet_surveying += 1;
             }
             // Pickup is the loading end of an inter-station transfer —
             // count it alongside Deposit as loading/unloading activity.
-            Some(TaskKind::Deposit { .. } | TaskKind::Pickup { .. }) => {
+            Some(
+                TaskKind::Deposit { .. } | TaskKind::Pickup { .. } | TaskKind::TransferCargo { .. },
+            ) => {
                 self.fleet_depositing += 1;
             }
-            Some(TaskKind::Refuel { .. }) => self.fleet_refueling += 1,
+            // Docking for repair is servicing at a station, like refueling.
+            Some(TaskKind::Refuel { .. } | TaskKind::DockForRepair { .. }) => {
+                self.fleet_refueling += 1;
+            }
         }
     }
 
@@ -862,9 +896,13 @@ impl MetricsAccumulator {
             thruster_count: self.total_thruster_count,
             export_revenue_total: state.export_revenue_total,
             export_count: state.export_count,
+            loan_outstanding: state.loan.outstanding,
+            loan_missed_payments: state.loan.missed_payments,
+            loan_defaulted: u32::from(state.loan.defaulted),
             power_generated_kw: self.power_generated_kw,
             power_consumed_kw: self.power_consumed_kw,
             power_deficit_kw: self.power_deficit_kw,
+            power_brownout_kw: self.power_brownout_kw,
             battery_charge_pct: avgs.battery_charge_pct,
             station_max_temp_mk: self.thermal_max_temp_mk,
             station_avg_temp_mk: avgs.station_avg_temp_mk,
@@ -1108,8 +1146,10 @@ mod tests {
                 data_pool: AHashMap::default(),
                 evidence: AHashMap::default(),
                 action_counts: AHashMap::default(),
+                queue: Vec::new(),
             },
             balance: 0.0,
+            loan: crate::LoanState::default(),
             export_revenue_total: 0.0,
             export_count: 0,
             counters: Counters {
@@ -1127,7 +1167,20 @@ mod tests {
             transfer_count: 0,
             progression: Default::default(),
             strategy_config: Default::default(),
+            import_orders: Vec::new(),
+            freight_orders: Vec::new(),
+            freight_routes: Vec::new(),
+            market: crate::MarketState::default(),
+            contracts: Vec::new(),
             body_cache: AHashMap::default(),
+            jettisoned_slag_kg: std::collections::BTreeMap::new(),
+            solar_storms: std::collections::BTreeMap::new(),
+            claims: std::collections::BTreeMap::new(),
+            hotspots: crate::HotspotWindow::default(),
+            projects: Vec::new(),
+            station_builds: Vec::new(),
+            belts: std::collections::BTreeMap::new(),
+            pending_commands: Vec::new(),
         }
     }
 
@@ -1148,9 +1201,15 @@ mod tests {
                 module_type_index: crate::ModuleTypeIndex::default(),
                 module_id_index: HashMap::new(),
                 power_budget_cache: crate::PowerBudgetCache::default(),
+                production_windows: std::collections::BTreeMap::new(),
+                life_support_failing: false,
             },
             leaders: Vec::new(),
             frame_id: None,
+            owner: PrincipalId("principal_autopilot".to_string()),
+            expansions: crate::StationExpansions::default(),
+            power_policy: crate::PowerPolicy::default(),
+            history: std::collections::VecDeque::new(),
         }
     }
 
@@ -1171,6 +1230,10 @@ mod tests {
             crew: Default::default(),
             leaders: Vec::new(),
             home_station: None,
+            name: String::new(),
+            class_id: None,
+            role: None,
+            wear: crate::WearState::default(),
         }
     }
 
@@ -1486,7 +1549,11 @@ mod tests {
                 knowledge: AsteroidKnowledge {
                     tag_beliefs: vec![],
                     composition: None,
+                    layers: None,
+                    composition_sigma: None,
+                    deep_scan_count: 0,
                 },
+                layers: None,
             },
         );
 
@@ -1628,7 +1695,11 @@ mod tests {
                 knowledge: AsteroidKnowledge {
                     tag_beliefs: vec![],
                     composition: None,
+                    layers: None,
+                    composition_sigma: None,
+                    deep_scan_count: 0,
                 },
+                layers: None,
             },
         );
         // Depleted asteroid
@@ -1643,7 +1714,11 @@ mod tests {
                 knowledge: AsteroidKnowledge {
                     tag_beliefs: vec![],
                     composition: None,
+                    layers: None,
+                    composition_sigma: None,
+                    deep_scan_count: 0,
                 },
+                layers: None,
             },
         );
 
@@ -1760,9 +1835,14 @@ mod tests {
             generated_kw: 100.0,
             consumed_kw: 80.0,
             deficit_kw: 0.0,
+            idle_kw: 80.0,
+            active_kw: 0.0,
             battery_discharge_kw: 0.0,
             battery_charge_kw: 20.0,
             battery_stored_kwh: 50.0,
+            stalled_kw: 0.0,
+            brownout_kw: 0.0,
+            deferred_kw: 0.0,
         };
         // Add a battery module so we can compute capacity for charge_pct
         station.core.modules.push(ModuleState {
@@ -0,0 +1,278 @@
+//! Upgrades saved `GameState` JSON to [`CURRENT_SCHEMA_VERSION`].
+//!
+//! Serde defaults and aliases absorb additive changes, but a save written
+//! before a rename, a removal or a type change would no longer parse. Each
+//! such change bumps the schema version and registers a migration from the
+//! previous version here. A migration rewrites the raw JSON one version
+//! forward; [`migrate_state_json`] chains them from the save's
+//! `meta.schema_version` up to the current one, so a save from any older
+//! engine loads on this one. Saves from a newer engine are refused.
+//!
+//! Migrations work on `serde_json::Value` because the old shape no longer
+//! has a Rust type. Keep each one small and self-contained: it must keep
+//! working unchanged for as long as saves at its version may exist.
+
+use serde_json::Value;
+
+use crate::CURRENT_SCHEMA_VERSION;
+
+/// One step of the upgrade chain.
+pub struct Migration {
+    /// Schema version this migration reads; it writes `from + 1`.
+    pub from: u32,
+    pub description: &'static str,
+    apply: fn(&mut Value) -> Result<(), String>,
+}
+
+/// Every migration, ordered by `from`. Extend when bumping
+/// `CURRENT_SCHEMA_VERSION`.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from: 1,
+        description: "rename module `manufacturing_priority` to `module_priority`",
+        apply: v1_rename_manufacturing_priority,
+    },
+    Migration {
+        from: 2,
+        description: "record the issuer of in-transit import orders",
+        apply: v2_import_order_issuer,
+    },
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationError {
+    /// The save was written by a newer engine.
+    FutureVersion { found: u32, current: u32 },
+    /// No migration is registered from this version.
+    MissingMigration { from: u32 },
+    /// A migration could not make sense of the save.
+    Failed { from: u32, message: String },
+    /// The save has no readable `meta.schema_version`.
+    Unversioned,
+}
+
+impl std::fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FutureVersion { found, current } => write!(
+                f,
+                "save has schema version {found}, but the engine expects version {current}; \
+                 it was written by a newer engine"
+            ),
+            Self::MissingMigration { from } => {
+                write!(f, "no migration from schema version {from}")
+            }
+            Self::Failed { from, message } => {
+                write!(f, "migrating from schema version {from}: {message}")
+            }
+            Self::Unversioned => write!(f, "save has no meta.schema_version"),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+/// `meta.schema_version` of a raw save, if it has one.
+pub fn schema_version(state: &Value) -> Option<u32> {
+    state["meta"]["schema_version"]
+        .as_u64()
+        .and_then(|version| u32::try_from(version).ok())
+}
+
+/// Bring `state` up to `CURRENT_SCHEMA_VERSION` in place and return the
+/// version it started at. A state already at the current version is left
+/// untouched.
+pub fn migrate_state_json(state: &mut Value) -> Result<u32, MigrationError> {
+    let found = schema_version(state).ok_or(MigrationError::Unversioned)?;
+    if found > CURRENT_SCHEMA_VERSION {
+        return Err(MigrationError::FutureVersion {
+            found,
+            current: CURRENT_SCHEMA_VERSION,
+        });
+    }
+    for version in found..CURRENT_SCHEMA_VERSION {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|migration| migration.from == version)
+            .ok_or(MigrationError::MissingMigration { from: version })?;
+        (migration.apply)(state).map_err(|message| MigrationError::Failed {
+            from: version,
+            message,
+        })?;
+        state["meta"]["schema_version"] = Value::from(version + 1);
+    }
+    Ok(found)
+}
+
+// ---------------------------------------------------------------------------
+// Migrations
+// ---------------------------------------------------------------------------
+
+/// Module instances of every station and ground facility.
+fn facility_modules(state: &mut Value) -> Vec<&mut Value> {
+    let mut modules = Vec::new();
+    let Some(root) = state.as_object_mut() else {
+        return modules;
+    };
+    for (key, facilities) in root.iter_mut() {
+        if key != "stations" && key != "ground_facilities" {
+            continue;
+        }
+        let Some(facilities) = facilities.as_object_mut() else {
+            continue;
+        };
+        for facility in facilities.values_mut() {
+            if let Some(list) = facility["modules"].as_array_mut() {
+                modules.extend(list.iter_mut());
+            }
+        }
+    }
+    modules
+}
+
+/// v1 → v2: the crew system renamed `manufacturing_priority`, which until
+/// now only a serde alias accepted.
+fn v1_rename_manufacturing_priority(state: &mut Value) -> Result<(), String> {
+    for module in facility_modules(state) {
+        let Some(fields) = module.as_object_mut() else {
+            return Err("module instance is not an object".to_string());
+        };
+        if let Some(priority) = fields.remove("manufacturing_priority") {
+            fields.entry("module_priority").or_insert(priority);
+        }
+    }
+    Ok(())
+}
+
+/// v2 → v3: import orders carry the principal who placed them. Older
+/// orders are credited to their station's owner.
+fn v2_import_order_issuer(state: &mut Value) -> Result<(), String> {
+    let owners: std::collections::BTreeMap<String, Value> = state["stations"]
+        .as_object()
+        .map(|stations| {
+            stations
+                .iter()
+                .map(|(id, station)| (id.clone(), station["owner"].clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let Some(orders) = state["import_orders"].as_array_mut() else {
+        return Ok(());
+    };
+    for order in orders {
+        let Some(station_id) = order["station_id"].as_str() else {
+            return Err("import order has no station_id".to_string());
+        };
+        let Some(owner) = owners.get(station_id) else {
+            return Err(format!("import order for unknown station {station_id}"));
+        };
+        order["issued_by"] = owner.clone();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{base_content, base_state, test_station_id};
+    use crate::GameState;
+
+    #[test]
+    fn registry_covers_every_version_below_current() {
+        for version in 1..CURRENT_SCHEMA_VERSION {
+            assert_eq!(
+                MIGRATIONS
+                    .iter()
+                    .filter(|migration| migration.from == version)
+                    .count(),
+                1,
+                "schema version {version} needs exactly one migration"
+            );
+        }
+        assert!(MIGRATIONS
+            .iter()
+            .all(|migration| migration.from < CURRENT_SCHEMA_VERSION));
+    }
+
+    #[test]
+    fn current_state_round_trips_untouched() {
+        let content = base_content();
+        let state = base_state(&content);
+        let mut value = serde_json::to_value(&state).unwrap();
+        let before = value.clone();
+        assert_eq!(
+            migrate_state_json(&mut value).unwrap(),
+            CURRENT_SCHEMA_VERSION
+        );
+        assert_eq!(value, before);
+    }
+
+    #[test]
+    fn v1_save_loads_with_module_priority() {
+        let content = base_content();
+        let state = base_state(&content);
+        let mut value = serde_json::to_value(&state).unwrap();
+        value["meta"]["schema_version"] = Value::from(1);
+        let station = test_station_id().0;
+        value["stations"][&station]["modules"] = serde_json::json!([{
+            "id": "inst_legacy",
+            "def_id": "module_legacy",
+            "enabled": true,
+            "kind_state": "Storage",
+            "wear": {"wear": 0.0},
+            "manufacturing_priority": 7
+        }]);
+
+        assert_eq!(migrate_state_json(&mut value).unwrap(), 1);
+        let loaded: GameState = serde_json::from_value(value).unwrap();
+        assert_eq!(loaded.meta.schema_version, CURRENT_SCHEMA_VERSION);
+        let module = &loaded.stations[&test_station_id()].core.modules[0];
+        assert_eq!(module.module_priority, 7);
+    }
+
+    #[test]
+    fn v2_import_orders_are_credited_to_the_station_owner() {
+        let content = base_content();
+        let state = base_state(&content);
+        let owner = state.stations[&test_station_id()].owner.clone();
+        let mut value = serde_json::to_value(&state).unwrap();
+        value["meta"]["schema_version"] = Value::from(2);
+        value["import_orders"] = serde_json::json!([{
+            "id": 4,
+            "station_id": test_station_id().0,
+            "item_spec": {"Material": {"element": "Fe", "kg": 100.0}},
+            "items": [],
+            "cost": 1000.0,
+            "placed_tick": 0,
+            "arrival_tick": 5
+        }]);
+
+        assert_eq!(migrate_state_json(&mut value).unwrap(), 2);
+        let loaded: GameState = serde_json::from_value(value).unwrap();
+        assert_eq!(loaded.import_orders[0].issued_by, owner);
+    }
+
+    #[test]
+    fn future_and_unversioned_saves_are_refused() {
+        let content = base_content();
+        let state = base_state(&content);
+        let mut value = serde_json::to_value(&state).unwrap();
+        value["meta"]["schema_version"] = Value::from(CURRENT_SCHEMA_VERSION + 1);
+        assert_eq!(
+            migrate_state_json(&mut value),
+            Err(MigrationError::FutureVersion {
+                found: CURRENT_SCHEMA_VERSION + 1,
+                current: CURRENT_SCHEMA_VERSION,
+            })
+        );
+
+        value["meta"]
+            .as_object_mut()
+            .unwrap()
+            .remove("schema_version");
+        assert_eq!(
+            migrate_state_json(&mut value),
+            Err(MigrationError::Unversioned)
+        );
+    }
+}
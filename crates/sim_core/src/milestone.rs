@@ -280,7 +280,7 @@ pub fn evaluate_milestones(
                         amount: milestone.rewards.grant_amount,
                         tick: state.meta.tick,
                     });
-                    state.balance += milestone.rewards.grant_amount;
+                    crate::fixed::add_credits(&mut state.balance, milestone.rewards.grant_amount);
                     events.push(crate::emit(
                         &mut state.counters,
                         state.meta.tick,
@@ -495,7 +495,11 @@ mod tests {
                 knowledge: crate::AsteroidKnowledge {
                     tag_beliefs: vec![],
                     composition: None,
+                    layers: None,
+                    composition_sigma: None,
+                    deep_scan_count: 0,
                 },
+                layers: None,
             },
         );
 
@@ -765,7 +769,11 @@ mod tests {
                 knowledge: crate::AsteroidKnowledge {
                     tag_beliefs: vec![],
                     composition: None,
+                    layers: None,
+                    composition_sigma: None,
+                    deep_scan_count: 0,
                 },
+                layers: None,
             },
         );
         assert_eq!(
@@ -835,6 +843,10 @@ mod tests {
                 core: crate::FacilityCore::default(),
                 frame_id: None,
                 leaders: Vec::new(),
+                owner: crate::PrincipalId("principal_autopilot".to_string()),
+                expansions: crate::StationExpansions::default(),
+                power_policy: crate::PowerPolicy::default(),
+                history: std::collections::VecDeque::new(),
             },
         );
         assert_eq!(
@@ -21,12 +21,14 @@ pub enum StatId {
     DataGeneration,
     ProcessingInterval,
     ProcessingQuality,
+    ProcessingThroughput,
     ProcessingYield,
 
     // Mining & scanning
     MiningRate,
     ScanDuration,
     ScanInterval,
+    SensorQuality,
 
     // Research
     ResearchSpeed,
@@ -75,6 +77,8 @@ pub enum ModifierOp {
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ModifierSource {
+    /// Station layout bonus from an adjacent module of the named behavior type.
+    Adjacency(String),
     /// Crew-based modifier (forward compat — not wired in Phase 1).
     Crew(crate::CrewRole),
     Environment,
@@ -0,0 +1,106 @@
+//! Local consequences of jettisoned slag.
+//!
+//! `JettisonSlag` no longer makes waste disappear for free: the mass is
+//! recorded against the station's parent body, and once the cumulative total
+//! passes `slag_pollution_threshold_kg` that body degrades scan data and
+//! occasionally damages arriving ships.
+
+use rand::Rng;
+
+use crate::{BodyId, DataKind, Event, EventEnvelope, GameContent, GameState, ShipId};
+
+/// True when the cumulative slag jettisoned at `body_id` has reached the
+/// pollution threshold.
+pub(crate) fn is_polluted(state: &GameState, body_id: &BodyId, content: &GameContent) -> bool {
+    state
+        .jettisoned_slag_kg
+        .get(body_id)
+        .is_some_and(|kg| *kg >= content.constants.slag_pollution_threshold_kg)
+}
+
+/// Record `kg` of slag dumped at `body_id`, emitting `SlagPollutionWarning`
+/// the first time the body crosses the threshold.
+pub(crate) fn record_jettison(
+    state: &mut GameState,
+    body_id: &BodyId,
+    kg: f32,
+    content: &GameContent,
+    current_tick: u64,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let threshold_kg = content.constants.slag_pollution_threshold_kg;
+    let total = state
+        .jettisoned_slag_kg
+        .entry(body_id.clone())
+        .or_insert(0.0);
+    let before = *total;
+    *total += kg;
+    let jettisoned_kg = *total;
+    if before < threshold_kg && jettisoned_kg >= threshold_kg {
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            Event::SlagPollutionWarning {
+                body_id: body_id.clone(),
+                jettisoned_kg,
+                threshold_kg,
+            },
+        ));
+    }
+}
+
+/// Scale freshly generated scan data down when it was gathered at a polluted
+/// body. `amount` has already been added to the data pool by
+/// `generate_data`; the penalty share is removed again and the reduced
+/// amount returned for the `DataGenerated` event.
+pub(crate) fn dampen_scan_data(
+    state: &mut GameState,
+    body_id: &BodyId,
+    kind: &DataKind,
+    amount: f32,
+    content: &GameContent,
+) -> f32 {
+    if !is_polluted(state, body_id, content) {
+        return amount;
+    }
+    let reduced = amount * content.constants.slag_pollution_scan_data_mult;
+    if let Some(pool) = state.research.data_pool.get_mut(kind) {
+        *pool = (*pool - (amount - reduced)).max(0.0);
+    }
+    reduced
+}
+
+/// Roll for a debris strike on a ship that just arrived at `body_id`.
+/// Draws from the RNG only when the body is polluted, so clean runs keep
+/// their random sequence.
+pub(crate) fn roll_debris_strike(
+    state: &mut GameState,
+    ship_id: &ShipId,
+    body_id: &BodyId,
+    content: &GameContent,
+    rng: &mut impl Rng,
+    events: &mut Vec<EventEnvelope>,
+) {
+    if !is_polluted(state, body_id, content) {
+        return;
+    }
+    if rng.gen::<f32>() >= content.constants.slag_pollution_hazard_chance {
+        return;
+    }
+    let Some(ship) = state.ships.get_mut(ship_id) else {
+        return;
+    };
+    let propellant_lost_kg =
+        ship.propellant_kg * content.constants.slag_pollution_hazard_propellant_frac;
+    ship.propellant_kg -= propellant_lost_kg;
+    let current_tick = state.meta.tick;
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::DebrisStrike {
+            ship_id: ship_id.clone(),
+            body_id: body_id.clone(),
+            propellant_lost_kg,
+        },
+    ));
+}
@@ -0,0 +1,18 @@
+//! Curated imports for code that drives the simulation from outside.
+//!
+//! `use sim_core::prelude::*;` brings in the stable facade: the `tick`
+//! entry point, the command and event envelopes, `GameState` and the entity
+//! types its accessors return, and the content types needed to run a world.
+//! Items here follow `CURRENT_SCHEMA_VERSION`; anything reached through
+//! `sim_core::internals` does not.
+
+pub use crate::engine::tick;
+pub use crate::instrumentation::TickTimings;
+pub use crate::metrics::{compute_metrics, MetricsSnapshot};
+pub use crate::spatial::{build_body_cache, Position};
+pub use crate::types::{
+    AsteroidId, AsteroidState, Command, CommandEnvelope, CommandId, Constants, Event,
+    EventEnvelope, EventId, GameContent, GameState, InventoryItem, ModuleDefId, ModuleState,
+    PrincipalId, ShipId, ShipState, StationId, StationState, TaskKind, TechId, TradeItemSpec,
+    COMMAND_SCHEMA_VERSION, CURRENT_SCHEMA_VERSION,
+};
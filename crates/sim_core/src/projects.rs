@@ -0,0 +1,275 @@
+//! Station construction projects.
+//!
+//! `Command::StartProject` begins a content-defined `ProjectDef` at a
+//! station. Each tick the project draws `1 / duration_ticks` of every
+//! material from the station's lots; a tick where any share is missing
+//! stalls the project without consuming anything. On completion the
+//! outcome is recorded in `StationState.expansions` so it survives frame
+//! recomputes. Cancelling returns part of what was consumed.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    ConstructionProject, ConsumedMaterial, ElementId, Event, EventEnvelope, GameContent, GameState,
+    InventoryItem, ProjectId, ProjectOutcome, SlotDef, StationId, StationState,
+};
+
+/// Start a project at a station. Rejected for unknown defs or stations,
+/// locked techs, or a station already running a project.
+pub(crate) fn handle_start_project(
+    state: &mut GameState,
+    content: &GameContent,
+    station_id: &StationId,
+    project_def_id: &str,
+    current_tick: u64,
+    events: &mut Vec<EventEnvelope>,
+) -> Result<(), String> {
+    let Some(def) = content.project_defs.get(project_def_id) else {
+        return Err(format!("unknown project def '{project_def_id}'"));
+    };
+    if !state.stations.contains_key(station_id)
+        || state.projects.iter().any(|p| &p.station_id == station_id)
+    {
+        return Err("station is unknown or already has a project".to_string());
+    }
+    if def
+        .required_tech
+        .as_ref()
+        .is_some_and(|tech| !state.research.unlocked.contains(tech))
+    {
+        return Err("project requires a tech not yet unlocked".to_string());
+    }
+
+    let id = ProjectId(state.counters.next_project_id);
+    state.counters.next_project_id += 1;
+    state.projects.push(ConstructionProject {
+        id,
+        def_id: def.id.clone(),
+        station_id: station_id.clone(),
+        started_tick: current_tick,
+        progress_ticks: 0,
+        consumed: BTreeMap::new(),
+        stalled: false,
+    });
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::ProjectStarted {
+            project_id: id,
+            station_id: station_id.clone(),
+            project_def_id: def.id.clone(),
+        },
+    ));
+    Ok(())
+}
+
+/// Abandon a project and return `cancel_refund_fraction` of each consumed
+/// material to the station, at the average quality it was drawn at.
+pub(crate) fn handle_cancel_project(
+    state: &mut GameState,
+    content: &GameContent,
+    project_id: ProjectId,
+    current_tick: u64,
+    events: &mut Vec<EventEnvelope>,
+) -> Result<(), String> {
+    let Some(index) = state.projects.iter().position(|p| p.id == project_id) else {
+        return Err(format!("unknown project {}", project_id.0));
+    };
+    let project = state.projects.remove(index);
+    let fraction = content
+        .project_defs
+        .get(&project.def_id)
+        .map_or(0.0, |def| def.cancel_refund_fraction.clamp(0.0, 1.0));
+
+    let mut refunded_kg = 0.0;
+    if let Some(station) = state.stations.get_mut(&project.station_id) {
+        for (element, material) in &project.consumed {
+            let kg = material.kg * fraction;
+            if kg < content.constants.min_meaningful_kg {
+                continue;
+            }
+            let quality = material.quality_kg / material.kg;
+            crate::composition::merge_material_lot(
+                &mut station.core.inventory,
+                element.clone(),
+                kg,
+                quality,
+                None,
+            );
+            refunded_kg += kg;
+        }
+        station.invalidate_volume_cache();
+    }
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::ProjectCancelled {
+            project_id,
+            station_id: project.station_id,
+            project_def_id: project.def_id,
+            refunded_kg,
+        },
+    ));
+    Ok(())
+}
+
+/// Advance every project by one tick, completing those that reach their
+/// duration.
+pub(crate) fn tick_projects(
+    state: &mut GameState,
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+) {
+    if state.projects.is_empty() {
+        return;
+    }
+    let current_tick = state.meta.tick;
+    let mut completed = Vec::new();
+
+    for project in &mut state.projects {
+        let Some(def) = content.project_defs.get(&project.def_id) else {
+            continue;
+        };
+        let Some(station) = state.stations.get_mut(&project.station_id) else {
+            continue;
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let duration = def.duration_ticks.max(1) as f32;
+        let shares: Vec<(&ElementId, f32)> = def
+            .materials
+            .iter()
+            .map(|(element, total_kg)| (element, total_kg / duration))
+            .collect();
+
+        let short = shares
+            .iter()
+            .find(|(element, kg)| material_kg(station, element) + f32::EPSILON < *kg);
+        if let Some((element, _)) = short {
+            if !project.stalled {
+                project.stalled = true;
+                events.push(crate::emit(
+                    &mut state.counters,
+                    current_tick,
+                    Event::ProjectStalled {
+                        project_id: project.id,
+                        station_id: project.station_id.clone(),
+                        element: (*element).clone(),
+                    },
+                ));
+            }
+            continue;
+        }
+
+        for (element, kg) in shares {
+            let drawn = take_material(station, element, kg, content.constants.min_meaningful_kg);
+            let entry = project.consumed.entry(element.clone()).or_default();
+            crate::fixed::add_kg(&mut entry.kg, drawn.kg);
+            entry.quality_kg += drawn.quality_kg;
+        }
+        station.invalidate_volume_cache();
+        project.stalled = false;
+        project.progress_ticks += 1;
+        if project.progress_ticks >= def.duration_ticks {
+            completed.push(project.id);
+        }
+    }
+
+    for project_id in completed {
+        complete_project(state, content, project_id, current_tick, events);
+    }
+}
+
+fn complete_project(
+    state: &mut GameState,
+    content: &GameContent,
+    project_id: ProjectId,
+    current_tick: u64,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let Some(index) = state.projects.iter().position(|p| p.id == project_id) else {
+        return;
+    };
+    let project = state.projects.remove(index);
+    let Some(def) = content.project_defs.get(&project.def_id) else {
+        return;
+    };
+    if let Some(station) = state.stations.get_mut(&project.station_id) {
+        match &def.outcome {
+            ProjectOutcome::CargoCapacity { m3 } => {
+                station.expansions.cargo_capacity_m3 += m3;
+                station.core.cargo_capacity_m3 += m3;
+            }
+            ProjectOutcome::ModuleSlot { slot_type, label } => {
+                station.expansions.slots.push(SlotDef {
+                    slot_type: slot_type.clone(),
+                    label: label.clone(),
+                    adjacent: Vec::new(),
+                });
+            }
+        }
+        station.invalidate_volume_cache();
+    }
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::ProjectCompleted {
+            project_id,
+            station_id: project.station_id,
+            project_def_id: project.def_id,
+            outcome: def.outcome.clone(),
+        },
+    ));
+}
+
+fn material_kg(station: &StationState, element: &str) -> f32 {
+    station
+        .core
+        .inventory
+        .iter()
+        .filter_map(|item| match item {
+            InventoryItem::Material {
+                element: lot_element,
+                kg,
+                ..
+            } if lot_element == element => Some(*kg),
+            _ => None,
+        })
+        .sum()
+}
+
+/// Draw `required_kg` of an element across its lots, first lot first,
+/// pruning lots that fall below `min_kg`.
+fn take_material(
+    station: &mut StationState,
+    element: &str,
+    required_kg: f32,
+    min_kg: f32,
+) -> ConsumedMaterial {
+    let mut remaining = required_kg;
+    let mut drawn = ConsumedMaterial::default();
+    for item in &mut station.core.inventory {
+        if remaining <= 0.0 {
+            break;
+        }
+        if let InventoryItem::Material {
+            element: lot_element,
+            kg,
+            quality,
+            ..
+        } = item
+        {
+            if lot_element == element {
+                let take = kg.min(remaining);
+                crate::fixed::add_kg(kg, -take);
+                remaining -= take;
+                drawn.kg += take;
+                drawn.quality_kg += take * *quality;
+            }
+        }
+    }
+    station
+        .core
+        .inventory
+        .retain(|i| !matches!(i, InventoryItem::Material { kg, .. } if *kg < min_kg));
+    drawn
+}
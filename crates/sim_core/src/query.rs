@@ -0,0 +1,816 @@
+//! Filter expressions over snapshot collections.
+//!
+//! A query names a collection and an optional filter in brackets:
+//!
+//! ```text
+//! ships[owner=principal_autopilot & task=Idle]
+//! asteroids[body=belt_inner & mass_kg>1000 & !scanned=true]
+//! stations
+//! ```
+//!
+//! Comparisons are `field op value` with `=`, `!=`, `<`, `<=`, `>`, `>=`,
+//! combined with `&`, `|`, `!` and parentheses (`&` binds tighter than
+//! `|`). Values are bare words or `"quoted strings"`. Every collection has a
+//! fixed set of typed fields (see [`Query::fields`]); unknown fields, a
+//! non-numeric value for a numeric field, or an ordering on a text field are
+//! parse errors, so a query that parses always evaluates. `node` is accepted
+//! as an alias for `body`. Set-valued fields (`tag`) test membership.
+//!
+//! Callers with structured filters build the same conditions with
+//! [`Query::all`] and [`Query::and`], and order and page results with a
+//! [`SortKey`] and [`QueryResults::sort_and_page`].
+
+use serde::Serialize;
+
+use crate::{inventory_mass_kg, AsteroidState, GameState, ScanSite, ShipState, StationState};
+
+/// Collections a query can select from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Collection {
+    Ships,
+    Stations,
+    Asteroids,
+    ScanSites,
+}
+
+impl Collection {
+    const ALL: [(&'static str, Self); 4] = [
+        ("ships", Self::Ships),
+        ("stations", Self::Stations),
+        ("asteroids", Self::Asteroids),
+        ("scan_sites", Self::ScanSites),
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldKind {
+    Text,
+    Number,
+    Bool,
+    /// Set of strings; `=` tests membership.
+    Tags,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Id,
+    Owner,
+    Task,
+    Body,
+    Hull,
+    HomeStation,
+    CargoKg,
+    PropellantKg,
+    Modules,
+    MassKg,
+    Scanned,
+    Tag,
+    Template,
+}
+
+type FieldSpec = (&'static str, Field, FieldKind);
+
+const SHIP_FIELDS: &[FieldSpec] = &[
+    ("id", Field::Id, FieldKind::Text),
+    ("owner", Field::Owner, FieldKind::Text),
+    ("task", Field::Task, FieldKind::Text),
+    ("body", Field::Body, FieldKind::Text),
+    ("hull", Field::Hull, FieldKind::Text),
+    ("home_station", Field::HomeStation, FieldKind::Text),
+    ("cargo_kg", Field::CargoKg, FieldKind::Number),
+    ("propellant_kg", Field::PropellantKg, FieldKind::Number),
+];
+const STATION_FIELDS: &[FieldSpec] = &[
+    ("id", Field::Id, FieldKind::Text),
+    ("owner", Field::Owner, FieldKind::Text),
+    ("body", Field::Body, FieldKind::Text),
+    ("modules", Field::Modules, FieldKind::Number),
+    ("cargo_kg", Field::CargoKg, FieldKind::Number),
+];
+const ASTEROID_FIELDS: &[FieldSpec] = &[
+    ("id", Field::Id, FieldKind::Text),
+    ("body", Field::Body, FieldKind::Text),
+    ("mass_kg", Field::MassKg, FieldKind::Number),
+    ("scanned", Field::Scanned, FieldKind::Bool),
+    ("tag", Field::Tag, FieldKind::Tags),
+];
+const SCAN_SITE_FIELDS: &[FieldSpec] = &[
+    ("id", Field::Id, FieldKind::Text),
+    ("body", Field::Body, FieldKind::Text),
+    ("template", Field::Template, FieldKind::Text),
+];
+
+impl Collection {
+    /// Filterable fields. `node` is also accepted as an alias for `body`.
+    fn field_specs(self) -> &'static [FieldSpec] {
+        match self {
+            Self::Ships => SHIP_FIELDS,
+            Self::Stations => STATION_FIELDS,
+            Self::Asteroids => ASTEROID_FIELDS,
+            Self::ScanSites => SCAN_SITE_FIELDS,
+        }
+    }
+
+    fn field(self, name: &str) -> Option<(Field, FieldKind)> {
+        let lookup = if name == "node" { "body" } else { name };
+        self.field_specs()
+            .iter()
+            .find(|(n, ..)| *n == lookup)
+            .map(|&(_, field, kind)| (field, kind))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Literal {
+    Text(String),
+    Number(f64),
+    Bool(bool),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Compare(Field, Op, Literal),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+/// A parsed query. Parse once, run against any number of states.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    pub collection: Collection,
+    filter: Option<Expr>,
+}
+
+/// Result order for [`QueryResults::sort_and_page`]: a non-set field of
+/// the collection, ties broken by id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    field: Field,
+    descending: bool,
+}
+
+impl SortKey {
+    /// `field` ascending, or `-field` descending. `node` is accepted for
+    /// `body`.
+    pub fn parse(collection: Collection, input: &str) -> Result<Self, QueryError> {
+        let (name, descending) = match input.strip_prefix('-') {
+            Some(name) => (name, true),
+            None => (input, false),
+        };
+        let (field, kind) = collection.field(name).ok_or_else(|| {
+            let fields: Vec<&str> = Query::fields(collection).collect();
+            error(
+                &format!("unknown sort field '{name}' (expected one of {fields:?})"),
+                0,
+            )
+        })?;
+        if kind == FieldKind::Tags {
+            return Err(error(&format!("cannot sort by '{name}'"), 0));
+        }
+        Ok(Self { field, descending })
+    }
+}
+
+/// A query that failed to parse. `offset` is the byte offset of the
+/// offending token in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    pub message: String,
+    pub offset: usize,
+}
+
+impl std::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at offset {})", self.message, self.offset)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+/// Entities matched by [`Query::run`], in id order. Serializes as a plain
+/// array of the matched entities.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum QueryResults<'a> {
+    Ships(Vec<&'a ShipState>),
+    Stations(Vec<&'a StationState>),
+    Asteroids(Vec<&'a AsteroidState>),
+    ScanSites(Vec<&'a ScanSite>),
+}
+
+impl QueryResults<'_> {
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Ships(items) => items.len(),
+            Self::Stations(items) => items.len(),
+            Self::Asteroids(items) => items.len(),
+            Self::ScanSites(items) => items.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Ids of the matched entities.
+    pub fn ids(&self) -> Vec<&str> {
+        match self {
+            Self::Ships(items) => items.iter().map(|s| s.id.0.as_str()).collect(),
+            Self::Stations(items) => items.iter().map(|s| s.id.0.as_str()).collect(),
+            Self::Asteroids(items) => items.iter().map(|a| a.id.0.as_str()).collect(),
+            Self::ScanSites(items) => items.iter().map(|s| s.id.0.as_str()).collect(),
+        }
+    }
+
+    /// Order by `key` (id order when `None`), then keep at most `limit`
+    /// results starting at `offset`. A key from another collection's
+    /// fields sorts as if every value were equal.
+    pub fn sort_and_page(&mut self, key: Option<SortKey>, offset: usize, limit: usize) {
+        match self {
+            Self::Ships(items) => sort_and_page(items, key, offset, limit),
+            Self::Stations(items) => sort_and_page(items, key, offset, limit),
+            Self::Asteroids(items) => sort_and_page(items, key, offset, limit),
+            Self::ScanSites(items) => sort_and_page(items, key, offset, limit),
+        }
+    }
+}
+
+/// `items` are in id order, so a stable sort breaks ties by id.
+fn sort_and_page<T: Entity>(
+    items: &mut Vec<&T>,
+    key: Option<SortKey>,
+    offset: usize,
+    limit: usize,
+) {
+    if let Some(key) = key {
+        items.sort_by(|a, b| {
+            let order = order_values(&a.value(key.field), &b.value(key.field));
+            if key.descending {
+                order.reverse()
+            } else {
+                order
+            }
+        });
+    }
+    items.drain(..offset.min(items.len()));
+    items.truncate(limit);
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        let tokens = tokenize(input)?;
+        Parser {
+            tokens: &tokens,
+            pos: 0,
+            end: input.len(),
+            collection: Collection::Ships,
+        }
+        .query()
+    }
+
+    /// Every entity of `collection`.
+    pub fn all(collection: Collection) -> Self {
+        Self {
+            collection,
+            filter: None,
+        }
+    }
+
+    /// Also require `field op value`, checked as a bracketed comparison
+    /// would be. `op` is one of `=`, `!=`, `<`, `<=`, `>`, `>=`; `value` is
+    /// taken verbatim, as if quoted.
+    pub fn and(self, field: &str, op: &str, value: &str) -> Result<Self, QueryError> {
+        let mut tokens = vec![(Token::Word(field.to_string()), 0)];
+        tokens.extend(tokenize(op)?);
+        tokens.push((Token::Quoted(value.to_string()), 0));
+        let mut parser = Parser {
+            tokens: &tokens,
+            pos: 0,
+            end: 0,
+            collection: self.collection,
+        };
+        let condition = parser.comparison()?;
+        if parser.pos < tokens.len() {
+            return Err(error(&format!("'{op}' is not a comparison operator"), 0));
+        }
+        let filter = match self.filter {
+            Some(expr) => Expr::And(Box::new(expr), Box::new(condition)),
+            None => condition,
+        };
+        Ok(Self {
+            collection: self.collection,
+            filter: Some(filter),
+        })
+    }
+
+    /// Field names the collection can be filtered on.
+    pub fn fields(collection: Collection) -> impl Iterator<Item = &'static str> {
+        collection.field_specs().iter().map(|(name, ..)| *name)
+    }
+
+    pub fn run<'a>(&self, state: &'a GameState) -> QueryResults<'a> {
+        match self.collection {
+            Collection::Ships => QueryResults::Ships(self.select(state.ships.values())),
+            Collection::Stations => QueryResults::Stations(self.select(state.stations.values())),
+            Collection::Asteroids => QueryResults::Asteroids(self.select(state.asteroids.values())),
+            Collection::ScanSites => {
+                let mut sites = self.select(state.scan_sites.iter());
+                sites.sort_by(|a, b| a.id.cmp(&b.id));
+                QueryResults::ScanSites(sites)
+            }
+        }
+    }
+
+    fn select<'a, T: Entity + 'a>(&self, items: impl Iterator<Item = &'a T>) -> Vec<&'a T> {
+        items
+            .filter(|item| self.filter.as_ref().is_none_or(|expr| eval(expr, *item)))
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Evaluation
+// ---------------------------------------------------------------------------
+
+enum Value<'a> {
+    Text(Option<&'a str>),
+    Number(f64),
+    Bool(bool),
+    Tags(Vec<&'a str>),
+}
+
+trait Entity {
+    /// Only called with fields from the entity's `Collection::field_specs`.
+    fn value(&self, field: Field) -> Value<'_>;
+}
+
+impl Entity for ShipState {
+    fn value(&self, field: Field) -> Value<'_> {
+        match field {
+            Field::Id => Value::Text(Some(&self.id.0)),
+            Field::Owner => Value::Text(Some(&self.owner.0)),
+            Field::Task => Value::Text(Some(
+                self.task.as_ref().map_or("Idle", |task| task.kind.label()),
+            )),
+            Field::Body => Value::Text(Some(&self.position.parent_body.0)),
+            Field::Hull => Value::Text(Some(&self.hull_id.0)),
+            Field::HomeStation => Value::Text(self.home_station.as_ref().map(|s| s.0.as_str())),
+            Field::CargoKg => Value::Number(f64::from(inventory_mass_kg(&self.inventory))),
+            Field::PropellantKg => Value::Number(f64::from(self.propellant_kg)),
+            _ => Value::Text(None),
+        }
+    }
+}
+
+impl Entity for StationState {
+    fn value(&self, field: Field) -> Value<'_> {
+        match field {
+            Field::Id => Value::Text(Some(&self.id.0)),
+            Field::Owner => Value::Text(Some(&self.owner.0)),
+            Field::Body => Value::Text(Some(&self.position.parent_body.0)),
+            #[allow(clippy::cast_precision_loss)]
+            Field::Modules => Value::Number(self.core.modules.len() as f64),
+            Field::CargoKg => Value::Number(f64::from(inventory_mass_kg(&self.core.inventory))),
+            _ => Value::Text(None),
+        }
+    }
+}
+
+impl Entity for AsteroidState {
+    fn value(&self, field: Field) -> Value<'_> {
+        match field {
+            Field::Id => Value::Text(Some(&self.id.0)),
+            Field::Body => Value::Text(Some(&self.position.parent_body.0)),
+            Field::MassKg => Value::Number(f64::from(self.mass_kg)),
+            Field::Scanned => Value::Bool(self.knowledge.composition.is_some()),
+            Field::Tag => Value::Tags(self.anomaly_tags.iter().map(|t| t.0.as_str()).collect()),
+            _ => Value::Text(None),
+        }
+    }
+}
+
+impl Entity for ScanSite {
+    fn value(&self, field: Field) -> Value<'_> {
+        match field {
+            Field::Id => Value::Text(Some(&self.id.0)),
+            Field::Body => Value::Text(Some(&self.position.parent_body.0)),
+            Field::Template => Value::Text(Some(&self.template_id)),
+            _ => Value::Text(None),
+        }
+    }
+}
+
+/// Order of two values of one field. Missing text sorts first.
+fn order_values(a: &Value<'_>, b: &Value<'_>) -> std::cmp::Ordering {
+    match (a, b) {
+        (Value::Text(a), Value::Text(b)) => a.cmp(b),
+        (Value::Number(a), Value::Number(b)) => a.total_cmp(b),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        _ => std::cmp::Ordering::Equal,
+    }
+}
+
+fn eval(expr: &Expr, entity: &impl Entity) -> bool {
+    match expr {
+        Expr::Not(inner) => !eval(inner, entity),
+        Expr::And(a, b) => eval(a, entity) && eval(b, entity),
+        Expr::Or(a, b) => eval(a, entity) || eval(b, entity),
+        Expr::Compare(field, op, literal) => compare(&entity.value(*field), *op, literal),
+    }
+}
+
+fn compare(value: &Value<'_>, op: Op, literal: &Literal) -> bool {
+    let equal = match (value, literal) {
+        (Value::Number(v), Literal::Number(n)) => {
+            return match op {
+                Op::Eq => (v - n).abs() < f64::EPSILON,
+                Op::Ne => (v - n).abs() >= f64::EPSILON,
+                Op::Lt => v < n,
+                Op::Le => v <= n,
+                Op::Gt => v > n,
+                Op::Ge => v >= n,
+            };
+        }
+        (Value::Text(v), Literal::Text(t)) => *v == Some(t.as_str()),
+        (Value::Bool(v), Literal::Bool(b)) => v == b,
+        (Value::Tags(tags), Literal::Text(t)) => tags.contains(&t.as_str()),
+        _ => false,
+    };
+    match op {
+        Op::Ne => !equal,
+        _ => equal,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Op(Op),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+    OpenBracket,
+    CloseBracket,
+}
+
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some(&(offset, c)) = chars.peek() {
+        chars.next();
+        let next_is_eq = chars.peek().is_some_and(|&(_, next)| next == '=');
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '[' => Token::OpenBracket,
+            ']' => Token::CloseBracket,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '&' => Token::And,
+            '|' => Token::Or,
+            '=' => Token::Op(Op::Eq),
+            '!' | '<' | '>' if next_is_eq => {
+                chars.next();
+                Token::Op(match c {
+                    '!' => Op::Ne,
+                    '<' => Op::Le,
+                    _ => Op::Ge,
+                })
+            }
+            '!' => Token::Not,
+            '<' => Token::Op(Op::Lt),
+            '>' => Token::Op(Op::Gt),
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, ch)) => text.push(ch),
+                        None => return Err(error("unterminated string", offset)),
+                    }
+                }
+                Token::Quoted(text)
+            }
+            c if is_word_char(c) => {
+                let mut word = c.to_string();
+                while let Some(&(_, ch)) = chars.peek() {
+                    if !is_word_char(ch) {
+                        break;
+                    }
+                    word.push(ch);
+                    chars.next();
+                }
+                Token::Word(word)
+            }
+            _ => return Err(error(&format!("unexpected character '{c}'"), offset)),
+        };
+        tokens.push((token, offset));
+    }
+    Ok(tokens)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':')
+}
+
+fn error(message: &str, offset: usize) -> QueryError {
+    QueryError {
+        message: message.to_string(),
+        offset,
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [(Token, usize)],
+    pos: usize,
+    /// Offset reported for errors at end of input.
+    end: usize,
+    collection: Collection,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map_or(self.end, |(_, offset)| *offset)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: &Token, what: &str) -> Result<(), QueryError> {
+        let offset = self.offset();
+        if self.next().as_ref() == Some(expected) {
+            Ok(())
+        } else {
+            Err(error(&format!("expected {what}"), offset))
+        }
+    }
+
+    fn query(mut self) -> Result<Query, QueryError> {
+        let offset = self.offset();
+        let Some(Token::Word(name)) = self.next() else {
+            return Err(error("expected a collection name", offset));
+        };
+        self.collection = Collection::ALL
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, c)| *c)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Collection::ALL.iter().map(|(n, _)| *n).collect();
+                error(
+                    &format!("unknown collection '{name}' (expected one of {names:?})"),
+                    offset,
+                )
+            })?;
+        let filter = if self.peek() == Some(&Token::OpenBracket) {
+            self.pos += 1;
+            let expr = self.or()?;
+            self.expect(&Token::CloseBracket, "']'")?;
+            Some(expr)
+        } else {
+            None
+        };
+        if self.pos < self.tokens.len() {
+            return Err(error("unexpected input after query", self.offset()));
+        }
+        Ok(Query {
+            collection: self.collection,
+            filter,
+        })
+    }
+
+    fn or(&mut self) -> Result<Expr, QueryError> {
+        let mut expr = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, QueryError> {
+        let mut expr = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, QueryError> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.pos += 1;
+                Ok(Expr::Not(Box::new(self.unary()?)))
+            }
+            Some(Token::Open) => {
+                self.pos += 1;
+                let expr = self.or()?;
+                self.expect(&Token::Close, "')'")?;
+                Ok(expr)
+            }
+            _ => self.comparison(),
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Expr, QueryError> {
+        let offset = self.offset();
+        let Some(Token::Word(name)) = self.next() else {
+            return Err(error("expected a field name", offset));
+        };
+        let Some((field, kind)) = self.collection.field(&name) else {
+            let fields: Vec<&str> = Query::fields(self.collection).collect();
+            return Err(error(
+                &format!("unknown field '{name}' (expected one of {fields:?})"),
+                offset,
+            ));
+        };
+
+        let op_offset = self.offset();
+        let Some(Token::Op(op)) = self.next() else {
+            return Err(error("expected a comparison operator", op_offset));
+        };
+        if kind != FieldKind::Number && !matches!(op, Op::Eq | Op::Ne) {
+            return Err(error(
+                &format!("'{name}' only supports '=' and '!='"),
+                op_offset,
+            ));
+        }
+
+        let value_offset = self.offset();
+        let Some(Token::Word(raw) | Token::Quoted(raw)) = self.next() else {
+            return Err(error("expected a value", value_offset));
+        };
+        let literal = match kind {
+            FieldKind::Text | FieldKind::Tags => Literal::Text(raw),
+            FieldKind::Number => Literal::Number(raw.parse().map_err(|_| {
+                error(
+                    &format!("'{name}' needs a number, got '{raw}'"),
+                    value_offset,
+                )
+            })?),
+            FieldKind::Bool => Literal::Bool(raw.parse().map_err(|_| {
+                error(
+                    &format!("'{name}' needs true or false, got '{raw}'"),
+                    value_offset,
+                )
+            })?),
+        };
+        Ok(Expr::Compare(field, op, literal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{base_content, base_state};
+    use crate::{AsteroidId, AsteroidKnowledge, BodyId, TaskKind, TaskState};
+
+    fn asteroid(id: &str, mass_kg: f32, body: &str) -> AsteroidState {
+        AsteroidState {
+            id: AsteroidId(id.to_string()),
+            position: crate::Position {
+                parent_body: BodyId(body.to_string()),
+                ..crate::test_fixtures::test_position()
+            },
+            true_composition: std::collections::HashMap::new(),
+            anomaly_tags: vec![crate::AnomalyTag("IronRich".to_string())],
+            mass_kg,
+            knowledge: AsteroidKnowledge {
+                tag_beliefs: Vec::new(),
+                composition: None,
+                layers: None,
+                composition_sigma: None,
+                deep_scan_count: 0,
+            },
+            layers: None,
+        }
+    }
+
+    #[test]
+    fn filters_ships_by_owner_and_task() {
+        let content = base_content();
+        let mut state = base_state(&content);
+        let ship_id = crate::test_fixtures::test_ship_id();
+        let owner = state.ships[&ship_id].owner.0.clone();
+
+        let idle = Query::parse(&format!("ships[owner={owner} & task=Idle]")).unwrap();
+        assert_eq!(idle.run(&state).ids(), vec![ship_id.0.as_str()]);
+
+        state.ships.get_mut(&ship_id).unwrap().task = Some(TaskState {
+            kind: TaskKind::Survey {
+                site: crate::SiteId("site_0001".to_string()),
+            },
+            started_tick: 0,
+            eta_tick: 10,
+        });
+        assert!(idle.run(&state).is_empty());
+        let surveying = Query::parse("ships[task=Survey | task=DeepScan]").unwrap();
+        assert_eq!(surveying.run(&state).len(), 1);
+    }
+
+    #[test]
+    fn filters_asteroids_by_node_mass_and_tag() {
+        let content = base_content();
+        let mut state = base_state(&content);
+        state.asteroids.clear();
+        for a in [
+            asteroid("a1", 500.0, "belt_1"),
+            asteroid("a2", 5000.0, "belt_1"),
+            asteroid("a3", 5000.0, "belt_2"),
+        ] {
+            state.asteroids.insert(a.id.clone(), a);
+        }
+
+        let query = Query::parse(r#"asteroids[node="belt_1" & mass_kg>1000]"#).unwrap();
+        assert_eq!(query.run(&state).ids(), vec!["a2"]);
+
+        let query = Query::parse("asteroids[!(body=belt_1) & tag=IronRich & scanned=false]");
+        assert_eq!(query.unwrap().run(&state).ids(), vec!["a3"]);
+
+        assert_eq!(
+            Query::parse("asteroids").unwrap().run(&state).len(),
+            3,
+            "no filter selects everything"
+        );
+    }
+
+    #[test]
+    fn structured_conditions_sort_and_page() {
+        let content = base_content();
+        let mut state = base_state(&content);
+        state.asteroids.clear();
+        for a in [
+            asteroid("a1", 500.0, "belt_1"),
+            asteroid("a2", 3000.0, "belt_1"),
+            asteroid("a3", 2000.0, "belt_1"),
+            asteroid("a4", 9000.0, "belt_2"),
+        ] {
+            state.asteroids.insert(a.id.clone(), a);
+        }
+
+        let query = Query::all(Collection::Asteroids)
+            .and("node", "=", "belt_1")
+            .and_then(|query| query.and("mass_kg", ">=", "1000"))
+            .unwrap();
+        let mut results = query.run(&state);
+        assert_eq!(results.ids(), vec!["a2", "a3"]);
+        let by_mass = SortKey::parse(Collection::Asteroids, "-mass_kg").unwrap();
+
+        let mut all = Query::all(Collection::Asteroids).run(&state);
+        all.sort_and_page(Some(by_mass), 1, 2);
+        assert_eq!(all.ids(), vec!["a2", "a3"]);
+        results.sort_and_page(None, 5, 10);
+        assert!(results.is_empty(), "offset past the end");
+
+        assert!(Query::all(Collection::Asteroids)
+            .and("owner", "=", "principal_player")
+            .is_err());
+        assert!(Query::all(Collection::Asteroids)
+            .and("mass_kg", "~", "1")
+            .is_err());
+        assert!(SortKey::parse(Collection::Asteroids, "tag").is_err());
+    }
+
+    #[test]
+    fn rejects_ill_typed_queries() {
+        let cases = [
+            ("planets", 0),
+            ("ships[colour=red]", 6),
+            ("ships[owner>a]", 11),
+            ("asteroids[mass_kg=heavy]", 18),
+            ("asteroids[scanned=maybe]", 18),
+            ("ships[task=Idle", 15),
+            ("ships[task=Idle] extra", 17),
+        ];
+        for (input, offset) in cases {
+            let err = Query::parse(input).unwrap_err();
+            assert_eq!(err.offset, offset, "{input}: {err}");
+        }
+    }
+}
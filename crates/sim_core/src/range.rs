@@ -0,0 +1,121 @@
+//! Ship mission range.
+//!
+//! Distance here is counted in hops along the body tree (`parent` links in
+//! the solar system), measured from the nearest station the ship's owner
+//! has. A ship reaches `propellant_capacity_kg /
+//! mission_range_propellant_kg_per_hop` hops, so hull class and fitted
+//! tanks both set it. Task assignments whose target lies beyond range emit
+//! `TaskOutOfRange` and, with `mission_range_refuse`, are dropped; a
+//! forward station at or near a remote body is how that body is opened up.
+//! Owners with no station are not limited.
+
+use crate::{BodyId, GameContent, GameState, PrincipalId, ShipState, TaskKind};
+
+/// An assignment target beyond the ship's range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeViolation {
+    pub target_body: BodyId,
+    pub hops: u32,
+    pub range_hops: u32,
+}
+
+/// The ship's range in body hops, or `None` when range limits are off.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn mission_range_hops(ship: &ShipState, content: &GameContent) -> Option<u32> {
+    let per_hop = content.constants.mission_range_propellant_kg_per_hop;
+    if per_hop <= 0.0 {
+        return None;
+    }
+    Some((ship.propellant_capacity_kg / per_hop).floor().max(0.0) as u32)
+}
+
+/// Hops between two bodies through their nearest common ancestor, or
+/// `None` if either body is unknown or they share no root.
+pub fn body_hops(content: &GameContent, from: &BodyId, to: &BodyId) -> Option<u32> {
+    let from_chain = ancestry(content, from)?;
+    let to_chain = ancestry(content, to)?;
+    to_chain.iter().enumerate().find_map(|(up_to, body)| {
+        let up_from = from_chain.iter().position(|b| b == body)?;
+        u32::try_from(up_from + up_to).ok()
+    })
+}
+
+/// `body` followed by its parents up to the root.
+fn ancestry<'a>(content: &'a GameContent, body: &'a BodyId) -> Option<Vec<&'a BodyId>> {
+    let bodies = &content.solar_system.bodies;
+    let mut chain = vec![body];
+    let mut current = bodies.iter().find(|b| b.id == *body)?;
+    while let Some(parent) = &current.parent {
+        // Guard against a malformed tree with a parent cycle.
+        if chain.contains(&parent) || chain.len() > bodies.len() {
+            break;
+        }
+        chain.push(parent);
+        current = bodies.iter().find(|b| b.id == *parent)?;
+    }
+    Some(chain)
+}
+
+/// Hops from `body` to the nearest station owned by `owner`, or `None` if
+/// the owner has no station.
+pub fn hops_from_nearest_station(
+    state: &GameState,
+    content: &GameContent,
+    owner: &PrincipalId,
+    body: &BodyId,
+) -> Option<u32> {
+    state
+        .stations
+        .values()
+        .filter(|station| station.owner == *owner)
+        .filter_map(|station| body_hops(content, &station.position.parent_body, body))
+        .min()
+}
+
+/// The body a task sends the ship to, if it has one.
+pub fn task_target_body(state: &GameState, task: &TaskKind) -> Option<BodyId> {
+    let body = match task {
+        TaskKind::Transit { destination, .. } => &destination.parent_body,
+        TaskKind::Survey { site } => {
+            &state
+                .scan_sites
+                .iter()
+                .find(|s| s.id == *site)?
+                .position
+                .parent_body
+        }
+        TaskKind::DeepScan { asteroid }
+        | TaskKind::Resurvey { asteroid }
+        | TaskKind::Mine { asteroid, .. } => &state.asteroids.get(asteroid)?.position.parent_body,
+        TaskKind::ConstructStation { position, .. } => &position.parent_body,
+        TaskKind::Deposit { station, .. }
+        | TaskKind::DockForRepair { station }
+        | TaskKind::Refuel {
+            station_id: station,
+            ..
+        } => &state.stations.get(station)?.position.parent_body,
+        TaskKind::Pickup { from_station, .. } => {
+            &state.stations.get(from_station)?.position.parent_body
+        }
+        TaskKind::Idle | TaskKind::TransferCargo { .. } | TaskKind::Escort { .. } => return None,
+    };
+    Some(body.clone())
+}
+
+/// Check `task` against `ship`'s mission range. `None` means in range,
+/// unlimited, or not measurable.
+pub fn check_task_range(
+    state: &GameState,
+    content: &GameContent,
+    ship: &ShipState,
+    task: &TaskKind,
+) -> Option<RangeViolation> {
+    let range_hops = mission_range_hops(ship, content)?;
+    let target_body = task_target_body(state, task)?;
+    let hops = hops_from_nearest_station(state, content, &ship.owner, &target_body)?;
+    (hops > range_hops).then_some(RangeViolation {
+        target_body,
+        hops,
+        range_hops,
+    })
+}
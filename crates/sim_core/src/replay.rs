@@ -0,0 +1,249 @@
+//! Deterministic replay verification.
+//!
+//! A [`RunRecording`] holds everything needed to re-run a simulation — the
+//! initial state, the RNG seed and the commands applied each tick — plus
+//! what the run produced: each tick's events and a hash of the state after
+//! it. [`verify`] replays the recording against the same content and
+//! reports the first tick where the output diverges, which is how a
+//! refactor that changes iteration order, float evaluation or RNG draws
+//! shows up.
+//!
+//! Drivers seed the run's `ChaCha8Rng` with `seed_from_u64(seed)`.
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{CommandEnvelope, EventEnvelope, GameContent, GameState};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecording {
+    pub seed: u64,
+    pub initial_state: GameState,
+    pub ticks: Vec<RecordedTick>,
+}
+
+/// Input and output of one `tick` call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedTick {
+    /// `state.meta.tick` before the call.
+    pub tick: u64,
+    pub commands: Vec<CommandEnvelope>,
+    pub events: Vec<EventEnvelope>,
+    /// [`state_hash`] of the state after the call.
+    pub state_hash: u64,
+}
+
+impl RunRecording {
+    /// Start recording from `initial_state`, before its first tick.
+    pub fn new(seed: u64, initial_state: GameState) -> Self {
+        Self {
+            seed,
+            initial_state,
+            ticks: Vec::new(),
+        }
+    }
+
+    /// Record one tick; `state` is the state after it ran.
+    pub fn push_tick(
+        &mut self,
+        commands: &[CommandEnvelope],
+        events: &[EventEnvelope],
+        state: &GameState,
+    ) {
+        self.ticks.push(RecordedTick {
+            tick: state.meta.tick.saturating_sub(1),
+            commands: commands.to_vec(),
+            events: events.to_vec(),
+            state_hash: state_hash(state),
+        });
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayMismatch {
+    /// The replay reached a different tick number than recorded.
+    Tick { expected: u64, actual: u64 },
+    /// The tick's events differ; `index` is the first differing event
+    /// (equal to the shorter length when one log is a prefix of the other).
+    Events {
+        tick: u64,
+        index: usize,
+        expected: Option<String>,
+        actual: Option<String>,
+    },
+    /// Events matched but the resulting state did not.
+    StateHash {
+        tick: u64,
+        expected: u64,
+        actual: u64,
+    },
+}
+
+impl std::fmt::Display for ReplayMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tick { expected, actual } => {
+                write!(
+                    f,
+                    "replay reached tick {actual}, recording has tick {expected}"
+                )
+            }
+            Self::Events {
+                tick,
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "tick {tick}: event {index} differs (recorded {}, replayed {})",
+                expected.as_deref().unwrap_or("nothing"),
+                actual.as_deref().unwrap_or("nothing"),
+            ),
+            Self::StateHash {
+                tick,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "tick {tick}: state hash {actual:016x} does not match recorded {expected:016x}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReplayMismatch {}
+
+/// Re-run `recording` against `content` and check every tick's events and
+/// state hash. Returns the number of ticks verified.
+pub fn verify(recording: &RunRecording, content: &GameContent) -> Result<usize, ReplayMismatch> {
+    let mut state = recording.initial_state.clone();
+    let mut rng = ChaCha8Rng::seed_from_u64(recording.seed);
+    for recorded in &recording.ticks {
+        replay_tick(&mut state, recorded, content, &mut rng)?;
+    }
+    Ok(recording.ticks.len())
+}
+
+/// Run one recorded tick on `state` and check its events and state hash.
+/// `state` and `rng` must be where the recording was before that tick; on a
+/// mismatch the tick has still run, so a caller can carry on from the
+/// replayed state.
+pub fn replay_tick(
+    state: &mut GameState,
+    recorded: &RecordedTick,
+    content: &GameContent,
+    rng: &mut ChaCha8Rng,
+) -> Result<(), ReplayMismatch> {
+    if state.meta.tick != recorded.tick {
+        return Err(ReplayMismatch::Tick {
+            expected: recorded.tick,
+            actual: state.meta.tick,
+        });
+    }
+    let events = crate::tick(state, &recorded.commands, content, rng, None);
+    compare_events(recorded.tick, &recorded.events, &events)?;
+    let actual = state_hash(state);
+    if actual != recorded.state_hash {
+        return Err(ReplayMismatch::StateHash {
+            tick: recorded.tick,
+            expected: recorded.state_hash,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Events are compared in their JSON form; `Event` has no `PartialEq`.
+fn compare_events(
+    tick: u64,
+    expected: &[EventEnvelope],
+    actual: &[EventEnvelope],
+) -> Result<(), ReplayMismatch> {
+    let json = |event: Option<&EventEnvelope>| {
+        event.map(|event| serde_json::to_string(event).unwrap_or_default())
+    };
+    for index in 0..expected.len().max(actual.len()) {
+        let expected = json(expected.get(index));
+        let actual = json(actual.get(index));
+        if expected != actual {
+            return Err(ReplayMismatch::Events {
+                tick,
+                index,
+                expected,
+                actual,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The state's [`GameState::stable_hash`].
+pub fn state_hash(state: &GameState) -> u64 {
+    state.stable_hash()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{base_content, base_state, test_ship_id};
+    use crate::{Command, CommandId, TaskKind};
+
+    fn record(content: &GameContent, ticks: u64) -> RunRecording {
+        let mut state = base_state(content);
+        let mut recording = RunRecording::new(42, state.clone());
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let ship_id = test_ship_id();
+        let site = state.scan_sites[0].id.clone();
+        let survey = CommandEnvelope {
+            id: CommandId(0),
+            issued_by: state.ships[&ship_id].owner.clone(),
+            issued_tick: 0,
+            execute_at_tick: 0,
+            command: Command::AssignShipTask {
+                ship_id,
+                task_kind: TaskKind::Survey { site },
+            },
+        };
+        for i in 0..ticks {
+            let commands = if i == 0 { vec![survey.clone()] } else { vec![] };
+            let events = crate::tick(&mut state, &commands, content, &mut rng, None);
+            recording.push_tick(&commands, &events, &state);
+        }
+        recording
+    }
+
+    #[test]
+    fn recorded_run_replays_cleanly() {
+        let content = base_content();
+        let recording = record(&content, 30);
+
+        assert_eq!(verify(&recording, &content), Ok(30));
+    }
+
+    #[test]
+    fn dropped_command_diverges_at_its_tick() {
+        let content = base_content();
+        let mut recording = record(&content, 30);
+        recording.ticks[0].commands.clear();
+
+        let err = verify(&recording, &content).unwrap_err();
+        assert!(
+            matches!(err, ReplayMismatch::Events { tick: 0, .. }),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn tampered_state_hash_is_reported() {
+        let content = base_content();
+        let mut recording = record(&content, 5);
+        recording.ticks[3].state_hash ^= 1;
+
+        let err = verify(&recording, &content).unwrap_err();
+        assert!(
+            matches!(err, ReplayMismatch::StateHash { tick: 3, .. }),
+            "{err}"
+        );
+    }
+}
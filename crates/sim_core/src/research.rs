@@ -40,11 +40,62 @@ fn requirements_met(tech_def: &crate::TechDef, progress: Option<&crate::DomainPr
         })
 }
 
+/// The first tech in the research queue a lab of `domain` can work on:
+/// prereqs met, not unlocked, and still short of its `domain` requirement.
+pub(crate) fn queued_target<'a>(
+    research: &'a crate::ResearchState,
+    content: &GameContent,
+    domain: &crate::ResearchDomain,
+) -> Option<&'a TechId> {
+    research.queue.iter().find(|tech_id| {
+        if research.unlocked.contains(*tech_id) {
+            return false;
+        }
+        let Some(tech) = content.techs.iter().find(|t| t.id == **tech_id) else {
+            return false;
+        };
+        let Some(required) = tech.domain_requirements.get(domain) else {
+            return false;
+        };
+        let accumulated = research
+            .evidence
+            .get(*tech_id)
+            .and_then(|progress| progress.points.get(domain))
+            .copied()
+            .unwrap_or(0.0);
+        accumulated < *required
+            && tech
+                .prereqs
+                .iter()
+                .all(|prereq| research.unlocked.contains(prereq))
+    })
+}
+
+/// Replace the research queue, keeping the first occurrence of each known,
+/// still-locked tech.
+pub(crate) fn handle_set_research_queue(
+    state: &mut GameState,
+    content: &GameContent,
+    tech_ids: &[TechId],
+) {
+    let mut queue: Vec<TechId> = Vec::with_capacity(tech_ids.len());
+    for tech_id in tech_ids {
+        let known = content.techs.iter().any(|tech| tech.id == *tech_id);
+        if known && !state.research.unlocked.contains(tech_id) && !queue.contains(tech_id) {
+            queue.push(tech_id.clone());
+        }
+    }
+    state.research.queue = queue;
+}
+
 pub(crate) fn advance_research(
     state: &mut GameState,
     content: &GameContent,
     events: &mut Vec<crate::EventEnvelope>,
 ) {
+    if !content.subsystems.enabled(crate::Subsystem::Research) {
+        return;
+    }
     let current_tick = state.meta.tick;
 
     // Collect eligible techs: prereqs met, not yet unlocked. Sort for determinism.
@@ -69,19 +120,7 @@ pub(crate) fn advance_research(
         let progress = state.research.evidence.get(&tech_id);
 
         if requirements_met(tech_def, progress) {
-            state.research.unlocked.insert(tech_id.clone());
-            // Apply stat modifier effects to global modifiers.
-            for effect in &tech_def.effects {
-                if let crate::TechEffect::StatModifier { stat, op, value } = effect {
-                    state.modifiers.add(crate::modifiers::Modifier {
-                        stat: *stat,
-                        op: *op,
-                        value: *value,
-                        source: crate::modifiers::ModifierSource::Tech(tech_id.0.clone()),
-                        condition: None,
-                    });
-                }
-            }
+            grant_tech(state, tech_def);
             events.push(crate::emit(
                 &mut state.counters,
                 current_tick,
@@ -89,6 +128,29 @@ pub(crate) fn advance_research(
             ));
         }
     }
+    let unlocked = &state.research.unlocked;
+    state
+        .research
+        .queue
+        .retain(|tech_id| !unlocked.contains(tech_id));
+}
+
+/// Mark `tech` unlocked and apply its stat modifier effects to the global
+/// modifier set. Emits no event; research unlocks and world setup (for
+/// content `starting_unlocked` techs) both go through here.
+pub fn grant_tech(state: &mut GameState, tech: &crate::TechDef) {
+    state.research.unlocked.insert(tech.id.clone());
+    for effect in &tech.effects {
+        if let crate::TechEffect::StatModifier { stat, op, value } = effect {
+            state.modifiers.add(crate::modifiers::Modifier {
+                stat: *stat,
+                op: *op,
+                value: *value,
+                source: crate::modifiers::ModifierSource::Tech(tech.id.0.clone()),
+                condition: None,
+            });
+        }
+    }
 }
 
 #[cfg(test)]
@@ -127,6 +189,7 @@ mod data_generation_tests {
             data_pool: AHashMap::default(),
             evidence: AHashMap::default(),
             action_counts: AHashMap::default(),
+            queue: Vec::new(),
         };
         let constants = base_content().constants;
 
@@ -412,3 +475,78 @@ mod research_threshold_tests {
             .contains(&TechId("tech_b".to_string())));
     }
 }
+
+#[cfg(test)]
+mod research_queue_tests {
+    use super::*;
+    use crate::test_fixtures::{base_content, base_state};
+    use crate::{ResearchDomain, TechDef};
+    use std::collections::HashMap;
+
+    fn tech(id: &str, prereqs: &[&str]) -> TechDef {
+        TechDef {
+            id: TechId(id.to_string()),
+            name: id.to_string(),
+            tier: 1,
+            prereqs: prereqs.iter().map(|p| TechId((*p).to_string())).collect(),
+            domain_requirements: HashMap::from([(
+                ResearchDomain::new(ResearchDomain::SURVEY),
+                10.0,
+            )]),
+            accepted_data: vec![],
+            effects: vec![],
+        }
+    }
+
+    fn ids(names: &[&str]) -> Vec<TechId> {
+        names.iter().map(|n| TechId((*n).to_string())).collect()
+    }
+
+    #[test]
+    fn set_queue_drops_unknown_unlocked_and_repeated_techs() {
+        let mut content = base_content();
+        content.techs = vec![tech("tech_a", &[]), tech("tech_b", &[])];
+        let mut state = base_state(&content);
+        state.research.unlocked.insert(TechId("tech_a".to_string()));
+
+        handle_set_research_queue(
+            &mut state,
+            &content,
+            &ids(&["tech_b", "tech_missing", "tech_a", "tech_b"]),
+        );
+
+        assert_eq!(state.research.queue, ids(&["tech_b"]));
+    }
+
+    #[test]
+    fn queued_target_skips_techs_with_unmet_prereqs() {
+        let mut content = base_content();
+        content.techs = vec![tech("tech_a", &[]), tech("tech_b", &["tech_a"])];
+        let mut state = base_state(&content);
+        state.research.queue = ids(&["tech_b", "tech_a"]);
+        let survey = ResearchDomain::new(ResearchDomain::SURVEY);
+
+        let target = queued_target(&state.research, &content, &survey);
+        assert_eq!(target, Some(&TechId("tech_a".to_string())));
+        let materials = ResearchDomain::new("Materials");
+        assert_eq!(queued_target(&state.research, &content, &materials), None);
+    }
+
+    #[test]
+    fn unlocked_techs_leave_the_queue() {
+        let mut content = base_content();
+        content.techs = vec![tech("tech_a", &[]), tech("tech_b", &[])];
+        let mut state = base_state(&content);
+        state.research.queue = ids(&["tech_a", "tech_b"]);
+        state.research.evidence.insert(
+            TechId("tech_a".to_string()),
+            crate::DomainProgress {
+                points: HashMap::from([(ResearchDomain::new(ResearchDomain::SURVEY), 10.0)]),
+            },
+        );
+
+        advance_research(&mut state, &content, &mut Vec::new());
+
+        assert_eq!(state.research.queue, ids(&["tech_b"]));
+    }
+}
@@ -485,6 +485,7 @@ mod tests {
                 scan_site_weight: 10,
                 implicit_comm_tier: None,
             }),
+            security: 1.0,
         });
         // Ensure we have at least one template.
         if content.asteroid_templates.is_empty() {
@@ -609,6 +610,7 @@ mod tests {
                 scan_site_weight: 5,
                 implicit_comm_tier: None,
             }),
+            security: 1.0,
         });
     }
 
@@ -656,6 +658,7 @@ mod tests {
                 scan_site_weight: 1,
                 implicit_comm_tier: Some(CommTier::Advanced),
             }),
+            security: 1.0,
         });
         let state = base_state(&content);
         assert_eq!(
@@ -817,12 +820,16 @@ mod tests {
             &mut state,
             &content,
             &station_id,
+            &crate::PrincipalId("principal_autopilot".to_string()),
             &item,
             tick,
             &mut rng,
             &mut events,
         );
-        assert!(!imported, "import should fail in CommTier::None zone");
+        assert!(
+            imported.is_err(),
+            "import should fail in CommTier::None zone"
+        );
 
         // Export should also fail.
         state
@@ -845,7 +852,10 @@ mod tests {
             tick,
             &mut events,
         );
-        assert!(!exported, "export should fail in CommTier::None zone");
+        assert!(
+            exported.is_err(),
+            "export should fail in CommTier::None zone"
+        );
     }
 
     #[test]
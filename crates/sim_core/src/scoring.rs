@@ -397,14 +397,10 @@ fn resolve_signal_source(
         }
 
         // -- Fleet --
-        "fleet_utilization" => {
-            if metrics.fleet_total > 0 {
-                let total = f64::from(metrics.fleet_total);
-                let active = total - f64::from(metrics.fleet_idle);
-                active / total
-            } else {
-                0.0
-            }
+        "fleet_utilization" if metrics.fleet_total > 0 => {
+            let total = f64::from(metrics.fleet_total);
+            let active = total - f64::from(metrics.fleet_idle);
+            active / total
         }
         "ships_constructed" => (state.ships.len() as f64 - 1.0).max(0.0),
         "satellites_active" => f64::from(metrics.satellites_active),
@@ -423,13 +419,8 @@ fn resolve_signal_source(
 
         // -- Efficiency --
         "avg_module_wear" => f64::from(metrics.avg_module_wear),
-        "power_utilization" => {
-            if metrics.power_generated_kw > 0.0 {
-                (f64::from(metrics.power_consumed_kw) / f64::from(metrics.power_generated_kw))
-                    .min(1.0)
-            } else {
-                0.0
-            }
+        "power_utilization" if metrics.power_generated_kw > 0.0 => {
+            (f64::from(metrics.power_consumed_kw) / f64::from(metrics.power_generated_kw)).min(1.0)
         }
         "station_storage_used_pct" => f64::from(metrics.station_storage_used_pct),
         "satellite_utilization" => {
@@ -1130,6 +1121,9 @@ mod tests {
             thruster_count: 3,
             export_revenue_total: 50_000.0,
             export_count: 5,
+            loan_outstanding: 0.0,
+            loan_missed_payments: 0,
+            loan_defaulted: 0,
             power_generated_kw: 10.0,
             power_consumed_kw: 8.0,
             power_deficit_kw: 0.0,
@@ -1145,6 +1139,7 @@ mod tests {
             transfer_count: 0,
             milestones_completed: 0,
             game_phase: 0,
+            power_brownout_kw: 0.0,
         }
     }
 
@@ -1264,9 +1259,15 @@ mod tests {
                 module_type_index: crate::ModuleTypeIndex::default(),
                 module_id_index: std::collections::HashMap::new(),
                 power_budget_cache: crate::PowerBudgetCache::default(),
+                production_windows: std::collections::BTreeMap::new(),
+                life_support_failing: false,
             },
             leaders: Vec::new(),
             frame_id: None,
+            owner: crate::PrincipalId("principal_autopilot".to_string()),
+            expansions: crate::StationExpansions::default(),
+            power_policy: crate::PowerPolicy::default(),
+            history: std::collections::VecDeque::new(),
         }
     }
 
@@ -1524,6 +1525,8 @@ mod tests {
                     module_type_index: crate::ModuleTypeIndex::default(),
                     module_id_index: std::collections::HashMap::new(),
                     power_budget_cache: crate::PowerBudgetCache::default(),
+                    production_windows: std::collections::BTreeMap::new(),
+                    life_support_failing: false,
                 },
                 launch_transits: Vec::new(),
             },
@@ -0,0 +1,45 @@
+//! Child seed derivation.
+//!
+//! Subsystems that need their own random stream derive it from the run seed
+//! and a fixed label instead of drawing from a shared RNG. Adding, removing
+//! or reordering draws in one subsystem then cannot shift the values another
+//! subsystem sees for the same run seed.
+
+/// Deterministically derive a child seed from `parent` and a subsystem
+/// label, e.g. `derive_seed(seed, "worldgen")`.
+///
+/// The label is folded in with FNV-1a and the result mixed with the
+/// `SplitMix64` finalizer, so nearby parents and similar labels still give
+/// unrelated children. The mapping is part of the save/replay contract and
+/// must not change.
+pub fn derive_seed(parent: u64, label: &str) -> u64 {
+    let mut label_hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in label.bytes() {
+        label_hash ^= u64::from(byte);
+        label_hash = label_hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    let mut z = parent ^ label_hash;
+    z = z.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derived_seeds_are_stable_and_label_specific() {
+        assert_eq!(derive_seed(42, "worldgen"), derive_seed(42, "worldgen"));
+        assert_ne!(derive_seed(42, "worldgen"), derive_seed(42, "hazards"));
+        assert_ne!(derive_seed(42, "worldgen"), derive_seed(43, "worldgen"));
+        assert_ne!(derive_seed(42, "worldgen"), 42);
+    }
+
+    #[test]
+    fn derivation_is_frozen() {
+        // Changing this value silently re-rolls every existing seed's world.
+        assert_eq!(derive_seed(42, "worldgen"), 0x1b1b_4fbc_7aa7_ad5d);
+    }
+}
@@ -0,0 +1,55 @@
+//! Ship loss and insurance.
+//!
+//! A ship is lost when a solar storm catches it at a body
+//! (`solar_storm_ship_loss_chance`), when its wear reaches 1.0 on a
+//! completed task, or when a sim event's `DestroyShip` effect hits it. The
+//! ship, its task and its cargo are removed from the state, and
+//! `ship_insurance_payout_fraction` of its value is credited to the
+//! balance. Anything that remembers a ship id (freight runs, autopilot
+//! agents, queued commands) must treat a missing ship as gone.
+
+use crate::{Event, EventEnvelope, GameContent, GameState, ShipId, ShipLossCause, ShipState};
+
+/// A ship's insured value: its hull's `replacement_value` plus the base
+/// price of each fitted module. Cargo is not insured.
+pub fn ship_value(ship: &ShipState, content: &GameContent) -> f64 {
+    let hull_value = content
+        .hulls
+        .get(&ship.hull_id)
+        .map_or(0.0, |hull| hull.replacement_value);
+    let modules_value: f64 = ship
+        .fitted_modules
+        .iter()
+        .filter_map(|fitted| content.pricing.items.get(fitted.module_def_id.0.as_str()))
+        .map(|entry| entry.base_price_per_unit)
+        .sum();
+    hull_value + modules_value
+}
+
+/// Remove `ship_id` from the state, pay out its insurance and emit
+/// `ShipLost`. Returns `false` if the ship was already gone.
+pub(crate) fn lose_ship(
+    state: &mut GameState,
+    ship_id: &ShipId,
+    cause: ShipLossCause,
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+) -> bool {
+    let Some(ship) = state.ships.remove(ship_id) else {
+        return false;
+    };
+    let insurance_payout =
+        ship_value(&ship, content) * content.constants.ship_insurance_payout_fraction.max(0.0);
+    crate::fixed::add_credits(&mut state.balance, insurance_payout);
+    events.push(crate::emit(
+        &mut state.counters,
+        state.meta.tick,
+        Event::ShipLost {
+            ship_id: ship.id,
+            owner: ship.owner,
+            cause,
+            insurance_payout,
+        },
+    ));
+    true
+}
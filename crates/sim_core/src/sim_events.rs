@@ -208,6 +208,8 @@ pub enum EffectDef {
         severity: AlertSeverity,
         message: String,
     },
+    /// Destroy the targeted ship (see `ship_loss`).
+    DestroyShip,
 }
 
 /// An effect that was applied when an event fired (for history/SSE).
@@ -667,6 +669,7 @@ fn apply_single_effect(
             };
             apply_modifier(modifier, effect, target, state, *duration_ticks)
         }
+        EffectDef::DestroyShip => apply_destroy_ship(effect, target, state, content, events),
         EffectDef::TriggerAlert { severity, message } => {
             let alert_id = format!("evt_{}", event_def_id.0);
             events.push(crate::emit(
@@ -783,6 +786,31 @@ fn apply_spawn_scan_site(
     })
 }
 
+fn apply_destroy_ship(
+    effect: &EffectDef,
+    target: &ResolvedTarget,
+    state: &mut GameState,
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+) -> Option<AppliedEffect> {
+    let ResolvedTarget::Ship { ship_id } = target else {
+        return None;
+    };
+    if !crate::ship_loss::lose_ship(
+        state,
+        ship_id,
+        crate::ShipLossCause::SimEvent,
+        content,
+        events,
+    ) {
+        return None;
+    }
+    Some(AppliedEffect {
+        effect: effect.clone(),
+        target: target.clone(),
+    })
+}
+
 fn apply_modifier(
     modifier: crate::modifiers::Modifier,
     effect: &EffectDef,
@@ -913,86 +941,101 @@ fn sweep_expired_effects(state: &mut GameState, events: &mut Vec<EventEnvelope>)
 // ---------------------------------------------------------------------------
 
 /// Validate event definitions for authoring errors.
-/// Panics on invalid definitions (consistent with existing content validation).
+/// Panics on the first invalid definition; content loading collects every
+/// problem with [`event_def_errors`] instead.
 pub fn validate_event_defs(events: &[SimEventDef]) {
+    if let Some(error) = event_def_errors(events).into_iter().next() {
+        panic!("{error}");
+    }
+}
+
+/// Every authoring error in `events`, in definition order.
+pub fn event_def_errors(events: &[SimEventDef]) -> Vec<String> {
+    let mut errors = Vec::new();
     let mut seen_ids = std::collections::HashSet::new();
     for event in events {
         // Unique IDs
-        assert!(
-            seen_ids.insert(&event.id),
-            "duplicate event def id '{}'",
-            event.id.0
-        );
+        if !seen_ids.insert(&event.id) {
+            errors.push(format!("duplicate event def id '{}'", event.id.0));
+        }
 
         // Cooldown must be positive
-        assert!(
-            event.cooldown_ticks > 0,
-            "event '{}' must have cooldown_ticks > 0",
-            event.id.0
-        );
+        if event.cooldown_ticks == 0 {
+            errors.push(format!(
+                "event '{}' must have cooldown_ticks > 0",
+                event.id.0
+            ));
+        }
 
         // Weight modifiers must have non-zero multiplier
         for modifier in &event.weight_modifiers {
-            assert!(
-                modifier.weight_multiplier_pct > 0,
-                "event '{}' has weight_modifier with 0 multiplier",
-                event.id.0
-            );
+            if modifier.weight_multiplier_pct == 0 {
+                errors.push(format!(
+                    "event '{}' has weight_modifier with 0 multiplier",
+                    event.id.0
+                ));
+            }
         }
 
         // Effect-targeting coherence
         for effect in &event.effects {
-            validate_effect_values(effect, &event.id);
-            validate_effect_targeting(effect, &event.targeting, &event.id);
+            errors.extend(effect_value_error(effect, &event.id));
+            errors.extend(effect_targeting_error(effect, &event.targeting, &event.id));
         }
     }
+    errors
 }
 
 /// Validate effect parameter ranges.
-fn validate_effect_values(effect: &EffectDef, event_id: &EventDefId) {
-    if let EffectDef::DamageModule { wear_amount } = effect {
-        assert!(
-            *wear_amount > 0.0 && *wear_amount <= 1.0,
-            "event '{event_id}': DamageModule wear_amount must be in (0.0, 1.0], got {wear_amount}",
-        );
+fn effect_value_error(effect: &EffectDef, event_id: &EventDefId) -> Option<String> {
+    match effect {
+        EffectDef::DamageModule { wear_amount } if !(*wear_amount > 0.0 && *wear_amount <= 1.0) => {
+            Some(format!(
+                "event '{event_id}': DamageModule wear_amount must be in (0.0, 1.0], got {wear_amount}",
+            ))
+        }
+        _ => None,
     }
 }
 
 /// Validate that an effect is compatible with the event's targeting rule.
-fn validate_effect_targeting(effect: &EffectDef, targeting: &TargetingRule, event_id: &EventDefId) {
-    match effect {
-        EffectDef::DamageModule { .. } => {
-            assert!(
-                matches!(
-                    targeting,
-                    TargetingRule::RandomStation | TargetingRule::RandomModule { .. }
-                ),
-                "event '{event_id}': DamageModule requires RandomStation or RandomModule targeting",
-            );
-        }
-        EffectDef::AddInventory { .. } => {
-            assert!(
-                matches!(
-                    targeting,
-                    TargetingRule::RandomStation | TargetingRule::RandomModule { .. }
-                ),
-                "event '{event_id}': AddInventory requires RandomStation or RandomModule targeting",
-            );
-        }
-        EffectDef::SpawnScanSite { .. } => {
-            assert!(
-                matches!(
-                    targeting,
-                    TargetingRule::Global | TargetingRule::Zone { .. }
-                ),
-                "event '{event_id}': SpawnScanSite requires Global or Zone targeting",
-            );
-        }
+fn effect_targeting_error(
+    effect: &EffectDef,
+    targeting: &TargetingRule,
+    event_id: &EventDefId,
+) -> Option<String> {
+    let (ok, requirement) = match effect {
+        EffectDef::DamageModule { .. } => (
+            matches!(
+                targeting,
+                TargetingRule::RandomStation | TargetingRule::RandomModule { .. }
+            ),
+            "DamageModule requires RandomStation or RandomModule targeting",
+        ),
+        EffectDef::AddInventory { .. } => (
+            matches!(
+                targeting,
+                TargetingRule::RandomStation | TargetingRule::RandomModule { .. }
+            ),
+            "AddInventory requires RandomStation or RandomModule targeting",
+        ),
+        EffectDef::SpawnScanSite { .. } => (
+            matches!(
+                targeting,
+                TargetingRule::Global | TargetingRule::Zone { .. }
+            ),
+            "SpawnScanSite requires Global or Zone targeting",
+        ),
+        EffectDef::DestroyShip => (
+            matches!(targeting, TargetingRule::RandomShip),
+            "DestroyShip requires RandomShip targeting",
+        ),
         // These effects work with any targeting — no validation needed
         EffectDef::AddResearchData { .. }
         | EffectDef::ApplyModifier { .. }
-        | EffectDef::TriggerAlert { .. } => {}
-    }
+        | EffectDef::TriggerAlert { .. } => (true, ""),
+    };
+    (!ok).then(|| format!("event '{event_id}': {requirement}"))
 }
 
 // ---------------------------------------------------------------------------
@@ -1894,6 +1937,57 @@ mod tests {
             .any(|e| matches!(&e.event, crate::Event::SimEventExpired { .. })));
     }
 
+    #[test]
+    fn effect_destroy_ship_removes_target_and_pays_insurance() {
+        let mut content = base_content();
+        content.constants.events_enabled = true;
+        content.constants.ship_insurance_payout_fraction = 0.5;
+        content.pricing.items.insert(
+            "module_mining_laser".to_string(),
+            crate::PricingEntry {
+                base_price_per_unit: 400_000.0,
+                importable: true,
+                exportable: true,
+                category: "equipment".to_string(),
+                royalty_per_unit: 0.0,
+                elasticity: 0.0,
+            },
+        );
+        content.events = vec![make_event_def(
+            "evt_pirates",
+            TargetingRule::RandomShip,
+            vec![EffectDef::DestroyShip],
+        )];
+
+        let mut state = base_state(&content);
+        let ship_id = state.ships.keys().next().expect("ship").clone();
+        state
+            .ships
+            .get_mut(&ship_id)
+            .expect("ship")
+            .fitted_modules
+            .push(crate::FittedModule {
+                slot_index: 0,
+                module_def_id: crate::ModuleDefId("module_mining_laser".to_string()),
+            });
+        let balance_before = state.balance;
+        let mut rng = make_rng();
+        let mut events = Vec::new();
+
+        evaluate_events(&mut state, &content, &mut rng, &mut events);
+
+        assert!(!state.ships.contains_key(&ship_id));
+        assert!((state.balance - balance_before - 200_000.0).abs() < 1e-6);
+        assert!(events.iter().any(|e| matches!(
+            &e.event,
+            crate::Event::ShipLost {
+                ship_id: lost,
+                cause: crate::ShipLossCause::SimEvent,
+                ..
+            } if *lost == ship_id
+        )));
+    }
+
     #[test]
     fn effect_add_inventory_components() {
         let mut content = base_content();
@@ -20,6 +20,9 @@ pub const METERS_PER_AU: u64 = 149_597_870_700;
 /// Meters in one micro-AU.
 pub const METERS_PER_MICRO_AU: f64 = 149_597.870_7;
 
+/// Micro-AU (the unit of positions and distances) in one AU.
+pub const MICRO_AU_PER_AU: u64 = 1_000_000;
+
 /// Full circle in milli-degrees (360° = 360,000 m°).
 pub const FULL_CIRCLE: u32 = 360_000;
 
@@ -171,7 +174,7 @@ pub fn travel_ticks(
     min_transit: u64,
 ) -> u64 {
     let dist = from.distance(to);
-    let ticks = dist * ticks_per_au / 1_000_000;
+    let ticks = dist * ticks_per_au / MICRO_AU_PER_AU;
     ticks.max(min_transit)
 }
 
@@ -717,6 +720,7 @@ mod tests {
             angle_mdeg: angle,
             solar_intensity: 1.0,
             zone: None,
+            security: 1.0,
         }
     }
 
@@ -972,6 +976,7 @@ mod tests {
                 scan_site_weight: 1,
                 implicit_comm_tier: None,
             }),
+            security: 1.0,
         };
         let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(42);
         let pos = random_position_in_zone(&body, &mut rng);
@@ -1000,6 +1005,7 @@ mod tests {
                 scan_site_weight: 9,
                 implicit_comm_tier: None,
             }),
+            security: 1.0,
         };
         let light = OrbitalBodyDef {
             id: BodyId("light".to_string()),
@@ -1018,6 +1024,7 @@ mod tests {
                 scan_site_weight: 1,
                 implicit_comm_tier: None,
             }),
+            security: 1.0,
         };
         let zone_bodies: Vec<&OrbitalBodyDef> = vec![&heavy, &light];
         let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(42);
@@ -1096,6 +1103,7 @@ mod tests {
                 scan_site_weight: 1,
                 implicit_comm_tier: None,
             }),
+            security: 1.0,
         };
         let mut rng1 = rand_chacha::ChaCha8Rng::seed_from_u64(99);
         let mut rng2 = rand_chacha::ChaCha8Rng::seed_from_u64(99);
@@ -0,0 +1,407 @@
+//! Volatile loss from ore lots and quality decay of material lots.
+//!
+//! Elements flagged `volatile` in content evaporate out of ore at
+//! `constants.volatile_ore_loss_per_day`. Ore in ship holds always spoils;
+//! ore at a station is safe while the station has an enabled refrigerated
+//! storage module. Only the volatile share of a lot is lost, so the lot's
+//! composition shifts towards its stable elements as it ages.
+//!
+//! Material lots whose element sets `quality_decay_per_day` lose that
+//! fraction of their quality per day, wherever they are held. A station with
+//! an enabled climate-controlled storage module slows this to
+//! `constants.climate_controlled_decay_mult` of the rate. Decayed lots no
+//! longer match fresh output of the same element, so they stay separate lots.
+
+use std::collections::HashMap;
+
+use crate::{
+    boiloff_rate_per_tick, Event, EventEnvelope, GameContent, GameState, InventoryItem,
+    ModuleBehaviorDef, ShipId, StationId, StationState,
+};
+
+/// Apply one tick of volatile loss to every unprotected ore lot.
+pub(crate) fn tick_ore_spoilage(
+    state: &mut GameState,
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let rate_per_day = content.constants.volatile_ore_loss_per_day;
+    if rate_per_day <= 0.0 || !content.elements.iter().any(|e| e.volatile) {
+        return;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let rate = boiloff_rate_per_tick(rate_per_day, content.constants.minutes_per_tick) as f32;
+    let current_tick = state.meta.tick;
+    let mut losses: Vec<(Option<ShipId>, Option<StationId>, f32)> = Vec::new();
+
+    for ship in state.ships.values_mut() {
+        let lost = spoil_inventory(&mut ship.inventory, rate, content);
+        if lost > 0.0 {
+            losses.push((Some(ship.id.clone()), None, lost));
+        }
+    }
+    for station in state.stations.values_mut() {
+        if is_refrigerated(station, content) {
+            continue;
+        }
+        let lost = spoil_inventory(&mut station.core.inventory, rate, content);
+        if lost > 0.0 {
+            station.invalidate_volume_cache();
+            losses.push((None, Some(station.id.clone()), lost));
+        }
+    }
+
+    for (ship_id, station_id, kg_lost) in losses {
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            Event::OreSpoiled {
+                ship_id,
+                station_id,
+                kg_lost,
+            },
+        ));
+    }
+}
+
+/// Apply one tick of quality decay to every material lot with a decaying
+/// element.
+pub(crate) fn tick_material_decay(state: &mut GameState, content: &GameContent) {
+    let minutes_per_tick = content.constants.minutes_per_tick;
+    let rates: HashMap<&str, f64> = content
+        .elements
+        .iter()
+        .filter_map(|e| {
+            let rate_per_day = e.quality_decay_per_day.filter(|rate| *rate > 0.0)?;
+            Some((
+                e.id.as_str(),
+                boiloff_rate_per_tick(rate_per_day, minutes_per_tick),
+            ))
+        })
+        .collect();
+    if rates.is_empty() {
+        return;
+    }
+    for ship in state.ships.values_mut() {
+        decay_inventory(&mut ship.inventory, &rates, 1.0);
+    }
+    let climate_mult = content.constants.climate_controlled_decay_mult;
+    for station in state.stations.values_mut() {
+        let mult = if is_climate_controlled(station, content) {
+            climate_mult
+        } else {
+            1.0
+        };
+        decay_inventory(&mut station.core.inventory, &rates, mult);
+    }
+}
+
+fn decay_inventory(inventory: &mut [InventoryItem], rates: &HashMap<&str, f64>, mult: f64) {
+    for item in inventory {
+        let InventoryItem::Material {
+            element, quality, ..
+        } = item
+        else {
+            continue;
+        };
+        let Some(rate) = rates.get(element.as_str()) else {
+            continue;
+        };
+        #[allow(clippy::cast_possible_truncation)]
+        let kept = (1.0 - rate * mult).clamp(0.0, 1.0) as f32;
+        *quality *= kept;
+    }
+}
+
+/// True when the station has an enabled refrigerated storage module.
+fn is_refrigerated(station: &StationState, content: &GameContent) -> bool {
+    has_enabled_storage(station, content, |behavior| {
+        matches!(
+            behavior,
+            ModuleBehaviorDef::Storage {
+                refrigerated: true,
+                ..
+            }
+        )
+    })
+}
+
+/// True when the station has an enabled climate-controlled storage module.
+fn is_climate_controlled(station: &StationState, content: &GameContent) -> bool {
+    has_enabled_storage(station, content, |behavior| {
+        matches!(
+            behavior,
+            ModuleBehaviorDef::Storage {
+                climate_controlled: true,
+                ..
+            }
+        )
+    })
+}
+
+fn has_enabled_storage(
+    station: &StationState,
+    content: &GameContent,
+    matches: impl Fn(&ModuleBehaviorDef) -> bool,
+) -> bool {
+    station.core.modules.iter().any(|module| {
+        module.enabled
+            && content
+                .module_defs
+                .get(&module.def_id)
+                .is_some_and(|def| matches(&def.behavior))
+    })
+}
+
+/// Remove the volatile share lost this tick from each ore lot, dropping lots
+/// that fall below `min_meaningful_kg`. Returns the total kg lost.
+fn spoil_inventory(inventory: &mut Vec<InventoryItem>, rate: f32, content: &GameContent) -> f32 {
+    let mut total_lost = 0.0_f32;
+    for item in inventory.iter_mut() {
+        let InventoryItem::Ore {
+            kg, composition, ..
+        } = item
+        else {
+            continue;
+        };
+        let volatile_fraction: f32 = composition
+            .iter()
+            .filter(|(element, _)| is_volatile(element, content))
+            .map(|(_, fraction)| fraction)
+            .sum();
+        let lost = *kg * volatile_fraction * rate;
+        if lost <= 0.0 {
+            continue;
+        }
+        let remaining = (*kg - lost).max(0.0);
+        if remaining > 0.0 {
+            let scale = *kg / remaining;
+            for (element, fraction) in composition.iter_mut() {
+                let kept = if is_volatile(element, content) {
+                    1.0 - rate
+                } else {
+                    1.0
+                };
+                *fraction *= kept * scale;
+            }
+        }
+        crate::fixed::add_kg(kg, remaining - *kg);
+        total_lost += lost;
+    }
+    let min_kg = content.constants.min_meaningful_kg;
+    inventory.retain(|item| match item {
+        InventoryItem::Ore { kg, .. } => *kg >= min_kg,
+        _ => true,
+    });
+    total_lost
+}
+
+fn is_volatile(element: &str, content: &GameContent) -> bool {
+    content
+        .elements
+        .iter()
+        .any(|e| e.id == element && e.volatile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{base_content, base_state, make_rng, test_module, ModuleDefBuilder};
+    use crate::{tick, AsteroidId, LotId, ModuleKindState};
+
+    fn volatile_content() -> GameContent {
+        let mut content = base_content();
+        content.constants.volatile_ore_loss_per_day = 0.5;
+        for element in &mut content.elements {
+            if element.id == "H2O" {
+                element.volatile = true;
+            }
+        }
+        content
+    }
+
+    fn wet_ore(kg: f32) -> InventoryItem {
+        InventoryItem::Ore {
+            lot_id: LotId("lot_wet".to_string()),
+            asteroid_id: AsteroidId("asteroid_wet".to_string()),
+            kg,
+            composition: HashMap::from([("Fe".to_string(), 0.5), ("H2O".to_string(), 0.5)]),
+        }
+    }
+
+    fn station_ore(state: &GameState) -> (f32, f32) {
+        let station = state.stations.values().next().unwrap();
+        station
+            .core
+            .inventory
+            .iter()
+            .find_map(|item| match item {
+                InventoryItem::Ore {
+                    kg, composition, ..
+                } => Some((*kg, composition["Fe"] * *kg)),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn volatile_share_evaporates_from_station_ore() {
+        let content = volatile_content();
+        let mut state = base_state(&content);
+        let station = state.stations.values_mut().next().unwrap();
+        station.core.inventory.push(wet_ore(1_000.0));
+        let mut rng = make_rng();
+
+        let events = tick(&mut state, &[], &content, &mut rng, None);
+
+        let (kg, fe_kg) = station_ore(&state);
+        assert!(kg < 1_000.0, "wet ore should lose mass: {kg}");
+        assert!(
+            (fe_kg - 500.0).abs() < 1e-2,
+            "iron is not volatile: {fe_kg}"
+        );
+        assert!(events
+            .iter()
+            .any(|e| matches!(e.event, Event::OreSpoiled { .. })));
+    }
+
+    #[test]
+    fn refrigerated_storage_protects_station_ore() {
+        let mut content = volatile_content();
+        let def = ModuleDefBuilder::new("module_cold_storage")
+            .behavior(ModuleBehaviorDef::Storage {
+                capacity_m3: 50.0,
+                refrigerated: true,
+                climate_controlled: false,
+            })
+            .build();
+        content.module_defs.insert(def.id.clone(), def);
+        let mut state = base_state(&content);
+        let station = state.stations.values_mut().next().unwrap();
+        station.core.inventory.push(wet_ore(1_000.0));
+        station
+            .core
+            .modules
+            .push(test_module("module_cold_storage", ModuleKindState::Storage));
+        let mut rng = make_rng();
+
+        tick(&mut state, &[], &content, &mut rng, None);
+
+        let (kg, _) = station_ore(&state);
+        assert!((kg - 1_000.0).abs() < f32::EPSILON, "ore should keep: {kg}");
+    }
+
+    #[test]
+    fn ship_hold_ore_spoils() {
+        let content = volatile_content();
+        let mut state = base_state(&content);
+        let ship = state.ships.values_mut().next().unwrap();
+        ship.inventory.push(wet_ore(1_000.0));
+
+        let mut events = Vec::new();
+        tick_ore_spoilage(&mut state, &content, &mut events);
+
+        let ship = state.ships.values().next().unwrap();
+        let Some(InventoryItem::Ore {
+            kg, composition, ..
+        }) = ship.inventory.first()
+        else {
+            panic!("ore lot should remain");
+        };
+        assert!(*kg < 1_000.0);
+        assert!(composition["H2O"] < 0.5, "volatile share should fall");
+        let total: f32 = composition.values().sum();
+        assert!((total - 1.0).abs() < 1e-5, "composition stays normalised");
+    }
+
+    fn decaying_content() -> GameContent {
+        let mut content = base_content();
+        for element in &mut content.elements {
+            if element.id == "Fe" {
+                element.quality_decay_per_day = Some(0.5);
+            }
+        }
+        content
+    }
+
+    fn iron(quality: f32) -> InventoryItem {
+        InventoryItem::Material {
+            element: "Fe".to_string(),
+            kg: 100.0,
+            quality,
+            thermal: None,
+        }
+    }
+
+    fn station_iron_quality(state: &GameState) -> f32 {
+        let station = state.stations.values().next().unwrap();
+        station
+            .core
+            .inventory
+            .iter()
+            .find_map(|item| match item {
+                InventoryItem::Material { quality, .. } => Some(*quality),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn material_quality_decays_in_storage() {
+        let content = decaying_content();
+        let mut state = base_state(&content);
+        let station = state.stations.values_mut().next().unwrap();
+        station.core.inventory.push(iron(0.9));
+
+        for _ in 0..10 {
+            tick_material_decay(&mut state, &content);
+        }
+
+        let quality = station_iron_quality(&state);
+        assert!(quality < 0.9, "iron quality should fall: {quality}");
+        assert!(quality > 0.0);
+    }
+
+    #[test]
+    fn climate_controlled_storage_slows_decay() {
+        let mut content = decaying_content();
+        let def = ModuleDefBuilder::new("module_climate_store")
+            .behavior(ModuleBehaviorDef::Storage {
+                capacity_m3: 50.0,
+                refrigerated: false,
+                climate_controlled: true,
+            })
+            .build();
+        content.module_defs.insert(def.id.clone(), def);
+        let mut open = base_state(&content);
+        open.stations
+            .values_mut()
+            .next()
+            .unwrap()
+            .core
+            .inventory
+            .push(iron(0.9));
+        let mut controlled = open.clone();
+        controlled
+            .stations
+            .values_mut()
+            .next()
+            .unwrap()
+            .core
+            .modules
+            .push(test_module(
+                "module_climate_store",
+                ModuleKindState::Storage,
+            ));
+
+        tick_material_decay(&mut open, &content);
+        tick_material_decay(&mut controlled, &content);
+
+        let open_loss = 0.9 - station_iron_quality(&open);
+        let controlled_loss = 0.9 - station_iron_quality(&controlled);
+        assert!(
+            (controlled_loss - open_loss * 0.25).abs() < 1e-5,
+            "open {open_loss}, controlled {controlled_loss}"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Canonical state hashing for determinism checks.
+//!
+//! [`GameState::stable_hash`] hashes a canonical form of the serialized
+//! state: object keys in sorted order, set-valued fields sorted, and floats
+//! quantized to [`FLOAT_QUANTUM`] so that two runs agreeing to that
+//! precision hash the same. `sim_cli run --hash-every`, `sim_daemon run
+//! --hash-every` and the bench scenario's `hash_every` write these hashes
+//! to `state_hashes.csv` for comparing runs tick by tick.
+
+use serde_json::Value;
+
+use crate::GameState;
+
+/// Floats are rounded to a multiple of this before hashing.
+const FLOAT_QUANTUM: f64 = 1e-6;
+
+/// JSON paths of fields serialized from hash sets, whose element order is
+/// arbitrary.
+const UNORDERED_ARRAYS: &[&[&str]] = &[&["research", "unlocked"]];
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+impl GameState {
+    /// 64-bit FNV-1a over the canonical form of this state. Fields that are
+    /// not serialized (caches, diagnostics) do not contribute.
+    pub fn stable_hash(&self) -> u64 {
+        let mut value = serde_json::to_value(self).unwrap_or(Value::Null);
+        for path in UNORDERED_ARRAYS {
+            if let Some(Value::Array(items)) = path
+                .iter()
+                .try_fold(&mut value, |node, key| node.get_mut(*key))
+            {
+                items.sort_by_cached_key(Value::to_string);
+            }
+        }
+        let mut hasher = Fnv(FNV_OFFSET);
+        hasher.value(&value);
+        hasher.0
+    }
+}
+
+struct Fnv(u64);
+
+impl Fnv {
+    fn bytes(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn len(&mut self, len: usize) {
+        self.bytes(&(len as u64).to_le_bytes());
+    }
+
+    /// Each value is tagged with its kind and containers with their length,
+    /// so distinct structures cannot produce the same byte stream.
+    fn value(&mut self, value: &Value) {
+        match value {
+            Value::Null => self.bytes(&[0]),
+            Value::Bool(flag) => self.bytes(&[1, u8::from(*flag)]),
+            Value::Number(number) => {
+                if let Some(int) = number.as_i64() {
+                    self.bytes(&[2]);
+                    self.bytes(&int.to_le_bytes());
+                } else if let Some(uint) = number.as_u64() {
+                    self.bytes(&[3]);
+                    self.bytes(&uint.to_le_bytes());
+                } else {
+                    self.bytes(&[4]);
+                    self.bytes(&quantize(number.as_f64().unwrap_or(0.0)).to_le_bytes());
+                }
+            }
+            Value::String(text) => {
+                self.bytes(&[5]);
+                self.len(text.len());
+                self.bytes(text.as_bytes());
+            }
+            Value::Array(items) => {
+                self.bytes(&[6]);
+                self.len(items.len());
+                for item in items {
+                    self.value(item);
+                }
+            }
+            Value::Object(map) => {
+                // Sorted explicitly: `serde_json::Map` keeps insertion order
+                // if any crate in the build enables `preserve_order`.
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_unstable_by_key(|(key, _)| key.as_str());
+                self.bytes(&[7]);
+                self.len(entries.len());
+                for (key, item) in entries {
+                    self.len(key.len());
+                    self.bytes(key.as_bytes());
+                    self.value(item);
+                }
+            }
+        }
+    }
+}
+
+/// Out-of-range values saturate; NaN maps to zero.
+#[allow(clippy::cast_possible_truncation)]
+fn quantize(value: f64) -> i64 {
+    (value / FLOAT_QUANTUM).round() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_fixtures::{base_content, base_state};
+    use crate::TechId;
+
+    #[test]
+    fn equal_states_hash_equal() {
+        let content = base_content();
+        let state = base_state(&content);
+
+        assert_eq!(state.stable_hash(), state.clone().stable_hash());
+    }
+
+    #[test]
+    fn float_noise_below_quantum_is_ignored() {
+        let content = base_content();
+        let mut state = base_state(&content);
+        state.balance = 0.5;
+        let mut nudged = state.clone();
+        nudged.balance += 1e-9;
+        let mut changed = state.clone();
+        changed.balance += 1.0;
+
+        assert_eq!(state.stable_hash(), nudged.stable_hash());
+        assert_ne!(state.stable_hash(), changed.stable_hash());
+    }
+
+    #[test]
+    fn unlocked_tech_order_does_not_matter() {
+        let content = base_content();
+        let mut state = base_state(&content);
+        let techs: Vec<TechId> = (0..16).map(|i| TechId(format!("tech_{i}"))).collect();
+        state.research.unlocked = techs.iter().cloned().collect();
+        let mut reversed = state.clone();
+        reversed.research.unlocked = techs.iter().rev().cloned().collect();
+
+        assert_eq!(state.stable_hash(), reversed.stable_hash());
+    }
+}
@@ -1,8 +1,7 @@
 use crate::tasks::{ship_construction_enabled, ship_construction_tech_id};
 use crate::{
     Event, EventEnvelope, GameContent, GameState, InputAmount, InputFilter, InventoryItem,
-    ModuleBehaviorDef, OutputSpec, PrincipalId, QualityFormula, RecipeDef, ShipId, ShipState,
-    StationId,
+    ModuleBehaviorDef, OutputSpec, QualityFormula, RecipeDef, ShipId, ShipState, StationId,
 };
 
 pub(super) fn tick_assembler_modules(
@@ -28,7 +27,7 @@ pub(super) fn tick_assembler_modules(
     }
 
     for &module_idx in scratch.iter() {
-        let Some(ctx) = super::extract_context(state, station_id, module_idx, content) else {
+        let Some(mut ctx) = super::extract_context(state, station_id, module_idx, content) else {
             continue;
         };
 
@@ -38,12 +37,38 @@ pub(super) fn tick_assembler_modules(
             continue;
         };
 
-        if !super::should_run(state, &ctx) {
-            continue;
+        sync_extra_slots(state, &ctx, &assembler_def);
+        // Slots are evaluated every tick: idle slots try to start a job and
+        // busy slots advance theirs. The def's interval is the job's length.
+        ctx.interval = 1;
+        // Slots run in index order, so earlier slots get first claim on inputs.
+        for slot in 0..assembler_def.parallel_slots.max(1) {
+            ctx.slot = slot;
+            if !super::should_run(state, &ctx) {
+                continue;
+            }
+            let outcome = execute(&ctx, &assembler_def, state, content, rng, events);
+            super::apply_run_result(state, &ctx, outcome, content, events);
         }
+    }
+}
 
-        let outcome = execute(&ctx, &assembler_def, state, content, rng, events);
-        super::apply_run_result(state, &ctx, outcome, content, events);
+/// Grow or shrink the assembler's extra slot list to match the def's
+/// `parallel_slots` (e.g. after a content change).
+fn sync_extra_slots(
+    state: &mut GameState,
+    ctx: &super::ModuleTickContext,
+    assembler_def: &crate::AssemblerDef,
+) {
+    let Some(station) = state.stations.get_mut(&ctx.station_id) else {
+        return;
+    };
+    if let crate::ModuleKindState::Assembler(asmb) =
+        &mut station.core.modules[ctx.module_idx].kind_state
+    {
+        let extra = assembler_def.parallel_slots.saturating_sub(1) as usize;
+        asmb.extra_slots
+            .resize_with(extra, crate::AssemblerSlotState::default);
     }
 }
 
@@ -57,13 +82,17 @@ fn execute(
     rng: &mut impl rand::Rng,
     events: &mut Vec<EventEnvelope>,
 ) -> super::RunOutcome {
+    if has_job(ctx, state) {
+        return advance_job(ctx, assembler_def, state, content, rng, events);
+    }
+
     // Phase 1: resolve which recipe to run.
     let Some(recipe) = resolve_recipe(ctx, assembler_def, state, content, events) else {
         return super::RunOutcome::Skipped { reset_timer: true };
     };
 
     // Phase 2: recipe-required tech gate.
-    if let Some(outcome) = check_recipe_tech_gate(ctx, assembler_def, recipe, state, events) {
+    if let Some(outcome) = check_recipe_tech_gate(ctx, recipe, state, events) {
         return outcome;
     }
 
@@ -82,9 +111,7 @@ fn execute(
     // Phase 4: ship-construction tech gate (runs after inventory checks so
     // insufficient inputs / stock caps take precedence, matching original
     // execution order).
-    if let Some(outcome) =
-        check_ship_construction_gate(ctx, assembler_def, recipe, state, content, events)
-    {
+    if let Some(outcome) = check_ship_construction_gate(ctx, recipe, state, content, events) {
         return outcome;
     }
 
@@ -104,8 +131,113 @@ fn execute(
         });
     }
 
-    // Phase 6: all checks passed — execute the assembler run.
-    resolve_assembler_run(ctx, state, recipe, content, rng, events);
+    // Phase 6: all checks passed — consume inputs and start the job.
+    if !start_job(ctx, state, recipe, content, events) {
+        return super::RunOutcome::Skipped { reset_timer: true };
+    }
+    advance_job(ctx, assembler_def, state, content, rng, events)
+}
+
+/// True iff the context's slot has a job in progress.
+fn has_job(ctx: &super::ModuleTickContext, state: &mut GameState) -> bool {
+    job_slot(ctx, state).is_some_and(|job| job.is_some())
+}
+
+fn job_slot<'s>(
+    ctx: &super::ModuleTickContext,
+    state: &'s mut GameState,
+) -> Option<&'s mut Option<crate::AssemblerJob>> {
+    let station = state.stations.get_mut(&ctx.station_id)?;
+    match &mut station.core.modules[ctx.module_idx].kind_state {
+        crate::ModuleKindState::Assembler(asmb) => asmb.slot_job_mut(ctx.slot),
+        _ => None,
+    }
+}
+
+/// Consume the recipe's inputs and record a job on the slot. Returns `false`
+/// if nothing could be consumed.
+fn start_job(
+    ctx: &super::ModuleTickContext,
+    state: &mut GameState,
+    recipe: &RecipeDef,
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+) -> bool {
+    let Some((consumed, any)) = consume_recipe_inputs(ctx, state, recipe, content) else {
+        return false;
+    };
+    if !any {
+        return false;
+    }
+    if let Some(station) = state.stations.get_mut(&ctx.station_id) {
+        station.invalidate_volume_cache();
+        if let crate::ModuleKindState::Assembler(asmb) =
+            &mut station.core.modules[ctx.module_idx].kind_state
+        {
+            // The timer counts ticks the job has been in progress.
+            if let Some(timer) = asmb.slot_ticks_mut(ctx.slot) {
+                *timer = 1;
+            }
+            if let Some(job) = asmb.slot_job_mut(ctx.slot) {
+                *job = Some(crate::AssemblerJob {
+                    recipe_id: recipe.id.clone(),
+                    progress: 0.0,
+                    material_element: consumed.element,
+                    material_consumed_kg: consumed.kg,
+                });
+            }
+            if let Some(entry) = asmb
+                .queue
+                .iter_mut()
+                .find(|entry| entry.started < entry.count)
+                .filter(|entry| entry.recipe_id == recipe.id)
+            {
+                entry.started += 1;
+            }
+        }
+    }
+    super::handle_resume_if_stalled(state, ctx, events);
+    true
+}
+
+/// Add this tick's work to the slot's job and produce its output once the
+/// assembly interval is reached. Jobs are left untouched on ticks the module
+/// is disabled or power-stalled, so they resume where they stopped.
+fn advance_job(
+    ctx: &super::ModuleTickContext,
+    assembler_def: &crate::AssemblerDef,
+    state: &mut GameState,
+    content: &GameContent,
+    rng: &mut impl rand::Rng,
+    events: &mut Vec<EventEnvelope>,
+) -> super::RunOutcome {
+    #[allow(clippy::cast_precision_loss)] // interval ticks are small
+    let duration = assembler_def.assembly_interval_ticks as f32;
+    let Some(slot) = job_slot(ctx, state) else {
+        return super::RunOutcome::Skipped { reset_timer: true };
+    };
+    let Some(job) = slot.as_mut() else {
+        return super::RunOutcome::Skipped { reset_timer: true };
+    };
+    job.progress += ctx.efficiency;
+    if job.progress < duration {
+        return super::RunOutcome::Skipped { reset_timer: false };
+    }
+    let Some(job) = slot.take() else {
+        return super::RunOutcome::Skipped { reset_timer: true };
+    };
+
+    // A recipe removed from content mid-job forfeits its inputs.
+    let Some(recipe) = content.recipes.get(&job.recipe_id) else {
+        return super::RunOutcome::Skipped { reset_timer: true };
+    };
+    let consumed = ConsumedMaterial {
+        element: job.material_element,
+        kg: job.material_consumed_kg,
+    };
+    produce_recipe_outputs(ctx, state, recipe, content, rng, events, &consumed);
+    generate_assembly_research_data(state, recipe, content);
+    record_queue_completion(ctx, state, &job.recipe_id, events);
     super::RunOutcome::Completed
 }
 
@@ -119,13 +251,19 @@ fn resolve_recipe<'c>(
     content: &'c GameContent,
     events: &mut Vec<EventEnvelope>,
 ) -> Option<&'c RecipeDef> {
-    let selected = {
+    let (selected, queued) = {
         let station = state.stations.get(&ctx.station_id)?;
         match &station.core.modules[ctx.module_idx].kind_state {
-            crate::ModuleKindState::Assembler(asmb) => asmb.selected_recipe.clone(),
+            crate::ModuleKindState::Assembler(asmb) => (
+                asmb.selected_recipe.clone(),
+                next_queued(&asmb.queue).map(|entry| entry.recipe_id.clone()),
+            ),
             _ => return None,
         }
     };
+    if let Some(recipe_id) = queued.filter(|id| assembler_def.recipes.contains(id)) {
+        return content.recipes.get(&recipe_id);
+    }
 
     // Recipe fallback: if selected recipe is not in assembler's recipe list, reset it.
     let recipe_id = if let Some(ref sel_id) = selected {
@@ -163,12 +301,59 @@ fn resolve_recipe<'c>(
     recipe_id.and_then(|id| content.recipes.get(id))
 }
 
+/// The first queued order with runs left to start.
+fn next_queued(queue: &[crate::AssemblerQueueEntry]) -> Option<&crate::AssemblerQueueEntry> {
+    queue.iter().find(|entry| entry.started < entry.count)
+}
+
+/// Credit a finished run of `recipe_id` to the oldest order waiting on
+/// one, dropping the order with `AssemblerJobCompleted` once all its runs
+/// are done.
+fn record_queue_completion(
+    ctx: &super::ModuleTickContext,
+    state: &mut GameState,
+    recipe_id: &crate::RecipeId,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let Some(station) = state.stations.get_mut(&ctx.station_id) else {
+        return;
+    };
+    let crate::ModuleKindState::Assembler(asmb) =
+        &mut station.core.modules[ctx.module_idx].kind_state
+    else {
+        return;
+    };
+    let Some(pos) = asmb
+        .queue
+        .iter()
+        .position(|entry| entry.recipe_id == *recipe_id && entry.completed < entry.started)
+    else {
+        return;
+    };
+    let entry = &mut asmb.queue[pos];
+    entry.completed += 1;
+    if entry.completed < entry.count {
+        return;
+    }
+    let entry = asmb.queue.remove(pos);
+    let current_tick = state.meta.tick;
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::AssemblerJobCompleted {
+            station_id: ctx.station_id.clone(),
+            module_id: ctx.module_id.clone(),
+            recipe_id: entry.recipe_id,
+            count: entry.count,
+        },
+    ));
+}
+
 /// Check the recipe's `required_tech` gate. Returns `Some(outcome)` if the
 /// gate blocks execution (emitting `ModuleAwaitingTech` at the first trigger),
 /// or `None` if the gate is passed.
 fn check_recipe_tech_gate(
     ctx: &super::ModuleTickContext,
-    assembler_def: &crate::AssemblerDef,
     recipe: &RecipeDef,
     state: &mut GameState,
     events: &mut Vec<EventEnvelope>,
@@ -177,7 +362,7 @@ fn check_recipe_tech_gate(
     if state.research.unlocked.iter().any(|t| t == required_tech) {
         return None;
     }
-    if is_first_interval_trigger(ctx, assembler_def, state) {
+    if is_first_interval_trigger(ctx, state) {
         let current_tick = state.meta.tick;
         events.push(crate::emit(
             &mut state.counters,
@@ -195,7 +380,6 @@ fn check_recipe_tech_gate(
 /// Check the ship-construction tech gate if the recipe has a ship output.
 fn check_ship_construction_gate(
     ctx: &super::ModuleTickContext,
-    assembler_def: &crate::AssemblerDef,
     recipe: &RecipeDef,
     state: &mut GameState,
     content: &GameContent,
@@ -209,7 +393,7 @@ fn check_ship_construction_gate(
         return None;
     }
     // Only emit ModuleAwaitingTech once — when timer first reaches the interval.
-    if is_first_interval_trigger(ctx, assembler_def, state) {
+    if is_first_interval_trigger(ctx, state) {
         if let Some(tech_id) = ship_construction_tech_id(content) {
             let current_tick = state.meta.tick;
             events.push(crate::emit(
@@ -230,17 +414,20 @@ fn check_ship_construction_gate(
 /// True iff this is the first tick the timer reached the assembly interval.
 /// `should_run()` incremented the timer; if it equals exactly the interval,
 /// this is the first trigger.
-fn is_first_interval_trigger(
-    ctx: &super::ModuleTickContext,
-    assembler_def: &crate::AssemblerDef,
-    state: &GameState,
-) -> bool {
+fn is_first_interval_trigger(ctx: &super::ModuleTickContext, state: &GameState) -> bool {
     let Some(station) = state.stations.get(&ctx.station_id) else {
         return false;
     };
     match &station.core.modules[ctx.module_idx].kind_state {
         crate::ModuleKindState::Assembler(asmb) => {
-            asmb.ticks_since_last_run == assembler_def.assembly_interval_ticks
+            let ticks = match ctx.slot {
+                0 => Some(asmb.ticks_since_last_run),
+                slot => asmb
+                    .extra_slots
+                    .get(slot as usize - 1)
+                    .map(|s| s.ticks_since_last_run),
+            };
+            ticks == Some(ctx.interval)
         }
         _ => false,
     }
@@ -374,32 +561,14 @@ fn estimate_output_volume(recipe: &RecipeDef, content: &GameContent) -> f32 {
     (produced_volume - consumed_volume).max(0.0)
 }
 
-/// Tracks material consumed during the recipe's input phase, so the produced
-/// `AssemblerRan` event can report what was consumed.
+/// Tracks material consumed when a job starts, so the `AssemblerRan` event
+/// emitted on completion can report what was consumed.
 #[derive(Default)]
 struct ConsumedMaterial {
     element: String,
     kg: f32,
 }
 
-fn resolve_assembler_run(
-    ctx: &super::ModuleTickContext,
-    state: &mut GameState,
-    recipe: &RecipeDef,
-    content: &GameContent,
-    rng: &mut impl rand::Rng,
-    events: &mut Vec<EventEnvelope>,
-) {
-    let Some((consumed, any)) = consume_recipe_inputs(ctx, state, recipe, content) else {
-        return;
-    };
-    if !any {
-        return;
-    }
-    produce_recipe_outputs(ctx, state, recipe, content, rng, events, &consumed);
-    generate_assembly_research_data(state, recipe, content);
-}
-
 /// Consume the recipe's inputs from the station inventory. Returns
 /// `(consumed_material, any_consumed)`: the first tracks the most-recent
 /// element consumed (for event reporting), the second is `true` if at least
@@ -428,14 +597,10 @@ fn consume_recipe_inputs(
                 }
             }
             (InputFilter::Component(cid), InputAmount::Count(required)) => {
-                if take_components(ctx, state, &cid.0, *required) {
-                    any = true;
-                }
+                any |= take_components(ctx, state, &cid.0, *required);
             }
             (InputFilter::Module(def_id), InputAmount::Count(required)) => {
-                if take_modules(ctx, state, def_id.as_str(), *required) {
-                    any = true;
-                }
+                any |= take_modules(ctx, state, def_id.as_str(), *required);
             }
             _ => {}
         }
@@ -464,7 +629,7 @@ fn take_material(
         if let InventoryItem::Material { element, kg, .. } = item {
             if *element == element_id {
                 let take = kg.min(remaining);
-                *kg -= take;
+                crate::fixed::add_kg(kg, -take);
                 remaining -= take;
                 taken_total += take;
             }
@@ -559,7 +724,7 @@ fn produce_recipe_outputs(
                 component_id,
                 quality_formula,
             } => {
-                produce_component_output(
+                let units = produce_component_output(
                     ctx,
                     state,
                     recipe,
@@ -568,11 +733,28 @@ fn produce_recipe_outputs(
                     events,
                     consumed,
                 );
+                charge_royalty(ctx, state, content, &component_id.0, units, events);
             }
-            OutputSpec::Ship { hull_id } => {
-                if !produce_ship_output(ctx, state, content, rng, events, hull_id) {
-                    return; // unknown hull_id or missing station
-                }
+            OutputSpec::Ship {
+                hull_id,
+                class_id,
+                role,
+                owner,
+            } if !produce_ship_output(
+                ctx,
+                state,
+                content,
+                rng,
+                events,
+                &ShipSpec {
+                    hull_id,
+                    class_id: class_id.as_ref(),
+                    role: role.as_ref(),
+                    owner,
+                },
+            ) =>
+            {
+                return; // unknown hull_id or missing station
             }
             _ => {} // Material, Slag handled by processor
         }
@@ -580,7 +762,8 @@ fn produce_recipe_outputs(
 }
 
 /// Add produced components to station inventory (merging with an existing
-/// stack of matching quality) and emit an `AssemblerRan` event.
+/// stack of matching quality) and emit an `AssemblerRan` event. Returns the
+/// number of units produced.
 fn produce_component_output(
     ctx: &super::ModuleTickContext,
     state: &mut GameState,
@@ -589,19 +772,14 @@ fn produce_component_output(
     quality_formula: &QualityFormula,
     events: &mut Vec<EventEnvelope>,
     consumed: &ConsumedMaterial,
-) {
+) -> u32 {
     let quality = match quality_formula {
         QualityFormula::Fixed(q) => *q,
         QualityFormula::ElementFractionTimesMultiplier { .. } => 1.0,
     };
 
-    // Use round() so assemblers degrade gracefully: efficiency >= 0.5 still
-    // produces 1 item (VIO-460). floor() caused a cliff at < 1.0.
-    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
-    let produced_count = (1.0_f32 * ctx.efficiency).round() as u32;
-    if produced_count == 0 {
-        return;
-    }
+    // Efficiency slows job progress instead of scaling the yield.
+    let produced_count = 1;
 
     if let Some(station) = state.stations.get_mut(&ctx.station_id) {
         let existing = station.core.inventory.iter_mut().find(|i| {
@@ -632,22 +810,82 @@ fn produce_component_output(
             component_produced_id: component_id.clone(),
             component_produced_count: produced_count,
             component_quality: quality,
+            slot: ctx.slot,
+        },
+    ));
+    produced_count
+}
+
+/// Debit the blueprint royalty for `units` of an assembled item, unless an
+/// unlocked tech waives it.
+fn charge_royalty(
+    ctx: &super::ModuleTickContext,
+    state: &mut GameState,
+    content: &GameContent,
+    item_id: &str,
+    units: u32,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let royalty = content
+        .pricing
+        .items
+        .get(item_id)
+        .map_or(0.0, |entry| entry.royalty_per_unit);
+    if royalty <= 0.0 || royalty_waived(state, content, item_id) {
+        return;
+    }
+    let amount = royalty * f64::from(units);
+    crate::fixed::add_credits(&mut state.balance, -amount);
+    let current_tick = state.meta.tick;
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::RoyaltyPaid {
+            station_id: ctx.station_id.clone(),
+            module_id: ctx.module_id.clone(),
+            item_id: item_id.to_string(),
+            units,
+            amount,
+            balance_after: state.balance,
         },
     ));
 }
 
+/// True if any unlocked tech grants `WaiveRoyalty` for the item.
+fn royalty_waived(state: &GameState, content: &GameContent, item_id: &str) -> bool {
+    content
+        .techs
+        .iter()
+        .filter(|tech| state.research.unlocked.contains(&tech.id))
+        .flat_map(|tech| &tech.effects)
+        .any(|effect| {
+            matches!(effect, crate::TechEffect::WaiveRoyalty { item_id: waived } if waived == item_id)
+        })
+}
+
+/// Borrowed view of an `OutputSpec::Ship` output.
+struct ShipSpec<'a> {
+    hull_id: &'a crate::HullId,
+    class_id: Option<&'a String>,
+    role: Option<&'a String>,
+    owner: &'a crate::ShipOwnerRule,
+}
+
 /// Construct a new ship at the station and emit a `ShipConstructed` event.
-/// Returns `false` if the hull is missing from content or the station has
-/// disappeared (caller should abort the rest of the output loop).
+/// The ship is owned per the output's `ShipOwnerRule` and named after its
+/// hull plus a running count, so the owning principal's controllers pick it
+/// up on their next pass. Returns `false` if the hull is missing from
+/// content or the station has disappeared (caller should abort the rest of
+/// the output loop).
 fn produce_ship_output(
     ctx: &super::ModuleTickContext,
     state: &mut GameState,
     content: &GameContent,
     rng: &mut impl rand::Rng,
     events: &mut Vec<EventEnvelope>,
-    hull_id: &crate::HullId,
+    spec: &ShipSpec,
 ) -> bool {
-    let Some(hull) = content.hulls.get(hull_id) else {
+    let Some(hull) = content.hulls.get(spec.hull_id) else {
         return false;
     };
     let uuid = crate::generate_uuid(rng);
@@ -656,19 +894,29 @@ fn produce_ship_output(
         return false;
     };
     let ship_position = station.position.clone();
+    let owner = match spec.owner {
+        crate::ShipOwnerRule::StationOwner => station.owner.clone(),
+        crate::ShipOwnerRule::Principal(principal) => principal.clone(),
+    };
+    let hull_count = state
+        .ships
+        .values()
+        .filter(|s| s.hull_id == *spec.hull_id)
+        .count();
+    let name = format!("{} {}", hull.name, hull_count + 1);
     let mut ship = ShipState {
         id: ship_id.clone(),
         position: ship_position.clone(),
-        owner: PrincipalId("principal_autopilot".to_string()),
+        owner: owner.clone(),
         inventory: vec![],
         cargo_capacity_m3: hull.cargo_capacity_m3,
         task: None,
         speed_ticks_per_au: Some(hull.base_speed_ticks_per_au),
         modifiers: crate::modifiers::ModifierSet::default(),
-        hull_id: hull_id.clone(),
+        hull_id: spec.hull_id.clone(),
         fitted_modules: content
             .fitting_templates
-            .get(hull_id)
+            .get(spec.hull_id)
             .cloned()
             .unwrap_or_default(),
         propellant_kg: hull.base_propellant_capacity_kg,
@@ -678,6 +926,10 @@ fn produce_ship_output(
         // VIO-486: ships built by a shipyard belong to the
         // station that built them.
         home_station: Some(ctx.station_id.clone()),
+        name: name.clone(),
+        class_id: spec.class_id.cloned(),
+        role: spec.role.cloned(),
+        wear: crate::WearState::default(),
     };
     crate::commands::recompute_ship_stats(&mut ship, content);
     ship.propellant_kg = ship.propellant_capacity_kg;
@@ -693,8 +945,12 @@ fn produce_ship_output(
             ship_id,
             position: ship_position,
             cargo_capacity_m3: f64::from(actual_cargo),
-            hull_id: hull_id.clone(),
+            hull_id: spec.hull_id.clone(),
             fitted_modules: event_fitted,
+            owner,
+            name,
+            class_id: spec.class_id.cloned(),
+            role: spec.role.cloned(),
         },
     ));
     true
@@ -728,7 +984,7 @@ mod assembler_component_tests {
     use crate::*;
     use rand::SeedableRng;
     use rand_chacha::ChaCha8Rng;
-    use std::collections::{HashMap, HashSet};
+    use std::collections::HashMap;
 
     /// Content with an assembler that requires both Fe material and thruster components.
     fn assembler_content_with_component_input() -> GameContent {
@@ -786,107 +1042,56 @@ mod assembler_component_tests {
                     assembly_interval_ticks: 1,
                     recipes: vec![recipe_id],
                     max_stock: HashMap::new(),
+                    parallel_slots: 1,
                 }))
                 .build(),
         );
         content
     }
 
+    #[allow(clippy::too_many_lines)]
     fn assembler_state(content: &GameContent) -> GameState {
         let station_id = StationId("station_test".to_string());
-        GameState {
-            meta: MetaState {
-                tick: 0,
-                seed: 42,
-                schema_version: 1,
-                content_version: content.content_version.clone(),
-            },
-            scan_sites: vec![],
-            asteroids: std::collections::BTreeMap::new(),
-            ships: std::collections::BTreeMap::new(),
-            stations: [(
-                station_id.clone(),
-                StationState {
-                    id: station_id,
-                    position: crate::test_fixtures::test_position(),
-                    core: FacilityCore {
-                        inventory: vec![
-                            InventoryItem::Material {
-                                element: "Fe".to_string(),
-                                kg: 200.0,
-                                quality: 0.8,
-                                thermal: None,
-                            },
-                            InventoryItem::Component {
-                                component_id: ComponentId("thruster".to_string()),
-                                count: 6,
-                                quality: 0.9,
-                            },
-                        ],
-                        cargo_capacity_m3: 10_000.0,
-                        power_available_per_tick: 100.0,
-                        modules: vec![ModuleState {
-                            id: ModuleInstanceId("shipyard_inst_0001".to_string()),
-                            def_id: "module_shipyard".to_string(),
-                            enabled: true,
-                            kind_state: ModuleKindState::Assembler(AssemblerState {
-                                ticks_since_last_run: 0,
-                                stalled: false,
-                                capped: false,
-                                cap_override: HashMap::new(),
-                                selected_recipe: None,
-                            }),
-                            wear: WearState::default(),
-                            power_stalled: false,
-                            module_priority: 0,
-                            assigned_crew: Default::default(),
-                            efficiency: 1.0,
-                            prev_crew_satisfied: true,
-                            thermal: None,
-                            slot_index: None,
-                        }],
-                        modifiers: crate::modifiers::ModifierSet::default(),
-                        crew: Default::default(),
-                        thermal_links: Vec::new(),
-                        power: PowerState::default(),
-                        cached_inventory_volume_m3: None,
-                        module_type_index: crate::ModuleTypeIndex::default(),
-                        module_id_index: HashMap::new(),
-                        power_budget_cache: crate::PowerBudgetCache::default(),
-                    },
-                    leaders: Vec::new(),
-                    frame_id: None,
-                },
-            )]
-            .into_iter()
-            .collect(),
-            ground_facilities: std::collections::BTreeMap::new(),
-            satellites: std::collections::BTreeMap::new(),
-            research: ResearchState {
-                unlocked: HashSet::new(),
-                data_pool: AHashMap::default(),
-                evidence: AHashMap::default(),
-                action_counts: AHashMap::default(),
+        let mut station = crate::test_fixtures::test_station(station_id.clone());
+        station.core.inventory = vec![
+            InventoryItem::Material {
+                element: "Fe".to_string(),
+                kg: 200.0,
+                quality: 0.8,
+                thermal: None,
             },
-            balance: 0.0,
-            export_revenue_total: 0.0,
-            export_count: 0,
-            counters: Counters {
-                next_event_id: 0,
-                next_command_id: 0,
-                next_asteroid_id: 0,
-                next_lot_id: 0,
-                next_module_instance_id: 0,
-                ..Default::default()
+            InventoryItem::Component {
+                component_id: ComponentId("thruster".to_string()),
+                count: 6,
+                quality: 0.9,
             },
-            modifiers: crate::modifiers::ModifierSet::default(),
-            events: crate::sim_events::SimEventState::default(),
-            propellant_consumed_total: 0.0,
-            transfer_volume_kg: 0.0,
-            transfer_count: 0,
-            progression: Default::default(),
-            strategy_config: Default::default(),
-            body_cache: AHashMap::default(),
+        ];
+        station.core.modules = vec![ModuleState {
+            id: ModuleInstanceId("shipyard_inst_0001".to_string()),
+            def_id: "module_shipyard".to_string(),
+            enabled: true,
+            kind_state: ModuleKindState::Assembler(AssemblerState {
+                ticks_since_last_run: 0,
+                stalled: false,
+                capped: false,
+                cap_override: HashMap::new(),
+                selected_recipe: None,
+                extra_slots: Vec::new(),
+                job: None,
+                queue: Vec::new(),
+            }),
+            wear: WearState::default(),
+            power_stalled: false,
+            module_priority: 0,
+            assigned_crew: std::collections::BTreeMap::default(),
+            efficiency: 1.0,
+            prev_crew_satisfied: true,
+            thermal: None,
+            slot_index: None,
+        }];
+        GameState {
+            stations: [(station_id, station)].into_iter().collect(),
+            ..crate::test_fixtures::base_state(content)
         }
     }
 
@@ -1167,6 +1372,8 @@ mod assembler_component_tests {
                 bonuses: vec![],
                 required_tech: None,
                 tags: vec![],
+                hold_partitions: std::collections::BTreeMap::new(),
+                replacement_value: 0.0,
             },
         );
         // Shipyard recipe: 100kg Fe + 2 thrusters => Ship with test hull
@@ -1184,6 +1391,9 @@ mod assembler_component_tests {
             ],
             outputs: vec![OutputSpec::Ship {
                 hull_id: crate::HullId("hull_test_ship".to_string()),
+                class_id: None,
+                role: None,
+                owner: crate::ShipOwnerRule::StationOwner,
             }],
             efficiency: 1.0,
             thermal_req: None,
@@ -1203,107 +1413,57 @@ mod assembler_component_tests {
                     assembly_interval_ticks: 1,
                     recipes: vec![recipe_id],
                     max_stock: HashMap::new(),
+                    parallel_slots: 1,
                 }))
                 .build(),
         );
         content
     }
 
+    #[allow(clippy::too_many_lines)]
     fn shipyard_state(content: &GameContent) -> GameState {
         let station_id = StationId("station_test".to_string());
-        GameState {
-            meta: MetaState {
-                tick: 0,
-                seed: 42,
-                schema_version: 1,
-                content_version: content.content_version.clone(),
-            },
-            scan_sites: vec![],
-            asteroids: std::collections::BTreeMap::new(),
-            ships: std::collections::BTreeMap::new(),
-            stations: [(
-                station_id.clone(),
-                StationState {
-                    id: station_id,
-                    position: crate::test_fixtures::test_position(),
-                    core: FacilityCore {
-                        inventory: vec![
-                            InventoryItem::Material {
-                                element: "Fe".to_string(),
-                                kg: 200.0,
-                                quality: 0.8,
-                                thermal: None,
-                            },
-                            InventoryItem::Component {
-                                component_id: ComponentId("thruster".to_string()),
-                                count: 4,
-                                quality: 0.9,
-                            },
-                        ],
-                        cargo_capacity_m3: 10_000.0,
-                        power_available_per_tick: 100.0,
-                        modules: vec![ModuleState {
-                            id: ModuleInstanceId("shipyard_inst_0001".to_string()),
-                            def_id: "module_shipyard".to_string(),
-                            enabled: true,
-                            kind_state: ModuleKindState::Assembler(AssemblerState {
-                                ticks_since_last_run: 0,
-                                stalled: false,
-                                capped: false,
-                                cap_override: HashMap::new(),
-                                selected_recipe: None,
-                            }),
-                            wear: WearState::default(),
-                            power_stalled: false,
-                            module_priority: 0,
-                            assigned_crew: Default::default(),
-                            efficiency: 1.0,
-                            prev_crew_satisfied: true,
-                            thermal: None,
-                            slot_index: None,
-                        }],
-                        modifiers: crate::modifiers::ModifierSet::default(),
-                        crew: Default::default(),
-                        thermal_links: Vec::new(),
-                        power: PowerState::default(),
-                        cached_inventory_volume_m3: None,
-                        module_type_index: crate::ModuleTypeIndex::default(),
-                        module_id_index: HashMap::new(),
-                        power_budget_cache: crate::PowerBudgetCache::default(),
-                    },
-                    leaders: Vec::new(),
-                    frame_id: None,
-                },
-            )]
-            .into_iter()
-            .collect(),
-            ground_facilities: std::collections::BTreeMap::new(),
-            satellites: std::collections::BTreeMap::new(),
-            research: ResearchState {
-                unlocked: HashSet::new(),
-                data_pool: AHashMap::default(),
-                evidence: AHashMap::default(),
-                action_counts: AHashMap::default(),
+        let mut station = crate::test_fixtures::test_station(station_id.clone());
+        station.core.inventory = vec![
+            InventoryItem::Material {
+                element: "Fe".to_string(),
+                kg: 200.0,
+                quality: 0.8,
+                thermal: None,
             },
-            balance: 0.0,
-            export_revenue_total: 0.0,
-            export_count: 0,
-            counters: Counters {
-                next_event_id: 0,
-                next_command_id: 0,
-                next_asteroid_id: 0,
-                next_lot_id: 0,
-                next_module_instance_id: 0,
-                ..Default::default()
+            InventoryItem::Component {
+                component_id: ComponentId("thruster".to_string()),
+                count: 4,
+                quality: 0.9,
             },
-            modifiers: crate::modifiers::ModifierSet::default(),
-            events: crate::sim_events::SimEventState::default(),
-            propellant_consumed_total: 0.0,
-            transfer_volume_kg: 0.0,
-            transfer_count: 0,
-            progression: Default::default(),
-            strategy_config: Default::default(),
-            body_cache: AHashMap::default(),
+        ];
+        station.core.modules = vec![ModuleState {
+            id: ModuleInstanceId("shipyard_inst_0001".to_string()),
+            def_id: "module_shipyard".to_string(),
+            enabled: true,
+            kind_state: ModuleKindState::Assembler(AssemblerState {
+                ticks_since_last_run: 0,
+                stalled: false,
+                capped: false,
+                cap_override: HashMap::new(),
+                selected_recipe: None,
+                extra_slots: Vec::new(),
+                job: None,
+                queue: Vec::new(),
+            }),
+            wear: WearState::default(),
+            power_stalled: false,
+            module_priority: 0,
+            assigned_crew: std::collections::BTreeMap::default(),
+            efficiency: 1.0,
+            prev_crew_satisfied: true,
+            thermal: None,
+            slot_index: None,
+        }];
+        GameState {
+            ships: std::collections::BTreeMap::new(),
+            stations: [(station_id, station)].into_iter().collect(),
+            ..crate::test_fixtures::base_state(content)
         }
     }
 
@@ -1386,6 +1546,98 @@ mod assembler_component_tests {
         assert_eq!(thruster_count, 2, "expected 2 thrusters remaining");
     }
 
+    fn set_ship_output(content: &mut GameContent, owner: &ShipOwnerRule) {
+        for recipe in content.recipes.values_mut() {
+            for output in &mut recipe.outputs {
+                if let OutputSpec::Ship {
+                    class_id,
+                    role,
+                    owner: rule,
+                    ..
+                } = output
+                {
+                    *class_id = Some("class_prospector".to_string());
+                    *role = Some("miner".to_string());
+                    *rule = owner.clone();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shipyard_ship_inherits_station_owner_and_identity() {
+        let mut content = shipyard_content();
+        set_ship_output(&mut content, &ShipOwnerRule::StationOwner);
+        let mut state = shipyard_state(&content);
+        state
+            .research
+            .unlocked
+            .insert(TechId("tech_ship_construction".to_string()));
+        let station_id = StationId("station_test".to_string());
+        let rival = PrincipalId("principal_rival".to_string());
+        state.stations.get_mut(&station_id).unwrap().owner = rival.clone();
+
+        let mut events = Vec::new();
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        super::tick_assembler_modules(
+            &mut state,
+            &station_id,
+            &content,
+            &mut rng,
+            &mut events,
+            &mut Vec::new(),
+        );
+
+        let ship = state.ships.values().next().expect("ship constructed");
+        assert_eq!(ship.owner, rival);
+        assert_eq!(ship.name, "Test Ship Hull 1");
+        assert_eq!(ship.class_id.as_deref(), Some("class_prospector"));
+        assert_eq!(ship.role.as_deref(), Some("miner"));
+        let event = events
+            .iter()
+            .find_map(|e| match &e.event {
+                Event::ShipConstructed {
+                    owner, name, role, ..
+                } => Some((owner.clone(), name.clone(), role.clone())),
+                _ => None,
+            })
+            .expect("ShipConstructed event");
+        assert_eq!(
+            event,
+            (
+                rival,
+                "Test Ship Hull 1".to_string(),
+                Some("miner".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn shipyard_ship_owner_rule_can_name_a_principal() {
+        let mut content = shipyard_content();
+        let navy = PrincipalId("principal_navy".to_string());
+        set_ship_output(&mut content, &ShipOwnerRule::Principal(navy.clone()));
+        let mut state = shipyard_state(&content);
+        state
+            .research
+            .unlocked
+            .insert(TechId("tech_ship_construction".to_string()));
+        let station_id = StationId("station_test".to_string());
+
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        super::tick_assembler_modules(
+            &mut state,
+            &station_id,
+            &content,
+            &mut rng,
+            &mut Vec::new(),
+            &mut Vec::new(),
+        );
+
+        let ship = state.ships.values().next().expect("ship constructed");
+        assert_eq!(ship.owner, navy);
+    }
+
     #[test]
     fn shipyard_stalls_without_tech() {
         let content = shipyard_content();
@@ -1501,4 +1753,200 @@ mod assembler_component_tests {
             "Assembler at critical wear (0.5 efficiency) should still produce 1 item"
         );
     }
+
+    fn two_slot_assembler_content() -> GameContent {
+        let mut content = assembler_content_with_component_input();
+        let def = content.module_defs.get_mut("module_shipyard").unwrap();
+        if let ModuleBehaviorDef::Assembler(ref mut ad) = def.behavior {
+            ad.parallel_slots = 2;
+        }
+        content
+    }
+
+    fn set_thrusters(state: &mut GameState, station_id: &StationId, thrusters: u32) {
+        let station = state.stations.get_mut(station_id).unwrap();
+        for item in &mut station.core.inventory {
+            if let InventoryItem::Component {
+                component_id,
+                count,
+                ..
+            } = item
+            {
+                if component_id.0 == "thruster" {
+                    *count = thrusters;
+                }
+            }
+        }
+    }
+
+    fn ran_slots(events: &[EventEnvelope]) -> Vec<u32> {
+        events
+            .iter()
+            .filter_map(|e| match &e.event {
+                Event::AssemblerRan { slot, .. } => Some(*slot),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parallel_slots_run_concurrently() {
+        let content = two_slot_assembler_content();
+        let mut state = assembler_state(&content);
+        let station_id = StationId("station_test".to_string());
+        set_thrusters(&mut state, &station_id, 8);
+
+        let mut events = Vec::new();
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        super::tick_assembler_modules(
+            &mut state,
+            &station_id,
+            &content,
+            &mut rng,
+            &mut events,
+            &mut Vec::new(),
+        );
+
+        assert_eq!(ran_slots(&events), vec![0, 1]);
+        let station = state.stations.get(&station_id).unwrap();
+        let ModuleKindState::Assembler(asmb) = &station.core.modules[0].kind_state else {
+            panic!("expected assembler state");
+        };
+        assert_eq!(asmb.extra_slots.len(), 1);
+    }
+
+    #[test]
+    fn parallel_slot_skips_when_earlier_slot_took_inputs() {
+        let content = two_slot_assembler_content();
+        let mut state = assembler_state(&content);
+        let station_id = StationId("station_test".to_string());
+
+        // 6 thrusters: slot 0 uses 4, slot 1 is left short.
+        let mut events = Vec::new();
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        super::tick_assembler_modules(
+            &mut state,
+            &station_id,
+            &content,
+            &mut rng,
+            &mut events,
+            &mut Vec::new(),
+        );
+
+        assert_eq!(ran_slots(&events), vec![0]);
+    }
+
+    #[test]
+    fn parallel_slot_caps_independently() {
+        let mut content = two_slot_assembler_content();
+        let def = content.module_defs.get_mut("module_shipyard").unwrap();
+        if let ModuleBehaviorDef::Assembler(ref mut ad) = def.behavior {
+            ad.max_stock
+                .insert(ComponentId("hull_plate".to_string()), 1);
+        }
+        let mut state = assembler_state(&content);
+        let station_id = StationId("station_test".to_string());
+        set_thrusters(&mut state, &station_id, 8);
+
+        let mut events = Vec::new();
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        super::tick_assembler_modules(
+            &mut state,
+            &station_id,
+            &content,
+            &mut rng,
+            &mut events,
+            &mut Vec::new(),
+        );
+
+        assert_eq!(ran_slots(&events), vec![0]);
+        assert!(events
+            .iter()
+            .any(|e| matches!(&e.event, Event::AssemblerCapped { slot: 1, .. })));
+        let station = state.stations.get(&station_id).unwrap();
+        let ModuleKindState::Assembler(asmb) = &station.core.modules[0].kind_state else {
+            panic!("expected assembler state");
+        };
+        assert!(!asmb.capped, "slot 0 ran and should not be capped");
+        assert!(asmb.extra_slots[0].capped, "slot 1 should be capped");
+    }
+
+    #[test]
+    fn queued_order_runs_its_recipe_count_times_then_completes() {
+        let mut content = assembler_content_with_component_input();
+        // A second recipe that needs only iron.
+        let iron_plate = RecipeDef {
+            id: RecipeId("recipe_iron_plate".to_string()),
+            inputs: vec![RecipeInput {
+                filter: InputFilter::Element("Fe".to_string()),
+                amount: InputAmount::Kg(50.0),
+            }],
+            outputs: vec![OutputSpec::Component {
+                component_id: ComponentId("hull_plate".to_string()),
+                quality_formula: QualityFormula::Fixed(0.9),
+            }],
+            efficiency: 1.0,
+            thermal_req: None,
+            required_tech: None,
+            tags: vec![],
+        };
+        let iron_plate_id = crate::test_fixtures::insert_recipe(&mut content, iron_plate);
+        let def = content.module_defs.get_mut("module_shipyard").unwrap();
+        if let ModuleBehaviorDef::Assembler(ref mut ad) = def.behavior {
+            ad.recipes.push(iron_plate_id.clone());
+        }
+        let mut state = assembler_state(&content);
+        let station_id = StationId("station_test".to_string());
+        let module_id = ModuleInstanceId("shipyard_inst_0001".to_string());
+        assert!(crate::commands::handle_queue_assembler_job(
+            &mut state,
+            &content,
+            &station_id,
+            &module_id,
+            &iron_plate_id,
+            2,
+        )
+        .is_ok());
+
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let mut ran = Vec::new();
+        let mut completed = Vec::new();
+        for _ in 0..3 {
+            let mut events = Vec::new();
+            super::tick_assembler_modules(
+                &mut state,
+                &station_id,
+                &content,
+                &mut rng,
+                &mut events,
+                &mut Vec::new(),
+            );
+            for e in events {
+                match e.event {
+                    Event::AssemblerRan { recipe_id, .. } => ran.push(recipe_id.0),
+                    Event::AssemblerJobCompleted {
+                        recipe_id, count, ..
+                    } => completed.push((recipe_id.0, count)),
+                    _ => {}
+                }
+            }
+        }
+
+        // Two queued runs, then back to the default first recipe.
+        assert_eq!(
+            ran,
+            [
+                "recipe_iron_plate",
+                "recipe_iron_plate",
+                "recipe_hull_plate"
+            ]
+        );
+        assert_eq!(completed, [("recipe_iron_plate".to_string(), 2)]);
+        let ModuleKindState::Assembler(asmb) =
+            &state.stations[&station_id].core.modules[0].kind_state
+        else {
+            panic!("expected assembler state");
+        };
+        assert!(asmb.queue.is_empty());
+    }
 }
@@ -98,7 +98,7 @@ pub(super) fn apply_boiloff(
             as f32)
             .min(*kg);
         if loss > content.constants.min_meaningful_kg {
-            *kg -= loss;
+            crate::fixed::add_kg(kg, -loss);
             losses.push((element.clone(), loss));
         }
     }
@@ -148,6 +148,8 @@ mod tests {
             boiloff_rate_per_day_at_293k: Some(0.014),
             boiling_point_mk: Some(20_300),
             boiloff_curve: None,
+            volatile: false,
+            quality_decay_per_day: None,
         });
         content.elements.push(ElementDef {
             id: "LOX".to_string(),
@@ -161,6 +163,8 @@ mod tests {
             boiloff_rate_per_day_at_293k: Some(0.003),
             boiling_point_mk: Some(90_200),
             boiloff_curve: None,
+            volatile: false,
+            quality_decay_per_day: None,
         });
         content
     }
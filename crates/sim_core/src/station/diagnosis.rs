@@ -0,0 +1,98 @@
+//! Why a module is not running.
+//!
+//! `why_not_running` walks the same checks a module's run goes through —
+//! enabled, wear, power, then for processors recipe, tech, input threshold,
+//! temperature and output storage — without mutating anything, and returns
+//! every check that fails rather than stopping at the first. The run timer is
+//! ignored: an empty chain means the module would run once its interval is
+//! up. Every `idle_diagnosis_interval_ticks` each blocked processor reports
+//! its chain as a `ModuleIdleDiagnosis` debug event.
+
+use crate::{
+    Event, EventEnvelope, GameContent, GameState, IdleReason, ModuleBehaviorDef, ModuleInstanceId,
+    StationId,
+};
+
+/// Reasons the module would not run, in check order. Empty when nothing
+/// blocks it; `None` when the station or module does not exist.
+pub fn why_not_running(
+    state: &GameState,
+    content: &GameContent,
+    station_id: &StationId,
+    module_id: &ModuleInstanceId,
+) -> Option<Vec<IdleReason>> {
+    let station = state.stations.get(station_id)?;
+    let module_idx = station
+        .core
+        .modules
+        .iter()
+        .position(|module| module.id == *module_id)?;
+    let module = &station.core.modules[module_idx];
+    let mut reasons = Vec::new();
+    if !module.enabled {
+        reasons.push(IdleReason::Disabled);
+    }
+    if module.wear.wear >= 1.0 {
+        reasons.push(IdleReason::WornOut {
+            wear: module.wear.wear,
+        });
+    } else if module.power_stalled {
+        reasons.push(IdleReason::PowerStalled);
+    } else if module.efficiency <= 0.0 {
+        reasons.push(IdleReason::ZeroEfficiency);
+    }
+    let Some(def) = content.module_defs.get(&module.def_id) else {
+        return Some(reasons);
+    };
+    let available_kw = station.core.power_available_per_tick;
+    if available_kw < def.power_consumption_per_run {
+        reasons.push(IdleReason::InsufficientPower {
+            available_kw,
+            needed_kw: def.power_consumption_per_run,
+        });
+    }
+    if matches!(def.behavior, ModuleBehaviorDef::Processor(_)) {
+        super::processor::diagnose(state, station, module_idx, def, content, &mut reasons);
+    }
+    Some(reasons)
+}
+
+/// Emit a `ModuleIdleDiagnosis` for every blocked processor at the station
+/// on report ticks.
+pub(super) fn emit_idle_diagnoses(
+    state: &mut GameState,
+    station_id: &StationId,
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let interval = content.constants.idle_diagnosis_interval_ticks;
+    let current_tick = state.meta.tick;
+    if interval == 0 || !current_tick.is_multiple_of(interval) {
+        return;
+    }
+    let Some(station) = state.stations.get(station_id) else {
+        return;
+    };
+    let diagnoses: Vec<(ModuleInstanceId, Vec<IdleReason>)> = station
+        .core
+        .module_type_index
+        .processors
+        .iter()
+        .filter_map(|&idx| {
+            let module_id = &station.core.modules[idx].id;
+            let reasons = why_not_running(state, content, station_id, module_id)?;
+            (!reasons.is_empty()).then(|| (module_id.clone(), reasons))
+        })
+        .collect();
+    for (module_id, reasons) in diagnoses {
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            Event::ModuleIdleDiagnosis {
+                station_id: station_id.clone(),
+                module_id,
+                reasons,
+            },
+        ));
+    }
+}
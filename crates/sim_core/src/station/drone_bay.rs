@@ -0,0 +1,112 @@
+use crate::{
+    AsteroidId, DroneBayDef, Event, EventEnvelope, GameContent, GameState, ModuleBehaviorDef,
+    StationId,
+};
+
+pub(super) fn tick_drone_bay_modules(
+    state: &mut GameState,
+    station_id: &StationId,
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+) {
+    super::ensure_station_index(state, station_id, content);
+    let indices: Vec<usize> = state
+        .stations
+        .get(station_id)
+        .map(|s| s.core.module_type_index.drone_bays.clone())
+        .unwrap_or_default();
+
+    for module_idx in indices {
+        let Some(ctx) = super::extract_context(state, station_id, module_idx, content) else {
+            continue;
+        };
+
+        let ModuleBehaviorDef::DroneBay(bay_def) = &ctx.def.behavior else {
+            continue;
+        };
+
+        if !super::should_run(state, &ctx) {
+            continue;
+        }
+
+        let outcome = execute(&ctx, bay_def, state, content, events);
+        super::apply_run_result(state, &ctx, outcome, content, events);
+    }
+}
+
+/// The deep-scanned asteroid nearest the station in body hops (ties by
+/// id) within the bay's range, skipping bodies claimed by someone else.
+fn pick_target(
+    state: &GameState,
+    station_id: &StationId,
+    bay_def: &DroneBayDef,
+    content: &GameContent,
+) -> Option<AsteroidId> {
+    let station = state.stations.get(station_id)?;
+    let home = &station.position.parent_body;
+    state
+        .asteroids
+        .values()
+        .filter(|asteroid| asteroid.knowledge.composition.is_some())
+        .filter(|asteroid| {
+            crate::claims::mining_blocked_by(state, &station.owner, &asteroid.position.parent_body)
+                .is_none()
+        })
+        .filter_map(|asteroid| {
+            let hops = crate::range::body_hops(content, home, &asteroid.position.parent_body)?;
+            (hops <= bay_def.range_hops).then_some((hops, &asteroid.id))
+        })
+        .min()
+        .map(|(_, id)| id.clone())
+}
+
+fn execute(
+    ctx: &super::ModuleTickContext,
+    bay_def: &DroneBayDef,
+    state: &mut GameState,
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+) -> super::RunOutcome {
+    let current_tick = state.meta.tick;
+
+    let Some(asteroid_id) = pick_target(state, &ctx.station_id, bay_def, content) else {
+        return super::RunOutcome::Skipped { reset_timer: true };
+    };
+    let kg = (bay_def.kg_per_run * ctx.efficiency).min(state.asteroids[&asteroid_id].mass_kg);
+    if kg <= 0.0 {
+        return super::RunOutcome::Skipped { reset_timer: true };
+    }
+
+    let Some(station) = state.stations.get(&ctx.station_id) else {
+        return super::RunOutcome::Skipped { reset_timer: false };
+    };
+    let ore_volume = kg / crate::tasks::element_density(content, crate::ELEMENT_ORE);
+    let current_used = station
+        .core
+        .cached_inventory_volume_m3
+        .unwrap_or_else(|| crate::inventory_volume_m3(&station.core.inventory, content));
+    let shortfall = (current_used + ore_volume) - station.core.cargo_capacity_m3;
+    if shortfall > 0.0 {
+        return super::RunOutcome::Stalled(super::StallReason::VolumeCap {
+            shortfall_m3: shortfall,
+        });
+    }
+
+    let (ore_item, asteroid_remaining_kg, _) = crate::tasks::extract_ore(state, &asteroid_id, kg);
+    if let Some(station) = state.stations.get_mut(&ctx.station_id) {
+        station.core.inventory.push(ore_item.clone());
+    }
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::DroneOreMined {
+            station_id: ctx.station_id.clone(),
+            module_id: ctx.module_id.clone(),
+            asteroid_id,
+            ore_lot: ore_item,
+            asteroid_remaining_kg: asteroid_remaining_kg.max(0.0),
+        },
+    ));
+
+    super::RunOutcome::Completed
+}
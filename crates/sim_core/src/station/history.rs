@@ -0,0 +1,113 @@
+use crate::{Event, EventEnvelope, GameContent, GameState, StationId};
+
+/// Step 5.5: Append this tick's significant station events (installs,
+/// stalls, repairs, trades) to each station's `history` ring buffer,
+/// dropping the oldest entries beyond `station_history_capacity`.
+pub(crate) fn record_station_history(
+    state: &mut GameState,
+    content: &GameContent,
+    events: &[EventEnvelope],
+) {
+    let capacity = content.constants.station_history_capacity;
+    if capacity == 0 {
+        return;
+    }
+    for envelope in events {
+        let Some(station_id) = history_station(&envelope.event) else {
+            continue;
+        };
+        let Some(station) = state.stations.get_mut(station_id) else {
+            continue;
+        };
+        station.history.push_back(envelope.clone());
+        while station.history.len() > capacity {
+            station.history.pop_front();
+        }
+    }
+}
+
+/// The station a significant event belongs to, or `None` for events that
+/// are too frequent or not station-scoped.
+fn history_station(event: &Event) -> Option<&StationId> {
+    match event {
+        Event::ModuleInstalled { station_id, .. }
+        | Event::ModuleUninstalled { station_id, .. }
+        | Event::ModuleScrapped { station_id, .. }
+        | Event::ModuleUpgraded { station_id, .. }
+        | Event::ModuleAutoDisabled { station_id, .. }
+        | Event::ModuleStalled { station_id, .. }
+        | Event::ModuleResumed { station_id, .. }
+        | Event::MaintenanceRan { station_id, .. }
+        | Event::OverheatDamage { station_id, .. }
+        | Event::ItemImported { station_id, .. }
+        | Event::ItemExported { station_id, .. }
+        | Event::ImportDelivered { station_id, .. }
+        | Event::ProjectCompleted { station_id, .. }
+        | Event::LifeSupportFailed { station_id, .. }
+        | Event::LifeSupportRestored { station_id }
+        | Event::StationBankrupt { station_id, .. } => Some(station_id),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{base_content, base_state};
+    use crate::{EventId, ModuleInstanceId};
+
+    fn envelope(tick: u64, event: Event) -> EventEnvelope {
+        EventEnvelope {
+            id: EventId(tick),
+            tick,
+            event,
+        }
+    }
+
+    fn auto_disabled(station_id: &StationId) -> Event {
+        Event::ModuleAutoDisabled {
+            station_id: station_id.clone(),
+            module_id: ModuleInstanceId("module_refinery".to_string()),
+        }
+    }
+
+    #[test]
+    fn history_keeps_the_newest_significant_events() {
+        let mut content = base_content();
+        content.constants.station_history_capacity = 2;
+        let mut state = base_state(&content);
+        let station_id = state.stations.keys().next().unwrap().clone();
+
+        let events: Vec<_> = (0..3)
+            .map(|tick| envelope(tick, auto_disabled(&station_id)))
+            .collect();
+        record_station_history(&mut state, &content, &events);
+
+        let ticks: Vec<u64> = state.stations[&station_id]
+            .history
+            .iter()
+            .map(|e| e.tick)
+            .collect();
+        assert_eq!(ticks, vec![1, 2]);
+    }
+
+    #[test]
+    fn routine_events_are_not_recorded() {
+        let content = base_content();
+        let mut state = base_state(&content);
+        let station_id = state.stations.keys().next().unwrap().clone();
+
+        let events = vec![envelope(
+            0,
+            Event::WearAccumulated {
+                station_id: station_id.clone(),
+                module_id: ModuleInstanceId("module_refinery".to_string()),
+                wear_before: 0.1,
+                wear_after: 0.2,
+            },
+        )];
+        record_station_history(&mut state, &content, &events);
+
+        assert!(state.stations[&station_id].history.is_empty());
+    }
+}
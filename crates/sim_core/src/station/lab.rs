@@ -1,6 +1,6 @@
 use crate::{
     Event, EventEnvelope, GameContent, GameState, ModuleBehaviorDef, ModuleKindState,
-    ResearchDomain, StationId,
+    ResearchDomain, StationId, TechId,
 };
 use std::collections::HashMap;
 
@@ -69,6 +69,27 @@ pub(super) fn tick_lab_modules(
     }
 }
 
+/// The tech a lab's output goes to: the first queued tech that still needs
+/// the lab's domain, else its assigned tech if that is not yet unlocked.
+fn lab_target(
+    ctx: &super::ModuleTickContext,
+    lab_def: &crate::LabDef,
+    state: &GameState,
+    content: &GameContent,
+) -> Option<TechId> {
+    if let Some(tech_id) = crate::research::queued_target(&state.research, content, &lab_def.domain)
+    {
+        return Some(tech_id.clone());
+    }
+    let station = state.stations.get(&ctx.station_id)?;
+    let ModuleKindState::Lab(ls) = &station.core.modules[ctx.module_idx].kind_state else {
+        return None;
+    };
+    ls.assigned_tech
+        .clone()
+        .filter(|tech_id| !state.research.unlocked.contains(tech_id))
+}
+
 fn execute(
     ctx: &super::ModuleTickContext,
     lab_def: &crate::LabDef,
@@ -79,27 +100,10 @@ fn execute(
 ) -> super::RunOutcome {
     let current_tick = state.meta.tick;
 
-    // Check assigned_tech
-    let assigned_tech = {
-        let Some(station) = state.stations.get(&ctx.station_id) else {
-            return super::RunOutcome::Skipped { reset_timer: true };
-        };
-        if let ModuleKindState::Lab(ls) = &station.core.modules[ctx.module_idx].kind_state {
-            ls.assigned_tech.clone()
-        } else {
-            return super::RunOutcome::Skipped { reset_timer: true };
-        }
-    };
-
-    let Some(tech_id) = assigned_tech else {
+    let Some(tech_id) = lab_target(ctx, lab_def, state, content) else {
         return super::RunOutcome::Skipped { reset_timer: true };
     };
 
-    // Skip if tech already unlocked
-    if state.research.unlocked.contains(&tech_id) {
-        return super::RunOutcome::Skipped { reset_timer: true };
-    }
-
     // Sum available data
     let available_data: f32 = lab_def
         .accepted_data
@@ -157,13 +161,18 @@ fn execute(
     #[allow(clippy::cast_possible_truncation)]
     let pacing_mult = (speed_mult * domain_rate * tier_scaling * dr_factor) as f32;
 
-    // Route wear through modifier system for research output.
+    // Route wear and layout bonuses through the modifier system.
     let mut lab_mods = crate::modifiers::ModifierSet::new();
     lab_mods.add(crate::modifiers::Modifier::pct_mult(
         crate::modifiers::StatId::ResearchSpeed,
         f64::from(ctx.efficiency),
         crate::modifiers::ModifierSource::Wear,
     ));
+    if let Some(station) = state.stations.get(&ctx.station_id) {
+        for modifier in super::layout::adjacency_modifiers(station, ctx.module_idx, content) {
+            lab_mods.add(modifier);
+        }
+    }
     let points = lab_mods.resolve_with_f32(
         crate::modifiers::StatId::ResearchSpeed,
         lab_def.research_points_per_run * ratio * pacing_mult,
@@ -178,7 +187,10 @@ fn execute(
         .or_insert_with(|| crate::DomainProgress {
             points: HashMap::new(),
         });
-    *progress.points.entry(lab_def.domain.clone()).or_insert(0.0) += points;
+    crate::fixed::add_points(
+        progress.points.entry(lab_def.domain.clone()).or_insert(0.0),
+        points,
+    );
 
     // Emit LabRan event
     events.push(crate::emit(
@@ -200,9 +212,8 @@ fn execute(
 #[cfg(test)]
 mod tests {
     use crate::test_fixtures::ModuleDefBuilder;
-    use crate::AHashMap;
     use crate::*;
-    use std::collections::{HashMap, HashSet};
+    use std::collections::HashMap;
 
     fn lab_content() -> GameContent {
         let mut content = crate::test_fixtures::base_content();
@@ -229,85 +240,28 @@ mod tests {
 
     fn lab_state(content: &GameContent) -> GameState {
         let station_id = StationId("station_test".to_string());
+        let mut station = crate::test_fixtures::test_station(station_id.clone());
+        station.core.modules = vec![ModuleState {
+            id: ModuleInstanceId("lab_inst_0001".to_string()),
+            def_id: "module_exploration_lab".to_string(),
+            enabled: true,
+            kind_state: ModuleKindState::Lab(LabState {
+                ticks_since_last_run: 0,
+                assigned_tech: Some(TechId("tech_deep_scan_v1".to_string())),
+                starved: false,
+            }),
+            wear: WearState::default(),
+            power_stalled: false,
+            module_priority: 0,
+            assigned_crew: std::collections::BTreeMap::default(),
+            efficiency: 1.0,
+            prev_crew_satisfied: true,
+            thermal: None,
+            slot_index: None,
+        }];
         let mut state = GameState {
-            meta: MetaState {
-                tick: 0,
-                seed: 42,
-                schema_version: 1,
-                content_version: content.content_version.clone(),
-            },
-            scan_sites: vec![],
-            asteroids: std::collections::BTreeMap::new(),
-            ships: std::collections::BTreeMap::new(),
-            stations: [(
-                station_id.clone(),
-                StationState {
-                    id: station_id,
-                    position: crate::test_fixtures::test_position(),
-                    core: FacilityCore {
-                        inventory: vec![],
-                        cargo_capacity_m3: 10_000.0,
-                        power_available_per_tick: 100.0,
-                        modules: vec![ModuleState {
-                            id: ModuleInstanceId("lab_inst_0001".to_string()),
-                            def_id: "module_exploration_lab".to_string(),
-                            enabled: true,
-                            kind_state: ModuleKindState::Lab(LabState {
-                                ticks_since_last_run: 0,
-                                assigned_tech: Some(TechId("tech_deep_scan_v1".to_string())),
-                                starved: false,
-                            }),
-                            wear: WearState::default(),
-                            power_stalled: false,
-                            module_priority: 0,
-                            assigned_crew: Default::default(),
-                            efficiency: 1.0,
-                            prev_crew_satisfied: true,
-                            thermal: None,
-                            slot_index: None,
-                        }],
-                        modifiers: crate::modifiers::ModifierSet::default(),
-                        crew: Default::default(),
-                        thermal_links: Vec::new(),
-                        power: PowerState::default(),
-                        cached_inventory_volume_m3: None,
-                        module_type_index: crate::ModuleTypeIndex::default(),
-                        module_id_index: HashMap::new(),
-                        power_budget_cache: crate::PowerBudgetCache::default(),
-                    },
-                    leaders: Vec::new(),
-                    frame_id: None,
-                },
-            )]
-            .into_iter()
-            .collect(),
-            ground_facilities: std::collections::BTreeMap::new(),
-            satellites: std::collections::BTreeMap::new(),
-            research: ResearchState {
-                unlocked: HashSet::new(),
-                data_pool: AHashMap::default(),
-                evidence: AHashMap::default(),
-                action_counts: AHashMap::default(),
-            },
-            balance: 0.0,
-            export_revenue_total: 0.0,
-            export_count: 0,
-            counters: Counters {
-                next_event_id: 0,
-                next_command_id: 0,
-                next_asteroid_id: 0,
-                next_lot_id: 0,
-                next_module_instance_id: 0,
-                ..Default::default()
-            },
-            modifiers: crate::modifiers::ModifierSet::default(),
-            events: crate::sim_events::SimEventState::default(),
-            propellant_consumed_total: 0.0,
-            transfer_volume_kg: 0.0,
-            transfer_count: 0,
-            progression: Default::default(),
-            strategy_config: Default::default(),
-            body_cache: AHashMap::default(),
+            stations: [(station_id, station)].into_iter().collect(),
+            ..crate::test_fixtures::base_state(content)
         };
         crate::test_fixtures::rebuild_indices(&mut state, content);
         state
@@ -349,6 +303,60 @@ mod tests {
         assert!(lab_ran, "expected LabRan event");
     }
 
+    #[test]
+    fn lab_output_goes_to_first_queued_tech_that_needs_its_domain() {
+        let mut content = lab_content();
+        let survey = ResearchDomain::new(ResearchDomain::SURVEY);
+        let tech = |id: &str, domain: &str| TechDef {
+            id: TechId(id.to_string()),
+            name: id.to_string(),
+            tier: 1,
+            prereqs: vec![],
+            domain_requirements: HashMap::from([(ResearchDomain::new(domain), 10.0)]),
+            accepted_data: vec![],
+            effects: vec![],
+        };
+        content.techs.push(tech("tech_other_domain", "Materials"));
+        content
+            .techs
+            .push(tech("tech_queued", ResearchDomain::SURVEY));
+        let mut state = lab_state(&content);
+        state
+            .research
+            .data_pool
+            .insert(DataKind::new(DataKind::SURVEY), 100.0);
+        state.research.queue = vec![
+            TechId("tech_other_domain".to_string()),
+            TechId("tech_queued".to_string()),
+        ];
+
+        let mut events = Vec::new();
+        let station_id = StationId("station_test".to_string());
+        super::tick_lab_modules(&mut state, &station_id, &content, &mut events);
+
+        let queued = &state.research.evidence[&TechId("tech_queued".to_string())];
+        assert!((queued.points[&survey] - 4.0).abs() < 1e-3);
+        assert!(!state
+            .research
+            .evidence
+            .contains_key(&TechId("tech_deep_scan_v1".to_string())));
+
+        // Once the queued tech has enough survey points, the lab falls back
+        // to its assigned tech.
+        state
+            .research
+            .evidence
+            .get_mut(&TechId("tech_queued".to_string()))
+            .unwrap()
+            .points
+            .insert(survey.clone(), 10.0);
+        super::tick_lab_modules(&mut state, &station_id, &content, &mut events);
+        assert!(state
+            .research
+            .evidence
+            .contains_key(&TechId("tech_deep_scan_v1".to_string())));
+    }
+
     #[test]
     fn lab_starves_when_no_data() {
         let content = lab_content();
@@ -663,6 +671,8 @@ mod tests {
                 module_type_index: crate::ModuleTypeIndex::default(),
                 module_id_index: HashMap::new(),
                 power_budget_cache: crate::PowerBudgetCache::default(),
+                production_windows: std::collections::BTreeMap::new(),
+                life_support_failing: false,
             },
             launch_transits: Vec::new(),
         };
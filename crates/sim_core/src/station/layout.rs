@@ -0,0 +1,121 @@
+//! Station module layout: slot adjacency and the content rules keyed on it.
+//!
+//! A frame's `SlotDef.adjacent` lists describe which slots share a wall.
+//! `content.adjacency_rules` then grant stat bonuses to a module when a
+//! matching neighbour is installed next to it, or forbid certain pairings
+//! outright (checked when `InstallModule` picks a slot).
+
+use crate::modifiers::{Modifier, ModifierSource};
+use crate::{FrameDef, GameContent, ModuleDef, StationState};
+
+/// True when frame slots `a` and `b` share a wall. Adjacency is symmetric,
+/// so it is enough for either slot to list the other.
+pub(crate) fn slots_adjacent(frame: &FrameDef, a: usize, b: usize) -> bool {
+    if a == b {
+        return false;
+    }
+    let lists = |from: usize, to: usize| {
+        frame
+            .slots
+            .get(from)
+            .is_some_and(|slot| slot.adjacent.contains(&to))
+    };
+    lists(a, b) || lists(b, a)
+}
+
+/// Behavior type names of the modules installed next to `slot_index`,
+/// skipping the module at `exclude_idx` (the module being evaluated).
+fn neighbor_types<'a>(
+    station: &StationState,
+    frame: &FrameDef,
+    slot_index: usize,
+    exclude_idx: Option<usize>,
+    content: &'a GameContent,
+) -> Vec<&'a str> {
+    station
+        .core
+        .modules
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| Some(*idx) != exclude_idx)
+        .filter_map(|(_, module)| {
+            let other_slot = module.slot_index?;
+            if !slots_adjacent(frame, slot_index, other_slot) {
+                return None;
+            }
+            content
+                .module_defs
+                .get(&module.def_id)
+                .map(|def| def.behavior.type_name())
+        })
+        .collect()
+}
+
+/// Modifiers granted to the module at `module_idx` by its neighbours.
+/// Empty for frameless stations, unslotted modules, or when no rule matches.
+pub(crate) fn adjacency_modifiers(
+    station: &StationState,
+    module_idx: usize,
+    content: &GameContent,
+) -> Vec<Modifier> {
+    if content.adjacency_rules.is_empty() {
+        return Vec::new();
+    }
+    let Some(frame) = station
+        .frame_id
+        .as_ref()
+        .and_then(|id| content.frames.get(id))
+    else {
+        return Vec::new();
+    };
+    let Some(module) = station.core.modules.get(module_idx) else {
+        return Vec::new();
+    };
+    let (Some(slot_index), Some(def)) =
+        (module.slot_index, content.module_defs.get(&module.def_id))
+    else {
+        return Vec::new();
+    };
+    let own_type = def.behavior.type_name();
+    let neighbors = neighbor_types(station, frame, slot_index, Some(module_idx), content);
+
+    let mut modifiers = Vec::new();
+    for rule in &content.adjacency_rules {
+        if rule.forbidden || rule.module_type != own_type {
+            continue;
+        }
+        if !neighbors.contains(&rule.neighbor_type.as_str()) {
+            continue;
+        }
+        modifiers.extend(rule.bonuses.iter().map(|bonus| Modifier {
+            stat: bonus.stat,
+            op: bonus.op,
+            value: bonus.value,
+            source: ModifierSource::Adjacency(rule.neighbor_type.clone()),
+            condition: None,
+        }));
+    }
+    modifiers
+}
+
+/// True when installing `def` into `slot_index` would put it next to a
+/// module that a `forbidden` adjacency rule keeps it away from.
+pub(crate) fn placement_forbidden(
+    station: &StationState,
+    frame: &FrameDef,
+    def: &ModuleDef,
+    slot_index: usize,
+    content: &GameContent,
+) -> bool {
+    let own_type = def.behavior.type_name();
+    let neighbors = neighbor_types(station, frame, slot_index, None, content);
+    content
+        .adjacency_rules
+        .iter()
+        .filter(|rule| rule.forbidden)
+        .any(|rule| {
+            (rule.module_type == own_type && neighbors.contains(&rule.neighbor_type.as_str()))
+                || (rule.neighbor_type == own_type
+                    && neighbors.contains(&rule.module_type.as_str()))
+        })
+}
@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    ElementId, Event, EventEnvelope, GameContent, GameState, ModuleBehaviorDef, StationId,
+    TradeItemSpec,
+};
+
+/// Life support: house the station's crew in its enabled habitats, in
+/// module order, and draw each habitat's consumables for the crew it
+/// houses from station inventory.
+///
+/// Life support fails while crew outnumber habitat capacity or any
+/// consumable is short; nothing is drawn while short. Crewed modules then
+/// run at `life_support_failure_efficiency` (applied in
+/// `update_module_efficiencies`). Stations without habitat modules have no
+/// life support to fail.
+pub(super) fn tick_life_support(
+    state: &mut GameState,
+    station_id: &StationId,
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let current_tick = state.meta.tick;
+    let hours_per_tick = content.constants.minutes_per_tick as f32 / 60.0;
+    let Some(station) = state.stations.get_mut(station_id) else {
+        return;
+    };
+
+    let mut has_habitats = false;
+    let crew: u32 = station.core.crew.values().sum();
+    let mut unhoused = crew;
+    let mut crew_capacity = 0_u32;
+    let mut demand: BTreeMap<ElementId, f32> = BTreeMap::new();
+    for module in &station.core.modules {
+        let Some(ModuleBehaviorDef::Habitat(habitat)) = content
+            .module_defs
+            .get(&module.def_id)
+            .map(|def| &def.behavior)
+        else {
+            continue;
+        };
+        has_habitats = true;
+        if !module.enabled {
+            continue;
+        }
+        let housed = unhoused.min(habitat.crew_capacity);
+        unhoused -= housed;
+        crew_capacity += habitat.crew_capacity;
+        for (element, kg_per_crew_hour) in &habitat.consumables_kg_per_crew_hour {
+            *demand.entry(element.clone()).or_default() +=
+                kg_per_crew_hour * housed as f32 * hours_per_tick;
+        }
+    }
+    if !has_habitats && !station.core.life_support_failing {
+        return;
+    }
+
+    let draws: Vec<TradeItemSpec> = demand
+        .into_iter()
+        .filter(|(_, kg)| *kg > 0.0)
+        .map(|(element, kg)| TradeItemSpec::Material { element, kg })
+        .collect();
+    let short_element = draws
+        .iter()
+        .find(|spec| !crate::trade::has_enough_for_export(&station.core.inventory, spec))
+        .map(|spec| ElementId::from(spec.pricing_key()));
+    if short_element.is_none() && !draws.is_empty() {
+        for spec in &draws {
+            crate::trade::remove_inventory_items(&mut station.core.inventory, spec);
+        }
+        station.invalidate_volume_cache();
+    }
+
+    let failing = has_habitats && (unhoused > 0 || short_element.is_some());
+    let was_failing = station.core.life_support_failing;
+    station.core.life_support_failing = failing;
+    if failing && !was_failing {
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            Event::LifeSupportFailed {
+                station_id: station_id.clone(),
+                crew,
+                crew_capacity,
+                short_element,
+            },
+        ));
+    } else if was_failing && !failing {
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            Event::LifeSupportRestored {
+                station_id: station_id.clone(),
+            },
+        ));
+    }
+}
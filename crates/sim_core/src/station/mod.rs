@@ -1,8 +1,14 @@
 mod assembler;
 mod boiloff;
+mod diagnosis;
+mod drone_bay;
+pub(crate) mod history;
 mod lab;
+pub(crate) mod layout;
+mod life_support;
 mod maintenance;
 mod processor;
+mod production_report;
 mod sensor;
 pub(crate) mod thermal;
 
@@ -14,6 +20,8 @@ use crate::{
 };
 use std::collections::HashMap;
 
+pub use diagnosis::why_not_running;
+
 /// Estimate the total output volume (m3) a recipe would produce given the
 /// consumed lots and their weighted-average composition.
 fn estimate_output_volume_m3(
@@ -156,7 +164,10 @@ fn update_crew_satisfaction(
 }
 
 /// Compute and store efficiency for all modules on a station.
-/// Call after `compute_power_budget` so `power_stalled` flags are set.
+/// Call after `compute_power_budget` so `power_stalled` flags and the
+/// brownout share are set; browned-out consumers run at the reduced share,
+/// and crewed modules at `life_support_failure_efficiency` while life
+/// support is failing.
 fn update_module_efficiencies(
     state: &mut GameState,
     station_id: &StationId,
@@ -167,10 +178,28 @@ fn update_module_efficiencies(
         return;
     };
     let current_tick = state.meta.tick;
-    for module in &mut station.core.modules {
+    let brownout_factor = 1.0 - station.core.power_budget_cache.brownout_shed;
+    let browned_out: Vec<usize> = if brownout_factor < 1.0 {
+        let cache = &station.core.power_budget_cache;
+        cache.consumers.iter().map(|&(idx, _, _, _)| idx).collect()
+    } else {
+        Vec::new()
+    };
+    let life_support_factor = if station.core.life_support_failing {
+        content.constants.life_support_failure_efficiency
+    } else {
+        1.0
+    };
+    for (module_idx, module) in station.core.modules.iter_mut().enumerate() {
         if let Some(def) = content.module_defs.get(&module.def_id) {
             let old_efficiency = module.efficiency;
             module.efficiency = crate::compute_module_efficiency(module, def, &content.constants);
+            if browned_out.contains(&module_idx) {
+                module.efficiency *= brownout_factor;
+            }
+            if !def.crew_requirement.is_empty() {
+                module.efficiency *= life_support_factor;
+            }
             if (module.efficiency - old_efficiency).abs() > f32::EPSILON {
                 events.push(crate::emit(
                     &mut state.counters,
@@ -200,53 +229,75 @@ pub(crate) fn tick_stations(
     ensure_indices(state, content);
     let station_ids: Vec<StationId> = state.stations.keys().cloned().collect();
     let mut scratch_indices: Vec<usize> = Vec::new();
+    let track_hotspots = content.constants.hotspot_report_interval_ticks > 0;
+    let industry = content.subsystems.enabled(crate::Subsystem::Industry);
+    let research = content.subsystems.enabled(crate::Subsystem::Research);
+    let mining = content.subsystems.enabled(crate::Subsystem::Mining);
     for station_id in &station_ids {
+        let phases_before = timings
+            .as_deref()
+            .filter(|_| track_hotspots)
+            .map(TickTimings::station_phases);
+        let first_event = events.len();
         // Update crew satisfaction events (before efficiency recompute)
         update_crew_satisfaction(state, station_id, content, events);
+        life_support::tick_life_support(state, station_id, content, events);
         timed!(
             timings,
             power_budget,
-            compute_power_budget(state, station_id, content, events)
+            compute_power_budget(state, station_id, content)
         );
         // Compute combined efficiency after power budget sets power_stalled flags
         update_module_efficiencies(state, station_id, content, events);
-        timed!(
-            timings,
-            processors,
-            processor::tick_station_modules(
-                state,
-                station_id,
-                content,
-                events,
-                &mut scratch_indices
-            )
-        );
-        timed!(
-            timings,
-            assemblers,
-            assembler::tick_assembler_modules(
-                state,
-                station_id,
-                content,
-                rng,
-                events,
-                &mut scratch_indices
-            )
-        );
+        if industry {
+            timed!(
+                timings,
+                processors,
+                processor::tick_station_modules(
+                    state,
+                    station_id,
+                    content,
+                    events,
+                    &mut scratch_indices
+                )
+            );
+            timed!(
+                timings,
+                assemblers,
+                assembler::tick_assembler_modules(
+                    state,
+                    station_id,
+                    content,
+                    rng,
+                    events,
+                    &mut scratch_indices
+                )
+            );
+        }
+        if mining {
+            drone_bay::tick_drone_bay_modules(state, station_id, content, events);
+        }
         timed!(
             timings,
             sensors,
             sensor::tick_sensor_array_modules(state, station_id, content, rng, events)
         );
+        if research {
+            timed!(
+                timings,
+                labs,
+                lab::tick_lab_modules(state, station_id, content, events)
+            );
+        }
         timed!(
             timings,
-            labs,
-            lab::tick_lab_modules(state, station_id, content, events)
+            maintenance,
+            maintenance::tick_maintenance_modules(state, station_id, content, events)
         );
         timed!(
             timings,
-            maintenance,
-            maintenance::tick_maintenance_modules(state, station_id, content, events)
+            power_budget,
+            settle_power_budget(state, station_id, content, events)
         );
         timed!(
             timings,
@@ -259,6 +310,21 @@ pub(crate) fn tick_stations(
             boiloff,
             boiloff::apply_boiloff(state, station_id, content, events)
         );
+        // Step 3.8: Production reports over this station's run events.
+        production_report::update_production_reports(
+            state,
+            station_id,
+            content,
+            events,
+            first_event,
+        );
+        // Step 3.9: Idle diagnoses for processors that would not run.
+        diagnosis::emit_idle_diagnoses(state, station_id, content, events);
+        if let (Some(before), Some(after)) = (phases_before, timings.as_deref()) {
+            state
+                .hotspots
+                .record_station(station_id, &before, &after.station_phases());
+        }
     }
 }
 
@@ -302,13 +368,17 @@ pub(crate) fn tick_ground_facilities(
             core: std::mem::take(&mut gf.core),
             frame_id: None,
             leaders: Vec::new(),
+            owner: crate::PrincipalId("principal_autopilot".to_string()),
+            expansions: crate::StationExpansions::default(),
+            power_policy: crate::PowerPolicy::default(),
+            history: std::collections::VecDeque::new(),
         };
         state.stations.insert(proxy_station_id.clone(), proxy);
 
         // Tick using existing subsystem pipeline.
         ensure_station_index(state, &proxy_station_id, content);
         update_crew_satisfaction(state, &proxy_station_id, content, events);
-        compute_power_budget(state, &proxy_station_id, content, events);
+        compute_power_budget(state, &proxy_station_id, content);
         update_module_efficiencies(state, &proxy_station_id, content, events);
         processor::tick_station_modules(
             state,
@@ -328,6 +398,7 @@ pub(crate) fn tick_ground_facilities(
         sensor::tick_sensor_array_modules(state, &proxy_station_id, content, rng, events);
         lab::tick_lab_modules(state, &proxy_station_id, content, events);
         maintenance::tick_maintenance_modules(state, &proxy_station_id, content, events);
+        settle_power_budget(state, &proxy_station_id, content, events);
         thermal::tick_thermal(state, &proxy_station_id, content, events);
         boiloff::apply_boiloff(state, &proxy_station_id, content, events);
 
@@ -439,20 +510,22 @@ fn resolve_solar_output(
 /// Only called when `power_budget_cache.is_valid()` is false.
 fn rebuild_power_cache(
     station: &crate::StationState,
+    storm_mult: f32,
     content: &GameContent,
     global_modifiers: &crate::modifiers::ModifierSet,
 ) -> crate::PowerBudgetCache {
-    let solar_intensity = content
-        .solar_system
-        .bodies
-        .iter()
-        .find(|b| b.id == station.position.parent_body)
-        .map_or(1.0, |b| b.solar_intensity);
+    let solar_intensity = storm_mult
+        * content
+            .solar_system
+            .bodies
+            .iter()
+            .find(|b| b.id == station.position.parent_body)
+            .map_or(1.0, |b| b.solar_intensity);
 
     let mut generated_kw = 0.0_f32;
-    let mut consumed_kw = 0.0_f32;
+    let mut idle_kw = 0.0_f32;
     let mut has_power_infrastructure = false;
-    let mut consumers: Vec<(usize, u8, f32)> = Vec::new();
+    let mut consumers: Vec<(usize, u8, f32, u64)> = Vec::new();
     let mut battery_entries: Vec<(usize, crate::BatteryDef, f32)> = Vec::new();
     let mut solar_wear_targets: Vec<(usize, f32)> = Vec::new();
     let mut wear_band_snapshot: Vec<(usize, u8)> = Vec::new();
@@ -472,6 +545,7 @@ fn rebuild_power_cache(
         let Some(def) = content.module_defs.get(&module.def_id) else {
             continue;
         };
+        idle_kw += def.power_idle_kw * power_consumption_mult;
 
         match &def.behavior {
             crate::ModuleBehaviorDef::SolarArray(solar_def) => {
@@ -483,7 +557,7 @@ fn rebuild_power_cache(
                     &content.constants,
                     global_modifiers,
                 );
-                consumed_kw += def.power_consumption_per_run * power_consumption_mult;
+                idle_kw += def.power_consumption_per_run * power_consumption_mult;
                 if def.wear_per_run > 0.0 {
                     solar_wear_targets.push((module_index, def.wear_per_run));
                 }
@@ -511,30 +585,39 @@ fn rebuild_power_cache(
                 let mut scaled_battery = battery_def.clone();
                 scaled_battery.capacity_kwh *= battery_capacity_mult;
                 battery_entries.push((module_index, scaled_battery, efficiency));
-                consumed_kw += def.power_consumption_per_run * power_consumption_mult;
+                idle_kw += def.power_consumption_per_run * power_consumption_mult;
                 wear_band_snapshot.push((
                     module_index,
                     crate::wear::wear_band(module.wear.wear, &content.constants),
                 ));
             }
             _ => {
-                let effective_consumption = def.power_consumption_per_run * power_consumption_mult;
-                consumed_kw += effective_consumption;
-                if let Some(priority) = def.power_priority() {
-                    consumers.push((module_index, priority, effective_consumption));
+                let mut run_kw = def.power_consumption_per_run * power_consumption_mult;
+                // Budget for every assembler slot running on the same tick.
+                if let crate::ModuleBehaviorDef::Assembler(assembler_def) = &def.behavior {
+                    #[allow(clippy::cast_precision_loss)] // slot counts are tiny
+                    let slots = assembler_def.parallel_slots.max(1) as f32;
+                    run_kw *= slots;
+                }
+                match (def.behavior.interval_ticks(), def.power_priority()) {
+                    (Some(interval), Some(priority)) => {
+                        consumers.push((module_index, priority, run_kw, interval));
+                    }
+                    // No run interval — the module "runs" every tick.
+                    _ => idle_kw += run_kw,
                 }
             }
         }
     }
 
     // Pre-sort consumers by priority so we don't need to sort every tick.
-    consumers.sort_by_key(|&(_, priority, _)| priority);
+    consumers.sort_by_key(|&(_, priority, _, _)| priority);
 
     let enabled_count = station.core.modules.iter().filter(|m| m.enabled).count();
 
     crate::PowerBudgetCache {
         generated_kw,
-        consumed_kw,
+        idle_kw,
         has_power_infrastructure,
         consumers,
         battery_entries,
@@ -561,7 +644,9 @@ fn ensure_power_cache(state: &mut GameState, station_id: &StationId, content: &G
             != (station.core.modules.len(), enabled_count);
 
     if needs_rebuild {
-        let mut cache = rebuild_power_cache(station, content, &state.modifiers);
+        let storm_mult =
+            crate::storms::solar_intensity_mult(state, &station.position.parent_body, content);
+        let mut cache = rebuild_power_cache(station, storm_mult, content, &state.modifiers);
         cache.mark_valid();
         if let Some(station) = state.stations.get_mut(station_id) {
             station.core.power_budget_cache = cache;
@@ -580,8 +665,15 @@ fn apply_solar_wear_and_check_bands(
         return;
     };
     let wear_targets = station.core.power_budget_cache.solar_wear_targets.clone();
+    let storm_mult = crate::storms::station_wear_mult(state, station_id, content);
     for (module_idx, wear_per_run) in &wear_targets {
-        apply_wear(state, station_id, *module_idx, *wear_per_run, events);
+        apply_wear(
+            state,
+            station_id,
+            *module_idx,
+            *wear_per_run * storm_mult,
+            events,
+        );
     }
 
     // Check if any power-related module crossed a wear band boundary.
@@ -607,14 +699,131 @@ fn apply_solar_wear_and_check_bands(
     }
 }
 
-/// Compute the power budget for a station, store it in `PowerState`, and
-/// mark modules as `power_stalled` when there is a deficit.
+/// Pre-run power allocation: decide which due modules can be powered this
+/// tick and mark the rest `power_stalled`, lowest priority first.
+///
+/// Demand is idle draw plus the per-run draw of modules whose timer reaches
+/// their interval this tick; supply is generation plus what batteries can
+/// discharge. Energy is only booked afterwards by `settle_power_budget`,
+/// using the modules that actually completed a run.
 ///
 /// Uses a cached generation/consumption summary when available. The cache
 /// is rebuilt only when modules change (install/uninstall/enable/disable)
 /// or when a power-relevant module crosses a wear band boundary.
-/// Battery buffering and stall logic run every tick regardless.
-fn compute_power_budget(
+fn compute_power_budget(state: &mut GameState, station_id: &StationId, content: &GameContent) {
+    ensure_power_cache(state, station_id, content);
+
+    let Some(station) = state.stations.get_mut(station_id) else {
+        return;
+    };
+    let cache = &station.core.power_budget_cache;
+    let battery_supply_kw: f32 = cache
+        .battery_entries
+        .iter()
+        .map(
+            |(idx, def, _)| match &station.core.modules[*idx].kind_state {
+                crate::ModuleKindState::Battery(bs) => bs.charge_kwh.min(def.discharge_rate_kw),
+                _ => 0.0,
+            },
+        )
+        .sum();
+    let supply_kw = cache.generated_kw + battery_supply_kw;
+    let has_power_infrastructure = cache.has_power_infrastructure;
+
+    // Consumers due this tick (timer will reach interval in `should_run`).
+    let (deferrable_due, due): (Vec<_>, Vec<_>) = cache
+        .consumers
+        .iter()
+        .filter(|&&(idx, _, _, interval)| {
+            station.core.modules[idx]
+                .kind_state
+                .ticks_since_last_run()
+                .is_some_and(|ticks| ticks + 1 >= interval)
+        })
+        .map(|&(idx, _, run_kw, _)| (idx, run_kw))
+        .partition(|&(idx, _)| {
+            content
+                .module_defs
+                .get(&station.core.modules[idx].def_id)
+                .is_some_and(|def| def.deferrable)
+        });
+    let firm_kw = cache.idle_kw + due.iter().map(|&(_, run_kw)| run_kw).sum::<f32>();
+
+    for module in &mut station.core.modules {
+        module.power_stalled = false;
+    }
+
+    // Deferrable runs take only what is left after every other load, and
+    // only battery charge above the reserve. The rest wait, highest
+    // priority admitted first.
+    let mut deferred_kw = 0.0_f32;
+    let mut admitted_kw = 0.0_f32;
+    if has_power_infrastructure && !deferrable_due.is_empty() {
+        let reserve = content.constants.power_deferrable_battery_reserve;
+        let cache = &station.core.power_budget_cache;
+        let battery_spare_kw: f32 = cache
+            .battery_entries
+            .iter()
+            .map(
+                |(idx, def, _)| match &station.core.modules[*idx].kind_state {
+                    crate::ModuleKindState::Battery(bs) => (bs.charge_kwh
+                        - def.capacity_kwh * reserve)
+                        .max(0.0)
+                        .min(def.discharge_rate_kw),
+                    _ => 0.0,
+                },
+            )
+            .sum();
+        let mut spare_kw = cache.generated_kw + battery_spare_kw - firm_kw;
+        for &(module_index, run_kw) in deferrable_due.iter().rev() {
+            if run_kw <= spare_kw {
+                spare_kw -= run_kw;
+                admitted_kw += run_kw;
+            } else {
+                station.core.modules[module_index].power_stalled = true;
+                deferred_kw += run_kw;
+            }
+        }
+    } else {
+        admitted_kw = deferrable_due.iter().map(|&(_, run_kw)| run_kw).sum();
+    }
+    let demand_kw = firm_kw + admitted_kw;
+    let mut stalled_kw = 0.0_f32;
+    let mut brownout_shed = 0.0_f32;
+    let mut shed_kw = 0.0_f32;
+    if demand_kw > supply_kw && has_power_infrastructure {
+        let mut remaining = demand_kw - supply_kw;
+        match station.power_policy {
+            crate::PowerPolicy::Stall => {
+                for &(module_index, run_kw) in &due {
+                    if remaining <= 0.0 {
+                        break;
+                    }
+                    station.core.modules[module_index].power_stalled = true;
+                    stalled_kw += run_kw;
+                    remaining -= run_kw;
+                }
+            }
+            crate::PowerPolicy::Brownout => {
+                let due_kw: f32 = due.iter().map(|&(_, run_kw)| run_kw).sum();
+                if due_kw > 0.0 {
+                    brownout_shed = (remaining / due_kw).min(1.0);
+                    shed_kw = due_kw * brownout_shed;
+                }
+            }
+        }
+    }
+    station.core.power_budget_cache.stalled_kw = stalled_kw;
+    station.core.power_budget_cache.brownout_shed = brownout_shed;
+    station.core.power_budget_cache.brownout_kw = shed_kw;
+    station.core.power_budget_cache.deferred_kw = deferred_kw;
+    station.core.power_budget_cache.active_kw = 0.0;
+}
+
+/// Post-run power settlement: book idle draw plus the per-run draw of
+/// modules that completed this tick, buffer the surplus or shortfall
+/// through batteries, and store the result in `PowerState`.
+fn settle_power_budget(
     state: &mut GameState,
     station_id: &StationId,
     content: &GameContent,
@@ -624,17 +833,14 @@ fn compute_power_budget(
         return;
     };
     let prev_power = station.core.power.clone();
-
-    ensure_power_cache(state, station_id, content);
-
-    // Read cached values and build per-tick battery list with live charge.
-    let Some(station) = state.stations.get(station_id) else {
-        return;
-    };
     let cache = &station.core.power_budget_cache;
     let generated_kw = cache.generated_kw;
-    let consumed_kw = cache.consumed_kw;
-    let has_power_infrastructure = cache.has_power_infrastructure;
+    let idle_kw = cache.idle_kw;
+    let active_kw = cache.active_kw;
+    let stalled_kw = cache.stalled_kw;
+    let brownout_kw = cache.brownout_kw;
+    let deferred_kw = cache.deferred_kw;
+    let consumed_kw = idle_kw + active_kw;
 
     // Build per-tick battery list with live charge values.
     let batteries: Vec<(usize, crate::BatteryDef, f32, f32)> = cache
@@ -649,40 +855,28 @@ fn compute_power_budget(
         })
         .collect();
 
-    // Clone consumer list for stall logic (small vec — typically 5-8 entries).
-    let consumers = cache.consumers.clone();
-
     let raw_surplus = (generated_kw - consumed_kw).max(0.0);
     let raw_deficit = (consumed_kw - generated_kw).max(0.0);
 
     let (battery_discharge_kw, battery_charge_kw, battery_stored_kwh) =
         apply_battery_buffering(state, station_id, &batteries, raw_surplus, raw_deficit);
-    let deficit_kw = (raw_deficit - battery_discharge_kw).max(0.0);
+    let deficit_kw = (raw_deficit - battery_discharge_kw).max(0.0) + stalled_kw + brownout_kw;
 
-    // Apply stalls and update PowerState.
     let Some(station) = state.stations.get_mut(station_id) else {
         return;
     };
-    for module in &mut station.core.modules {
-        module.power_stalled = false;
-    }
-    if deficit_kw > 0.0 && has_power_infrastructure {
-        let mut remaining = deficit_kw;
-        for &(module_index, _, consumption) in &consumers {
-            if remaining <= 0.0 {
-                break;
-            }
-            station.core.modules[module_index].power_stalled = true;
-            remaining -= consumption;
-        }
-    }
     station.core.power = crate::PowerState {
         generated_kw,
         consumed_kw,
         deficit_kw,
+        idle_kw,
+        active_kw,
         battery_discharge_kw,
         battery_charge_kw,
         battery_stored_kwh,
+        stalled_kw,
+        brownout_kw,
+        deferred_kw,
     };
 
     if station.core.power != prev_power {
@@ -710,6 +904,8 @@ pub(crate) struct ModuleTickContext<'a> {
     pub power_needed: f32,
     pub wear_per_run: f32,
     pub efficiency: f32,
+    /// Assembler slot this run belongs to; 0 for every other module kind.
+    pub slot: u32,
 }
 
 /// Reason a module stalled (distinct from "skipped").
@@ -767,6 +963,7 @@ fn extract_context<'a>(
         power_needed: def.power_consumption_per_run,
         wear_per_run: def.wear_per_run,
         efficiency: module.efficiency,
+        slot: 0,
     })
 }
 
@@ -819,6 +1016,17 @@ fn apply_wear(
     }
 }
 
+/// Run timer for the context's module, resolving assembler slots.
+fn run_timer_mut<'a>(
+    kind_state: &'a mut crate::ModuleKindState,
+    ctx: &ModuleTickContext,
+) -> Option<&'a mut u64> {
+    match kind_state {
+        crate::ModuleKindState::Assembler(s) => s.slot_ticks_mut(ctx.slot),
+        other => other.ticks_since_last_run_mut(),
+    }
+}
+
 /// Increment timer and check if the module should run this tick.
 /// Returns true if: timer >= interval AND station has enough power.
 fn should_run(state: &mut GameState, ctx: &ModuleTickContext) -> bool {
@@ -827,7 +1035,7 @@ fn should_run(state: &mut GameState, ctx: &ModuleTickContext) -> bool {
             return false;
         };
         let module = &mut station.core.modules[ctx.module_idx];
-        let Some(timer) = module.kind_state.ticks_since_last_run_mut() else {
+        let Some(timer) = run_timer_mut(&mut module.kind_state, ctx) else {
             return false;
         };
         *timer += 1;
@@ -852,7 +1060,7 @@ fn reset_timer(state: &mut GameState, ctx: &ModuleTickContext) {
         return;
     };
     let module = &mut station.core.modules[ctx.module_idx];
-    if let Some(timer) = module.kind_state.ticks_since_last_run_mut() {
+    if let Some(timer) = run_timer_mut(&mut module.kind_state, ctx) {
         *timer = 0;
     }
 }
@@ -876,27 +1084,24 @@ fn handle_stall_transition(
 
         match reason {
             StallReason::VolumeCap { .. } => {
-                let was_stalled = match &module.kind_state {
-                    crate::ModuleKindState::Processor(s) => s.stalled,
-                    crate::ModuleKindState::Assembler(s) => s.stalled,
-                    _ => false,
+                let stalled = match &mut module.kind_state {
+                    crate::ModuleKindState::Processor(s) => Some(&mut s.stalled),
+                    crate::ModuleKindState::Assembler(s) => {
+                        s.slot_flags_mut(ctx.slot).map(|(stalled, _)| stalled)
+                    }
+                    crate::ModuleKindState::DroneBay(s) => Some(&mut s.stalled),
+                    _ => None,
                 };
-                match &mut module.kind_state {
-                    crate::ModuleKindState::Processor(s) => s.stalled = true,
-                    crate::ModuleKindState::Assembler(s) => s.stalled = true,
-                    _ => {}
-                }
-                !was_stalled
+                stalled.is_some_and(|flag| !std::mem::replace(flag, true))
             }
             StallReason::StockCap => {
-                let was_capped = match &module.kind_state {
-                    crate::ModuleKindState::Assembler(s) => s.capped,
-                    _ => false,
+                let capped = match &mut module.kind_state {
+                    crate::ModuleKindState::Assembler(s) => {
+                        s.slot_flags_mut(ctx.slot).map(|(_, capped)| capped)
+                    }
+                    _ => None,
                 };
-                if let crate::ModuleKindState::Assembler(s) = &mut module.kind_state {
-                    s.capped = true;
-                }
-                !was_capped
+                capped.is_some_and(|flag| !std::mem::replace(flag, true))
             }
             StallReason::DataStarved => {
                 let was_starved = match &module.kind_state {
@@ -928,10 +1133,12 @@ fn handle_stall_transition(
                 station_id: ctx.station_id.clone(),
                 module_id: ctx.module_id.clone(),
                 shortfall_m3: *shortfall_m3,
+                slot: ctx.slot,
             },
             StallReason::StockCap => Event::AssemblerCapped {
                 station_id: ctx.station_id.clone(),
                 module_id: ctx.module_id.clone(),
+                slot: ctx.slot,
             },
             StallReason::DataStarved => Event::LabStarved {
                 station_id: ctx.station_id.clone(),
@@ -971,27 +1178,23 @@ fn handle_resume_if_stalled(
         let module = &mut station.core.modules[ctx.module_idx];
 
         match &mut module.kind_state {
-            crate::ModuleKindState::Processor(s) => {
-                if s.stalled {
-                    s.stalled = false;
-                    emit_resumed = true;
-                }
+            crate::ModuleKindState::Processor(s) if s.stalled => {
+                s.stalled = false;
+                emit_resumed = true;
+            }
+            crate::ModuleKindState::DroneBay(s) if s.stalled => {
+                s.stalled = false;
+                emit_resumed = true;
             }
             crate::ModuleKindState::Assembler(s) => {
-                if s.stalled {
-                    s.stalled = false;
-                    emit_resumed = true;
-                }
-                if s.capped {
-                    s.capped = false;
-                    emit_uncapped = true;
+                if let Some((stalled, capped)) = s.slot_flags_mut(ctx.slot) {
+                    emit_resumed = std::mem::take(stalled);
+                    emit_uncapped = std::mem::take(capped);
                 }
             }
-            crate::ModuleKindState::Lab(s) => {
-                if s.starved {
-                    s.starved = false;
-                    emit_lab_resumed = true;
-                }
+            crate::ModuleKindState::Lab(s) if s.starved => {
+                s.starved = false;
+                emit_lab_resumed = true;
             }
             _ => {}
         }
@@ -1005,6 +1208,7 @@ fn handle_resume_if_stalled(
             Event::ModuleResumed {
                 station_id: ctx.station_id.clone(),
                 module_id: ctx.module_id.clone(),
+                slot: ctx.slot,
             },
         ));
     }
@@ -1015,6 +1219,7 @@ fn handle_resume_if_stalled(
             Event::AssemblerUncapped {
                 station_id: ctx.station_id.clone(),
                 module_id: ctx.module_id.clone(),
+                slot: ctx.slot,
             },
         ));
     }
@@ -1040,6 +1245,16 @@ fn apply_run_result(
 ) {
     match outcome {
         RunOutcome::Completed => {
+            // Book this run's energy for `settle_power_budget`; a
+            // browned-out run only draws its share.
+            let run_kw = ctx.power_needed
+                * state
+                    .modifiers
+                    .resolve_f32(crate::modifiers::StatId::PowerConsumption, 1.0);
+            if let Some(station) = state.stations.get_mut(&ctx.station_id) {
+                let cache = &mut station.core.power_budget_cache;
+                cache.active_kw += run_kw * (1.0 - cache.brownout_shed);
+            }
             // Clear stall flag if was stalled, emit resume event
             handle_resume_if_stalled(state, ctx, events);
             // Reset timer
@@ -1059,6 +1274,15 @@ fn apply_run_result(
                 f64::from(heat_multiplier),
                 crate::modifiers::ModifierSource::Thermal,
             ));
+            wear_mods.add(crate::modifiers::Modifier::pct_mult(
+                crate::modifiers::StatId::WearRate,
+                f64::from(crate::storms::station_wear_mult(
+                    state,
+                    &ctx.station_id,
+                    content,
+                )),
+                crate::modifiers::ModifierSource::Environment,
+            ));
             let effective_wear = wear_mods.resolve_with_f32(
                 crate::modifiers::StatId::WearRate,
                 ctx.wear_per_run,
@@ -1098,10 +1322,9 @@ fn apply_run_result(
 mod framework_tests {
     use super::*;
     use crate::test_fixtures::ModuleDefBuilder;
-    use crate::AHashMap;
     use crate::*;
     use rand::SeedableRng;
-    use std::collections::{HashMap, HashSet};
+    use std::collections::HashMap;
 
     fn test_content_with_processor() -> GameContent {
         let mut content = crate::test_fixtures::base_content();
@@ -1125,82 +1348,27 @@ mod framework_tests {
 
     fn test_state_with_module(content: &GameContent, kind_state: ModuleKindState) -> GameState {
         let station_id = StationId("station_test".to_string());
-        GameState {
-            meta: MetaState {
-                tick: 10,
-                seed: 42,
-                schema_version: 1,
-                content_version: content.content_version.clone(),
-            },
-            scan_sites: vec![],
-            asteroids: std::collections::BTreeMap::new(),
-            ships: std::collections::BTreeMap::new(),
-            stations: [(
-                station_id.clone(),
-                StationState {
-                    id: station_id,
-                    position: crate::test_fixtures::test_position(),
-                    core: FacilityCore {
-                        inventory: vec![],
-                        cargo_capacity_m3: 10_000.0,
-                        power_available_per_tick: 100.0,
-                        modules: vec![ModuleState {
-                            id: ModuleInstanceId("refinery_inst_0001".to_string()),
-                            def_id: "module_refinery".to_string(),
-                            enabled: true,
-                            kind_state,
-                            wear: WearState::default(),
-                            power_stalled: false,
-                            module_priority: 0,
-                            assigned_crew: Default::default(),
-                            efficiency: 1.0,
-                            prev_crew_satisfied: true,
-                            thermal: None,
-                            slot_index: None,
-                        }],
-                        modifiers: crate::modifiers::ModifierSet::default(),
-                        crew: Default::default(),
-                        thermal_links: Vec::new(),
-                        power: PowerState::default(),
-                        cached_inventory_volume_m3: None,
-                        module_type_index: crate::ModuleTypeIndex::default(),
-                        module_id_index: HashMap::new(),
-                        power_budget_cache: crate::PowerBudgetCache::default(),
-                    },
-                    leaders: Vec::new(),
-                    frame_id: None,
-                },
-            )]
-            .into_iter()
-            .collect(),
-            ground_facilities: std::collections::BTreeMap::new(),
-            satellites: std::collections::BTreeMap::new(),
-            research: ResearchState {
-                unlocked: HashSet::new(),
-                data_pool: AHashMap::default(),
-                evidence: AHashMap::default(),
-                action_counts: AHashMap::default(),
-            },
-            balance: 0.0,
-            export_revenue_total: 0.0,
-            export_count: 0,
-            counters: Counters {
-                next_event_id: 0,
-                next_command_id: 0,
-                next_asteroid_id: 0,
-                next_lot_id: 0,
-                next_module_instance_id: 0,
-                ..Default::default()
-            },
-            modifiers: crate::modifiers::ModifierSet::default(),
-            events: crate::sim_events::SimEventState::default(),
-            propellant_consumed_total: 0.0,
-            transfer_volume_kg: 0.0,
-            transfer_count: 0,
-            progression: Default::default(),
-            strategy_config: Default::default(),
-            body_cache: AHashMap::default(),
-        }
+        let mut station = crate::test_fixtures::test_station(station_id.clone());
+        station.core.modules = vec![ModuleState {
+            id: ModuleInstanceId("refinery_inst_0001".to_string()),
+            def_id: "module_refinery".to_string(),
+            enabled: true,
+            kind_state,
+            wear: WearState::default(),
+            power_stalled: false,
+            module_priority: 0,
+            assigned_crew: std::collections::BTreeMap::default(),
+            efficiency: 1.0,
+            prev_crew_satisfied: true,
+            thermal: None,
+            slot_index: None,
+        }];
+        let mut state = GameState {
+            stations: [(station_id, station)].into_iter().collect(),
+            ..crate::test_fixtures::base_state(content)
+        };
+        state.meta.tick = 10;
+        state
     }
 
     #[test]
@@ -1273,7 +1441,11 @@ mod framework_tests {
                 .name("Storage")
                 .mass(1000.0)
                 .volume(5.0)
-                .behavior(ModuleBehaviorDef::Storage { capacity_m3: 500.0 })
+                .behavior(ModuleBehaviorDef::Storage {
+                    capacity_m3: 500.0,
+                    refrigerated: false,
+                    climate_controlled: false,
+                })
                 .build(),
         );
         assert!(extract_context(&state, &station_id, 0, &content2).is_none());
@@ -1650,6 +1822,8 @@ mod framework_tests {
                 module_type_index: ModuleTypeIndex::default(),
                 module_id_index: HashMap::new(),
                 power_budget_cache: PowerBudgetCache::default(),
+                production_windows: std::collections::BTreeMap::new(),
+                life_support_failing: false,
             },
             launch_transits: Vec::new(),
         };
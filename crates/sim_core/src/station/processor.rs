@@ -1,6 +1,6 @@
 use crate::{
     composition::{blend_slag_composition, merge_material_lot, weighted_composition},
-    thermal, Event, EventEnvelope, GameContent, GameState, InputAmount, InventoryItem,
+    thermal, Event, EventEnvelope, GameContent, GameState, IdleReason, InputAmount, InventoryItem,
     MaterialThermalProps, ModuleBehaviorDef, ModuleKindState, OutputSpec, Phase, PortDirection,
     PortFilter, QualityFormula, StationId, StationState, YieldFormula,
 };
@@ -156,6 +156,101 @@ fn execute(
     super::RunOutcome::Completed
 }
 
+/// Processor blockers for `why_not_running`, checked in `execute`'s order
+/// without mutating state or emitting events.
+pub(super) fn diagnose(
+    state: &GameState,
+    station: &StationState,
+    module_idx: usize,
+    def: &crate::ModuleDef,
+    content: &GameContent,
+    reasons: &mut Vec<IdleReason>,
+) {
+    let module = &station.core.modules[module_idx];
+    let (ModuleBehaviorDef::Processor(processor_def), ModuleKindState::Processor(ps)) =
+        (&def.behavior, &module.kind_state)
+    else {
+        return;
+    };
+    let recipe = ps
+        .selected_recipe
+        .as_ref()
+        .filter(|id| processor_def.recipes.contains(id))
+        .or_else(|| processor_def.recipes.first())
+        .and_then(|id| content.recipes.get(id));
+    let Some(recipe) = recipe else {
+        reasons.push(IdleReason::NoRecipe);
+        return;
+    };
+    if let Some(tech_id) = &recipe.required_tech {
+        if !state.research.unlocked.iter().any(|t| t == tech_id) {
+            reasons.push(IdleReason::AwaitingTech {
+                tech_id: tech_id.clone(),
+            });
+        }
+    }
+
+    let ctx = super::ModuleTickContext {
+        station_id: station.id.clone(),
+        module_idx,
+        module_id: module.id.clone(),
+        def,
+        interval: processor_def.processing_interval_ticks,
+        power_needed: def.power_consumption_per_run,
+        wear_per_run: def.wear_per_run,
+        efficiency: module.efficiency,
+        slot: 0,
+    };
+    let input_filter = recipe.inputs.first().map(|i| &i.filter);
+    let input_container_idx = find_linked_input_container(station, &ctx.module_id, def);
+    let input_kg = scan_input_kg(state, &station.id, input_container_idx, input_filter);
+    let min_kg = content.constants.min_meaningful_kg;
+    if input_kg < ps.threshold_kg || input_kg < min_kg {
+        reasons.push(IdleReason::BelowThreshold {
+            input_kg,
+            threshold_kg: ps.threshold_kg,
+        });
+    }
+    let thermal_eff = match check_thermal_gate(state, &ctx, recipe) {
+        Ok((efficiency, _)) => efficiency,
+        Err(super::RunOutcome::Stalled(super::StallReason::TooCold {
+            current_temp_mk,
+            required_temp_mk,
+        })) => {
+            reasons.push(IdleReason::TooCold {
+                current_temp_mk,
+                required_temp_mk,
+            });
+            1.0
+        }
+        Err(_) => 1.0,
+    };
+
+    let Some(InputAmount::Kg(rate_kg)) = recipe.inputs.first().map(|i| &i.amount) else {
+        return;
+    };
+    let (peeked_kg, lots) =
+        peek_from_source(state, &ctx, input_container_idx, *rate_kg, input_filter);
+    if peeked_kg < min_kg
+        || find_linked_output_container(station, content, &ctx.module_id, def).is_some()
+    {
+        return;
+    }
+    let lot_refs: Vec<(&HashMap<String, f32>, f32)> =
+        lots.iter().map(|(comp, kg)| (comp, *kg)).collect();
+    let output_volume =
+        estimate_output_volume_m3(recipe, &weighted_composition(&lot_refs), peeked_kg, content)
+            * thermal_eff;
+    let current_used = station
+        .core
+        .cached_inventory_volume_m3
+        .unwrap_or_else(|| crate::inventory_volume_m3(&station.core.inventory, content));
+    let shortfall_m3 = (current_used + output_volume) - station.core.cargo_capacity_m3;
+    if shortfall_m3 > 0.0 {
+        reasons.push(IdleReason::StorageFull { shortfall_m3 });
+    }
+}
+
 /// Shared context for processor output emission helpers.
 struct ProcessorRunCtx<'a> {
     station_id: &'a StationId,
@@ -233,11 +328,19 @@ fn find_linked_input_container(
 }
 
 fn build_processor_modifiers(
-    module_efficiency: f32,
+    ctx: &super::ModuleTickContext,
+    state: &GameState,
+    content: &GameContent,
     thermal_efficiency: f32,
     thermal_quality: f32,
 ) -> crate::modifiers::ModifierSet {
+    let module_efficiency = ctx.efficiency;
     let mut mods = crate::modifiers::ModifierSet::new();
+    if let Some(station) = state.stations.get(&ctx.station_id) {
+        for modifier in super::layout::adjacency_modifiers(station, ctx.module_idx, content) {
+            mods.add(modifier);
+        }
+    }
     mods.add(crate::modifiers::Modifier::pct_mult(
         crate::modifiers::StatId::ProcessingYield,
         f64::from(module_efficiency),
@@ -271,10 +374,17 @@ fn resolve_processor_run(
     let Some(recipe) = content.recipes.get(recipe_id) else {
         return;
     };
-    let rate_kg = match recipe.inputs.first().map(|i| &i.amount) {
+    let base_rate_kg = match recipe.inputs.first().map(|i| &i.amount) {
         Some(InputAmount::Kg(kg)) => *kg,
         _ => return,
     };
+    let proc_mods =
+        build_processor_modifiers(ctx, state, content, thermal_efficiency, thermal_quality);
+    let rate_kg = proc_mods.resolve_with_f32(
+        crate::modifiers::StatId::ProcessingThroughput,
+        base_rate_kg,
+        &state.modifiers,
+    );
     let input_filter = recipe.inputs.first().map(|i| &i.filter).cloned();
     let min_kg = content.constants.min_meaningful_kg;
 
@@ -301,7 +411,6 @@ fn resolve_processor_run(
         }
     });
 
-    let proc_mods = build_processor_modifiers(ctx.efficiency, thermal_efficiency, thermal_quality);
     let run_ctx = ProcessorRunCtx {
         station_id: &ctx.station_id,
         module_id: &ctx.module_id,
@@ -591,7 +700,7 @@ fn emit_slag_output(
             {
                 let blended =
                     blend_slag_composition(existing_comp, *existing_kg, &slag_composition, slag_kg);
-                *existing_kg += slag_kg;
+                crate::fixed::add_kg(existing_kg, slag_kg);
                 *existing_comp = blended;
             } else {
                 station.core.inventory.push(InventoryItem::Slag {
@@ -836,7 +945,8 @@ fn consume_ore_fifo_with_lots(
                 remaining -= take;
                 consumed_kg += take;
                 lots.push((composition.clone(), take));
-                let leftover = kg - take;
+                let mut leftover = kg;
+                crate::fixed::add_kg(&mut leftover, -take);
                 if leftover > min_meaningful_kg {
                     new_inventory.push(InventoryItem::Ore {
                         lot_id,
@@ -856,7 +966,8 @@ fn consume_ore_fifo_with_lots(
                 remaining -= take;
                 consumed_kg += take;
                 lots.push((HashMap::from([(element.clone(), 1.0)]), take));
-                let leftover = kg - take;
+                let mut leftover = kg;
+                crate::fixed::add_kg(&mut leftover, -take);
                 if leftover > min_meaningful_kg {
                     new_inventory.push(InventoryItem::Material {
                         element,
@@ -940,8 +1051,7 @@ fn slag_composition_from_avg(
 mod tests {
     use super::*;
     use crate::test_fixtures::ModuleDefBuilder;
-    use crate::AHashMap;
-    use crate::{AsteroidId, FacilityCore, InventoryItem, LotId};
+    use crate::{AsteroidId, InventoryItem, LotId};
 
     #[test]
     fn peek_ore_fifo_does_not_mutate() {
@@ -1000,11 +1110,9 @@ mod tests {
     // ── Thermal recipe gating integration tests ──────────────────────
 
     use crate::{
-        Counters, InputFilter, MetaState, ModuleInstanceId, ModuleState, PowerState, ProcessorDef,
-        ProcessorState, RecipeId, RecipeInput, RecipeThermalReq, StationState, ThermalDef,
-        ThermalState, WearState,
+        InputFilter, ModuleInstanceId, ModuleState, ProcessorDef, ProcessorState, RecipeId,
+        RecipeInput, RecipeThermalReq, ThermalDef, ThermalState, WearState,
     };
-    use std::collections::HashSet;
 
     /// Build content with a processor that has a thermal recipe.
     fn thermal_processor_content() -> GameContent {
@@ -1067,99 +1175,42 @@ mod tests {
     /// Build state with a thermal processor module at the given temperature.
     fn thermal_processor_state(content: &GameContent, temp_mk: u32) -> GameState {
         let station_id = StationId("station_test".to_string());
-        GameState {
-            meta: MetaState {
-                tick: 10,
-                seed: 42,
-                schema_version: 1,
-                content_version: content.content_version.clone(),
-            },
-            scan_sites: vec![],
-            asteroids: std::collections::BTreeMap::new(),
-            ships: std::collections::BTreeMap::new(),
-            stations: [(
-                station_id.clone(),
-                StationState {
-                    id: station_id,
-                    position: crate::test_fixtures::test_position(),
-                    core: FacilityCore {
-                        inventory: vec![InventoryItem::Ore {
-                            lot_id: LotId("lot_0001".to_string()),
-                            asteroid_id: AsteroidId("ast_0001".to_string()),
-                            kg: 500.0,
-                            composition: HashMap::from([
-                                ("Fe".to_string(), 0.7),
-                                ("Si".to_string(), 0.3),
-                            ]),
-                        }],
-                        cargo_capacity_m3: 10_000.0,
-                        power_available_per_tick: 100.0,
-                        modules: vec![ModuleState {
-                            id: ModuleInstanceId("smelter_0001".to_string()),
-                            def_id: "module_smelter".to_string(),
-                            enabled: true,
-                            kind_state: ModuleKindState::Processor(ProcessorState {
-                                threshold_kg: 0.0,
-                                ticks_since_last_run: 0,
-                                stalled: false,
-                                selected_recipe: None,
-                            }),
-                            wear: WearState::default(),
-                            power_stalled: false,
-                            module_priority: 0,
-                            assigned_crew: Default::default(),
-                            efficiency: 1.0,
-                            prev_crew_satisfied: true,
-                            thermal: Some(ThermalState {
-                                temp_mk,
-                                thermal_group: Some("smelting".to_string()),
-                                ..Default::default()
-                            }),
-                            slot_index: None,
-                        }],
-                        modifiers: crate::modifiers::ModifierSet::default(),
-                        crew: Default::default(),
-                        thermal_links: Vec::new(),
-                        power: PowerState::default(),
-                        cached_inventory_volume_m3: None,
-                        module_type_index: crate::ModuleTypeIndex::default(),
-                        module_id_index: HashMap::new(),
-                        power_budget_cache: crate::PowerBudgetCache::default(),
-                    },
-                    leaders: Vec::new(),
-                    frame_id: None,
-                },
-            )]
-            .into_iter()
-            .collect(),
-            ground_facilities: std::collections::BTreeMap::new(),
-            satellites: std::collections::BTreeMap::new(),
-            research: crate::ResearchState {
-                unlocked: HashSet::new(),
-                data_pool: AHashMap::default(),
-                evidence: AHashMap::default(),
-                action_counts: AHashMap::default(),
-            },
-            balance: 0.0,
-            export_revenue_total: 0.0,
-            export_count: 0,
-            counters: Counters {
-                next_event_id: 0,
-                next_command_id: 0,
-                next_asteroid_id: 0,
-                next_lot_id: 0,
-                next_module_instance_id: 0,
+        let mut station = crate::test_fixtures::test_station(station_id.clone());
+        station.core.inventory = vec![InventoryItem::Ore {
+            lot_id: LotId("lot_0001".to_string()),
+            asteroid_id: AsteroidId("ast_0001".to_string()),
+            kg: 500.0,
+            composition: HashMap::from([("Fe".to_string(), 0.7), ("Si".to_string(), 0.3)]),
+        }];
+        station.core.modules = vec![ModuleState {
+            id: ModuleInstanceId("smelter_0001".to_string()),
+            def_id: "module_smelter".to_string(),
+            enabled: true,
+            kind_state: ModuleKindState::Processor(ProcessorState {
+                threshold_kg: 0.0,
+                ticks_since_last_run: 0,
+                stalled: false,
+                selected_recipe: None,
+            }),
+            wear: WearState::default(),
+            power_stalled: false,
+            module_priority: 0,
+            assigned_crew: std::collections::BTreeMap::default(),
+            efficiency: 1.0,
+            prev_crew_satisfied: true,
+            thermal: Some(ThermalState {
+                temp_mk,
+                thermal_group: Some("smelting".to_string()),
                 ..Default::default()
-            },
-            modifiers: crate::modifiers::ModifierSet::default(),
-            events: crate::sim_events::SimEventState::default(),
-            propellant_consumed_total: 0.0,
-            transfer_volume_kg: 0.0,
-            transfer_count: 0,
-            progression: Default::default(),
-            strategy_config: Default::default(),
-            body_cache: AHashMap::default(),
-        }
+            }),
+            slot_index: None,
+        }];
+        let mut state = GameState {
+            stations: [(station_id, station)].into_iter().collect(),
+            ..crate::test_fixtures::base_state(content)
+        };
+        state.meta.tick = 10;
+        state
     }
 
     #[test]
@@ -1453,118 +1504,60 @@ mod tests {
     fn higher_priority_processor_consumes_first() {
         let content = priority_content();
         let station_id = StationId("station_test".to_string());
-        let mut state = GameState {
-            meta: crate::MetaState {
-                tick: 10,
-                seed: 42,
-                schema_version: 1,
-                content_version: content.content_version.clone(),
-            },
-            scan_sites: vec![],
-            asteroids: std::collections::BTreeMap::new(),
-            ships: std::collections::BTreeMap::new(),
-            stations: [(
-                station_id.clone(),
-                crate::StationState {
-                    id: station_id.clone(),
-                    position: crate::test_fixtures::test_position(),
-                    core: FacilityCore {
-                        inventory: vec![crate::InventoryItem::Ore {
-                            lot_id: crate::LotId("lot_0001".to_string()),
-                            asteroid_id: crate::AsteroidId("ast_0001".to_string()),
-                            kg: 100.0,
-                            composition: HashMap::from([
-                                ("Fe".to_string(), 0.7),
-                                ("Si".to_string(), 0.3),
-                            ]),
-                        }],
-                        cargo_capacity_m3: 10_000.0,
-                        power_available_per_tick: 100.0,
-                        modules: vec![
-                            // Low-priority processor (id comes first alphabetically)
-                            ModuleState {
-                                id: ModuleInstanceId("proc_aaa".to_string()),
-                                def_id: "module_refinery".to_string(),
-                                enabled: true,
-                                kind_state: ModuleKindState::Processor(ProcessorState {
-                                    threshold_kg: 0.0,
-                                    ticks_since_last_run: 0,
-                                    stalled: false,
-                                    selected_recipe: None,
-                                }),
-                                wear: crate::WearState::default(),
-                                thermal: None,
-                                power_stalled: false,
-                                module_priority: 0,
-                                assigned_crew: Default::default(),
-                                efficiency: 1.0,
-                                prev_crew_satisfied: true,
-                                slot_index: None,
-                            },
-                            // High-priority processor
-                            ModuleState {
-                                id: ModuleInstanceId("proc_bbb".to_string()),
-                                def_id: "module_refinery".to_string(),
-                                enabled: true,
-                                kind_state: ModuleKindState::Processor(ProcessorState {
-                                    threshold_kg: 0.0,
-                                    ticks_since_last_run: 0,
-                                    stalled: false,
-                                    selected_recipe: None,
-                                }),
-                                wear: crate::WearState::default(),
-                                thermal: None,
-                                power_stalled: false,
-                                module_priority: 10,
-                                assigned_crew: Default::default(),
-                                efficiency: 1.0,
-                                prev_crew_satisfied: true,
-                                slot_index: None,
-                            },
-                        ],
-                        modifiers: crate::modifiers::ModifierSet::default(),
-                        crew: Default::default(),
-                        thermal_links: Vec::new(),
-                        power: PowerState::default(),
-                        cached_inventory_volume_m3: None,
-                        module_type_index: crate::ModuleTypeIndex::default(),
-                        module_id_index: HashMap::new(),
-                        power_budget_cache: crate::PowerBudgetCache::default(),
-                    },
-                    leaders: Vec::new(),
-                    frame_id: None,
-                },
-            )]
-            .into_iter()
-            .collect(),
-            ground_facilities: std::collections::BTreeMap::new(),
-            satellites: std::collections::BTreeMap::new(),
-            research: crate::ResearchState {
-                unlocked: HashSet::new(),
-                data_pool: AHashMap::default(),
-                evidence: AHashMap::default(),
-                action_counts: AHashMap::default(),
+        let mut station = crate::test_fixtures::test_station(station_id.clone());
+        station.core.inventory = vec![crate::InventoryItem::Ore {
+            lot_id: crate::LotId("lot_0001".to_string()),
+            asteroid_id: crate::AsteroidId("ast_0001".to_string()),
+            kg: 100.0,
+            composition: HashMap::from([("Fe".to_string(), 0.7), ("Si".to_string(), 0.3)]),
+        }];
+        station.core.modules = vec![
+            // Low-priority processor (id comes first alphabetically)
+            ModuleState {
+                id: ModuleInstanceId("proc_aaa".to_string()),
+                def_id: "module_refinery".to_string(),
+                enabled: true,
+                kind_state: ModuleKindState::Processor(ProcessorState {
+                    threshold_kg: 0.0,
+                    ticks_since_last_run: 0,
+                    stalled: false,
+                    selected_recipe: None,
+                }),
+                wear: crate::WearState::default(),
+                thermal: None,
+                power_stalled: false,
+                module_priority: 0,
+                assigned_crew: std::collections::BTreeMap::default(),
+                efficiency: 1.0,
+                prev_crew_satisfied: true,
+                slot_index: None,
             },
-            balance: 0.0,
-            export_revenue_total: 0.0,
-            export_count: 0,
-            counters: Counters {
-                next_event_id: 0,
-                next_command_id: 0,
-                next_asteroid_id: 0,
-                next_lot_id: 0,
-                next_module_instance_id: 0,
-                ..Default::default()
+            // High-priority processor
+            ModuleState {
+                id: ModuleInstanceId("proc_bbb".to_string()),
+                def_id: "module_refinery".to_string(),
+                enabled: true,
+                kind_state: ModuleKindState::Processor(ProcessorState {
+                    threshold_kg: 0.0,
+                    ticks_since_last_run: 0,
+                    stalled: false,
+                    selected_recipe: None,
+                }),
+                wear: crate::WearState::default(),
+                thermal: None,
+                power_stalled: false,
+                module_priority: 10,
+                assigned_crew: std::collections::BTreeMap::default(),
+                efficiency: 1.0,
+                prev_crew_satisfied: true,
+                slot_index: None,
             },
-            modifiers: crate::modifiers::ModifierSet::default(),
-            events: crate::sim_events::SimEventState::default(),
-            propellant_consumed_total: 0.0,
-            transfer_volume_kg: 0.0,
-            transfer_count: 0,
-            progression: Default::default(),
-            strategy_config: Default::default(),
-            body_cache: AHashMap::default(),
+        ];
+        let mut state = GameState {
+            stations: [(station_id.clone(), station)].into_iter().collect(),
+            ..crate::test_fixtures::base_state(&content)
         };
+        state.meta.tick = 10;
 
         let mut events = Vec::new();
         tick_station_modules(
@@ -1612,96 +1605,38 @@ mod tests {
             })
             .unwrap();
 
+        let mut station = crate::test_fixtures::test_station(station_id.clone());
+        station.core.inventory = vec![crate::InventoryItem::Ore {
+            lot_id: crate::LotId("lot_0001".to_string()),
+            asteroid_id: crate::AsteroidId("ast_0001".to_string()),
+            kg: 500.0,
+            composition: HashMap::from([("Fe".to_string(), 0.7), ("Si".to_string(), 0.3)]),
+        }];
+        station.core.modules = vec![ModuleState {
+            id: ModuleInstanceId("proc_0001".to_string()),
+            def_id: "module_refinery".to_string(),
+            enabled: true,
+            kind_state: ModuleKindState::Processor(ProcessorState {
+                threshold_kg: 0.0,
+                ticks_since_last_run: 0,
+                stalled: false,
+                // Select a recipe that does NOT exist in the processor's recipe list
+                selected_recipe: Some(RecipeId("nonexistent_recipe".to_string())),
+            }),
+            wear: crate::WearState::default(),
+            thermal: None,
+            power_stalled: false,
+            module_priority: 0,
+            assigned_crew: std::collections::BTreeMap::default(),
+            efficiency: 1.0,
+            prev_crew_satisfied: true,
+            slot_index: None,
+        }];
         let mut state = GameState {
-            meta: crate::MetaState {
-                tick: 10,
-                seed: 42,
-                schema_version: 1,
-                content_version: content.content_version.clone(),
-            },
-            scan_sites: vec![],
-            asteroids: std::collections::BTreeMap::new(),
-            ships: std::collections::BTreeMap::new(),
-            stations: [(
-                station_id.clone(),
-                crate::StationState {
-                    id: station_id.clone(),
-                    position: crate::test_fixtures::test_position(),
-                    core: FacilityCore {
-                        inventory: vec![crate::InventoryItem::Ore {
-                            lot_id: crate::LotId("lot_0001".to_string()),
-                            asteroid_id: crate::AsteroidId("ast_0001".to_string()),
-                            kg: 500.0,
-                            composition: HashMap::from([
-                                ("Fe".to_string(), 0.7),
-                                ("Si".to_string(), 0.3),
-                            ]),
-                        }],
-                        cargo_capacity_m3: 10_000.0,
-                        power_available_per_tick: 100.0,
-                        modules: vec![ModuleState {
-                            id: ModuleInstanceId("proc_0001".to_string()),
-                            def_id: "module_refinery".to_string(),
-                            enabled: true,
-                            kind_state: ModuleKindState::Processor(ProcessorState {
-                                threshold_kg: 0.0,
-                                ticks_since_last_run: 0,
-                                stalled: false,
-                                // Select a recipe that does NOT exist in the processor's recipe list
-                                selected_recipe: Some(RecipeId("nonexistent_recipe".to_string())),
-                            }),
-                            wear: crate::WearState::default(),
-                            thermal: None,
-                            power_stalled: false,
-                            module_priority: 0,
-                            assigned_crew: Default::default(),
-                            efficiency: 1.0,
-                            prev_crew_satisfied: true,
-                            slot_index: None,
-                        }],
-                        modifiers: crate::modifiers::ModifierSet::default(),
-                        crew: Default::default(),
-                        thermal_links: Vec::new(),
-                        power: PowerState::default(),
-                        cached_inventory_volume_m3: None,
-                        module_type_index: crate::ModuleTypeIndex::default(),
-                        module_id_index: HashMap::new(),
-                        power_budget_cache: crate::PowerBudgetCache::default(),
-                    },
-                    leaders: Vec::new(),
-                    frame_id: None,
-                },
-            )]
-            .into_iter()
-            .collect(),
-            ground_facilities: std::collections::BTreeMap::new(),
-            satellites: std::collections::BTreeMap::new(),
-            research: crate::ResearchState {
-                unlocked: HashSet::new(),
-                data_pool: AHashMap::default(),
-                evidence: AHashMap::default(),
-                action_counts: AHashMap::default(),
-            },
-            balance: 0.0,
-            export_revenue_total: 0.0,
-            export_count: 0,
-            counters: Counters {
-                next_event_id: 0,
-                next_command_id: 0,
-                next_asteroid_id: 0,
-                next_lot_id: 0,
-                next_module_instance_id: 0,
-                ..Default::default()
-            },
-            modifiers: crate::modifiers::ModifierSet::default(),
-            events: crate::sim_events::SimEventState::default(),
-            propellant_consumed_total: 0.0,
-            transfer_volume_kg: 0.0,
-            transfer_count: 0,
-            progression: Default::default(),
-            strategy_config: Default::default(),
-            body_cache: AHashMap::default(),
+            stations: [(station_id.clone(), station)].into_iter().collect(),
+            ..crate::test_fixtures::base_state(&content)
         };
+        state.meta.tick = 10;
 
         let mut events = Vec::new();
         tick_station_modules(
@@ -1792,95 +1727,37 @@ mod tests {
         );
 
         let station_id = StationId("station_test".to_string());
+        let mut station = crate::test_fixtures::test_station(station_id.clone());
+        station.core.inventory = vec![crate::InventoryItem::Ore {
+            lot_id: crate::LotId("lot_0001".to_string()),
+            asteroid_id: crate::AsteroidId("ast_0001".to_string()),
+            kg: 500.0,
+            composition: HashMap::from([("Fe".to_string(), 0.7), ("Si".to_string(), 0.3)]),
+        }];
+        station.core.modules = vec![ModuleState {
+            id: ModuleInstanceId("proc_0001".to_string()),
+            def_id: "module_adv_refinery".to_string(),
+            enabled: true,
+            kind_state: ModuleKindState::Processor(ProcessorState {
+                threshold_kg: 0.0,
+                ticks_since_last_run: 0,
+                stalled: false,
+                selected_recipe: None,
+            }),
+            wear: crate::WearState::default(),
+            thermal: None,
+            power_stalled: false,
+            module_priority: 0,
+            assigned_crew: std::collections::BTreeMap::default(),
+            efficiency: 1.0,
+            prev_crew_satisfied: true,
+            slot_index: None,
+        }];
         let mut state = GameState {
-            meta: crate::MetaState {
-                tick: 10,
-                seed: 42,
-                schema_version: 1,
-                content_version: content.content_version.clone(),
-            },
-            scan_sites: vec![],
-            asteroids: std::collections::BTreeMap::new(),
-            ships: std::collections::BTreeMap::new(),
-            stations: [(
-                station_id.clone(),
-                crate::StationState {
-                    id: station_id.clone(),
-                    position: crate::test_fixtures::test_position(),
-                    core: FacilityCore {
-                        inventory: vec![crate::InventoryItem::Ore {
-                            lot_id: crate::LotId("lot_0001".to_string()),
-                            asteroid_id: crate::AsteroidId("ast_0001".to_string()),
-                            kg: 500.0,
-                            composition: HashMap::from([
-                                ("Fe".to_string(), 0.7),
-                                ("Si".to_string(), 0.3),
-                            ]),
-                        }],
-                        cargo_capacity_m3: 10_000.0,
-                        power_available_per_tick: 100.0,
-                        modules: vec![ModuleState {
-                            id: ModuleInstanceId("proc_0001".to_string()),
-                            def_id: "module_adv_refinery".to_string(),
-                            enabled: true,
-                            kind_state: ModuleKindState::Processor(ProcessorState {
-                                threshold_kg: 0.0,
-                                ticks_since_last_run: 0,
-                                stalled: false,
-                                selected_recipe: None,
-                            }),
-                            wear: crate::WearState::default(),
-                            thermal: None,
-                            power_stalled: false,
-                            module_priority: 0,
-                            assigned_crew: Default::default(),
-                            efficiency: 1.0,
-                            prev_crew_satisfied: true,
-                            slot_index: None,
-                        }],
-                        modifiers: crate::modifiers::ModifierSet::default(),
-                        crew: Default::default(),
-                        thermal_links: Vec::new(),
-                        power: PowerState::default(),
-                        cached_inventory_volume_m3: None,
-                        module_type_index: crate::ModuleTypeIndex::default(),
-                        module_id_index: HashMap::new(),
-                        power_budget_cache: crate::PowerBudgetCache::default(),
-                    },
-                    leaders: Vec::new(),
-                    frame_id: None,
-                },
-            )]
-            .into_iter()
-            .collect(),
-            ground_facilities: std::collections::BTreeMap::new(),
-            satellites: std::collections::BTreeMap::new(),
-            research: crate::ResearchState {
-                unlocked: HashSet::new(), // tech NOT unlocked
-                data_pool: AHashMap::default(),
-                evidence: AHashMap::default(),
-                action_counts: AHashMap::default(),
-            },
-            balance: 0.0,
-            export_revenue_total: 0.0,
-            export_count: 0,
-            counters: Counters {
-                next_event_id: 0,
-                next_command_id: 0,
-                next_asteroid_id: 0,
-                next_lot_id: 0,
-                next_module_instance_id: 0,
-                ..Default::default()
-            },
-            modifiers: crate::modifiers::ModifierSet::default(),
-            events: crate::sim_events::SimEventState::default(),
-            propellant_consumed_total: 0.0,
-            transfer_volume_kg: 0.0,
-            transfer_count: 0,
-            progression: Default::default(),
-            strategy_config: Default::default(),
-            body_cache: AHashMap::default(),
+            stations: [(station_id.clone(), station)].into_iter().collect(),
+            ..crate::test_fixtures::base_state(&content)
         };
+        state.meta.tick = 10;
 
         let mut events = Vec::new();
         tick_station_modules(
@@ -1966,95 +1843,37 @@ mod tests {
         );
 
         let station_id = StationId("station_test".to_string());
+        let mut station = crate::test_fixtures::test_station(station_id.clone());
+        station.core.inventory = vec![crate::InventoryItem::Ore {
+            lot_id: crate::LotId("lot_0001".to_string()),
+            asteroid_id: crate::AsteroidId("ast_0001".to_string()),
+            kg: 300.0,
+            composition: HashMap::from([("Fe".to_string(), 0.7), ("Si".to_string(), 0.3)]),
+        }];
+        station.core.modules = vec![ModuleState {
+            id: ModuleInstanceId("ingot_maker_0001".to_string()),
+            def_id: "module_ingot_maker".to_string(),
+            enabled: true,
+            kind_state: ModuleKindState::Processor(ProcessorState {
+                threshold_kg: 0.0,
+                ticks_since_last_run: 0,
+                stalled: false,
+                selected_recipe: None,
+            }),
+            wear: crate::WearState::default(),
+            thermal: None,
+            power_stalled: false,
+            module_priority: 0,
+            assigned_crew: std::collections::BTreeMap::default(),
+            efficiency: 1.0,
+            prev_crew_satisfied: true,
+            slot_index: None,
+        }];
         let mut state = GameState {
-            meta: crate::MetaState {
-                tick: 10,
-                seed: 42,
-                schema_version: 1,
-                content_version: content.content_version.clone(),
-            },
-            scan_sites: vec![],
-            asteroids: std::collections::BTreeMap::new(),
-            ships: std::collections::BTreeMap::new(),
-            stations: [(
-                station_id.clone(),
-                crate::StationState {
-                    id: station_id.clone(),
-                    position: crate::test_fixtures::test_position(),
-                    core: FacilityCore {
-                        inventory: vec![crate::InventoryItem::Ore {
-                            lot_id: crate::LotId("lot_0001".to_string()),
-                            asteroid_id: crate::AsteroidId("ast_0001".to_string()),
-                            kg: 300.0,
-                            composition: HashMap::from([
-                                ("Fe".to_string(), 0.7),
-                                ("Si".to_string(), 0.3),
-                            ]),
-                        }],
-                        cargo_capacity_m3: 10_000.0,
-                        power_available_per_tick: 100.0,
-                        modules: vec![ModuleState {
-                            id: ModuleInstanceId("ingot_maker_0001".to_string()),
-                            def_id: "module_ingot_maker".to_string(),
-                            enabled: true,
-                            kind_state: ModuleKindState::Processor(ProcessorState {
-                                threshold_kg: 0.0,
-                                ticks_since_last_run: 0,
-                                stalled: false,
-                                selected_recipe: None,
-                            }),
-                            wear: crate::WearState::default(),
-                            thermal: None,
-                            power_stalled: false,
-                            module_priority: 0,
-                            assigned_crew: Default::default(),
-                            efficiency: 1.0,
-                            prev_crew_satisfied: true,
-                            slot_index: None,
-                        }],
-                        modifiers: crate::modifiers::ModifierSet::default(),
-                        crew: Default::default(),
-                        thermal_links: Vec::new(),
-                        power: PowerState::default(),
-                        cached_inventory_volume_m3: None,
-                        module_type_index: crate::ModuleTypeIndex::default(),
-                        module_id_index: HashMap::new(),
-                        power_budget_cache: crate::PowerBudgetCache::default(),
-                    },
-                    leaders: Vec::new(),
-                    frame_id: None,
-                },
-            )]
-            .into_iter()
-            .collect(),
-            ground_facilities: std::collections::BTreeMap::new(),
-            satellites: std::collections::BTreeMap::new(),
-            research: crate::ResearchState {
-                unlocked: HashSet::new(),
-                data_pool: AHashMap::default(),
-                evidence: AHashMap::default(),
-                action_counts: AHashMap::default(),
-            },
-            balance: 0.0,
-            export_revenue_total: 0.0,
-            export_count: 0,
-            counters: Counters {
-                next_event_id: 0,
-                next_command_id: 0,
-                next_asteroid_id: 0,
-                next_lot_id: 0,
-                next_module_instance_id: 0,
-                ..Default::default()
-            },
-            modifiers: crate::modifiers::ModifierSet::default(),
-            events: crate::sim_events::SimEventState::default(),
-            propellant_consumed_total: 0.0,
-            transfer_volume_kg: 0.0,
-            transfer_count: 0,
-            progression: Default::default(),
-            strategy_config: Default::default(),
-            body_cache: AHashMap::default(),
+            stations: [(station_id.clone(), station)].into_iter().collect(),
+            ..crate::test_fixtures::base_state(&content)
         };
+        state.meta.tick = 10;
 
         let mut events = Vec::new();
         tick_station_modules(
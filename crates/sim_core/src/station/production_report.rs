@@ -0,0 +1,192 @@
+use crate::{Event, EventEnvelope, GameContent, GameState, ProductionWindow, StationId};
+
+/// Step 3.8: Fold this tick's `RefineryRan`/`AssemblerRan` events for the
+/// station (those from `events[first_event..]`) into per-module windows, and
+/// emit a `ProductionReport` for every window that has covered
+/// `production_report_interval_ticks`.
+pub(super) fn update_production_reports(
+    state: &mut GameState,
+    station_id: &StationId,
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+    first_event: usize,
+) {
+    let interval = content.constants.production_report_interval_ticks;
+    if interval == 0 {
+        return;
+    }
+    let current_tick = state.meta.tick;
+    let Some(station) = state.stations.get_mut(station_id) else {
+        return;
+    };
+    let windows = &mut station.core.production_windows;
+
+    for envelope in &events[first_event..] {
+        let (module_id, input_kg, output_kg, output_units, quality) = match &envelope.event {
+            Event::RefineryRan {
+                station_id: run_station,
+                module_id,
+                ore_consumed_kg,
+                material_produced_kg,
+                material_quality,
+                ..
+            } if run_station == station_id => (
+                module_id,
+                *ore_consumed_kg,
+                *material_produced_kg,
+                0,
+                *material_quality,
+            ),
+            Event::AssemblerRan {
+                station_id: run_station,
+                module_id,
+                material_consumed_kg,
+                component_produced_count,
+                component_quality,
+                ..
+            } if run_station == station_id => (
+                module_id,
+                *material_consumed_kg,
+                0.0,
+                *component_produced_count,
+                *component_quality,
+            ),
+            _ => continue,
+        };
+        let window = windows
+            .entry(module_id.clone())
+            .or_insert_with(|| ProductionWindow {
+                start_tick: current_tick,
+                ..ProductionWindow::default()
+            });
+        window.runs += 1;
+        window.input_kg += input_kg;
+        window.output_kg += output_kg;
+        window.output_units += output_units;
+        window.quality_sum += quality;
+    }
+
+    let end_tick = current_tick + 1;
+    let due: Vec<_> = windows
+        .iter()
+        .filter(|(_, window)| end_tick - window.start_tick >= interval)
+        .map(|(module_id, _)| module_id.clone())
+        .collect();
+    for module_id in due {
+        let Some(window) = windows.remove(&module_id) else {
+            continue;
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let average_quality = window.quality_sum / window.runs.max(1) as f32;
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            Event::ProductionReport {
+                station_id: station_id.clone(),
+                module_id,
+                start_tick: window.start_tick,
+                end_tick,
+                runs: window.runs,
+                input_kg: window.input_kg,
+                output_kg: window.output_kg,
+                output_units: window.output_units,
+                average_quality,
+            },
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{base_content, base_state};
+    use crate::{EventId, ModuleInstanceId};
+
+    fn refinery_ran(station_id: &StationId, quality: f32) -> EventEnvelope {
+        EventEnvelope {
+            id: EventId(0),
+            tick: 0,
+            event: Event::RefineryRan {
+                station_id: station_id.clone(),
+                module_id: ModuleInstanceId("module_refinery".to_string()),
+                ore_consumed_kg: 100.0,
+                material_produced_kg: 60.0,
+                material_quality: quality,
+                slag_produced_kg: 40.0,
+                material_element: "Fe".to_string(),
+            },
+        }
+    }
+
+    fn reports(events: &[EventEnvelope]) -> Vec<&Event> {
+        events
+            .iter()
+            .map(|e| &e.event)
+            .filter(|e| matches!(e, Event::ProductionReport { .. }))
+            .collect()
+    }
+
+    #[test]
+    fn runs_fold_into_one_report_per_interval() {
+        let mut content = base_content();
+        content.constants.production_report_interval_ticks = 3;
+        let mut state = base_state(&content);
+        let station_id = state.stations.keys().next().unwrap().clone();
+
+        let mut events = Vec::new();
+        for (tick, quality) in [(0, 0.6), (1, 0.8), (2, 1.0)] {
+            state.meta.tick = tick;
+            let first_event = events.len();
+            events.push(refinery_ran(&station_id, quality));
+            update_production_reports(&mut state, &station_id, &content, &mut events, first_event);
+        }
+
+        let reports = reports(&events);
+        assert_eq!(reports.len(), 1);
+        let Event::ProductionReport {
+            start_tick,
+            end_tick,
+            runs,
+            input_kg,
+            output_kg,
+            average_quality,
+            ..
+        } = reports[0]
+        else {
+            unreachable!();
+        };
+        assert_eq!((*start_tick, *end_tick, *runs), (0, 3, 3));
+        assert!((input_kg - 300.0).abs() < 1e-3);
+        assert!((output_kg - 180.0).abs() < 1e-3);
+        assert!((average_quality - 0.8).abs() < 1e-5);
+        assert!(state.stations[&station_id]
+            .core
+            .production_windows
+            .is_empty());
+    }
+
+    #[test]
+    fn zero_interval_disables_reports() {
+        let content = base_content();
+        let mut state = base_state(&content);
+        let station_id = state.stations.keys().next().unwrap().clone();
+
+        let mut events = vec![refinery_ran(&station_id, 1.0)];
+        update_production_reports(&mut state, &station_id, &content, &mut events, 0);
+
+        assert!(reports(&events).is_empty());
+        assert!(state.stations[&station_id]
+            .core
+            .production_windows
+            .is_empty());
+    }
+
+    #[test]
+    fn raw_run_events_are_debug_level() {
+        let station_id = StationId("station".to_string());
+        assert_eq!(
+            refinery_ran(&station_id, 1.0).event.level(),
+            crate::EventLevel::Debug
+        );
+    }
+}
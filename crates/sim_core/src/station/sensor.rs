@@ -38,7 +38,7 @@ pub(super) fn tick_sensor_array_modules(
 }
 
 fn execute(
-    _ctx: &super::ModuleTickContext,
+    ctx: &super::ModuleTickContext,
     sensor_def: &crate::SensorArrayDef,
     state: &mut GameState,
     content: &GameContent,
@@ -47,12 +47,25 @@ fn execute(
 ) -> super::RunOutcome {
     let current_tick = state.meta.tick;
 
-    let amount = generate_data(
+    let mut amount = generate_data(
         &mut state.research,
         sensor_def.data_kind.clone(),
         &sensor_def.action_key,
         &content.constants,
     );
+    if let Some(body_id) = state
+        .stations
+        .get(&ctx.station_id)
+        .map(|station| station.position.parent_body.clone())
+    {
+        amount = crate::pollution::dampen_scan_data(
+            state,
+            &body_id,
+            &sensor_def.data_kind,
+            amount,
+            content,
+        );
+    }
 
     events.push(crate::emit(
         &mut state.counters,
@@ -124,10 +137,8 @@ fn try_discover_scan_site(
 #[cfg(test)]
 mod tests {
     use crate::test_fixtures::ModuleDefBuilder;
-    use crate::AHashMap;
     use crate::*;
     use rand::SeedableRng;
-    use std::collections::{HashMap, HashSet};
 
     fn sensor_content() -> GameContent {
         let mut content = crate::test_fixtures::base_content();
@@ -155,83 +166,31 @@ mod tests {
 
     fn sensor_state(content: &GameContent) -> GameState {
         let station_id = StationId("station_test".to_string());
+        let mut station = crate::test_fixtures::test_station(station_id.clone());
+        station.core.cargo_capacity_m3 = 2000.0;
+        station.core.modules = vec![ModuleState {
+            id: ModuleInstanceId("sensor_inst_0001".to_string()),
+            def_id: "module_sensor_array".to_string(),
+            enabled: true,
+            kind_state: ModuleKindState::SensorArray(SensorArrayState {
+                ticks_since_last_run: 0,
+            }),
+            wear: WearState::default(),
+            power_stalled: false,
+            module_priority: 0,
+            assigned_crew: std::collections::BTreeMap::default(),
+            efficiency: 1.0,
+            prev_crew_satisfied: true,
+            thermal: None,
+            slot_index: None,
+        }];
         let mut state = GameState {
-            meta: MetaState {
-                tick: 0,
-                seed: 42,
-                schema_version: 1,
-                content_version: content.content_version.clone(),
-            },
-            scan_sites: vec![],
-            asteroids: std::collections::BTreeMap::new(),
-            ships: std::collections::BTreeMap::new(),
-            stations: [(
-                station_id.clone(),
-                StationState {
-                    id: station_id,
-                    position: crate::test_fixtures::test_position(),
-                    core: FacilityCore {
-                        inventory: vec![],
-                        cargo_capacity_m3: 2000.0,
-                        power_available_per_tick: 100.0,
-                        modules: vec![ModuleState {
-                            id: ModuleInstanceId("sensor_inst_0001".to_string()),
-                            def_id: "module_sensor_array".to_string(),
-                            enabled: true,
-                            kind_state: ModuleKindState::SensorArray(SensorArrayState {
-                                ticks_since_last_run: 0,
-                            }),
-                            wear: WearState::default(),
-                            power_stalled: false,
-                            module_priority: 0,
-                            assigned_crew: Default::default(),
-                            efficiency: 1.0,
-                            prev_crew_satisfied: true,
-                            thermal: None,
-                            slot_index: None,
-                        }],
-                        modifiers: crate::modifiers::ModifierSet::default(),
-                        crew: Default::default(),
-                        thermal_links: Vec::new(),
-                        power: PowerState::default(),
-                        cached_inventory_volume_m3: None,
-                        module_type_index: crate::ModuleTypeIndex::default(),
-                        module_id_index: HashMap::new(),
-                        power_budget_cache: crate::PowerBudgetCache::default(),
-                    },
-                    leaders: Vec::new(),
-                    frame_id: None,
-                },
-            )]
-            .into_iter()
-            .collect(),
-            ground_facilities: std::collections::BTreeMap::new(),
-            satellites: std::collections::BTreeMap::new(),
-            research: ResearchState {
-                unlocked: HashSet::new(),
-                data_pool: AHashMap::default(),
-                evidence: AHashMap::default(),
-                action_counts: AHashMap::default(),
-            },
-            balance: 0.0,
-            export_revenue_total: 0.0,
-            export_count: 0,
+            stations: [(station_id, station)].into_iter().collect(),
             counters: Counters {
-                next_event_id: 0,
-                next_command_id: 0,
-                next_asteroid_id: 0,
-                next_lot_id: 0,
                 next_module_instance_id: 2,
                 ..Default::default()
             },
-            modifiers: crate::modifiers::ModifierSet::default(),
-            events: crate::sim_events::SimEventState::default(),
-            propellant_consumed_total: 0.0,
-            transfer_volume_kg: 0.0,
-            transfer_count: 0,
-            progression: Default::default(),
-            strategy_config: Default::default(),
-            body_cache: AHashMap::default(),
+            ..crate::test_fixtures::base_state(content)
         };
         crate::test_fixtures::rebuild_indices(&mut state, content);
         state
@@ -394,6 +353,7 @@ mod tests {
                 scan_site_weight: 1,
                 implicit_comm_tier: None,
             }),
+            security: 1.0,
         });
         let mut state = sensor_state(&content);
         state.scan_sites.clear();
@@ -581,9 +581,7 @@ fn overheat_zone_event(
 mod tests {
     use super::*;
     use crate::test_fixtures::ModuleDefBuilder;
-    use crate::AHashMap;
     use crate::*;
-    use std::collections::{HashMap, HashSet};
 
     /// Create content with a module def that has thermal properties.
     fn thermal_test_content() -> GameContent {
@@ -618,91 +616,36 @@ mod tests {
     /// Create a state with a single thermal module at the given temperature.
     fn thermal_test_state(content: &GameContent, temp_mk: u32) -> GameState {
         let station_id = StationId("station_test".to_string());
-        GameState {
-            meta: MetaState {
-                tick: 10,
-                seed: 42,
-                schema_version: 1,
-                content_version: content.content_version.clone(),
-            },
-            scan_sites: vec![],
-            asteroids: std::collections::BTreeMap::new(),
-            ships: std::collections::BTreeMap::new(),
-            stations: [(
-                station_id.clone(),
-                StationState {
-                    id: station_id,
-                    position: crate::test_fixtures::test_position(),
-                    core: FacilityCore {
-                        inventory: vec![],
-                        cargo_capacity_m3: 10_000.0,
-                        power_available_per_tick: 100.0,
-                        modules: vec![ModuleState {
-                            id: ModuleInstanceId("smelter_0001".to_string()),
-                            def_id: "module_smelter".to_string(),
-                            enabled: true,
-                            kind_state: ModuleKindState::Processor(ProcessorState {
-                                threshold_kg: 0.0,
-                                ticks_since_last_run: 0,
-                                stalled: false,
-                                selected_recipe: None,
-                            }),
-                            wear: WearState::default(),
-                            power_stalled: false,
-                            module_priority: 0,
-                            assigned_crew: Default::default(),
-                            efficiency: 1.0,
-                            prev_crew_satisfied: true,
-                            thermal: Some(ThermalState {
-                                temp_mk,
-                                thermal_group: Some("smelting".to_string()),
-                                ..Default::default()
-                            }),
-                            slot_index: None,
-                        }],
-                        modifiers: crate::modifiers::ModifierSet::default(),
-                        crew: Default::default(),
-                        thermal_links: Vec::new(),
-                        power: PowerState::default(),
-                        cached_inventory_volume_m3: None,
-                        module_type_index: crate::ModuleTypeIndex::default(),
-                        module_id_index: HashMap::new(),
-                        power_budget_cache: crate::PowerBudgetCache::default(),
-                    },
-                    leaders: Vec::new(),
-                    frame_id: None,
-                },
-            )]
-            .into_iter()
-            .collect(),
-            ground_facilities: std::collections::BTreeMap::new(),
-            satellites: std::collections::BTreeMap::new(),
-            research: ResearchState {
-                unlocked: HashSet::new(),
-                data_pool: AHashMap::default(),
-                evidence: AHashMap::default(),
-                action_counts: AHashMap::default(),
-            },
-            balance: 0.0,
-            export_revenue_total: 0.0,
-            export_count: 0,
-            counters: Counters {
-                next_event_id: 0,
-                next_command_id: 0,
-                next_asteroid_id: 0,
-                next_lot_id: 0,
-                next_module_instance_id: 0,
+        let mut station = crate::test_fixtures::test_station(station_id.clone());
+        station.core.modules = vec![ModuleState {
+            id: ModuleInstanceId("smelter_0001".to_string()),
+            def_id: "module_smelter".to_string(),
+            enabled: true,
+            kind_state: ModuleKindState::Processor(ProcessorState {
+                threshold_kg: 0.0,
+                ticks_since_last_run: 0,
+                stalled: false,
+                selected_recipe: None,
+            }),
+            wear: WearState::default(),
+            power_stalled: false,
+            module_priority: 0,
+            assigned_crew: std::collections::BTreeMap::default(),
+            efficiency: 1.0,
+            prev_crew_satisfied: true,
+            thermal: Some(ThermalState {
+                temp_mk,
+                thermal_group: Some("smelting".to_string()),
                 ..Default::default()
-            },
-            modifiers: crate::modifiers::ModifierSet::default(),
-            events: crate::sim_events::SimEventState::default(),
-            propellant_consumed_total: 0.0,
-            transfer_volume_kg: 0.0,
-            transfer_count: 0,
-            progression: Default::default(),
-            strategy_config: Default::default(),
-            body_cache: AHashMap::default(),
-        }
+            }),
+            slot_index: None,
+        }];
+        let mut state = GameState {
+            stations: [(station_id, station)].into_iter().collect(),
+            ..crate::test_fixtures::base_state(content)
+        };
+        state.meta.tick = 10;
+        state
     }
 
     #[test]
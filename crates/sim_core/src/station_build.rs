@@ -0,0 +1,149 @@
+//! Station-founded stations.
+//!
+//! `Command::BuildStation` lets a station spend a station kit from its own
+//! inventory (typically one its assemblers produced) to found a new station
+//! without a ship. The kit is hauled to the target position at the default
+//! ship speed and assembled on arrival; the `StationBuild` is held in
+//! `GameState.station_builds` until `complete_tick`.
+
+use crate::{
+    ComponentId, Event, EventEnvelope, GameContent, GameState, InventoryItem, Position,
+    StationBuild, StationId,
+};
+
+/// Consume one kit from the station and schedule the new station.
+/// Rejected for unknown stations, components that do not deploy a known
+/// frame, or a station without the kit in stock.
+pub(crate) fn handle_build_station(
+    state: &mut GameState,
+    content: &GameContent,
+    station_id: &StationId,
+    kit_component_id: &ComponentId,
+    position: &Position,
+    current_tick: u64,
+    events: &mut Vec<EventEnvelope>,
+) -> Result<(), String> {
+    let Some(kit_def) = content
+        .component_defs
+        .iter()
+        .find(|c| c.id == kit_component_id.0)
+    else {
+        return Err(format!("unknown component '{}'", kit_component_id.0));
+    };
+    let Some(frame_id) = kit_def
+        .deploys_frame
+        .clone()
+        .filter(|frame_id| content.frames.contains_key(frame_id))
+    else {
+        return Err("component does not deploy a known frame".to_string());
+    };
+    let Some(station) = state.stations.get_mut(station_id) else {
+        return Err(format!("unknown station '{}'", station_id.0));
+    };
+    if !take_kit(&mut station.core.inventory, kit_component_id) {
+        return Err("station kit is not in station inventory".to_string());
+    }
+    station.invalidate_volume_cache();
+
+    let haul_ticks = if crate::is_co_located(
+        &station.position,
+        position,
+        &state.body_cache,
+        content.constants.docking_range_au_um,
+    ) {
+        0
+    } else {
+        crate::travel_ticks(
+            crate::compute_entity_absolute(&station.position, &state.body_cache),
+            crate::compute_entity_absolute(position, &state.body_cache),
+            content.constants.ticks_per_au,
+            content.constants.min_transit_ticks,
+        )
+    };
+    let complete_tick =
+        current_tick + haul_ticks + crate::commands::assembly_ticks_for_kit(kit_def);
+
+    state.station_builds.push(StationBuild {
+        source_station: station_id.clone(),
+        frame_id: frame_id.clone(),
+        kit_component_id: kit_component_id.0.clone(),
+        position: position.clone(),
+        complete_tick,
+    });
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::StationBuildStarted {
+            source_station: station_id.clone(),
+            frame_id,
+            position: position.clone(),
+            complete_tick,
+        },
+    ));
+    Ok(())
+}
+
+/// Found every station whose build is due. The new station belongs to the
+/// owner of the station that paid for the kit.
+pub(crate) fn tick_station_builds(
+    state: &mut GameState,
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let current_tick = state.meta.tick;
+    if !state
+        .station_builds
+        .iter()
+        .any(|build| build.complete_tick <= current_tick)
+    {
+        return;
+    }
+    let (due, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut state.station_builds)
+        .into_iter()
+        .partition(|build| build.complete_tick <= current_tick);
+    state.station_builds = pending;
+
+    for build in due {
+        let owner = state.stations.get(&build.source_station).map_or_else(
+            || crate::PrincipalId("principal_autopilot".to_string()),
+            |station| station.owner.clone(),
+        );
+        let station_id = crate::tasks::found_station_from_kit(
+            state,
+            &build.frame_id,
+            &build.position,
+            &build.kit_component_id,
+            owner,
+            content,
+        );
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            Event::StationDeployed {
+                station_id,
+                position: build.position,
+                ship_id: None,
+                frame_id: Some(build.frame_id),
+                kit_component_id: Some(build.kit_component_id),
+            },
+        ));
+    }
+}
+
+/// Remove one unit of the kit from the inventory, dropping the stack when
+/// it empties. Returns `false` when the station has none.
+fn take_kit(inventory: &mut Vec<InventoryItem>, kit_component_id: &ComponentId) -> bool {
+    let Some(index) = inventory.iter().position(|item| {
+        matches!(item, InventoryItem::Component { component_id, count, .. }
+            if component_id == kit_component_id && *count > 0)
+    }) else {
+        return false;
+    };
+    if let InventoryItem::Component { count, .. } = &mut inventory[index] {
+        *count -= 1;
+        if *count == 0 {
+            inventory.remove(index);
+        }
+    }
+    true
+}
@@ -0,0 +1,192 @@
+//! Solar storms.
+//!
+//! Each tick, every body that is not a star and has no storm in progress
+//! rolls `solar_storm_chance_per_day` (scaled to one tick) for a new storm
+//! lasting `solar_storm_duration_minutes`. While a storm is active at a
+//! body, stations there see their `solar_intensity` scaled by
+//! `solar_storm_intensity_mult` and their modules wear
+//! `solar_storm_wear_mult` times faster. When a storm starts, each ship
+//! deep-scanning an asteroid at that body is interrupted with
+//! `solar_storm_deep_scan_interrupt_chance` and goes idle without a result,
+//! and each ship at the body that is not in transit is lost with
+//! `solar_storm_ship_loss_chance` (see `ship_loss`). A chance of 0 disables
+//! storms (or storm losses) and draws nothing from the RNG.
+
+use rand::Rng;
+
+use crate::{
+    BodyId, BodyType, Event, EventEnvelope, GameContent, GameState, ShipId, ShipLossCause,
+    SolarStorm, StationId, TaskKind,
+};
+
+/// End expired storms, then roll new ones.
+pub(crate) fn tick_solar_storms(
+    state: &mut GameState,
+    content: &GameContent,
+    rng: &mut impl Rng,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let current_tick = state.meta.tick;
+    let ended: Vec<BodyId> = state
+        .solar_storms
+        .iter()
+        .filter(|(_, storm)| storm.ends_tick <= current_tick)
+        .map(|(body_id, _)| body_id.clone())
+        .collect();
+    for body_id in ended {
+        state.solar_storms.remove(&body_id);
+        invalidate_station_power(state, &body_id);
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            Event::StormEnded { body_id },
+        ));
+    }
+
+    let constants = &content.constants;
+    if constants.solar_storm_chance_per_day <= 0.0 {
+        return;
+    }
+    let chance_per_tick =
+        (constants.solar_storm_chance_per_day * f64::from(constants.minutes_per_tick) / 1440.0)
+            .min(1.0);
+    for body in &content.solar_system.bodies {
+        if body.body_type == BodyType::Star || state.solar_storms.contains_key(&body.id) {
+            continue;
+        }
+        if rng.gen::<f64>() >= chance_per_tick {
+            continue;
+        }
+        start_storm(state, &body.id, content, rng, events);
+    }
+}
+
+fn start_storm(
+    state: &mut GameState,
+    body_id: &BodyId,
+    content: &GameContent,
+    rng: &mut impl Rng,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let current_tick = state.meta.tick;
+    let ends_tick = current_tick + content.constants.solar_storm_duration_ticks.max(1);
+    state.solar_storms.insert(
+        body_id.clone(),
+        SolarStorm {
+            started_tick: current_tick,
+            ends_tick,
+        },
+    );
+    invalidate_station_power(state, body_id);
+
+    let loss_chance = content.constants.solar_storm_ship_loss_chance;
+    let lost_ships: Vec<ShipId> = if loss_chance > 0.0 {
+        ships_at_body(state, body_id)
+            .into_iter()
+            .filter(|_| rng.gen::<f32>() < loss_chance)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut interrupted_ships = Vec::new();
+    for ship_id in deep_scanning_ships(state, body_id) {
+        if lost_ships.contains(&ship_id) {
+            continue;
+        }
+        if rng.gen::<f32>() < content.constants.solar_storm_deep_scan_interrupt_chance {
+            crate::tasks::set_ship_idle(state, &ship_id, current_tick);
+            interrupted_ships.push(ship_id);
+        }
+    }
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::StormStarted {
+            body_id: body_id.clone(),
+            ends_tick,
+            interrupted_ships,
+        },
+    ));
+    for ship_id in lost_ships {
+        crate::ship_loss::lose_ship(state, &ship_id, ShipLossCause::SolarStorm, content, events);
+    }
+}
+
+/// Ships at `body_id` that are not in transit, in id order.
+fn ships_at_body(state: &GameState, body_id: &BodyId) -> Vec<ShipId> {
+    state
+        .ships
+        .values()
+        .filter(|ship| {
+            ship.position.parent_body == *body_id
+                && !matches!(
+                    ship.task.as_ref().map(|task| &task.kind),
+                    Some(TaskKind::Transit { .. })
+                )
+        })
+        .map(|ship| ship.id.clone())
+        .collect()
+}
+
+/// Ships deep-scanning an asteroid at `body_id`, in id order.
+fn deep_scanning_ships(state: &GameState, body_id: &BodyId) -> Vec<ShipId> {
+    state
+        .ships
+        .values()
+        .filter(|ship| {
+            matches!(
+                ship.task.as_ref().map(|task| &task.kind),
+                Some(TaskKind::DeepScan { asteroid })
+                    if state
+                        .asteroids
+                        .get(asteroid)
+                        .is_some_and(|a| a.position.parent_body == *body_id)
+            )
+        })
+        .map(|ship| ship.id.clone())
+        .collect()
+}
+
+/// Solar output changes with the storm, so stations at the body need their
+/// power budget rebuilt.
+fn invalidate_station_power(state: &mut GameState, body_id: &BodyId) {
+    for station in state.stations.values_mut() {
+        if station.position.parent_body == *body_id {
+            station.invalidate_power_cache();
+        }
+    }
+}
+
+/// True when a storm is in progress at `body_id`.
+pub(crate) fn is_storm_active(state: &GameState, body_id: &BodyId) -> bool {
+    state.solar_storms.contains_key(body_id)
+}
+
+/// Multiplier on solar intensity at `body_id`: `solar_storm_intensity_mult`
+/// during a storm, 1.0 otherwise.
+pub(crate) fn solar_intensity_mult(
+    state: &GameState,
+    body_id: &BodyId,
+    content: &GameContent,
+) -> f32 {
+    if is_storm_active(state, body_id) {
+        content.constants.solar_storm_intensity_mult
+    } else {
+        1.0
+    }
+}
+
+/// Multiplier on module wear at a station: `solar_storm_wear_mult` while a
+/// storm is active at its body, 1.0 otherwise.
+pub(crate) fn station_wear_mult(
+    state: &GameState,
+    station_id: &StationId,
+    content: &GameContent,
+) -> f32 {
+    state
+        .stations
+        .get(station_id)
+        .filter(|station| is_storm_active(state, &station.position.parent_body))
+        .map_or(1.0, |_| content.constants.solar_storm_wear_mult)
+}
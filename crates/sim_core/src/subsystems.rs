@@ -0,0 +1,81 @@
+//! Whole-subsystem switches for focused runs.
+//!
+//! `GameContent.subsystems` lists the subsystems a run leaves out, so a
+//! benchmark can isolate one part of the sim instead of zeroing the
+//! constants that feed the others:
+//!
+//! - `mining`: no scan sites are generated or replenished, and survey, deep
+//!   scan and mine tasks are refused.
+//! - `trade`: import and export commands are refused.
+//! - `research`: labs do not run and research does not advance.
+//! - `industry`: station processors and assemblers do not run.
+//!
+//! [`SimMode`] names the usual combinations.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Subsystem {
+    Mining,
+    Trade,
+    Research,
+    Industry,
+}
+
+impl Subsystem {
+    pub const ALL: [Subsystem; 4] = [
+        Subsystem::Mining,
+        Subsystem::Trade,
+        Subsystem::Research,
+        Subsystem::Industry,
+    ];
+}
+
+/// Preset subsystem selections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimMode {
+    /// Everything runs.
+    #[default]
+    Full,
+    /// Zero asteroids and no research: stations process, assemble and trade
+    /// what they start with.
+    EconomyOnly,
+    /// Zero asteroids, no trade and no station industry: only labs and the
+    /// research they feed.
+    ResearchOnly,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Subsystems {
+    #[serde(default)]
+    pub disabled: BTreeSet<Subsystem>,
+}
+
+impl Subsystems {
+    pub fn for_mode(mode: SimMode) -> Self {
+        let disabled = match mode {
+            SimMode::Full => BTreeSet::new(),
+            SimMode::EconomyOnly => BTreeSet::from([Subsystem::Mining, Subsystem::Research]),
+            SimMode::ResearchOnly => {
+                BTreeSet::from([Subsystem::Mining, Subsystem::Trade, Subsystem::Industry])
+            }
+        };
+        Self { disabled }
+    }
+
+    pub fn enabled(&self, subsystem: Subsystem) -> bool {
+        !self.disabled.contains(&subsystem)
+    }
+
+    pub fn set(&mut self, subsystem: Subsystem, enabled: bool) {
+        if enabled {
+            self.disabled.remove(&subsystem);
+        } else {
+            self.disabled.insert(subsystem);
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Survey sensing: per-ship sensor quality and Bayesian tag beliefs.
+//!
+//! A ship's sensor quality `q` is `base_sensor_quality` resolved through its
+//! hull and module `SensorQuality` modifiers plus tech modifiers, clamped to
+//! `0.0..=1.0`. The sensor sees a tag the asteroid carries with probability
+//! `q` and is modelled as false-alarming with `1 − q`, so each look is a
+//! Bayesian update of `tag_beliefs`:
+//!
+//! - seen: `b' = q·b / (q·b + (1 − q)·(1 − b))`
+//! - missed: `b' = (1 − q)·b / ((1 − q)·b + q·(1 − b))`
+//!
+//! A tag enters `tag_beliefs` the first time it is seen, from an uninformed
+//! prior of 0.5, which puts it at `q`. Missing a tag that was never seen
+//! leaves it out. One draw is taken per tag the asteroid carries, in order.
+
+use rand::Rng;
+
+use crate::modifiers::{ModifierSet, StatId};
+use crate::{
+    AnomalyTag, AsteroidId, Event, EventEnvelope, GameContent, GameState, ShipId, ShipState,
+};
+
+/// The chance `ship` detects a tag the surveyed asteroid carries.
+pub fn sensor_quality(ship: &ShipState, global: &ModifierSet, content: &GameContent) -> f32 {
+    ship.modifiers
+        .resolve_with_f32(
+            StatId::SensorQuality,
+            content.constants.base_sensor_quality,
+            global,
+        )
+        .clamp(0.0, 1.0)
+}
+
+/// Look once for each of `true_tags` with a sensor of `quality`, updating
+/// `beliefs` in place.
+pub(crate) fn observe_tags(
+    beliefs: &mut Vec<(AnomalyTag, f32)>,
+    true_tags: &[AnomalyTag],
+    quality: f32,
+    rng: &mut impl Rng,
+) {
+    for tag in true_tags {
+        let seen = rng.gen::<f32>() < quality;
+        match beliefs.iter_mut().find(|(known, _)| known == tag) {
+            Some((_, belief)) => *belief = update_belief(*belief, seen, quality),
+            None if seen => beliefs.push((tag.clone(), quality)),
+            None => {}
+        }
+    }
+}
+
+/// Posterior that a tag is present after one look.
+fn update_belief(prior: f32, seen: bool, quality: f32) -> f32 {
+    let (if_present, if_absent) = if seen {
+        (quality, 1.0 - quality)
+    } else {
+        (1.0 - quality, quality)
+    };
+    let evidence = if_present * prior + if_absent * (1.0 - prior);
+    if evidence <= 0.0 {
+        return prior;
+    }
+    (if_present * prior / evidence).clamp(0.0, 1.0)
+}
+
+/// Finalize a `Resurvey` task: look at the asteroid again and refine its
+/// `tag_beliefs`. Unlike a first survey, no survey data is generated.
+pub(crate) fn resolve_resurvey(
+    state: &mut GameState,
+    ship_id: &ShipId,
+    asteroid_id: &AsteroidId,
+    content: &GameContent,
+    rng: &mut impl Rng,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let current_tick = state.meta.tick;
+    let quality = state
+        .ships
+        .get(ship_id)
+        .map_or(content.constants.base_sensor_quality, |ship| {
+            sensor_quality(ship, &state.modifiers, content)
+        });
+
+    if let Some(asteroid) = state.asteroids.get_mut(asteroid_id) {
+        observe_tags(
+            &mut asteroid.knowledge.tag_beliefs,
+            &asteroid.anomaly_tags,
+            quality,
+            rng,
+        );
+        let tags = asteroid.knowledge.tag_beliefs.clone();
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            Event::ScanResult {
+                asteroid_id: asteroid_id.clone(),
+                tags,
+            },
+        ));
+    }
+
+    crate::tasks::set_ship_idle(state, ship_id, current_tick);
+
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::TaskCompleted {
+            ship_id: ship_id.clone(),
+            task_kind: "Resurvey".to_string(),
+            target: Some(asteroid_id.0.clone()),
+        },
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::update_belief;
+
+    #[test]
+    fn repeated_sightings_raise_belief_and_misses_lower_it() {
+        let seen_twice = update_belief(0.85, true, 0.85);
+        assert!(seen_twice > 0.85 && seen_twice < 1.0);
+        // A miss exactly cancels a sighting under a symmetric sensor.
+        assert!((update_belief(seen_twice, false, 0.85) - 0.85).abs() < 1e-5);
+        // An uninformative sensor leaves the belief alone.
+        assert!((update_belief(0.3, true, 0.5) - 0.3).abs() < 1e-6);
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{
-    AnomalyTag, AsteroidId, AsteroidKnowledge, AsteroidState, CompositionVec, DataKind, Event,
-    EventEnvelope, GameContent, GameState, InventoryItem, LotId, ResearchState, ShipId, ShipState,
-    SiteId, StationId, TaskKind, TaskState, TechEffect,
+    AnomalyTag, AsteroidId, AsteroidKnowledge, AsteroidLayer, AsteroidState, AsteroidTemplateDef,
+    CompositionLayers, CompositionVec, DataKind, Event, EventEnvelope, GameContent, GameState,
+    InventoryItem, LotId, ResearchState, ShipId, ShipState, SiteId, StationId, TaskKind, TaskState,
+    TechEffect,
 };
 use rand::Rng;
 
@@ -19,13 +20,16 @@ pub(crate) fn resolve_task(
             ref destination,
             ref then,
             ..
-        } => resolve_transit(state, ship_id, destination, then, content, events),
+        } => resolve_transit(state, ship_id, destination, then, content, rng, events),
         TaskKind::Survey { ref site } => {
             resolve_survey(state, ship_id, site, content, rng, events);
         }
         TaskKind::DeepScan { ref asteroid } => {
             resolve_deep_scan(state, ship_id, asteroid, content, rng, events);
         }
+        TaskKind::Resurvey { ref asteroid } => {
+            crate::survey::resolve_resurvey(state, ship_id, asteroid, content, rng, events);
+        }
         TaskKind::Mine { ref asteroid, .. } => {
             resolve_mine(state, ship_id, asteroid, content, events);
         }
@@ -39,6 +43,21 @@ pub(crate) fn resolve_task(
         } => {
             resolve_pickup(state, ship_id, from_station, items, then, content, events);
         }
+        TaskKind::TransferCargo {
+            ref counterparty,
+            direction,
+            ref items,
+        } => {
+            crate::cargo_transfer::resolve_transfer_cargo(
+                state,
+                ship_id,
+                counterparty,
+                *direction,
+                items,
+                content,
+                events,
+            );
+        }
         TaskKind::ConstructStation {
             ref frame_id,
             ref position,
@@ -55,8 +74,12 @@ pub(crate) fn resolve_task(
                 events,
             );
         }
-        TaskKind::Idle | TaskKind::Refuel { .. } => {}
+        TaskKind::DockForRepair { ref station } => {
+            resolve_dock_for_repair(state, ship_id, station, content, events);
+        }
+        TaskKind::Idle | TaskKind::Refuel { .. } | TaskKind::Escort { .. } => {}
     }
+    crate::wear::accrue_ship_task_wear(state, ship_id, task_kind, content, events);
 }
 
 /// Finalize a `ConstructStation` task: create a fresh, empty `StationState`
@@ -73,7 +96,41 @@ pub(crate) fn resolve_construct_station(
     events: &mut Vec<EventEnvelope>,
 ) {
     let current_tick = state.meta.tick;
+    // The station belongs to whoever owns the ship that built it.
+    let owner = state.ships.get(ship_id).map_or_else(
+        || crate::PrincipalId("principal_autopilot".to_string()),
+        |ship| ship.owner.clone(),
+    );
+    let station_id =
+        found_station_from_kit(state, frame_id, position, kit_component_id, owner, content);
+
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::StationDeployed {
+            station_id,
+            position: position.clone(),
+            ship_id: Some(ship_id.clone()),
+            frame_id: Some(frame_id.clone()),
+            kit_component_id: Some(kit_component_id.to_string()),
+        },
+    ));
 
+    set_ship_idle(state, ship_id, current_tick);
+}
+
+/// Create an empty `StationState` with the kit's frame and seed inventory
+/// at `position`, applying frame bonuses via the modifier pipeline. Shared
+/// by ship-built (`ConstructStation`) and station-built (`BuildStation`)
+/// deployments. Returns the new station's id.
+pub(crate) fn found_station_from_kit(
+    state: &mut GameState,
+    frame_id: &crate::FrameId,
+    position: &crate::Position,
+    kit_component_id: &str,
+    owner: crate::PrincipalId,
+    content: &GameContent,
+) -> crate::StationId {
     // Allocate a deterministic station id off the existing deploy counter.
     // This matches the P4 launch-delivered station naming convention
     // (station_{counter}) so save files and scoring treat them uniformly.
@@ -101,23 +158,14 @@ pub(crate) fn resolve_construct_station(
         },
         frame_id: Some(frame_id.clone()),
         leaders: Vec::new(),
+        owner,
+        expansions: crate::StationExpansions::default(),
+        power_policy: crate::PowerPolicy::default(),
+        history: std::collections::VecDeque::new(),
     };
     crate::recompute_station_stats(&mut station, content);
     state.stations.insert(station_id.clone(), station);
-
-    events.push(crate::emit(
-        &mut state.counters,
-        current_tick,
-        Event::StationDeployed {
-            station_id,
-            position: position.clone(),
-            ship_id: Some(ship_id.clone()),
-            frame_id: Some(frame_id.clone()),
-            kit_component_id: Some(kit_component_id.to_string()),
-        },
-    ));
-
-    set_ship_idle(state, ship_id, current_tick);
+    station_id
 }
 
 /// Build the initial inventory for a station deployed from a kit
@@ -261,6 +309,15 @@ pub fn inventory_mass_kg(inventory: &[InventoryItem]) -> f32 {
     inventory.iter().map(InventoryItem::mass_kg).sum()
 }
 
+/// kg of ore per tick this ship mines: `mining_rate_kg_per_tick` scaled by
+/// its class's `mining_rate_multiplier`.
+pub fn mining_rate_kg_per_tick(ship: &ShipState, content: &GameContent) -> f32 {
+    let multiplier = ship
+        .class(content)
+        .map_or(1.0, |class| class.mining_rate_multiplier);
+    content.constants.mining_rate_kg_per_tick * multiplier
+}
+
 /// Pre-compute how many ticks a mining run will take.
 ///
 /// Stops when the cargo hold fills OR the asteroid is depleted, whichever comes first.
@@ -268,9 +325,9 @@ pub fn mine_duration(asteroid: &AsteroidState, ship: &ShipState, content: &GameC
     let ore_density = element_density(content, crate::ELEMENT_ORE);
     let effective_m3_per_kg = 1.0 / ore_density;
 
-    let volume_used = inventory_volume_m3(&ship.inventory, content);
-    let free_volume = (ship.cargo_capacity_m3 - volume_used).max(0.0);
-    let rate = content.constants.mining_rate_kg_per_tick;
+    let free_volume =
+        crate::HoldSpace::of_ship(ship, content).free_for(crate::HoldPartition::OreBay);
+    let rate = mining_rate_kg_per_tick(ship, content);
 
     #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // clamp guards
     let ticks_to_fill = (free_volume / (rate * effective_m3_per_kg))
@@ -308,6 +365,7 @@ pub(crate) fn resolve_transit(
     destination: &crate::Position,
     then: &TaskKind,
     content: &GameContent,
+    rng: &mut impl Rng,
     events: &mut Vec<EventEnvelope>,
 ) {
     let current_tick = state.meta.tick;
@@ -324,6 +382,23 @@ pub(crate) fn resolve_transit(
             position: destination.clone(),
         },
     ));
+    crate::pollution::roll_debris_strike(
+        state,
+        ship_id,
+        &destination.parent_body,
+        content,
+        rng,
+        events,
+    );
+    crate::threats::roll_pirate_encounter(
+        state,
+        ship_id,
+        &destination.parent_body,
+        content,
+        rng,
+        events,
+    );
+    crate::threats::arrive_escorts(state, ship_id, destination, events);
 
     // Generate transit data from completed flight
     let data_amount = crate::research::generate_data(
@@ -412,15 +487,14 @@ pub(crate) fn resolve_survey(
     };
 
     // Roll composition from ranges, then normalise.
-    let mut composition: CompositionVec = template
-        .composition_ranges
-        .iter()
-        .map(|(element, &(min, max))| (element.clone(), rng.gen_range(min..=max)))
-        .collect();
-    normalise(&mut composition);
+    let mut composition = roll_composition(template, rng);
 
     let mass_kg = rng
         .gen_range(content.constants.asteroid_mass_min_kg..=content.constants.asteroid_mass_max_kg);
+    let layers = roll_layers(composition.clone(), mass_kg, template, content, rng);
+    if let Some(layers) = &layers {
+        composition = layers.bulk();
+    }
 
     let asteroid_id = AsteroidId(format!("asteroid_{:04}", state.counters.next_asteroid_id));
     state.counters.next_asteroid_id += 1;
@@ -437,7 +511,11 @@ pub(crate) fn resolve_survey(
             knowledge: AsteroidKnowledge {
                 tag_beliefs: vec![],
                 composition: None,
+                layers: None,
+                composition_sigma: None,
+                deep_scan_count: 0,
             },
+            layers,
         },
     );
 
@@ -449,14 +527,17 @@ pub(crate) fn resolve_survey(
             position: site.position.clone(),
         },
     ));
+    crate::contracts::record_survey(state, ship_id);
 
-    // Detect anomaly tags probabilistically.
-    let detection_prob = content.constants.survey_tag_detection_probability;
-    let detected_tags: Vec<(AnomalyTag, f32)> = anomaly_tags
-        .iter()
-        .filter(|_| rng.gen::<f32>() < detection_prob)
-        .map(|tag| (tag.clone(), detection_prob))
-        .collect();
+    // Detect anomaly tags with the ship's sensor.
+    let quality = state
+        .ships
+        .get(ship_id)
+        .map_or(content.constants.base_sensor_quality, |ship| {
+            crate::survey::sensor_quality(ship, &state.modifiers, content)
+        });
+    let mut detected_tags: Vec<(AnomalyTag, f32)> = Vec::new();
+    crate::survey::observe_tags(&mut detected_tags, &anomaly_tags, quality, rng);
 
     if let Some(asteroid) = state.asteroids.get_mut(&asteroid_id) {
         asteroid.knowledge.tag_beliefs.clone_from(&detected_tags);
@@ -477,6 +558,13 @@ pub(crate) fn resolve_survey(
         "survey",
         &content.constants,
     );
+    let data_amount = crate::pollution::dampen_scan_data(
+        state,
+        &site.position.parent_body,
+        &DataKind::new(DataKind::SURVEY),
+        data_amount,
+        content,
+    );
 
     events.push(crate::emit(
         &mut state.counters,
@@ -500,6 +588,96 @@ pub(crate) fn resolve_survey(
     ));
 }
 
+fn roll_composition(template: &AsteroidTemplateDef, rng: &mut impl Rng) -> CompositionVec {
+    let mut composition: CompositionVec = template
+        .composition_ranges
+        .iter()
+        .map(|(element, &(min, max))| (element.clone(), rng.gen_range(min..=max)))
+        .collect();
+    normalise(&mut composition);
+    composition
+}
+
+/// Roll a surface/core split for a new asteroid, using `surface` as the
+/// surface composition. Draws nothing when layering is disabled, so
+/// uniform-rock content keeps its random sequence.
+fn roll_layers(
+    surface: CompositionVec,
+    mass_kg: f32,
+    template: &AsteroidTemplateDef,
+    content: &GameContent,
+    rng: &mut impl Rng,
+) -> Option<CompositionLayers> {
+    let c = &content.constants;
+    if c.asteroid_surface_fraction_max <= 0.0 {
+        return None;
+    }
+    let core = roll_composition(template, rng);
+    let min = c
+        .asteroid_surface_fraction_min
+        .min(c.asteroid_surface_fraction_max);
+    let surface_fraction = rng.gen_range(min..=c.asteroid_surface_fraction_max);
+    Some(CompositionLayers {
+        surface_fraction,
+        initial_mass_kg: mass_kg,
+        surface,
+        core,
+    })
+}
+
+/// Cut `kg` of ore from an asteroid into a new lot, removing the asteroid
+/// once it is exhausted. Returns the lot, the mass left and the deepest
+/// layer reached (`None` for uniform rocks). `kg` must not exceed the
+/// asteroid's mass.
+pub(crate) fn extract_ore(
+    state: &mut GameState,
+    asteroid_id: &AsteroidId,
+    kg: f32,
+) -> (InventoryItem, f32, Option<AsteroidLayer>) {
+    let asteroid = &state.asteroids[asteroid_id];
+    // Snapshot composition at mine-time (known composition if deep-scanned, else true
+    // composition). Layered rocks yield the composition of the layer(s) being cut.
+    let (composition, layer) = match &asteroid.layers {
+        Some(layers) => {
+            let profile = asteroid.knowledge.layers.as_ref().unwrap_or(layers);
+            // Report the deepest layer the cut reached.
+            let reached_core = layers.surface_remaining_kg(asteroid.mass_kg) < kg;
+            let layer = if reached_core {
+                AsteroidLayer::Core
+            } else {
+                layers.layer_at(asteroid.mass_kg)
+            };
+            (profile.extract(asteroid.mass_kg, kg), Some(layer))
+        }
+        None => (
+            asteroid
+                .knowledge
+                .composition
+                .clone()
+                .unwrap_or_else(|| asteroid.true_composition.clone()),
+            None,
+        ),
+    };
+
+    let lot_id = LotId(format!("lot_{:04}", state.counters.next_lot_id));
+    state.counters.next_lot_id += 1;
+
+    let asteroid_remaining_kg = asteroid.mass_kg - kg;
+    if asteroid_remaining_kg <= 0.0 {
+        state.asteroids.remove(asteroid_id);
+    } else if let Some(asteroid) = state.asteroids.get_mut(asteroid_id) {
+        asteroid.mass_kg = asteroid_remaining_kg;
+    }
+
+    let ore_item = InventoryItem::Ore {
+        lot_id,
+        asteroid_id: asteroid_id.clone(),
+        kg,
+        composition,
+    };
+    (ore_item, asteroid_remaining_kg, layer)
+}
+
 pub(crate) fn resolve_mine(
     state: &mut GameState,
     ship_id: &ShipId,
@@ -521,34 +699,14 @@ pub(crate) fn resolve_mine(
     let ore_density = element_density(content, crate::ELEMENT_ORE);
     let effective_m3_per_kg = 1.0 / ore_density;
 
-    let volume_used = inventory_volume_m3(&ship.inventory, content);
-    let free_volume = (ship.cargo_capacity_m3 - volume_used).max(0.0);
+    let free_volume =
+        crate::HoldSpace::of_ship(ship, content).free_for(crate::HoldPartition::OreBay);
     let max_kg_by_volume = free_volume / effective_m3_per_kg;
-    let extracted_total_kg = asteroid.mass_kg.min(max_kg_by_volume);
-
-    // Snapshot composition at mine-time (known composition if deep-scanned, else true composition).
-    let composition = asteroid
-        .knowledge
-        .composition
-        .clone()
-        .unwrap_or_else(|| asteroid.true_composition.clone());
-
-    let lot_id = LotId(format!("lot_{:04}", state.counters.next_lot_id));
-    state.counters.next_lot_id += 1;
-
-    let asteroid_remaining_kg = asteroid.mass_kg - extracted_total_kg;
-    if asteroid_remaining_kg <= 0.0 {
-        state.asteroids.remove(asteroid_id);
-    } else if let Some(asteroid) = state.asteroids.get_mut(asteroid_id) {
-        asteroid.mass_kg = asteroid_remaining_kg;
-    }
-
-    let ore_item = InventoryItem::Ore {
-        lot_id,
-        asteroid_id: asteroid_id.clone(),
-        kg: extracted_total_kg,
-        composition,
-    };
+    // A worn ship brings back less of what it cuts; the rest stays put.
+    let yield_factor = crate::wear::wear_efficiency(ship.wear.wear, &content.constants);
+    let extracted_total_kg = asteroid.mass_kg.min(max_kg_by_volume) * yield_factor;
+    let (ore_item, asteroid_remaining_kg, layer) =
+        extract_ore(state, asteroid_id, extracted_total_kg);
 
     if let Some(ship) = state.ships.get_mut(ship_id) {
         ship.inventory.push(ore_item.clone());
@@ -562,6 +720,7 @@ pub(crate) fn resolve_mine(
             asteroid_id: asteroid_id.clone(),
             ore_lot: ore_item,
             asteroid_remaining_kg: asteroid_remaining_kg.max(0.0),
+            layer,
         },
     ));
 
@@ -704,6 +863,7 @@ pub(crate) fn resolve_deposit(
             items: to_deposit,
         },
     ));
+    crate::freight_routes::record_delivery(state, ship_id, station_id, current_tick, events);
 
     if was_blocked {
         events.push(crate::emit(
@@ -747,12 +907,11 @@ pub(crate) fn resolve_pickup(
     let current_tick = state.meta.tick;
 
     // Compute remaining ship capacity before any pickups.
-    let mut remaining_capacity = {
+    let mut space = {
         let Some(ship) = state.ships.get(ship_id) else {
             return;
         };
-        let used = inventory_volume_m3(&ship.inventory, content);
-        (ship.cargo_capacity_m3 - used).max(0.0)
+        crate::HoldSpace::of_ship(ship, content)
     };
 
     // Pull matching items out of the station inventory, one TradeItemSpec
@@ -763,9 +922,10 @@ pub(crate) fn resolve_pickup(
         let Some(station) = state.stations.get_mut(from_station) else {
             break; // Station disappeared — abort further pickups.
         };
-        let taken = take_items_for_spec(station, spec, remaining_capacity, content);
+        let partition = crate::HoldPartition::of_trade(spec);
+        let taken = take_items_for_spec(station, spec, space.free_for(partition), content);
         for item in taken {
-            remaining_capacity = (remaining_capacity - item_volume_m3(&item, content)).max(0.0);
+            space.fill(partition, item_volume_m3(&item, content));
             picked_up.push(item);
         }
     }
@@ -783,7 +943,7 @@ pub(crate) fn resolve_pickup(
     // VIO-600: Track inter-station transfer volume (only for non-empty pickups).
     if !picked_up.is_empty() {
         let pickup_mass_kg: f32 = picked_up.iter().map(InventoryItem::mass_kg).sum();
-        state.transfer_volume_kg += f64::from(pickup_mass_kg);
+        crate::fixed::add_total_kg(&mut state.transfer_volume_kg, f64::from(pickup_mass_kg));
         state.transfer_count += 1;
     }
 
@@ -835,18 +995,34 @@ pub(crate) fn resolve_pickup(
 /// component stacks are split, missing items are skipped. Volume check
 /// is strict — items whose `item_volume_m3` exceeds remaining capacity
 /// are not partially loaded (components/modules are atomic).
-fn take_items_for_spec(
+pub(crate) fn take_items_for_spec(
     station: &mut crate::StationState,
     spec: &crate::TradeItemSpec,
     remaining_capacity: f32,
     content: &GameContent,
+) -> Vec<InventoryItem> {
+    take_spec_from_inventory(
+        &mut station.core.inventory,
+        spec,
+        remaining_capacity,
+        content,
+    )
+}
+
+/// Remove the items `spec` asks for from any inventory, bounded by the
+/// receiving side's `remaining_capacity` (m³).
+pub(crate) fn take_spec_from_inventory(
+    inventory: &mut Vec<InventoryItem>,
+    spec: &crate::TradeItemSpec,
+    remaining_capacity: f32,
+    content: &GameContent,
 ) -> Vec<InventoryItem> {
     match spec {
         crate::TradeItemSpec::Material {
             element,
             kg: requested_kg,
         } => take_material(
-            &mut station.core.inventory,
+            inventory,
             element,
             *requested_kg,
             remaining_capacity,
@@ -855,19 +1031,10 @@ fn take_items_for_spec(
         crate::TradeItemSpec::Component {
             component_id,
             count,
-        } => take_components(
-            &mut station.core.inventory,
-            component_id,
-            *count,
-            remaining_capacity,
-            content,
-        ),
-        crate::TradeItemSpec::Module { module_def_id } => take_module(
-            &mut station.core.inventory,
-            module_def_id,
-            remaining_capacity,
-            content,
-        ),
+        } => take_components(inventory, component_id, *count, remaining_capacity, content),
+        crate::TradeItemSpec::Module { module_def_id } => {
+            take_module(inventory, module_def_id, remaining_capacity, content)
+        }
         crate::TradeItemSpec::Crew { .. } => Vec::new(), // Crew transfer not supported.
     }
 }
@@ -916,7 +1083,7 @@ fn take_material(
             taken.push(inventory.remove(index));
         } else {
             // Split: extract `to_take` kg into a new InventoryItem.
-            *kg -= to_take;
+            crate::fixed::add_kg(kg, -to_take);
             taken.push(InventoryItem::Material {
                 element: e.clone(),
                 kg: to_take,
@@ -1012,6 +1179,90 @@ fn take_module(
     }
 }
 
+/// True value + uniform noise in [-sigma, sigma], clamped and normalised.
+fn map_composition(
+    true_composition: &CompositionVec,
+    sigma: f32,
+    rng: &mut impl Rng,
+) -> CompositionVec {
+    let mut mapped: CompositionVec = true_composition
+        .iter()
+        .map(|(element, &true_value)| {
+            let noise = if sigma > 0.0 {
+                rng.gen_range(-sigma..=sigma)
+            } else {
+                0.0
+            };
+            (element.clone(), (true_value + noise).clamp(0.0, 1.0))
+        })
+        .collect();
+    normalise(&mut mapped);
+    mapped
+}
+
+/// Fold a deep scan sample with noise half-width `sigma` into the
+/// asteroid's known composition. Samples are weighted by inverse variance,
+/// so repeat scans shrink the error roughly as `sigma / sqrt(n)` and a scan
+/// with better tech outweighs older, noisier ones. An estimate of unknown
+/// accuracy is replaced.
+fn refine_composition(
+    knowledge: &mut AsteroidKnowledge,
+    sample: CompositionVec,
+    sample_layers: Option<CompositionLayers>,
+    sigma: f32,
+) {
+    let prior_sigma = knowledge
+        .composition
+        .as_ref()
+        .and(knowledge.composition_sigma);
+    let (weight, combined_sigma) = match prior_sigma {
+        Some(prior) => {
+            let (prior_var, sample_var) = (prior * prior, sigma * sigma);
+            let total = prior_var + sample_var;
+            if total > 0.0 {
+                (prior_var / total, (prior_var * sample_var / total).sqrt())
+            } else {
+                (0.0, 0.0)
+            }
+        }
+        None => (1.0, sigma),
+    };
+
+    knowledge.composition = Some(match knowledge.composition.take() {
+        Some(known) if weight < 1.0 => blend_composition(&known, &sample, weight),
+        _ => sample,
+    });
+    knowledge.layers = match (knowledge.layers.take(), sample_layers) {
+        (Some(known), Some(layers)) if weight < 1.0 => Some(CompositionLayers {
+            surface: blend_composition(&known.surface, &layers.surface, weight),
+            core: blend_composition(&known.core, &layers.core, weight),
+            ..layers
+        }),
+        (_, layers) => layers,
+    };
+    knowledge.composition_sigma = Some(combined_sigma);
+    knowledge.deep_scan_count += 1;
+}
+
+/// `known × (1 − weight) + sample × weight`, element-wise, normalised.
+fn blend_composition(
+    known: &CompositionVec,
+    sample: &CompositionVec,
+    weight: f32,
+) -> CompositionVec {
+    let mut blended: CompositionVec = known
+        .keys()
+        .chain(sample.keys())
+        .map(|element| {
+            let old = known.get(element).copied().unwrap_or(0.0);
+            let new = sample.get(element).copied().unwrap_or(0.0);
+            (element.clone(), old * (1.0 - weight) + new * weight)
+        })
+        .collect();
+    normalise(&mut blended);
+    blended
+}
+
 pub(crate) fn resolve_deep_scan(
     state: &mut GameState,
     ship_id: &ShipId,
@@ -1024,40 +1275,39 @@ pub(crate) fn resolve_deep_scan(
 
     let sigma = composition_noise_sigma(&state.research, content);
 
-    let Some(true_composition) = state
-        .asteroids
-        .get(asteroid_id)
-        .map(|a| a.true_composition.clone())
+    let Some((true_composition, true_layers, body_id)) =
+        state.asteroids.get(asteroid_id).map(|a| {
+            (
+                a.true_composition.clone(),
+                a.layers.clone(),
+                a.position.parent_body.clone(),
+            )
+        })
     else {
         return; // Asteroid not found — shouldn't happen with valid state.
     };
 
-    // Map composition: true value + uniform noise in [-sigma, sigma], clamped and normalised.
-    let mut mapped: CompositionVec = true_composition
-        .iter()
-        .map(|(element, &true_value)| {
-            let noise = if sigma > 0.0 {
-                rng.gen_range(-sigma..=sigma)
-            } else {
-                0.0
-            };
-            (element.clone(), (true_value + noise).clamp(0.0, 1.0))
-        })
-        .collect();
-    normalise(&mut mapped);
-
-    if let Some(asteroid) = state.asteroids.get_mut(asteroid_id) {
-        asteroid.knowledge.composition = Some(mapped.clone());
-    }
+    let mapped = map_composition(&true_composition, sigma, rng);
+    // Layer profiles are drawn after the bulk map so uniform rocks keep their sequence.
+    let mapped_layers = true_layers.map(|layers| CompositionLayers {
+        surface: map_composition(&layers.surface, sigma, rng),
+        core: map_composition(&layers.core, sigma, rng),
+        ..layers
+    });
 
-    events.push(crate::emit(
-        &mut state.counters,
-        current_tick,
-        Event::CompositionMapped {
-            asteroid_id: asteroid_id.clone(),
-            composition: mapped,
-        },
-    ));
+    let Some(asteroid) = state.asteroids.get_mut(asteroid_id) else {
+        return;
+    };
+    refine_composition(&mut asteroid.knowledge, mapped, mapped_layers, sigma);
+    let knowledge = &asteroid.knowledge;
+    let event = Event::CompositionMapped {
+        asteroid_id: asteroid_id.clone(),
+        composition: knowledge.composition.clone().unwrap_or_default(),
+        layers: knowledge.layers.clone(),
+        composition_sigma: knowledge.composition_sigma.unwrap_or(sigma),
+        deep_scan_count: knowledge.deep_scan_count,
+    };
+    events.push(crate::emit(&mut state.counters, current_tick, event));
 
     let data_amount = crate::research::generate_data(
         &mut state.research,
@@ -1065,6 +1315,13 @@ pub(crate) fn resolve_deep_scan(
         "deep_scan",
         &content.constants,
     );
+    let data_amount = crate::pollution::dampen_scan_data(
+        state,
+        &body_id,
+        &DataKind::new(DataKind::SURVEY),
+        data_amount,
+        content,
+    );
 
     events.push(crate::emit(
         &mut state.counters,
@@ -1088,6 +1345,97 @@ pub(crate) fn resolve_deep_scan(
     ));
 }
 
+/// Finish a `DockForRepair` task. The station's maintenance bay takes
+/// `wear_reduction_per_run` off the ship's wear for every
+/// `repair_kit_cost` kits it draws from station inventory, until the ship
+/// is unworn or the kits run out. Nothing is repaired when the ship is not
+/// docked at the station or the station has no enabled bay.
+pub(crate) fn resolve_dock_for_repair(
+    state: &mut GameState,
+    ship_id: &ShipId,
+    station_id: &StationId,
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let current_tick = state.meta.tick;
+    let (Some(ship), Some(station)) = (state.ships.get(ship_id), state.stations.get(station_id))
+    else {
+        set_ship_idle(state, ship_id, current_tick);
+        return;
+    };
+    let docked = crate::is_co_located(
+        &ship.position,
+        &station.position,
+        &state.body_cache,
+        content.constants.docking_range_au_um,
+    );
+    let wear_before = ship.wear.wear;
+    let bay = crate::wear::repair_bay(station, content).filter(|bay| {
+        docked && wear_before > 0.0 && bay.wear_reduction_per_run > 0.0 && bay.repair_kit_cost > 0
+    });
+
+    if let Some(bay) = bay {
+        let kits_on_hand = state.stations.get(station_id).map_or(0, |station| {
+            station
+                .core
+                .inventory
+                .iter()
+                .filter_map(|item| match item {
+                    InventoryItem::Component {
+                        component_id,
+                        count,
+                        ..
+                    } if component_id.0 == bay.maintenance_component_id => Some(*count),
+                    _ => None,
+                })
+                .sum()
+        });
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // wear is in 0..=1
+        let runs_needed = (wear_before / bay.wear_reduction_per_run).ceil() as u32;
+        let runs = runs_needed.min(kits_on_hand / bay.repair_kit_cost);
+        if runs > 0 {
+            let repair_kits_used = runs * bay.repair_kit_cost;
+            #[allow(clippy::cast_precision_loss)]
+            let wear_after = (wear_before - bay.wear_reduction_per_run * runs as f32).max(0.0);
+            if let Some(station) = state.stations.get_mut(station_id) {
+                crate::trade::remove_inventory_items(
+                    &mut station.core.inventory,
+                    &crate::TradeItemSpec::Component {
+                        component_id: crate::ComponentId(bay.maintenance_component_id.clone()),
+                        count: repair_kits_used,
+                    },
+                );
+                station.invalidate_volume_cache();
+            }
+            if let Some(ship) = state.ships.get_mut(ship_id) {
+                ship.wear.wear = wear_after;
+            }
+            events.push(crate::emit(
+                &mut state.counters,
+                current_tick,
+                Event::ShipRepaired {
+                    ship_id: ship_id.clone(),
+                    station_id: station_id.clone(),
+                    wear_before,
+                    wear_after,
+                    repair_kits_used,
+                },
+            ));
+        }
+    }
+
+    set_ship_idle(state, ship_id, current_tick);
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::TaskCompleted {
+            ship_id: ship_id.clone(),
+            task_kind: "DockForRepair".to_string(),
+            target: Some(station_id.0.clone()),
+        },
+    ));
+}
+
 // ---------------------------------------------------------------------------
 // Refuel resolution (ongoing task — runs every tick)
 // ---------------------------------------------------------------------------
@@ -1312,7 +1660,7 @@ fn deduct_station_propellant(
             if let InventoryItem::Material { element, kg, .. } = item {
                 if element == propellant {
                     let deduct = remaining.min(*kg);
-                    *kg -= deduct;
+                    crate::fixed::add_kg(kg, -deduct);
                     remaining -= deduct;
                 }
             }
@@ -113,6 +113,7 @@ impl ModuleDefBuilder {
                 mass_kg: 0.0,
                 volume_m3: 0.0,
                 power_consumption_per_run: 0.0,
+                power_idle_kw: 0.0,
                 wear_per_run: 0.0,
                 behavior: ModuleBehaviorDef::Processor(ProcessorDef {
                     processing_interval_minutes: 1,
@@ -128,6 +129,8 @@ impl ModuleDefBuilder {
                 required_tech: None,
                 ports: Vec::new(),
                 operating_cost_per_tick: 0.0,
+                upgrade_to: None,
+                deferrable: false,
             },
         }
     }
@@ -152,6 +155,12 @@ impl ModuleDefBuilder {
         self
     }
 
+    #[must_use]
+    pub fn idle_power(mut self, kw: f32) -> Self {
+        self.def.power_idle_kw = kw;
+        self
+    }
+
     pub fn wear(mut self, per_run: f32) -> Self {
         self.def.wear_per_run = per_run;
         self
@@ -209,6 +218,18 @@ impl ModuleDefBuilder {
         self
     }
 
+    #[must_use]
+    pub fn deferrable(mut self) -> Self {
+        self.def.deferrable = true;
+        self
+    }
+
+    #[must_use]
+    pub fn upgrade_to(mut self, upgrade: crate::ModuleUpgradeDef) -> Self {
+        self.def.upgrade_to = Some(upgrade);
+        self
+    }
+
     pub fn build(self) -> ModuleDef {
         self.def
     }
@@ -243,6 +264,7 @@ pub fn base_content() -> GameContent {
                 angle_mdeg: 0,
                 solar_intensity: 1.0,
                 zone: None,
+                security: 1.0,
             }],
             nodes: vec![NodeDef {
                 id: NodeId("node_test".to_string()),
@@ -274,6 +296,8 @@ pub fn base_content() -> GameContent {
                 boiloff_rate_per_day_at_293k: None,
                 boiling_point_mk: None,
                 boiloff_curve: None,
+                volatile: false,
+                quality_decay_per_day: None,
             },
             ElementDef {
                 id: "Fe".to_string(),
@@ -287,6 +311,8 @@ pub fn base_content() -> GameContent {
                 boiloff_rate_per_day_at_293k: None,
                 boiling_point_mk: None,
                 boiloff_curve: None,
+                volatile: false,
+                quality_decay_per_day: None,
             },
             ElementDef {
                 id: "Si".to_string(),
@@ -300,6 +326,8 @@ pub fn base_content() -> GameContent {
                 boiloff_rate_per_day_at_293k: None,
                 boiling_point_mk: None,
                 boiloff_curve: None,
+                volatile: false,
+                quality_decay_per_day: None,
             },
             ElementDef {
                 id: "slag".to_string(),
@@ -313,6 +341,8 @@ pub fn base_content() -> GameContent {
                 boiloff_rate_per_day_at_293k: None,
                 boiling_point_mk: None,
                 boiloff_curve: None,
+                volatile: false,
+                quality_decay_per_day: None,
             },
             ElementDef {
                 id: "H2O".to_string(),
@@ -326,6 +356,8 @@ pub fn base_content() -> GameContent {
                 boiloff_rate_per_day_at_293k: None,
                 boiling_point_mk: None,
                 boiloff_curve: None,
+                volatile: false,
+                quality_decay_per_day: None,
             },
         ],
         module_defs: AHashMap::default(),
@@ -340,7 +372,7 @@ pub fn base_content() -> GameContent {
             survey_scan_minutes: 1,
             deep_scan_minutes: 1,
             // Always detect tags so tests are predictable.
-            survey_tag_detection_probability: 1.0,
+            base_sensor_quality: 1.0,
             asteroid_count_per_template: 1,
             asteroid_mass_min_kg: 500.0, // fixed range so tests are deterministic
             asteroid_mass_max_kg: 500.0,
@@ -381,6 +413,10 @@ pub fn base_content() -> GameContent {
             min_meaningful_kg: 1e-3,
             replenish_batch_size: 5,
             trade_unlock_delay_minutes: 525_600,
+            import_delivery_minutes_per_kg: 0.0,
+            import_cancel_refund_fraction: 0.5,
+            freight_cost_per_kg_hop: 25.0,
+            freight_handling_minutes: 720,
             autopilot_budget_cap_fraction: 0.05,
             autopilot_lh2_abundant_multiplier: 2.0,
             boiloff_hot_offset_mk: 100_000,
@@ -391,12 +427,22 @@ pub fn base_content() -> GameContent {
             deposit_ticks: 0,
             station_power_available_per_tick: 0.0,
             refuel_kg_per_tick: 0.0,
+            solar_storm_duration_ticks: 0,
+            claim_duration_ticks: 0,
+            loan_interest_rate_per_tick: 0.0,
+            loan_repayment_interval_ticks: 0,
+            market_update_interval_ticks: 0,
+            contract_offer_interval_ticks: 0,
+            contract_duration_ticks: 0,
+            ship_repair_ticks: 0,
             events_enabled: false,
             event_global_cooldown_ticks: 200,
             event_history_capacity: 100,
             // Propulsion
             fuel_cost_per_au: 500.0,
             reference_mass_kg: 15_000.0,
+            mission_range_propellant_kg_per_hop: 0.0,
+            mission_range_refuse: false,
             refuel_kg_per_minute: 16.67,
             autopilot_refuel_threshold_pct: 0.8,
             autopilot_refuel_max_pct: 0.99,
@@ -413,10 +459,63 @@ pub fn base_content() -> GameContent {
             research_domain_rates: std::collections::HashMap::new(),
             research_tier_scaling: Vec::new(),
             research_lab_diminishing_returns: 1.0,
+            slag_pollution_threshold_kg: 100_000.0,
+            slag_pollution_scan_data_mult: 0.75,
+            slag_pollution_hazard_chance: 0.05,
+            slag_pollution_hazard_propellant_frac: 0.1,
+            hotspot_report_interval_ticks: 0,
+            hotspot_report_top_n: 5,
+            solar_storm_chance_per_day: 0.0,
+            solar_storm_duration_minutes: 720,
+            solar_storm_intensity_mult: 0.3,
+            solar_storm_wear_mult: 2.0,
+            solar_storm_deep_scan_interrupt_chance: 0.5,
+            solar_storm_ship_loss_chance: 0.0,
+            claim_cost: 0.0,
+            claim_duration_minutes: 0,
+            starting_loan: 0.0,
+            loan_interest_rate_per_day: 0.0,
+            loan_repayment_interval_minutes: 0,
+            loan_repayment_amount: 0.0,
+            loan_default_after_missed: 3,
+            market_update_interval_minutes: 0,
+            market_volatility: 0.0,
+            market_price_min_mult: 0.25,
+            market_price_max_mult: 4.0,
+            contract_offer_interval_minutes: 0,
+            contract_max_offers: 3,
+            contract_duration_minutes: 10_080,
+            contract_deliver_kg_min: 500.0,
+            contract_deliver_kg_max: 5_000.0,
+            contract_survey_count_max: 5,
+            contract_reward_premium: 1.5,
+            contract_reward_per_survey: 100_000.0,
+            contract_penalty_fraction: 0.25,
+            module_scrap_recovery_fraction: 0.5,
+            module_scrap_element: "Fe".to_string(),
+            module_upgrade_wear_carryover: 0.5,
+            power_deferrable_battery_reserve: 0.5,
+            life_support_failure_efficiency: 0.5,
+            // Ship wear
+            ship_wear_per_mining_tick: 0.0,
+            ship_wear_per_transit_tick: 0.0,
+            ship_repair_minutes: 240,
+            ship_insurance_payout_fraction: 0.0,
+            pirate_encounter_chance: 0.0,
+            pirate_cargo_theft_fraction: 0.5,
+            asteroid_surface_fraction_min: 0.0,
+            asteroid_surface_fraction_max: 0.0,
+            volatile_ore_loss_per_day: 0.0,
+            climate_controlled_decay_mult: 0.25,
+            production_report_interval_ticks: 0,
+            idle_diagnosis_interval_ticks: 0,
+            station_history_capacity: 32,
         },
         alert_rules: Vec::new(),
         events: Vec::new(),
         hulls: BTreeMap::new(),
+        ship_classes: BTreeMap::new(),
+        belts: Vec::new(),
         fitting_templates: BTreeMap::new(),
         initial_station: InitialStationDef::default(),
         initial_stations: Vec::new(),
@@ -428,8 +527,12 @@ pub fn base_content() -> GameContent {
         rocket_defs: std::collections::BTreeMap::new(),
         satellite_defs: std::collections::BTreeMap::new(),
         frames: std::collections::BTreeMap::new(),
+        adjacency_rules: Vec::new(),
         phase_presets: std::collections::BTreeMap::new(),
+        subsystems: crate::Subsystems::default(),
         density_map: AHashMap::default(),
+        starting_unlocked: Vec::new(),
+        project_defs: std::collections::BTreeMap::new(),
     };
     content.constants.derive_tick_values();
     content.init_caches();
@@ -536,6 +639,8 @@ pub fn minimal_content() -> GameContent {
                 boiloff_rate_per_day_at_293k: None,
                 boiling_point_mk: None,
                 boiloff_curve: None,
+                volatile: false,
+                quality_decay_per_day: None,
             },
             ElementDef {
                 id: "Fe".to_string(),
@@ -549,6 +654,8 @@ pub fn minimal_content() -> GameContent {
                 boiloff_rate_per_day_at_293k: None,
                 boiling_point_mk: None,
                 boiloff_curve: None,
+                volatile: false,
+                quality_decay_per_day: None,
             },
             ElementDef {
                 id: "slag".to_string(),
@@ -562,6 +669,8 @@ pub fn minimal_content() -> GameContent {
                 boiloff_rate_per_day_at_293k: None,
                 boiling_point_mk: None,
                 boiloff_curve: None,
+                volatile: false,
+                quality_decay_per_day: None,
             },
             ElementDef {
                 id: "H2O".to_string(),
@@ -575,6 +684,8 @@ pub fn minimal_content() -> GameContent {
                 boiloff_rate_per_day_at_293k: None,
                 boiling_point_mk: None,
                 boiloff_curve: None,
+                volatile: false,
+                quality_decay_per_day: None,
             },
         ],
         module_defs: AHashMap::default(),
@@ -588,7 +699,7 @@ pub fn minimal_content() -> GameContent {
         constants: Constants {
             survey_scan_minutes: 1,
             deep_scan_minutes: 1,
-            survey_tag_detection_probability: 1.0,
+            base_sensor_quality: 1.0,
             asteroid_count_per_template: 0,
             station_power_available_per_minute: 0.0,
             asteroid_mass_min_kg: 100.0,
@@ -629,6 +740,10 @@ pub fn minimal_content() -> GameContent {
             min_meaningful_kg: 1e-3,
             replenish_batch_size: 5,
             trade_unlock_delay_minutes: 525_600,
+            import_delivery_minutes_per_kg: 0.0,
+            import_cancel_refund_fraction: 0.5,
+            freight_cost_per_kg_hop: 25.0,
+            freight_handling_minutes: 720,
             autopilot_budget_cap_fraction: 0.05,
             autopilot_lh2_abundant_multiplier: 2.0,
             boiloff_hot_offset_mk: 100_000,
@@ -639,12 +754,22 @@ pub fn minimal_content() -> GameContent {
             deposit_ticks: 0,
             station_power_available_per_tick: 0.0,
             refuel_kg_per_tick: 0.0,
+            solar_storm_duration_ticks: 0,
+            claim_duration_ticks: 0,
+            loan_interest_rate_per_tick: 0.0,
+            loan_repayment_interval_ticks: 0,
+            market_update_interval_ticks: 0,
+            contract_offer_interval_ticks: 0,
+            contract_duration_ticks: 0,
+            ship_repair_ticks: 0,
             events_enabled: false,
             event_global_cooldown_ticks: 200,
             event_history_capacity: 100,
             // Propulsion
             fuel_cost_per_au: 500.0,
             reference_mass_kg: 15_000.0,
+            mission_range_propellant_kg_per_hop: 0.0,
+            mission_range_refuse: false,
             refuel_kg_per_minute: 16.67,
             autopilot_refuel_threshold_pct: 0.8,
             autopilot_refuel_max_pct: 0.99,
@@ -661,10 +786,63 @@ pub fn minimal_content() -> GameContent {
             research_domain_rates: std::collections::HashMap::new(),
             research_tier_scaling: Vec::new(),
             research_lab_diminishing_returns: 1.0,
+            slag_pollution_threshold_kg: 100_000.0,
+            slag_pollution_scan_data_mult: 0.75,
+            slag_pollution_hazard_chance: 0.05,
+            slag_pollution_hazard_propellant_frac: 0.1,
+            hotspot_report_interval_ticks: 0,
+            hotspot_report_top_n: 5,
+            solar_storm_chance_per_day: 0.0,
+            solar_storm_duration_minutes: 720,
+            solar_storm_intensity_mult: 0.3,
+            solar_storm_wear_mult: 2.0,
+            solar_storm_deep_scan_interrupt_chance: 0.5,
+            solar_storm_ship_loss_chance: 0.0,
+            claim_cost: 0.0,
+            claim_duration_minutes: 0,
+            starting_loan: 0.0,
+            loan_interest_rate_per_day: 0.0,
+            loan_repayment_interval_minutes: 0,
+            loan_repayment_amount: 0.0,
+            loan_default_after_missed: 3,
+            market_update_interval_minutes: 0,
+            market_volatility: 0.0,
+            market_price_min_mult: 0.25,
+            market_price_max_mult: 4.0,
+            contract_offer_interval_minutes: 0,
+            contract_max_offers: 3,
+            contract_duration_minutes: 10_080,
+            contract_deliver_kg_min: 500.0,
+            contract_deliver_kg_max: 5_000.0,
+            contract_survey_count_max: 5,
+            contract_reward_premium: 1.5,
+            contract_reward_per_survey: 100_000.0,
+            contract_penalty_fraction: 0.25,
+            module_scrap_recovery_fraction: 0.5,
+            module_scrap_element: "Fe".to_string(),
+            module_upgrade_wear_carryover: 0.5,
+            power_deferrable_battery_reserve: 0.5,
+            life_support_failure_efficiency: 0.5,
+            // Ship wear
+            ship_wear_per_mining_tick: 0.0,
+            ship_wear_per_transit_tick: 0.0,
+            ship_repair_minutes: 240,
+            ship_insurance_payout_fraction: 0.0,
+            pirate_encounter_chance: 0.0,
+            pirate_cargo_theft_fraction: 0.5,
+            asteroid_surface_fraction_min: 0.0,
+            asteroid_surface_fraction_max: 0.0,
+            volatile_ore_loss_per_day: 0.0,
+            climate_controlled_decay_mult: 0.25,
+            production_report_interval_ticks: 0,
+            idle_diagnosis_interval_ticks: 0,
+            station_history_capacity: 32,
         },
         alert_rules: Vec::new(),
         events: Vec::new(),
         hulls: BTreeMap::new(),
+        ship_classes: BTreeMap::new(),
+        belts: Vec::new(),
         fitting_templates: BTreeMap::new(),
         initial_station: InitialStationDef::default(),
         initial_stations: Vec::new(),
@@ -692,25 +870,83 @@ pub fn minimal_content() -> GameContent {
         rocket_defs: std::collections::BTreeMap::new(),
         satellite_defs: std::collections::BTreeMap::new(),
         frames: std::collections::BTreeMap::new(),
+        adjacency_rules: Vec::new(),
         phase_presets: std::collections::BTreeMap::new(),
+        subsystems: crate::Subsystems::default(),
         density_map: AHashMap::default(),
+        starting_unlocked: Vec::new(),
+        project_defs: std::collections::BTreeMap::new(),
     };
     content.constants.derive_tick_values();
     content.init_caches();
     content
 }
 
+/// Empty ship `ship_0001` at `test_body`, homed at the test station.
+pub fn test_ship() -> ShipState {
+    ShipState {
+        id: test_ship_id(),
+        position: test_position(),
+        owner: PrincipalId("principal_autopilot".to_string()),
+        inventory: vec![],
+        cargo_capacity_m3: 20.0,
+        task: None,
+        speed_ticks_per_au: None,
+        modifiers: crate::modifiers::ModifierSet::default(),
+        hull_id: HullId("hull_general_purpose".to_string()),
+        fitted_modules: vec![],
+        propellant_kg: 0.0,
+        propellant_capacity_kg: 0.0,
+        crew: std::collections::BTreeMap::default(),
+        leaders: Vec::new(),
+        home_station: Some(test_station_id()),
+        name: String::new(),
+        class_id: None,
+        role: None,
+        wear: crate::WearState::default(),
+    }
+}
+
+/// Empty autopilot station with no modules at `test_body`.
+pub fn test_station(station_id: StationId) -> StationState {
+    StationState {
+        id: station_id,
+        position: test_position(),
+        core: FacilityCore {
+            inventory: vec![],
+            cargo_capacity_m3: 10_000.0,
+            power_available_per_tick: 100.0,
+            modules: vec![],
+            modifiers: crate::modifiers::ModifierSet::default(),
+            crew: std::collections::BTreeMap::default(),
+            thermal_links: Vec::new(),
+            power: crate::PowerState::default(),
+            cached_inventory_volume_m3: None,
+            module_type_index: crate::ModuleTypeIndex::default(),
+            module_id_index: std::collections::HashMap::new(),
+            power_budget_cache: crate::PowerBudgetCache::default(),
+            production_windows: std::collections::BTreeMap::new(),
+            life_support_failing: false,
+        },
+        leaders: Vec::new(),
+        frame_id: None,
+        owner: PrincipalId("principal_autopilot".to_string()),
+        expansions: crate::StationExpansions::default(),
+        power_policy: crate::PowerPolicy::default(),
+        history: std::collections::VecDeque::new(),
+    }
+}
+
 /// Standard game state: 1 ship, 1 station, 1 scan site at `test_body`.
+///
+/// Tests that need a different shape override fields with struct-update
+/// syntax (`GameState { ships: .., ..base_state(content) }`).
 pub fn base_state(content: &GameContent) -> GameState {
-    let ship_id = test_ship_id();
-    let station_id = test_station_id();
-    let owner = PrincipalId("principal_autopilot".to_string());
-
     GameState {
         meta: MetaState {
             tick: 0,
             seed: 42,
-            schema_version: 1,
+            schema_version: crate::CURRENT_SCHEMA_VERSION,
             content_version: content.content_version.clone(),
         },
         scan_sites: vec![ScanSite {
@@ -719,72 +955,18 @@ pub fn base_state(content: &GameContent) -> GameState {
             template_id: "tmpl_iron_rich".to_string(),
         }],
         asteroids: std::collections::BTreeMap::new(),
-        ships: [(
-            ship_id.clone(),
-            ShipState {
-                id: ship_id,
-                position: test_position(),
-                owner,
-                inventory: vec![],
-                cargo_capacity_m3: 20.0,
-                task: None,
-                speed_ticks_per_au: None,
-                modifiers: crate::modifiers::ModifierSet::default(),
-                hull_id: HullId("hull_general_purpose".to_string()),
-                fitted_modules: vec![],
-                propellant_kg: 0.0,
-                propellant_capacity_kg: 0.0,
-                crew: Default::default(),
-                leaders: Vec::new(),
-                home_station: Some(station_id.clone()),
-            },
-        )]
-        .into_iter()
-        .collect(),
-        stations: [(
-            station_id.clone(),
-            StationState {
-                id: station_id,
-                position: test_position(),
-                core: FacilityCore {
-                    inventory: vec![],
-                    cargo_capacity_m3: 10_000.0,
-                    power_available_per_tick: 100.0,
-                    modules: vec![],
-                    modifiers: crate::modifiers::ModifierSet::default(),
-                    crew: Default::default(),
-                    thermal_links: Vec::new(),
-                    power: crate::PowerState::default(),
-                    cached_inventory_volume_m3: None,
-                    module_type_index: crate::ModuleTypeIndex::default(),
-                    module_id_index: std::collections::HashMap::new(),
-                    power_budget_cache: crate::PowerBudgetCache::default(),
-                },
-                leaders: Vec::new(),
-                frame_id: None,
-            },
-        )]
-        .into_iter()
-        .collect(),
+        ships: [(test_ship_id(), test_ship())].into_iter().collect(),
+        stations: [(test_station_id(), test_station(test_station_id()))]
+            .into_iter()
+            .collect(),
         ground_facilities: std::collections::BTreeMap::new(),
         satellites: std::collections::BTreeMap::new(),
-        research: ResearchState {
-            unlocked: std::collections::HashSet::new(),
-            data_pool: AHashMap::default(),
-            evidence: AHashMap::default(),
-            action_counts: AHashMap::default(),
-        },
+        research: ResearchState::default(),
         balance: 0.0,
+        loan: crate::LoanState::default(),
         export_revenue_total: 0.0,
         export_count: 0,
-        counters: Counters {
-            next_event_id: 0,
-            next_command_id: 0,
-            next_asteroid_id: 0,
-            next_lot_id: 0,
-            next_module_instance_id: 0,
-            ..Default::default()
-        },
+        counters: Counters::default(),
         modifiers: crate::modifiers::ModifierSet::default(),
         events: crate::sim_events::SimEventState::default(),
         propellant_consumed_total: 0.0,
@@ -792,7 +974,20 @@ pub fn base_state(content: &GameContent) -> GameState {
         transfer_count: 0,
         progression: ProgressionState::default(),
         strategy_config: Default::default(),
+        import_orders: Vec::new(),
+        freight_orders: Vec::new(),
+        freight_routes: Vec::new(),
+        market: crate::MarketState::default(),
+        contracts: Vec::new(),
         body_cache: crate::build_body_cache(&content.solar_system.bodies),
+        jettisoned_slag_kg: std::collections::BTreeMap::new(),
+        solar_storms: std::collections::BTreeMap::new(),
+        claims: std::collections::BTreeMap::new(),
+        hotspots: crate::HotspotWindow::default(),
+        projects: Vec::new(),
+        station_builds: Vec::new(),
+        belts: std::collections::BTreeMap::new(),
+        pending_commands: Vec::new(),
     }
 }
 
@@ -114,9 +114,10 @@ fn test_assembler_stalls_on_capacity() {
         .core
         .cargo_capacity_m3 = 0.001;
 
+    // Checks run when the job would start (tick 1), output lands on tick 2.
     let mut rng = make_rng();
-    tick(&mut state, &[], &content, &mut rng, None);
-    let events = tick(&mut state, &[], &content, &mut rng, None);
+    let mut events = tick(&mut state, &[], &content, &mut rng, None);
+    events.extend(tick(&mut state, &[], &content, &mut rng, None));
 
     let station = &state.stations[&station_id];
     if let ModuleKindState::Assembler(asmb) = &station.core.modules[0].kind_state {
@@ -171,9 +172,12 @@ fn test_assembler_auto_disables_at_max_wear() {
         .wear
         .wear = 0.995;
 
+    // Critical wear halves efficiency, so the 2-tick job needs 4 ticks.
     let mut rng = make_rng();
-    tick(&mut state, &[], &content, &mut rng, None);
-    let events = tick(&mut state, &[], &content, &mut rng, None);
+    let mut events = Vec::new();
+    for _ in 0..4 {
+        events.extend(tick(&mut state, &[], &content, &mut rng, None));
+    }
 
     let station = &state.stations[&station_id];
     assert!(
@@ -298,9 +302,10 @@ fn test_assembler_stops_at_max_stock() {
             quality: 1.0,
         });
 
+    // Checks run when the job would start (tick 1), output lands on tick 2.
     let mut rng = make_rng();
-    tick(&mut state, &[], &content, &mut rng, None);
-    let events = tick(&mut state, &[], &content, &mut rng, None);
+    let mut events = tick(&mut state, &[], &content, &mut rng, None);
+    events.extend(tick(&mut state, &[], &content, &mut rng, None));
 
     assert!(
         !events
@@ -365,9 +370,10 @@ fn test_assembler_resumes_below_max_stock() {
         asmb.capped = true;
     }
 
+    // Checks run when the job would start (tick 1), output lands on tick 2.
     let mut rng = make_rng();
-    tick(&mut state, &[], &content, &mut rng, None);
-    let events = tick(&mut state, &[], &content, &mut rng, None);
+    let mut events = tick(&mut state, &[], &content, &mut rng, None);
+    events.extend(tick(&mut state, &[], &content, &mut rng, None));
 
     assert!(
         events
@@ -428,3 +434,142 @@ fn test_assembler_cap_override_takes_priority() {
         "assembler should NOT run when at override cap (2), even though content cap is 10"
     );
 }
+
+fn repair_kit_count(state: &GameState) -> u32 {
+    state.stations[&test_station_id()]
+        .core
+        .inventory
+        .iter()
+        .filter_map(|item| match item {
+            InventoryItem::Component {
+                component_id,
+                count,
+                ..
+            } if component_id.0 == "repair_kit" => Some(*count),
+            _ => None,
+        })
+        .sum()
+}
+
+fn assembler_job(state: &GameState) -> Option<AssemblerJob> {
+    match &state.stations[&test_station_id()].core.modules[0].kind_state {
+        ModuleKindState::Assembler(asmb) => asmb.job.clone(),
+        _ => panic!("expected assembler state"),
+    }
+}
+
+#[test]
+fn test_assembler_consumes_inputs_when_job_starts() {
+    let content = assembler_content();
+    let mut state = state_with_assembler(&content);
+    let mut rng = make_rng();
+
+    tick(&mut state, &[], &content, &mut rng, None);
+
+    let fe_kg: f32 = state.stations[&test_station_id()]
+        .core
+        .inventory
+        .iter()
+        .filter_map(|i| match i {
+            InventoryItem::Material { element, kg, .. } if element == "Fe" => Some(*kg),
+            _ => None,
+        })
+        .sum();
+    assert!(
+        (fe_kg - 400.0).abs() < 1.0,
+        "inputs should be consumed at job start"
+    );
+    assert_eq!(repair_kit_count(&state), 0, "output not ready yet");
+    let job = assembler_job(&state).expect("job should be in progress");
+    assert!((job.progress - 1.0).abs() < f32::EPSILON);
+}
+
+#[test]
+fn test_assembler_job_survives_disable_and_save_load() {
+    let content = assembler_content();
+    let mut state = state_with_assembler(&content);
+    let station_id = test_station_id();
+    let mut rng = make_rng();
+
+    tick(&mut state, &[], &content, &mut rng, None);
+    state.stations.get_mut(&station_id).unwrap().core.modules[0].enabled = false;
+    for _ in 0..3 {
+        tick(&mut state, &[], &content, &mut rng, None);
+    }
+    let job = assembler_job(&state).expect("job should persist while disabled");
+    assert!((job.progress - 1.0).abs() < f32::EPSILON);
+
+    let json = serde_json::to_string(&state).unwrap();
+    let mut state: GameState = serde_json::from_str(&json).unwrap();
+    state.stations.get_mut(&station_id).unwrap().core.modules[0].enabled = true;
+    tick(&mut state, &[], &content, &mut rng, None);
+
+    assert_eq!(repair_kit_count(&state), 1, "resumed job should finish");
+    assert!(assembler_job(&state).is_none());
+}
+
+fn royalty_content() -> GameContent {
+    let mut content = assembler_content();
+    content.pricing.items.insert(
+        "repair_kit".to_string(),
+        crate::PricingEntry {
+            base_price_per_unit: 8000.0,
+            importable: true,
+            exportable: true,
+            category: "component".to_string(),
+            royalty_per_unit: 250.0,
+            elasticity: 0.0,
+        },
+    );
+    content
+}
+
+#[test]
+fn test_assembler_pays_royalty_per_unit() {
+    let content = royalty_content();
+    let mut state = state_with_assembler(&content);
+    state.balance = 1_000.0;
+    let mut rng = make_rng();
+
+    tick(&mut state, &[], &content, &mut rng, None);
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+
+    assert_eq!(repair_kit_count(&state), 1);
+    assert!((state.balance - 750.0).abs() < 1e-9);
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::RoyaltyPaid { item_id, units: 1, .. } if item_id == "repair_kit"
+    )));
+}
+
+#[test]
+fn test_royalty_waived_by_unlocked_tech() {
+    let mut content = royalty_content();
+    content.techs.push(TechDef {
+        id: TechId("tech_licence_buyout".to_string()),
+        name: "Licence Buyout".to_string(),
+        tier: 1,
+        prereqs: vec![],
+        domain_requirements: HashMap::new(),
+        accepted_data: vec![],
+        effects: vec![TechEffect::WaiveRoyalty {
+            item_id: "repair_kit".to_string(),
+        }],
+    });
+    let mut state = state_with_assembler(&content);
+    state.balance = 1_000.0;
+    state
+        .research
+        .unlocked
+        .insert(TechId("tech_licence_buyout".to_string()));
+    let mut rng = make_rng();
+
+    tick(&mut state, &[], &content, &mut rng, None);
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+
+    assert_eq!(repair_kit_count(&state), 1);
+    assert!((state.balance - 1_000.0).abs() < 1e-9);
+    assert!(!events
+        .iter()
+        .any(|e| matches!(e.event, Event::RoyaltyPaid { .. })));
+}
@@ -0,0 +1,182 @@
+//! `Command::TransferCargo` — partial ship/station and ship/ship cargo moves.
+
+use super::*;
+
+fn ore_lot(lot_id: &str, kg: f32) -> InventoryItem {
+    InventoryItem::Ore {
+        lot_id: LotId(lot_id.to_string()),
+        asteroid_id: AsteroidId("asteroid_test".to_string()),
+        kg,
+        composition: HashMap::from([("Fe".to_string(), 0.7_f32), ("Si".to_string(), 0.3_f32)]),
+    }
+}
+
+fn transfer_command(
+    state: &GameState,
+    counterparty: CargoHolder,
+    direction: CargoDirection,
+    items: Vec<CargoSpec>,
+) -> CommandEnvelope {
+    let ship_id = test_ship_id();
+    CommandEnvelope {
+        id: CommandId(0),
+        issued_by: state.ships[&ship_id].owner.clone(),
+        issued_tick: state.meta.tick,
+        execute_at_tick: state.meta.tick,
+        command: Command::TransferCargo {
+            ship_id,
+            counterparty,
+            direction,
+            items,
+        },
+    }
+}
+
+fn ore_kg(inventory: &[InventoryItem]) -> f32 {
+    inventory
+        .iter()
+        .filter_map(|item| match item {
+            InventoryItem::Ore { kg, .. } => Some(*kg),
+            _ => None,
+        })
+        .sum()
+}
+
+#[test]
+fn unload_splits_ore_lot_and_keeps_the_rest_aboard() {
+    let content = test_content();
+    let mut state = test_state(&content);
+    let mut rng = make_rng();
+    let ship_id = test_ship_id();
+    state
+        .ships
+        .get_mut(&ship_id)
+        .unwrap()
+        .inventory
+        .push(ore_lot("lot_test_0001", 100.0));
+
+    let cmd = transfer_command(
+        &state,
+        CargoHolder::Station(test_station_id()),
+        CargoDirection::Unload,
+        vec![CargoSpec::Ore {
+            lot_id: LotId("lot_test_0001".to_string()),
+            kg: Some(40.0),
+        }],
+    );
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+
+    let ship = &state.ships[&ship_id];
+    assert!((ore_kg(&ship.inventory) - 60.0).abs() < 1e-3);
+    assert!(
+        matches!(&ship.inventory[0], InventoryItem::Ore { lot_id, .. } if lot_id.0 == "lot_test_0001")
+    );
+    let station = &state.stations[&test_station_id()];
+    assert!((ore_kg(&station.core.inventory) - 40.0).abs() < 1e-3);
+    assert!(events
+        .iter()
+        .any(|e| matches!(&e.event, Event::CargoTransferred { items, .. } if items.len() == 1)));
+    assert!(matches!(
+        ship.task.as_ref().map(|t| &t.kind),
+        Some(TaskKind::Idle)
+    ));
+}
+
+#[test]
+fn load_takes_material_from_station() {
+    let content = test_content();
+    let mut state = test_state(&content);
+    let mut rng = make_rng();
+    state
+        .stations
+        .get_mut(&test_station_id())
+        .unwrap()
+        .core
+        .inventory
+        .push(InventoryItem::Material {
+            element: "Fe".to_string(),
+            kg: 500.0,
+            quality: 0.8,
+            thermal: None,
+        });
+
+    let cmd = transfer_command(
+        &state,
+        CargoHolder::Station(test_station_id()),
+        CargoDirection::Load,
+        vec![CargoSpec::Item(TradeItemSpec::Material {
+            element: "Fe".to_string(),
+            kg: 200.0,
+        })],
+    );
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+    tick(&mut state, &[], &content, &mut rng, None);
+
+    let loaded: f32 = state.ships[&test_ship_id()]
+        .inventory
+        .iter()
+        .map(InventoryItem::mass_kg)
+        .sum();
+    assert!((loaded - 200.0).abs() < 1e-3);
+}
+
+#[test]
+fn unload_to_co_located_ship() {
+    let content = test_content();
+    let mut state = test_state(&content);
+    let mut rng = make_rng();
+    let ship_id = test_ship_id();
+    let other_id = ShipId("ship_0002".to_string());
+    let mut other = state.ships[&ship_id].clone();
+    other.id = other_id.clone();
+    other.inventory.clear();
+    state.ships.insert(other_id.clone(), other);
+    state
+        .ships
+        .get_mut(&ship_id)
+        .unwrap()
+        .inventory
+        .push(ore_lot("lot_test_0001", 100.0));
+
+    let cmd = transfer_command(
+        &state,
+        CargoHolder::Ship(other_id.clone()),
+        CargoDirection::Unload,
+        vec![CargoSpec::Ore {
+            lot_id: LotId("lot_test_0001".to_string()),
+            kg: None,
+        }],
+    );
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+    tick(&mut state, &[], &content, &mut rng, None);
+
+    assert!(state.ships[&ship_id].inventory.is_empty());
+    assert!((ore_kg(&state.ships[&other_id].inventory) - 100.0).abs() < 1e-3);
+}
+
+#[test]
+fn transfer_rejected_out_of_docking_range() {
+    let content = test_content();
+    let mut state = test_state(&content);
+    let mut rng = make_rng();
+    let ship_id = test_ship_id();
+    let ship = state.ships.get_mut(&ship_id).unwrap();
+    ship.inventory.push(ore_lot("lot_test_0001", 100.0));
+    ship.position.radius_au_um = RadiusAuMicro(1_000_000);
+
+    let cmd = transfer_command(
+        &state,
+        CargoHolder::Station(test_station_id()),
+        CargoDirection::Unload,
+        vec![CargoSpec::Ore {
+            lot_id: LotId("lot_test_0001".to_string()),
+            kg: None,
+        }],
+    );
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+    tick(&mut state, &[], &content, &mut rng, None);
+
+    assert!((ore_kg(&state.ships[&ship_id].inventory) - 100.0).abs() < 1e-3);
+    assert!(ore_kg(&state.stations[&test_station_id()].core.inventory) < 1e-3);
+}
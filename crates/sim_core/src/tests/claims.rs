@@ -0,0 +1,181 @@
+use super::*;
+
+fn autopilot() -> PrincipalId {
+    PrincipalId("principal_autopilot".to_string())
+}
+
+fn rival() -> PrincipalId {
+    PrincipalId("principal_rival".to_string())
+}
+
+fn claim_command(state: &GameState, issued_by: PrincipalId, command: Command) -> CommandEnvelope {
+    CommandEnvelope {
+        id: CommandId(0),
+        issued_by,
+        issued_tick: state.meta.tick,
+        execute_at_tick: state.meta.tick,
+        command,
+    }
+}
+
+fn claim_body(state: &GameState, issued_by: PrincipalId, body: &str) -> CommandEnvelope {
+    claim_command(
+        state,
+        issued_by,
+        Command::ClaimBody {
+            body_id: BodyId(body.to_string()),
+        },
+    )
+}
+
+/// Adds `far_zone`, a body where nobody has a station, and sets claims to
+/// cost 100 credits for 60 minutes.
+fn claims_setup() -> (GameContent, GameState) {
+    let mut content = test_content();
+    let mut body = content.solar_system.bodies[0].clone();
+    body.id = BodyId("far_zone".to_string());
+    body.parent = Some(BodyId("test_body".to_string()));
+    content.solar_system.bodies.push(body);
+    content.constants.claim_cost = 100.0;
+    content.constants.claim_duration_minutes = 60;
+    content.constants.derive_tick_values();
+    let state = test_state(&content);
+    (content, state)
+}
+
+#[test]
+fn station_backed_claim_is_free_and_does_not_expire() {
+    let (content, mut state) = claims_setup();
+    let mut rng = make_rng();
+
+    let cmd = claim_body(&state, autopilot(), "test_body");
+    let events = tick(&mut state, &[cmd], &content, &mut rng, None);
+
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::BodyClaimed {
+            basis: ClaimBasis::Station,
+            expires_tick: None,
+            contested_from: None,
+            ..
+        }
+    )));
+    assert!(state.balance.abs() < f64::EPSILON);
+    assert_eq!(
+        state.claims[&BodyId("test_body".to_string())].owner,
+        autopilot()
+    );
+}
+
+#[test]
+fn credit_claim_is_paid_for_and_expires() {
+    let (content, mut state) = claims_setup();
+    let mut rng = make_rng();
+
+    let cmd = claim_body(&state, rival(), "far_zone");
+    let events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::ClaimRejected { reason, .. } if reason == "insufficient credits"
+    )));
+    assert!(state.claims.is_empty());
+
+    state.balance = 250.0;
+    let cmd = claim_body(&state, rival(), "far_zone");
+    let claimed_tick = state.meta.tick;
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+    let claim = &state.claims[&BodyId("far_zone".to_string())];
+    assert_eq!(claim.basis, ClaimBasis::Credits);
+    assert_eq!(
+        claim.expires_tick,
+        Some(claimed_tick + content.constants.claim_duration_ticks)
+    );
+    assert!((state.balance - 150.0).abs() < 1e-9);
+
+    let mut expired = false;
+    for _ in 0..content.constants.claim_duration_ticks {
+        let events = tick(&mut state, &[], &content, &mut rng, None);
+        expired |= events
+            .iter()
+            .any(|e| matches!(e.event, Event::ClaimExpired { .. }));
+    }
+    assert!(expired);
+    assert!(state.claims.is_empty());
+}
+
+#[test]
+fn mining_on_a_body_claimed_by_another_principal_is_refused() {
+    let content = test_content();
+    let (mut state, asteroid_id) = state_with_asteroid(&content);
+    let body_id = state.asteroids[&asteroid_id].position.parent_body.clone();
+    state.claims.insert(
+        body_id.clone(),
+        BodyClaim {
+            owner: rival(),
+            claimed_tick: 0,
+            expires_tick: None,
+            basis: ClaimBasis::Credits,
+        },
+    );
+    let mut rng = make_rng();
+
+    let cmd = mine_command(&state, &asteroid_id, &content);
+    let events = tick(&mut state, &[cmd], &content, &mut rng, None);
+
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::MiningRightsDenied { body_id: b, holder, .. } if *b == body_id && *holder == rival()
+    )));
+    assert!(!matches!(
+        state.ships[&test_ship_id()].task.as_ref().map(|t| &t.kind),
+        Some(TaskKind::Mine { .. })
+    ));
+}
+
+#[test]
+fn station_owner_can_take_over_a_credit_claim_but_not_the_reverse() {
+    let (content, mut state) = claims_setup();
+    state.balance = 1_000.0;
+    let mut rng = make_rng();
+    let cmd = claim_body(&state, rival(), "test_body");
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+
+    let cmd = claim_body(&state, autopilot(), "test_body");
+    let events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::BodyClaimed { contested_from: Some(previous), .. } if *previous == rival()
+    )));
+
+    let cmd = claim_body(&state, rival(), "test_body");
+    let events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    assert!(events
+        .iter()
+        .any(|e| matches!(e.event, Event::ClaimRejected { .. })));
+    assert_eq!(
+        state.claims[&BodyId("test_body".to_string())].owner,
+        autopilot()
+    );
+}
+
+#[test]
+fn only_the_holder_can_release_a_claim() {
+    let (content, mut state) = claims_setup();
+    let mut rng = make_rng();
+    let cmd = claim_body(&state, autopilot(), "test_body");
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+    let release = Command::ReleaseClaim {
+        body_id: BodyId("test_body".to_string()),
+    };
+
+    let cmd = claim_command(&state, rival(), release.clone());
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+    assert_eq!(state.claims.len(), 1);
+
+    let cmd = claim_command(&state, autopilot(), release);
+    let events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    assert!(events
+        .iter()
+        .any(|e| matches!(e.event, Event::ClaimReleased { .. })));
+    assert!(state.claims.is_empty());
+}
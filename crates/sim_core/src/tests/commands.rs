@@ -53,13 +53,93 @@ fn test_wrong_owner_command_is_dropped() {
         },
     };
 
-    tick(&mut state, &[bad_command], &content, &mut rng, None);
+    let events = tick(&mut state, &[bad_command], &content, &mut rng, None);
 
     let ship = &state.ships[&ship_id];
     assert!(
         ship.task.is_none(),
-        "command from wrong owner should be silently dropped"
+        "command from wrong owner should be dropped"
     );
+    assert!(
+        events.iter().any(|e| matches!(
+            &e.event,
+            Event::CommandRejected { command_id: CommandId(0), reason } if reason.contains("not owned")
+        )),
+        "dropped command should be reported with its reason"
+    );
+}
+
+#[test]
+fn test_commands_for_unknown_targets_are_rejected() {
+    let content = test_content();
+    let mut state = test_state(&content);
+    let mut rng = make_rng();
+
+    let mut missing_ship = survey_command(&state);
+    missing_ship.id = CommandId(3);
+    if let Command::AssignShipTask { ship_id, .. } = &mut missing_ship.command {
+        *ship_id = ShipId("ship_missing".to_string());
+    }
+    let mut missing_station = survey_command(&state);
+    missing_station.id = CommandId(4);
+    missing_station.command = Command::JettisonSlag {
+        station_id: StationId("station_missing".to_string()),
+    };
+    let events = tick(
+        &mut state,
+        &[missing_ship, missing_station],
+        &content,
+        &mut rng,
+        None,
+    );
+
+    let rejected: Vec<(CommandId, &str)> = events
+        .iter()
+        .filter_map(|e| match &e.event {
+            Event::CommandRejected { command_id, reason } => Some((*command_id, reason.as_str())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        rejected,
+        vec![
+            (CommandId(3), "unknown ship 'ship_missing'"),
+            (CommandId(4), "unknown station 'station_missing'"),
+        ]
+    );
+}
+
+#[test]
+fn test_stale_command_is_rejected() {
+    let content = test_content();
+    let mut state = test_state(&content);
+    let mut rng = make_rng();
+    state.meta.tick = 5;
+
+    let mut late = survey_command(&state);
+    late.id = CommandId(7);
+    late.execute_at_tick = 4;
+    let events = tick(&mut state, &[late], &content, &mut rng, None);
+
+    assert!(state.ships[&ShipId("ship_0001".to_string())].task.is_none());
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::CommandRejected { command_id: CommandId(7), reason }
+            if reason == "execute_at_tick 4 is before the current tick 5"
+    )));
+}
+
+#[test]
+fn test_accepted_command_emits_no_rejection() {
+    let content = test_content();
+    let mut state = test_state(&content);
+    let mut rng = make_rng();
+
+    let cmd = survey_command(&state);
+    let events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    assert!(!events
+        .iter()
+        .any(|e| matches!(e.event, Event::CommandRejected { .. })));
 }
 
 #[test]
@@ -91,6 +171,100 @@ fn test_future_command_not_applied_early() {
     );
 }
 
+fn scheduled_survey(state: &GameState, id: u64, execute_at_tick: u64) -> CommandEnvelope {
+    let ship_id = ShipId("ship_0001".to_string());
+    CommandEnvelope {
+        id: CommandId(id),
+        issued_by: state.ships[&ship_id].owner.clone(),
+        issued_tick: state.meta.tick,
+        execute_at_tick,
+        command: Command::AssignShipTask {
+            ship_id,
+            task_kind: TaskKind::Survey {
+                site: SiteId("site_0001".to_string()),
+            },
+        },
+    }
+}
+
+#[test]
+fn test_future_command_survives_save_and_runs_at_its_tick() {
+    let content = test_content();
+    let mut state = test_state(&content);
+    let mut rng = make_rng();
+    let ship_id = ShipId("ship_0001".to_string());
+
+    let survey = scheduled_survey(&state, 0, 3);
+    tick(&mut state, &[survey], &content, &mut rng, None);
+    assert_eq!(state.pending_commands.len(), 1);
+
+    let json = serde_json::to_string(&state).unwrap();
+    let mut state: GameState = serde_json::from_str(&json).unwrap();
+    while state.meta.tick < 3 {
+        tick(&mut state, &[], &content, &mut rng, None);
+    }
+    assert!(
+        state.ships[&ship_id].task.is_none(),
+        "not due before tick 3"
+    );
+
+    tick(&mut state, &[], &content, &mut rng, None);
+    assert!(state.pending_commands.is_empty());
+    assert!(
+        matches!(&state.ships[&ship_id].task, Some(task) if matches!(task.kind, TaskKind::Survey { .. })),
+        "scheduled survey should run at tick 3"
+    );
+}
+
+#[test]
+fn test_cancel_command_drops_only_the_issuers_scheduled_command() {
+    let content = test_content();
+    let mut state = test_state(&content);
+    let mut rng = make_rng();
+    let ship_id = ShipId("ship_0001".to_string());
+    let owner = state.ships[&ship_id].owner.clone();
+    let cancel = |issued_by: &PrincipalId, id: u64| CommandEnvelope {
+        id: CommandId(id),
+        issued_by: issued_by.clone(),
+        issued_tick: 0,
+        execute_at_tick: 0,
+        command: Command::CancelCommand {
+            command_id: CommandId(7),
+        },
+    };
+
+    let intruder = PrincipalId("principal_intruder".to_string());
+    let survey = scheduled_survey(&state, 7, 5);
+    let events = tick(
+        &mut state,
+        &[survey, cancel(&intruder, 8)],
+        &content,
+        &mut rng,
+        None,
+    );
+    assert_eq!(state.pending_commands.len(), 1, "intruder cannot cancel");
+    assert!(!events
+        .iter()
+        .any(|e| matches!(e.event, Event::CommandCancelled { .. })));
+
+    let mut cancel_now = cancel(&owner, 9);
+    cancel_now.execute_at_tick = state.meta.tick;
+    let events = tick(&mut state, &[cancel_now], &content, &mut rng, None);
+    assert!(state.pending_commands.is_empty());
+    assert!(events.iter().any(|e| matches!(
+        e.event,
+        Event::CommandCancelled {
+            command_id: CommandId(7),
+            ..
+        }
+    )));
+
+    while state.meta.tick <= 5 {
+        tick(&mut state, &[], &content, &mut rng, None);
+    }
+    assert!(state.ships[&ship_id].task.is_none());
+}
+
 #[test]
 fn test_install_module_initializes_thermal_state_for_thermal_modules() {
     use crate::test_fixtures::thermal_content;
@@ -107,6 +281,7 @@ fn test_install_module_initializes_thermal_state_for_thermal_modules() {
     station.core.inventory.push(InventoryItem::Module {
         item_id: module_item_id.clone(),
         module_def_id: "module_basic_smelter".to_string(),
+        wear: 0.0,
     });
     station.invalidate_volume_cache();
 
@@ -173,6 +348,7 @@ fn test_install_module_no_thermal_state_for_non_thermal_modules() {
     station.core.inventory.push(InventoryItem::Module {
         item_id: module_item_id.clone(),
         module_def_id: "module_basic_iron_refinery".to_string(),
+        wear: 0.0,
     });
     station.invalidate_volume_cache();
 
@@ -368,10 +544,12 @@ fn framed_install_setup() -> (GameContent, GameState) {
                 crate::SlotDef {
                     slot_type: crate::SlotType("industrial".to_string()),
                     label: "I1".to_string(),
+                    adjacent: Vec::new(),
                 },
                 crate::SlotDef {
                     slot_type: crate::SlotType("research".to_string()),
                     label: "R1".to_string(),
+                    adjacent: Vec::new(),
                 },
             ],
             bonuses: vec![],
@@ -386,10 +564,12 @@ fn framed_install_setup() -> (GameContent, GameState) {
     station.core.inventory.push(InventoryItem::Module {
         item_id: crate::ModuleItemId("inv_industrial".to_string()),
         module_def_id: "module_sf05_industrial".to_string(),
+        wear: 0.0,
     });
     station.core.inventory.push(InventoryItem::Module {
         item_id: crate::ModuleItemId("inv_research".to_string()),
         module_def_id: "module_sf05_research".to_string(),
+        wear: 0.0,
     });
 
     (content, state)
@@ -510,6 +690,7 @@ fn install_module_occupied_slot_rejected() {
         station.core.inventory.push(InventoryItem::Module {
             item_id: crate::ModuleItemId("inv_industrial_2".to_string()),
             module_def_id: "module_sf05_industrial".to_string(),
+            wear: 0.0,
         });
     }
 
@@ -553,6 +734,118 @@ fn install_module_frameless_station_keeps_legacy_behavior() {
     );
 }
 
+#[test]
+fn install_module_uses_project_expansion_slot() {
+    let (content, mut state) = framed_install_setup();
+    let station_id = test_station_id();
+    state
+        .stations
+        .get_mut(&station_id)
+        .unwrap()
+        .expansions
+        .slots
+        .push(crate::SlotDef {
+            slot_type: crate::SlotType("industrial".to_string()),
+            label: "Annex".to_string(),
+            adjacent: Vec::new(),
+        });
+    let mut rng = make_rng();
+
+    let cmd = install_command(&state, &station_id, "inv_industrial", Some(2));
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+
+    let station = &state.stations[&station_id];
+    let module = station
+        .core
+        .modules
+        .iter()
+        .find(|m| m.def_id == "module_sf05_industrial")
+        .expect("module should install into the expansion slot");
+    assert_eq!(module.slot_index, Some(2));
+}
+
+// --------------------------------------------------------------------
+// Station layout: slot adjacency rules
+// --------------------------------------------------------------------
+
+/// `framed_install_setup` with slots 0 and 1 sharing a wall and a single
+/// equipment↔equipment adjacency rule (both SF-05 defs are Equipment).
+fn adjacent_install_setup(forbidden: bool) -> (GameContent, GameState) {
+    let (mut content, state) = framed_install_setup();
+    let frame = content
+        .frames
+        .get_mut(&crate::FrameId("frame_test_install".to_string()))
+        .unwrap();
+    frame.slots[0].adjacent = vec![1];
+    content.adjacency_rules.push(crate::AdjacencyRuleDef {
+        module_type: "equipment".to_string(),
+        neighbor_type: "equipment".to_string(),
+        bonuses: vec![crate::AdjacencyBonusDef {
+            stat: crate::modifiers::StatId::ResearchSpeed,
+            op: crate::modifiers::ModifierOp::PctAdditive,
+            value: 0.1,
+        }],
+        forbidden,
+    });
+    (content, state)
+}
+
+#[test]
+fn adjacent_modules_receive_layout_bonus() {
+    let (content, mut state) = adjacent_install_setup(false);
+    let station_id = test_station_id();
+    let mut rng = make_rng();
+
+    let first = install_command(&state, &station_id, "inv_industrial", Some(0));
+    tick(&mut state, &[first], &content, &mut rng, None);
+    let station = &state.stations[&station_id];
+    assert!(
+        crate::station::layout::adjacency_modifiers(station, 0, &content).is_empty(),
+        "no bonus while the neighbouring slot is empty"
+    );
+
+    let second = install_command(&state, &station_id, "inv_research", Some(1));
+    tick(&mut state, &[second], &content, &mut rng, None);
+    let station = &state.stations[&station_id];
+    for module_idx in 0..2 {
+        let mods = crate::station::layout::adjacency_modifiers(station, module_idx, &content);
+        assert_eq!(mods.len(), 1, "module {module_idx} should get one bonus");
+        assert_eq!(
+            mods[0].source,
+            crate::modifiers::ModifierSource::Adjacency("equipment".to_string())
+        );
+    }
+}
+
+#[test]
+fn install_module_rejects_forbidden_adjacency() {
+    let (content, mut state) = adjacent_install_setup(true);
+    let station_id = test_station_id();
+    let mut rng = make_rng();
+
+    let first = install_command(&state, &station_id, "inv_industrial", Some(0));
+    tick(&mut state, &[first], &content, &mut rng, None);
+
+    let second = install_command(&state, &station_id, "inv_research", Some(1));
+    let events = tick(&mut state, &[second], &content, &mut rng, None);
+
+    let station = &state.stations[&station_id];
+    assert!(
+        station
+            .core
+            .modules
+            .iter()
+            .all(|m| m.def_id != "module_sf05_research"),
+        "forbidden neighbour should not be installed"
+    );
+    assert!(
+        events
+            .iter()
+            .any(|e| matches!(&e.event, Event::ModuleNoCompatibleSlot { .. })),
+        "forbidden placement should emit ModuleNoCompatibleSlot"
+    );
+}
+
 // --------------------------------------------------------------------
 // VIO-592: Command::DeployStation + TaskKind::ConstructStation
 // --------------------------------------------------------------------
@@ -576,6 +869,7 @@ fn deploy_station_setup() -> (crate::GameContent, crate::GameState, crate::ShipI
             slots: vec![crate::SlotDef {
                 slot_type: SlotType("utility".to_string()),
                 label: "U1".to_string(),
+                adjacent: Vec::new(),
             }],
             bonuses: vec![],
             required_tech: None,
@@ -845,3 +1139,269 @@ fn deploy_station_seeds_new_station_inventory_from_kit_def() {
         .expect("seed repair_kit components should be present");
     assert_eq!(repair_kits, 5, "expected 5 seed repair_kits");
 }
+
+// --------------------------------------------------------------------
+// Command::BuildStation — station-founded stations
+// --------------------------------------------------------------------
+
+/// `deploy_station_setup` with the kit moved from the ship into the
+/// starting station's inventory.
+fn build_station_setup() -> (crate::GameContent, crate::GameState) {
+    let (content, mut state, ship_id) = deploy_station_setup();
+    state.ships.get_mut(&ship_id).unwrap().inventory.clear();
+    let station = state.stations.get_mut(&test_station_id()).unwrap();
+    station.core.inventory.push(InventoryItem::Component {
+        component_id: crate::ComponentId("test_outpost_kit".to_string()),
+        count: 1,
+        quality: 1.0,
+    });
+    (content, state)
+}
+
+fn build_station_command(state: &crate::GameState, kit: &str) -> CommandEnvelope {
+    CommandEnvelope {
+        id: CommandId(0),
+        issued_by: PrincipalId("principal_autopilot".to_string()),
+        issued_tick: state.meta.tick,
+        execute_at_tick: state.meta.tick,
+        command: Command::BuildStation {
+            station_id: test_station_id(),
+            kit_component_id: crate::ComponentId(kit.to_string()),
+            position: state.stations[&test_station_id()].position.clone(),
+        },
+    }
+}
+
+#[test]
+fn build_station_founds_station_from_station_inventory() {
+    let (content, mut state) = build_station_setup();
+    let mut rng = make_rng();
+
+    let cmd = build_station_command(&state, "test_outpost_kit");
+    let mut events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    assert!(
+        !state.stations[&test_station_id()]
+            .core
+            .inventory
+            .iter()
+            .any(|i| matches!(i, InventoryItem::Component { component_id, .. } if component_id.0 == "test_outpost_kit")),
+        "kit should be consumed from station inventory"
+    );
+    assert_eq!(state.station_builds.len(), 1);
+    // Co-located, so only the 48-tick assembly floor applies.
+    assert_eq!(state.station_builds[0].complete_tick, 48);
+
+    for _ in 0..48 {
+        events.extend(tick(&mut state, &[], &content, &mut rng, None));
+    }
+
+    assert!(state.station_builds.is_empty());
+    let deployed = state
+        .stations
+        .values()
+        .find(|s| s.frame_id.as_ref() == Some(&crate::FrameId("frame_test_outpost".to_string())))
+        .expect("station-built outpost should exist");
+    assert_eq!(deployed.owner, state.stations[&test_station_id()].owner);
+    assert!(
+        !deployed.core.inventory.is_empty(),
+        "seeded from the kit def"
+    );
+    assert!(events
+        .iter()
+        .any(|e| matches!(&e.event, Event::StationDeployed { ship_id: None, .. })));
+}
+
+#[test]
+fn build_station_rejects_kit_not_in_stock() {
+    let (content, mut state) = build_station_setup();
+    state
+        .stations
+        .get_mut(&test_station_id())
+        .unwrap()
+        .core
+        .inventory
+        .clear();
+    let mut rng = make_rng();
+
+    let cmd = build_station_command(&state, "test_outpost_kit");
+    let events = tick(&mut state, &[cmd], &content, &mut rng, None);
+
+    assert!(state.station_builds.is_empty());
+    assert!(!events
+        .iter()
+        .any(|e| matches!(&e.event, Event::StationBuildStarted { .. })));
+}
+
+fn worn_module_setup() -> (GameContent, GameState) {
+    let mut content = test_content();
+    content.module_defs.insert(
+        "module_worn".to_string(),
+        ModuleDefBuilder::new("module_worn")
+            .behavior(crate::ModuleBehaviorDef::Equipment)
+            .mass(1000.0)
+            .build(),
+    );
+    let mut state = test_state(&content);
+    let station = state.stations.get_mut(&test_station_id()).unwrap();
+    station.core.inventory.push(InventoryItem::Module {
+        item_id: crate::ModuleItemId("inv_worn".to_string()),
+        module_def_id: "module_worn".to_string(),
+        wear: 0.4,
+    });
+    (content, state)
+}
+
+fn module_command(state: &GameState, command: Command) -> CommandEnvelope {
+    CommandEnvelope {
+        id: CommandId(0),
+        issued_by: PrincipalId("principal_autopilot".to_string()),
+        issued_tick: state.meta.tick,
+        execute_at_tick: state.meta.tick,
+        command,
+    }
+}
+
+#[test]
+fn install_and_uninstall_preserve_module_wear() {
+    let (content, mut state) = worn_module_setup();
+    let mut rng = make_rng();
+
+    let cmd = install_command(&state, &test_station_id(), "inv_worn", None);
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+    let module = &state.stations[&test_station_id()].core.modules[0];
+    assert!((module.wear.wear - 0.4).abs() < 1e-6);
+
+    let cmd = module_command(
+        &state,
+        Command::UninstallModule {
+            facility_id: test_station_id().into(),
+            module_id: module.id.clone(),
+        },
+    );
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+
+    let station = &state.stations[&test_station_id()];
+    assert!(station.core.modules.is_empty());
+    let wear = station.core.inventory.iter().find_map(|item| match item {
+        InventoryItem::Module { wear, .. } => Some(*wear),
+        _ => None,
+    });
+    assert!((wear.expect("module back in inventory") - 0.4).abs() < 1e-6);
+}
+
+#[test]
+fn scrap_module_recovers_mass_scaled_by_wear() {
+    let (content, mut state) = worn_module_setup();
+    let mut rng = make_rng();
+
+    let cmd = install_command(&state, &test_station_id(), "inv_worn", None);
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+    let module_id = state.stations[&test_station_id()].core.modules[0]
+        .id
+        .clone();
+
+    let cmd = module_command(
+        &state,
+        Command::ScrapModule {
+            facility_id: test_station_id().into(),
+            module_id,
+        },
+    );
+    let events = tick(&mut state, &[cmd], &content, &mut rng, None);
+
+    let station = &state.stations[&test_station_id()];
+    assert!(station.core.modules.is_empty());
+    assert!(!station
+        .core
+        .inventory
+        .iter()
+        .any(|item| matches!(item, InventoryItem::Module { .. })));
+    // 1000 kg × 0.5 recovery × (1 − 0.4 wear).
+    let fe_kg: f32 = station
+        .core
+        .inventory
+        .iter()
+        .filter_map(|item| match item {
+            InventoryItem::Material { element, kg, .. } if element == "Fe" => Some(*kg),
+            _ => None,
+        })
+        .sum();
+    assert!((fe_kg - 300.0).abs() < 1e-3, "got {fe_kg}");
+    assert!(events.iter().any(
+        |e| matches!(&e.event, Event::ModuleScrapped { kg, .. } if (*kg - 300.0).abs() < 1e-3)
+    ));
+}
+
+#[test]
+fn upgrade_module_consumes_cost_and_carries_over_wear() {
+    let (mut content, mut state) = worn_module_setup();
+    content.module_defs.insert(
+        "module_worn".to_string(),
+        ModuleDefBuilder::new("module_worn")
+            .behavior(crate::ModuleBehaviorDef::Equipment)
+            .upgrade_to(crate::ModuleUpgradeDef {
+                module_def_id: "module_worn_mk2".to_string(),
+                materials: [("Fe".to_string(), 100.0)].into(),
+                components: std::collections::BTreeMap::new(),
+            })
+            .build(),
+    );
+    content.module_defs.insert(
+        "module_worn_mk2".to_string(),
+        ModuleDefBuilder::new("module_worn_mk2")
+            .behavior(crate::ModuleBehaviorDef::Equipment)
+            .build(),
+    );
+    let mut rng = make_rng();
+
+    let cmd = install_command(&state, &test_station_id(), "inv_worn", None);
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+    let module_id = state.stations[&test_station_id()].core.modules[0]
+        .id
+        .clone();
+    let upgrade = |state: &GameState| {
+        module_command(
+            state,
+            Command::UpgradeModule {
+                facility_id: test_station_id().into(),
+                module_id: module_id.clone(),
+            },
+        )
+    };
+
+    // No iron in stock: rejected.
+    let cmd = upgrade(&state);
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+    assert_eq!(
+        state.stations[&test_station_id()].core.modules[0].def_id,
+        "module_worn"
+    );
+
+    state
+        .stations
+        .get_mut(&test_station_id())
+        .unwrap()
+        .core
+        .inventory
+        .push(InventoryItem::Material {
+            element: "Fe".to_string(),
+            kg: 150.0,
+            quality: 1.0,
+            thermal: None,
+        });
+    let cmd = upgrade(&state);
+    let events = tick(&mut state, &[cmd], &content, &mut rng, None);
+
+    let station = &state.stations[&test_station_id()];
+    let module = &station.core.modules[0];
+    assert_eq!(module.id, module_id);
+    assert_eq!(module.def_id, "module_worn_mk2");
+    // 0.4 wear × 0.5 carryover.
+    assert!((module.wear.wear - 0.2).abs() < 1e-6);
+    assert!(station.core.inventory.iter().any(
+        |item| matches!(item, InventoryItem::Material { element, kg, .. } if element == "Fe" && (*kg - 50.0).abs() < 1e-3)
+    ));
+    assert!(events
+        .iter()
+        .any(|e| matches!(&e.event, Event::ModuleUpgraded { to_def_id, .. } if to_def_id == "module_worn_mk2")));
+}
@@ -0,0 +1,213 @@
+//! Contracts: offers, `AcceptContract`/`AbandonContract`, completion and
+//! deadlines.
+
+use super::*;
+
+fn contract_command(state: &GameState, command: Command) -> CommandEnvelope {
+    CommandEnvelope {
+        id: CommandId(0),
+        issued_by: state.ships[&test_ship_id()].owner.clone(),
+        issued_tick: state.meta.tick,
+        execute_at_tick: state.meta.tick,
+        command,
+    }
+}
+
+fn accept(state: &GameState, id: u64) -> CommandEnvelope {
+    contract_command(
+        state,
+        Command::AcceptContract {
+            contract_id: ContractId(id),
+        },
+    )
+}
+
+/// An open offer due at `deadline_tick`.
+fn offer(id: u64, kind: ContractKind, deadline_tick: u64) -> Contract {
+    Contract {
+        id: ContractId(id),
+        kind,
+        reward: 1_000.0,
+        penalty: 250.0,
+        offered_tick: 0,
+        deadline_tick,
+        holder: None,
+        progress: 0,
+    }
+}
+
+fn deliver_fe(kg: f32) -> ContractKind {
+    ContractKind::Deliver {
+        element: "Fe".to_string(),
+        kg,
+    }
+}
+
+fn add_fe(state: &mut GameState, kg: f32) {
+    let station = state.stations.get_mut(&test_station_id()).unwrap();
+    station.core.inventory.push(InventoryItem::Material {
+        element: "Fe".to_string(),
+        kg,
+        quality: 0.8,
+        thermal: None,
+    });
+    station.invalidate_volume_cache();
+}
+
+#[test]
+fn offers_appear_on_interval_up_to_the_cap_and_lapse_unaccepted() {
+    let mut content = test_content();
+    content.pricing.items.insert(
+        "Fe".to_string(),
+        PricingEntry {
+            base_price_per_unit: 50.0,
+            importable: true,
+            exportable: true,
+            category: "material".to_string(),
+            ..Default::default()
+        },
+    );
+    content.constants.contract_offer_interval_minutes = 2;
+    content.constants.contract_max_offers = 2;
+    content.constants.contract_duration_minutes = 5;
+    content.constants.derive_tick_values();
+    let mut state = test_state(&content);
+    let mut rng = make_rng();
+
+    let mut offered = Vec::new();
+    for _ in 0..7 {
+        let events = tick(&mut state, &[], &content, &mut rng, None);
+        offered.extend(events.iter().filter_map(|e| match &e.event {
+            Event::ContractOffered {
+                kind,
+                reward,
+                penalty,
+                ..
+            } => Some((kind.clone(), *reward, *penalty)),
+            _ => None,
+        }));
+    }
+    // Offers at ticks 2 and 4; the cap holds back tick 6's.
+    assert_eq!(offered.len(), 2);
+    for (kind, reward, penalty) in &offered {
+        match kind {
+            ContractKind::Deliver { element, kg } => {
+                assert_eq!(element, "Fe");
+                assert!((500.0..=5_000.0).contains(kg));
+                assert!((reward - 50.0 * f64::from(*kg) * 1.5).abs() < 1e-6);
+            }
+            ContractKind::Survey { count } => {
+                assert!((1..=5).contains(count));
+                assert!((reward - f64::from(*count) * 100_000.0).abs() < 1e-6);
+            }
+        }
+        assert!((penalty - reward * 0.25).abs() < 1e-6);
+    }
+
+    // The first offer (deadline tick 7) is withdrawn on tick 8, which
+    // frees room for a new offer the same tick.
+    tick(&mut state, &[], &content, &mut rng, None);
+    assert!(state.contracts.iter().any(|c| c.id == ContractId(0)));
+    tick(&mut state, &[], &content, &mut rng, None);
+    let ids: Vec<ContractId> = state.contracts.iter().map(|c| c.id).collect();
+    assert_eq!(ids, [ContractId(1), ContractId(2)]);
+    assert!(state.contracts.iter().all(|c| c.holder.is_none()));
+}
+
+#[test]
+fn delivery_completes_from_station_stock_and_pays_reward() {
+    let content = test_content();
+    let mut state = test_state(&content);
+    let mut rng = make_rng();
+    state.contracts.push(offer(0, deliver_fe(300.0), 100));
+    let balance = state.balance;
+
+    let cmd = accept(&state, 0);
+    let events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    assert!(events
+        .iter()
+        .any(|e| matches!(&e.event, Event::ContractAccepted { .. })));
+    assert!(state.contracts[0].holder.is_some(), "no Fe yet, still held");
+
+    add_fe(&mut state, 500.0);
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::ContractCompleted { reward, .. } if (*reward - 1_000.0).abs() < 1e-9
+    )));
+    assert!(state.contracts.is_empty());
+    assert!((state.balance - (balance + 1_000.0)).abs() < 1e-6);
+    let fe: f32 = state.stations[&test_station_id()]
+        .core
+        .inventory
+        .iter()
+        .filter_map(|item| match item {
+            InventoryItem::Material { element, kg, .. } if element == "Fe" => Some(*kg),
+            _ => None,
+        })
+        .sum();
+    assert!((fe - 200.0).abs() < 1e-3, "300 kg handed over, got {fe}");
+}
+
+#[test]
+fn survey_contract_counts_discoveries_after_acceptance() {
+    let content = test_content();
+    let mut state = test_state(&content);
+    let mut rng = make_rng();
+    state
+        .contracts
+        .push(offer(0, ContractKind::Survey { count: 1 }, 100));
+
+    let cmds = [accept(&state, 0), survey_command(&state)];
+    tick(&mut state, &cmds, &content, &mut rng, None);
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+
+    assert!(events
+        .iter()
+        .any(|e| matches!(&e.event, Event::AsteroidDiscovered { .. })));
+    assert!(events
+        .iter()
+        .any(|e| matches!(&e.event, Event::ContractCompleted { .. })));
+}
+
+#[test]
+fn abandoning_or_missing_the_deadline_charges_the_penalty() {
+    let content = test_content();
+    let mut state = test_state(&content);
+    let mut rng = make_rng();
+    state.contracts.push(offer(0, deliver_fe(300.0), 100));
+    state.contracts.push(offer(1, deliver_fe(300.0), 1));
+    let balance = state.balance;
+
+    let cmds = [accept(&state, 0), accept(&state, 1)];
+    tick(&mut state, &cmds, &content, &mut rng, None);
+    let abandon = contract_command(
+        &state,
+        Command::AbandonContract {
+            contract_id: ContractId(0),
+        },
+    );
+    let events = tick(&mut state, &[abandon], &content, &mut rng, None);
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::ContractFailed {
+            contract_id: ContractId(0),
+            abandoned: true,
+            ..
+        }
+    )));
+    assert!(state.contracts.len() == 1 && state.contracts[0].id == ContractId(1));
+
+    // Due at tick 1; fails on tick 2.
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::ContractFailed {
+            contract_id: ContractId(1),
+            abandoned: false,
+            ..
+        }
+    )));
+    assert!(state.contracts.is_empty());
+    assert!((state.balance - (balance - 500.0)).abs() < 1e-6);
+}
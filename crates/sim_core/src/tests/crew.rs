@@ -279,6 +279,8 @@ fn crew_import_via_trade() {
             importable: true,
             exportable: false,
             category: "crew".to_string(),
+            royalty_per_unit: 0.0,
+            elasticity: 0.0,
         },
     );
 
@@ -369,3 +371,117 @@ fn available_crew_computation() {
         0
     );
 }
+
+/// Helper: install a one-berth habitat drawing 6 kg H2O per crew-hour
+/// (0.1 kg/tick) and stock `water_kg` of H2O.
+fn add_habitat(state: &mut GameState, content: &mut GameContent, water_kg: f32) {
+    content.module_defs.insert(
+        "module_habitat".to_string(),
+        ModuleDefBuilder::new("module_habitat")
+            .behavior(ModuleBehaviorDef::Habitat(crate::HabitatDef {
+                crew_capacity: 1,
+                consumables_kg_per_crew_hour: BTreeMap::from([("H2O".to_string(), 6.0)]),
+            }))
+            .build(),
+    );
+    let station = state.stations.get_mut(&test_station_id()).unwrap();
+    station.core.modules.push(ModuleState {
+        id: ModuleInstanceId("mod_habitat".to_string()),
+        def_id: "module_habitat".to_string(),
+        enabled: true,
+        kind_state: ModuleKindState::Habitat,
+        wear: WearState::default(),
+        thermal: None,
+        power_stalled: false,
+        module_priority: 0,
+        assigned_crew: BTreeMap::new(),
+        efficiency: 1.0,
+        prev_crew_satisfied: true,
+        slot_index: None,
+    });
+    station.core.inventory.push(InventoryItem::Material {
+        element: "H2O".to_string(),
+        kg: water_kg,
+        quality: 1.0,
+        thermal: None,
+    });
+    station.rebuild_module_index(content);
+}
+
+fn station_kg(state: &GameState, element: &str) -> f32 {
+    state.stations[&test_station_id()]
+        .core
+        .inventory
+        .iter()
+        .filter_map(|item| match item {
+            InventoryItem::Material {
+                element: el, kg, ..
+            } if el == element => Some(*kg),
+            _ => None,
+        })
+        .sum()
+}
+
+#[test]
+fn life_support_draws_consumables_and_fails_when_short() {
+    let mut content = crew_content();
+    let mut state = crew_state(&content, 1);
+    add_habitat(&mut state, &mut content, 0.25);
+    let mut rng = make_rng();
+
+    for _ in 0..2 {
+        tick(&mut state, &[], &content, &mut rng, None);
+    }
+    assert!((station_kg(&state, "H2O") - 0.05).abs() < 1e-4);
+    assert!((state.stations[&test_station_id()].core.modules[0].efficiency - 1.0).abs() < 1e-6);
+
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::LifeSupportFailed { short_element: Some(element), crew: 1, crew_capacity: 1, .. }
+            if element == "H2O"
+    )));
+    let station = &state.stations[&test_station_id()];
+    assert!((station.core.modules[0].efficiency - 0.5).abs() < 1e-6);
+    assert!(
+        (station_kg(&state, "H2O") - 0.05).abs() < 1e-4,
+        "nothing drawn while short"
+    );
+
+    state
+        .stations
+        .get_mut(&test_station_id())
+        .unwrap()
+        .core
+        .inventory
+        .push(InventoryItem::Material {
+            element: "H2O".to_string(),
+            kg: 10.0,
+            quality: 1.0,
+            thermal: None,
+        });
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+    assert!(events
+        .iter()
+        .any(|e| matches!(e.event, Event::LifeSupportRestored { .. })));
+    assert!((state.stations[&test_station_id()].core.modules[0].efficiency - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn crew_beyond_habitat_capacity_fails_life_support() {
+    let mut content = crew_content();
+    let mut state = crew_state(&content, 2);
+    add_habitat(&mut state, &mut content, 100.0);
+    let mut rng = make_rng();
+
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+    assert!(events.iter().any(|e| matches!(
+        e.event,
+        Event::LifeSupportFailed {
+            crew: 2,
+            crew_capacity: 1,
+            short_element: None,
+            ..
+        }
+    )));
+}
@@ -133,3 +133,128 @@ fn test_deep_scan_composition_matches_truth_with_zero_sigma() {
         );
     }
 }
+
+#[test]
+fn test_deep_scan_reveals_layer_profile() {
+    let mut content = test_content();
+    content.constants.asteroid_surface_fraction_min = 0.2;
+    content.constants.asteroid_surface_fraction_max = 0.3;
+    let (mut state, asteroid_id) = state_with_asteroid(&content);
+    let ship_id = test_ship_id();
+    let mut rng = make_rng();
+
+    let mut events = Vec::new();
+    crate::tasks::resolve_deep_scan(
+        &mut state,
+        &ship_id,
+        &asteroid_id,
+        &content,
+        &mut rng,
+        &mut events,
+    );
+
+    let asteroid = &state.asteroids[&asteroid_id];
+    let known = asteroid
+        .knowledge
+        .layers
+        .as_ref()
+        .expect("deep scan should map the layers");
+    let truth = asteroid.layers.as_ref().unwrap();
+    assert!((known.surface_fraction - truth.surface_fraction).abs() < f32::EPSILON);
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::CompositionMapped {
+            layers: Some(_),
+            ..
+        }
+    )));
+}
+
+/// Test content whose deep scans carry `sigma` of composition noise.
+fn noisy_scan_content(sigma: f32) -> GameContent {
+    let mut content = test_content();
+    for effect in &mut content.techs[0].effects {
+        if let TechEffect::DeepScanCompositionNoise { sigma: noise } = effect {
+            *noise = sigma;
+        }
+    }
+    content
+}
+
+fn max_composition_error(asteroid: &AsteroidState) -> f32 {
+    let mapped = asteroid.knowledge.composition.as_ref().unwrap();
+    asteroid
+        .true_composition
+        .iter()
+        .map(|(element, &truth)| (mapped.get(element).copied().unwrap_or(0.0) - truth).abs())
+        .fold(0.0, f32::max)
+}
+
+#[test]
+fn test_repeat_deep_scans_average_down_the_noise() {
+    let content = noisy_scan_content(0.1);
+    let (mut state, asteroid_id) = state_with_asteroid(&content);
+    let ship_id = test_ship_id();
+    let mut rng = make_rng();
+
+    let mut events = Vec::new();
+    for _ in 0..16 {
+        crate::tasks::resolve_deep_scan(
+            &mut state,
+            &ship_id,
+            &asteroid_id,
+            &content,
+            &mut rng,
+            &mut events,
+        );
+    }
+
+    let asteroid = &state.asteroids[&asteroid_id];
+    assert_eq!(asteroid.knowledge.deep_scan_count, 16);
+    // Sixteen equal-noise samples leave a quarter of the noise.
+    let sigma = asteroid.knowledge.composition_sigma.unwrap();
+    assert!((sigma - 0.025).abs() < 1e-5);
+    assert!(max_composition_error(asteroid) < 0.05);
+    assert!(matches!(
+        events.last().map(|e| &e.event),
+        Some(Event::TaskCompleted { .. })
+    ));
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::CompositionMapped { deep_scan_count: 16, composition_sigma, .. }
+            if (*composition_sigma - 0.025).abs() < 1e-5
+    )));
+}
+
+#[test]
+fn test_exact_deep_scan_overrides_earlier_noisy_scans() {
+    let noisy = noisy_scan_content(0.1);
+    let (mut state, asteroid_id) = state_with_asteroid(&noisy);
+    let ship_id = test_ship_id();
+    let mut rng = make_rng();
+    let mut events = Vec::new();
+
+    crate::tasks::resolve_deep_scan(
+        &mut state,
+        &ship_id,
+        &asteroid_id,
+        &noisy,
+        &mut rng,
+        &mut events,
+    );
+    // Better scanning tech arrives: its sample carries all the weight.
+    let exact = noisy_scan_content(0.0);
+    crate::tasks::resolve_deep_scan(
+        &mut state,
+        &ship_id,
+        &asteroid_id,
+        &exact,
+        &mut rng,
+        &mut events,
+    );
+
+    let asteroid = &state.asteroids[&asteroid_id];
+    assert!(max_composition_error(asteroid) < 1e-5);
+    assert_eq!(asteroid.knowledge.composition_sigma, Some(0.0));
+    assert_eq!(asteroid.knowledge.deep_scan_count, 2);
+}
@@ -0,0 +1,78 @@
+use super::*;
+
+/// A drone bay that cuts 100 kg every tick from asteroids at its own body.
+fn drone_bay_content() -> GameContent {
+    let mut content = test_content();
+    content.module_defs.insert(
+        "module_drone_bay".to_string(),
+        ModuleDefBuilder::new("module_drone_bay")
+            .wear(0.01)
+            .behavior(ModuleBehaviorDef::DroneBay(crate::DroneBayDef {
+                mining_interval_minutes: 1,
+                mining_interval_ticks: 1,
+                range_hops: 0,
+                kg_per_run: 100.0,
+            }))
+            .build(),
+    );
+    content
+}
+
+fn drone_bay_state(content: &GameContent, deep_scanned: bool) -> (GameState, AsteroidId) {
+    let (mut state, asteroid_id) = state_with_asteroid(content);
+    let asteroid = state.asteroids.get_mut(&asteroid_id).unwrap();
+    if deep_scanned {
+        asteroid.knowledge.composition = Some(asteroid.true_composition.clone());
+    }
+    let station = state.stations.get_mut(&test_station_id()).unwrap();
+    station.core.modules.push(test_module(
+        "module_drone_bay",
+        ModuleKindState::DroneBay(crate::DroneBayState::default()),
+    ));
+    station.rebuild_module_index(content);
+    (state, asteroid_id)
+}
+
+fn station_ore_kg(state: &GameState) -> f32 {
+    state.stations[&test_station_id()]
+        .core
+        .inventory
+        .iter()
+        .filter_map(|item| match item {
+            InventoryItem::Ore { kg, .. } => Some(*kg),
+            _ => None,
+        })
+        .sum()
+}
+
+#[test]
+fn drone_bay_mines_deep_scanned_asteroid_into_station() {
+    let content = drone_bay_content();
+    let (mut state, asteroid_id) = drone_bay_state(&content, true);
+    let mass_before = state.asteroids[&asteroid_id].mass_kg;
+    let mut rng = make_rng();
+
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::DroneOreMined { asteroid_id: id, .. } if *id == asteroid_id
+    )));
+    assert!((station_ore_kg(&state) - 100.0).abs() < 1e-3);
+    assert!((state.asteroids[&asteroid_id].mass_kg - (mass_before - 100.0)).abs() < 1e-3);
+    let bay = &state.stations[&test_station_id()].core.modules[0];
+    assert!(bay.wear.wear > 0.0, "each run wears the drones");
+}
+
+#[test]
+fn drone_bay_ignores_asteroids_without_deep_scan() {
+    let content = drone_bay_content();
+    let (mut state, _) = drone_bay_state(&content, false);
+    let mut rng = make_rng();
+
+    for _ in 0..3 {
+        tick(&mut state, &[], &content, &mut rng, None);
+    }
+
+    assert!(station_ore_kg(&state).abs() < 1e-6);
+}
@@ -16,6 +16,8 @@ fn electrolysis_content() -> GameContent {
         boiloff_rate_per_day_at_293k: None,
         boiling_point_mk: None,
         boiloff_curve: None,
+        volatile: false,
+        quality_decay_per_day: None,
     });
     content.elements.push(ElementDef {
         id: "LH2".to_string(),
@@ -29,6 +31,8 @@ fn electrolysis_content() -> GameContent {
         boiloff_rate_per_day_at_293k: None,
         boiling_point_mk: None,
         boiloff_curve: None,
+        volatile: false,
+        quality_decay_per_day: None,
     });
     content.elements.push(ElementDef {
         id: "LOX".to_string(),
@@ -42,6 +46,8 @@ fn electrolysis_content() -> GameContent {
         boiloff_rate_per_day_at_293k: None,
         boiling_point_mk: None,
         boiloff_curve: None,
+        volatile: false,
+        quality_decay_per_day: None,
     });
 
     let electrolysis_recipe = RecipeDef {
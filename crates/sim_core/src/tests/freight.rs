@@ -0,0 +1,281 @@
+//! `Command::ScheduleFreight` — paid NPC shipping between stations — and
+//! `Command::CreateFreightRoute` — recurring runs with the owner's ships.
+
+use super::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+fn outpost_id() -> StationId {
+    StationId("station_outpost".to_string())
+}
+
+/// Base state plus a second, empty station co-located with the first
+/// (one hop apart). Origin holds 1000 kg of Fe.
+fn freight_state(content: &GameContent) -> GameState {
+    let mut state = base_state(content);
+    state.balance = 1_000_000.0;
+    let origin = state.stations.get_mut(&test_station_id()).unwrap();
+    origin.core.inventory.push(InventoryItem::Material {
+        element: "Fe".to_string(),
+        kg: 1000.0,
+        quality: 0.8,
+        thermal: None,
+    });
+    let mut outpost = origin.clone();
+    outpost.id = outpost_id();
+    outpost.core.inventory.clear();
+    state.stations.insert(outpost_id(), outpost);
+    state
+}
+
+fn freight_content() -> GameContent {
+    let mut content = base_content();
+    content.constants.freight_cost_per_kg_hop = 2.0;
+    content.constants.freight_handling_minutes = 10;
+    content
+}
+
+fn schedule(kg: f32) -> CommandEnvelope {
+    CommandEnvelope {
+        id: CommandId(0),
+        issued_by: PrincipalId("principal_autopilot".to_string()),
+        issued_tick: 0,
+        execute_at_tick: 0,
+        command: Command::ScheduleFreight {
+            from_station: test_station_id(),
+            to_station: outpost_id(),
+            item_spec: TradeItemSpec::Material {
+                element: "Fe".to_string(),
+                kg,
+            },
+        },
+    }
+}
+
+fn fe_kg(state: &GameState, station_id: &StationId) -> f32 {
+    state.stations[station_id]
+        .core
+        .inventory
+        .iter()
+        .filter_map(|item| match item {
+            InventoryItem::Material { element, kg, .. } if element == "Fe" => Some(*kg),
+            _ => None,
+        })
+        .sum()
+}
+
+#[test]
+fn freight_charges_per_kg_hop_and_delivers_after_delay() {
+    let content = freight_content();
+    let mut state = freight_state(&content);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+    let events = tick(&mut state, &[schedule(400.0)], &content, &mut rng, None);
+
+    // 400 kg * 2.0 per kg-hop * 1 hop
+    assert!((state.balance - (1_000_000.0 - 800.0)).abs() < 0.01);
+    assert!((fe_kg(&state, &test_station_id()) - 600.0).abs() < 0.01);
+    assert!(fe_kg(&state, &outpost_id()) < f32::EPSILON);
+    let arrival_tick = state.freight_orders[0].arrival_tick;
+    // 10 handling ticks + min transit
+    assert_eq!(arrival_tick, 10 + content.constants.min_transit_ticks);
+    assert!(events
+        .iter()
+        .any(|e| matches!(&e.event, Event::FreightScheduled { .. })));
+
+    let mut delivered = false;
+    while state.meta.tick <= arrival_tick {
+        let events = tick(&mut state, &[], &content, &mut rng, None);
+        delivered |= events
+            .iter()
+            .any(|e| matches!(&e.event, Event::FreightDelivered { .. }));
+    }
+    assert!(delivered, "should emit FreightDelivered on arrival");
+    assert!(state.freight_orders.is_empty());
+    assert!((fe_kg(&state, &outpost_id()) - 400.0).abs() < 0.01);
+}
+
+#[test]
+fn freight_rejected_when_origin_lacks_goods() {
+    let content = freight_content();
+    let mut state = freight_state(&content);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+    tick(&mut state, &[schedule(5000.0)], &content, &mut rng, None);
+
+    assert!(state.freight_orders.is_empty());
+    assert!((state.balance - 1_000_000.0).abs() < 0.01);
+    assert!((fe_kg(&state, &test_station_id()) - 1000.0).abs() < 0.01);
+}
+
+#[test]
+fn freight_rejected_when_destination_lacks_space() {
+    let content = freight_content();
+    let mut state = freight_state(&content);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    state
+        .stations
+        .get_mut(&outpost_id())
+        .unwrap()
+        .core
+        .cargo_capacity_m3 = 0.01;
+
+    tick(&mut state, &[schedule(400.0)], &content, &mut rng, None);
+
+    assert!(state.freight_orders.is_empty());
+    assert!((state.balance - 1_000_000.0).abs() < 0.01);
+    assert!(
+        (fe_kg(&state, &test_station_id()) - 1000.0).abs() < 0.01,
+        "partial take should be returned to the origin"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Freight routes (`Command::CreateFreightRoute`)
+// ---------------------------------------------------------------------------
+
+fn create_route(kg: f32, interval_ticks: u64) -> CommandEnvelope {
+    CommandEnvelope {
+        id: CommandId(0),
+        issued_by: PrincipalId("principal_autopilot".to_string()),
+        issued_tick: 0,
+        execute_at_tick: 0,
+        command: Command::CreateFreightRoute {
+            from_station: test_station_id(),
+            to_station: outpost_id(),
+            item_spec: TradeItemSpec::Material {
+                element: "Fe".to_string(),
+                kg,
+            },
+            interval_ticks,
+        },
+    }
+}
+
+#[test]
+fn route_sends_idle_ship_and_reports_delivery() {
+    let content = freight_content();
+    let mut state = freight_state(&content);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+    let events = tick(
+        &mut state,
+        &[create_route(400.0, 50)],
+        &content,
+        &mut rng,
+        None,
+    );
+    assert!(events
+        .iter()
+        .any(|e| matches!(&e.event, Event::FreightRouteCreated { .. })));
+    let trip = state.freight_routes[0]
+        .active_trip
+        .clone()
+        .expect("dispatched");
+    assert_eq!(trip.ship_id, test_ship_id());
+    assert_eq!(state.freight_routes[0].next_dispatch_tick, 50);
+
+    let mut delivery = None;
+    while delivery.is_none() && state.meta.tick < 50 {
+        let events = tick(&mut state, &[], &content, &mut rng, None);
+        delivery = events.into_iter().find_map(|e| match e.event {
+            Event::FreightDelivered {
+                route_id, ship_id, ..
+            } => Some((route_id, ship_id)),
+            _ => None,
+        });
+    }
+    assert_eq!(
+        delivery,
+        Some((Some(state.freight_routes[0].id), Some(test_ship_id())))
+    );
+    assert!((fe_kg(&state, &outpost_id()) - 400.0).abs() < 0.01);
+    assert!(state.freight_routes[0].active_trip.is_none());
+    assert!(
+        state.freight_orders.is_empty(),
+        "route runs are not NPC orders"
+    );
+
+    // The origin still holds 600 kg, but the next run waits for the interval.
+    while state.meta.tick < 50 {
+        tick(&mut state, &[], &content, &mut rng, None);
+        assert!(state.freight_routes[0].active_trip.is_none());
+    }
+    tick(&mut state, &[], &content, &mut rng, None);
+    assert!(state.freight_routes[0].active_trip.is_some());
+}
+
+#[test]
+fn route_waits_for_stock_and_drops_runs_of_reassigned_ships() {
+    let content = freight_content();
+    let mut state = freight_state(&content);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+    tick(
+        &mut state,
+        &[create_route(5000.0, 10)],
+        &content,
+        &mut rng,
+        None,
+    );
+    assert!(
+        state.freight_routes[0].active_trip.is_none(),
+        "origin holds only 1000 kg"
+    );
+
+    state.freight_routes[0].item_spec = TradeItemSpec::Material {
+        element: "Fe".to_string(),
+        kg: 100.0,
+    };
+    tick(&mut state, &[], &content, &mut rng, None);
+    assert!(state.freight_routes[0].active_trip.is_some());
+
+    state.ships.get_mut(&test_ship_id()).unwrap().task = None;
+    tick(&mut state, &[], &content, &mut rng, None);
+    assert!(state.freight_routes[0].active_trip.is_none());
+}
+
+#[test]
+fn route_rejected_unless_issuer_owns_both_stations() {
+    let content = freight_content();
+    let mut state = freight_state(&content);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    state.stations.get_mut(&outpost_id()).unwrap().owner =
+        PrincipalId("principal_rival".to_string());
+
+    let events = tick(
+        &mut state,
+        &[create_route(100.0, 10)],
+        &content,
+        &mut rng,
+        None,
+    );
+
+    assert!(state.freight_routes.is_empty());
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::CommandRejected { reason, .. }
+            if reason == "station 'station_outpost' is not owned by principal_autopilot"
+    )));
+}
+
+#[test]
+fn route_rejected_for_zero_interval_or_same_station() {
+    let content = freight_content();
+    let mut state = freight_state(&content);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    let mut same_station = create_route(100.0, 10);
+    if let Command::CreateFreightRoute { to_station, .. } = &mut same_station.command {
+        *to_station = test_station_id();
+    }
+
+    tick(
+        &mut state,
+        &[create_route(100.0, 0), same_station],
+        &content,
+        &mut rng,
+        None,
+    );
+
+    assert!(state.freight_routes.is_empty());
+}
@@ -11,6 +11,8 @@ fn trade_content() -> GameContent {
             importable: true,
             exportable: true,
             category: String::new(),
+            royalty_per_unit: 0.0,
+            elasticity: 0.0,
         },
     );
     content
@@ -35,6 +37,8 @@ fn add_ground_facility(state: &mut GameState) -> GroundFacilityId {
             module_type_index: ModuleTypeIndex::default(),
             module_id_index: HashMap::new(),
             power_budget_cache: PowerBudgetCache::default(),
+            production_windows: std::collections::BTreeMap::new(),
+            life_support_failing: false,
         },
         launch_transits: Vec::new(),
     };
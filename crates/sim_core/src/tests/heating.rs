@@ -16,6 +16,8 @@ fn heating_content() -> GameContent {
         boiloff_rate_per_day_at_293k: None,
         boiling_point_mk: None,
         boiloff_curve: None,
+        volatile: false,
+        quality_decay_per_day: None,
     });
     let water_recipe = RecipeDef {
         id: RecipeId("recipe_extract_water".to_string()),
@@ -713,6 +713,10 @@ fn orbital_deploy_satellite_from_station() {
             },
             leaders: Vec::new(),
             frame_id: None,
+            owner: PrincipalId("principal_autopilot".to_string()),
+            expansions: crate::StationExpansions::default(),
+            power_policy: crate::PowerPolicy::default(),
+            history: std::collections::VecDeque::new(),
         },
     );
     crate::test_fixtures::rebuild_indices(&mut state, &content);
@@ -778,6 +782,10 @@ fn orbital_deploy_rejected_no_component() {
             },
             leaders: Vec::new(),
             frame_id: None,
+            owner: PrincipalId("principal_autopilot".to_string()),
+            expansions: crate::StationExpansions::default(),
+            power_policy: crate::PowerPolicy::default(),
+            history: std::collections::VecDeque::new(),
         },
     );
     crate::test_fixtures::rebuild_indices(&mut state, &content);
@@ -826,6 +834,10 @@ fn orbital_deploy_rejected_unknown_def() {
             },
             leaders: Vec::new(),
             frame_id: None,
+            owner: PrincipalId("principal_autopilot".to_string()),
+            expansions: crate::StationExpansions::default(),
+            power_policy: crate::PowerPolicy::default(),
+            history: std::collections::VecDeque::new(),
         },
     );
     crate::test_fixtures::rebuild_indices(&mut state, &content);
@@ -875,6 +887,10 @@ fn orbital_deploy_rejected_tech_gated() {
             },
             leaders: Vec::new(),
             frame_id: None,
+            owner: PrincipalId("principal_autopilot".to_string()),
+            expansions: crate::StationExpansions::default(),
+            power_policy: crate::PowerPolicy::default(),
+            history: std::collections::VecDeque::new(),
         },
     );
     crate::test_fixtures::rebuild_indices(&mut state, &content);
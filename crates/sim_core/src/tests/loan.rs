@@ -0,0 +1,115 @@
+use super::*;
+use crate::test_fixtures::{base_content, base_state, make_rng};
+
+/// Repayments of 100 every 10 ticks, defaulting after 2 misses in a row.
+fn loan_content(interest_per_day: f64) -> GameContent {
+    let mut content = base_content();
+    content.constants.loan_interest_rate_per_day = interest_per_day;
+    content.constants.loan_repayment_interval_minutes = 10;
+    content.constants.loan_repayment_amount = 100.0;
+    content.constants.loan_default_after_missed = 2;
+    content.constants.derive_tick_values();
+    content
+}
+
+fn loan_state(content: &GameContent, outstanding: f64, balance: f64) -> GameState {
+    let mut state = base_state(content);
+    state.balance = balance;
+    state.loan.outstanding = outstanding;
+    state
+}
+
+/// Ticks until the state is at `tick`, returning every event.
+fn run_to(state: &mut GameState, content: &GameContent, tick: u64) -> Vec<EventEnvelope> {
+    let mut rng = make_rng();
+    let mut events = Vec::new();
+    while state.meta.tick <= tick {
+        events.extend(crate::tick(state, &[], content, &mut rng, None));
+    }
+    events
+}
+
+fn bankruptcies(events: &[EventEnvelope]) -> usize {
+    events
+        .iter()
+        .filter(|e| matches!(e.event, Event::StationBankrupt { .. }))
+        .count()
+}
+
+#[test]
+fn repayments_are_taken_on_schedule() {
+    let content = loan_content(0.0);
+    let mut state = loan_state(&content, 250.0, 1_000.0);
+
+    run_to(&mut state, &content, 9);
+    assert!((state.balance - 1_000.0).abs() < 1e-9);
+
+    run_to(&mut state, &content, 30);
+    // 100 at ticks 10 and 20, then the last 50 owed at tick 30.
+    assert!((state.balance - 750.0).abs() < 1e-9, "{}", state.balance);
+    assert!(state.loan.outstanding.abs() < 1e-9);
+
+    run_to(&mut state, &content, 40);
+    assert!(
+        (state.balance - 750.0).abs() < 1e-9,
+        "nothing left to repay"
+    );
+}
+
+#[test]
+fn interest_accrues_every_tick() {
+    // 1440% per day at one minute per tick is 1% per tick.
+    let content = loan_content(14.4);
+    let mut state = loan_state(&content, 1_000.0, 0.0);
+
+    run_to(&mut state, &content, 0);
+
+    assert!((state.loan.outstanding - 1_010.0).abs() < 1e-6);
+}
+
+#[test]
+fn missed_repayments_default_the_loan() {
+    let content = loan_content(0.0);
+    let mut state = loan_state(&content, 1_000.0, 50.0);
+
+    let events = run_to(&mut state, &content, 10);
+    assert_eq!(state.loan.missed_payments, 1);
+    assert!(!state.loan.defaulted);
+    assert_eq!(bankruptcies(&events), 0);
+
+    let events = run_to(&mut state, &content, 20);
+    assert!(state.loan.defaulted);
+    assert_eq!(bankruptcies(&events), 1);
+    assert!(
+        (state.balance - 50.0).abs() < 1e-9,
+        "missed payments take nothing"
+    );
+
+    let events = run_to(&mut state, &content, 40);
+    assert_eq!(bankruptcies(&events), 0, "a defaulted loan stays quiet");
+}
+
+#[test]
+fn a_made_repayment_clears_earlier_misses() {
+    let content = loan_content(0.0);
+    let mut state = loan_state(&content, 1_000.0, 50.0);
+
+    run_to(&mut state, &content, 10);
+    assert_eq!(state.loan.missed_payments, 1);
+    state.balance = 500.0;
+    run_to(&mut state, &content, 20);
+
+    assert_eq!(state.loan.missed_payments, 0);
+    assert!((state.loan.outstanding - 900.0).abs() < 1e-9);
+}
+
+#[test]
+fn default_constants_leave_the_loan_untouched() {
+    let content = base_content();
+    let mut state = loan_state(&content, 1_000.0, 1_000.0);
+
+    run_to(&mut state, &content, 50);
+
+    assert!((state.loan.outstanding - 1_000.0).abs() < 1e-9);
+    assert!((state.balance - 1_000.0).abs() < 1e-9);
+}
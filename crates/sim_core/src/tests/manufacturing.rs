@@ -167,6 +167,7 @@ fn competing_demand_content() -> GameContent {
                 assembly_interval_ticks: 2,
                 max_stock: HashMap::new(),
                 recipes: vec![recipe_structural_beam_id],
+                parallel_slots: 1,
             }))
             .build(),
     );
@@ -183,6 +184,7 @@ fn competing_demand_content() -> GameContent {
                 assembly_interval_ticks: 2,
                 max_stock: HashMap::new(),
                 recipes: vec![recipe_advanced_repair_kit_id],
+                parallel_slots: 1,
             }))
             .build(),
     );
@@ -229,6 +231,9 @@ fn state_with_competing_assemblers(content: &GameContent) -> GameState {
             capped: false,
             cap_override: HashMap::new(),
             selected_recipe: Some(RecipeId("recipe_structural_beam".to_string())),
+            extra_slots: Vec::new(),
+            job: None,
+            queue: Vec::new(),
         }),
         wear: WearState::default(),
         power_stalled: false,
@@ -251,6 +256,9 @@ fn state_with_competing_assemblers(content: &GameContent) -> GameState {
             capped: false,
             cap_override: HashMap::new(),
             selected_recipe: Some(RecipeId("recipe_advanced_repair_kit".to_string())),
+            extra_slots: Vec::new(),
+            job: None,
+            queue: Vec::new(),
         }),
         wear: WearState::default(),
         power_stalled: false,
@@ -89,3 +89,50 @@ fn test_mine_removes_depleted_asteroid() {
         "fully mined asteroid should be removed from state"
     );
 }
+
+#[test]
+fn test_layered_asteroid_yields_surface_then_core() {
+    let content = test_content();
+    let (mut state, asteroid_id) = state_with_asteroid(&content);
+    let ship_id = test_ship_id();
+    {
+        let asteroid = state.asteroids.get_mut(&asteroid_id).unwrap();
+        asteroid.mass_kg = 1_000.0;
+        asteroid.layers = Some(CompositionLayers {
+            surface_fraction: 0.5,
+            initial_mass_kg: 1_000.0,
+            surface: HashMap::from([("Fe".to_string(), 1.0)]),
+            core: HashMap::from([("Si".to_string(), 1.0)]),
+        });
+    }
+    // Hold takes 400 kg of ore per trip.
+    let ore_density = content.density_map[crate::ELEMENT_ORE];
+    state.ships.get_mut(&ship_id).unwrap().cargo_capacity_m3 = 400.0 / ore_density;
+
+    let mut cuts = Vec::new();
+    for _ in 0..3 {
+        state.ships.get_mut(&ship_id).unwrap().inventory.clear();
+        let mut events = Vec::new();
+        crate::tasks::resolve_mine(&mut state, &ship_id, &asteroid_id, &content, &mut events);
+        let layer = events.iter().find_map(|e| match &e.event {
+            Event::OreMined { layer, .. } => *layer,
+            _ => None,
+        });
+        let Some(InventoryItem::Ore { composition, .. }) = state.ships[&ship_id].inventory.first()
+        else {
+            panic!("mining should load an ore lot");
+        };
+        let fe = composition.get("Fe").copied().unwrap_or(0.0);
+        cuts.push((layer, fe));
+    }
+
+    assert_eq!(cuts[0].0, Some(AsteroidLayer::Surface));
+    assert!((cuts[0].1 - 1.0).abs() < 1e-5, "first cut is all surface");
+    assert_eq!(cuts[1].0, Some(AsteroidLayer::Core));
+    assert!(
+        (cuts[1].1 - 0.25).abs() < 1e-5,
+        "second cut blends 100 kg surface with 300 kg core"
+    );
+    assert_eq!(cuts[2].0, Some(AsteroidLayer::Core));
+    assert!(cuts[2].1.abs() < 1e-5, "third cut is all core");
+}
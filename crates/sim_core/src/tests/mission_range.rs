@@ -0,0 +1,82 @@
+use super::*;
+
+/// `test_body` → `inner_zone` → `outer_zone`, with the only scan site at
+/// `outer_zone` (two hops from the station) and ships ranging one hop.
+fn ranged_setup() -> (GameContent, GameState) {
+    let mut content = test_content();
+    for (id, parent) in [("inner_zone", "test_body"), ("outer_zone", "inner_zone")] {
+        let mut body = content.solar_system.bodies[0].clone();
+        body.id = BodyId(id.to_string());
+        body.parent = Some(BodyId(parent.to_string()));
+        content.solar_system.bodies.push(body);
+    }
+    content.constants.mission_range_refuse = true;
+    let mut state = test_state(&content);
+    let ship = state.ships.get_mut(&test_ship_id()).unwrap();
+    content.constants.mission_range_propellant_kg_per_hop = ship.propellant_capacity_kg.max(1.0);
+    ship.propellant_capacity_kg = content.constants.mission_range_propellant_kg_per_hop;
+    state.scan_sites[0].position.parent_body = BodyId("outer_zone".to_string());
+    (content, state)
+}
+
+#[test]
+fn survey_beyond_range_is_refused() {
+    let (content, mut state) = ranged_setup();
+    let mut rng = make_rng();
+
+    let cmd = survey_command(&state);
+    let events = tick(&mut state, &[cmd], &content, &mut rng, None);
+
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::TaskOutOfRange {
+            hops: 2,
+            range_hops: 1,
+            refused: true,
+            ..
+        }
+    )));
+    assert!(!matches!(
+        state.ships[&test_ship_id()].task.as_ref().map(|t| &t.kind),
+        Some(TaskKind::Survey { .. })
+    ));
+}
+
+#[test]
+fn forward_station_brings_target_into_range() {
+    let (content, mut state) = ranged_setup();
+    let mut forward = state.stations[&test_station_id()].clone();
+    forward.id = StationId("station_forward".to_string());
+    forward.position.parent_body = BodyId("inner_zone".to_string());
+    state.stations.insert(forward.id.clone(), forward);
+    let mut rng = make_rng();
+
+    let cmd = survey_command(&state);
+    let events = tick(&mut state, &[cmd], &content, &mut rng, None);
+
+    assert!(!events
+        .iter()
+        .any(|e| matches!(e.event, Event::TaskOutOfRange { .. })));
+    assert!(matches!(
+        state.ships[&test_ship_id()].task.as_ref().map(|t| &t.kind),
+        Some(TaskKind::Survey { .. })
+    ));
+}
+
+#[test]
+fn warn_only_mode_keeps_the_assignment() {
+    let (mut content, mut state) = ranged_setup();
+    content.constants.mission_range_refuse = false;
+    let mut rng = make_rng();
+
+    let cmd = survey_command(&state);
+    let events = tick(&mut state, &[cmd], &content, &mut rng, None);
+
+    assert!(events
+        .iter()
+        .any(|e| matches!(&e.event, Event::TaskOutOfRange { refused: false, .. })));
+    assert!(matches!(
+        state.ships[&test_ship_id()].task.as_ref().map(|t| &t.kind),
+        Some(TaskKind::Survey { .. })
+    ));
+}
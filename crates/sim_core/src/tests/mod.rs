@@ -8,10 +8,14 @@ use rand_chacha::ChaCha8Rng;
 use std::collections::HashMap;
 
 mod assembler;
+mod cargo_transfer;
+mod claims;
 mod cold_refinery_regression;
 mod commands;
+mod contracts;
 mod deep_scan;
 mod deposit;
+mod drone_bay;
 mod electrolysis;
 mod heating;
 mod integration;
@@ -33,18 +37,28 @@ mod determinism_canary;
 mod efficiency;
 mod ground_trade;
 mod manufacturing;
+mod mission_range;
 mod module_index;
 mod operating_costs;
+mod pollution;
 mod replenish;
 mod salary;
+mod solar_storm;
+mod subsystems;
 mod thermal_link;
 mod trade;
 mod trade_integration;
 mod transfer_molten;
 
+mod freight;
 mod launch;
+mod loan;
+mod prelude;
+mod projects;
 mod research_lifecycle;
 mod satellite;
+mod ship_loss;
+mod threats;
 mod transfer;
 
 // --- Shared test helpers ------------------------------------------------
@@ -235,6 +249,7 @@ fn assembler_content() -> GameContent {
                 assembly_interval_ticks: 2,
                 max_stock: HashMap::new(),
                 recipes: vec![recipe_id],
+                parallel_slots: 1,
             }))
             .build(),
     )]
@@ -265,6 +280,9 @@ fn state_with_assembler(content: &GameContent) -> GameState {
             capped: false,
             cap_override: HashMap::new(),
             selected_recipe: None,
+            extra_slots: Vec::new(),
+            job: None,
+            queue: Vec::new(),
         }),
     ));
 
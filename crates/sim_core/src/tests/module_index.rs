@@ -17,7 +17,11 @@ fn module_id_index_after_install() {
             .name("Test Storage")
             .mass(100.0)
             .volume(1.0)
-            .behavior(ModuleBehaviorDef::Storage { capacity_m3: 100.0 })
+            .behavior(ModuleBehaviorDef::Storage {
+                capacity_m3: 100.0,
+                refrigerated: false,
+                climate_controlled: false,
+            })
             .build(),
     );
     let mut state = base_state(&content);
@@ -29,6 +33,7 @@ fn module_id_index_after_install() {
     station.core.inventory.push(InventoryItem::Module {
         item_id: ModuleItemId("mod_item_test".to_string()),
         module_def_id: "module_test_storage".to_string(),
+        wear: 0.0,
     });
     station.invalidate_volume_cache();
 
@@ -73,7 +78,11 @@ fn module_id_index_after_uninstall() {
             .mass(100.0)
             .volume(1.0)
             .power(1.0)
-            .behavior(ModuleBehaviorDef::Storage { capacity_m3: 100.0 })
+            .behavior(ModuleBehaviorDef::Storage {
+                capacity_m3: 100.0,
+                refrigerated: false,
+                climate_controlled: false,
+            })
             .build(),
     );
 
@@ -26,6 +26,8 @@ fn add_ground_facility(
             module_type_index: ModuleTypeIndex::default(),
             module_id_index: HashMap::new(),
             power_budget_cache: PowerBudgetCache::default(),
+            production_windows: std::collections::BTreeMap::new(),
+            life_support_failing: false,
         },
         launch_transits: Vec::new(),
     };
@@ -54,7 +56,11 @@ fn content_with_costly_module() -> GameContent {
     let mut content = base_content();
     let mut def = ModuleDefBuilder::new("module_costly")
         .name("Costly Module")
-        .behavior(ModuleBehaviorDef::Storage { capacity_m3: 100.0 })
+        .behavior(ModuleBehaviorDef::Storage {
+            capacity_m3: 100.0,
+            refrigerated: false,
+            climate_controlled: false,
+        })
         .build();
     def.operating_cost_per_tick = 50.0;
     content.module_defs.insert("module_costly".to_string(), def);
@@ -0,0 +1,142 @@
+use super::*;
+
+fn jettison_command(state: &GameState, station_id: &StationId) -> CommandEnvelope {
+    CommandEnvelope {
+        id: CommandId(0),
+        issued_by: PrincipalId("principal_autopilot".to_string()),
+        issued_tick: state.meta.tick,
+        execute_at_tick: state.meta.tick,
+        command: Command::JettisonSlag {
+            station_id: station_id.clone(),
+        },
+    }
+}
+
+fn push_slag(state: &mut GameState, kg: f32) {
+    let station = state.stations.get_mut(&test_station_id()).unwrap();
+    station.core.inventory.push(InventoryItem::Slag {
+        kg,
+        composition: HashMap::from([("slag".to_string(), 1.0)]),
+    });
+}
+
+fn survey_data_generated(events: &[EventEnvelope]) -> f32 {
+    events
+        .iter()
+        .find_map(|e| match &e.event {
+            Event::DataGenerated { kind, amount } if kind.0 == DataKind::SURVEY => Some(*amount),
+            _ => None,
+        })
+        .expect("survey should generate data")
+}
+
+#[test]
+fn jettison_accumulates_per_body_and_warns_once_past_threshold() {
+    let mut content = test_content();
+    content.constants.slag_pollution_threshold_kg = 150.0;
+    let mut state = test_state(&content);
+    let station_id = test_station_id();
+    let body_id = state.stations[&station_id].position.parent_body.clone();
+    let mut rng = make_rng();
+
+    push_slag(&mut state, 100.0);
+    let cmd = jettison_command(&state, &station_id);
+    let events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    assert!((state.jettisoned_slag_kg[&body_id] - 100.0).abs() < 1e-3);
+    assert!(!events
+        .iter()
+        .any(|e| matches!(e.event, Event::SlagPollutionWarning { .. })));
+
+    push_slag(&mut state, 100.0);
+    let cmd = jettison_command(&state, &station_id);
+    let events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    assert!((state.jettisoned_slag_kg[&body_id] - 200.0).abs() < 1e-3);
+    let warnings = events
+        .iter()
+        .filter(|e| matches!(e.event, Event::SlagPollutionWarning { .. }))
+        .count();
+    assert_eq!(warnings, 1, "crossing the threshold should warn once");
+
+    push_slag(&mut state, 100.0);
+    let cmd = jettison_command(&state, &station_id);
+    let events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    assert!(
+        !events
+            .iter()
+            .any(|e| matches!(e.event, Event::SlagPollutionWarning { .. })),
+        "already-polluted body should not warn again"
+    );
+}
+
+#[test]
+fn survey_at_polluted_body_yields_less_data() {
+    let content = test_content();
+    let site_body = test_position().parent_body;
+
+    let mut clean = test_state(&content);
+    let mut rng = make_rng();
+    let cmd = survey_command(&clean);
+    tick(&mut clean, &[cmd], &content, &mut rng, None);
+    let clean_amount = survey_data_generated(&tick(&mut clean, &[], &content, &mut rng, None));
+
+    let mut polluted = test_state(&content);
+    polluted
+        .jettisoned_slag_kg
+        .insert(site_body, content.constants.slag_pollution_threshold_kg);
+    let survey_kind = DataKind::new(DataKind::SURVEY);
+    let pooled_before = polluted
+        .research
+        .data_pool
+        .get(&survey_kind)
+        .copied()
+        .unwrap_or(0.0);
+    let mut rng = make_rng();
+    let cmd = survey_command(&polluted);
+    tick(&mut polluted, &[cmd], &content, &mut rng, None);
+    let polluted_amount =
+        survey_data_generated(&tick(&mut polluted, &[], &content, &mut rng, None));
+
+    let expected = clean_amount * content.constants.slag_pollution_scan_data_mult;
+    assert!(
+        (polluted_amount - expected).abs() < 1e-3,
+        "polluted survey should yield {expected}, got {polluted_amount}"
+    );
+    let pooled = polluted.research.data_pool[&survey_kind] - pooled_before;
+    assert!(
+        (pooled - expected).abs() < 1e-3,
+        "data pool should only hold the reduced amount"
+    );
+}
+
+#[test]
+fn arrival_at_polluted_body_can_strike_ship() {
+    let mut content = test_content();
+    content.constants.slag_pollution_hazard_chance = 1.0;
+    content.constants.slag_pollution_hazard_propellant_frac = 0.25;
+    let mut state = test_state(&content);
+    let ship_id = test_ship_id();
+    let destination = test_position();
+    state.ships.get_mut(&ship_id).unwrap().propellant_kg = 1000.0;
+    state.jettisoned_slag_kg.insert(
+        destination.parent_body.clone(),
+        content.constants.slag_pollution_threshold_kg,
+    );
+
+    let mut rng = make_rng();
+    let mut events = Vec::new();
+    crate::tasks::resolve_transit(
+        &mut state,
+        &ship_id,
+        &destination,
+        &TaskKind::Idle,
+        &content,
+        &mut rng,
+        &mut events,
+    );
+
+    assert!((state.ships[&ship_id].propellant_kg - 750.0).abs() < 1e-3);
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::DebrisStrike { propellant_lost_kg, .. } if (*propellant_lost_kg - 250.0).abs() < 1e-3
+    )));
+}
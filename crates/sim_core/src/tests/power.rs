@@ -86,12 +86,17 @@ fn power_budget_solar_only() {
 
 #[test]
 fn power_budget_with_consumer() {
-    let content = solar_array_content();
+    let mut content = solar_array_content();
+    content
+        .module_defs
+        .get_mut("module_basic_iron_refinery")
+        .unwrap()
+        .power_idle_kw = 10.0;
     let mut state = state_with_solar_array(&content);
     let station_id = StationId("station_earth_orbit".to_string());
     let station = state.stations.get_mut(&station_id).unwrap();
 
-    // Add a refinery consuming 10 kW
+    // Add a refinery with a 10 kW standing draw
     station.core.modules.push(ModuleState {
         id: ModuleInstanceId("refinery_inst_0001".to_string()),
         def_id: "module_basic_iron_refinery".to_string(),
@@ -143,7 +148,7 @@ fn power_budget_deficit_when_insufficient() {
             .name("Power Hungry")
             .mass(1000.0)
             .volume(5.0)
-            .power(80.0)
+            .idle_power(80.0)
             .behavior(ModuleBehaviorDef::Processor(ProcessorDef {
                 processing_interval_minutes: 60,
                 processing_interval_ticks: 60,
@@ -196,6 +201,54 @@ fn power_budget_deficit_when_insufficient() {
     );
 }
 
+#[test]
+fn power_budget_books_run_draw_only_on_ticks_that_run() {
+    let mut content = stall_content();
+    let sensor = content.module_defs.get_mut("module_sensor_array").unwrap();
+    sensor.power_idle_kw = 2.0;
+    let mut state = state_with_solar_array(&content);
+    let station_id = StationId("station_earth_orbit".to_string());
+    let station = state.stations.get_mut(&station_id).unwrap();
+    station.core.modules.push(ModuleState {
+        id: ModuleInstanceId("sensor_inst_0001".to_string()),
+        def_id: "module_sensor_array".to_string(),
+        enabled: true,
+        kind_state: ModuleKindState::SensorArray(SensorArrayState {
+            ticks_since_last_run: 119,
+        }),
+        wear: WearState::default(),
+        power_stalled: false,
+        module_priority: 0,
+        assigned_crew: std::collections::BTreeMap::default(),
+        efficiency: 1.0,
+        prev_crew_satisfied: true,
+        thermal: None,
+        slot_index: None,
+    });
+
+    // Scan runs this tick: 2 kW idle + 8 kW run.
+    let mut rng = make_rng();
+    tick(&mut state, &[], &content, &mut rng, None);
+    let power = &state.stations[&station_id].core.power;
+    assert!((power.idle_kw - 2.0).abs() < f32::EPSILON);
+    assert!(
+        (power.active_kw - 8.0).abs() < f32::EPSILON,
+        "active = {}",
+        power.active_kw
+    );
+    assert!((power.consumed_kw - 10.0).abs() < f32::EPSILON);
+
+    // Between scans only the idle draw is booked.
+    tick(&mut state, &[], &content, &mut rng, None);
+    let power = &state.stations[&station_id].core.power;
+    assert!(power.active_kw.abs() < f32::EPSILON);
+    assert!(
+        (power.consumed_kw - 2.0).abs() < f32::EPSILON,
+        "consumed = {}",
+        power.consumed_kw
+    );
+}
+
 #[test]
 fn power_budget_solar_intensity_affects_output() {
     let mut content = solar_array_content();
@@ -347,11 +400,16 @@ fn solar_tech_modifier_does_not_affect_battery() {
 
 #[test]
 fn power_consumption_reduced_by_tech_modifier() {
-    let content = solar_array_content();
+    let mut content = solar_array_content();
+    content
+        .module_defs
+        .get_mut("module_basic_iron_refinery")
+        .unwrap()
+        .power_idle_kw = 10.0;
     let mut state = state_with_solar_array(&content);
     let station_id = StationId("station_earth_orbit".to_string());
 
-    // Add a refinery consuming 10 kW
+    // Add a refinery with a 10 kW standing draw
     let station = state.stations.get_mut(&station_id).unwrap();
     station.core.modules.push(ModuleState {
         id: ModuleInstanceId("refinery_inst_0001".to_string()),
@@ -533,7 +591,7 @@ fn power_stall_lowest_priority_first() {
         enabled: true,
         kind_state: ModuleKindState::Processor(ProcessorState {
             threshold_kg: 0.0,
-            ticks_since_last_run: 0,
+            ticks_since_last_run: 59,
             stalled: false,
             selected_recipe: None,
         }),
@@ -550,7 +608,9 @@ fn power_stall_lowest_priority_first() {
         id: ModuleInstanceId("sensor_inst_0001".to_string()),
         def_id: "module_sensor_array".to_string(),
         enabled: true,
-        kind_state: ModuleKindState::SensorArray(SensorArrayState::default()),
+        kind_state: ModuleKindState::SensorArray(SensorArrayState {
+            ticks_since_last_run: 119,
+        }),
         wear: WearState::default(),
         power_stalled: false,
         module_priority: 0,
@@ -635,7 +695,7 @@ fn power_stall_clears_when_power_restored() {
         enabled: true,
         kind_state: ModuleKindState::Processor(ProcessorState {
             threshold_kg: 0.0,
-            ticks_since_last_run: 0,
+            ticks_since_last_run: 59,
             stalled: false,
             selected_recipe: None,
         }),
@@ -652,7 +712,9 @@ fn power_stall_clears_when_power_restored() {
         id: ModuleInstanceId("sensor_inst_0001".to_string()),
         def_id: "module_sensor_array".to_string(),
         enabled: true,
-        kind_state: ModuleKindState::SensorArray(SensorArrayState::default()),
+        kind_state: ModuleKindState::SensorArray(SensorArrayState {
+            ticks_since_last_run: 119,
+        }),
         wear: WearState::default(),
         power_stalled: false,
         module_priority: 0,
@@ -690,6 +752,161 @@ fn power_stall_clears_when_power_restored() {
     );
 }
 
+/// `state_with_solar_array` plus a 10 kW refinery and an 8 kW sensor, both
+/// due to run this tick: 18 kW of demand against 15 kW of solar.
+fn overloaded_state(content: &GameContent, policy: crate::PowerPolicy) -> GameState {
+    let mut state = state_with_solar_array(content);
+    let station_id = StationId("station_earth_orbit".to_string());
+    let station = state.stations.get_mut(&station_id).unwrap();
+    station.power_policy = policy;
+    station.core.modules.push(ModuleState {
+        id: ModuleInstanceId("refinery_inst_0001".to_string()),
+        def_id: "module_basic_iron_refinery".to_string(),
+        enabled: true,
+        kind_state: ModuleKindState::Processor(ProcessorState {
+            threshold_kg: 0.0,
+            ticks_since_last_run: 59,
+            stalled: false,
+            selected_recipe: None,
+        }),
+        wear: WearState::default(),
+        power_stalled: false,
+        module_priority: 0,
+        assigned_crew: std::collections::BTreeMap::default(),
+        efficiency: 1.0,
+        prev_crew_satisfied: true,
+        thermal: None,
+        slot_index: None,
+    });
+    station.core.modules.push(ModuleState {
+        id: ModuleInstanceId("sensor_inst_0001".to_string()),
+        def_id: "module_sensor_array".to_string(),
+        enabled: true,
+        kind_state: ModuleKindState::SensorArray(SensorArrayState {
+            ticks_since_last_run: 119,
+        }),
+        wear: WearState::default(),
+        power_stalled: false,
+        module_priority: 0,
+        assigned_crew: std::collections::BTreeMap::default(),
+        efficiency: 1.0,
+        prev_crew_satisfied: true,
+        thermal: None,
+        slot_index: None,
+    });
+    state
+}
+
+#[test]
+fn brownout_runs_every_consumer_at_reduced_efficiency() {
+    let content = stall_content();
+    let mut state = overloaded_state(&content, crate::PowerPolicy::Brownout);
+    let station_id = StationId("station_earth_orbit".to_string());
+
+    let mut rng = make_rng();
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+
+    let station = state.stations.get(&station_id).unwrap();
+    // 3 kW short of 18 kW due: every consumer sheds 1/6.
+    for module in &station.core.modules[1..] {
+        assert!(!module.power_stalled, "{} should not stall", module.id.0);
+        assert!(
+            (module.efficiency - 5.0 / 6.0).abs() < 1e-4,
+            "{} efficiency {}",
+            module.id.0,
+            module.efficiency
+        );
+    }
+    assert!((station.core.power.brownout_kw - 3.0).abs() < 1e-3);
+    assert!(events
+        .iter()
+        .any(|e| matches!(e.event, Event::ModuleEfficiencyChanged { .. })));
+}
+
+#[test]
+fn deficit_counts_stalled_run_draw_and_idle_shortfall() {
+    let mut content = stall_content();
+    content
+        .module_defs
+        .get_mut("module_basic_iron_refinery")
+        .unwrap()
+        .power_idle_kw = 20.0;
+    let mut state = overloaded_state(&content, crate::PowerPolicy::Stall);
+    let station_id = StationId("station_earth_orbit".to_string());
+
+    let mut rng = make_rng();
+    tick(&mut state, &[], &content, &mut rng, None);
+
+    let station = state.stations.get(&station_id).unwrap();
+    // 20 kW idle + 18 kW due vs 15 kW solar: both consumers stall, and the
+    // idle draw still leaves 5 kW uncovered.
+    assert!(station.core.modules[1..].iter().all(|m| m.power_stalled));
+    assert!(
+        (station.core.power.stalled_kw - 18.0).abs() < 1e-3,
+        "stalled = {}",
+        station.core.power.stalled_kw
+    );
+    assert!(
+        (station.core.power.deficit_kw - 23.0).abs() < 1e-3,
+        "deficit should be 18 stalled + 5 idle shortfall, got {}",
+        station.core.power.deficit_kw
+    );
+}
+
+#[test]
+fn set_power_policy_switches_from_stall_to_brownout() {
+    let content = stall_content();
+    let mut state = overloaded_state(&content, crate::PowerPolicy::Stall);
+    let station_id = StationId("station_earth_orbit".to_string());
+    let cmd = CommandEnvelope {
+        id: CommandId(0),
+        issued_by: PrincipalId("principal_autopilot".to_string()),
+        issued_tick: 0,
+        execute_at_tick: 0,
+        command: Command::SetPowerPolicy {
+            station_id: station_id.clone(),
+            policy: crate::PowerPolicy::Brownout,
+        },
+    };
+
+    let mut rng = make_rng();
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+
+    let station = state.stations.get(&station_id).unwrap();
+    assert_eq!(station.power_policy, crate::PowerPolicy::Brownout);
+    assert!(station.core.modules.iter().all(|m| !m.power_stalled));
+}
+
+#[test]
+fn deferrable_module_waits_for_spare_power() {
+    let mut content = stall_content();
+    content
+        .module_defs
+        .get_mut("module_sensor_array")
+        .unwrap()
+        .deferrable = true;
+    let mut state = overloaded_state(&content, crate::PowerPolicy::Brownout);
+    let station_id = StationId("station_earth_orbit".to_string());
+
+    let mut rng = make_rng();
+    tick(&mut state, &[], &content, &mut rng, None);
+
+    // 5 kW spare after the refinery: the 8 kW scan waits instead of
+    // browning the refinery out.
+    let station = state.stations.get(&station_id).unwrap();
+    let (refinery, sensor) = (&station.core.modules[1], &station.core.modules[2]);
+    assert!(!refinery.power_stalled);
+    assert!((refinery.efficiency - 1.0).abs() < 1e-6);
+    assert!(sensor.power_stalled);
+    assert!(matches!(
+        sensor.kind_state,
+        ModuleKindState::SensorArray(SensorArrayState { ticks_since_last_run }) if ticks_since_last_run >= 119
+    ));
+    assert!((station.core.power.deferred_kw - 8.0).abs() < 1e-3);
+    assert!(station.core.power.brownout_kw.abs() < 1e-6);
+    assert!(station.core.power.deficit_kw.abs() < 1e-6);
+}
+
 // --- Battery tests ---
 
 fn battery_content() -> GameContent {
@@ -765,7 +982,7 @@ fn battery_discharges_to_cover_deficit() {
             .name("Power Hungry")
             .mass(1000.0)
             .volume(5.0)
-            .power(80.0)
+            .idle_power(80.0)
             .behavior(ModuleBehaviorDef::Processor(ProcessorDef {
                 processing_interval_minutes: 60,
                 processing_interval_ticks: 60,
@@ -849,7 +1066,7 @@ fn battery_discharges_to_cover_deficit() {
 #[test]
 fn battery_partial_discharge_then_stall() {
     let mut content = battery_content();
-    // 80 kW consumer vs 50 kW solar = 30 kW deficit
+    // 80 kW run vs 50 kW solar + 10 kWh battery
     content.module_defs.insert(
         "module_power_hungry".to_string(),
         ModuleDefBuilder::new("module_power_hungry")
@@ -890,7 +1107,7 @@ fn battery_partial_discharge_then_stall() {
         enabled: true,
         kind_state: ModuleKindState::Processor(ProcessorState {
             threshold_kg: 0.0,
-            ticks_since_last_run: 0,
+            ticks_since_last_run: 59,
             stalled: false,
             selected_recipe: None,
         }),
@@ -908,21 +1125,22 @@ fn battery_partial_discharge_then_stall() {
     tick(&mut state, &[], &content, &mut rng, None);
 
     let station = state.stations.get(&station_id).unwrap();
-    // Battery discharges 10 kW (limited by stored charge), deficit = 30 - 10 = 20 kW
+    // Due run needs 80 kW but only 50 solar + 10 battery is available,
+    // so the consumer stalls and its run draw is reported as unmet demand.
     assert!(
-        (station.core.power.battery_discharge_kw - 10.0).abs() < f32::EPSILON,
-        "battery should discharge 10 kW (all it has), got {}",
-        station.core.power.battery_discharge_kw
+        station.core.modules[2].power_stalled,
+        "consumer should be stalled when battery can't fully cover deficit"
     );
     assert!(
-        (station.core.power.deficit_kw - 20.0).abs() < f32::EPSILON,
-        "remaining deficit should be 20 kW, got {}",
+        (station.core.power.deficit_kw - 80.0).abs() < f32::EPSILON,
+        "stalled run draw should be reported as deficit, got {}",
         station.core.power.deficit_kw
     );
-    // Consumer should be stalled since battery can't cover full deficit
+    // The stalled module drew nothing, so the battery was not drained.
     assert!(
-        station.core.modules[2].power_stalled,
-        "consumer should be stalled when battery can't fully cover deficit"
+        station.core.power.battery_discharge_kw.abs() < f32::EPSILON,
+        "battery should not discharge for a stalled run, got {}",
+        station.core.power.battery_discharge_kw
     );
 }
 
@@ -1093,7 +1311,7 @@ fn battery_discharge_rate_unchanged_by_capacity_modifier() {
             .name("Power Hungry")
             .mass(1000.0)
             .volume(5.0)
-            .power(80.0)
+            .idle_power(80.0)
             .behavior(ModuleBehaviorDef::Processor(ProcessorDef {
                 processing_interval_minutes: 60,
                 processing_interval_ticks: 60,
@@ -0,0 +1,22 @@
+use super::{make_rng, test_content, test_ship_id, test_state, test_station_id};
+use crate::prelude::*;
+
+// The prelude alone must be enough to drive a run and read state back.
+
+#[test]
+fn test_prelude_drives_tick_and_accessors() {
+    let content: GameContent = test_content();
+    let mut state: GameState = test_state(&content);
+    let mut rng = make_rng();
+
+    let events: Vec<EventEnvelope> = tick(&mut state, &[], &content, &mut rng, None);
+
+    assert_eq!(state.current_tick(), 1);
+    assert!(events.iter().all(|e| e.tick == 0));
+    assert!(state.ship(&test_ship_id()).is_some());
+    assert!(state.station(&test_station_id()).is_some());
+    assert!(state
+        .asteroid(&AsteroidId("asteroid_missing".to_string()))
+        .is_none());
+    assert!(!state.is_unlocked(&TechId("tech_missing".to_string())));
+}
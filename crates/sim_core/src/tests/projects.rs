@@ -0,0 +1,182 @@
+//! `Command::StartProject` / `CancelProject` — multi-tick station construction.
+
+use super::*;
+
+/// 10-tick cargo expansion drawing 100 kg Fe (10 kg per tick).
+fn project_content() -> GameContent {
+    let mut content = test_content();
+    content.project_defs.insert(
+        "project_cargo".to_string(),
+        crate::ProjectDef {
+            id: "project_cargo".to_string(),
+            name: "Cargo Bay".to_string(),
+            duration_ticks: 10,
+            materials: std::collections::BTreeMap::from([("Fe".to_string(), 100.0)]),
+            outcome: crate::ProjectOutcome::CargoCapacity { m3: 250.0 },
+            required_tech: None,
+            cancel_refund_fraction: 0.5,
+        },
+    );
+    content
+}
+
+fn project_state(content: &GameContent, fe_kg: f32) -> GameState {
+    let mut state = test_state(content);
+    let station = state.stations.get_mut(&test_station_id()).unwrap();
+    station.core.inventory.push(InventoryItem::Material {
+        element: "Fe".to_string(),
+        kg: fe_kg,
+        quality: 0.8,
+        thermal: None,
+    });
+    state
+}
+
+fn command(state: &GameState, command: Command) -> CommandEnvelope {
+    CommandEnvelope {
+        id: CommandId(0),
+        issued_by: PrincipalId("principal_autopilot".to_string()),
+        issued_tick: state.meta.tick,
+        execute_at_tick: state.meta.tick,
+        command,
+    }
+}
+
+fn start(state: &GameState) -> CommandEnvelope {
+    command(
+        state,
+        Command::StartProject {
+            station_id: test_station_id(),
+            project_def_id: "project_cargo".to_string(),
+        },
+    )
+}
+
+fn fe_kg(state: &GameState) -> f32 {
+    state.stations[&test_station_id()]
+        .core
+        .inventory
+        .iter()
+        .filter_map(|item| match item {
+            InventoryItem::Material { element, kg, .. } if element == "Fe" => Some(*kg),
+            _ => None,
+        })
+        .sum()
+}
+
+#[test]
+fn project_draws_materials_and_expands_cargo_on_completion() {
+    let content = project_content();
+    let mut state = project_state(&content, 500.0);
+    let before_m3 = state.stations[&test_station_id()].core.cargo_capacity_m3;
+    let mut rng = make_rng();
+
+    let cmd = start(&state);
+    let mut events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    assert_eq!(state.projects.len(), 1);
+    assert_eq!(state.projects[0].progress_ticks, 1);
+    assert!((fe_kg(&state) - 490.0).abs() < 1e-3);
+
+    for _ in 0..9 {
+        events.extend(tick(&mut state, &[], &content, &mut rng, None));
+    }
+
+    assert!(state.projects.is_empty());
+    assert!((fe_kg(&state) - 400.0).abs() < 1e-3);
+    let station = &state.stations[&test_station_id()];
+    assert!((station.core.cargo_capacity_m3 - (before_m3 + 250.0)).abs() < 1e-3);
+    assert!((station.expansions.cargo_capacity_m3 - 250.0).abs() < 1e-3);
+    assert!(events
+        .iter()
+        .any(|e| matches!(e.event, Event::ProjectStarted { .. })));
+    assert!(events
+        .iter()
+        .any(|e| matches!(e.event, Event::ProjectCompleted { .. })));
+}
+
+#[test]
+fn project_stalls_once_when_short_and_resumes_on_restock() {
+    let content = project_content();
+    let mut state = project_state(&content, 15.0);
+    let mut rng = make_rng();
+
+    let cmd = start(&state);
+    let mut events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    for _ in 0..3 {
+        events.extend(tick(&mut state, &[], &content, &mut rng, None));
+    }
+
+    let stalls = events
+        .iter()
+        .filter(|e| matches!(e.event, Event::ProjectStalled { .. }))
+        .count();
+    assert_eq!(stalls, 1, "stall is reported on the transition only");
+    assert!(state.projects[0].stalled);
+    assert_eq!(state.projects[0].progress_ticks, 1);
+    assert!(
+        (fe_kg(&state) - 5.0).abs() < 1e-3,
+        "stalled ticks draw nothing"
+    );
+
+    let station = state.stations.get_mut(&test_station_id()).unwrap();
+    station.core.inventory.push(InventoryItem::Material {
+        element: "Fe".to_string(),
+        kg: 100.0,
+        quality: 0.8,
+        thermal: None,
+    });
+    tick(&mut state, &[], &content, &mut rng, None);
+    assert!(!state.projects[0].stalled);
+    assert_eq!(state.projects[0].progress_ticks, 2);
+}
+
+#[test]
+fn cancel_refunds_share_of_consumed_materials() {
+    let content = project_content();
+    let mut state = project_state(&content, 500.0);
+    let mut rng = make_rng();
+
+    let cmd = start(&state);
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+    for _ in 0..3 {
+        tick(&mut state, &[], &content, &mut rng, None);
+    }
+    // 40 kg consumed; half comes back.
+    let project_id = state.projects[0].id;
+    let cancel = command(&state, Command::CancelProject { project_id });
+    let events = tick(&mut state, &[cancel], &content, &mut rng, None);
+
+    assert!(state.projects.is_empty());
+    assert!((fe_kg(&state) - 480.0).abs() < 1e-3);
+    let refunded = events.iter().find_map(|e| match e.event {
+        Event::ProjectCancelled { refunded_kg, .. } => Some(refunded_kg),
+        _ => None,
+    });
+    assert!((refunded.unwrap() - 20.0).abs() < 1e-3);
+}
+
+#[test]
+fn second_project_at_same_station_is_rejected() {
+    let content = project_content();
+    let mut state = project_state(&content, 500.0);
+    let mut rng = make_rng();
+
+    let commands = [start(&state), start(&state)];
+    tick(&mut state, &commands, &content, &mut rng, None);
+
+    assert_eq!(state.projects.len(), 1);
+}
+
+#[test]
+fn project_requires_its_tech() {
+    let mut content = project_content();
+    let def = content.project_defs.get_mut("project_cargo").unwrap();
+    def.required_tech = Some(TechId("tech_locked".to_string()));
+    let mut state = project_state(&content, 500.0);
+    let mut rng = make_rng();
+
+    let cmd = start(&state);
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+
+    assert!(state.projects.is_empty());
+}
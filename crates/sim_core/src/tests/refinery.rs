@@ -335,3 +335,62 @@ fn test_storage_pressure_cascade() {
         assert!(ps.stalled, "module should be stalled");
     }
 }
+
+#[test]
+fn why_not_running_lists_every_blocker() {
+    let content = refinery_content();
+    let mut state = state_with_refinery(&content);
+    let station_id = test_station_id();
+    let station = state.stations.get_mut(&station_id).unwrap();
+    station.core.inventory.clear();
+    station.core.power_available_per_tick = 0.0;
+    let module = &mut station.core.modules[0];
+    module.enabled = false;
+    let module_id = module.id.clone();
+
+    let reasons = crate::why_not_running(&state, &content, &station_id, &module_id).unwrap();
+
+    assert_eq!(
+        reasons,
+        vec![
+            IdleReason::Disabled,
+            IdleReason::InsufficientPower {
+                available_kw: 0.0,
+                needed_kw: 10.0,
+            },
+            IdleReason::BelowThreshold {
+                input_kg: 0.0,
+                threshold_kg: 100.0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn idle_refinery_reports_diagnosis_event() {
+    let mut content = refinery_content();
+    content.constants.idle_diagnosis_interval_ticks = 1;
+    let mut state = state_with_refinery(&content);
+    state
+        .stations
+        .get_mut(&test_station_id())
+        .unwrap()
+        .core
+        .inventory
+        .clear();
+    let mut rng = make_rng();
+
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+
+    let reasons = events
+        .iter()
+        .find_map(|e| match &e.event {
+            Event::ModuleIdleDiagnosis { reasons, .. } => Some(reasons),
+            _ => None,
+        })
+        .expect("idle refinery should be diagnosed");
+    assert!(matches!(
+        reasons.as_slice(),
+        [IdleReason::BelowThreshold { .. }]
+    ));
+}
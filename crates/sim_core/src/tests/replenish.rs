@@ -26,6 +26,7 @@ fn replenish_test_content() -> GameContent {
                     scan_site_weight: 1,
                     implicit_comm_tier: None,
                 }),
+                security: 1.0,
             }],
             nodes: vec![NodeDef {
                 id: NodeId("node_test".to_string()),
@@ -55,6 +56,8 @@ fn replenish_test_content() -> GameContent {
             boiloff_rate_per_day_at_293k: None,
             boiling_point_mk: None,
             boiloff_curve: None,
+            volatile: false,
+            quality_decay_per_day: None,
         }],
         module_defs: AHashMap::default(),
         component_defs: vec![],
@@ -67,7 +70,7 @@ fn replenish_test_content() -> GameContent {
         constants: Constants {
             survey_scan_minutes: 1,
             deep_scan_minutes: 1,
-            survey_tag_detection_probability: 1.0,
+            base_sensor_quality: 1.0,
             asteroid_count_per_template: 1,
             asteroid_mass_min_kg: 500.0,
             asteroid_mass_max_kg: 500.0,
@@ -108,6 +111,10 @@ fn replenish_test_content() -> GameContent {
             min_meaningful_kg: 1e-3,
             replenish_batch_size: 5,
             trade_unlock_delay_minutes: 525_600,
+            import_delivery_minutes_per_kg: 0.0,
+            import_cancel_refund_fraction: 0.5,
+            freight_cost_per_kg_hop: 25.0,
+            freight_handling_minutes: 720,
             autopilot_budget_cap_fraction: 0.05,
             autopilot_lh2_abundant_multiplier: 2.0,
             boiloff_hot_offset_mk: 100_000,
@@ -118,12 +125,22 @@ fn replenish_test_content() -> GameContent {
             deposit_ticks: 0,
             station_power_available_per_tick: 0.0,
             refuel_kg_per_tick: 0.0,
+            solar_storm_duration_ticks: 0,
+            claim_duration_ticks: 0,
+            loan_interest_rate_per_tick: 0.0,
+            loan_repayment_interval_ticks: 0,
+            market_update_interval_ticks: 0,
+            contract_offer_interval_ticks: 0,
+            contract_duration_ticks: 0,
+            ship_repair_ticks: 0,
             events_enabled: false,
             event_global_cooldown_ticks: 200,
             event_history_capacity: 100,
             // Propulsion
             fuel_cost_per_au: 500.0,
             reference_mass_kg: 15_000.0,
+            mission_range_propellant_kg_per_hop: 0.0,
+            mission_range_refuse: false,
             refuel_kg_per_minute: 16.67,
             autopilot_refuel_threshold_pct: 0.8,
             autopilot_refuel_max_pct: 0.99,
@@ -139,10 +156,62 @@ fn replenish_test_content() -> GameContent {
             research_domain_rates: std::collections::HashMap::new(),
             research_tier_scaling: Vec::new(),
             research_lab_diminishing_returns: 1.0,
+            slag_pollution_threshold_kg: 100_000.0,
+            slag_pollution_scan_data_mult: 0.75,
+            slag_pollution_hazard_chance: 0.05,
+            slag_pollution_hazard_propellant_frac: 0.1,
+            hotspot_report_interval_ticks: 0,
+            hotspot_report_top_n: 5,
+            solar_storm_chance_per_day: 0.0,
+            solar_storm_duration_minutes: 720,
+            solar_storm_intensity_mult: 0.3,
+            solar_storm_wear_mult: 2.0,
+            solar_storm_deep_scan_interrupt_chance: 0.5,
+            solar_storm_ship_loss_chance: 0.0,
+            claim_cost: 0.0,
+            claim_duration_minutes: 0,
+            starting_loan: 0.0,
+            loan_interest_rate_per_day: 0.0,
+            loan_repayment_interval_minutes: 0,
+            loan_repayment_amount: 0.0,
+            loan_default_after_missed: 3,
+            market_update_interval_minutes: 0,
+            market_volatility: 0.0,
+            market_price_min_mult: 0.25,
+            market_price_max_mult: 4.0,
+            contract_offer_interval_minutes: 0,
+            contract_max_offers: 3,
+            contract_duration_minutes: 10_080,
+            contract_deliver_kg_min: 500.0,
+            contract_deliver_kg_max: 5_000.0,
+            contract_survey_count_max: 5,
+            contract_reward_premium: 1.5,
+            contract_reward_per_survey: 100_000.0,
+            contract_penalty_fraction: 0.25,
+            module_scrap_recovery_fraction: 0.5,
+            module_scrap_element: "Fe".to_string(),
+            module_upgrade_wear_carryover: 0.5,
+            power_deferrable_battery_reserve: 0.5,
+            life_support_failure_efficiency: 0.5,
+            // Ship wear
+            ship_wear_per_mining_tick: 0.0,
+            ship_wear_per_transit_tick: 0.0,
+            ship_repair_minutes: 240,
+            ship_insurance_payout_fraction: 0.0,
+            pirate_encounter_chance: 0.0,
+            pirate_cargo_theft_fraction: 0.5,
+            asteroid_surface_fraction_min: 0.0,
+            asteroid_surface_fraction_max: 0.0,
+            volatile_ore_loss_per_day: 0.0,
+            climate_controlled_decay_mult: 0.25,
+            production_report_interval_ticks: 0,
+            idle_diagnosis_interval_ticks: 0,
+            station_history_capacity: 32,
         },
         alert_rules: Vec::new(),
         events: Vec::new(),
         hulls: std::collections::BTreeMap::new(),
+        ship_classes: std::collections::BTreeMap::new(),
         fitting_templates: std::collections::BTreeMap::new(),
         initial_station: crate::InitialStationDef::default(),
         initial_stations: Vec::new(),
@@ -154,8 +223,13 @@ fn replenish_test_content() -> GameContent {
         rocket_defs: std::collections::BTreeMap::new(),
         satellite_defs: std::collections::BTreeMap::new(),
         frames: std::collections::BTreeMap::new(),
+        adjacency_rules: Vec::new(),
         phase_presets: std::collections::BTreeMap::new(),
+        subsystems: crate::Subsystems::default(),
         density_map: AHashMap::default(),
+        starting_unlocked: Vec::new(),
+        project_defs: std::collections::BTreeMap::new(),
+        belts: Vec::new(),
     };
     content.constants.derive_tick_values();
     content.init_caches();
@@ -191,9 +265,15 @@ fn empty_sites_state(content: &GameContent) -> GameState {
                     module_type_index: crate::ModuleTypeIndex::default(),
                     module_id_index: HashMap::new(),
                     power_budget_cache: crate::PowerBudgetCache::default(),
+                    production_windows: std::collections::BTreeMap::new(),
+                    life_support_failing: false,
                 },
                 leaders: Vec::new(),
                 frame_id: None,
+                owner: PrincipalId("principal_autopilot".to_string()),
+                expansions: crate::StationExpansions::default(),
+                power_policy: crate::PowerPolicy::default(),
+                history: std::collections::VecDeque::new(),
             },
         )]
         .into_iter()
@@ -205,8 +285,10 @@ fn empty_sites_state(content: &GameContent) -> GameState {
             data_pool: AHashMap::default(),
             evidence: AHashMap::default(),
             action_counts: AHashMap::default(),
+            queue: Vec::new(),
         },
         balance: 0.0,
+        loan: crate::LoanState::default(),
         export_revenue_total: 0.0,
         export_count: 0,
         counters: Counters {
@@ -224,7 +306,20 @@ fn empty_sites_state(content: &GameContent) -> GameState {
         transfer_count: 0,
         progression: Default::default(),
         strategy_config: Default::default(),
+        import_orders: Vec::new(),
+        freight_orders: Vec::new(),
+        freight_routes: Vec::new(),
+        market: crate::MarketState::default(),
+        contracts: Vec::new(),
         body_cache: AHashMap::default(),
+        jettisoned_slag_kg: std::collections::BTreeMap::new(),
+        solar_storms: std::collections::BTreeMap::new(),
+        claims: std::collections::BTreeMap::new(),
+        hotspots: crate::HotspotWindow::default(),
+        projects: Vec::new(),
+        station_builds: Vec::new(),
+        belts: std::collections::BTreeMap::new(),
+        pending_commands: Vec::new(),
     }
 }
 
@@ -511,3 +606,62 @@ fn replenish_uses_zone_weighted_positions() {
         );
     }
 }
+
+fn test_belt(initial_budget: u32, max_sites: u32) -> BeltDef {
+    BeltDef {
+        body: BodyId("test_body".to_string()),
+        initial_budget,
+        max_budget: 10,
+        respawn_interval_minutes: 10,
+        respawn_amount: 2,
+        max_sites,
+        templates: Vec::new(),
+    }
+}
+
+#[test]
+fn belt_keeps_max_sites_open_from_its_budget() {
+    let mut content = replenish_test_content();
+    content.belts = vec![test_belt(6, 4)];
+    let mut state = empty_sites_state(&content);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+    tick(&mut state, &[], &content, &mut rng, None);
+    assert_eq!(state.scan_sites.len(), 4, "belt fills to max_sites");
+    assert_eq!(state.belts[&BodyId("test_body".to_string())].budget, 2);
+
+    // Surveying sites frees slots; the pool covers only two of three.
+    state.scan_sites.truncate(1);
+    tick(&mut state, &[], &content, &mut rng, None);
+    assert_eq!(state.scan_sites.len(), 3);
+    assert_eq!(state.belts[&BodyId("test_body".to_string())].budget, 0);
+}
+
+#[test]
+fn belt_budget_respawns_on_its_interval_up_to_max() {
+    let mut content = replenish_test_content();
+    let mut belt = test_belt(0, 7);
+    belt.respawn_amount = 7;
+    content.belts = vec![belt];
+    let mut state = empty_sites_state(&content);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    let body = BodyId("test_body".to_string());
+
+    // Ticks 0..=9: an empty pool spawns nothing.
+    for _ in 0..10 {
+        tick(&mut state, &[], &content, &mut rng, None);
+    }
+    assert!(state.scan_sites.is_empty());
+
+    // Tick 10 refills the pool by 7 and spends it at once.
+    tick(&mut state, &[], &content, &mut rng, None);
+    assert_eq!(state.scan_sites.len(), 7);
+    assert_eq!(state.belts[&body].budget, 0);
+
+    // With the belt full, refills at ticks 20 and 30 bank 14, capped at 10.
+    for _ in 0..20 {
+        tick(&mut state, &[], &content, &mut rng, None);
+    }
+    assert_eq!(state.scan_sites.len(), 7);
+    assert_eq!(state.belts[&body].budget, 10);
+}
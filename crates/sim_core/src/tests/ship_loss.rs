@@ -0,0 +1,121 @@
+use super::*;
+
+fn ship_lost(events: &[EventEnvelope]) -> Option<(&ShipId, ShipLossCause, f64)> {
+    events.iter().find_map(|e| match &e.event {
+        Event::ShipLost {
+            ship_id,
+            cause,
+            insurance_payout,
+            ..
+        } => Some((ship_id, *cause, *insurance_payout)),
+        _ => None,
+    })
+}
+
+/// Test content with an insured hull worth 2M under the test ship.
+fn insured_content() -> GameContent {
+    let mut content = test_content();
+    content.constants.ship_insurance_payout_fraction = 0.5;
+    let hull_id = HullId("hull_general_purpose".to_string());
+    content.hulls.insert(
+        hull_id.clone(),
+        HullDef {
+            id: hull_id,
+            name: "General Purpose".to_string(),
+            mass_kg: 5000.0,
+            cargo_capacity_m3: 20.0,
+            base_speed_ticks_per_au: 2133,
+            base_propellant_capacity_kg: 10000.0,
+            replacement_value: 2_000_000.0,
+            slots: vec![],
+            bonuses: vec![],
+            required_tech: None,
+            tags: vec![],
+            hold_partitions: std::collections::BTreeMap::new(),
+        },
+    );
+    content
+}
+
+fn insured_state(content: &GameContent) -> GameState {
+    let mut state = test_state(content);
+    let ship = state.ships.get_mut(&test_ship_id()).unwrap();
+    crate::commands::recompute_ship_stats(ship, content);
+    state
+}
+
+#[test]
+fn storm_destroys_ship_at_its_body_and_pays_insurance() {
+    let mut content = insured_content();
+    content.constants.solar_storm_chance_per_day = 1e9;
+    content.constants.solar_storm_ship_loss_chance = 1.0;
+    content.constants.derive_tick_values();
+    let mut state = insured_state(&content);
+    let ship_id = test_ship_id();
+    let balance_before = state.balance;
+    let mut rng = make_rng();
+
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+
+    let (lost_id, cause, payout) = ship_lost(&events).expect("ShipLost event");
+    assert_eq!(*lost_id, ship_id);
+    assert_eq!(cause, ShipLossCause::SolarStorm);
+    assert!((payout - 1_000_000.0).abs() < 1e-6);
+    assert!(!state.ships.contains_key(&ship_id));
+    assert!((state.balance - balance_before - payout).abs() < 1e-6);
+}
+
+#[test]
+fn storm_with_zero_loss_chance_spares_ships() {
+    let mut content = insured_content();
+    content.constants.solar_storm_chance_per_day = 1e9;
+    content.constants.derive_tick_values();
+    let mut state = insured_state(&content);
+    let mut rng = make_rng();
+
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+
+    assert!(ship_lost(&events).is_none());
+    assert!(state.ships.contains_key(&test_ship_id()));
+}
+
+#[test]
+fn ship_worn_out_mid_mining_is_lost_and_later_commands_are_ignored() {
+    let mut content = insured_content();
+    content.constants.ship_wear_per_mining_tick = 0.01;
+    // Survey without the hull, whose stats the test ship does not have yet.
+    let (mut state, asteroid_id) = state_with_asteroid(&test_content());
+    let ship_id = test_ship_id();
+    let owner = state.ships[&ship_id].owner.clone();
+    {
+        let ship = state.ships.get_mut(&ship_id).unwrap();
+        crate::commands::recompute_ship_stats(ship, &content);
+        ship.wear.wear = 0.95;
+    }
+    let mut rng = make_rng();
+
+    let cmd = mine_command(&state, &asteroid_id, &content);
+    let mut all_events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    for _ in 0..10 {
+        all_events.extend(tick(&mut state, &[], &content, &mut rng, None));
+    }
+
+    let (_, cause, payout) = ship_lost(&all_events).expect("ShipLost event");
+    assert_eq!(cause, ShipLossCause::HullFailure);
+    assert!((payout - 1_000_000.0).abs() < 1e-6);
+    assert!(!state.ships.contains_key(&ship_id));
+
+    // A command still addressed to the lost ship is dropped.
+    let stale_command = CommandEnvelope {
+        id: CommandId(99),
+        issued_by: owner,
+        issued_tick: state.meta.tick,
+        execute_at_tick: state.meta.tick,
+        command: Command::AssignShipTask {
+            ship_id: ship_id.clone(),
+            task_kind: TaskKind::Idle,
+        },
+    };
+    tick(&mut state, &[stale_command], &content, &mut rng, None);
+    assert!(!state.ships.contains_key(&ship_id));
+}
@@ -0,0 +1,118 @@
+use super::*;
+use crate::test_fixtures::ModuleDefBuilder;
+
+/// Content where a storm starts on the first roll and lasts `duration_minutes`.
+fn stormy_content(duration_minutes: u64) -> GameContent {
+    let mut content = test_content();
+    content.constants.solar_storm_chance_per_day = 1e9;
+    content.constants.solar_storm_duration_minutes = duration_minutes;
+    content.constants.derive_tick_values();
+    content
+}
+
+fn storm_started(events: &[EventEnvelope]) -> Option<&Vec<ShipId>> {
+    events.iter().find_map(|e| match &e.event {
+        Event::StormStarted {
+            interrupted_ships, ..
+        } => Some(interrupted_ships),
+        _ => None,
+    })
+}
+
+#[test]
+fn storm_starts_and_ends_at_body() {
+    let mut content = stormy_content(3);
+    let mut state = test_state(&content);
+    let body_id = test_position().parent_body;
+    let mut rng = make_rng();
+
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+    assert!(storm_started(&events).is_some());
+    assert_eq!(state.solar_storms[&body_id].ends_tick, 3);
+
+    content.constants.solar_storm_chance_per_day = 0.0;
+    tick(&mut state, &[], &content, &mut rng, None);
+    tick(&mut state, &[], &content, &mut rng, None);
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+    assert!(events
+        .iter()
+        .any(|e| matches!(&e.event, Event::StormEnded { body_id: b } if *b == body_id)));
+    assert!(state.solar_storms.is_empty());
+}
+
+#[test]
+fn storm_cuts_solar_output_and_doubles_wear() {
+    let mut content = stormy_content(100);
+    content.module_defs.insert(
+        "module_basic_solar_array".to_string(),
+        ModuleDefBuilder::new("module_basic_solar_array")
+            .wear(0.002)
+            .behavior(ModuleBehaviorDef::SolarArray(SolarArrayDef {
+                base_output_kw: 50.0,
+            }))
+            .build(),
+    );
+    let mut state = test_state(&content);
+    state
+        .stations
+        .get_mut(&test_station_id())
+        .unwrap()
+        .core
+        .modules
+        .push(test_module(
+            "module_basic_solar_array",
+            ModuleKindState::SolarArray(SolarArrayState::default()),
+        ));
+    let mut rng = make_rng();
+
+    tick(&mut state, &[], &content, &mut rng, None);
+
+    let station = &state.stations[&test_station_id()];
+    assert!((station.core.power.generated_kw - 15.0).abs() < 1e-3);
+    assert!((station.core.modules[0].wear.wear - 0.004).abs() < 1e-6);
+}
+
+#[test]
+fn storm_interrupts_deep_scan_at_its_body() {
+    let mut content = stormy_content(100);
+    content.constants.solar_storm_deep_scan_interrupt_chance = 1.0;
+    let mut state = test_state(&content);
+    let mut rng = make_rng();
+    state
+        .research
+        .unlocked
+        .insert(TechId("tech_deep_scan_v1".to_string()));
+
+    content.constants.solar_storm_chance_per_day = 0.0;
+    let cmd = survey_command(&state);
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+    tick(&mut state, &[], &content, &mut rng, None);
+
+    let asteroid_id = state.asteroids.keys().next().unwrap().clone();
+    let ship_id = test_ship_id();
+    let deep_cmd = CommandEnvelope {
+        id: CommandId(0),
+        issued_by: state.ships[&ship_id].owner.clone(),
+        issued_tick: state.meta.tick,
+        execute_at_tick: state.meta.tick,
+        command: Command::AssignShipTask {
+            ship_id: ship_id.clone(),
+            task_kind: TaskKind::DeepScan {
+                asteroid: asteroid_id,
+            },
+        },
+    };
+    tick(&mut state, &[deep_cmd], &content, &mut rng, None);
+
+    content.constants.solar_storm_chance_per_day = 1e9;
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+
+    assert_eq!(storm_started(&events), Some(&vec![ship_id.clone()]));
+    assert!(matches!(
+        state.ships[&ship_id].task.as_ref().map(|t| &t.kind),
+        Some(TaskKind::Idle)
+    ));
+    assert!(!events
+        .iter()
+        .any(|e| matches!(e.event, Event::CompositionMapped { .. })));
+}
@@ -0,0 +1,59 @@
+use super::*;
+use crate::{SimMode, Subsystem, Subsystems};
+
+#[test]
+fn disabled_mining_refuses_mine_tasks_and_stops_replenishing() {
+    let mut content = test_content();
+    content.constants.replenish_check_interval_ticks = 1;
+    content.constants.replenish_target_count = 5;
+    let (mut state, asteroid_id) = state_with_asteroid(&content);
+    content.subsystems.set(Subsystem::Mining, false);
+    state.scan_sites.clear();
+    let mut rng = make_rng();
+
+    let cmd = mine_command(&state, &asteroid_id, &content);
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+
+    let ship = &state.ships[&ShipId("ship_0001".to_string())];
+    assert!(
+        !matches!(
+            ship.task.as_ref().map(|t| &t.kind),
+            Some(TaskKind::Mine { .. })
+        ),
+        "mine task should be refused"
+    );
+    assert!(state.scan_sites.is_empty(), "no scan sites replenished");
+}
+
+#[test]
+fn disabled_industry_skips_processors() {
+    let mut content = refinery_content();
+    content.subsystems = Subsystems::for_mode(SimMode::ResearchOnly);
+    let mut state = state_with_refinery(&content);
+    let mut rng = make_rng();
+
+    tick(&mut state, &[], &content, &mut rng, None);
+    tick(&mut state, &[], &content, &mut rng, None);
+
+    let station = &state.stations[&StationId("station_earth_orbit".to_string())];
+    assert!(
+        !station.core.inventory.iter().any(|i| matches!(
+            i,
+            InventoryItem::Material { .. } | InventoryItem::Slag { .. }
+        )),
+        "refinery should not run with industry disabled"
+    );
+}
+
+#[test]
+fn disabled_research_unlocks_nothing() {
+    let mut content = test_content();
+    content.techs[0].domain_requirements = std::collections::HashMap::new();
+    content.subsystems = Subsystems::for_mode(SimMode::EconomyOnly);
+    let mut state = test_state(&content);
+    let mut rng = make_rng();
+
+    tick(&mut state, &[], &content, &mut rng, None);
+
+    assert!(state.research.unlocked.is_empty());
+}
@@ -162,3 +162,133 @@ fn test_content_has_element_densities() {
         "Fe density should be ~7874 kg/m³"
     );
 }
+
+#[test]
+fn test_survey_rolls_layers_when_enabled() {
+    let mut content = test_content();
+    content.constants.asteroid_surface_fraction_min = 0.2;
+    content.constants.asteroid_surface_fraction_max = 0.3;
+    let (state, asteroid_id) = state_with_asteroid(&content);
+
+    let asteroid = &state.asteroids[&asteroid_id];
+    let layers = asteroid
+        .layers
+        .as_ref()
+        .expect("layered content should roll a surface/core split");
+    assert!((0.2..=0.3).contains(&layers.surface_fraction));
+    assert!((layers.initial_mass_kg - asteroid.mass_kg).abs() < 1e-3);
+    for (element, &bulk) in &layers.bulk() {
+        let truth = asteroid.true_composition[element];
+        assert!(
+            (truth - bulk).abs() < 1e-5,
+            "true composition should be the layer bulk"
+        );
+    }
+}
+
+#[test]
+fn test_survey_without_layering_keeps_uniform_rock() {
+    let content = test_content();
+    let (state, asteroid_id) = state_with_asteroid(&content);
+    assert!(state.asteroids[&asteroid_id].layers.is_none());
+}
+
+fn resurvey_command(state: &GameState, asteroid_id: &AsteroidId) -> CommandEnvelope {
+    let ship = &state.ships[&test_ship_id()];
+    CommandEnvelope {
+        id: CommandId(0),
+        issued_by: ship.owner.clone(),
+        issued_tick: state.meta.tick,
+        execute_at_tick: state.meta.tick,
+        command: Command::AssignShipTask {
+            ship_id: test_ship_id(),
+            task_kind: TaskKind::Resurvey {
+                asteroid: asteroid_id.clone(),
+            },
+        },
+    }
+}
+
+#[test]
+fn test_resurvey_refines_existing_tag_belief() {
+    let content = test_content();
+    let (mut state, asteroid_id) = state_with_asteroid(&content);
+    state
+        .asteroids
+        .get_mut(&asteroid_id)
+        .unwrap()
+        .knowledge
+        .tag_beliefs = vec![(AnomalyTag::new("IronRich"), 0.6)];
+    let mut rng = make_rng();
+
+    let cmd = resurvey_command(&state, &asteroid_id);
+    let mut events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    events.extend(tick(&mut state, &[], &content, &mut rng, None));
+
+    // A perfect sensor that sees the tag leaves no doubt.
+    let beliefs = &state.asteroids[&asteroid_id].knowledge.tag_beliefs;
+    assert_eq!(beliefs.len(), 1);
+    assert!((beliefs[0].1 - 1.0).abs() < 1e-6);
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::TaskCompleted { task_kind, .. } if task_kind == "Resurvey"
+    )));
+    assert!(events
+        .iter()
+        .any(|e| matches!(e.event, Event::ScanResult { .. })));
+    assert!(
+        !events
+            .iter()
+            .any(|e| matches!(e.event, Event::DataGenerated { .. })),
+        "a resurvey should not generate survey data"
+    );
+}
+
+#[test]
+fn test_sensor_quality_stacks_ship_and_tech_modifiers() {
+    let mut content = test_content();
+    content.constants.base_sensor_quality = 0.8;
+    let mut state = test_state(&content);
+    let hull_bonus = |value: f64| crate::modifiers::Modifier {
+        stat: crate::modifiers::StatId::SensorQuality,
+        op: crate::modifiers::ModifierOp::Flat,
+        value,
+        source: crate::modifiers::ModifierSource::Hull(HullId("hull_test".to_string())),
+        condition: None,
+    };
+    state
+        .ships
+        .get_mut(&test_ship_id())
+        .unwrap()
+        .modifiers
+        .add(hull_bonus(0.1));
+    let mut tech_bonus = hull_bonus(0.05);
+    tech_bonus.source = crate::modifiers::ModifierSource::Tech("tech_test".to_string());
+    state.modifiers.add(tech_bonus);
+
+    let ship = &state.ships[&test_ship_id()];
+    let quality = sensor_quality(ship, &state.modifiers, &content);
+    assert!((quality - 0.95).abs() < 1e-6);
+
+    // Quality is a probability, whatever the modifiers add up to.
+    state
+        .ships
+        .get_mut(&test_ship_id())
+        .unwrap()
+        .modifiers
+        .add(hull_bonus(0.5));
+    let ship = &state.ships[&test_ship_id()];
+    assert!((sensor_quality(ship, &state.modifiers, &content) - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_survey_with_blind_sensor_detects_nothing() {
+    let mut content = test_content();
+    content.constants.base_sensor_quality = 0.0;
+    let (state, asteroid_id) = state_with_asteroid(&content);
+
+    assert!(state.asteroids[&asteroid_id]
+        .knowledge
+        .tag_beliefs
+        .is_empty());
+}
@@ -152,7 +152,7 @@ fn thermal_constants_deserialize_from_json() {
     let json = r#"{
         "survey_scan_minutes": 1,
         "deep_scan_minutes": 1,
-        "survey_tag_detection_probability": 1.0,
+        "base_sensor_quality": 1.0,
         "asteroid_count_per_template": 1,
         "asteroid_mass_min_kg": 500.0,
         "asteroid_mass_max_kg": 500.0,
@@ -193,7 +193,7 @@ fn thermal_constants_default_when_missing_from_json() {
     let json = r#"{
         "survey_scan_minutes": 1,
         "deep_scan_minutes": 1,
-        "survey_tag_detection_probability": 1.0,
+        "base_sensor_quality": 1.0,
         "asteroid_count_per_template": 1,
         "asteroid_mass_min_kg": 500.0,
         "asteroid_mass_max_kg": 500.0,
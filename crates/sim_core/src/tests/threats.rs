@@ -0,0 +1,194 @@
+//! Pirate raids on arriving ships and `TaskKind::Escort`.
+
+use super::*;
+
+/// Test content where every cargo-carrying arrival at the test body meets
+/// pirates.
+fn lawless_content() -> GameContent {
+    let mut content = test_content();
+    content.constants.pirate_encounter_chance = 1.0;
+    content.constants.pirate_cargo_theft_fraction = 0.5;
+    content.solar_system.bodies[0].security = 0.0;
+    content
+}
+
+fn loaded_state(content: &GameContent) -> GameState {
+    let mut state = test_state(content);
+    state
+        .ships
+        .get_mut(&test_ship_id())
+        .unwrap()
+        .inventory
+        .push(InventoryItem::Material {
+            element: "Fe".to_string(),
+            kg: 100.0,
+            quality: 0.7,
+            thermal: None,
+        });
+    state
+}
+
+fn add_escort(state: &mut GameState) -> ShipId {
+    let escort_id = ShipId("ship_0002".to_string());
+    let mut escort = state.ships[&test_ship_id()].clone();
+    escort.id = escort_id.clone();
+    escort.inventory.clear();
+    state.ships.insert(escort_id.clone(), escort);
+    escort_id
+}
+
+fn assign(state: &GameState, ship_id: &ShipId, task_kind: TaskKind) -> CommandEnvelope {
+    CommandEnvelope {
+        id: CommandId(0),
+        issued_by: state.ships[ship_id].owner.clone(),
+        issued_tick: state.meta.tick,
+        execute_at_tick: state.meta.tick,
+        command: Command::AssignShipTask {
+            ship_id: ship_id.clone(),
+            task_kind,
+        },
+    }
+}
+
+fn transit_home() -> TaskKind {
+    TaskKind::Transit {
+        destination: test_position(),
+        total_ticks: 2,
+        then: Box::new(TaskKind::Idle),
+    }
+}
+
+fn cargo_kg(ship: &ShipState) -> f32 {
+    ship.inventory.iter().map(InventoryItem::mass_kg).sum()
+}
+
+#[test]
+fn unescorted_arrival_at_lawless_body_loses_cargo() {
+    let content = lawless_content();
+    let mut state = loaded_state(&content);
+    let ship_id = test_ship_id();
+    let mut rng = make_rng();
+
+    let cmd = assign(&state, &ship_id, transit_home());
+    let mut events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    for _ in 0..3 {
+        events.extend(tick(&mut state, &[], &content, &mut rng, None));
+    }
+
+    let stolen = events.iter().find_map(|e| match &e.event {
+        Event::CargoStolen { kg_stolen, .. } => Some(*kg_stolen),
+        _ => None,
+    });
+    assert_eq!(stolen, Some(50.0));
+    assert!((cargo_kg(&state.ships[&ship_id]) - 50.0).abs() < 1e-3);
+}
+
+#[cfg(feature = "fixed-point")]
+#[test]
+fn stolen_cargo_stays_on_the_mass_grid() {
+    let mut content = lawless_content();
+    content.constants.pirate_cargo_theft_fraction = 1.0 / 3.0;
+    let mut state = loaded_state(&content);
+    let ship_id = test_ship_id();
+    let mut rng = make_rng();
+
+    let cmd = assign(&state, &ship_id, transit_home());
+    let mut events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    for _ in 0..3 {
+        events.extend(tick(&mut state, &[], &content, &mut rng, None));
+    }
+
+    assert!(events
+        .iter()
+        .any(|e| matches!(e.event, Event::CargoStolen { .. })));
+    for item in &state.ships[&ship_id].inventory {
+        let kg = item.mass_kg();
+        let mut requantized = kg;
+        crate::fixed::add_kg(&mut requantized, 0.0);
+        assert_eq!(requantized.to_bits(), kg.to_bits(), "{kg} is off the grid");
+    }
+}
+
+#[test]
+fn escort_repels_raid_and_arrives_with_its_charge() {
+    let content = lawless_content();
+    let mut state = loaded_state(&content);
+    let ship_id = test_ship_id();
+    let escort_id = add_escort(&mut state);
+    let mut rng = make_rng();
+
+    let escort_cmd = assign(
+        &state,
+        &escort_id,
+        TaskKind::Escort {
+            ship: ship_id.clone(),
+        },
+    );
+    let transit_cmd = assign(&state, &ship_id, transit_home());
+    let mut events = tick(
+        &mut state,
+        &[escort_cmd, transit_cmd],
+        &content,
+        &mut rng,
+        None,
+    );
+    for _ in 0..3 {
+        events.extend(tick(&mut state, &[], &content, &mut rng, None));
+    }
+
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::ThreatRepelled { escorts, .. } if *escorts == vec![escort_id.clone()]
+    )));
+    assert!(!events
+        .iter()
+        .any(|e| matches!(e.event, Event::CargoStolen { .. })));
+    assert!((cargo_kg(&state.ships[&ship_id]) - 100.0).abs() < 1e-3);
+    assert!(matches!(
+        state.ships[&escort_id].task.as_ref().map(|t| &t.kind),
+        Some(TaskKind::Idle)
+    ));
+}
+
+#[test]
+fn secure_body_never_raids() {
+    let mut content = lawless_content();
+    content.solar_system.bodies[0].security = 1.0;
+    let mut state = loaded_state(&content);
+    let ship_id = test_ship_id();
+    let mut rng = make_rng();
+
+    let cmd = assign(&state, &ship_id, transit_home());
+    let mut events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    for _ in 0..3 {
+        events.extend(tick(&mut state, &[], &content, &mut rng, None));
+    }
+
+    assert!(!events
+        .iter()
+        .any(|e| matches!(e.event, Event::CargoStolen { .. })));
+    assert!((cargo_kg(&state.ships[&ship_id]) - 100.0).abs() < 1e-3);
+}
+
+#[test]
+fn escort_requires_a_colocated_charge() {
+    let content = lawless_content();
+    let mut state = loaded_state(&content);
+    let ship_id = test_ship_id();
+    let escort_id = add_escort(&mut state);
+    state
+        .ships
+        .get_mut(&escort_id)
+        .unwrap()
+        .position
+        .parent_body = BodyId("elsewhere".to_string());
+    let mut rng = make_rng();
+
+    let cmd = assign(&state, &escort_id, TaskKind::Escort { ship: ship_id });
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+
+    assert!(!matches!(
+        state.ships[&escort_id].task.as_ref().map(|t| &t.kind),
+        Some(TaskKind::Escort { .. })
+    ));
+}
@@ -676,3 +676,294 @@ fn export_rejected_without_export_tier() {
         "should not emit ItemExported before trade unlock"
     );
 }
+
+// ---- Import delivery latency ----
+
+fn station_fe_kg(state: &GameState, station_id: &StationId) -> f32 {
+    state.stations[station_id]
+        .core
+        .inventory
+        .iter()
+        .filter_map(|item| match item {
+            InventoryItem::Material { element, kg, .. } if element == "Fe" => Some(*kg),
+            _ => None,
+        })
+        .sum()
+}
+
+#[test]
+fn delayed_import_charges_now_and_delivers_on_arrival() {
+    let mut content = trade_content();
+    // base_content runs at 1 minute per tick: 100 kg * 0.05 = 5 ticks in transit.
+    content.constants.import_delivery_minutes_per_kg = 0.05;
+    let mut state = trade_state(&content);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    let station_id = StationId("station_earth_orbit".to_string());
+
+    let cmd = make_command(Command::Import {
+        facility_id: station_id.clone().into(),
+        item_spec: TradeItemSpec::Material {
+            element: "Fe".to_string(),
+            kg: 100.0,
+        },
+    });
+    let expected_cost = 50.0 * 100.0 + 100.0 * 100.0;
+
+    let events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    assert!((state.balance - (10_000_000.0 - expected_cost)).abs() < 0.01);
+    assert!(
+        station_fe_kg(&state, &station_id) < f32::EPSILON,
+        "goods should not land before arrival"
+    );
+    assert_eq!(state.import_orders.len(), 1);
+    assert_eq!(state.import_orders[0].arrival_tick, 5);
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::ImportOrderPlaced {
+            arrival_tick: 5,
+            ..
+        }
+    )));
+    assert!(!events
+        .iter()
+        .any(|e| matches!(&e.event, Event::ItemImported { .. })));
+
+    for _ in 1..5 {
+        tick(&mut state, &[], &content, &mut rng, None);
+    }
+    assert!(station_fe_kg(&state, &station_id) < f32::EPSILON);
+
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+    assert!((station_fe_kg(&state, &station_id) - 100.0).abs() < 0.01);
+    assert!(state.import_orders.is_empty());
+    assert!(events
+        .iter()
+        .any(|e| matches!(&e.event, Event::ImportDelivered { .. })));
+}
+
+#[test]
+fn delayed_import_reserves_station_capacity() {
+    let mut content = trade_content();
+    content.constants.import_delivery_minutes_per_kg = 0.05;
+    let mut state = trade_state(&content);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    let station_id = StationId("station_earth_orbit".to_string());
+    // Room for one 5 m3 module, not two.
+    state
+        .stations
+        .get_mut(&station_id)
+        .unwrap()
+        .core
+        .cargo_capacity_m3 = 8.0;
+
+    let import = || {
+        make_command(Command::Import {
+            facility_id: station_id.clone().into(),
+            item_spec: TradeItemSpec::Module {
+                module_def_id: "module_basic_iron_refinery".to_string(),
+            },
+        })
+    };
+    tick(&mut state, &[import(), import()], &content, &mut rng, None);
+
+    assert_eq!(
+        state.import_orders.len(),
+        1,
+        "second order should be rejected for space held by the first"
+    );
+}
+
+#[test]
+fn cancel_import_order_refunds_fraction_and_drops_goods() {
+    let mut content = trade_content();
+    content.constants.import_delivery_minutes_per_kg = 0.05;
+    content.constants.import_cancel_refund_fraction = 0.5;
+    let mut state = trade_state(&content);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    let station_id = StationId("station_earth_orbit".to_string());
+
+    let cmd = make_command(Command::Import {
+        facility_id: station_id.clone().into(),
+        item_spec: TradeItemSpec::Material {
+            element: "Fe".to_string(),
+            kg: 100.0,
+        },
+    });
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+    let order_id = state.import_orders[0].id;
+    let cost = state.import_orders[0].cost;
+    let balance_after_order = state.balance;
+
+    let cancel = CommandEnvelope {
+        id: CommandId(1),
+        issued_by: PrincipalId("principal_autopilot".to_string()),
+        issued_tick: state.meta.tick,
+        execute_at_tick: state.meta.tick,
+        command: Command::CancelImportOrder { order_id },
+    };
+    let events = tick(&mut state, &[cancel], &content, &mut rng, None);
+
+    assert!((state.balance - (balance_after_order + cost * 0.5)).abs() < 0.01);
+    assert!(state.import_orders.is_empty());
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::ImportOrderCancelled { refund, .. } if (*refund - cost * 0.5).abs() < 0.01
+    )));
+
+    for _ in 0..10 {
+        tick(&mut state, &[], &content, &mut rng, None);
+    }
+    assert!(
+        station_fe_kg(&state, &station_id) < f32::EPSILON,
+        "cancelled goods should never arrive"
+    );
+}
+
+#[test]
+fn only_the_issuer_may_cancel_an_import_order() {
+    let mut content = market_content();
+    content.constants.import_delivery_minutes_per_kg = 0.05;
+    let mut state = trade_state(&content);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+    tick(&mut state, &[import_fe(100.0)], &content, &mut rng, None);
+    let order_id = state.import_orders[0].id;
+    assert_eq!(state.import_orders[0].issued_by.0, "principal_autopilot");
+    assert!((state.market.items["Fe"].net_volume - 100.0).abs() < 1e-9);
+
+    let cancel = |issued_by: &str, id: u64, tick: u64| CommandEnvelope {
+        id: CommandId(id),
+        issued_by: PrincipalId(issued_by.to_string()),
+        issued_tick: tick,
+        execute_at_tick: tick,
+        command: Command::CancelImportOrder { order_id },
+    };
+    let events = tick(
+        &mut state,
+        &[cancel("principal_rival", 1, 1)],
+        &content,
+        &mut rng,
+        None,
+    );
+    assert_eq!(state.import_orders.len(), 1);
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::CommandRejected { command_id: CommandId(1), reason }
+            if reason.contains("not placed by principal_rival")
+    )));
+
+    tick(
+        &mut state,
+        &[cancel("principal_autopilot", 2, 2)],
+        &content,
+        &mut rng,
+        None,
+    );
+    assert!(state.import_orders.is_empty());
+    assert!(
+        state.market.items["Fe"].net_volume.abs() < 1e-9,
+        "a cancelled order no longer counts as traded volume"
+    );
+}
+
+// ---- Market tests ----
+
+/// Trade content with the market repricing every 2 ticks and Fe at
+/// elasticity 0.001 (100 kg of net imports lifts the price by ~10.5%).
+fn market_content() -> GameContent {
+    let mut content = trade_content();
+    content.constants.market_update_interval_ticks = 2;
+    content.pricing.items.get_mut("Fe").unwrap().elasticity = 0.001;
+    content
+}
+
+fn import_fe(kg: f32) -> CommandEnvelope {
+    make_command(Command::Import {
+        facility_id: StationId("station_earth_orbit".to_string()).into(),
+        item_spec: TradeItemSpec::Material {
+            element: "Fe".to_string(),
+            kg,
+        },
+    })
+}
+
+#[test]
+fn imports_raise_the_market_price_on_the_next_update() {
+    let content = market_content();
+    let mut state = trade_state(&content);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+    tick(&mut state, &[import_fe(100.0)], &content, &mut rng, None);
+    assert!((state.market.items["Fe"].net_volume - 100.0).abs() < 1e-9);
+    assert!(
+        (state.market.multiplier("Fe") - 1.0).abs() < 1e-9,
+        "not repriced yet"
+    );
+
+    tick(&mut state, &[], &content, &mut rng, None);
+    let events = tick(&mut state, &[], &content, &mut rng, None);
+    let expected = 50.0 * 0.1_f64.exp();
+    assert!(events.iter().any(|e| matches!(
+        &e.event,
+        Event::PriceChanged { item_id, old_price, new_price }
+            if item_id == "Fe" && (*old_price - 50.0).abs() < 1e-9
+                && (*new_price - expected).abs() < 1e-9
+    )));
+    assert!(
+        !events.iter().any(|e| matches!(
+            &e.event,
+            Event::PriceChanged { item_id, .. } if item_id != "Fe"
+        )),
+        "items without elasticity or noise keep their price"
+    );
+
+    let balance_before = state.balance;
+    let mut cmd = import_fe(100.0);
+    cmd.execute_at_tick = state.meta.tick;
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+    let expected_cost = expected * 100.0 + 100.0 * 100.0;
+    assert!(
+        (balance_before - state.balance - expected_cost).abs() < 0.01,
+        "import should pay the market price"
+    );
+}
+
+#[test]
+fn market_noise_is_seeded_and_bounded() {
+    let mut content = trade_content();
+    content.constants.market_update_interval_ticks = 1;
+    content.constants.market_volatility = 0.5;
+    content.constants.market_price_max_mult = 1.5;
+
+    let run = |seed: u64| {
+        let mut state = trade_state(&content);
+        state.meta.seed = seed;
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        for _ in 0..50 {
+            tick(&mut state, &[], &content, &mut rng, None);
+        }
+        state.market
+    };
+    let first = run(7);
+    assert_eq!(first, run(7), "same seed, same prices");
+    assert_ne!(first, run(8), "different seed, different walk");
+    for item in first.items.values() {
+        assert!((0.25..=1.5).contains(&item.multiplier));
+    }
+}
+
+#[test]
+fn disabled_market_keeps_base_prices() {
+    let mut content = trade_content();
+    content.pricing.items.get_mut("Fe").unwrap().elasticity = 0.001;
+    let mut state = trade_state(&content);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+
+    let balance_before = state.balance;
+    tick(&mut state, &[import_fe(100.0)], &content, &mut rng, None);
+    for _ in 0..5 {
+        tick(&mut state, &[], &content, &mut rng, None);
+    }
+    assert!(state.market.items.is_empty());
+    assert!((balance_before - state.balance - 15_000.0).abs() < 0.01);
+}
@@ -84,6 +84,8 @@ fn economy_content() -> GameContent {
             bonuses: vec![],
             required_tech: None,
             tags: vec![],
+            hold_partitions: std::collections::BTreeMap::new(),
+            replacement_value: 0.0,
         },
     );
 
@@ -103,6 +105,9 @@ fn economy_content() -> GameContent {
         ],
         outputs: vec![OutputSpec::Ship {
             hull_id: HullId("hull_test_ship".to_string()),
+            class_id: None,
+            role: None,
+            owner: ShipOwnerRule::StationOwner,
         }],
         efficiency: 1.0,
         thermal_req: None,
@@ -122,6 +127,7 @@ fn economy_content() -> GameContent {
                 assembly_interval_ticks: 2,
                 recipes: vec![recipe_id],
                 max_stock: HashMap::new(),
+                parallel_slots: 1,
             }))
             .build(),
     )]
@@ -339,19 +345,18 @@ fn economy_full_loop() {
             enabled: true,
         },
     );
-    tick(&mut state, &[cmd_enable], &content, &mut rng, None);
-
-    // Tick forward enough for the assembler interval (2 ticks) without tech
+    // The idle shipyard tries to start a job on the enable tick itself.
     let ships_before = state.ships.len();
+    let mut events = tick(&mut state, &[cmd_enable], &content, &mut rng, None);
     let mut saw_awaiting_tech = false;
     for _ in 0..4 {
-        let events = tick(&mut state, &[], &content, &mut rng, None);
         if events
             .iter()
             .any(|e| matches!(&e.event, Event::ModuleAwaitingTech { .. }))
         {
             saw_awaiting_tech = true;
         }
+        events = tick(&mut state, &[], &content, &mut rng, None);
     }
 
     assert!(
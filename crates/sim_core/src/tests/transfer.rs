@@ -77,9 +77,15 @@ fn two_station_state(content: &GameContent) -> GameState {
                 module_type_index: ModuleTypeIndex::default(),
                 module_id_index: std::collections::HashMap::new(),
                 power_budget_cache: PowerBudgetCache::default(),
+                production_windows: std::collections::BTreeMap::new(),
+                life_support_failing: false,
             },
             leaders: Vec::new(),
             frame_id: None,
+            owner: PrincipalId("principal_autopilot".to_string()),
+            expansions: crate::StationExpansions::default(),
+            power_policy: crate::PowerPolicy::default(),
+            history: std::collections::VecDeque::new(),
         },
     );
     state
@@ -113,6 +119,8 @@ fn spatial_two_station_state() -> (GameContent, GameState) {
             bonuses: vec![],
             required_tech: None,
             tags: vec![],
+            hold_partitions: std::collections::BTreeMap::new(),
+            replacement_value: 0.0,
         },
     );
     content.solar_system.bodies = vec![
@@ -125,6 +133,7 @@ fn spatial_two_station_state() -> (GameContent, GameState) {
             angle_mdeg: 0,
             solar_intensity: 1.0,
             zone: None,
+            security: 1.0,
         },
         crate::OrbitalBodyDef {
             id: crate::BodyId("zone_b".to_string()),
@@ -135,6 +144,7 @@ fn spatial_two_station_state() -> (GameContent, GameState) {
             angle_mdeg: 0,
             solar_intensity: 1.0,
             zone: None,
+            security: 1.0,
         },
     ];
     content.constants.derive_tick_values();
@@ -175,9 +185,15 @@ fn spatial_two_station_state() -> (GameContent, GameState) {
                 module_type_index: ModuleTypeIndex::default(),
                 module_id_index: std::collections::HashMap::new(),
                 power_budget_cache: PowerBudgetCache::default(),
+                production_windows: std::collections::BTreeMap::new(),
+                life_support_failing: false,
             },
             leaders: Vec::new(),
             frame_id: None,
+            owner: PrincipalId("principal_autopilot".to_string()),
+            expansions: crate::StationExpansions::default(),
+            power_policy: crate::PowerPolicy::default(),
+            history: std::collections::VecDeque::new(),
         },
     );
 
@@ -413,6 +429,7 @@ fn transfer_moves_module_between_stations() {
         .push(InventoryItem::Module {
             item_id: ModuleItemId("module_item_0001".to_string()),
             module_def_id: "module_basic_iron_refinery".to_string(),
+            wear: 0.0,
         });
 
     let cmd = transfer_command(
@@ -515,6 +532,87 @@ fn transfer_respects_ship_cargo_capacity() {
     );
 }
 
+#[test]
+fn pickup_fills_each_hold_partition_separately() {
+    let mut content = transfer_content();
+    content.hulls.insert(
+        crate::HullId("hull_general_purpose".to_string()),
+        crate::HullDef {
+            id: crate::HullId("hull_general_purpose".to_string()),
+            name: "General Purpose".to_string(),
+            mass_kg: 5000.0,
+            cargo_capacity_m3: 10.0,
+            base_speed_ticks_per_au: 2133,
+            base_propellant_capacity_kg: 10_000.0,
+            slots: vec![],
+            bonuses: vec![],
+            required_tech: None,
+            tags: vec![],
+            hold_partitions: [(crate::HoldPartition::ComponentLocker, 3.0)].into(),
+            replacement_value: 0.0,
+        },
+    );
+    let mut state = two_station_state(&content);
+    let mut rng = make_rng();
+
+    // Kits alone would fill an undivided 10 m^3 hold (1 m^3 each).
+    let ship = state.ships.get_mut(&test_ship_id()).unwrap();
+    crate::commands::recompute_ship_stats(ship, &content);
+    let earth_id = StationId("station_earth_orbit".to_string());
+    let mars_id = StationId("station_mars_orbit".to_string());
+    let earth = &mut state.stations.get_mut(&earth_id).unwrap().core.inventory;
+    earth.push(InventoryItem::Component {
+        component_id: ComponentId("repair_kit".to_string()),
+        count: 10,
+        quality: 1.0,
+    });
+    earth.push(InventoryItem::Material {
+        element: "Fe".to_string(),
+        kg: 100_000.0,
+        quality: 0.9,
+        thermal: None,
+    });
+
+    let cmd = transfer_command(
+        &state,
+        "station_earth_orbit",
+        "station_mars_orbit",
+        vec![
+            TradeItemSpec::Component {
+                component_id: ComponentId("repair_kit".to_string()),
+                count: 10,
+            },
+            TradeItemSpec::Material {
+                element: "Fe".to_string(),
+                kg: 100_000.0,
+            },
+        ],
+    );
+    tick(&mut state, &[cmd], &content, &mut rng, None);
+    for _ in 0..10 {
+        tick(&mut state, &[], &content, &mut rng, None);
+    }
+
+    let mars = &state.stations[&mars_id].core.inventory;
+    let kits: u32 = mars
+        .iter()
+        .filter_map(|i| match i {
+            InventoryItem::Component { count, .. } => Some(*count),
+            _ => None,
+        })
+        .sum();
+    let fe_kg: f32 = mars
+        .iter()
+        .filter_map(|i| match i {
+            InventoryItem::Material { element, kg, .. } if element == "Fe" => Some(*kg),
+            _ => None,
+        })
+        .sum();
+    assert_eq!(kits, 3, "the 3 m^3 locker takes 3 kits");
+    // The other 7 m^3 are left to bulk cargo: 7 * 7874 kg/m^3.
+    assert!((fe_kg - 55_118.0).abs() < 5.0, "got {fe_kg} kg Fe");
+}
+
 #[test]
 fn transfer_same_station_is_rejected() {
     let content = transfer_content();
@@ -73,6 +73,10 @@ fn transit_moves_ship_and_starts_next_task() {
                 crew: Default::default(),
                 leaders: Vec::new(),
                 home_station: None,
+                name: String::new(),
+                class_id: None,
+                role: None,
+                wear: crate::WearState::default(),
             },
         )]
         .into_iter()
@@ -95,9 +99,15 @@ fn transit_moves_ship_and_starts_next_task() {
                     module_type_index: crate::ModuleTypeIndex::default(),
                     module_id_index: HashMap::new(),
                     power_budget_cache: crate::PowerBudgetCache::default(),
+                    production_windows: std::collections::BTreeMap::new(),
+                    life_support_failing: false,
                 },
                 leaders: Vec::new(),
                 frame_id: None,
+                owner: PrincipalId("principal_autopilot".to_string()),
+                expansions: crate::StationExpansions::default(),
+                power_policy: crate::PowerPolicy::default(),
+                history: std::collections::VecDeque::new(),
             },
         )]
         .into_iter()
@@ -109,8 +119,10 @@ fn transit_moves_ship_and_starts_next_task() {
             data_pool: AHashMap::default(),
             evidence: AHashMap::default(),
             action_counts: AHashMap::default(),
+            queue: Vec::new(),
         },
         balance: 0.0,
+        loan: crate::LoanState::default(),
         export_revenue_total: 0.0,
         export_count: 0,
         counters: Counters {
@@ -128,7 +140,20 @@ fn transit_moves_ship_and_starts_next_task() {
         transfer_count: 0,
         progression: Default::default(),
         strategy_config: Default::default(),
+        import_orders: Vec::new(),
+        freight_orders: Vec::new(),
+        freight_routes: Vec::new(),
+        market: crate::MarketState::default(),
+        contracts: Vec::new(),
         body_cache: AHashMap::default(),
+        jettisoned_slag_kg: std::collections::BTreeMap::new(),
+        solar_storms: std::collections::BTreeMap::new(),
+        claims: std::collections::BTreeMap::new(),
+        hotspots: crate::HotspotWindow::default(),
+        projects: Vec::new(),
+        station_builds: Vec::new(),
+        belts: std::collections::BTreeMap::new(),
+        pending_commands: Vec::new(),
     };
 
     let mut rng = ChaCha8Rng::seed_from_u64(0);
@@ -281,6 +306,10 @@ fn transit_generates_transit_data_with_diminishing_returns() {
                 crew: Default::default(),
                 leaders: Vec::new(),
                 home_station: None,
+                name: String::new(),
+                class_id: None,
+                role: None,
+                wear: crate::WearState::default(),
             },
         )]
         .into_iter()
@@ -303,9 +332,15 @@ fn transit_generates_transit_data_with_diminishing_returns() {
                     module_type_index: crate::ModuleTypeIndex::default(),
                     module_id_index: HashMap::new(),
                     power_budget_cache: crate::PowerBudgetCache::default(),
+                    production_windows: std::collections::BTreeMap::new(),
+                    life_support_failing: false,
                 },
                 leaders: Vec::new(),
                 frame_id: None,
+                owner: PrincipalId("principal_autopilot".to_string()),
+                expansions: crate::StationExpansions::default(),
+                power_policy: crate::PowerPolicy::default(),
+                history: std::collections::VecDeque::new(),
             },
         )]
         .into_iter()
@@ -317,8 +352,10 @@ fn transit_generates_transit_data_with_diminishing_returns() {
             data_pool: AHashMap::default(),
             evidence: AHashMap::default(),
             action_counts: AHashMap::default(),
+            queue: Vec::new(),
         },
         balance: 0.0,
+        loan: crate::LoanState::default(),
         export_revenue_total: 0.0,
         export_count: 0,
         counters: Counters {
@@ -336,7 +373,20 @@ fn transit_generates_transit_data_with_diminishing_returns() {
         transfer_count: 0,
         progression: Default::default(),
         strategy_config: Default::default(),
+        import_orders: Vec::new(),
+        freight_orders: Vec::new(),
+        freight_routes: Vec::new(),
+        market: crate::MarketState::default(),
+        contracts: Vec::new(),
         body_cache: AHashMap::default(),
+        jettisoned_slag_kg: std::collections::BTreeMap::new(),
+        solar_storms: std::collections::BTreeMap::new(),
+        claims: std::collections::BTreeMap::new(),
+        hotspots: crate::HotspotWindow::default(),
+        projects: Vec::new(),
+        station_builds: Vec::new(),
+        belts: std::collections::BTreeMap::new(),
+        pending_commands: Vec::new(),
     };
 
     let mut rng = ChaCha8Rng::seed_from_u64(42);
@@ -434,6 +484,10 @@ fn ship_ticks_per_au_uses_per_ship_override() {
         crew: Default::default(),
         leaders: Vec::new(),
         home_station: None,
+        name: String::new(),
+        class_id: None,
+        role: None,
+        wear: crate::WearState::default(),
     };
     let ship_fast = ShipState {
         id: ShipId("ship_fast".to_string()),
@@ -451,6 +505,10 @@ fn ship_ticks_per_au_uses_per_ship_override() {
         crew: Default::default(),
         leaders: Vec::new(),
         home_station: None,
+        name: String::new(),
+        class_id: None,
+        role: None,
+        wear: crate::WearState::default(),
     };
     let ship_slow = ShipState {
         id: ShipId("ship_slow".to_string()),
@@ -468,6 +526,10 @@ fn ship_ticks_per_au_uses_per_ship_override() {
         crew: Default::default(),
         leaders: Vec::new(),
         home_station: None,
+        name: String::new(),
+        class_id: None,
+        role: None,
+        wear: crate::WearState::default(),
     };
 
     let global = 2133;
@@ -528,6 +590,8 @@ fn spatial_transit_setup() -> (GameContent, GameState) {
             bonuses: vec![],
             required_tech: None,
             tags: vec![],
+            hold_partitions: std::collections::BTreeMap::new(),
+            replacement_value: 0.0,
         },
     );
     // Add two zone bodies so we have spatial positions
@@ -541,6 +605,7 @@ fn spatial_transit_setup() -> (GameContent, GameState) {
             angle_mdeg: 0,
             solar_intensity: 1.0,
             zone: None,
+            security: 1.0,
         },
         crate::OrbitalBodyDef {
             id: crate::BodyId("zone_b".to_string()),
@@ -551,6 +616,7 @@ fn spatial_transit_setup() -> (GameContent, GameState) {
             angle_mdeg: 0,
             solar_intensity: 1.0,
             zone: None,
+            security: 1.0,
         },
     ];
     content.constants.derive_tick_values();
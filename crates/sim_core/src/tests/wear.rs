@@ -423,3 +423,96 @@ fn test_maintenance_repairs_above_threshold() {
         "kits should be consumed when wear exceeds threshold"
     );
 }
+
+fn ore_kg(inventory: &[InventoryItem]) -> f32 {
+    inventory
+        .iter()
+        .filter_map(|item| match item {
+            InventoryItem::Ore { kg, .. } => Some(*kg),
+            _ => None,
+        })
+        .sum()
+}
+
+fn mine_once(content: &GameContent, ship_wear: f32) -> (f32, f32) {
+    let (mut state, asteroid_id) = state_with_asteroid(content);
+    let ship_id = test_ship_id();
+    state.ships.get_mut(&ship_id).unwrap().wear.wear = ship_wear;
+    let mut rng = make_rng();
+
+    let cmd = mine_command(&state, &asteroid_id, content);
+    tick(&mut state, &[cmd], content, &mut rng, None);
+    for _ in 0..10 {
+        tick(&mut state, &[], content, &mut rng, None);
+    }
+    let ship = &state.ships[&ship_id];
+    (ore_kg(&ship.inventory), ship.wear.wear)
+}
+
+#[test]
+fn test_mining_wears_ship_and_worn_ship_yields_less() {
+    let mut content = test_content();
+    content.constants.ship_wear_per_mining_tick = 0.01;
+
+    let (fresh_kg, fresh_wear_after) = mine_once(&content, 0.0);
+    let (worn_kg, _) = mine_once(&content, 0.6);
+
+    assert!(
+        (fresh_wear_after - 0.1).abs() < 1e-5,
+        "10 mining ticks at 0.01 should leave 0.1 wear, got {fresh_wear_after}"
+    );
+    assert!(fresh_kg > 0.0);
+    assert!(
+        (worn_kg - fresh_kg * 0.75).abs() < 1.0,
+        "degraded ship should bring back 75% of {fresh_kg} kg, got {worn_kg}"
+    );
+}
+
+#[test]
+fn test_dock_for_repair_spends_station_repair_kits() {
+    let mut content = maintenance_content();
+    content.constants.ship_repair_ticks = 2;
+    let mut state = state_with_maintenance(&content);
+    let ship_id = test_ship_id();
+    let station_id = test_station_id();
+    state.ships.get_mut(&ship_id).unwrap().wear.wear = 0.5;
+    // Keep the refinery idle so the bay has no module wear to spend kits on.
+    state.stations.get_mut(&station_id).unwrap().core.modules[0].enabled = false;
+
+    let cmd = CommandEnvelope {
+        id: CommandId(0),
+        issued_by: state.ships[&ship_id].owner.clone(),
+        issued_tick: state.meta.tick,
+        execute_at_tick: state.meta.tick,
+        command: Command::AssignShipTask {
+            ship_id: ship_id.clone(),
+            task_kind: TaskKind::DockForRepair {
+                station: station_id.clone(),
+            },
+        },
+    };
+    let mut rng = make_rng();
+    let mut events = tick(&mut state, &[cmd], &content, &mut rng, None);
+    for _ in 0..2 {
+        events.extend(tick(&mut state, &[], &content, &mut rng, None));
+    }
+
+    assert!(state.ships[&ship_id].wear.wear.abs() < 1e-5);
+    assert!(events.iter().any(|e| matches!(
+        e.event,
+        Event::ShipRepaired {
+            repair_kits_used: 3,
+            ..
+        }
+    )));
+    let kits: u32 = state.stations[&station_id]
+        .core
+        .inventory
+        .iter()
+        .filter_map(|item| match item {
+            InventoryItem::Component { count, .. } => Some(*count),
+            _ => None,
+        })
+        .sum();
+    assert_eq!(kits, 2, "0.5 wear at 0.2 per kit should use 3 of 5 kits");
+}
@@ -475,6 +475,8 @@ mod tests {
             boiloff_rate_per_day_at_293k: None,
             boiling_point_mk: None,
             boiloff_curve: None,
+            volatile: false,
+            quality_decay_per_day: None,
         }
     }
 
@@ -602,6 +604,8 @@ mod tests {
             boiloff_rate_per_day_at_293k: None,
             boiling_point_mk: None,
             boiloff_curve: None,
+            volatile: false,
+            quality_decay_per_day: None,
         };
         let mut props = solid_fe_props(1_000_000);
         update_phase(&mut props, &ore, 100.0, 10_000_000);
@@ -0,0 +1,145 @@
+//! Pirate raids on arriving ships, and escorts that fend them off.
+//!
+//! A ship carrying cargo that arrives at a body meets pirates with
+//! `pirate_encounter_chance × (1 − security)`. If any ship is escorting it
+//! (`TaskKind::Escort`), the raid is repelled; otherwise pirates take
+//! `pirate_cargo_theft_fraction` of each ore and material lot in the hold.
+//! Escorts travel with their charge and go idle when it arrives. A chance
+//! of 0, or a fully secure body, draws nothing from the RNG.
+
+use rand::Rng;
+
+use crate::{
+    BodyId, Event, EventEnvelope, GameContent, GameState, InventoryItem, ShipId, TaskKind,
+};
+
+/// Ships escorting `ship_id`, in id order.
+fn escorts_of(state: &GameState, ship_id: &ShipId) -> Vec<ShipId> {
+    state
+        .ships
+        .values()
+        .filter(|ship| {
+            matches!(
+                ship.task.as_ref().map(|task| &task.kind),
+                Some(TaskKind::Escort { ship }) if ship == ship_id
+            )
+        })
+        .map(|ship| ship.id.clone())
+        .collect()
+}
+
+/// Roll for a pirate encounter on a ship that just arrived at `body_id`.
+pub(crate) fn roll_pirate_encounter(
+    state: &mut GameState,
+    ship_id: &ShipId,
+    body_id: &BodyId,
+    content: &GameContent,
+    rng: &mut impl Rng,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let security = content
+        .solar_system
+        .bodies
+        .iter()
+        .find(|body| body.id == *body_id)
+        .map_or(1.0, |body| body.security);
+    let chance = content.constants.pirate_encounter_chance * (1.0 - security);
+    if chance <= 0.0 {
+        return;
+    }
+    let carrying_cargo = state
+        .ships
+        .get(ship_id)
+        .is_some_and(|ship| !ship.inventory.is_empty());
+    if !carrying_cargo || rng.gen::<f32>() >= chance {
+        return;
+    }
+
+    let current_tick = state.meta.tick;
+    let escorts = escorts_of(state, ship_id);
+    if !escorts.is_empty() {
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            Event::ThreatRepelled {
+                ship_id: ship_id.clone(),
+                body_id: body_id.clone(),
+                escorts,
+            },
+        ));
+        return;
+    }
+
+    let Some(ship) = state.ships.get_mut(ship_id) else {
+        return;
+    };
+    let fraction = content
+        .constants
+        .pirate_cargo_theft_fraction
+        .clamp(0.0, 1.0);
+    let mut kg_stolen = 0.0;
+    for item in &mut ship.inventory {
+        if let InventoryItem::Ore { kg, .. } | InventoryItem::Material { kg, .. } = item {
+            let before = *kg;
+            crate::fixed::add_kg(kg, -before * fraction);
+            kg_stolen += before - *kg;
+        }
+    }
+    ship.inventory.retain(|item| match item {
+        InventoryItem::Ore { kg, .. } | InventoryItem::Material { kg, .. } => *kg > 0.0,
+        _ => true,
+    });
+    events.push(crate::emit(
+        &mut state.counters,
+        current_tick,
+        Event::CargoStolen {
+            ship_id: ship_id.clone(),
+            body_id: body_id.clone(),
+            kg_stolen,
+        },
+    ));
+}
+
+/// Bring `ship_id`'s escorts along to its arrival position and idle them.
+pub(crate) fn arrive_escorts(
+    state: &mut GameState,
+    ship_id: &ShipId,
+    destination: &crate::Position,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let current_tick = state.meta.tick;
+    for escort_id in escorts_of(state, ship_id) {
+        if let Some(escort) = state.ships.get_mut(&escort_id) {
+            escort.position = destination.clone();
+        }
+        crate::tasks::set_ship_idle(state, &escort_id, current_tick);
+        events.push(crate::emit(
+            &mut state.counters,
+            current_tick,
+            Event::TaskCompleted {
+                ship_id: escort_id,
+                task_kind: "Escort".to_string(),
+                target: Some(ship_id.0.clone()),
+            },
+        ));
+    }
+}
+
+/// Idle escorts whose charge no longer exists.
+pub(crate) fn release_orphaned_escorts(state: &mut GameState) {
+    let current_tick = state.meta.tick;
+    let orphaned: Vec<ShipId> = state
+        .ships
+        .values()
+        .filter(|ship| {
+            matches!(
+                ship.task.as_ref().map(|task| &task.kind),
+                Some(TaskKind::Escort { ship }) if !state.ships.contains_key(ship)
+            )
+        })
+        .map(|ship| ship.id.clone())
+        .collect();
+    for escort_id in orphaned {
+        crate::tasks::set_ship_idle(state, &escort_id, current_tick);
+    }
+}
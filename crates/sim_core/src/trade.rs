@@ -1,44 +1,88 @@
 //! Trade helpers for import/export pricing and inventory manipulation.
 
 use crate::composition::blend_thermal;
-use crate::{GameContent, InventoryItem, ModuleItemId, PricingEntry, PricingTable, TradeItemSpec};
+use crate::{
+    AbsolutePos, Constants, GameContent, InventoryItem, MarketState, ModuleItemId, PricingEntry,
+    TradeItemSpec,
+};
 use rand::Rng;
 
-/// Compute the import cost for a trade item.
+/// Compute the import cost for a trade item at current market prices.
 /// Returns `None` if pricing entry not found, item not importable, or mass can't be computed.
 pub fn compute_import_cost(
     item_spec: &TradeItemSpec,
-    pricing: &PricingTable,
+    market: &MarketState,
     content: &GameContent,
 ) -> Option<f64> {
+    let pricing = &content.pricing;
     let entry: &PricingEntry = pricing.items.get(item_spec.pricing_key())?;
     if !entry.importable {
         return None;
     }
     let mass = item_spec.compute_mass(content)?;
-    let cost =
-        entry.base_price_per_unit * item_spec.quantity() + mass * pricing.import_surcharge_per_kg;
+    let unit_price = entry.base_price_per_unit * market.multiplier(item_spec.pricing_key());
+    let cost = unit_price * item_spec.quantity() + mass * pricing.import_surcharge_per_kg;
     Some(cost)
 }
 
-/// Compute the export revenue for a trade item.
+/// Compute the export revenue for a trade item at current market prices.
 /// Returns `None` if pricing entry not found, item not exportable, or mass can't be computed.
 pub fn compute_export_revenue(
     item_spec: &TradeItemSpec,
-    pricing: &PricingTable,
+    market: &MarketState,
     content: &GameContent,
 ) -> Option<f64> {
+    let pricing = &content.pricing;
     let entry: &PricingEntry = pricing.items.get(item_spec.pricing_key())?;
     if !entry.exportable {
         return None;
     }
     let mass = item_spec.compute_mass(content)?;
-    let revenue = (entry.base_price_per_unit * item_spec.quantity()
-        - mass * pricing.export_surcharge_per_kg)
-        .max(0.0);
+    let unit_price = entry.base_price_per_unit * market.multiplier(item_spec.pricing_key());
+    let revenue =
+        (unit_price * item_spec.quantity() - mass * pricing.export_surcharge_per_kg).max(0.0);
     Some(revenue)
 }
 
+/// Delivery time in ticks for a station import, proportional to item mass.
+/// Returns 0 (instant delivery) when `import_delivery_minutes_per_kg` is 0.
+pub fn import_delivery_ticks(item_spec: &TradeItemSpec, content: &GameContent) -> u64 {
+    let mass = item_spec.compute_mass(content).unwrap_or(0.0);
+    let minutes = (mass * content.constants.import_delivery_minutes_per_kg).ceil();
+    if minutes <= 0.0 {
+        return 0;
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // positive, ceil'd
+    content.constants.game_minutes_to_ticks(minutes as u64)
+}
+
+/// Number of freight hops between two points: one per started AU, minimum 1.
+pub fn freight_hops(from: AbsolutePos, to: AbsolutePos) -> u64 {
+    from.distance(to).div_ceil(crate::MICRO_AU_PER_AU).max(1)
+}
+
+/// Freight service price for shipping `mass_kg` between two points.
+pub fn compute_freight_cost(
+    mass_kg: f64,
+    from: AbsolutePos,
+    to: AbsolutePos,
+    constants: &Constants,
+) -> f64 {
+    mass_kg * constants.freight_cost_per_kg_hop * freight_hops(from, to) as f64
+}
+
+/// Freight delivery time in ticks: fixed handling time plus transit at the
+/// reference `ticks_per_au` speed.
+pub fn freight_delivery_ticks(from: AbsolutePos, to: AbsolutePos, constants: &Constants) -> u64 {
+    constants.game_minutes_to_ticks(constants.freight_handling_minutes)
+        + crate::travel_ticks(
+            from,
+            to,
+            constants.ticks_per_au,
+            constants.min_transit_ticks,
+        )
+}
+
 /// Create inventory items for an import operation.
 /// For modules, generates a unique `ModuleItemId` using the RNG.
 pub fn create_inventory_items(item_spec: &TradeItemSpec, rng: &mut impl Rng) -> Vec<InventoryItem> {
@@ -66,6 +110,7 @@ pub fn create_inventory_items(item_spec: &TradeItemSpec, rng: &mut impl Rng) ->
             vec![InventoryItem::Module {
                 item_id: ModuleItemId(format!("module_item_{uuid}")),
                 module_def_id: module_def_id.clone(),
+                wear: 0.0,
             }]
         }
         TradeItemSpec::Crew { .. } => {
@@ -137,7 +182,7 @@ pub fn remove_inventory_items(
                 {
                     if el == element {
                         let take = item_kg.min(remaining);
-                        *item_kg -= take;
+                        crate::fixed::add_kg(item_kg, -take);
                         remaining -= take;
                     }
                 }
@@ -219,7 +264,7 @@ pub fn merge_into_inventory(inventory: &mut Vec<InventoryItem>, new_items: Vec<I
                         thermal.as_ref(),
                         *kg,
                     );
-                    *existing_kg += kg;
+                    crate::fixed::add_kg(existing_kg, *kg);
                     *existing_thermal = blended;
                 } else {
                     inventory.push(new_item);
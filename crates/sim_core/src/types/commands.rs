@@ -3,15 +3,24 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    CommandId, ComponentId, CrewRole, FacilityId, GroundFacilityId, LaunchPayload, ModuleDefId,
-    ModuleInstanceId, ModuleItemId, Position, PrincipalId, RecipeId, ShipId, StationId, TaskKind,
-    TechId, TradeItemSpec,
+    BodyId, CommandId, ComponentId, CrewRole, FacilityId, GroundFacilityId, LaunchPayload,
+    ModuleDefId, ModuleInstanceId, ModuleItemId, Position, PrincipalId, RecipeId, ShipId,
+    StationId, TaskKind, TechId, TradeItemSpec,
 };
 
 // ---------------------------------------------------------------------------
 // Command types
 // ---------------------------------------------------------------------------
 
+/// Version of the `Command` JSON shape accepted by external clients.
+///
+/// Renaming a variant or field is not a breaking change: keep the old name
+/// as a `#[serde(alias = ...)]` and add a case to
+/// `tests/fixtures/commands/legacy_aliases.json`. Bump this only when an old
+/// payload can no longer be read, and freeze the previous fixture file
+/// alongside a new `vN.json`.
+pub const COMMAND_SCHEMA_VERSION: u32 = 1;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommandEnvelope {
     pub id: CommandId,
@@ -55,6 +64,18 @@ pub enum Command {
         facility_id: FacilityId,
         module_id: ModuleInstanceId,
     },
+    /// Destroy an installed module, recovering part of its mass as
+    /// material. Stations only.
+    ScrapModule {
+        facility_id: FacilityId,
+        module_id: ModuleInstanceId,
+    },
+    /// Replace an installed module with its def's `upgrade_to` tier,
+    /// consuming the upgrade's materials and components. Stations only.
+    UpgradeModule {
+        facility_id: FacilityId,
+        module_id: ModuleInstanceId,
+    },
     SetModuleEnabled {
         facility_id: FacilityId,
         module_id: ModuleInstanceId,
@@ -84,6 +105,46 @@ pub enum Command {
         facility_id: FacilityId,
         item_spec: TradeItemSpec,
     },
+    /// Cancel an in-transit station import. Refunds
+    /// `import_cancel_refund_fraction` of the paid cost; the goods are lost.
+    CancelImportOrder {
+        order_id: crate::ImportOrderId,
+    },
+    /// Ship goods between two stations with the paid NPC freight service.
+    /// Charged `freight_cost_per_kg_hop` per kg per hop; delivered after
+    /// handling time plus reference-speed transit.
+    ScheduleFreight {
+        from_station: StationId,
+        to_station: StationId,
+        item_spec: TradeItemSpec,
+    },
+    /// Haul `item_spec` from `from_station` to `to_station` with the
+    /// issuer's own idle ships, at most once every `interval_ticks`. See
+    /// `freight_routes.rs`.
+    CreateFreightRoute {
+        from_station: StationId,
+        to_station: StationId,
+        item_spec: TradeItemSpec,
+        interval_ticks: u64,
+    },
+    /// Start a `ProjectDef` at a station. One project per station at a time.
+    StartProject {
+        station_id: StationId,
+        project_def_id: String,
+    },
+    /// Abandon a project, refunding its `cancel_refund_fraction` of the
+    /// materials consumed so far.
+    CancelProject {
+        project_id: crate::ProjectId,
+    },
+    /// Spend a station kit from a station's own inventory to found a new
+    /// station at `position`. The kit is delivered and assembled without a
+    /// ship; the new station appears once the build completes.
+    BuildStation {
+        station_id: StationId,
+        kit_component_id: crate::ComponentId,
+        position: Position,
+    },
     JettisonSlag {
         station_id: StationId,
     },
@@ -92,12 +153,25 @@ pub enum Command {
         module_id: ModuleInstanceId,
         recipe_id: RecipeId,
     },
+    /// Order `count` runs of `recipe_id` from an assembler, after any
+    /// orders already queued.
+    QueueAssemblerJob {
+        station_id: StationId,
+        module_id: ModuleInstanceId,
+        recipe_id: RecipeId,
+        count: u32,
+    },
     #[serde(alias = "SetManufacturingPriority")]
     SetModulePriority {
         station_id: StationId,
         module_id: ModuleInstanceId,
         priority: u32,
     },
+    /// Choose how the station's modules share a power shortfall.
+    SetPowerPolicy {
+        station_id: StationId,
+        policy: crate::PowerPolicy,
+    },
     FitShipModule {
         ship_id: ShipId,
         slot_index: usize,
@@ -162,6 +236,13 @@ pub enum Command {
     SetStrategyConfig {
         config: crate::StrategyConfig,
     },
+    /// Replace `ResearchState.queue`. Each lab works on the first queued
+    /// tech whose prereqs are met and that still needs the lab's domain,
+    /// falling back to its assigned tech. Unknown and unlocked ids are
+    /// dropped; an empty list clears the queue.
+    SetResearchQueue {
+        tech_ids: Vec<TechId>,
+    },
     /// VIO-595: Move inventory items from one orbital station to another
     /// via a ship. The command handler builds a chained task
     /// `Transit(src) → Pickup → Transit(dst) → Deposit` and assigns it
@@ -175,4 +256,117 @@ pub enum Command {
         to_station: StationId,
         items: Vec<TradeItemSpec>,
     },
+    /// Claim exclusive mining rights at a body for `issued_by`. See
+    /// `claims.rs` for cost, contest and expiry rules.
+    ClaimBody {
+        body_id: BodyId,
+    },
+    /// Give up `issued_by`'s claim on a body. No refund.
+    ReleaseClaim {
+        body_id: BodyId,
+    },
+    /// Drop a command `issued_by` scheduled for a later tick from
+    /// `GameState.pending_commands`. Ignored once the command has run.
+    CancelCommand {
+        command_id: CommandId,
+    },
+    /// Take a contract on offer for `issued_by`. See `contracts.rs`.
+    AcceptContract {
+        contract_id: crate::ContractId,
+    },
+    /// Give up a contract `issued_by` holds, paying its penalty.
+    AbandonContract {
+        contract_id: crate::ContractId,
+    },
+    /// Move selected cargo between a ship and a co-located station or
+    /// ship, without a full `Deposit`. The ship spends `deposit_ticks` on
+    /// a `TransferCargo` task; whatever fits at the receiving end moves.
+    TransferCargo {
+        ship_id: ShipId,
+        counterparty: crate::CargoHolder,
+        direction: crate::CargoDirection,
+        items: Vec<crate::CargoSpec>,
+    },
+}
+
+impl Command {
+    /// The ship this command acts on, if any. The sim only applies these
+    /// commands when `issued_by` owns the ship.
+    pub fn ship_id(&self) -> Option<&ShipId> {
+        match self {
+            Self::AssignShipTask { ship_id, .. }
+            | Self::DeployStation { ship_id, .. }
+            | Self::FitShipModule { ship_id, .. }
+            | Self::UnfitShipModule { ship_id, .. }
+            | Self::TransferItems { ship_id, .. }
+            | Self::TransferCargo { ship_id, .. } => Some(ship_id),
+            _ => None,
+        }
+    }
+
+    /// The stations this command names directly. Orders and projects
+    /// referenced by id belong to a station too; look those up in state.
+    pub fn station_ids(&self) -> Vec<&StationId> {
+        match self {
+            Self::InstallModule { facility_id, .. }
+            | Self::UninstallModule { facility_id, .. }
+            | Self::ScrapModule { facility_id, .. }
+            | Self::UpgradeModule { facility_id, .. }
+            | Self::SetModuleEnabled { facility_id, .. }
+            | Self::SetModuleThreshold { facility_id, .. }
+            | Self::Import { facility_id, .. }
+            | Self::Export { facility_id, .. } => match facility_id {
+                FacilityId::Station(station_id) => vec![station_id],
+                FacilityId::Ground(_) => Vec::new(),
+            },
+            Self::ScheduleFreight {
+                from_station,
+                to_station,
+                ..
+            }
+            | Self::CreateFreightRoute {
+                from_station,
+                to_station,
+                ..
+            }
+            | Self::TransferItems {
+                from_station,
+                to_station,
+                ..
+            } => vec![from_station, to_station],
+            Self::AssignLabTech { station_id, .. }
+            | Self::SetAssemblerCap { station_id, .. }
+            | Self::StartProject { station_id, .. }
+            | Self::BuildStation { station_id, .. }
+            | Self::JettisonSlag { station_id }
+            | Self::SelectRecipe { station_id, .. }
+            | Self::QueueAssemblerJob { station_id, .. }
+            | Self::SetModulePriority { station_id, .. }
+            | Self::SetPowerPolicy { station_id, .. }
+            | Self::FitShipModule { station_id, .. }
+            | Self::UnfitShipModule { station_id, .. }
+            | Self::AssignCrew { station_id, .. }
+            | Self::UnassignCrew { station_id, .. }
+            | Self::CreateThermalLink { station_id, .. }
+            | Self::RemoveThermalLink { station_id, .. }
+            | Self::TransferMolten { station_id, .. }
+            | Self::DeploySatellite { station_id, .. } => vec![station_id],
+            Self::TransferCargo { counterparty, .. } => match counterparty {
+                crate::CargoHolder::Station(station_id) => vec![station_id],
+                crate::CargoHolder::Ship(_) => Vec::new(),
+            },
+            Self::AssignShipTask { .. }
+            | Self::DeployStation { .. }
+            | Self::CancelImportOrder { .. }
+            | Self::CancelProject { .. }
+            | Self::Launch { .. }
+            | Self::SetStrategyConfig { .. }
+            | Self::SetResearchQueue { .. }
+            | Self::ClaimBody { .. }
+            | Self::ReleaseClaim { .. }
+            | Self::CancelCommand { .. }
+            | Self::AcceptContract { .. }
+            | Self::AbandonContract { .. } => Vec::new(),
+        }
+    }
 }
@@ -13,7 +13,9 @@ pub struct Constants {
     // -- Game-time fields (deserialized from JSON) --
     pub survey_scan_minutes: u64,
     pub deep_scan_minutes: u64,
-    pub survey_tag_detection_probability: f32,
+    /// Survey sensor quality before hull, module and tech `SensorQuality`
+    /// modifiers: the chance a survey detects a tag the asteroid carries.
+    pub base_sensor_quality: f32,
     pub asteroid_count_per_template: u32,
     pub asteroid_mass_min_kg: f32,
     pub asteroid_mass_max_kg: f32,
@@ -105,6 +107,18 @@ pub struct Constants {
     /// Trade (import/export) unlocks after this many game-minutes (default: 1 year = 525,600).
     #[serde(default = "default_trade_unlock_delay_minutes")]
     pub trade_unlock_delay_minutes: u64,
+    /// Game-minutes of delivery time per kg of imported goods. 0 = imports arrive instantly.
+    #[serde(default)]
+    pub import_delivery_minutes_per_kg: f64,
+    /// Fraction of an import's cost refunded when the order is cancelled in transit.
+    #[serde(default = "default_import_cancel_refund_fraction")]
+    pub import_cancel_refund_fraction: f64,
+    /// Freight service price per kg per hop (one hop per started AU of distance, minimum 1).
+    #[serde(default = "default_freight_cost_per_kg_hop")]
+    pub freight_cost_per_kg_hop: f64,
+    /// Fixed freight loading/unloading time in game-minutes, added to transit time.
+    #[serde(default = "default_freight_handling_minutes")]
+    pub freight_handling_minutes: u64,
     /// Autopilot won't spend more than this fraction of balance on a single import.
     #[serde(default = "default_autopilot_budget_cap_fraction")]
     pub autopilot_budget_cap_fraction: f64,
@@ -114,6 +128,22 @@ pub struct Constants {
     /// Temperature offset above ambient for the "hot" boiloff multiplier zone (milli-Kelvin).
     #[serde(default = "default_boiloff_hot_offset_mk")]
     pub boiloff_hot_offset_mk: u32,
+    /// Fraction of an ore lot's volatile-element mass lost per day outside
+    /// refrigerated storage. 0 disables spoilage.
+    #[serde(default)]
+    pub volatile_ore_loss_per_day: f64,
+    /// Multiplier on material quality decay at a station with an enabled
+    /// climate-controlled storage module.
+    #[serde(default = "default_climate_controlled_decay_mult")]
+    pub climate_controlled_decay_mult: f64,
+    /// Ticks folded into one `ProductionReport` per processor/assembler.
+    /// 0 disables reports.
+    #[serde(default)]
+    pub production_report_interval_ticks: u64,
+    /// Ticks between `ModuleIdleDiagnosis` reports for blocked processors.
+    /// 0 disables them.
+    #[serde(default)]
+    pub idle_diagnosis_interval_ticks: u64,
     // Sim events system
     /// Whether the sim events system is enabled.
     #[serde(default = "default_events_enabled")]
@@ -121,6 +151,10 @@ pub struct Constants {
     /// Global cooldown between any two sim events (ticks).
     #[serde(default = "default_event_global_cooldown_ticks")]
     pub event_global_cooldown_ticks: u64,
+    /// Significant events kept per station in `StationState.history`.
+    /// 0 disables station history.
+    #[serde(default = "default_station_history_capacity")]
+    pub station_history_capacity: usize,
     /// Maximum number of fired events to keep in history ring buffer.
     #[serde(default = "default_event_history_capacity")]
     pub event_history_capacity: usize,
@@ -131,6 +165,15 @@ pub struct Constants {
     /// Reference ship mass (kg) for fuel cost scaling. Ships heavier than this burn more.
     #[serde(default = "default_reference_mass_kg")]
     pub reference_mass_kg: f32,
+    /// Propellant capacity (kg) per body hop of mission range; a ship's
+    /// range is `propellant_capacity_kg / this`, counted from the nearest
+    /// station its owner has. 0 disables range limits.
+    #[serde(default)]
+    pub mission_range_propellant_kg_per_hop: f32,
+    /// Refuse task assignments beyond mission range instead of only
+    /// reporting them.
+    #[serde(default)]
+    pub mission_range_refuse: bool,
     /// LH2 transfer rate (kg/minute) during refueling. Derived to per-tick.
     #[serde(default = "default_refuel_kg_per_minute")]
     pub refuel_kg_per_minute: f32,
@@ -173,6 +216,183 @@ pub struct Constants {
     /// Element ID for rocket fuel (consumed from ground facility inventory on launch).
     #[serde(default = "default_launch_fuel_element")]
     pub launch_fuel_element: String,
+    // Asteroid layers
+    /// Range for the share of a newly discovered asteroid's mass that sits in
+    /// its surface layer. Surface and core compositions are rolled
+    /// independently from the template ranges. A max of 0 disables layering.
+    #[serde(default)]
+    pub asteroid_surface_fraction_min: f32,
+    #[serde(default)]
+    pub asteroid_surface_fraction_max: f32,
+
+    // Slag pollution
+    /// Cumulative jettisoned slag (kg) at a body above which local penalties apply.
+    #[serde(default = "default_slag_pollution_threshold_kg")]
+    pub slag_pollution_threshold_kg: f32,
+    /// Multiplier on survey, deep-scan and sensor data generated at a polluted body.
+    #[serde(default = "default_slag_pollution_scan_data_mult")]
+    pub slag_pollution_scan_data_mult: f32,
+    /// Chance per ship arrival at a polluted body of a debris strike.
+    #[serde(default = "default_slag_pollution_hazard_chance")]
+    pub slag_pollution_hazard_chance: f32,
+    /// Fraction of the ship's propellant lost to a debris strike.
+    #[serde(default = "default_slag_pollution_hazard_propellant_frac")]
+    pub slag_pollution_hazard_propellant_frac: f32,
+
+    // Solar storms
+    /// Chance per day that a storm starts at a given body. 0 disables storms.
+    #[serde(default)]
+    pub solar_storm_chance_per_day: f64,
+    /// Storm duration in game-minutes. Derived to `solar_storm_duration_ticks`.
+    #[serde(default = "default_solar_storm_duration_minutes")]
+    pub solar_storm_duration_minutes: u64,
+    /// Multiplier on `solar_intensity` at a body during a storm.
+    #[serde(default = "default_solar_storm_intensity_mult")]
+    pub solar_storm_intensity_mult: f32,
+    /// Multiplier on module wear at stations under a storm.
+    #[serde(default = "default_solar_storm_wear_mult")]
+    pub solar_storm_wear_mult: f32,
+    /// Chance that a deep scan in progress at a body is lost when a storm starts there.
+    #[serde(default = "default_solar_storm_deep_scan_interrupt_chance")]
+    pub solar_storm_deep_scan_interrupt_chance: f32,
+    /// Chance that a ship at a body (not in transit) is lost when a storm
+    /// starts there. 0 = storms never destroy ships.
+    #[serde(default)]
+    pub solar_storm_ship_loss_chance: f32,
+
+    // Body claims
+    /// Credits charged for a claim on a body where the claimant has no
+    /// station. Claims backed by a station there are free.
+    #[serde(default)]
+    pub claim_cost: f64,
+    /// How long a claim lasts before it must be renewed, in game-minutes.
+    /// 0 = claims never expire. Derived to `claim_duration_ticks`.
+    #[serde(default)]
+    pub claim_duration_minutes: u64,
+
+    // Starting loan
+    /// Credits lent at world start, which make up the starting balance.
+    /// With no interest or repayments (the defaults) it is a plain grant.
+    #[serde(default = "default_starting_loan")]
+    pub starting_loan: f64,
+    /// Interest added to the outstanding loan per game-day, as a fraction.
+    /// Accrues every tick. Derived to `loan_interest_rate_per_tick`.
+    #[serde(default)]
+    pub loan_interest_rate_per_day: f64,
+    /// Game-minutes between scheduled repayments. 0 = no repayments.
+    /// Derived to `loan_repayment_interval_ticks`.
+    #[serde(default)]
+    pub loan_repayment_interval_minutes: u64,
+    /// Credits due at each repayment, capped at what is still owed.
+    #[serde(default)]
+    pub loan_repayment_amount: f64,
+    /// Repayments missed in a row before the loan defaults.
+    #[serde(default = "default_loan_default_after_missed")]
+    pub loan_default_after_missed: u32,
+
+    // Market
+    /// Game-minutes between market price updates. 0 = prices stay at their
+    /// `PricingTable` base. Derived to `market_update_interval_ticks`.
+    #[serde(default)]
+    pub market_update_interval_minutes: u64,
+    /// Largest random step of an item's log price per update (uniform in
+    /// ±this). 0 = prices move only with trade.
+    #[serde(default)]
+    pub market_volatility: f64,
+    /// Bounds on current price ÷ base price.
+    #[serde(default = "default_market_price_min_mult")]
+    pub market_price_min_mult: f64,
+    #[serde(default = "default_market_price_max_mult")]
+    pub market_price_max_mult: f64,
+
+    // Contracts
+    /// Game-minutes between new contract offers. 0 = no contracts are
+    /// offered. Derived to `contract_offer_interval_ticks`.
+    #[serde(default)]
+    pub contract_offer_interval_minutes: u64,
+    /// Open offers at most; no new offer is made while this many wait.
+    #[serde(default = "default_contract_max_offers")]
+    pub contract_max_offers: usize,
+    /// Game-minutes from offer to deadline. Derived to
+    /// `contract_duration_ticks`.
+    #[serde(default = "default_contract_duration_minutes")]
+    pub contract_duration_minutes: u64,
+    /// Range of kg asked for by delivery contracts.
+    #[serde(default = "default_contract_deliver_kg_min")]
+    pub contract_deliver_kg_min: f32,
+    #[serde(default = "default_contract_deliver_kg_max")]
+    pub contract_deliver_kg_max: f32,
+    /// Survey contracts ask for 1 to this many asteroids.
+    #[serde(default = "default_contract_survey_count_max")]
+    pub contract_survey_count_max: u32,
+    /// Delivery reward as a multiple of the material's market value.
+    #[serde(default = "default_contract_reward_premium")]
+    pub contract_reward_premium: f64,
+    /// Survey reward per asteroid asked for.
+    #[serde(default = "default_contract_reward_per_survey")]
+    pub contract_reward_per_survey: f64,
+    /// Penalty as a fraction of the reward.
+    #[serde(default = "default_contract_penalty_fraction")]
+    pub contract_penalty_fraction: f64,
+
+    // Module scrapping
+    /// Fraction of a scrapped module's mass recovered, scaled by `1 - wear`.
+    #[serde(default = "default_module_scrap_recovery_fraction")]
+    pub module_scrap_recovery_fraction: f32,
+    /// Element the recovered mass comes back as.
+    #[serde(default = "default_module_scrap_element")]
+    pub module_scrap_element: String,
+    /// Fraction of a module's wear kept through `UpgradeModule`.
+    #[serde(default = "default_module_upgrade_wear_carryover")]
+    pub module_upgrade_wear_carryover: f32,
+
+    // Power scheduling
+    /// Fraction of battery capacity deferrable modules may not draw below.
+    #[serde(default = "default_power_deferrable_battery_reserve")]
+    pub power_deferrable_battery_reserve: f32,
+
+    // Life support
+    /// Efficiency multiplier for crewed modules while a station's habitats
+    /// cannot house or supply its crew.
+    #[serde(default = "default_life_support_failure_efficiency")]
+    pub life_support_failure_efficiency: f32,
+
+    // Ship wear
+    /// Wear a ship takes per tick of a completed Mine task. 0 = mining
+    /// does not wear ships.
+    #[serde(default)]
+    pub ship_wear_per_mining_tick: f32,
+    /// Wear a ship takes per tick of a completed transit.
+    #[serde(default)]
+    pub ship_wear_per_transit_tick: f32,
+    /// Game-minutes a `DockForRepair` task takes. Derived to
+    /// `ship_repair_ticks`.
+    #[serde(default = "default_ship_repair_minutes")]
+    pub ship_repair_minutes: u64,
+
+    // Ship loss
+    /// Fraction of a lost ship's value (hull `replacement_value` plus the
+    /// base price of its fitted modules) refunded to the balance. 0 = uninsured.
+    #[serde(default)]
+    pub ship_insurance_payout_fraction: f64,
+
+    // Threats
+    /// Chance that a ship carrying cargo meets pirates on arriving at a
+    /// body with security 0; scaled by `1 − security`. 0 disables pirates.
+    #[serde(default)]
+    pub pirate_encounter_chance: f32,
+    /// Fraction of each ore and material lot taken by an unescorted raid.
+    #[serde(default = "default_pirate_cargo_theft_fraction")]
+    pub pirate_cargo_theft_fraction: f32,
+
+    // Diagnostics
+    /// Emit a `TickHotspots` event every N ticks when timings are collected.
+    /// 0 disables hotspot reporting.
+    #[serde(default)]
+    pub hotspot_report_interval_ticks: u64,
+    /// Number of station phases listed per `TickHotspots` report.
+    #[serde(default = "default_hotspot_report_top_n")]
+    pub hotspot_report_top_n: usize,
 
     // -- Derived tick fields (computed at load time, not in JSON) --
     #[serde(skip_deserializing, default)]
@@ -187,6 +407,22 @@ pub struct Constants {
     pub station_power_available_per_tick: f32,
     #[serde(skip_deserializing, default)]
     pub refuel_kg_per_tick: f32,
+    #[serde(skip_deserializing, default)]
+    pub solar_storm_duration_ticks: u64,
+    #[serde(skip_deserializing, default)]
+    pub claim_duration_ticks: u64,
+    #[serde(skip_deserializing, default)]
+    pub loan_interest_rate_per_tick: f64,
+    #[serde(skip_deserializing, default)]
+    pub loan_repayment_interval_ticks: u64,
+    #[serde(skip_deserializing, default)]
+    pub market_update_interval_ticks: u64,
+    #[serde(skip_deserializing, default)]
+    pub contract_offer_interval_ticks: u64,
+    #[serde(skip_deserializing, default)]
+    pub contract_duration_ticks: u64,
+    #[serde(skip_deserializing, default)]
+    pub ship_repair_ticks: u64,
 }
 
 impl Constants {
@@ -231,6 +467,19 @@ impl Constants {
         self.station_power_available_per_tick =
             self.rate_per_minute_to_per_tick(self.station_power_available_per_minute);
         self.refuel_kg_per_tick = self.rate_per_minute_to_per_tick(self.refuel_kg_per_minute);
+        self.solar_storm_duration_ticks =
+            self.game_minutes_to_ticks(self.solar_storm_duration_minutes);
+        self.claim_duration_ticks = self.game_minutes_to_ticks(self.claim_duration_minutes);
+        self.loan_interest_rate_per_tick =
+            self.loan_interest_rate_per_day * f64::from(self.minutes_per_tick) / 1440.0;
+        self.loan_repayment_interval_ticks =
+            self.game_minutes_to_ticks(self.loan_repayment_interval_minutes);
+        self.market_update_interval_ticks =
+            self.game_minutes_to_ticks(self.market_update_interval_minutes);
+        self.contract_offer_interval_ticks =
+            self.game_minutes_to_ticks(self.contract_offer_interval_minutes);
+        self.contract_duration_ticks = self.game_minutes_to_ticks(self.contract_duration_minutes);
+        self.ship_repair_ticks = self.game_minutes_to_ticks(self.ship_repair_minutes);
     }
 }
 
@@ -302,6 +551,72 @@ fn default_replenish_batch_size() -> usize {
 fn default_trade_unlock_delay_minutes() -> u64 {
     365 * 24 * 60
 }
+fn default_import_cancel_refund_fraction() -> f64 {
+    0.5
+}
+fn default_freight_cost_per_kg_hop() -> f64 {
+    25.0
+}
+fn default_freight_handling_minutes() -> u64 {
+    12 * 60
+}
+fn default_starting_loan() -> f64 {
+    1_000_000_000.0
+}
+fn default_market_price_min_mult() -> f64 {
+    0.25
+}
+fn default_market_price_max_mult() -> f64 {
+    4.0
+}
+fn default_contract_max_offers() -> usize {
+    3
+}
+fn default_contract_duration_minutes() -> u64 {
+    10_080
+}
+fn default_contract_deliver_kg_min() -> f32 {
+    500.0
+}
+fn default_contract_deliver_kg_max() -> f32 {
+    5_000.0
+}
+fn default_contract_survey_count_max() -> u32 {
+    5
+}
+fn default_contract_reward_premium() -> f64 {
+    1.5
+}
+fn default_contract_reward_per_survey() -> f64 {
+    100_000.0
+}
+fn default_contract_penalty_fraction() -> f64 {
+    0.25
+}
+fn default_module_scrap_recovery_fraction() -> f32 {
+    0.5
+}
+fn default_module_scrap_element() -> String {
+    "Fe".to_string()
+}
+fn default_module_upgrade_wear_carryover() -> f32 {
+    0.5
+}
+fn default_power_deferrable_battery_reserve() -> f32 {
+    0.5
+}
+fn default_life_support_failure_efficiency() -> f32 {
+    0.5
+}
+fn default_ship_repair_minutes() -> u64 {
+    240
+}
+fn default_pirate_cargo_theft_fraction() -> f32 {
+    0.5
+}
+fn default_loan_default_after_missed() -> u32 {
+    3
+}
 fn default_autopilot_budget_cap_fraction() -> f64 {
     0.05
 }
@@ -320,6 +635,9 @@ fn default_event_global_cooldown_ticks() -> u64 {
 fn default_event_history_capacity() -> usize {
     100
 }
+fn default_station_history_capacity() -> usize {
+    32
+}
 fn default_fuel_cost_per_au() -> f32 {
     500.0 // 500 kg LH2 per AU for a reference-mass ship
 }
@@ -359,6 +677,36 @@ fn default_launch_fuel_cost_per_kg() -> f64 {
 fn default_launch_fuel_element() -> String {
     "LH2".to_string()
 }
+fn default_hotspot_report_top_n() -> usize {
+    5
+}
+fn default_slag_pollution_threshold_kg() -> f32 {
+    100_000.0
+}
+fn default_slag_pollution_scan_data_mult() -> f32 {
+    0.75
+}
+fn default_slag_pollution_hazard_chance() -> f32 {
+    0.05
+}
+fn default_slag_pollution_hazard_propellant_frac() -> f32 {
+    0.1
+}
+fn default_climate_controlled_decay_mult() -> f64 {
+    0.25
+}
+fn default_solar_storm_duration_minutes() -> u64 {
+    720 // half a day
+}
+fn default_solar_storm_intensity_mult() -> f32 {
+    0.3
+}
+fn default_solar_storm_wear_mult() -> f32 {
+    2.0
+}
+fn default_solar_storm_deep_scan_interrupt_chance() -> f32 {
+    0.5
+}
 
 // ---------------------------------------------------------------------------
 // Tests
@@ -8,8 +8,9 @@ use serde::{Deserialize, Serialize};
 use crate::{
     AlertSeverity, AnomalyTag, AssemblerState, BatteryState, BehaviorType, BodyId, ComponentId,
     Constants, CrewRole, DataKind, ElementId, FittedModule, FrameId, HullId, ItemKind, LabState,
-    MaintenanceState, ModuleKindState, NodeId, Phase, PricingTable, ProcessorState, RadiatorState,
-    RecipeId, ResearchDomain, SensorArrayState, SlotType, SolarArrayState, TechId, ThermalGroupId,
+    MaintenanceState, ModuleKindState, NodeId, Phase, PricingTable, PrincipalId, ProcessorState,
+    RadiatorState, RecipeId, ResearchDomain, SensorArrayState, SlotType, SolarArrayState, TechId,
+    ThermalGroupId,
 };
 
 // ---------------------------------------------------------------------------
@@ -124,6 +125,21 @@ pub struct AutopilotConfig {
     pub power_deficit_threshold_kw: f32,
     /// Forward-looking salary projection window (game-minutes) for crew hiring decisions.
     pub crew_hire_projection_minutes: u64,
+    /// Max scan sites at one node handed to a single ship as a chained
+    /// survey batch. 1 disables batching.
+    #[serde(default = "default_survey_batch_size")]
+    pub survey_batch_size: usize,
+    /// Max asteroids chained into one mining trip before depositing.
+    /// 1 disables trip planning.
+    #[serde(default = "default_mining_trip_max_stops")]
+    pub mining_trip_max_stops: usize,
+    /// Ship wear at or above which the autopilot sends the ship to dock
+    /// for repair before its next job.
+    #[serde(default = "default_ship_repair_wear_threshold")]
+    pub ship_repair_wear_threshold: f32,
+    /// Weights of the terms in the autopilot's candidate scores.
+    #[serde(default)]
+    pub weights: AutopilotWeights,
 
     // -- Ground facility parameters --
     /// Ordered list of sensor module IDs to purchase for ground facilities.
@@ -215,6 +231,10 @@ impl Default for AutopilotConfig {
             budget_cap_fraction: 0.05,
             power_deficit_threshold_kw: 0.01,
             crew_hire_projection_minutes: 30 * 24 * 60, // 30 days
+            survey_batch_size: default_survey_batch_size(),
+            mining_trip_max_stops: default_mining_trip_max_stops(),
+            ship_repair_wear_threshold: default_ship_repair_wear_threshold(),
+            weights: AutopilotWeights::default(),
             ground_sensor_modules: vec![
                 "module_optical_telescope".to_string(),
                 "module_radio_telescope".to_string(),
@@ -235,6 +255,44 @@ impl Default for AutopilotConfig {
     }
 }
 
+/// Weights the autopilot combines its scoring terms with (see
+/// `sim_control::scoring`). Each term is normalized to 0..1 across the
+/// candidates of one decision. The defaults reproduce the single-key sorts
+/// the autopilot used before the terms were weighted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutopilotWeights {
+    /// Mine targets: kg of the element the station wants, relative to the
+    /// richest candidate.
+    pub mine_value: f64,
+    /// Mine targets: distance from the station, relative to the farthest
+    /// candidate. Counts against the target.
+    pub mine_distance: f64,
+    /// Lab techs: how close the evidence is to unlocking the tech.
+    pub research_progress: f64,
+    /// Lab techs: how many locked techs list the tech as a prerequisite,
+    /// relative to the candidate with the most.
+    pub research_urgency: f64,
+    /// How far a purchase's urgency scales its budget cap. At 0 every
+    /// purchase may spend `budget_cap_fraction` of the balance; at 1 a
+    /// purchase with no urgency may spend nothing. Only ground sensor
+    /// purchases report an urgency (the research priority); the rest
+    /// always get the full cap.
+    pub budget_urgency: f64,
+}
+
+impl Default for AutopilotWeights {
+    fn default() -> Self {
+        Self {
+            mine_value: 1.0,
+            mine_distance: 0.0,
+            research_progress: 1.0,
+            research_urgency: 0.0,
+            budget_urgency: 0.5,
+        }
+    }
+}
+
 /// Component export configuration: which component to export and how many to reserve.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExportComponentConfig {
@@ -295,6 +353,10 @@ pub struct CrewRoleDef {
 pub struct GameContent {
     pub content_version: String,
     pub techs: Vec<TechDef>,
+    /// Techs already unlocked when a world is created, from
+    /// `starting_unlocked` in `content/techs.json`. Must be prereq-closed.
+    #[serde(default)]
+    pub starting_unlocked: Vec<TechId>,
     pub solar_system: SolarSystemDef,
     pub asteroid_templates: Vec<AsteroidTemplateDef>,
     pub elements: Vec<ElementDef>,
@@ -314,10 +376,22 @@ pub struct GameContent {
     /// Hull definitions loaded from `content/hull_defs.json`. Empty if file is missing.
     #[serde(default)]
     pub hulls: BTreeMap<HullId, HullDef>,
+    /// Ship class definitions loaded from `content/ship_classes.json`.
+    /// Empty if file is missing.
+    #[serde(default)]
+    pub ship_classes: BTreeMap<String, ShipClassDef>,
+    /// Per-node asteroid belts loaded from `content/belts.json`. Empty if
+    /// the file is missing, which keeps the global scan-site top-up.
+    #[serde(default)]
+    pub belts: Vec<BeltDef>,
     /// Station frame definitions loaded from `content/frame_defs.json`.
     /// Empty if file is missing.
     #[serde(default)]
     pub frames: BTreeMap<FrameId, FrameDef>,
+    /// Module adjacency rules from `content/adjacency_rules.json`. Empty if
+    /// the file is missing.
+    #[serde(default)]
+    pub adjacency_rules: Vec<AdjacencyRuleDef>,
     /// Default fitting loadouts per hull from `content/fitting_templates.json`.
     #[serde(default)]
     pub fitting_templates: BTreeMap<HullId, Vec<FittedModule>>,
@@ -356,6 +430,14 @@ pub struct GameContent {
     /// Satellite definitions loaded from `content/satellite_defs.json`.
     #[serde(default)]
     pub satellite_defs: BTreeMap<String, SatelliteDef>,
+    /// Construction project definitions loaded from `content/project_defs.json`.
+    #[serde(default)]
+    pub project_defs: BTreeMap<String, ProjectDef>,
+    /// Subsystems this run leaves out (see `subsystems.rs`). Set by a
+    /// scenario's `mode` or `subsystems.*` overrides; everything runs by
+    /// default.
+    #[serde(default)]
+    pub subsystems: crate::Subsystems,
     /// Pre-computed element id -> density (kg/m3) lookup. Populated by `init_caches()`.
     #[serde(skip)]
     pub density_map: AHashMap<String, f32>,
@@ -445,10 +527,22 @@ pub struct ShipSetupDef {
     pub hull_id: String,
 }
 
+fn default_survey_batch_size() -> usize {
+    3
+}
+
+fn default_mining_trip_max_stops() -> usize {
+    3
+}
+
 fn default_ground_opex_max_fraction() -> f64 {
     0.001
 }
 
+fn default_ship_repair_wear_threshold() -> f32 {
+    0.4
+}
+
 fn default_satellite_replacement_wear() -> f64 {
     0.7
 }
@@ -511,6 +605,43 @@ pub struct LaunchPadDef {
     pub recovery_ticks: u64,
 }
 
+// ---------------------------------------------------------------------------
+// Construction projects
+// ---------------------------------------------------------------------------
+
+/// A long-running station construction project, started with
+/// `Command::StartProject`. Materials are drawn evenly over
+/// `duration_ticks`; the outcome is applied to the station on completion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectDef {
+    pub id: String,
+    pub name: String,
+    pub duration_ticks: u64,
+    /// Total kg of each element consumed over the project.
+    pub materials: BTreeMap<ElementId, f32>,
+    pub outcome: ProjectOutcome,
+    #[serde(default)]
+    pub required_tech: Option<TechId>,
+    /// Share of the consumed materials returned when the project is cancelled.
+    #[serde(default = "default_project_cancel_refund_fraction")]
+    pub cancel_refund_fraction: f32,
+}
+
+fn default_project_cancel_refund_fraction() -> f32 {
+    0.5
+}
+
+/// Permanent station change applied when a project completes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ProjectOutcome {
+    /// Adds to the station's cargo capacity.
+    CargoCapacity { m3: f32 },
+    /// Appends a module slot after the frame's own slots. Has no
+    /// adjacency. Framed stations only; frameless stations have no slot
+    /// limit.
+    ModuleSlot { slot_type: SlotType, label: String },
+}
+
 // ---------------------------------------------------------------------------
 // Rocket definitions
 // ---------------------------------------------------------------------------
@@ -597,6 +728,10 @@ pub enum TechEffect {
         op: crate::modifiers::ModifierOp,
         value: f64,
     },
+    /// Stops charging `PricingEntry::royalty_per_unit` for one assembled item.
+    WaiveRoyalty {
+        item_id: String,
+    },
 }
 
 // ---------------------------------------------------------------------------
@@ -627,6 +762,10 @@ fn default_solar_intensity() -> f32 {
     1.0
 }
 
+fn default_security() -> f32 {
+    1.0
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BodyType {
     Star,
@@ -650,6 +789,31 @@ pub struct ZoneDef {
     pub implicit_comm_tier: Option<crate::CommTier>,
 }
 
+/// A node's asteroid belt: a shared pool of scan sites that drains as sites
+/// spawn and refills on a cadence. Loaded from `content/belts.json`; when any
+/// belt is defined it replaces the global `replenish_*` top-up. See `belts.rs`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeltDef {
+    /// Zone body the belt's sites spawn in.
+    pub body: BodyId,
+    /// Sites left in the pool at the start of a run.
+    pub initial_budget: u32,
+    /// Cap on the pool; respawns beyond it are lost.
+    pub max_budget: u32,
+    /// Game minutes between respawns. 0 means the pool never refills.
+    #[serde(default)]
+    pub respawn_interval_minutes: u64,
+    /// Sites returned to the pool on each respawn.
+    #[serde(default)]
+    pub respawn_amount: u32,
+    /// Most unsurveyed sites the belt keeps open at once.
+    pub max_sites: u32,
+    /// Template ids the belt draws from. Empty means every template, biased
+    /// by the zone's resource class.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub templates: Vec<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OrbitalBodyDef {
     pub id: BodyId,
@@ -661,6 +825,10 @@ pub struct OrbitalBodyDef {
     #[serde(default = "default_solar_intensity")]
     pub solar_intensity: f32,
     pub zone: Option<ZoneDef>,
+    /// How well policed the body is, 0.0–1.0. Ships arriving at a body
+    /// meet pirates with `pirate_encounter_chance × (1 − security)`.
+    #[serde(default = "default_security")]
+    pub security: f32,
 }
 
 // ---------------------------------------------------------------------------
@@ -710,6 +878,14 @@ pub struct ElementDef {
     /// Per-element boiloff temperature curve parameters. If absent, uses defaults.
     #[serde(default)]
     pub boiloff_curve: Option<BoiloffCurveDef>,
+    /// Volatile elements evaporate out of unrefrigerated ore lots at
+    /// `constants.volatile_ore_loss_per_day`.
+    #[serde(default)]
+    pub volatile: bool,
+    /// Fraction of a stored material lot's quality lost per day. `None` =
+    /// quality never decays.
+    #[serde(default)]
+    pub quality_decay_per_day: Option<f64>,
 }
 
 /// Per-element boiloff temperature multiplier curve parameters.
@@ -802,6 +978,10 @@ pub struct HullDef {
     pub cargo_capacity_m3: f32,
     pub base_speed_ticks_per_au: u64,
     pub base_propellant_capacity_kg: f32,
+    /// Value of the bare hull. With its fitted modules' base prices it
+    /// sets the insurance payout when a ship on this hull is lost.
+    #[serde(default)]
+    pub replacement_value: f64,
     pub slots: Vec<SlotDef>,
     #[serde(default)]
     pub bonuses: Vec<crate::modifiers::Modifier>,
@@ -809,12 +989,74 @@ pub struct HullDef {
     pub required_tech: Option<TechId>,
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Hold volume (m³) reserved for each listed partition. Cargo of a
+    /// listed partition only fits in its own volume; cargo of an unlisted
+    /// one shares what is left of the ship's cargo capacity. Empty means an
+    /// undivided hold.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hold_partitions: BTreeMap<crate::HoldPartition, f32>,
+}
+
+/// Construction variant of a hull, named by `OutputSpec::Ship::class_id`
+/// and carried on the built ship as `ShipState::class_id`. Loaded from
+/// `content/ship_classes.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShipClassDef {
+    pub id: String,
+    pub name: String,
+    /// Cargo capacity (m³) in place of the hull's. `None` keeps the hull's.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cargo_capacity_m3: Option<f32>,
+    /// Propellant tank (kg) in place of the hull's. `None` keeps the hull's.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub propellant_capacity_kg: Option<f32>,
+    /// Travel speed relative to the hull's: 2.0 crosses an AU in half the ticks.
+    #[serde(default = "default_class_multiplier")]
+    pub speed_multiplier: f32,
+    /// Mining rate relative to `mining_rate_kg_per_tick`. 0 means the class
+    /// cannot mine.
+    #[serde(default = "default_class_multiplier")]
+    pub mining_rate_multiplier: f32,
+}
+
+fn default_class_multiplier() -> f32 {
+    1.0
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SlotDef {
     pub slot_type: SlotType,
     pub label: String,
+    /// Indices of the frame slots that share a wall with this one. Adjacency
+    /// is symmetric — listing it on either slot is enough. Empty means the
+    /// slot has no neighbours for `adjacency_rules` purposes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub adjacent: Vec<usize>,
+}
+
+/// Placement rule for modules installed in adjacent frame slots, loaded from
+/// `content/adjacency_rules.json`. Module and neighbour are matched by
+/// behavior type name (see `ModuleBehaviorDef::type_name`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdjacencyRuleDef {
+    pub module_type: String,
+    pub neighbor_type: String,
+    /// Stat bonuses the module gets while at least one matching neighbour
+    /// is installed next to it. A rule applies once regardless of how many
+    /// matching neighbours there are.
+    #[serde(default)]
+    pub bonuses: Vec<AdjacencyBonusDef>,
+    /// When set, `InstallModule` refuses placements that would put the two
+    /// behavior types next to each other (in either direction).
+    #[serde(default)]
+    pub forbidden: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdjacencyBonusDef {
+    pub stat: crate::modifiers::StatId,
+    pub op: crate::modifiers::ModifierOp,
+    pub value: f64,
 }
 
 // ---------------------------------------------------------------------------
@@ -879,7 +1121,11 @@ pub struct ModuleDef {
     pub name: String,
     pub mass_kg: f32,
     pub volume_m3: f32,
+    /// Power (kW) drawn on ticks the module actually runs.
     pub power_consumption_per_run: f32,
+    /// Power (kW) drawn every tick while enabled, whether or not it runs.
+    #[serde(default)]
+    pub power_idle_kw: f32,
     #[serde(default)]
     pub wear_per_run: f32,
     pub behavior: ModuleBehaviorDef,
@@ -913,6 +1159,26 @@ pub struct ModuleDef {
     /// Ground facility modules have positive costs; orbital station modules default to 0.
     #[serde(default)]
     pub operating_cost_per_tick: f64,
+    /// Runs may wait for spare power instead of stalling: when due, the
+    /// module only runs if generation or battery charge above
+    /// `power_deferrable_battery_reserve` covers it after every other load.
+    #[serde(default)]
+    pub deferrable: bool,
+    /// Next tier, reachable in place with `Command::UpgradeModule`. `None`
+    /// = top of its chain.
+    #[serde(default)]
+    pub upgrade_to: Option<ModuleUpgradeDef>,
+}
+
+/// One step of a module upgrade chain and what it consumes from station
+/// inventory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleUpgradeDef {
+    pub module_def_id: String,
+    #[serde(default)]
+    pub materials: BTreeMap<ElementId, f32>,
+    #[serde(default)]
+    pub components: BTreeMap<ComponentId, u32>,
 }
 
 impl ModuleDef {
@@ -929,6 +1195,15 @@ pub enum ModuleBehaviorDef {
     Processor(ProcessorDef),
     Storage {
         capacity_m3: f32,
+        /// Ore held at a station with an enabled refrigerated storage
+        /// module does not lose volatiles.
+        #[serde(default)]
+        refrigerated: bool,
+        /// Material lots at a station with an enabled climate-controlled
+        /// storage module lose quality at `climate_controlled_decay_mult` of
+        /// the normal rate.
+        #[serde(default)]
+        climate_controlled: bool,
     },
     Maintenance(MaintenanceDef),
     Assembler(AssemblerDef),
@@ -944,9 +1219,34 @@ pub enum ModuleBehaviorDef {
     Equipment,
     /// Thermal container: holds molten material at temperature.
     ThermalContainer(ThermalContainerDef),
+    /// Crew quarters and life support. Passive — consumables are drawn by
+    /// the station's life-support step.
+    Habitat(HabitatDef),
+    /// Mining drones: each interval, cut ore from a deep-scanned asteroid
+    /// near the station without a ship.
+    DroneBay(DroneBayDef),
 }
 
 impl ModuleBehaviorDef {
+    /// Every value `type_name` can return, for validating content that
+    /// refers to behavior types by name.
+    pub const TYPE_NAMES: &'static [&'static str] = &[
+        "processor",
+        "storage",
+        "maintenance",
+        "assembler",
+        "lab",
+        "sensor_array",
+        "solar_array",
+        "battery",
+        "radiator",
+        "launch_pad",
+        "equipment",
+        "thermal_container",
+        "habitat",
+        "drone_bay",
+    ];
+
     /// Returns a stable lowercase name for the behavior type, used as the key
     /// in `per_module_metrics` and as a column name prefix in CSV/Parquet.
     pub fn type_name(&self) -> &'static str {
@@ -963,6 +1263,8 @@ impl ModuleBehaviorDef {
             Self::LaunchPad(_) => "launch_pad",
             Self::Equipment => "equipment",
             Self::ThermalContainer(_) => "thermal_container",
+            Self::Habitat(_) => "habitat",
+            Self::DroneBay(_) => "drone_bay",
         }
     }
 
@@ -974,13 +1276,15 @@ impl ModuleBehaviorDef {
             Self::SensorArray(s) => Some(s.scan_interval_ticks),
             Self::Lab(l) => Some(l.research_interval_ticks),
             Self::Maintenance(m) => Some(m.repair_interval_ticks),
+            Self::DroneBay(d) => Some(d.mining_interval_ticks),
             Self::Storage { .. }
             | Self::SolarArray(_)
             | Self::Battery(_)
             | Self::Radiator(_)
             | Self::LaunchPad(_)
             | Self::Equipment
-            | Self::ThermalContainer(_) => None,
+            | Self::ThermalContainer(_)
+            | Self::Habitat(_) => None,
         }
     }
 
@@ -1010,6 +1314,9 @@ impl ModuleBehaviorDef {
                     capped: false,
                     cap_override: std::collections::HashMap::new(),
                     selected_recipe: None,
+                    extra_slots: Vec::new(),
+                    job: None,
+                    queue: Vec::new(),
                 }),
                 BehaviorType::Assembler,
             ),
@@ -1046,6 +1353,11 @@ impl ModuleBehaviorDef {
                 ModuleKindState::ThermalContainer(crate::ThermalContainerState::default()),
                 BehaviorType::ThermalContainer,
             ),
+            Self::Habitat(_) => (ModuleKindState::Habitat, BehaviorType::Habitat),
+            Self::DroneBay(_) => (
+                ModuleKindState::DroneBay(crate::DroneBayState::default()),
+                BehaviorType::DroneBay,
+            ),
         }
     }
 
@@ -1055,7 +1367,7 @@ impl ModuleBehaviorDef {
         match self {
             Self::SensorArray(_) => Some(0),
             Self::Lab(_) => Some(1),
-            Self::Assembler(_) => Some(2),
+            Self::Assembler(_) | Self::DroneBay(_) => Some(2),
             Self::Processor(_) => Some(3),
             Self::Maintenance(_) => Some(4),
             Self::Storage { .. }
@@ -1064,7 +1376,8 @@ impl ModuleBehaviorDef {
             | Self::Radiator(_)
             | Self::LaunchPad(_)
             | Self::Equipment
-            | Self::ThermalContainer(_) => None,
+            | Self::ThermalContainer(_)
+            | Self::Habitat(_) => None,
         }
     }
 }
@@ -1081,6 +1394,14 @@ pub struct AssemblerDef {
     pub recipes: Vec<RecipeId>,
     #[serde(default)]
     pub max_stock: HashMap<ComponentId, u32>,
+    /// Number of recipe runs the assembler can work on concurrently. Each
+    /// slot keeps its own timer and stall/cap flags.
+    #[serde(default = "default_parallel_slots")]
+    pub parallel_slots: u32,
+}
+
+fn default_parallel_slots() -> u32 {
+    1
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1148,6 +1469,28 @@ pub struct BatteryDef {
     pub discharge_rate_kw: f32,
 }
 
+/// Mining drones. Every `mining_interval_minutes` the bay cuts up to
+/// `kg_per_run` of ore (scaled by efficiency) from the nearest
+/// deep-scanned asteroid within `range_hops` body hops of its station.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DroneBayDef {
+    pub mining_interval_minutes: u64,
+    #[serde(skip_deserializing, default)]
+    pub mining_interval_ticks: u64,
+    pub range_hops: u32,
+    pub kg_per_run: f32,
+}
+
+/// Crew quarters. Each habitat houses up to `crew_capacity` of the
+/// station's crew and draws `consumables_kg_per_crew_hour` of each element
+/// per housed crew member.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HabitatDef {
+    pub crew_capacity: u32,
+    #[serde(default)]
+    pub consumables_kg_per_crew_hour: BTreeMap<ElementId, f32>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RadiatorDef {
     /// Maximum cooling capacity in Watts.
@@ -1262,9 +1605,28 @@ pub enum OutputSpec {
     },
     Ship {
         hull_id: HullId,
+        /// Ship class the constructed ship is tagged with.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        class_id: Option<String>,
+        /// Role hint copied onto the constructed ship.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        role: Option<String>,
+        /// Who owns the constructed ship.
+        #[serde(default)]
+        owner: ShipOwnerRule,
     },
 }
 
+/// Owner-assignment rule for ships produced by a recipe.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShipOwnerRule {
+    /// The owner of the station whose shipyard built the ship.
+    #[default]
+    StationOwner,
+    /// A fixed principal.
+    Principal(PrincipalId),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum YieldFormula {
     ElementFraction { element: ElementId },
@@ -1314,6 +1676,10 @@ pub fn derive_module_tick_values(
             ModuleBehaviorDef::LaunchPad(lp) => {
                 lp.recovery_ticks = constants.game_minutes_to_ticks(lp.recovery_minutes);
             }
+            ModuleBehaviorDef::DroneBay(d) => {
+                d.mining_interval_ticks =
+                    constants.game_minutes_to_ticks(d.mining_interval_minutes);
+            }
             _ => {}
         }
     }
@@ -1339,10 +1705,12 @@ mod frame_def_tests {
                 SlotDef {
                     slot_type: SlotType("utility".to_string()),
                     label: "Utility 1".to_string(),
+                    adjacent: Vec::new(),
                 },
                 SlotDef {
                     slot_type: SlotType("research".to_string()),
                     label: "Research 1".to_string(),
+                    adjacent: Vec::new(),
                 },
             ],
             bonuses: vec![Modifier {
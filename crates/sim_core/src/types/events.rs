@@ -5,8 +5,8 @@ use serde::{Deserialize, Serialize};
 use crate::{
     AlertSeverity, AnomalyTag, AsteroidId, BehaviorType, ComponentId, CompositionVec, CrewRole,
     DataKind, ElementId, EventId, FittedModule, GroundFacilityId, HullId, InventoryItem,
-    LaunchPayload, ModuleDefId, ModuleInstanceId, ModuleItemId, Position, PowerState, RecipeId,
-    ResearchDomain, SatelliteId, ShipId, SiteId, StationId, TechId, TradeItemSpec,
+    LaunchPayload, ModuleDefId, ModuleInstanceId, ModuleItemId, Position, PowerState, PrincipalId,
+    RecipeId, ResearchDomain, SatelliteId, ShipId, SiteId, StationId, TechId, TradeItemSpec,
 };
 
 // ---------------------------------------------------------------------------
@@ -43,6 +43,15 @@ pub enum Event {
     CompositionMapped {
         asteroid_id: AsteroidId,
         composition: CompositionVec,
+        /// Mapped surface/core profile when the asteroid is layered.
+        #[serde(default)]
+        layers: Option<crate::CompositionLayers>,
+        /// Noise half-width left after combining this scan with earlier ones.
+        #[serde(default)]
+        composition_sigma: f32,
+        /// Deep scans completed on the asteroid, this one included.
+        #[serde(default)]
+        deep_scan_count: u32,
     },
     DataGenerated {
         kind: DataKind,
@@ -64,6 +73,17 @@ pub enum Event {
         asteroid_id: AsteroidId,
         ore_lot: InventoryItem,
         asteroid_remaining_kg: f32,
+        /// Deepest layer this extraction reached; `None` for uniform rocks.
+        #[serde(default)]
+        layer: Option<crate::AsteroidLayer>,
+    },
+    /// A drone bay cut ore from an asteroid straight into its station.
+    DroneOreMined {
+        station_id: StationId,
+        module_id: ModuleInstanceId,
+        asteroid_id: AsteroidId,
+        ore_lot: InventoryItem,
+        asteroid_remaining_kg: f32,
     },
     OreDeposited {
         ship_id: ShipId,
@@ -79,6 +99,13 @@ pub enum Event {
         station_id: StationId,
         items: Vec<InventoryItem>,
     },
+    /// A `TransferCargo` task finished; `items` is what actually moved.
+    CargoTransferred {
+        ship_id: ShipId,
+        counterparty: crate::CargoHolder,
+        direction: crate::CargoDirection,
+        items: Vec<InventoryItem>,
+    },
     ModuleInstalled {
         station_id: StationId,
         module_id: ModuleInstanceId,
@@ -95,6 +122,24 @@ pub enum Event {
         module_id: ModuleInstanceId,
         module_item_id: ModuleItemId,
     },
+    /// A module was destroyed by `ScrapModule`; `kg` of `element` went
+    /// into station inventory.
+    ModuleScrapped {
+        station_id: StationId,
+        module_id: ModuleInstanceId,
+        module_def_id: String,
+        element: String,
+        kg: f32,
+    },
+    /// An installed module was replaced in place by the next tier of its
+    /// upgrade chain.
+    ModuleUpgraded {
+        station_id: StationId,
+        module_id: ModuleInstanceId,
+        from_def_id: String,
+        to_def_id: String,
+        wear: f32,
+    },
     /// Emitted when an `InstallModule` command on a framed station fails
     /// because no compatible, unoccupied slot is available for the module.
     /// The module is returned to the station inventory unchanged.
@@ -154,14 +199,30 @@ pub enum Event {
         component_produced_id: ComponentId,
         component_produced_count: u32,
         component_quality: f32,
+        /// Assembler slot index; 0 for single-slot modules.
+        #[serde(default)]
+        slot: u32,
+    },
+    /// Every run of a `QueueAssemblerJob` order has produced its output.
+    AssemblerJobCompleted {
+        station_id: StationId,
+        module_id: ModuleInstanceId,
+        recipe_id: RecipeId,
+        count: u32,
     },
     AssemblerCapped {
         station_id: StationId,
         module_id: ModuleInstanceId,
+        /// Assembler slot index; 0 for single-slot modules.
+        #[serde(default)]
+        slot: u32,
     },
     AssemblerUncapped {
         station_id: StationId,
         module_id: ModuleInstanceId,
+        /// Assembler slot index; 0 for single-slot modules.
+        #[serde(default)]
+        slot: u32,
     },
     LabRan {
         station_id: StationId,
@@ -186,14 +247,28 @@ pub enum Event {
         wear_after: f32,
         repair_kits_remaining: u32,
     },
+    /// A `DockForRepair` task finished at a station's maintenance bay.
+    ShipRepaired {
+        ship_id: ShipId,
+        station_id: StationId,
+        wear_before: f32,
+        wear_after: f32,
+        repair_kits_used: u32,
+    },
     ModuleStalled {
         station_id: StationId,
         module_id: ModuleInstanceId,
         shortfall_m3: f32,
+        /// Assembler slot index; 0 for single-slot modules.
+        #[serde(default)]
+        slot: u32,
     },
     ModuleResumed {
         station_id: StationId,
         module_id: ModuleInstanceId,
+        /// Assembler slot index; 0 for single-slot modules.
+        #[serde(default)]
+        slot: u32,
     },
     DepositBlocked {
         ship_id: ShipId,
@@ -216,6 +291,68 @@ pub enum Event {
         revenue: f64,
         balance_after: f64,
     },
+    /// A station import was paid for and is now in transit.
+    ImportOrderPlaced {
+        order_id: crate::ImportOrderId,
+        station_id: StationId,
+        item_spec: TradeItemSpec,
+        cost: f64,
+        balance_after: f64,
+        arrival_tick: u64,
+    },
+    /// An in-transit import arrived and was added to station inventory.
+    ImportDelivered {
+        order_id: crate::ImportOrderId,
+        station_id: StationId,
+        item_spec: TradeItemSpec,
+    },
+    /// An in-transit import was cancelled; part of the cost was refunded.
+    ImportOrderCancelled {
+        order_id: crate::ImportOrderId,
+        station_id: StationId,
+        item_spec: TradeItemSpec,
+        refund: f64,
+        balance_after: f64,
+    },
+    /// Goods left `from_station` with the freight service.
+    FreightScheduled {
+        order_id: crate::FreightOrderId,
+        from_station: StationId,
+        to_station: StationId,
+        item_spec: TradeItemSpec,
+        cost: f64,
+        balance_after: f64,
+        arrival_tick: u64,
+    },
+    /// A freight shipment arrived and was added to `to_station` inventory.
+    FreightDelivered {
+        order_id: crate::FreightOrderId,
+        from_station: StationId,
+        to_station: StationId,
+        item_spec: TradeItemSpec,
+        /// Set when one of the owner's ships ran the shipment on a freight
+        /// route; its cargo already arrived with `OreDeposited`. Unset for
+        /// the freight service.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        route_id: Option<crate::FreightRouteId>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ship_id: Option<ShipId>,
+    },
+    /// A market update moved an item's unit price (`market.rs`).
+    PriceChanged {
+        item_id: String,
+        old_price: f64,
+        new_price: f64,
+    },
+    /// `Command::CreateFreightRoute` set up a recurring ship-run route.
+    FreightRouteCreated {
+        route_id: crate::FreightRouteId,
+        owner: crate::PrincipalId,
+        from_station: StationId,
+        to_station: StationId,
+        item_spec: TradeItemSpec,
+        interval_ticks: u64,
+    },
     ShipConstructed {
         station_id: StationId,
         ship_id: ShipId,
@@ -223,6 +360,21 @@ pub enum Event {
         cargo_capacity_m3: f64,
         hull_id: HullId,
         fitted_modules: Vec<FittedModule>,
+        owner: PrincipalId,
+        #[serde(default)]
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        class_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        role: Option<String>,
+    },
+    /// A ship was destroyed and removed from the state along with its
+    /// cargo. `insurance_payout` was credited to the balance.
+    ShipLost {
+        ship_id: ShipId,
+        owner: PrincipalId,
+        cause: ShipLossCause,
+        insurance_payout: f64,
     },
     /// Emitted when a ship begins assembling a station on-site from a kit.
     /// Fires on the tick the `ConstructStation` task actually starts (after
@@ -233,6 +385,14 @@ pub enum Event {
         position: crate::Position,
         assembly_ticks: u64,
     },
+    /// A station spent a kit on `Command::BuildStation`; the new station
+    /// is founded at `position` on `complete_tick`.
+    StationBuildStarted {
+        source_station: StationId,
+        frame_id: crate::FrameId,
+        position: crate::Position,
+        complete_tick: u64,
+    },
     /// Emitted when a module item arrives at a destination station via
     /// inter-station transfer (VIO-595). The source station is included so
     /// downstream consumers can show the full "from → to" route in the UI.
@@ -267,10 +427,74 @@ pub enum Event {
         module_id: ModuleInstanceId,
         tech_id: TechId,
     },
+    /// A sandboxed controller failed (panicked, raised an error or ran past
+    /// its time limit) and issued no commands this tick.
+    ControllerFault {
+        controller: String,
+        message: String,
+    },
+    /// The daemon paused the run, on request or at a scheduled tick.
+    /// Emitted by the daemon, not the tick.
+    SimPaused {
+        reason: Option<String>,
+        scheduled: bool,
+    },
+    /// The daemon resumed a paused run. Emitted by the daemon, not the tick.
+    SimResumed {},
+    /// Periodic debug report for a processor that would not run if its
+    /// timer came due now. `reasons` is the full chain of blockers.
+    ModuleIdleDiagnosis {
+        station_id: StationId,
+        module_id: ModuleInstanceId,
+        reasons: Vec<IdleReason>,
+    },
     SlagJettisoned {
         station_id: StationId,
         kg: f32,
     },
+    /// Cumulative jettisoned slag at a body crossed the pollution threshold;
+    /// scan data there is degraded and arriving ships risk debris strikes.
+    SlagPollutionWarning {
+        body_id: crate::BodyId,
+        jettisoned_kg: f32,
+        threshold_kg: f32,
+    },
+    /// A solar storm began at a body. Stations there lose solar output and
+    /// wear faster until `ends_tick`; `interrupted_ships` lost their deep
+    /// scan in progress.
+    StormStarted {
+        body_id: crate::BodyId,
+        ends_tick: u64,
+        interrupted_ships: Vec<ShipId>,
+    },
+    StormEnded {
+        body_id: crate::BodyId,
+    },
+    /// Diagnostic: the station phases that took the most wall-clock time
+    /// over the last `window_ticks` ticks. Only emitted when
+    /// `hotspot_report_interval_ticks > 0` and timings are being collected.
+    TickHotspots {
+        window_ticks: u64,
+        hotspots: Vec<TickHotspot>,
+    },
+    /// A ship arriving at a polluted body was hit by jettisoned debris.
+    DebrisStrike {
+        ship_id: ShipId,
+        body_id: crate::BodyId,
+        propellant_lost_kg: f32,
+    },
+    /// Pirates raided an unescorted ship arriving at a low-security body.
+    CargoStolen {
+        ship_id: ShipId,
+        body_id: crate::BodyId,
+        kg_stolen: f32,
+    },
+    /// Escorts drove off pirates at a ship's arrival.
+    ThreatRepelled {
+        ship_id: ShipId,
+        body_id: crate::BodyId,
+        escorts: Vec<ShipId>,
+    },
     PowerStateUpdated {
         station_id: StationId,
         power: PowerState,
@@ -310,6 +534,55 @@ pub enum Event {
         element: ElementId,
         kg_lost: f32,
     },
+    ProjectStarted {
+        project_id: crate::ProjectId,
+        station_id: StationId,
+        project_def_id: String,
+    },
+    /// The station ran short of a project material; emitted once per stall.
+    ProjectStalled {
+        project_id: crate::ProjectId,
+        station_id: StationId,
+        element: ElementId,
+    },
+    ProjectCompleted {
+        project_id: crate::ProjectId,
+        station_id: StationId,
+        project_def_id: String,
+        outcome: crate::ProjectOutcome,
+    },
+    ProjectCancelled {
+        project_id: crate::ProjectId,
+        station_id: StationId,
+        project_def_id: String,
+        refunded_kg: f32,
+    },
+    /// Summary of a processor's or assembler's runs over
+    /// `constants.production_report_interval_ticks`. The per-run
+    /// `RefineryRan`/`AssemblerRan` events are `Debug` level; this is the
+    /// `Info`-level view of the same work.
+    ProductionReport {
+        station_id: StationId,
+        module_id: ModuleInstanceId,
+        /// First tick covered (inclusive).
+        start_tick: u64,
+        /// Last tick covered (exclusive).
+        end_tick: u64,
+        runs: u32,
+        input_kg: f32,
+        output_kg: f32,
+        output_units: u32,
+        average_quality: f32,
+    },
+    /// Volatile elements evaporated out of ore held by a ship or an
+    /// unrefrigerated station this tick. Exactly one holder is set.
+    OreSpoiled {
+        #[serde(default)]
+        ship_id: Option<ShipId>,
+        #[serde(default)]
+        station_id: Option<StationId>,
+        kg_lost: f32,
+    },
     RecipeSelectionReset {
         station_id: StationId,
         module_id: ModuleInstanceId,
@@ -333,6 +606,83 @@ pub enum Event {
         ship_id: ShipId,
         destination: crate::Position,
     },
+    /// A task was assigned at a body more hops from the owner's nearest
+    /// station than the ship's mission range. `refused` when the assignment
+    /// was dropped (`mission_range_refuse`).
+    TaskOutOfRange {
+        ship_id: ShipId,
+        target_body: crate::BodyId,
+        hops: u32,
+        range_hops: u32,
+        refused: bool,
+    },
+    /// `owner` claimed a body. `contested_from` is the previous holder when
+    /// the claim was taken over; a renewal by the same owner leaves it
+    /// `None`.
+    BodyClaimed {
+        body_id: crate::BodyId,
+        owner: crate::PrincipalId,
+        basis: crate::ClaimBasis,
+        cost: f64,
+        expires_tick: Option<u64>,
+        contested_from: Option<crate::PrincipalId>,
+    },
+    ClaimRejected {
+        body_id: crate::BodyId,
+        principal: crate::PrincipalId,
+        reason: String,
+    },
+    ClaimReleased {
+        body_id: crate::BodyId,
+        owner: crate::PrincipalId,
+    },
+    /// `Command::CancelCommand` removed a scheduled command before it ran.
+    CommandCancelled {
+        command_id: crate::CommandId,
+        issued_by: crate::PrincipalId,
+    },
+    /// A command was dropped without effect; `reason` says why.
+    CommandRejected {
+        command_id: crate::CommandId,
+        reason: String,
+    },
+    /// A claim ran past its expiry, or its owner lost the station backing it.
+    ClaimExpired {
+        body_id: crate::BodyId,
+        owner: crate::PrincipalId,
+    },
+    /// A new contract went on offer.
+    ContractOffered {
+        contract_id: crate::ContractId,
+        kind: crate::ContractKind,
+        reward: f64,
+        penalty: f64,
+        deadline_tick: u64,
+    },
+    ContractAccepted {
+        contract_id: crate::ContractId,
+        holder: crate::PrincipalId,
+    },
+    /// The holder met the terms and was paid `reward`.
+    ContractCompleted {
+        contract_id: crate::ContractId,
+        holder: crate::PrincipalId,
+        reward: f64,
+    },
+    /// The holder abandoned the contract (`abandoned`) or missed its
+    /// deadline, and was charged `penalty`.
+    ContractFailed {
+        contract_id: crate::ContractId,
+        holder: crate::PrincipalId,
+        penalty: f64,
+        abandoned: bool,
+    },
+    /// A `Mine` assignment was refused because `holder` claims the body.
+    MiningRightsDenied {
+        ship_id: ShipId,
+        body_id: crate::BodyId,
+        holder: crate::PrincipalId,
+    },
     RefuelComplete {
         ship_id: ShipId,
         station_id: StationId,
@@ -363,6 +713,17 @@ pub enum Event {
         station_id: StationId,
         module_id: ModuleInstanceId,
     },
+    /// The station's habitats can no longer house or supply its crew.
+    /// `short_element` names the first consumable that ran out, if any.
+    LifeSupportFailed {
+        station_id: StationId,
+        crew: u32,
+        crew_capacity: u32,
+        short_element: Option<ElementId>,
+    },
+    LifeSupportRestored {
+        station_id: StationId,
+    },
     ThermalLinkCreated {
         station_id: StationId,
         from_module_id: ModuleInstanceId,
@@ -427,6 +788,15 @@ pub enum Event {
         amount: f64,
         balance_after: f64,
     },
+    /// Blueprint royalty debited for units an assembler just produced.
+    RoyaltyPaid {
+        station_id: StationId,
+        module_id: ModuleInstanceId,
+        item_id: String,
+        units: u32,
+        amount: f64,
+        balance_after: f64,
+    },
     /// Emitted when a satellite is deployed into orbit.
     SatelliteDeployed {
         satellite_id: SatelliteId,
@@ -493,3 +863,78 @@ pub enum Event {
     /// and `ci_event_sync.sh`'s struct-variant grep also depends on this.
     StrategyConfigChanged {},
 }
+
+/// How chatty an event is. Consumers that only want a readable log can
+/// drop `Debug` events, which fire per module run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventLevel {
+    Debug,
+    Info,
+}
+
+impl Event {
+    pub fn level(&self) -> EventLevel {
+        match self {
+            Self::RefineryRan { .. }
+            | Self::AssemblerRan { .. }
+            | Self::ModuleIdleDiagnosis { .. } => EventLevel::Debug,
+            _ => EventLevel::Info,
+        }
+    }
+}
+
+/// What destroyed a ship, as reported by `ShipLost`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShipLossCause {
+    /// Caught at a body when a solar storm started there.
+    SolarStorm,
+    /// Wear reached 1.0 on a completed task.
+    HullFailure,
+    /// A sim event's `DestroyShip` effect.
+    SimEvent,
+}
+
+/// One reason a module would not run, as reported by `why_not_running`.
+/// Reasons are listed in the order the module's run checks them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum IdleReason {
+    Disabled,
+    WornOut {
+        wear: f32,
+    },
+    /// Shed by the station's power budget this tick.
+    PowerStalled,
+    /// Efficiency is zero for another reason, e.g. missing crew.
+    ZeroEfficiency,
+    InsufficientPower {
+        available_kw: f32,
+        needed_kw: f32,
+    },
+    NoRecipe,
+    AwaitingTech {
+        tech_id: TechId,
+    },
+    BelowThreshold {
+        input_kg: f32,
+        threshold_kg: f32,
+    },
+    TooCold {
+        current_temp_mk: u32,
+        required_temp_mk: u32,
+    },
+    StorageFull {
+        shortfall_m3: f32,
+    },
+}
+
+/// One entry of a `TickHotspots` report: wall-clock time a station spent in
+/// one station sub-step over the report window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TickHotspot {
+    pub station_id: StationId,
+    /// Station sub-step name, as in `TickTimings::iter_fields`.
+    pub phase: String,
+    pub total_us: u64,
+}
@@ -42,6 +42,9 @@ pub enum InventoryItem {
     Module {
         item_id: ModuleItemId,
         module_def_id: String,
+        /// Wear carried over from an uninstalled module; 0 for new ones.
+        #[serde(default)]
+        wear: f32,
     },
 }
 
@@ -99,6 +102,77 @@ impl InventoryItem {
     }
 }
 
+/// Section of a ship hold that a hull can reserve volume for (see
+/// `HullDef::hold_partitions`). Bulk cargo goes in the ore bay, discrete
+/// items in the component locker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HoldPartition {
+    /// Ore, slag and materials.
+    OreBay,
+    /// Components and modules.
+    ComponentLocker,
+}
+
+impl HoldPartition {
+    pub fn of_item(item: &InventoryItem) -> Self {
+        match item {
+            InventoryItem::Ore { .. }
+            | InventoryItem::Slag { .. }
+            | InventoryItem::Material { .. } => Self::OreBay,
+            InventoryItem::Component { .. } | InventoryItem::Module { .. } => Self::ComponentLocker,
+        }
+    }
+
+    pub fn of_cargo(spec: &CargoSpec) -> Self {
+        match spec {
+            CargoSpec::Ore { .. } | CargoSpec::Slag { .. } => Self::OreBay,
+            CargoSpec::Item(trade) => Self::of_trade(trade),
+        }
+    }
+
+    /// Crew take no hold space; they are filed with components.
+    pub fn of_trade(spec: &TradeItemSpec) -> Self {
+        match spec {
+            TradeItemSpec::Material { .. } => Self::OreBay,
+            TradeItemSpec::Component { .. }
+            | TradeItemSpec::Module { .. }
+            | TradeItemSpec::Crew { .. } => Self::ComponentLocker,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Cargo transfer types
+// ---------------------------------------------------------------------------
+
+/// The other side of a `TransferCargo`: a station or another ship at the
+/// same location as the transferring ship.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CargoHolder {
+    Ship(crate::ShipId),
+    Station(crate::StationId),
+}
+
+/// Whether a `TransferCargo` loads cargo onto the ship or unloads it.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CargoDirection {
+    Load,
+    Unload,
+}
+
+/// Cargo selected for a `TransferCargo`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum CargoSpec {
+    /// An ore lot by id. `kg: None` moves the whole lot; otherwise the lot
+    /// is split and the moved share gets a fresh lot id.
+    Ore { lot_id: LotId, kg: Option<f32> },
+    /// Up to `kg` of slag.
+    Slag { kg: f32 },
+    /// Materials, components or modules, selected as for trade.
+    Item(TradeItemSpec),
+}
+
 // ---------------------------------------------------------------------------
 // Trade types
 // ---------------------------------------------------------------------------
@@ -179,6 +253,15 @@ pub struct PricingEntry {
     /// Item category for UI grouping: `material`, `component`, `module`, `raw_ore`, `byproduct`.
     #[serde(default)]
     pub category: String,
+    /// Blueprint licence fee debited each time an assembler produces one
+    /// unit. Waived by a `TechEffect::WaiveRoyalty` for the item.
+    #[serde(default)]
+    pub royalty_per_unit: f64,
+    /// Fractional move of the log price per unit of net player trade (kg
+    /// for materials, count otherwise): imports raise the price, exports
+    /// lower it. 0 = trade does not move this price.
+    #[serde(default)]
+    pub elasticity: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,8 +59,9 @@ pub const TAG_VOLATILE_RICH: &str = "VolatileRich";
 // ---------------------------------------------------------------------------
 
 /// Current save-file schema version. Bump when state shape changes in a
-/// backward-incompatible way (new required fields, removed fields, type changes).
-pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+/// backward-incompatible way (new required fields, removed fields, type changes)
+/// and register the upgrade from the previous version in `migrations.rs`.
+pub const CURRENT_SCHEMA_VERSION: u32 = 3;
 
 // ---------------------------------------------------------------------------
 // Ambient temperature constant
@@ -111,6 +112,11 @@ string_id!(BodyId);
 string_id!(SiteId);
 numeric_id!(CommandId);
 numeric_id!(EventId);
+numeric_id!(ImportOrderId);
+numeric_id!(FreightOrderId);
+numeric_id!(FreightRouteId);
+numeric_id!(ContractId);
+numeric_id!(ProjectId);
 string_id!(PrincipalId);
 string_id!(LotId);
 string_id!(GroundFacilityId);
@@ -277,6 +283,8 @@ pub enum BehaviorType {
     LaunchPad,
     Equipment,
     ThermalContainer,
+    Habitat,
+    DroneBay,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
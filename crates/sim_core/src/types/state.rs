@@ -1,7 +1,7 @@
 //! Runtime state types: `GameState`, ships, stations, asteroids, modules, tasks.
 
 use super::AHashMap;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use serde::{Deserialize, Serialize};
 
@@ -9,7 +9,7 @@ use std::collections::BTreeMap;
 
 use crate::{
     AnomalyTag, AsteroidId, BodyId, ComponentId, CompositionVec, Constants, CrewRole, DataKind,
-    DomainProgress, FrameId, GameContent, HullId, InventoryItem, LeaderId, ModuleDefId,
+    DomainProgress, ElementId, FrameId, GameContent, HullId, InventoryItem, LeaderId, ModuleDefId,
     ModuleInstanceId, OverheatZone, Phase, PrincipalId, RecipeId, SatelliteId, ShipId, SiteId,
     StationId, TechId, ThermalGroupId, DEFAULT_AMBIENT_TEMP_MK,
 };
@@ -36,6 +36,12 @@ pub struct GameState {
     pub research: ResearchState,
     #[serde(default)]
     pub balance: f64,
+    /// What is still owed on the starting loan. See `loan.rs`.
+    #[serde(default)]
+    pub loan: LoanState,
+    /// Drifting import/export prices. See `market.rs`.
+    #[serde(default)]
+    pub market: MarketState,
     /// Cumulative export revenue since simulation start.
     #[serde(default)]
     pub export_revenue_total: f64,
@@ -66,11 +72,79 @@ pub struct GameState {
     /// replaced at runtime via `Command::SetStrategyConfig` (VIO-483).
     #[serde(default)]
     pub strategy_config: crate::StrategyConfig,
+    /// Paid imports still in transit to their destination station, in order
+    /// of placement. Drained by `resolve_import_deliveries` on arrival.
+    #[serde(default)]
+    pub import_orders: Vec<ImportOrder>,
+    /// Paid freight shipments between stations, in order of scheduling.
+    /// Drained by `resolve_freight_deliveries` on arrival.
+    #[serde(default)]
+    pub freight_orders: Vec<FreightOrder>,
+    /// Recurring ship-run freight routes, in order of creation. See
+    /// `freight_routes.rs`.
+    #[serde(default)]
+    pub freight_routes: Vec<FreightRoute>,
+    /// Station construction projects in progress, in order of starting.
+    #[serde(default)]
+    pub projects: Vec<ConstructionProject>,
+    /// Stations being founded by `Command::BuildStation`, in order of issue.
+    #[serde(default)]
+    pub station_builds: Vec<StationBuild>,
+    /// Cumulative slag jettisoned at each body, in kg. Drives local
+    /// pollution penalties once past `slag_pollution_threshold_kg`.
+    #[serde(default)]
+    pub jettisoned_slag_kg: BTreeMap<BodyId, f32>,
+    /// Solar storms in progress, by body. See `storms.rs`.
+    #[serde(default)]
+    pub solar_storms: BTreeMap<BodyId, SolarStorm>,
+    /// Mining claims, by body. See `claims.rs`.
+    #[serde(default)]
+    pub claims: BTreeMap<BodyId, BodyClaim>,
+    /// Remaining spawn pool per belt body. Filled from `BeltDef::initial_budget`
+    /// the first time a belt is seen. See `belts.rs`.
+    #[serde(default)]
+    pub belts: BTreeMap<BodyId, BeltState>,
+    /// Commands received ahead of their `execute_at_tick`, in execution
+    /// order. Applied at the start of that tick; `CancelCommand` removes one.
+    #[serde(default)]
+    pub pending_commands: Vec<crate::CommandEnvelope>,
+    /// Contracts on offer or held, in order of offer. See `contracts.rs`.
+    #[serde(default)]
+    pub contracts: Vec<Contract>,
+    /// Station phase timings accumulated for the next `TickHotspots` report.
+    /// Diagnostic only; not serialized.
+    #[serde(skip, default)]
+    pub hotspots: crate::HotspotWindow,
     /// Cached absolute positions for orbital bodies. Not serialized -- recomputed on load.
     #[serde(skip, default)]
     pub body_cache: AHashMap<BodyId, crate::spatial::BodyCache>,
 }
 
+/// Read accessors that make up the stable facade over `GameState`. Fields
+/// stay public for now, but callers outside the workspace should prefer these.
+impl GameState {
+    /// The tick the next call to `tick()` will process.
+    pub fn current_tick(&self) -> u64 {
+        self.meta.tick
+    }
+
+    pub fn ship(&self, id: &ShipId) -> Option<&ShipState> {
+        self.ships.get(id)
+    }
+
+    pub fn station(&self, id: &StationId) -> Option<&StationState> {
+        self.stations.get(id)
+    }
+
+    pub fn asteroid(&self, id: &AsteroidId) -> Option<&AsteroidState> {
+        self.asteroids.get(id)
+    }
+
+    pub fn is_unlocked(&self, tech: &TechId) -> bool {
+        self.research.unlocked.contains(tech)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetaState {
     pub tick: u64,
@@ -97,6 +171,225 @@ pub struct Counters {
     /// Stations deployed from ground facility launches (`StationKit` payload).
     #[serde(default)]
     pub stations_deployed: u64,
+    #[serde(default)]
+    pub next_import_order_id: u64,
+    #[serde(default)]
+    pub next_freight_order_id: u64,
+    #[serde(default)]
+    pub next_project_id: u64,
+    #[serde(default)]
+    pub next_freight_route_id: u64,
+    #[serde(default)]
+    pub next_contract_id: u64,
+}
+
+/// A `ProjectDef` being built at a station. Advances one tick at a time
+/// while the station holds that tick's share of every material.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstructionProject {
+    pub id: crate::ProjectId,
+    pub def_id: String,
+    pub station_id: StationId,
+    pub started_tick: u64,
+    /// Ticks of work done; the project completes at `duration_ticks`.
+    pub progress_ticks: u64,
+    /// Materials drawn so far, for refunds on cancellation.
+    #[serde(default)]
+    pub consumed: BTreeMap<crate::ElementId, ConsumedMaterial>,
+    /// True while the station is short of materials.
+    #[serde(default)]
+    pub stalled: bool,
+}
+
+/// A station kit on its way from `source_station` to `position`. The new
+/// station is created on `complete_tick`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StationBuild {
+    pub source_station: StationId,
+    pub frame_id: crate::FrameId,
+    pub kit_component_id: String,
+    pub position: crate::Position,
+    pub complete_tick: u64,
+}
+
+/// A solar storm at one body, active until `ends_tick`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolarStorm {
+    pub started_tick: u64,
+    pub ends_tick: u64,
+}
+
+/// The starting loan (`Constants::starting_loan`) and its repayment record.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoanState {
+    /// Principal plus accrued interest not yet repaid.
+    pub outstanding: f64,
+    /// Scheduled repayments missed in a row.
+    pub missed_payments: u32,
+    /// Set once `loan_default_after_missed` repayments were missed in a row.
+    /// A defaulted loan stops accruing interest and taking repayments.
+    pub defaulted: bool,
+}
+
+/// Market drift of trade prices away from `PricingTable` base prices.
+/// Items without an entry trade at their base price.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MarketState {
+    /// By pricing key (element, component or module def id).
+    pub items: BTreeMap<String, MarketItem>,
+}
+
+impl MarketState {
+    /// Current price ÷ base price for a pricing key.
+    pub fn multiplier(&self, pricing_key: &str) -> f64 {
+        self.items
+            .get(pricing_key)
+            .map_or(1.0, |item| item.multiplier)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketItem {
+    /// Units imported minus units exported so far (kg for materials, count
+    /// otherwise).
+    pub net_volume: f64,
+    /// Accumulated random walk, as an offset to the log price.
+    pub noise: f64,
+    /// Current price ÷ base price, as of the last market update.
+    pub multiplier: f64,
+}
+
+impl Default for MarketItem {
+    fn default() -> Self {
+        Self {
+            net_volume: 0.0,
+            noise: 0.0,
+            multiplier: 1.0,
+        }
+    }
+}
+
+/// Runtime state of one asteroid belt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BeltState {
+    /// Sites the belt can still spawn before its next respawn.
+    pub budget: u32,
+}
+
+/// A principal's exclusive mining rights at one body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BodyClaim {
+    pub owner: PrincipalId,
+    pub claimed_tick: u64,
+    /// `None` = the claim does not expire.
+    pub expires_tick: Option<u64>,
+    pub basis: ClaimBasis,
+}
+
+/// What a claim was made on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimBasis {
+    /// Paid for with `claim_cost` credits.
+    Credits,
+    /// Backed by a station the owner has at the body.
+    Station,
+}
+
+/// An offered or accepted contract. Settled contracts are removed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Contract {
+    pub id: crate::ContractId,
+    pub kind: ContractKind,
+    pub reward: f64,
+    /// Charged when the holder abandons the contract or misses the deadline.
+    pub penalty: f64,
+    pub offered_tick: u64,
+    /// Last tick the terms can be met. An offer nobody accepted is
+    /// withdrawn after it.
+    pub deadline_tick: u64,
+    /// `None` while on offer.
+    pub holder: Option<PrincipalId>,
+    /// Asteroids the holder's ships discovered since accepting a `Survey`
+    /// contract.
+    #[serde(default)]
+    pub progress: u32,
+}
+
+/// What a contract asks for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ContractKind {
+    /// Hand over `kg` of `element` at one of the holder's stations.
+    Deliver { element: ElementId, kg: f32 },
+    /// Discover `count` asteroids with the holder's ships.
+    Survey { count: u32 },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConsumedMaterial {
+    pub kg: f32,
+    /// Sum of kg × quality, so refunds keep the average quality.
+    pub quality_kg: f32,
+}
+
+/// Goods shipped between two stations by the NPC freight service. Items are
+/// removed from the origin and paid for when scheduled; they land at the
+/// destination once `arrival_tick` is reached.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FreightOrder {
+    pub id: crate::FreightOrderId,
+    pub from_station: StationId,
+    pub to_station: StationId,
+    pub item_spec: crate::TradeItemSpec,
+    pub items: Vec<InventoryItem>,
+    pub cost: f64,
+    pub placed_tick: u64,
+    pub arrival_tick: u64,
+}
+
+/// A standing order to haul `item_spec` from `from_station` to `to_station`
+/// with the owner's own ships, at most once every `interval_ticks`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FreightRoute {
+    pub id: crate::FreightRouteId,
+    /// Principal whose idle ships run the route.
+    pub owner: PrincipalId,
+    pub from_station: StationId,
+    pub to_station: StationId,
+    pub item_spec: crate::TradeItemSpec,
+    pub interval_ticks: u64,
+    /// Earliest tick the next run may be dispatched.
+    pub next_dispatch_tick: u64,
+    /// The run in progress, if any. A route runs one ship at a time.
+    #[serde(default)]
+    pub active_trip: Option<FreightTrip>,
+}
+
+/// One run of a `FreightRoute`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FreightTrip {
+    pub ship_id: ShipId,
+    /// Shipment id reported by `FreightDelivered`, drawn from the same
+    /// counter as the freight service's orders.
+    pub order_id: crate::FreightOrderId,
+    pub dispatched_tick: u64,
+}
+
+/// A paid import travelling to a station. Cost is charged when the order is
+/// placed; the items land in station inventory once `arrival_tick` is reached.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportOrder {
+    pub id: crate::ImportOrderId,
+    pub station_id: StationId,
+    /// Principal who placed the order; only they may cancel it.
+    pub issued_by: PrincipalId,
+    pub item_spec: crate::TradeItemSpec,
+    /// Concrete items generated at placement (lots, qualities) so delivery is
+    /// independent of RNG state at arrival.
+    pub items: Vec<InventoryItem>,
+    pub cost: f64,
+    pub placed_tick: u64,
+    pub arrival_tick: u64,
 }
 
 // ---------------------------------------------------------------------------
@@ -123,7 +416,7 @@ pub struct ModuleState {
     /// Module priority. Higher values run first within each behavior class.
     /// Used to control which modules consume shared inventory first, crew assignment,
     /// and power allocation. 0 = default.
-    #[serde(default, alias = "manufacturing_priority")]
+    #[serde(default)]
     pub module_priority: u32,
     /// Crew assigned to this module, by role. Empty = no crew assigned.
     #[serde(default)]
@@ -203,19 +496,43 @@ pub enum ModuleKindState {
     LaunchPad(LaunchPadState),
     Equipment,
     ThermalContainer(ThermalContainerState),
+    Habitat,
+    DroneBay(DroneBayState),
 }
 
 impl ModuleKindState {
     /// Returns `true` if this module's kind state indicates it is stalled.
-    /// Only Processor and Assembler have a stalled concept; all others return `false`.
+    /// Only processors, assemblers and drone bays have a stalled concept; all
+    /// others return `false`.
     pub fn is_stalled(&self) -> bool {
         match self {
             Self::Processor(s) => s.stalled,
             Self::Assembler(s) => s.stalled,
+            Self::DroneBay(s) => s.stalled,
             _ => false,
         }
     }
 
+    /// Returns the tick timer, or `None` for non-ticking modules.
+    pub fn ticks_since_last_run(&self) -> Option<u64> {
+        match self {
+            Self::Processor(s) => Some(s.ticks_since_last_run),
+            Self::Assembler(s) => Some(s.ticks_since_last_run),
+            Self::SensorArray(s) => Some(s.ticks_since_last_run),
+            Self::Lab(s) => Some(s.ticks_since_last_run),
+            Self::Maintenance(s) => Some(s.ticks_since_last_run),
+            Self::DroneBay(s) => Some(s.ticks_since_last_run),
+            Self::Storage
+            | Self::SolarArray(_)
+            | Self::Battery(_)
+            | Self::Radiator(_)
+            | Self::LaunchPad(_)
+            | Self::Equipment
+            | Self::ThermalContainer(_)
+            | Self::Habitat => None,
+        }
+    }
+
     /// Returns a mutable reference to the tick timer, or `None` for non-ticking modules.
     pub fn ticks_since_last_run_mut(&mut self) -> Option<&mut u64> {
         match self {
@@ -224,13 +541,15 @@ impl ModuleKindState {
             Self::SensorArray(s) => Some(&mut s.ticks_since_last_run),
             Self::Lab(s) => Some(&mut s.ticks_since_last_run),
             Self::Maintenance(s) => Some(&mut s.ticks_since_last_run),
+            Self::DroneBay(s) => Some(&mut s.ticks_since_last_run),
             Self::Storage
             | Self::SolarArray(_)
             | Self::Battery(_)
             | Self::Radiator(_)
             | Self::LaunchPad(_)
             | Self::Equipment
-            | Self::ThermalContainer(_) => None,
+            | Self::ThermalContainer(_)
+            | Self::Habitat => None,
         }
     }
 }
@@ -254,6 +573,14 @@ pub struct BatteryState {
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct RadiatorState {}
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DroneBayState {
+    pub ticks_since_last_run: u64,
+    /// Set while station storage has no room for the next cut.
+    #[serde(default)]
+    pub stalled: bool,
+}
+
 /// Runtime state for a launch pad module.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LaunchPadState {
@@ -308,6 +635,90 @@ pub struct AssemblerState {
     pub cap_override: HashMap<ComponentId, u32>,
     #[serde(default)]
     pub selected_recipe: Option<RecipeId>,
+    /// Job in progress on slot 0, if any.
+    #[serde(default)]
+    pub job: Option<AssemblerJob>,
+    /// Progress for slots beyond the first when the def has
+    /// `parallel_slots > 1`. Slot 0 is tracked by the fields above.
+    #[serde(default)]
+    pub extra_slots: Vec<AssemblerSlotState>,
+    /// Orders from `QueueAssemblerJob`, worked front to back. While any
+    /// order has runs left to start it takes precedence over
+    /// `selected_recipe`.
+    #[serde(default)]
+    pub queue: Vec<AssemblerQueueEntry>,
+}
+
+/// A queued order for `count` runs of one recipe.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AssemblerQueueEntry {
+    pub recipe_id: RecipeId,
+    pub count: u32,
+    /// Runs that have taken their inputs.
+    #[serde(default)]
+    pub started: u32,
+    /// Runs that have produced their output.
+    #[serde(default)]
+    pub completed: u32,
+}
+
+/// An assembler run in progress. Inputs are consumed when the job starts;
+/// the output appears once `progress` reaches the def's assembly interval.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AssemblerJob {
+    pub recipe_id: RecipeId,
+    /// Effective ticks of work done. Each tick adds the module's efficiency,
+    /// so wear, crew shortfalls and power stalls slow or pause the job.
+    pub progress: f32,
+    /// Material consumed at start, reported by `AssemblerRan` on completion.
+    #[serde(default)]
+    pub material_element: ElementId,
+    #[serde(default)]
+    pub material_consumed_kg: f32,
+}
+
+impl AssemblerState {
+    /// Run timer for `slot`. Slot 0 is the module's own timer.
+    pub fn slot_ticks_mut(&mut self, slot: u32) -> Option<&mut u64> {
+        if slot == 0 {
+            return Some(&mut self.ticks_since_last_run);
+        }
+        self.extra_slots
+            .get_mut(slot as usize - 1)
+            .map(|s| &mut s.ticks_since_last_run)
+    }
+
+    /// Job slot for `slot`. Slot 0 is the module's own job.
+    pub fn slot_job_mut(&mut self, slot: u32) -> Option<&mut Option<AssemblerJob>> {
+        if slot == 0 {
+            return Some(&mut self.job);
+        }
+        self.extra_slots
+            .get_mut(slot as usize - 1)
+            .map(|s| &mut s.job)
+    }
+
+    /// `(stalled, capped)` flags for `slot`. Slot 0 is the module's own flags.
+    pub fn slot_flags_mut(&mut self, slot: u32) -> Option<(&mut bool, &mut bool)> {
+        if slot == 0 {
+            return Some((&mut self.stalled, &mut self.capped));
+        }
+        self.extra_slots
+            .get_mut(slot as usize - 1)
+            .map(|s| (&mut s.stalled, &mut s.capped))
+    }
+}
+
+/// Per-slot progress for an additional assembler slot.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AssemblerSlotState {
+    pub ticks_since_last_run: u64,
+    #[serde(default)]
+    pub stalled: bool,
+    #[serde(default)]
+    pub capped: bool,
+    #[serde(default)]
+    pub job: Option<AssemblerJob>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -340,13 +751,86 @@ pub struct AsteroidState {
     pub anomaly_tags: Vec<AnomalyTag>,
     pub mass_kg: f32,
     pub knowledge: AsteroidKnowledge,
+    /// Surface/core split rolled at discovery. `None` = uniform rock, in
+    /// which case `true_composition` applies at every depth. When set,
+    /// `true_composition` is the mass-weighted bulk average of the layers.
+    #[serde(default)]
+    pub layers: Option<CompositionLayers>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AsteroidKnowledge {
     pub tag_beliefs: Vec<(AnomalyTag, f32)>,
-    /// Set after a deep scan. Exact composition -- no uncertainty model.
+    /// Set after a deep scan: the noise-weighted mean of every deep scan's
+    /// sample, normalised.
     pub composition: Option<CompositionVec>,
+    /// Layer profile revealed by a deep scan of a layered asteroid, averaged
+    /// the same way as `composition`.
+    #[serde(default)]
+    pub layers: Option<CompositionLayers>,
+    /// Noise half-width left in `composition` after combining every deep
+    /// scan. `None` when the accuracy is not known, in which case the next
+    /// deep scan replaces the estimate outright.
+    #[serde(default)]
+    pub composition_sigma: Option<f32>,
+    /// Deep scans completed on this asteroid.
+    #[serde(default)]
+    pub deep_scan_count: u32,
+}
+
+/// Which layer of a layered asteroid is being mined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AsteroidLayer {
+    Surface,
+    Core,
+}
+
+/// Composition of a layered asteroid. Mining strips the surface first: the
+/// first `surface_fraction` of `initial_mass_kg` comes out with `surface`
+/// composition, everything after that with `core`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompositionLayers {
+    pub surface_fraction: f32,
+    pub initial_mass_kg: f32,
+    pub surface: CompositionVec,
+    pub core: CompositionVec,
+}
+
+impl CompositionLayers {
+    /// Surface mass still left when the asteroid has `remaining_kg`.
+    pub fn surface_remaining_kg(&self, remaining_kg: f32) -> f32 {
+        let core_kg = self.initial_mass_kg * (1.0 - self.surface_fraction);
+        (remaining_kg - core_kg).max(0.0)
+    }
+
+    /// The layer exposed when the asteroid has `remaining_kg`.
+    pub fn layer_at(&self, remaining_kg: f32) -> AsteroidLayer {
+        if self.surface_remaining_kg(remaining_kg) > 0.0 {
+            AsteroidLayer::Surface
+        } else {
+            AsteroidLayer::Core
+        }
+    }
+
+    /// Composition of `extracted_kg` taken from the top of an asteroid with
+    /// `remaining_kg`, blending across the boundary when the cut reaches
+    /// the core.
+    pub fn extract(&self, remaining_kg: f32, extracted_kg: f32) -> CompositionVec {
+        let from_surface = self.surface_remaining_kg(remaining_kg).min(extracted_kg);
+        let from_core = extracted_kg - from_surface;
+        crate::composition::weighted_composition(&[
+            (&self.surface, from_surface),
+            (&self.core, from_core),
+        ])
+    }
+
+    /// Mass-weighted bulk composition of the whole rock.
+    pub fn bulk(&self) -> CompositionVec {
+        crate::composition::weighted_composition(&[
+            (&self.surface, self.surface_fraction),
+            (&self.core, 1.0 - self.surface_fraction),
+        ])
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -379,6 +863,10 @@ pub struct ShipState {
     /// Cached propellant capacity (kg). Recomputed from hull + tank module modifiers.
     #[serde(default)]
     pub propellant_capacity_kg: f32,
+    /// Hull wear from mining and transits. Lowers mining yield through the
+    /// wear bands; cleared by `DockForRepair` at a maintenance bay.
+    #[serde(default)]
+    pub wear: WearState,
     /// Ship crew roster.
     #[serde(default)]
     pub crew: BTreeMap<CrewRole, u32>,
@@ -394,6 +882,15 @@ pub struct ShipState {
     /// auto-assigned transparently.
     #[serde(default)]
     pub home_station: Option<StationId>,
+    /// Display name assigned at construction. Empty for starting ships.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    /// Ship class from the constructing recipe, if it named one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_id: Option<String>,
+    /// Role hint for controllers (e.g. `"miner"`, `"hauler"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
 }
 
 fn default_hull_id() -> HullId {
@@ -406,6 +903,11 @@ impl ShipState {
         self.speed_ticks_per_au.unwrap_or(global_default)
     }
 
+    /// This ship's class, when it has one defined in content.
+    pub fn class<'a>(&self, content: &'a GameContent) -> Option<&'a crate::ShipClassDef> {
+        content.ship_classes.get(self.class_id.as_ref()?)
+    }
+
     /// Ship mass without propellant or cargo: hull + fitted module masses.
     pub fn dry_mass_kg(&self, content: &GameContent) -> f32 {
         let hull_mass = content.hulls.get(&self.hull_id).map_or(0.0, |h| h.mass_kg);
@@ -439,14 +941,36 @@ pub struct FittedModule {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct PowerState {
     pub generated_kw: f32,
+    /// Energy actually drawn this tick: `idle_kw + active_kw`.
     pub consumed_kw: f32,
+    /// Unmet demand: draw that could not be supplied this tick. Since stalls
+    /// are decided before modules run, this is `stalled_kw + brownout_kw`
+    /// plus any idle-draw shortfall batteries could not cover.
     pub deficit_kw: f32,
+    /// Idle draw of all enabled modules (kW).
+    #[serde(default)]
+    pub idle_kw: f32,
+    /// Per-run draw of modules that completed a run this tick (kW).
+    #[serde(default)]
+    pub active_kw: f32,
     /// Power discharged from batteries this tick (kW).
     pub battery_discharge_kw: f32,
     /// Power stored into batteries this tick (kW).
     pub battery_charge_kw: f32,
     /// Total energy stored across all batteries (kWh).
     pub battery_stored_kwh: f32,
+    /// Run draw of due modules stalled for lack of power this tick (kW).
+    /// Also counted in `deficit_kw`.
+    #[serde(default)]
+    pub stalled_kw: f32,
+    /// Run draw shed by `PowerPolicy::Brownout` this tick (kW). Also
+    /// counted in `deficit_kw`.
+    #[serde(default)]
+    pub brownout_kw: f32,
+    /// Run draw of due `deferrable` modules held back for spare power this
+    /// tick (kW). Not counted in `deficit_kw`.
+    #[serde(default)]
+    pub deferred_kw: f32,
 }
 
 /// Cached power generation/consumption summary. Avoids re-iterating all modules
@@ -458,12 +982,14 @@ pub struct PowerBudgetCache {
     pub(crate) valid: bool,
     /// Sum of solar generation (after wear + solar intensity + modifiers).
     pub generated_kw: f32,
-    /// Sum of all enabled modules' `power_consumption_per_run`.
-    pub consumed_kw: f32,
+    /// Sum of all enabled modules' `power_idle_kw`, plus the per-run draw of
+    /// modules without a run interval (they run every tick).
+    pub idle_kw: f32,
     /// Whether any solar array or battery module exists (gates stall logic).
     pub has_power_infrastructure: bool,
-    /// `(module_index, priority, consumption_kw)` for stall ordering.
-    pub consumers: Vec<(usize, u8, f32)>,
+    /// `(module_index, priority, run_kw, interval_ticks)` for stall ordering.
+    /// Run draw only counts toward demand on ticks the module is due.
+    pub consumers: Vec<(usize, u8, f32, u64)>,
     /// `(module_index, battery_def, efficiency)` for battery buffering.
     pub battery_entries: Vec<(usize, crate::BatteryDef, f32)>,
     /// `(module_index, wear_per_run)` for solar array wear application.
@@ -476,6 +1002,17 @@ pub struct PowerBudgetCache {
     /// Snapshot of `(module_count, enabled_count)` at cache time. Detects direct
     /// state mutations that bypass command handlers.
     pub(crate) module_enabled_snapshot: (usize, usize),
+    /// Per-tick scratch: run draw of due modules stalled for lack of power.
+    pub(crate) stalled_kw: f32,
+    /// Per-tick scratch: run draw of modules that completed this tick.
+    pub(crate) active_kw: f32,
+    /// Per-tick scratch: fraction of due run draw shed under
+    /// `PowerPolicy::Brownout` (0 = fully powered).
+    pub(crate) brownout_shed: f32,
+    /// Per-tick scratch: run draw of due modules shed by a brownout.
+    pub(crate) brownout_kw: f32,
+    /// Per-tick scratch: run draw of due deferrable modules held back.
+    pub(crate) deferred_kw: f32,
 }
 
 impl PowerBudgetCache {
@@ -507,6 +1044,7 @@ pub struct ModuleTypeIndex {
     pub sensors: Vec<usize>,
     pub labs: Vec<usize>,
     pub maintenance: Vec<usize>,
+    pub drone_bays: Vec<usize>,
     /// Modules with a `ThermalDef` (cross-cutting, any behavior type).
     pub thermal: Vec<usize>,
     /// Role name → module indices. Rebuilt on module install/uninstall.
@@ -571,6 +1109,28 @@ pub struct FacilityCore {
     /// when nothing power-relevant has changed.
     #[serde(skip, default)]
     pub power_budget_cache: PowerBudgetCache,
+    /// Open `ProductionReport` windows, keyed by module.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub production_windows: BTreeMap<ModuleInstanceId, ProductionWindow>,
+    /// Whether life support was short last tick. Tracks transitions for
+    /// `LifeSupportFailed`/`LifeSupportRestored`; recomputed every tick.
+    #[serde(skip)]
+    pub life_support_failing: bool,
+}
+
+/// Runs of one processor or assembler folded together until the report
+/// interval elapses.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProductionWindow {
+    /// Tick of the first run in the window.
+    pub start_tick: u64,
+    pub runs: u32,
+    pub input_kg: f32,
+    /// Material produced (processors).
+    pub output_kg: f32,
+    /// Components produced (assemblers).
+    pub output_units: u32,
+    pub quality_sum: f32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -588,6 +1148,53 @@ pub struct StationState {
     /// Station leaders (reserved for Phase 2 leader system).
     #[serde(default)]
     pub leaders: Vec<LeaderId>,
+    /// Principal that owns the station. Ships its shipyards build inherit
+    /// this owner unless the recipe says otherwise.
+    #[serde(default = "default_station_owner")]
+    pub owner: PrincipalId,
+    /// Capacity added by completed construction projects.
+    #[serde(default, skip_serializing_if = "StationExpansions::is_empty")]
+    pub expansions: StationExpansions,
+    /// How due modules share a power shortfall.
+    #[serde(default)]
+    pub power_policy: PowerPolicy,
+    /// Recent significant events at this station, oldest first, capped at
+    /// `constants.station_history_capacity`. Lets a fresh client or a
+    /// loaded save show station history without the event log.
+    #[serde(default, skip_serializing_if = "VecDeque::is_empty")]
+    pub history: VecDeque<crate::EventEnvelope>,
+}
+
+/// Station response to a power shortfall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum PowerPolicy {
+    /// Lowest-priority due modules stall outright until demand fits supply.
+    #[default]
+    Stall,
+    /// Every power-consuming module keeps running at `1 - shed` of its
+    /// draw and efficiency, where `shed` is the fraction of due run draw
+    /// the station cannot supply.
+    Brownout,
+}
+
+/// Permanent additions from completed `ProjectDef`s, on top of the frame.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StationExpansions {
+    #[serde(default)]
+    pub cargo_capacity_m3: f32,
+    /// Slots appended after the frame's own slots, in completion order.
+    #[serde(default)]
+    pub slots: Vec<crate::SlotDef>,
+}
+
+impl StationExpansions {
+    pub fn is_empty(&self) -> bool {
+        self.cargo_capacity_m3 == 0.0 && self.slots.is_empty()
+    }
+}
+
+fn default_station_owner() -> PrincipalId {
+    PrincipalId("principal_autopilot".to_string())
 }
 
 // ---------------------------------------------------------------------------
@@ -719,6 +1326,7 @@ impl FacilityCore {
         idx.sensors.clear();
         idx.labs.clear();
         idx.maintenance.clear();
+        idx.drone_bays.clear();
         idx.thermal.clear();
         idx.roles.clear();
 
@@ -734,6 +1342,7 @@ impl FacilityCore {
                     crate::ModuleBehaviorDef::SensorArray(_) => idx.sensors.push(i),
                     crate::ModuleBehaviorDef::Lab(_) => idx.labs.push(i),
                     crate::ModuleBehaviorDef::Maintenance(_) => idx.maintenance.push(i),
+                    crate::ModuleBehaviorDef::DroneBay(_) => idx.drone_bays.push(i),
                     _ => {}
                 }
                 if def.thermal.is_some() {
@@ -814,14 +1423,19 @@ impl StationState {
 // Research state
 // ---------------------------------------------------------------------------
 
-/// Research distributes automatically to all eligible techs -- no player allocation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Labs research the tech they are assigned, unless `queue` names one that
+/// still needs their domain (see `research::queued_target`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ResearchState {
     pub unlocked: HashSet<TechId>,
     pub data_pool: AHashMap<DataKind, f32>,
     pub evidence: AHashMap<TechId, DomainProgress>,
     #[serde(default)]
     pub action_counts: AHashMap<String, u64>,
+    /// Techs to focus lab output on, in order. Set by
+    /// `Command::SetResearchQueue`; unlocked techs drop out.
+    #[serde(default)]
+    pub queue: Vec<TechId>,
 }
 
 // ---------------------------------------------------------------------------
@@ -851,6 +1465,11 @@ pub enum TaskKind {
     DeepScan {
         asteroid: AsteroidId,
     },
+    /// Ship surveys an already-discovered asteroid again, refining its
+    /// `tag_beliefs`. Takes `survey_scan_ticks`.
+    Resurvey {
+        asteroid: AsteroidId,
+    },
     Mine {
         asteroid: AsteroidId,
         /// Pre-computed mining duration (ticks), computed at task assignment.
@@ -866,6 +1485,12 @@ pub enum TaskKind {
         station_id: StationId,
         target_kg: f32,
     },
+    /// Ship is docked at a station's maintenance bay having its wear
+    /// repaired. Takes `ship_repair_ticks`; repair kits are drawn from the
+    /// station when it completes.
+    DockForRepair {
+        station: StationId,
+    },
     /// Ship is assembling a station kit on-site (VIO-592). Entered after
     /// `Transit` delivers the ship to the target position. On completion,
     /// a new empty `StationState` with `frame_id` is created at `position`
@@ -895,19 +1520,34 @@ pub enum TaskKind {
         items: Vec<crate::TradeItemSpec>,
         then: Box<TaskKind>,
     },
+    /// Ship is moving selected cargo to or from a co-located station or
+    /// ship (`Command::TransferCargo`). Takes `deposit_ticks`.
+    TransferCargo {
+        counterparty: crate::CargoHolder,
+        direction: crate::CargoDirection,
+        items: Vec<crate::CargoSpec>,
+    },
+    /// Ship is guarding `ship` against pirates. Ongoing task — the escort
+    /// travels with its charge and goes idle when the charge next arrives.
+    Escort {
+        ship: ShipId,
+    },
 }
 
 impl TaskKind {
-    /// Task duration in ticks. Returns 0 for ongoing tasks (Refuel).
+    /// Task duration in ticks. Returns 0 for ongoing tasks (Refuel, Escort).
     pub fn duration(&self, constants: &Constants) -> u64 {
         match self {
             Self::Transit { total_ticks, .. } => *total_ticks,
-            Self::Survey { .. } => constants.survey_scan_ticks,
+            Self::Survey { .. } | Self::Resurvey { .. } => constants.survey_scan_ticks,
             Self::DeepScan { .. } => constants.deep_scan_ticks,
             Self::Mine { duration_ticks, .. } => *duration_ticks,
-            Self::Deposit { .. } | Self::Pickup { .. } => constants.deposit_ticks,
+            Self::Deposit { .. } | Self::Pickup { .. } | Self::TransferCargo { .. } => {
+                constants.deposit_ticks
+            }
             Self::ConstructStation { assembly_ticks, .. } => *assembly_ticks,
-            Self::Idle | Self::Refuel { .. } => 0,
+            Self::DockForRepair { .. } => constants.ship_repair_ticks,
+            Self::Idle | Self::Refuel { .. } | Self::Escort { .. } => 0,
         }
     }
 
@@ -918,11 +1558,15 @@ impl TaskKind {
             Self::Transit { .. } => "Transit",
             Self::Survey { .. } => "Survey",
             Self::DeepScan { .. } => "DeepScan",
+            Self::Resurvey { .. } => "Resurvey",
             Self::Mine { .. } => "Mine",
             Self::Deposit { .. } => "Deposit",
             Self::Refuel { .. } => "Refuel",
+            Self::DockForRepair { .. } => "DockForRepair",
             Self::ConstructStation { .. } => "ConstructStation",
             Self::Pickup { .. } => "Pickup",
+            Self::TransferCargo { .. } => "TransferCargo",
+            Self::Escort { .. } => "Escort",
         }
     }
 
@@ -932,14 +1576,22 @@ impl TaskKind {
             Self::Idle => None,
             Self::Transit { destination, .. } => Some(destination.parent_body.0.clone()),
             Self::Survey { site } => Some(site.0.clone()),
-            Self::DeepScan { asteroid } | Self::Mine { asteroid, .. } => Some(asteroid.0.clone()),
+            Self::DeepScan { asteroid }
+            | Self::Resurvey { asteroid }
+            | Self::Mine { asteroid, .. } => Some(asteroid.0.clone()),
             Self::Deposit { station, .. }
+            | Self::DockForRepair { station }
             | Self::Refuel {
                 station_id: station,
                 ..
             } => Some(station.0.clone()),
             Self::ConstructStation { frame_id, .. } => Some(frame_id.0.clone()),
             Self::Pickup { from_station, .. } => Some(from_station.0.clone()),
+            Self::TransferCargo { counterparty, .. } => Some(match counterparty {
+                crate::CargoHolder::Ship(ship_id) => ship_id.0.clone(),
+                crate::CargoHolder::Station(station_id) => station_id.0.clone(),
+            }),
+            Self::Escort { ship } => Some(ship.0.clone()),
         }
     }
 }
@@ -949,7 +1601,7 @@ impl TaskKind {
 // ---------------------------------------------------------------------------
 
 /// Standalone wear state, embedded wherever wear applies.
-/// Generic -- used by station modules now, ships later.
+/// Generic -- used by station modules and ships.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WearState {
     pub wear: f32,
@@ -1231,6 +1883,10 @@ mod tests {
             crew: Default::default(),
             leaders: Vec::new(),
             home_station: Some(StationId("station_alpha".to_string())),
+            name: String::new(),
+            class_id: None,
+            role: None,
+            wear: crate::WearState::default(),
         };
         let json = serde_json::to_string(&ship).expect("serialize");
         let decoded: ShipState = serde_json::from_str(&json).expect("deserialize");
@@ -1263,9 +1919,15 @@ mod tests {
                 module_type_index: crate::ModuleTypeIndex::default(),
                 module_id_index: std::collections::HashMap::new(),
                 power_budget_cache: crate::PowerBudgetCache::default(),
+                production_windows: std::collections::BTreeMap::new(),
+                life_support_failing: false,
             },
             frame_id: Some(FrameId("frame_outpost".to_string())),
             leaders: Vec::new(),
+            owner: PrincipalId("principal_autopilot".to_string()),
+            expansions: crate::StationExpansions::default(),
+            power_policy: crate::PowerPolicy::default(),
+            history: std::collections::VecDeque::new(),
         };
 
         let json = serde_json::to_string(&station).expect("serialize");
@@ -279,6 +279,12 @@ pub struct StrategyConfig {
     /// processor modules. Default 2000.0. Mirrors
     /// `Constants.autopilot_refinery_threshold_kg`.
     pub refinery_threshold_kg: f32,
+    /// Ticks of recent ore inflow an ore processor's threshold aims to
+    /// batch. The autopilot retargets thresholds from observed inflow,
+    /// bounded below by one recipe batch and above by half the station's
+    /// reachable ore capacity. 0 keeps `refinery_threshold_kg` fixed.
+    /// Default 24.
+    pub refinery_batch_ticks: u64,
     /// Cargo fraction at which slag is jettisoned. Default 0.75. Mirrors
     /// `Constants.autopilot_slag_jettison_pct`.
     pub slag_jettison_pct: f32,
@@ -331,6 +337,7 @@ impl Default for StrategyConfig {
             lh2_threshold_kg: 5000.0,
             lh2_abundant_multiplier: 2.0,
             refinery_threshold_kg: 2000.0,
+            refinery_batch_ticks: 24,
             slag_jettison_pct: 0.75,
             export_batch_size_kg: 500.0,
             export_min_revenue: 1_000.0,
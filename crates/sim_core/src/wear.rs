@@ -1,6 +1,9 @@
-//! Wear math — generic across modules and (future) ships.
+//! Wear math — generic across modules and ships.
 
-use crate::Constants;
+use crate::{
+    Constants, EventEnvelope, GameContent, GameState, MaintenanceDef, ModuleBehaviorDef, ShipId,
+    ShipLossCause, StationState, TaskKind,
+};
 
 /// Returns the efficiency multiplier for the given wear level.
 /// Pure function — no mutation.
@@ -25,6 +28,58 @@ pub fn wear_band(wear: f32, constants: &Constants) -> u8 {
     }
 }
 
+/// Add the wear a ship takes for a finished Mine or Transit task, scaled
+/// by the task's length. Wear is capped at 1.0; a ship that reaches it is
+/// lost to hull failure.
+pub(crate) fn accrue_ship_task_wear(
+    state: &mut GameState,
+    ship_id: &ShipId,
+    task_kind: &TaskKind,
+    content: &GameContent,
+    events: &mut Vec<EventEnvelope>,
+) {
+    let constants = &content.constants;
+    #[allow(clippy::cast_precision_loss)]
+    let added = match task_kind {
+        TaskKind::Mine { duration_ticks, .. } => {
+            constants.ship_wear_per_mining_tick * *duration_ticks as f32
+        }
+        TaskKind::Transit { total_ticks, .. } => {
+            constants.ship_wear_per_transit_tick * *total_ticks as f32
+        }
+        _ => return,
+    };
+    if added <= 0.0 {
+        return;
+    }
+    let Some(ship) = state.ships.get_mut(ship_id) else {
+        return;
+    };
+    ship.wear.wear = (ship.wear.wear + added).min(1.0);
+    if ship.wear.wear >= 1.0 {
+        crate::ship_loss::lose_ship(state, ship_id, ShipLossCause::HullFailure, content, events);
+    }
+}
+
+/// The station's first enabled maintenance bay, which services docked
+/// ships. `None` when the station has none.
+pub fn repair_bay<'a>(
+    station: &StationState,
+    content: &'a GameContent,
+) -> Option<&'a MaintenanceDef> {
+    station
+        .core
+        .modules
+        .iter()
+        .filter(|module| module.enabled)
+        .find_map(
+            |module| match &content.module_defs.get(&module.def_id)?.behavior {
+                ModuleBehaviorDef::Maintenance(def) => Some(def),
+                _ => None,
+            },
+        )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
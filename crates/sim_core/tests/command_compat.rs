@@ -0,0 +1,67 @@
+//! Frozen historical command payloads must keep deserializing.
+//!
+//! Fixture files under `tests/fixtures/commands/` are never edited once a
+//! schema version ships; renames are covered by serde aliases instead.
+
+use std::path::PathBuf;
+
+use sim_core::Command;
+
+fn fixture(name: &str) -> serde_json::Value {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/commands")
+        .join(name);
+    let text = std::fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("reading {}: {err}", path.display()));
+    serde_json::from_str(&text).unwrap_or_else(|err| panic!("parsing {}: {err}", path.display()))
+}
+
+fn variant_name(command: &Command) -> String {
+    let value = serde_json::to_value(command).expect("command serializes");
+    match value {
+        serde_json::Value::Object(map) => map.keys().next().cloned().unwrap_or_default(),
+        serde_json::Value::String(name) => name,
+        other => panic!("unexpected command encoding: {other}"),
+    }
+}
+
+#[test]
+fn v1_commands_deserialize() {
+    let serde_json::Value::Array(commands) = fixture("v1.json") else {
+        panic!("v1.json must be an array");
+    };
+    assert!(!commands.is_empty());
+    for raw in commands {
+        let command: Command = serde_json::from_value(raw.clone())
+            .unwrap_or_else(|err| panic!("v1 command no longer parses: {raw}\n{err}"));
+        let expected = raw
+            .as_object()
+            .and_then(|map| map.keys().next().cloned())
+            .expect("fixture is an externally tagged variant");
+        assert_eq!(variant_name(&command), expected);
+    }
+}
+
+#[test]
+fn legacy_aliases_map_to_current_variants() {
+    let serde_json::Value::Array(cases) = fixture("legacy_aliases.json") else {
+        panic!("legacy_aliases.json must be an array");
+    };
+    for case in cases {
+        let command: Command = serde_json::from_value(case["command"].clone())
+            .unwrap_or_else(|err| panic!("legacy command no longer parses: {case}\n{err}"));
+        assert_eq!(variant_name(&command), case["variant"].as_str().unwrap());
+    }
+}
+
+#[test]
+fn command_schema_version_matches_latest_fixture() {
+    let latest = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/commands")
+        .join(format!("v{}.json", sim_core::COMMAND_SCHEMA_VERSION));
+    assert!(
+        latest.exists(),
+        "bump COMMAND_SCHEMA_VERSION together with a frozen {} fixture",
+        latest.display()
+    );
+}
@@ -418,6 +418,9 @@ mod tests {
             thruster_count: 0,
             export_revenue_total: 0.0,
             export_count: 0,
+            loan_outstanding: 0.0,
+            loan_missed_payments: 0,
+            loan_defaulted: 0,
             power_generated_kw: 0.0,
             power_consumed_kw: 0.0,
             power_deficit_kw: 0.0,
@@ -433,6 +436,7 @@ mod tests {
             transfer_count: 0,
             milestones_completed: 0,
             game_phase: 0,
+            power_brownout_kw: 0.0,
         }
     }
 
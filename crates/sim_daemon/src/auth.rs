@@ -0,0 +1,108 @@
+//! Per-principal API keys for the mutating routes.
+//!
+//! `sim_daemon run --api-keys FILE` loads a JSON object mapping each key to
+//! the principal it acts for (`{"<key>": "principal_player", ..}`). Every
+//! POST route then requires `Authorization: Bearer <key>`, and commands sent
+//! with a key are issued by its principal: `issued_by` may be left out and
+//! is refused when it names anyone else. Reads stay open. Without the flag
+//! nothing is checked and commands carry the `issued_by` they name.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{ensure, Context, Result};
+use axum::{
+    extract::{Request, State},
+    http::{header, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use sim_core::PrincipalId;
+
+use crate::state::AppState;
+
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeys {
+    principals: BTreeMap<String, PrincipalId>,
+}
+
+impl ApiKeys {
+    pub fn new(principals: BTreeMap<String, PrincipalId>) -> Self {
+        Self { principals }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("reading API keys: {}", path.display()))?;
+        let principals: BTreeMap<String, PrincipalId> = serde_json::from_str(&text)
+            .with_context(|| format!("parsing API keys: {}", path.display()))?;
+        ensure!(
+            principals.keys().all(|key| !key.trim().is_empty()),
+            "API keys file {} has an empty key",
+            path.display()
+        );
+        Ok(Self::new(principals))
+    }
+
+    pub fn principal(&self, key: &str) -> Option<&PrincipalId> {
+        self.principals.get(key)
+    }
+}
+
+/// The principal whose key authorized the request. Present on POST
+/// requests whenever the daemon has API keys.
+#[derive(Debug, Clone)]
+pub struct Authenticated(pub PrincipalId);
+
+/// Reject POST requests without a known bearer key, and tag the rest with
+/// the key's principal.
+pub async fn require_api_key(
+    State(app_state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(keys) = &app_state.api_keys else {
+        return next.run(request).await;
+    };
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+    let principal = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .and_then(|key| keys.principal(key.trim()))
+        .cloned();
+    let Some(principal) = principal else {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({"error": "missing or unknown API key"})),
+        )
+            .into_response();
+    };
+    request.extensions_mut().insert(Authenticated(principal));
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_maps_keys_to_principals() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys.json");
+        std::fs::write(
+            &path,
+            r#"{"k-one": "principal_player", "k-two": "principal_rival"}"#,
+        )
+        .unwrap();
+        let keys = ApiKeys::load(&path).unwrap();
+        assert_eq!(keys.principal("k-two").unwrap().0, "principal_rival");
+        assert!(keys.principal("k-three").is_none());
+
+        std::fs::write(&path, r#"{" ": "principal_player"}"#).unwrap();
+        assert!(ApiKeys::load(&path).is_err());
+    }
+}
@@ -0,0 +1,56 @@
+//! In-memory checkpoints for rewinding a running daemon.
+//!
+//! A checkpoint holds everything the tick loop needs to replay from a tick:
+//! the full `GameState`, the RNG position and the next command id. The
+//! autopilot's per-agent memory is not captured; a restore starts a fresh
+//! `AutopilotController`, exactly as loading a save does, so restoring the
+//! same checkpoint twice replays identically.
+
+use std::collections::VecDeque;
+
+use serde::Serialize;
+use sim_core::{GameState, RngCheckpoint};
+
+/// Maximum number of checkpoints kept. Each is a full state.
+pub const MAX_CHECKPOINTS: usize = 20;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Checkpoint {
+    pub id: u64,
+    pub tick: u64,
+    pub game_state: GameState,
+    pub rng: RngCheckpoint,
+    pub next_command_id: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct CheckpointStore {
+    checkpoints: VecDeque<Checkpoint>,
+    next_id: u64,
+}
+
+impl CheckpointStore {
+    /// Store a checkpoint, evicting the oldest past `MAX_CHECKPOINTS`.
+    /// Returns its id.
+    pub fn push(&mut self, game_state: GameState, rng: RngCheckpoint, next_command_id: u64) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        if self.checkpoints.len() >= MAX_CHECKPOINTS {
+            self.checkpoints.pop_front();
+        }
+        self.checkpoints.push_back(Checkpoint {
+            id,
+            tick: game_state.meta.tick,
+            game_state,
+            rng,
+            next_command_id,
+        });
+        id
+    }
+
+    pub fn get(&self, id: u64) -> Option<&Checkpoint> {
+        self.checkpoints
+            .iter()
+            .find(|checkpoint| checkpoint.id == id)
+    }
+}
@@ -0,0 +1,128 @@
+//! Recent streamed events, for resuming SSE clients.
+//!
+//! Every event the daemon streams gets a cursor: its position in the run's
+//! event stream, counting from 1. Unlike `EventId`, cursors never go back
+//! when a checkpoint restore rewinds the sim, so a client can always ask for
+//! "everything after cursor N". `/api/v1/stream` frames carry the cursor of
+//! their last event as the SSE `id`, and a reconnect with `Last-Event-ID`
+//! replays what it missed from this log.
+
+use std::collections::VecDeque;
+
+use sim_core::EventEnvelope;
+
+/// Maximum number of events kept for resuming clients.
+pub const MAX_EVENT_LOG: usize = 10_000;
+
+/// One tick's streamed events. `events[i]` has cursor `first_cursor + i`.
+#[derive(Debug, Clone)]
+pub struct EventBatch {
+    pub first_cursor: u64,
+    pub events: Vec<EventEnvelope>,
+}
+
+/// Outcome of asking the log for the events after a cursor.
+#[derive(Debug)]
+pub struct Resume {
+    /// Events after the cursor, oldest first, as `(cursor, event)`.
+    pub events: Vec<(u64, EventEnvelope)>,
+    /// Events after the cursor were evicted; the client missed some.
+    pub gap: bool,
+}
+
+#[derive(Debug)]
+pub struct EventLog {
+    events: VecDeque<EventEnvelope>,
+    /// Cursor of `events[0]`.
+    first_cursor: u64,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self {
+            events: VecDeque::new(),
+            first_cursor: 1,
+        }
+    }
+}
+
+impl EventLog {
+    /// Cursor the next pushed event will get.
+    pub fn next_cursor(&self) -> u64 {
+        self.first_cursor + self.events.len() as u64
+    }
+
+    /// Append a tick's events, evicting the oldest past `MAX_EVENT_LOG`.
+    pub fn push(&mut self, events: Vec<EventEnvelope>) -> EventBatch {
+        let batch = EventBatch {
+            first_cursor: self.next_cursor(),
+            events,
+        };
+        self.events.extend(batch.events.iter().cloned());
+        let excess = self.events.len().saturating_sub(MAX_EVENT_LOG);
+        self.events.drain(..excess);
+        self.first_cursor += excess as u64;
+        batch
+    }
+
+    /// The retained events after `cursor`.
+    pub fn since(&self, cursor: u64) -> Resume {
+        let skip = cursor.saturating_sub(self.first_cursor - 1);
+        let events = (self.first_cursor..)
+            .zip(&self.events)
+            .skip(usize::try_from(skip).unwrap_or(usize::MAX))
+            .map(|(cursor, event)| (cursor, event.clone()))
+            .collect();
+        Resume {
+            events,
+            gap: cursor + 1 < self.first_cursor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim_core::{Event, EventId};
+
+    fn events(ids: std::ops::Range<u64>) -> Vec<EventEnvelope> {
+        ids.map(|id| EventEnvelope {
+            id: EventId(id),
+            tick: id,
+            event: Event::PowerConsumed {
+                station_id: sim_core::StationId("station_earth_orbit".to_string()),
+                amount: 1.0,
+            },
+        })
+        .collect()
+    }
+
+    fn cursors(resume: &Resume) -> Vec<u64> {
+        resume.events.iter().map(|(cursor, _)| *cursor).collect()
+    }
+
+    #[test]
+    fn since_returns_events_after_the_cursor() {
+        let mut log = EventLog::default();
+        assert_eq!(log.push(events(0..3)).first_cursor, 1);
+        assert_eq!(log.push(events(3..5)).first_cursor, 4);
+
+        let resume = log.since(2);
+        assert_eq!(cursors(&resume), [3, 4, 5]);
+        assert!(!resume.gap);
+        assert!(log.since(5).events.is_empty());
+        assert_eq!(cursors(&log.since(0)), [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn evicted_cursor_reports_a_gap() {
+        let mut log = EventLog::default();
+        log.push(events(0..MAX_EVENT_LOG as u64 + 2));
+
+        let resume = log.since(0);
+        assert!(resume.gap);
+        assert_eq!(resume.events.len(), MAX_EVENT_LOG);
+        assert_eq!(resume.events[0].0, 3);
+        assert!(!log.since(2).gap);
+    }
+}
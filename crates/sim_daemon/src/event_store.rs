@@ -0,0 +1,364 @@
+//! Run-long event history in the run directory.
+//!
+//! The SSE resume log keeps only recent events, so anything a client was
+//! not connected for is lost. With a run directory, every streamed event is
+//! also appended to `events.jsonl`, one `EventEnvelope` per line, and
+//! `GET /api/v1/events` reads it back filtered. With `--events-rotate-mb N`
+//! the file is gzipped to `events.<first_tick>-<last_tick>.jsonl.gz` once it
+//! passes N MiB and a new one is started; queries read the rotated segments
+//! too, skipping those that end before `from_tick`. Like the timeline files,
+//! the history is append-only: after a checkpoint restore it keeps the
+//! abandoned ticks, followed by the replayed ones.
+//!
+//! The history holds exactly the streamed events in stream order, so the
+//! Nth line across the files is the event with `event_log` cursor N.
+//! `/api/v1/events/stream` resumes from it when a cursor has already left
+//! the in-memory log.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde_json::Value;
+use sim_core::EventEnvelope;
+
+pub const EVENTS_FILE: &str = "events.jsonl";
+
+/// Events returned by a query when it sets no `limit`.
+pub const DEFAULT_QUERY_LIMIT: usize = 1_000;
+
+/// Most events one query may return.
+pub const MAX_QUERY_LIMIT: usize = 10_000;
+
+/// Which events a query keeps. Unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    /// Keep events at or after this tick.
+    pub from_tick: Option<u64>,
+    /// `Event` variant name, e.g. `OreMined`.
+    pub kind: Option<String>,
+    /// Keep events whose payload has this `ship_id`.
+    pub ship_id: Option<String>,
+}
+
+impl EventFilter {
+    fn matches(&self, envelope: &Value) -> bool {
+        if self
+            .from_tick
+            .is_some_and(|from| envelope["tick"].as_u64().is_none_or(|tick| tick < from))
+        {
+            return false;
+        }
+        let Some((kind, payload)) = envelope["event"]
+            .as_object()
+            .and_then(|event| event.iter().next())
+        else {
+            return false;
+        };
+        if self.kind.as_ref().is_some_and(|wanted| wanted != kind) {
+            return false;
+        }
+        self.ship_id
+            .as_ref()
+            .is_none_or(|ship| payload["ship_id"].as_str() == Some(ship.as_str()))
+    }
+}
+
+/// Matching events, oldest first.
+#[derive(Debug)]
+pub struct EventQuery {
+    pub events: Vec<Value>,
+    /// More events matched than the limit allowed.
+    pub truncated: bool,
+}
+
+/// A file of persisted events and the ticks it covers.
+#[derive(Debug, Clone)]
+pub struct EventFile {
+    pub path: PathBuf,
+    /// Last tick in the file, for rotated segments. The open file has none.
+    last_tick: Option<u64>,
+    /// Events in the file when it was listed.
+    events: u64,
+}
+
+pub struct EventStore {
+    dir: PathBuf,
+    /// The open `events.jsonl`, written a tick at a time.
+    file: File,
+    /// Rotate once the open file passes this many bytes.
+    rotate_bytes: Option<u64>,
+    written_bytes: u64,
+    /// Events in the open file.
+    written_events: u64,
+    /// Ticks of the first and last event in the open file.
+    ticks: Option<(u64, u64)>,
+    rotated: Vec<EventFile>,
+}
+
+impl EventStore {
+    /// Start `events.jsonl` in `run_dir`. `rotate_mb` of 0 never rotates.
+    pub fn create(run_dir: &Path, rotate_mb: u64) -> Result<Self> {
+        let path = run_dir.join(EVENTS_FILE);
+        let file = File::create(&path)
+            .with_context(|| format!("creating event history: {}", path.display()))?;
+        Ok(Self {
+            dir: run_dir.to_path_buf(),
+            file,
+            rotate_bytes: (rotate_mb > 0).then(|| rotate_mb.saturating_mul(1024 * 1024)),
+            written_bytes: 0,
+            written_events: 0,
+            ticks: None,
+            rotated: Vec::new(),
+        })
+    }
+
+    /// Append one tick's events, rotating the file if it has grown past the
+    /// limit.
+    pub fn append(&mut self, events: &[EventEnvelope]) -> Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        let mut lines = Vec::new();
+        for envelope in events {
+            serde_json::to_writer(&mut lines, envelope).context("serializing event")?;
+            lines.push(b'\n');
+            let (first, _) = self.ticks.unwrap_or((envelope.tick, envelope.tick));
+            self.ticks = Some((first, envelope.tick));
+        }
+        self.file
+            .write_all(&lines)
+            .context("writing event history")?;
+        self.written_bytes += lines.len() as u64;
+        self.written_events += events.len() as u64;
+        if self
+            .rotate_bytes
+            .is_some_and(|limit| self.written_bytes >= limit)
+        {
+            self.rotate()?;
+        }
+        Ok(())
+    }
+
+    /// Gzip the open file into a tick-named segment and start a new one.
+    fn rotate(&mut self) -> Result<()> {
+        let Some((first, last)) = self.ticks.take() else {
+            return Ok(());
+        };
+        let open = self.dir.join(EVENTS_FILE);
+        let segment = self
+            .dir
+            .join(format!("events.{first:010}-{last:010}.jsonl.gz"));
+        let mut encoder = GzEncoder::new(
+            File::create(&segment).with_context(|| format!("creating {}", segment.display()))?,
+            Compression::default(),
+        );
+        std::io::copy(
+            &mut File::open(&open).with_context(|| format!("reading {}", open.display()))?,
+            &mut encoder,
+        )
+        .and_then(|_| encoder.finish())
+        .with_context(|| format!("compressing {}", segment.display()))?;
+        self.file =
+            File::create(&open).with_context(|| format!("restarting {}", open.display()))?;
+        self.written_bytes = 0;
+        self.rotated.push(EventFile {
+            path: segment,
+            last_tick: Some(last),
+            events: std::mem::take(&mut self.written_events),
+        });
+        Ok(())
+    }
+
+    /// Files a query should read, oldest first. They can be read without the
+    /// store: each tick is written in one call, and a torn last line is
+    /// skipped.
+    pub fn files(&self) -> Vec<EventFile> {
+        let mut files = self.rotated.clone();
+        files.push(EventFile {
+            path: self.dir.join(EVENTS_FILE),
+            last_tick: None,
+            events: self.written_events,
+        });
+        files
+    }
+}
+
+fn open_lines(file: &EventFile) -> Result<impl Iterator<Item = std::io::Result<String>>> {
+    let raw = File::open(&file.path).with_context(|| format!("opening {}", file.path.display()))?;
+    let reader: Box<dyn Read> = if file.path.extension().is_some_and(|ext| ext == "gz") {
+        Box::new(GzDecoder::new(raw))
+    } else {
+        Box::new(raw)
+    };
+    Ok(BufReader::new(reader).lines())
+}
+
+/// Read the events in `files` that match `filter`, up to `limit`.
+pub fn query(files: &[EventFile], filter: &EventFilter, limit: usize) -> Result<EventQuery> {
+    let mut events = Vec::new();
+    for file in files {
+        if filter
+            .from_tick
+            .zip(file.last_tick)
+            .is_some_and(|(from, last)| last < from)
+        {
+            continue;
+        }
+        for line in open_lines(file)? {
+            let line = line.with_context(|| format!("reading {}", file.path.display()))?;
+            let Ok(envelope) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            if !filter.matches(&envelope) {
+                continue;
+            }
+            if events.len() == limit {
+                return Ok(EventQuery {
+                    events,
+                    truncated: true,
+                });
+            }
+            events.push(envelope);
+        }
+    }
+    Ok(EventQuery {
+        events,
+        truncated: false,
+    })
+}
+
+/// The events with cursors `after + 1 ..= through`, as `(cursor, event)`,
+/// skipping whole segments that end at or before `after`.
+pub fn read_cursors(files: &[EventFile], after: u64, through: u64) -> Result<Vec<(u64, Value)>> {
+    let mut events = Vec::new();
+    let mut first_cursor = 1;
+    for file in files {
+        if first_cursor > through {
+            break;
+        }
+        if first_cursor + file.events <= after + 1 {
+            first_cursor += file.events;
+            continue;
+        }
+        for (cursor, line) in (first_cursor..=through).zip(open_lines(file)?) {
+            let line = line.with_context(|| format!("reading {}", file.path.display()))?;
+            if cursor <= after {
+                continue;
+            }
+            let envelope = serde_json::from_str(&line)
+                .with_context(|| format!("parsing event {cursor} in {}", file.path.display()))?;
+            events.push((cursor, envelope));
+        }
+        first_cursor += file.events;
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim_core::{Event, EventId, ShipId, StationId};
+
+    fn power_consumed(tick: u64, station: &str) -> EventEnvelope {
+        EventEnvelope {
+            id: EventId(tick),
+            tick,
+            event: Event::PowerConsumed {
+                station_id: StationId(station.to_string()),
+                amount: 1.0,
+            },
+        }
+    }
+
+    fn arrived(tick: u64, ship: &str) -> EventEnvelope {
+        EventEnvelope {
+            id: EventId(tick),
+            tick,
+            event: Event::ShipArrived {
+                ship_id: ShipId(ship.to_string()),
+                position: sim_core::test_fixtures::test_position(),
+            },
+        }
+    }
+
+    #[test]
+    fn filters_by_tick_kind_and_ship() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = EventStore::create(dir.path(), 0).unwrap();
+        for tick in 0..6 {
+            store
+                .append(&[power_consumed(tick, "station_a"), arrived(tick, "ship_a")])
+                .unwrap();
+        }
+        store.append(&[arrived(6, "ship_b")]).unwrap();
+
+        let filter = EventFilter {
+            from_tick: Some(3),
+            kind: Some("ShipArrived".to_string()),
+            ship_id: Some("ship_a".to_string()),
+        };
+        let result = query(&store.files(), &filter, DEFAULT_QUERY_LIMIT).unwrap();
+        let ticks: Vec<u64> = result
+            .events
+            .iter()
+            .map(|e| e["tick"].as_u64().unwrap())
+            .collect();
+        assert_eq!(ticks, [3, 4, 5]);
+        assert!(!result.truncated);
+
+        let limited = query(&store.files(), &EventFilter::default(), 4).unwrap();
+        assert_eq!(limited.events.len(), 4);
+        assert!(limited.truncated);
+    }
+
+    #[test]
+    fn read_cursors_spans_rotated_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = EventStore::create(dir.path(), 1).unwrap();
+        store.rotate_bytes = Some(200);
+        for tick in 0..10 {
+            store.append(&[arrived(tick, "ship_a")]).unwrap();
+        }
+        assert!(store.rotated.len() > 1);
+
+        let events = read_cursors(&store.files(), 3, 8).unwrap();
+        let cursors: Vec<u64> = events.iter().map(|(cursor, _)| *cursor).collect();
+        assert_eq!(cursors, [4, 5, 6, 7, 8]);
+        let ticks: Vec<u64> = events
+            .iter()
+            .map(|(_, e)| e["tick"].as_u64().unwrap())
+            .collect();
+        assert_eq!(ticks, [3, 4, 5, 6, 7], "cursor N is the Nth event written");
+        assert!(read_cursors(&store.files(), 10, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rotated_segments_are_gzipped_and_still_queried() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = EventStore::create(dir.path(), 1).unwrap();
+        store.rotate_bytes = Some(200);
+        for tick in 0..10 {
+            store.append(&[arrived(tick, "ship_a")]).unwrap();
+        }
+        assert!(!store.rotated.is_empty());
+        assert!(store
+            .rotated
+            .iter()
+            .all(|file| file.path.extension().is_some_and(|ext| ext == "gz")));
+
+        let all = query(&store.files(), &EventFilter::default(), MAX_QUERY_LIMIT).unwrap();
+        assert_eq!(all.events.len(), 10);
+
+        let late = EventFilter {
+            from_tick: Some(9),
+            ..EventFilter::default()
+        };
+        let result = query(&store.files(), &late, MAX_QUERY_LIMIT).unwrap();
+        assert_eq!(result.events.len(), 1);
+    }
+}
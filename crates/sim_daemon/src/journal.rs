@@ -0,0 +1,233 @@
+//! Write-ahead journal of externally submitted commands.
+//!
+//! Commands posted to the daemon are handed to the sim on the next tick,
+//! which holds future-dated ones in `GameState.pending_commands` until their
+//! `execute_at_tick`. A save therefore carries the plans scheduled before it,
+//! but not the ones accepted after it. Each accepted envelope is appended to
+//! `commands.jsonl` in the run directory before it is queued.
+//! `sim_daemon run --state` reads the journal of the run the save came from
+//! and re-queues the commands the saved state has not executed yet
+//! (`execute_at_tick` at or after its tick), skipping any the save already
+//! holds and any an executed `CancelCommand` removed. A restore to an
+//! in-memory checkpoint drops the queue, and the journal records that with
+//! a `cleared` entry.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sim_core::{Command, CommandEnvelope, CommandId};
+
+pub const COMMAND_JOURNAL_FILE: &str = "commands.jsonl";
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum JournalEntry {
+    Accepted {
+        envelope: CommandEnvelope,
+    },
+    /// Everything accepted before this was dropped from the queue.
+    Cleared {
+        tick: u64,
+    },
+}
+
+pub struct CommandJournal {
+    path: PathBuf,
+    file: File,
+}
+
+impl CommandJournal {
+    /// Start a journal in `run_dir`, replacing any existing one.
+    pub fn create(run_dir: &Path) -> Result<Self> {
+        let path = run_dir.join(COMMAND_JOURNAL_FILE);
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&path)
+            .with_context(|| format!("creating command journal: {}", path.display()))?;
+        Ok(Self { path, file })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Durably record `envelopes` as accepted. Call before queueing them.
+    pub fn append(&mut self, envelopes: &[CommandEnvelope]) -> Result<()> {
+        let entries: Vec<JournalEntry> = envelopes
+            .iter()
+            .cloned()
+            .map(|envelope| JournalEntry::Accepted { envelope })
+            .collect();
+        self.write(&entries)
+    }
+
+    /// Record that the queue was dropped at `tick`.
+    pub fn clear(&mut self, tick: u64) -> Result<()> {
+        self.write(&[JournalEntry::Cleared { tick }])
+    }
+
+    fn write(&mut self, entries: &[JournalEntry]) -> Result<()> {
+        let mut lines = Vec::new();
+        for entry in entries {
+            serde_json::to_writer(&mut lines, entry).context("serializing journal entry")?;
+            lines.push(b'\n');
+        }
+        self.file
+            .write_all(&lines)
+            .and_then(|()| self.file.sync_data())
+            .with_context(|| format!("writing command journal: {}", self.path.display()))
+    }
+}
+
+/// The journal that belongs with a save written by `POST /api/v1/save`
+/// (`<run_dir>/saves/save_<tick>.json`), if there is one.
+pub fn journal_for_save(save: &Path) -> Option<PathBuf> {
+    let saves_dir = save.parent()?;
+    if saves_dir.file_name()? != "saves" {
+        return None;
+    }
+    let journal = saves_dir.parent()?.join(COMMAND_JOURNAL_FILE);
+    journal.is_file().then_some(journal)
+}
+
+/// Commands in the journal at `path` still pending for a state at `tick`,
+/// in submission order. A torn final line, left by a crash mid-append, is
+/// skipped; that command was never acknowledged.
+pub fn load_pending(path: &Path, tick: u64) -> Result<Vec<CommandEnvelope>> {
+    let file =
+        File::open(path).with_context(|| format!("opening command journal: {}", path.display()))?;
+    let lines: Vec<String> = BufReader::new(file)
+        .lines()
+        .collect::<std::io::Result<_>>()
+        .with_context(|| format!("reading command journal: {}", path.display()))?;
+    let mut pending = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = match serde_json::from_str::<JournalEntry>(line) {
+            Ok(entry) => entry,
+            Err(err) if index + 1 == lines.len() => {
+                tracing::warn!("skipping torn last line of {}: {err}", path.display());
+                break;
+            }
+            Err(err) => {
+                return Err(err).with_context(|| {
+                    format!("command journal {} line {}", path.display(), index + 1)
+                })
+            }
+        };
+        match entry {
+            JournalEntry::Accepted { envelope } => pending.push(envelope),
+            JournalEntry::Cleared { .. } => pending.clear(),
+        }
+    }
+    let cancelled: Vec<CommandId> = pending
+        .iter()
+        .filter(|envelope| envelope.execute_at_tick < tick)
+        .filter_map(|envelope| match envelope.command {
+            Command::CancelCommand { command_id } => Some(command_id),
+            _ => None,
+        })
+        .collect();
+    pending
+        .retain(|envelope| envelope.execute_at_tick >= tick && !cancelled.contains(&envelope.id));
+    Ok(pending)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim_core::PrincipalId;
+
+    fn envelope(id: u64, execute_at_tick: u64) -> CommandEnvelope {
+        CommandEnvelope {
+            id: CommandId(id),
+            issued_by: PrincipalId("principal_agent".to_string()),
+            issued_tick: 0,
+            execute_at_tick,
+            command: Command::ReleaseClaim {
+                body_id: sim_core::BodyId("belt_inner".to_string()),
+            },
+        }
+    }
+
+    fn ids(envelopes: &[CommandEnvelope]) -> Vec<u64> {
+        envelopes.iter().map(|e| e.id.0).collect()
+    }
+
+    #[test]
+    fn pending_commands_survive_reload_and_respect_clears() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut journal = CommandJournal::create(dir.path()).unwrap();
+        journal.append(&[envelope(0, 5), envelope(1, 50)]).unwrap();
+        journal.clear(10).unwrap();
+        journal
+            .append(&[envelope(2, 20), envelope(3, 40), envelope(4, 30)])
+            .unwrap();
+
+        let pending = load_pending(journal.path(), 30).unwrap();
+
+        assert_eq!(ids(&pending), [3, 4]);
+    }
+
+    #[test]
+    fn executed_cancels_drop_their_targets() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut journal = CommandJournal::create(dir.path()).unwrap();
+        let cancel = |id: u64, target: u64, execute_at_tick: u64| CommandEnvelope {
+            command: Command::CancelCommand {
+                command_id: CommandId(target),
+            },
+            ..envelope(id, execute_at_tick)
+        };
+        journal
+            .append(&[
+                envelope(0, 40),
+                envelope(1, 50),
+                cancel(2, 0, 10),
+                cancel(3, 1, 35),
+            ])
+            .unwrap();
+
+        let pending = load_pending(journal.path(), 30).unwrap();
+
+        assert_eq!(ids(&pending), [1, 3]);
+    }
+
+    #[test]
+    fn torn_last_line_is_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut journal = CommandJournal::create(dir.path()).unwrap();
+        journal.append(&[envelope(0, 5)]).unwrap();
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(journal.path())
+            .unwrap();
+        file.write_all(br#"{"op":"accepted","envel"#).unwrap();
+
+        let pending = load_pending(journal.path(), 0).unwrap();
+
+        assert_eq!(ids(&pending), [0]);
+    }
+
+    #[test]
+    fn journal_is_found_next_to_daemon_saves() {
+        let dir = tempfile::tempdir().unwrap();
+        let saves = dir.path().join("saves");
+        std::fs::create_dir(&saves).unwrap();
+        let save = saves.join("save_100.json");
+        assert_eq!(journal_for_save(&save), None);
+
+        CommandJournal::create(dir.path()).unwrap();
+        assert_eq!(
+            journal_for_save(&save),
+            Some(dir.path().join(COMMAND_JOURNAL_FILE))
+        );
+    }
+}
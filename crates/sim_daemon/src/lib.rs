@@ -0,0 +1,21 @@
+//! `sim_daemon` — HTTP server around a running simulation.
+//!
+//! The binary in `main.rs` wires these modules to CLI flags. They are also
+//! exposed as a library so `sim_tests` can drive the router and tick loop
+//! in-process.
+
+pub mod alerts;
+mod analytics;
+pub mod auth;
+pub mod autosave;
+pub mod checkpoint;
+pub mod event_log;
+pub mod event_store;
+pub mod journal;
+pub mod pause;
+pub mod routes;
+pub mod runs;
+pub mod snapshot_delta;
+pub mod state;
+pub mod tick_loop;
+pub mod timeline;
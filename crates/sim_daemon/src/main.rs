@@ -1,22 +1,19 @@
-mod alerts;
-mod analytics;
-mod routes;
-mod state;
-mod tick_loop;
-
-use routes::make_router_with_cors;
-use sim_world::RunSetupBuilder;
-use state::{AppState, SimState};
-use tick_loop::run_tick_loop;
+use sim_daemon::routes::make_router_with_cors;
+use sim_daemon::runs::RunLauncher;
+use sim_daemon::state::{AppState, EventTx, SimState};
+use sim_daemon::tick_loop::run_tick_loop;
+use sim_daemon::{alerts, autosave, checkpoint, journal, timeline};
+use sim_world::{OrphanedModulePolicy, RunSetupBuilder};
 use tracing::info;
 
 use anyhow::Result;
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use parking_lot::Mutex;
-use sim_control::AutopilotController;
-use sim_core::EventEnvelope;
+use sim_control::{AutopilotController, ControllerProfile};
+use sim_core::{CommandEnvelope, EventLevel};
 use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64};
 use std::sync::Arc;
 use tokio::sync::broadcast;
@@ -30,131 +27,367 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
-    Run {
-        /// Generate world procedurally with this seed. Mutually exclusive with --state.
-        #[arg(long, conflicts_with = "state_file")]
-        seed: Option<u64>,
-        /// Load initial `GameState` from a JSON file. Mutually exclusive with --seed.
-        #[arg(long = "state", conflicts_with = "seed")]
-        state_file: Option<String>,
-        #[arg(long, default_value = "./content")]
-        content_dir: String,
-        #[arg(long, default_value_t = 3001)]
-        port: u16,
-        /// Ticks per second. 0 = as fast as possible.
-        #[arg(long, default_value_t = 10.0)]
-        ticks_per_sec: f64,
-        #[arg(long)]
-        max_ticks: Option<u64>,
-        /// Sample metrics every N ticks (default 60). 0 = disabled.
-        #[arg(long, default_value_t = 60)]
-        metrics_every: u64,
-        /// Disable automatic metrics collection to runs/ directory.
-        #[arg(long)]
-        no_metrics: bool,
-        /// CORS allowed origin (default: `http://localhost:5173`).
-        #[arg(long, default_value = "http://localhost:5173")]
-        cors_origin: String,
-        /// Start the simulation in a paused state.
-        #[arg(long)]
-        paused: bool,
-    },
+    Run(RunArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Generate world procedurally with this seed. Mutually exclusive with --state.
+    #[arg(long, conflicts_with = "state_file")]
+    seed: Option<u64>,
+    /// Load initial `GameState` from a JSON file. Mutually exclusive with --seed.
+    #[arg(long = "state", conflicts_with = "seed")]
+    state_file: Option<String>,
+    /// Continue from the `autosave.json` in this run directory, RNG
+    /// included, in a new run directory. Mutually exclusive with --seed
+    /// and --state.
+    #[arg(long, conflicts_with_all = ["seed", "state_file"])]
+    resume: Option<PathBuf>,
+    /// Command journal to resume pending commands from. Defaults to the
+    /// `commands.jsonl` of the run a `saves/` --state file or --resume
+    /// directory came from.
+    #[arg(long)]
+    command_journal: Option<String>,
+    /// How to handle saved modules whose def no longer exists in content:
+    /// error, scrap (refund to balance), or quarantine (disable).
+    #[arg(long, default_value = "quarantine")]
+    orphaned_modules: OrphanedModulePolicy,
+    #[arg(long, default_value = "./content")]
+    content_dir: String,
+    /// Content pack layered over --content-dir (repeatable; later packs
+    /// override earlier ones, merging defs by id).
+    #[arg(long = "content-pack")]
+    content_packs: Vec<String>,
+    #[arg(long, default_value_t = 3001)]
+    port: u16,
+    /// Ticks per second. 0 = as fast as possible.
+    #[arg(long, default_value_t = 10.0)]
+    ticks_per_sec: f64,
+    #[arg(long)]
+    max_ticks: Option<u64>,
+    /// Sample metrics every N ticks (default 60). 0 = disabled.
+    #[arg(long, default_value_t = 60)]
+    metrics_every: u64,
+    /// Disable automatic metrics collection to runs/ directory.
+    #[arg(long)]
+    no_metrics: bool,
+    /// Metrics files in the run directory: csv, parquet or both. The
+    /// Parquet file is completed when the run ends or is replaced.
+    #[arg(long, default_value = "csv")]
+    metrics_format: sim_world::MetricsFormat,
+    /// Record `GameState::stable_hash` every N ticks to `state_hashes.csv`
+    /// in the run directory. Also served at `/api/v1/state-hash`.
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    hash_every: Option<u64>,
+    /// Gzip `events.jsonl` into a tick-named segment once it passes N MiB.
+    /// 0 = never rotate.
+    #[arg(long, default_value_t = 0)]
+    events_rotate_mb: u64,
+    /// Write `autosave.json` to the run directory every N ticks. 0 = off.
+    #[arg(long, default_value_t = 0)]
+    autosave_every: u64,
+    /// Write `autosave.json` once N seconds have passed since the last
+    /// one. 0 = off.
+    #[arg(long, default_value_t = 0)]
+    autosave_secs: u64,
+    /// JSON file mapping API keys to principals. When set, POST routes
+    /// require `Authorization: Bearer <key>` and commands are issued by the
+    /// key's principal.
+    #[arg(long)]
+    api_keys: Option<PathBuf>,
+    /// CORS allowed origin (default: `http://localhost:5173`).
+    #[arg(long, default_value = "http://localhost:5173")]
+    cors_origin: String,
+    /// Start the simulation in a paused state.
+    #[arg(long)]
+    paused: bool,
+    /// Fold events into compacted timeline segments of N ticks. 0 = disabled.
+    #[arg(long, default_value_t = timeline::DEFAULT_SEGMENT_TICKS)]
+    timeline_every: u64,
+    /// Take a full-state keyframe every N timeline segments. 0 = never.
+    #[arg(long, default_value_t = timeline::DEFAULT_KEYFRAME_EVERY)]
+    keyframe_every: u64,
+    /// Drop events below this level (`debug` or `info`) from the stream.
+    /// `info` hides per-run `RefineryRan`/`AssemblerRan` events.
+    #[arg(long, default_value = "debug", value_parser = parse_event_level)]
+    event_level: EventLevel,
+    /// Autopilot profile: balanced, aggressive-miner, research-first, or
+    /// trade-focused. Kept across checkpoint restores.
+    #[arg(long, default_value = "balanced")]
+    controller: ControllerProfile,
+    #[command(flatten)]
+    experiment: ExperimentArgs,
+}
+
+/// Experiment notebook metadata recorded in `run_info.json`.
+#[derive(Args)]
+struct ExperimentArgs {
+    /// What this run is meant to show.
+    #[arg(long)]
+    hypothesis: Option<String>,
+    /// Free-form notes.
+    #[arg(long)]
+    notes: Option<String>,
+    /// Content git commit (default: detected from --content-dir).
+    #[arg(long)]
+    content_commit: Option<String>,
+}
+
+impl ExperimentArgs {
+    fn into_meta(self, content_dir: &str) -> sim_world::ExperimentMeta {
+        sim_world::ExperimentMeta {
+            hypothesis: self.hypothesis,
+            notes: self.notes,
+            content_commit: self.content_commit,
+        }
+        .with_detected_commit(content_dir)
+    }
+}
+
+fn describe_speed(ticks_per_sec: f64) -> String {
+    if ticks_per_sec == 0.0 {
+        "max".to_string()
+    } else {
+        format!("{ticks_per_sec} ticks/sec")
+    }
+}
+
+fn parse_event_level(value: &str) -> Result<EventLevel, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .map_err(|_| format!("unknown event level '{value}' (expected debug or info)"))
+}
+
+fn init_tracing() {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::from_default_env()
+                .add_directive("sim_daemon=info".parse().expect("valid tracing directive")),
+        )
+        .init();
+}
+
+/// Start this run's command journal, carrying over the commands from
+/// `resume_from` that a state at `tick` has not executed yet and does not
+/// already hold in `scheduled`.
+fn open_command_journal(
+    resume_from: Option<&Path>,
+    tick: u64,
+    scheduled: &[CommandEnvelope],
+    run_dir: Option<&Path>,
+) -> Result<(Option<journal::CommandJournal>, Vec<CommandEnvelope>)> {
+    let pending = match resume_from {
+        Some(path) => {
+            let mut pending = journal::load_pending(path, tick)?;
+            pending.retain(|envelope| !scheduled.iter().any(|held| held.id == envelope.id));
+            info!(
+                "Resumed {} pending command(s) from {}",
+                pending.len(),
+                path.display()
+            );
+            pending
+        }
+        None => Vec::new(),
+    };
+    let Some(dir) = run_dir else {
+        return Ok((None, pending));
+    };
+    let mut journal = journal::CommandJournal::create(dir)?;
+    journal.append(&pending)?;
+    Ok((Some(journal), pending))
+}
+
+/// Start the tick loop and serve the HTTP API until shutdown.
+async fn serve(
+    app_state: AppState,
+    event_tx: EventTx,
+    cors_origin: &str,
+    port: u16,
+    ticks_per_sec: f64,
+    max_ticks: Option<u64>,
+) -> Result<()> {
+    let router = make_router_with_cors(app_state.clone(), cors_origin)?;
+    let addr = std::net::SocketAddr::from(([0, 0, 0, 0], port));
+    info!(
+        "sim_daemon listening on http://localhost:{port}  speed={}",
+        describe_speed(ticks_per_sec)
+    );
+    tokio::spawn(run_tick_loop(
+        app_state.sim,
+        app_state.command_queue.clone(),
+        event_tx,
+        app_state.ticks_per_sec.clone(),
+        max_ticks,
+        app_state.paused.clone(),
+    ));
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, router).await?;
+    Ok(())
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
     match cli.command {
-        Commands::Run {
-            seed,
-            state_file,
-            content_dir,
-            port,
-            ticks_per_sec,
-            max_ticks,
-            metrics_every,
-            no_metrics,
-            cors_origin,
-            paused,
-        } => {
-            tracing_subscriber::fmt()
-                .with_env_filter(
-                    tracing_subscriber::EnvFilter::from_default_env()
-                        .add_directive("sim_daemon=info".parse().expect("valid tracing directive")),
-                )
-                .init();
-
-            let mut builder = RunSetupBuilder::from_content_dir(&content_dir)?
-                .seed(seed)
-                .state_file(state_file);
-            if !no_metrics {
-                builder = builder.metrics(
-                    metrics_every,
-                    serde_json::json!({
-                        "runner": "sim_daemon",
-                        "max_ticks": max_ticks,
-                    }),
-                );
-            }
-            let setup = builder.build()?;
-            if let Some(ref dir) = setup.run_dir {
-                info!("Run directory: {}", dir.display());
-            }
+        Commands::Run(args) => run(args).await,
+    }
+}
 
-            let alert_engine = if no_metrics {
-                None
-            } else {
-                Some(alerts::AlertEngine::new(
-                    &setup.content.alert_rules,
-                    setup.content.techs.len(),
-                ))
-            };
-
-            let (event_tx, _) = broadcast::channel::<Vec<EventEnvelope>>(256);
-            let ticks_per_sec_atomic = Arc::new(AtomicU64::new(ticks_per_sec.to_bits()));
-            let run_dir = setup.run_dir;
-            let app_state = AppState {
-                sim: Arc::new(Mutex::new(SimState {
-                    game_state: setup.game_state,
-                    content: setup.content,
-                    rng: setup.rng,
-                    autopilot: AutopilotController::new(),
-                    next_command_id: 0,
-                    metrics_every,
-                    metrics_history: VecDeque::new(),
-                    metrics_writer: setup.metrics_writer,
-                    alert_engine,
-                    timings_history: VecDeque::new(),
-                    score_history: VecDeque::new(),
-                    last_threshold: String::new(),
-                })),
-                command_queue: Arc::new(Mutex::new(Vec::new())),
-                event_tx: event_tx.clone(),
-                ticks_per_sec: ticks_per_sec_atomic,
-                run_dir,
-                paused: Arc::new(AtomicBool::new(paused)),
-            };
-            let router = make_router_with_cors(app_state.clone(), &cors_origin)?;
-            let addr = std::net::SocketAddr::from(([0, 0, 0, 0], port));
-            let speed = if ticks_per_sec == 0.0 {
-                "max".to_string()
-            } else {
-                format!("{ticks_per_sec} ticks/sec")
-            };
-            info!("sim_daemon listening on http://localhost:{port}  speed={speed}");
-            tokio::spawn(run_tick_loop(
-                app_state.sim,
-                app_state.command_queue.clone(),
-                event_tx,
-                app_state.ticks_per_sec.clone(),
-                max_ticks,
-                app_state.paused.clone(),
-            ));
-            let listener = tokio::net::TcpListener::bind(addr).await?;
-            axum::serve(listener, router).await?;
-        }
+#[allow(clippy::too_many_lines)] // Flag-by-flag startup wiring
+async fn run(args: RunArgs) -> Result<()> {
+    init_tracing();
+
+    let api_keys = args
+        .api_keys
+        .as_deref()
+        .map(sim_daemon::auth::ApiKeys::load)
+        .transpose()?
+        .map(Arc::new);
+
+    let content_dirs: Vec<&str> = std::iter::once(args.content_dir.as_str())
+        .chain(args.content_packs.iter().map(String::as_str))
+        .collect();
+    let resume_journal = args
+        .command_journal
+        .map(PathBuf::from)
+        .or_else(|| match &args.resume {
+            Some(dir) => {
+                Some(dir.join(journal::COMMAND_JOURNAL_FILE)).filter(|path| path.is_file())
+            }
+            None => args
+                .state_file
+                .as_deref()
+                .and_then(|save| journal::journal_for_save(Path::new(save))),
+        });
+    let mut builder = RunSetupBuilder::from_content_packs(&content_dirs)?
+        .seed(args.seed)
+        .state_file(args.state_file)
+        .orphan_policy(args.orphaned_modules);
+    let mut resumed_command_id = None;
+    if let Some(ref dir) = args.resume {
+        let saved = autosave::load(dir)?;
+        info!(
+            "Resuming {} at tick {}",
+            dir.display(),
+            saved.game_state.meta.tick
+        );
+        resumed_command_id = Some(saved.next_command_id);
+        builder = builder.resume_from(saved.game_state, saved.rng);
     }
-    Ok(())
+    if !args.no_metrics {
+        builder = builder.metrics(
+            args.metrics_every,
+            serde_json::json!({
+                "runner": "sim_daemon",
+                "max_ticks": args.max_ticks,
+                "controller": args.controller.name(),
+                "metrics_format": args.metrics_format,
+                "resumed_from": args.resume,
+            }),
+        );
+        builder = builder.metrics_format(args.metrics_format);
+        builder = builder.experiment(args.experiment.into_meta(&args.content_dir));
+    }
+    let setup = builder.build()?;
+    sim_daemon::runs::log_load_warnings(&setup.warnings);
+    if let Some(ref dir) = setup.run_dir {
+        info!("Run directory: {}", dir.display());
+    }
+
+    let alert_engine = (!args.no_metrics)
+        .then(|| alerts::AlertEngine::new(&setup.content.alert_rules, setup.content.techs.len()));
+
+    let timeline = timeline::TimelineCompactor::for_run(
+        args.timeline_every,
+        args.keyframe_every,
+        setup.game_state.meta.tick,
+        setup.run_dir.as_deref(),
+    )?;
+
+    let (command_journal, pending) = open_command_journal(
+        resume_journal.as_deref(),
+        setup.game_state.meta.tick,
+        &setup.game_state.pending_commands,
+        setup.run_dir.as_deref(),
+    )?;
+    let next_command_id = pending
+        .iter()
+        .chain(&setup.game_state.pending_commands)
+        .map(|envelope| envelope.id.0 + 1)
+        .chain(resumed_command_id)
+        .max();
+
+    let (event_tx, _) = broadcast::channel(256);
+    let ticks_per_sec_atomic = Arc::new(AtomicU64::new(args.ticks_per_sec.to_bits()));
+    let reporter = sim_world::RunReporter::new(&setup.game_state);
+    let state_hashes = match (args.hash_every, &setup.run_dir) {
+        (Some(every), Some(dir)) => Some(sim_world::StateHashWriter::create(dir, every)?),
+        _ => None,
+    };
+    let autosave = setup.run_dir.as_ref().and_then(|_| {
+        autosave::Autosaver::new(
+            args.autosave_every,
+            args.autosave_secs,
+            setup.game_state.meta.tick,
+        )
+    });
+    let event_store = setup
+        .run_dir
+        .as_deref()
+        .map(|dir| sim_daemon::event_store::EventStore::create(dir, args.events_rotate_mb))
+        .transpose()?;
+    let app_state = AppState {
+        sim: Arc::new(Mutex::new(SimState {
+            game_state: setup.game_state,
+            content: setup.content,
+            rng: setup.rng,
+            autopilot: AutopilotController::with_profile(args.controller),
+            next_command_id: next_command_id.unwrap_or(0),
+            metrics_every: args.metrics_every,
+            metrics_history: VecDeque::new(),
+            metrics_writer: setup.metrics_writer,
+            alert_engine,
+            timings_history: VecDeque::new(),
+            score_history: VecDeque::new(),
+            last_threshold: String::new(),
+            timeline,
+            event_level: args.event_level,
+            checkpoints: checkpoint::CheckpointStore::default(),
+            reporter,
+            state_hashes,
+            event_log: sim_daemon::event_log::EventLog::default(),
+            pauses: sim_daemon::pause::PauseSchedule::default(),
+            event_store,
+            snapshot_delta: sim_daemon::snapshot_delta::DeltaTracker::default(),
+            run_dir: setup.run_dir,
+            autosave,
+        })),
+        command_queue: Arc::new(Mutex::new(pending)),
+        event_tx: event_tx.clone(),
+        ticks_per_sec: ticks_per_sec_atomic,
+        paused: Arc::new(AtomicBool::new(args.paused)),
+        command_journal: command_journal.map(|journal| Arc::new(Mutex::new(journal))),
+        api_keys,
+        runs: Arc::new(RunLauncher {
+            root: PathBuf::from("runs"),
+            content_dir: args.content_dir,
+            record: !args.no_metrics,
+            max_ticks: args.max_ticks,
+            timeline_every: args.timeline_every,
+            keyframe_every: args.keyframe_every,
+            hash_every: args.hash_every,
+            events_rotate_mb: args.events_rotate_mb,
+            metrics_format: args.metrics_format,
+        }),
+    };
+    serve(
+        app_state,
+        event_tx,
+        &args.cors_origin,
+        args.port,
+        args.ticks_per_sec,
+        args.max_ticks,
+    )
+    .await
 }
 
 #[cfg(test)]
@@ -164,8 +397,9 @@ mod tests {
     use http_body_util::BodyExt;
     use rand::SeedableRng;
     use rand_chacha::ChaCha8Rng;
-    use routes::make_router;
     use sim_core::test_fixtures::base_content;
+    use sim_core::EventEnvelope;
+    use sim_daemon::routes::make_router;
     use sim_world::build_initial_state;
     use tower::ServiceExt;
 
@@ -194,12 +428,29 @@ mod tests {
                 timings_history: VecDeque::new(),
                 score_history: VecDeque::new(),
                 last_threshold: String::new(),
+                timeline: timeline::TimelineCompactor::new(
+                    timeline::DEFAULT_SEGMENT_TICKS,
+                    timeline::DEFAULT_KEYFRAME_EVERY,
+                    0,
+                ),
+                event_level: sim_core::EventLevel::Debug,
+                checkpoints: checkpoint::CheckpointStore::default(),
+                reporter: sim_world::RunReporter::default(),
+                state_hashes: None,
+                event_log: sim_daemon::event_log::EventLog::default(),
+                pauses: sim_daemon::pause::PauseSchedule::default(),
+                event_store: None,
+                snapshot_delta: sim_daemon::snapshot_delta::DeltaTracker::default(),
+                run_dir: None,
+                autosave: None,
             })),
             command_queue: Arc::new(Mutex::new(Vec::new())),
             event_tx,
             ticks_per_sec: Arc::new(AtomicU64::new(10.0_f64.to_bits())),
-            run_dir: None,
             paused: Arc::new(AtomicBool::new(false)),
+            command_journal: None,
+            api_keys: None,
+            runs: std::sync::Arc::default(),
         }
     }
 
@@ -225,6 +476,200 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_snapshot_delta_requires_since_tick() -> Result<(), Box<dyn std::error::Error>> {
+        let state = make_test_state();
+        state.sim.lock().game_state.meta.tick = 4;
+        let get = |uri: &'static str| {
+            make_router(state.clone())
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        };
+
+        let response = get("/api/v1/snapshot/delta").await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = get("/api/v1/snapshot/delta?since_tick=0").await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await?.to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(json["tick"], 4);
+        assert_eq!(json["complete"], true);
+        let ships = json["ships"].as_object().ok_or("ships")?;
+        assert_eq!(ships.len(), state.sim.lock().game_state.ships.len());
+
+        let response = get("/api/v1/snapshot/delta?since_tick=4").await?;
+        let body = response.into_body().collect().await?.to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(json["complete"], false);
+        assert_eq!(json["ships"], serde_json::json!({}));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_launch_run_replaces_the_running_world() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let root = tempfile::tempdir()?;
+        let mut state = make_test_state();
+        state.runs = Arc::new(RunLauncher {
+            root: root.path().to_path_buf(),
+            content_dir: concat!(env!("CARGO_MANIFEST_DIR"), "/../../content").to_string(),
+            ..RunLauncher::default()
+        });
+        state.sim.lock().game_state.meta.tick = 40;
+        state.command_queue.lock().push(CommandEnvelope {
+            id: sim_core::CommandId(9),
+            issued_by: sim_core::PrincipalId("principal_player".to_string()),
+            issued_tick: 40,
+            execute_at_tick: 41,
+            command: sim_core::Command::JettisonSlag {
+                station_id: sim_core::StationId("station_earth_orbit".to_string()),
+            },
+        });
+        let post = |body: &'static str| {
+            make_router(state.clone()).oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/runs")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+        };
+
+        let response = post(r#"{"seed": 7, "content_dir": "/no/such/content"}"#).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(state.sim.lock().game_state.meta.tick, 40);
+
+        let response = post(r#"{"seed": 7}"#).await?;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = response.into_body().collect().await?.to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(json["seed"], 7);
+        assert_eq!(json["run_dir"], serde_json::Value::Null);
+        {
+            let sim = state.sim.lock();
+            assert_eq!(sim.game_state.meta.tick, 0);
+            assert_eq!(sim.game_state.meta.seed, 7);
+        }
+        assert!(state.command_queue.lock().is_empty());
+
+        let response = make_router(state.clone())
+            .oneshot(Request::builder().uri("/api/v1/runs").body(Body::empty())?)
+            .await?;
+        let body = response.into_body().collect().await?.to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(json["runs"], serde_json::json!([]));
+
+        let response = make_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/runs/missing")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fast_forward_runs_ticks_only_while_paused(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let state = make_test_state();
+        let post = |body: &'static str| {
+            make_router(state.clone()).oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/fast_forward")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+        };
+
+        let response = post(r#"{"ticks": 5}"#).await?;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        state
+            .paused
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        let response = post(r#"{"ticks": 0}"#).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = post(r#"{"ticks": 5}"#).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await?.to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(json["from_tick"], 0);
+        assert_eq!(json["tick"], 5);
+        assert_eq!(json["ticks_run"], 5);
+        assert_eq!(json["stopped_by"], serde_json::Value::Null);
+        assert_eq!(json["metrics"]["tick"], 5);
+        assert_eq!(
+            state
+                .ticks_per_sec
+                .load(std::sync::atomic::Ordering::Relaxed),
+            10.0_f64.to_bits()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_collection_routes_filter_and_page() -> Result<(), Box<dyn std::error::Error>> {
+        let state = make_test_state();
+        let (owner, owned_ships) = {
+            let sim = state.sim.lock();
+            let owner = sim
+                .game_state
+                .ships
+                .values()
+                .next()
+                .ok_or("ship")?
+                .owner
+                .clone();
+            let owned_ships = sim
+                .game_state
+                .ships
+                .values()
+                .filter(|ship| ship.owner == owner)
+                .count();
+            (owner.0, owned_ships)
+        };
+        let get = |uri: String| {
+            let app = make_router(state.clone());
+            async move {
+                let response = app
+                    .oneshot(Request::builder().uri(uri).body(Body::empty())?)
+                    .await?;
+                let status = response.status();
+                let body = response.into_body().collect().await?.to_bytes();
+                let json: serde_json::Value = serde_json::from_slice(&body)?;
+                Ok::<_, Box<dyn std::error::Error>>((status, json))
+            }
+        };
+
+        let (status, json) = get(format!("/api/v1/ships?owner={owner}&limit=1")).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["total"], owned_ships);
+        assert_eq!(
+            json["items"].as_array().map(Vec::len),
+            Some(owned_ships.min(1))
+        );
+
+        let (status, json) = get("/api/v1/ships?owner=nobody".to_string()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["total"], 0);
+
+        let (status, _) = get("/api/v1/stations?sort=-modules&offset=1".to_string()).await?;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, json) = get("/api/v1/asteroids?owner=nobody".to_string()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "asteroids have no owner");
+        assert!(json["error"].as_str().is_some_and(|e| e.contains("owner")));
+        let (status, _) = get("/api/v1/asteroids?sort=tag".to_string()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_snapshot_returns_200() -> Result<(), Box<dyn std::error::Error>> {
         let app = make_router(make_test_state());
@@ -239,6 +684,25 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_state_hash_matches_game_state() -> Result<(), Box<dyn std::error::Error>> {
+        let state = make_test_state();
+        let expected = format!("{:016x}", state.sim.lock().game_state.stable_hash());
+        let app = make_router(state);
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/state-hash")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        let body = response.into_body().collect().await?.to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(json["tick"], 0);
+        assert_eq!(json["state_hash"], expected);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_meta_contains_ticks_per_sec() -> Result<(), Box<dyn std::error::Error>> {
         let app = make_router(make_test_state());
@@ -252,8 +716,8 @@ mod tests {
     }
 
     fn make_test_state_with_run_dir(run_dir: std::path::PathBuf) -> AppState {
-        let mut state = make_test_state();
-        state.run_dir = Some(run_dir);
+        let state = make_test_state();
+        state.sim.lock().run_dir = Some(run_dir);
         state
     }
 
@@ -379,6 +843,94 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_pause_reason_is_reported_and_streamed() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let state = make_test_state();
+        let response = make_router(state.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/pause")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"reason":"inspection"}"#))?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let meta_response = make_router(state.clone())
+            .oneshot(Request::builder().uri("/api/v1/meta").body(Body::empty())?)
+            .await?;
+        let meta_body = meta_response.into_body().collect().await?.to_bytes();
+        let meta_json: serde_json::Value = serde_json::from_slice(&meta_body)?;
+        assert_eq!(meta_json["paused"], true);
+        assert_eq!(meta_json["pause_reason"], "inspection");
+
+        make_router(state.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/resume")
+                    .body(Body::empty())?,
+            )
+            .await?;
+
+        let sim = state.sim.lock();
+        assert_eq!(sim.pauses.reason(), None);
+        let streamed: Vec<sim_core::Event> = sim
+            .event_log
+            .since(0)
+            .events
+            .into_iter()
+            .map(|(_, envelope)| envelope.event)
+            .collect();
+        assert!(matches!(
+            streamed.as_slice(),
+            [
+                sim_core::Event::SimPaused {
+                    reason: Some(reason),
+                    scheduled: false
+                },
+                sim_core::Event::SimResumed {}
+            ] if reason == "inspection"
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pause_schedule_validates_tick() -> Result<(), Box<dyn std::error::Error>> {
+        let state = make_test_state();
+        state.sim.lock().game_state.meta.tick = 10;
+
+        let schedule = |body: &'static str| {
+            Request::builder()
+                .method("POST")
+                .uri("/api/v1/pause/schedule")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+        };
+        let past = make_router(state.clone())
+            .oneshot(schedule(r#"{"at_tick":5}"#)?)
+            .await?;
+        assert_eq!(past.status(), StatusCode::BAD_REQUEST);
+
+        let ok = make_router(state.clone())
+            .oneshot(schedule(r#"{"at_tick":20,"reason":"backup"}"#)?)
+            .await?;
+        assert_eq!(ok.status(), StatusCode::OK);
+
+        let meta_response = make_router(state)
+            .oneshot(Request::builder().uri("/api/v1/meta").body(Body::empty())?)
+            .await?;
+        let meta_body = meta_response.into_body().collect().await?.to_bytes();
+        let meta_json: serde_json::Value = serde_json::from_slice(&meta_body)?;
+        assert_eq!(
+            meta_json["scheduled_pauses"],
+            serde_json::json!([{ "at_tick": 20, "reason": "backup" }])
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_snapshot_is_valid_json() -> Result<(), Box<dyn std::error::Error>> {
         let app = make_router(make_test_state());
@@ -638,55 +1190,827 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_pricing_returns_200() -> Result<(), Box<dyn std::error::Error>> {
-        let app = make_router(make_test_state());
-        let response = app
-            .oneshot(
-                Request::builder()
-                    .uri("/api/v1/pricing")
-                    .body(Body::empty())?,
-            )
-            .await?;
+    async fn test_command_accepts_legacy_alias_and_rejects_future_schema(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let state = make_test_state();
+        let post = |body: serde_json::Value| {
+            let app = make_router(state.clone());
+            async move {
+                app.oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/api/v1/command")
+                        .header("content-type", "application/json")
+                        .body(Body::from(serde_json::to_vec(&body)?))?,
+                )
+                .await
+                .map_err(Box::<dyn std::error::Error>::from)
+            }
+        };
+        let legacy = serde_json::json!({
+            "command_schema_version": 1,
+            "command": { "SetManufacturingPriority": {
+                "station_id": "station_earth_orbit",
+                "module_id": "module_inst_0001",
+                "priority": 3,
+            }},
+        });
+        let response = post(legacy).await?;
         assert_eq!(response.status(), StatusCode::OK);
-        let body = response.into_body().collect().await?.to_bytes();
-        let json: serde_json::Value = serde_json::from_slice(&body)?;
-        assert!(json.is_object(), "pricing response should be a JSON object");
-        assert!(
-            json.get("items").is_some(),
-            "pricing response should contain 'items'"
-        );
+        assert!(matches!(
+            state.command_queue.lock()[0].command,
+            sim_core::Command::SetModulePriority { priority: 3, .. }
+        ));
+
+        let future = serde_json::json!({
+            "command_schema_version": sim_core::COMMAND_SCHEMA_VERSION + 1,
+            "command": { "JettisonSlag": { "station_id": "station_earth_orbit" } },
+        });
+        let response = post(future).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(state.command_queue.lock().len(), 1);
         Ok(())
     }
 
     #[tokio::test]
-    async fn test_speed_sets_ticks_per_sec() -> Result<(), Box<dyn std::error::Error>> {
+    async fn test_commands_batch_checks_ownership_before_queueing(
+    ) -> Result<(), Box<dyn std::error::Error>> {
         let state = make_test_state();
-        let app = make_router(state.clone());
-        let response = app
-            .oneshot(
-                Request::builder()
-                    .method("POST")
-                    .uri("/api/v1/speed")
-                    .header("content-type", "application/json")
-                    .body(Body::from(r#"{"ticks_per_sec": 1000}"#))?,
-            )
-            .await?;
-        assert_eq!(response.status(), StatusCode::OK);
+        let (ship_id, owner) = {
+            let sim = state.sim.lock();
+            let ship = sim.game_state.ships.values().next().expect("test ship");
+            (ship.id.0.clone(), ship.owner.0.clone())
+        };
+        let post = |body: serde_json::Value| {
+            let app = make_router(state.clone());
+            async move {
+                app.oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/api/v1/commands")
+                        .header("content-type", "application/json")
+                        .body(Body::from(serde_json::to_vec(&body)?))?,
+                )
+                .await
+                .map_err(Box::<dyn std::error::Error>::from)
+            }
+        };
+        let jettison = |issued_by: &str| {
+            serde_json::json!({
+                "issued_by": issued_by,
+                "command": { "JettisonSlag": { "station_id": "station_earth_orbit" } },
+            })
+        };
+        let idle = |issued_by: &str| {
+            serde_json::json!({
+                "issued_by": issued_by,
+                "command": { "AssignShipTask": { "ship_id": ship_id, "task_kind": "Idle" } },
+            })
+        };
+
+        let response = post(serde_json::json!([
+            jettison(&owner),
+            idle("principal_player")
+        ]))
+        .await?;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
         let body = response.into_body().collect().await?.to_bytes();
         let json: serde_json::Value = serde_json::from_slice(&body)?;
-        assert_eq!(json["ticks_per_sec"], 1000.0);
+        assert_eq!(json["index"], 1);
+        assert!(state.command_queue.lock().is_empty());
 
-        // Verify atomic reflects new speed
-        let rate = f64::from_bits(
-            state
-                .ticks_per_sec
-                .load(std::sync::atomic::Ordering::Relaxed),
-        );
-        assert!((rate - 1000.0).abs() < 0.001);
-        Ok(())
-    }
+        // The station is the autopilot's too, so another principal may not
+        // act on it either.
+        let response = post(serde_json::json!([jettison("principal_player")])).await?;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = response.into_body().collect().await?.to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(json["index"], 0);
+        assert!(state.command_queue.lock().is_empty());
 
-    #[tokio::test]
+        let response = post(serde_json::json!([jettison(&owner), idle(&owner)])).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await?.to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(json["command_ids"], serde_json::json!([0, 1]));
+        let queue = state.command_queue.lock();
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue[1].issued_by.0, owner);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_commands_are_journaled_and_wait_for_their_tick(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = tempfile::tempdir()?;
+        let mut state = make_test_state();
+        let journal = journal::CommandJournal::create(tmp.path())?;
+        let journal_path = journal.path().to_path_buf();
+        state.command_journal = Some(Arc::new(Mutex::new(journal)));
+        let post = |body: serde_json::Value| {
+            let app = make_router(state.clone());
+            async move {
+                app.oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/api/v1/commands")
+                        .header("content-type", "application/json")
+                        .body(Body::from(serde_json::to_vec(&body)?))?,
+                )
+                .await
+                .map_err(Box::<dyn std::error::Error>::from)
+            }
+        };
+        let jettison = |execute_at_tick: u64| {
+            serde_json::json!({
+                "issued_by": "principal_autopilot",
+                "execute_at_tick": execute_at_tick,
+                "command": { "JettisonSlag": { "station_id": "station_earth_orbit" } },
+            })
+        };
+
+        let response = post(serde_json::json!([jettison(0), jettison(3)])).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        sim_daemon::tick_loop::execute_tick(&state.sim, &state.command_queue, None);
+        assert!(state.command_queue.lock().is_empty());
+        let scheduled: Vec<u64> = state
+            .sim
+            .lock()
+            .game_state
+            .pending_commands
+            .iter()
+            .map(|envelope| envelope.execute_at_tick)
+            .collect();
+        assert_eq!(scheduled, [3]);
+
+        let response = post(jettison(0)).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let pending = journal::load_pending(&journal_path, 1)?;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].execute_at_tick, 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_api_keys_guard_posts_and_fix_the_issuer() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mut state = make_test_state();
+        state.api_keys = Some(Arc::new(sim_daemon::auth::ApiKeys::new(
+            [(
+                "rival-key".to_string(),
+                sim_core::PrincipalId("principal_rival".to_string()),
+            )]
+            .into(),
+        )));
+        for station in state.sim.lock().game_state.stations.values_mut() {
+            station.owner = sim_core::PrincipalId("principal_rival".to_string());
+        }
+        let post = |key: Option<&str>, body: serde_json::Value| {
+            let app = make_router(state.clone());
+            let mut request = Request::builder()
+                .method("POST")
+                .uri("/api/v1/commands")
+                .header("content-type", "application/json");
+            if let Some(key) = key {
+                request = request.header("authorization", format!("Bearer {key}"));
+            }
+            async move {
+                app.oneshot(request.body(Body::from(serde_json::to_vec(&body)?))?)
+                    .await
+                    .map_err(Box::<dyn std::error::Error>::from)
+            }
+        };
+        let jettison = serde_json::json!({
+            "command": { "JettisonSlag": { "station_id": "station_earth_orbit" } },
+        });
+
+        let response = post(None, jettison.clone()).await?;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = post(Some("wrong-key"), jettison.clone()).await?;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let mut as_player = jettison.clone();
+        as_player["issued_by"] = "principal_player".into();
+        let response = post(Some("rival-key"), as_player).await?;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert!(state.command_queue.lock().is_empty());
+
+        let response = post(Some("rival-key"), jettison).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(state.command_queue.lock()[0].issued_by.0, "principal_rival");
+
+        let app = make_router(state.clone());
+        let response = app
+            .oneshot(Request::builder().uri("/api/v1/meta").body(Body::empty())?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK, "reads need no key");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_single_command_route_checks_ownership_with_api_keys(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut state = make_test_state();
+        state.api_keys = Some(Arc::new(sim_daemon::auth::ApiKeys::new(
+            [(
+                "rival-key".to_string(),
+                sim_core::PrincipalId("principal_rival".to_string()),
+            )]
+            .into(),
+        )));
+        let post = || {
+            let app = make_router(state.clone());
+            let body = serde_json::json!({
+                "command": { "JettisonSlag": { "station_id": "station_earth_orbit" } },
+            });
+            async move {
+                app.oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/api/v1/command")
+                        .header("content-type", "application/json")
+                        .header("authorization", "Bearer rival-key")
+                        .body(Body::from(serde_json::to_vec(&body)?))?,
+                )
+                .await
+                .map_err(Box::<dyn std::error::Error>::from)
+            }
+        };
+
+        let response = post().await?;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert!(state.command_queue.lock().is_empty());
+
+        for station in state.sim.lock().game_state.stations.values_mut() {
+            station.owner = sim_core::PrincipalId("principal_rival".to_string());
+        }
+        let response = post().await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(state.command_queue.lock()[0].issued_by.0, "principal_rival");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_module_diagnosis_reports_reasons() -> Result<(), Box<dyn std::error::Error>> {
+        let state = make_test_state();
+        let module_id = {
+            let mut sim = state.sim.lock();
+            let station = sim
+                .game_state
+                .stations
+                .values_mut()
+                .next()
+                .expect("test station");
+            let mut module = sim_core::test_fixtures::test_module(
+                "module_test",
+                sim_core::ModuleKindState::Storage,
+            );
+            module.enabled = false;
+            let module_id = module.id.0.clone();
+            station.core.modules.push(module);
+            module_id
+        };
+        let response = make_router(state.clone())
+            .oneshot(
+                Request::builder()
+                    .uri(format!(
+                        "/api/v1/stations/station_earth_orbit/modules/{module_id}/diagnosis"
+                    ))
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await?.to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(json["running"], false);
+        assert_eq!(json["reasons"][0], "Disabled");
+
+        let response = make_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/stations/station_earth_orbit/modules/nope/diagnosis")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ship_manifest_reports_hold_partitions() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let state = make_test_state();
+        let ship_id = {
+            let mut sim = state.sim.lock();
+            let ship = sim.game_state.ships.values().next().expect("test ship");
+            let (ship_id, hull_id) = (ship.id.clone(), ship.hull_id.clone());
+            sim.content.hulls.insert(
+                hull_id.clone(),
+                sim_core::HullDef {
+                    id: hull_id,
+                    name: "Partitioned".to_string(),
+                    mass_kg: 5000.0,
+                    cargo_capacity_m3: 50.0,
+                    base_speed_ticks_per_au: 2133,
+                    base_propellant_capacity_kg: 10_000.0,
+                    slots: vec![],
+                    bonuses: vec![],
+                    required_tech: None,
+                    tags: vec![],
+                    hold_partitions: [(sim_core::HoldPartition::ComponentLocker, 5.0)].into(),
+                    replacement_value: 0.0,
+                },
+            );
+            ship_id
+        };
+        let response = make_router(state.clone())
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/v1/ships/{ship_id}/manifest"))
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await?.to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        let partitions = &json["hold"]["partitions"];
+        assert_eq!(partitions[0]["partition"], "component_locker");
+        assert_eq!(partitions[0]["capacity_m3"], 5.0);
+        assert_eq!(partitions[1]["partition"], serde_json::Value::Null);
+
+        let response = make_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/ships/nope/manifest")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stream_resumes_after_last_event_id() -> Result<(), Box<dyn std::error::Error>> {
+        let state = make_test_state();
+        let events: Vec<EventEnvelope> = (0..4)
+            .map(|id| EventEnvelope {
+                id: sim_core::EventId(id),
+                tick: 0,
+                event: sim_core::Event::PowerConsumed {
+                    station_id: sim_core::StationId("station_earth_orbit".to_string()),
+                    amount: 1.0,
+                },
+            })
+            .collect();
+        state.sim.lock().event_log.push(events);
+        let last = 4;
+
+        let response = make_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/stream")
+                    .header("last-event-id", (last - 2).to_string())
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let mut body = response.into_body();
+        let frame = body.frame().await.ok_or("stream ended")??;
+        let text = String::from_utf8(frame.into_data().map_err(|_| "not a data frame")?.to_vec())?;
+
+        let id_line = format!("id: {last}\n");
+        assert!(text.contains(&id_line), "{text}");
+        let data = text
+            .lines()
+            .find_map(|line| line.strip_prefix("data: "))
+            .ok_or("no data line")?;
+        let events: Vec<serde_json::Value> = serde_json::from_str(data)?;
+        assert_eq!(events.len(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_event_level_is_set_at_runtime_and_per_subscriber(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let state = make_test_state();
+        let response = make_router(state.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/event_level")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"level": "info"}"#))?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(state.sim.lock().event_level, EventLevel::Info);
+        let response = make_router(state.clone())
+            .oneshot(Request::builder().uri("/api/v1/meta").body(Body::empty())?)
+            .await?;
+        let body = response.into_body().collect().await?.to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(json["event_level"], "info");
+
+        let station_id = sim_core::StationId("station_earth_orbit".to_string());
+        let events = vec![
+            EventEnvelope {
+                id: sim_core::EventId(0),
+                tick: 0,
+                event: sim_core::Event::ModuleIdleDiagnosis {
+                    station_id: station_id.clone(),
+                    module_id: sim_core::ModuleInstanceId("refinery_instance".to_string()),
+                    reasons: Vec::new(),
+                },
+            },
+            EventEnvelope {
+                id: sim_core::EventId(1),
+                tick: 0,
+                event: sim_core::Event::PowerConsumed {
+                    station_id,
+                    amount: 1.0,
+                },
+            },
+        ];
+        state.sim.lock().event_log.push(events);
+        for (level, expected) in [("debug", 2), ("info", 1)] {
+            let response = make_router(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/api/v1/stream?last_event_id=0&level={level}"))
+                        .body(Body::empty())?,
+                )
+                .await?;
+            let frame = response.into_body().frame().await.ok_or("stream ended")??;
+            let text =
+                String::from_utf8(frame.into_data().map_err(|_| "not a data frame")?.to_vec())?;
+            let data = text
+                .lines()
+                .find_map(|line| line.strip_prefix("data: "))
+                .ok_or("no data line")?;
+            let events: Vec<serde_json::Value> = serde_json::from_str(data)?;
+            assert_eq!(events.len(), expected, "level {level}");
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_event_stream_resumes_from_history_past_the_log(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = tempfile::tempdir()?;
+        let with_history = make_test_state_with_run_dir(tmp.path().to_path_buf());
+        let without_history = make_test_state();
+        for state in [&with_history, &without_history] {
+            let mut sim = state.sim.lock();
+            if sim.run_dir.is_some() {
+                sim.event_store = Some(sim_daemon::event_store::EventStore::create(tmp.path(), 0)?);
+            }
+            for tick in 0..11 {
+                sim.log_events(
+                    (0..1_000)
+                        .map(|n| EventEnvelope {
+                            id: sim_core::EventId(tick * 1_000 + n),
+                            tick,
+                            event: sim_core::Event::PowerConsumed {
+                                station_id: sim_core::StationId("station_earth_orbit".to_string()),
+                                amount: 1.0,
+                            },
+                        })
+                        .collect(),
+                );
+            }
+        }
+        let first_frame = |state: AppState| async move {
+            let response = make_router(state)
+                .oneshot(
+                    Request::builder()
+                        .uri("/api/v1/events/stream")
+                        .header("last-event-id", "500")
+                        .body(Body::empty())?,
+                )
+                .await?;
+            assert_eq!(response.status(), StatusCode::OK);
+            let frame = response.into_body().frame().await.ok_or("stream ended")??;
+            let data = frame.into_data().map_err(|_| "not a data frame")?;
+            Ok::<_, Box<dyn std::error::Error>>(String::from_utf8(data.to_vec())?)
+        };
+
+        let text = first_frame(with_history).await?;
+        assert!(text.contains("event: tick\n"), "{text}");
+        assert!(text.contains("id: 1000\n"), "{text}");
+        let data = text
+            .lines()
+            .find_map(|line| line.strip_prefix("data: "))
+            .ok_or("no data line")?;
+        let frame: serde_json::Value = serde_json::from_str(data)?;
+        assert_eq!(frame["tick"], 0);
+        assert_eq!(frame["events"].as_array().map(Vec::len), Some(500));
+
+        let text = first_frame(without_history).await?;
+        assert!(text.contains("event: gap\n"), "{text}");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_events_reads_back_persisted_history() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = tempfile::tempdir()?;
+        let state = make_test_state_with_run_dir(tmp.path().to_path_buf());
+        {
+            let mut sim = state.sim.lock();
+            sim.event_store = Some(sim_daemon::event_store::EventStore::create(tmp.path(), 0)?);
+            for tick in 0..5 {
+                sim.log_events(vec![EventEnvelope {
+                    id: sim_core::EventId(tick),
+                    tick,
+                    event: sim_core::Event::PowerConsumed {
+                        station_id: sim_core::StationId("station_earth_orbit".to_string()),
+                        amount: 1.0,
+                    },
+                }]);
+            }
+        }
+
+        let response = make_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/events?from_tick=3&kind=PowerConsumed")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await?.to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(json["count"], 2);
+        assert_eq!(json["truncated"], false);
+        assert_eq!(json["events"][0]["tick"], 3);
+
+        let response = make_router(make_test_state())
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/events")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_restore_rewinds_and_replays_identically() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let state = make_test_state();
+        let post = |uri: String| {
+            let app = make_router(state.clone());
+            async move {
+                let response = app
+                    .oneshot(
+                        Request::builder()
+                            .method("POST")
+                            .uri(uri)
+                            .body(Body::empty())?,
+                    )
+                    .await?;
+                let status = response.status();
+                let body = response.into_body().collect().await?.to_bytes();
+                let json: serde_json::Value = serde_json::from_slice(&body)?;
+                Ok::<_, Box<dyn std::error::Error>>((status, json))
+            }
+        };
+        let replay = || -> Result<String, serde_json::Error> {
+            for _ in 0..20 {
+                sim_daemon::tick_loop::execute_tick(&state.sim, &state.command_queue, None);
+            }
+            serde_json::to_string(&state.sim.lock().game_state)
+        };
+
+        let (status, json) = post("/api/v1/checkpoint".to_string()).await?;
+        assert_eq!(status, StatusCode::OK);
+        let checkpoint_id = json["checkpoint_id"].as_u64().expect("checkpoint id");
+        replay()?;
+
+        let restore = format!("/api/v1/restore/{checkpoint_id}");
+        let (status, json) = post(restore.clone()).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["tick"], 0);
+        assert_eq!(state.sim.lock().game_state.meta.tick, 0);
+        let first = replay()?;
+        post(restore).await?;
+        assert_eq!(replay()?, first);
+
+        let (status, _) = post("/api/v1/restore/99".to_string()).await?;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_timeline_returns_segments_and_keyframe() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let state = make_test_state();
+        {
+            let mut sim = state.sim.lock();
+            let SimState {
+                ref mut game_state,
+                ref mut timeline,
+                ..
+            } = *sim;
+            let end = timeline::DEFAULT_SEGMENT_TICKS * timeline::DEFAULT_KEYFRAME_EVERY;
+            for tick in 1..=end {
+                game_state.meta.tick = tick;
+                timeline.record(&[], game_state);
+            }
+        }
+
+        let app = make_router(state.clone());
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/timeline?since_tick=100")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await?.to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        let segments = json["segments"].as_array().ok_or("segments array")?;
+        assert_eq!(segments.len() as u64, timeline::DEFAULT_KEYFRAME_EVERY - 1);
+        assert_eq!(json["keyframe_ticks"], serde_json::json!([1000]));
+
+        let app = make_router(state.clone());
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/timeline/keyframe?tick=1500")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let app = make_router(state);
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/timeline/keyframe?tick=999")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_filters_snapshot() -> Result<(), Box<dyn std::error::Error>> {
+        let state = make_test_state();
+        let app = make_router(state.clone());
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/query?q=stations%5Bid%3Dstation_earth_orbit%5D")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await?.to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(json["count"], 1);
+        assert_eq!(json["results"][0]["id"], "station_earth_orbit");
+
+        let app = make_router(state);
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/query?q=stations%5Bcolour%3Dred%5D")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_asteroid_query_ranks_and_limits() -> Result<(), Box<dyn std::error::Error>> {
+        let state = make_test_state();
+        {
+            let mut sim = state.sim.lock();
+            for (id, mass_kg) in [("asteroid_small", 100.0), ("asteroid_large", 900.0)] {
+                let asteroid = sim_core::AsteroidState {
+                    id: sim_core::AsteroidId(id.to_string()),
+                    position: sim_core::test_fixtures::test_position(),
+                    true_composition: sim_core::CompositionVec::new(),
+                    anomaly_tags: vec![],
+                    mass_kg,
+                    knowledge: sim_core::AsteroidKnowledge {
+                        tag_beliefs: vec![],
+                        composition: None,
+                        layers: None,
+                        composition_sigma: None,
+                        deep_scan_count: 0,
+                    },
+                    layers: None,
+                };
+                sim.game_state
+                    .asteroids
+                    .insert(asteroid.id.clone(), asteroid);
+            }
+        }
+        let app = make_router(state.clone());
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/queries/asteroids?sort=mass&body=test_body&limit=1")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await?.to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(json["count"], 1);
+        assert_eq!(json["results"][0]["id"], "asteroid_large");
+
+        let app = make_router(state);
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/queries/asteroids?sort=value")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pricing_returns_200() -> Result<(), Box<dyn std::error::Error>> {
+        let app = make_router(make_test_state());
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/pricing")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await?.to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        assert!(json.is_object(), "pricing response should be a JSON object");
+        assert!(
+            json.get("items").is_some(),
+            "pricing response should contain 'items'"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_market_reports_current_prices() -> Result<(), Box<dyn std::error::Error>> {
+        let app = make_router(make_test_state());
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/market")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await?.to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        let items = json["items"]
+            .as_object()
+            .ok_or("market response should contain 'items'")?;
+        for item in items.values() {
+            assert_eq!(
+                item["current_price_per_unit"], item["base_price_per_unit"],
+                "prices start at base"
+            );
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_speed_sets_ticks_per_sec() -> Result<(), Box<dyn std::error::Error>> {
+        let state = make_test_state();
+        let app = make_router(state.clone());
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/speed")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"ticks_per_sec": 1000}"#))?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await?.to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(json["ticks_per_sec"], 1000.0);
+
+        // Verify atomic reflects new speed
+        let rate = f64::from_bits(
+            state
+                .ticks_per_sec
+                .load(std::sync::atomic::Ordering::Relaxed),
+        );
+        assert!((rate - 1000.0).abs() < 0.001);
+        Ok(())
+    }
+
+    #[tokio::test]
     async fn test_spatial_config_returns_200() -> Result<(), Box<dyn std::error::Error>> {
         let app = make_router(make_test_state());
         let response = app
@@ -763,6 +2087,11 @@ mod tests {
             json["body_absolutes"].is_object(),
             "snapshot should include body_absolutes"
         );
+        let holds = json["ship_holds"].as_object().expect("ship_holds map");
+        assert_eq!(
+            holds.len(),
+            json["ships"].as_object().map_or(0, serde_json::Map::len)
+        );
         Ok(())
     }
 
@@ -771,13 +2100,25 @@ mod tests {
         use clap::Parser;
         let cli = Cli::parse_from(["sim_daemon", "run", "--seed", "1", "--paused"]);
         match cli.command {
-            Commands::Run { paused, .. } => assert!(paused),
+            Commands::Run(args) => assert!(args.paused),
         }
     }
 
+    #[test]
+    fn test_controller_flag_parsed() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["sim_daemon", "run", "--controller", "trade-focused"]);
+        match cli.command {
+            Commands::Run(args) => {
+                assert_eq!(args.controller, ControllerProfile::TradeFocused);
+            }
+        }
+        assert!(Cli::try_parse_from(["sim_daemon", "run", "--controller", "greedy"]).is_err());
+    }
+
     #[test]
     fn test_push_metrics_caps_at_max_history() {
-        use state::MAX_METRICS_HISTORY;
+        use sim_daemon::state::MAX_METRICS_HISTORY;
 
         let content = base_content();
         let mut rng = ChaCha8Rng::seed_from_u64(0);
@@ -797,6 +2138,17 @@ mod tests {
             timings_history: VecDeque::new(),
             score_history: VecDeque::new(),
             last_threshold: String::new(),
+            timeline: timeline::TimelineCompactor::new(0, 0, 0),
+            event_level: sim_core::EventLevel::Debug,
+            checkpoints: checkpoint::CheckpointStore::default(),
+            reporter: sim_world::RunReporter::default(),
+            state_hashes: None,
+            event_log: sim_daemon::event_log::EventLog::default(),
+            pauses: sim_daemon::pause::PauseSchedule::default(),
+            event_store: None,
+            snapshot_delta: sim_daemon::snapshot_delta::DeltaTracker::default(),
+            run_dir: None,
+            autosave: None,
         };
 
         let total_pushes = MAX_METRICS_HISTORY + 10;
@@ -0,0 +1,159 @@
+//! Administrative pauses and scheduled maintenance windows.
+//!
+//! `POST /api/v1/pause` pauses now and `POST /api/v1/pause/schedule` pauses
+//! once the sim reaches a tick; both take an optional reason so people
+//! sharing a long run can see why it stopped. The current reason and the
+//! pending schedule are reported by `GET /api/v1/meta`, and every pause and
+//! resume is streamed as a `SimPaused`/`SimResumed` event.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::Serialize;
+use sim_core::{Event, EventEnvelope, EventId};
+
+use crate::state::{EventTx, SimState};
+
+/// A pause waiting for the sim to reach `at_tick`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScheduledPause {
+    pub at_tick: u64,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct PauseSchedule {
+    /// Why the run is paused, while it is.
+    reason: Option<String>,
+    /// Ordered by `at_tick`, then by when they were scheduled.
+    pending: Vec<ScheduledPause>,
+}
+
+impl PauseSchedule {
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    pub fn pending(&self) -> &[ScheduledPause] {
+        &self.pending
+    }
+
+    pub fn schedule(&mut self, pause: ScheduledPause) {
+        let index = self
+            .pending
+            .partition_point(|existing| existing.at_tick <= pause.at_tick);
+        self.pending.insert(index, pause);
+    }
+
+    /// Remove every pause due at or before `tick`. Several due at once
+    /// become one pause with their reasons joined.
+    pub fn take_due(&mut self, tick: u64) -> Option<ScheduledPause> {
+        let due = self.pending.partition_point(|pause| pause.at_tick <= tick);
+        if due == 0 {
+            return None;
+        }
+        let taken: Vec<ScheduledPause> = self.pending.drain(..due).collect();
+        let reasons: Vec<String> = taken.iter().filter_map(|p| p.reason.clone()).collect();
+        Some(ScheduledPause {
+            at_tick: taken[0].at_tick,
+            reason: (!reasons.is_empty()).then(|| reasons.join("; ")),
+        })
+    }
+}
+
+/// Pause the run, recording `reason`. Pausing a paused run only replaces
+/// the reason (when one is given) and streams nothing.
+pub fn pause(
+    sim: &mut SimState,
+    paused: &AtomicBool,
+    event_tx: &EventTx,
+    reason: Option<String>,
+    scheduled: bool,
+) {
+    if paused.swap(true, Ordering::Relaxed) {
+        if reason.is_some() {
+            sim.pauses.reason = reason;
+        }
+        return;
+    }
+    sim.pauses.reason.clone_from(&reason);
+    stream(sim, event_tx, Event::SimPaused { reason, scheduled });
+}
+
+/// Resume a paused run and clear its reason. Resuming a running sim is a
+/// no-op.
+pub fn resume(sim: &mut SimState, paused: &AtomicBool, event_tx: &EventTx) {
+    if !paused.swap(false, Ordering::Relaxed) {
+        return;
+    }
+    sim.pauses.reason = None;
+    stream(sim, event_tx, Event::SimResumed {});
+}
+
+/// Pause if a scheduled pause is due before the next tick. Returns whether
+/// it paused.
+pub fn pause_if_due(sim: &mut SimState, paused: &AtomicBool, event_tx: &EventTx) -> bool {
+    let Some(due) = sim.pauses.take_due(sim.game_state.meta.tick) else {
+        return false;
+    };
+    pause(sim, paused, event_tx, due.reason, true);
+    true
+}
+
+fn stream(sim: &mut SimState, event_tx: &EventTx, event: Event) {
+    let id = EventId(sim.game_state.counters.next_event_id);
+    sim.game_state.counters.next_event_id += 1;
+    let envelope = EventEnvelope {
+        id,
+        tick: sim.game_state.meta.tick,
+        event,
+    };
+    let batch = sim.log_events(vec![envelope]);
+    if event_tx.receiver_count() > 0 {
+        let _ = event_tx.send(batch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(at_tick: u64, reason: &str) -> ScheduledPause {
+        ScheduledPause {
+            at_tick,
+            reason: Some(reason.to_string()),
+        }
+    }
+
+    #[test]
+    fn pending_pauses_stay_in_tick_order() {
+        let mut schedule = PauseSchedule::default();
+        schedule.schedule(at(20, "b"));
+        schedule.schedule(at(10, "a"));
+        schedule.schedule(at(20, "c"));
+
+        let ticks: Vec<(u64, Option<&str>)> = schedule
+            .pending()
+            .iter()
+            .map(|p| (p.at_tick, p.reason.as_deref()))
+            .collect();
+        assert_eq!(ticks, [(10, Some("a")), (20, Some("b")), (20, Some("c"))]);
+    }
+
+    #[test]
+    fn take_due_merges_pauses_that_fall_due_together() {
+        let mut schedule = PauseSchedule::default();
+        schedule.schedule(at(5, "inspect"));
+        schedule.schedule(ScheduledPause {
+            at_tick: 7,
+            reason: None,
+        });
+        schedule.schedule(at(7, "backup"));
+        schedule.schedule(at(30, "later"));
+
+        assert_eq!(schedule.take_due(4), None);
+        let due = schedule.take_due(8).unwrap();
+        assert_eq!(due.at_tick, 5);
+        assert_eq!(due.reason.as_deref(), Some("inspect; backup"));
+        assert_eq!(schedule.pending(), [at(30, "later")]);
+    }
+}
@@ -1,7 +1,8 @@
+use crate::auth::Authenticated;
 use crate::state::AppState;
 use axum::{
-    extract::State,
-    http::{header, Method, StatusCode},
+    extract::{Extension, Path, Query, State},
+    http::{header, HeaderMap, Method, StatusCode},
     response::{
         sse::{Event, Sse},
         Json,
@@ -9,11 +10,12 @@ use axum::{
     routing::{get, post},
     Router,
 };
+use sim_core::query::Collection;
 use sim_core::{
     AbsolutePos, BodyId, CommandEnvelope, CommandId, DataKind, EventEnvelope, ModuleBehaviorDef,
-    ModuleKindState, OrbitalBodyDef, PrincipalId, TechDef,
+    ModuleKindState, OrbitalBodyDef, PrincipalId, ShipId, TechDef,
 };
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use std::convert::Infallible;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
@@ -21,7 +23,8 @@ use tokio::sync::broadcast;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 
-#[cfg(test)]
+/// Router with the dev UI's default CORS origin, for tests and in-process
+/// harnesses.
 pub fn make_router(state: AppState) -> Router {
     make_router_with_cors(state, "http://localhost:5173").expect("test CORS origin is valid")
 }
@@ -42,24 +45,71 @@ pub fn make_router_with_cors(state: AppState, cors_origin: &str) -> anyhow::Resu
     Ok(Router::new()
         .route("/api/v1/meta", get(meta_handler))
         .route("/api/v1/snapshot", get(snapshot_handler))
+        .route("/api/v1/snapshot/delta", get(snapshot_delta_handler))
+        .route("/api/v1/state-hash", get(state_hash_handler))
         .route("/api/v1/metrics", get(metrics_handler))
         .route("/api/v1/stream", get(stream_handler))
         .route("/api/v1/save", post(save_handler))
+        .route("/api/v1/runs", get(runs_handler).post(launch_run_handler))
+        .route("/api/v1/runs/:run_id", get(run_handler))
         .route("/api/v1/pause", post(pause_handler))
+        .route("/api/v1/pause/schedule", post(pause_schedule_handler))
+        .route("/api/v1/checkpoint", post(checkpoint_handler))
+        .route("/api/v1/restore/:checkpoint_id", post(restore_handler))
         .route("/api/v1/resume", post(resume_handler))
+        .route("/api/v1/fast_forward", post(fast_forward_handler))
         .route("/api/v1/alerts", get(alerts_handler))
         .route("/api/v1/advisor/digest", get(advisor_digest_handler))
         .route("/api/v1/command", post(command_handler))
+        .route("/api/v1/commands", post(commands_handler))
         .route("/api/v1/pricing", get(pricing_handler))
+        .route("/api/v1/market", get(market_handler))
         .route("/api/v1/spatial-config", get(spatial_config_handler))
         .route("/api/v1/content", get(content_handler))
         .route("/api/v1/perf", get(perf_handler))
         .route("/api/v1/score", get(score_handler))
         .route("/api/v1/speed", post(speed_handler))
+        .route("/api/v1/event_level", post(event_level_handler))
+        .route("/api/v1/timeline", get(timeline_handler))
+        .route("/api/v1/timeline/keyframe", get(timeline_keyframe_handler))
+        .route("/api/v1/events", get(events_handler))
+        .route("/api/v1/events/stream", get(event_stream_handler))
+        .route("/api/v1/query", get(query_handler))
+        .route(
+            "/api/v1/ships",
+            get(|state, params| async move {
+                collection_handler(Collection::Ships, state, params)
+            }),
+        )
+        .route(
+            "/api/v1/stations",
+            get(|state, params| async move {
+                collection_handler(Collection::Stations, state, params)
+            }),
+        )
+        .route(
+            "/api/v1/asteroids",
+            get(|state, params| async move {
+                collection_handler(Collection::Asteroids, state, params)
+            }),
+        )
+        .route("/api/v1/queries/asteroids", get(asteroid_query_handler))
+        .route(
+            "/api/v1/stations/:station_id/modules/:module_id/diagnosis",
+            get(module_diagnosis_handler),
+        )
+        .route(
+            "/api/v1/ships/:ship_id/manifest",
+            get(ship_manifest_handler),
+        )
         .route(
             "/api/v1/strategy",
             get(strategy_get_handler).post(strategy_post_handler),
         )
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            crate::auth::require_api_key,
+        ))
         .layer(cors)
         .layer(TraceLayer::new_for_http())
         .with_state(state))
@@ -75,8 +125,21 @@ pub async fn meta_handler(State(app_state): State<AppState>) -> Json<serde_json:
         "content_version": sim.game_state.meta.content_version,
         "ticks_per_sec": ticks_per_sec,
         "paused": paused,
+        "pause_reason": sim.pauses.reason(),
+        "scheduled_pauses": sim.pauses.pending(),
         "trade_unlock_tick": sim_core::trade_unlock_tick(&sim.content.constants),
         "minutes_per_tick": sim.content.constants.minutes_per_tick,
+        "command_schema_version": sim_core::COMMAND_SCHEMA_VERSION,
+        "event_level": sim.event_level,
+    }))
+}
+
+/// The current state's `GameState::stable_hash`, for comparing live runs.
+pub async fn state_hash_handler(State(app_state): State<AppState>) -> Json<serde_json::Value> {
+    let sim = app_state.sim.lock();
+    Json(serde_json::json!({
+        "tick": sim.game_state.meta.tick,
+        "state_hash": format!("{:016x}", sim.game_state.stable_hash()),
     }))
 }
 
@@ -93,10 +156,20 @@ pub async fn snapshot_handler(
                 .iter()
                 .map(|(id, bc)| (id.clone(), bc.absolute))
                 .collect();
+            // Inject ship_holds so FE can show hold partition usage.
+            let ship_holds: BTreeMap<&ShipId, sim_core::HoldUsage> = sim
+                .game_state
+                .ships
+                .iter()
+                .map(|(id, ship)| (id, sim_core::hold_usage(ship, &sim.content)))
+                .collect();
             if let Some(obj) = val.as_object_mut() {
                 if let Ok(ba) = serde_json::to_value(&body_absolutes) {
                     obj.insert("body_absolutes".to_string(), ba);
                 }
+                if let Ok(holds) = serde_json::to_value(&ship_holds) {
+                    obj.insert("ship_holds".to_string(), holds);
+                }
             }
             drop(sim);
             let json = serde_json::to_string(&val).unwrap_or_default();
@@ -118,6 +191,31 @@ pub async fn snapshot_handler(
     }
 }
 
+#[derive(serde::Deserialize)]
+pub struct SnapshotDeltaQuery {
+    since_tick: u64,
+}
+
+/// Ships, stations and asteroids changed since `since_tick`, the ids removed
+/// since then and the research state if it changed (see `snapshot_delta`).
+pub async fn snapshot_delta_handler(
+    State(app_state): State<AppState>,
+    Query(query): Query<SnapshotDeltaQuery>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let mut guard = app_state.sim.lock();
+    let sim = &mut *guard;
+    match sim.snapshot_delta.delta(&sim.game_state, query.since_tick) {
+        Ok(delta) => (StatusCode::OK, Json(delta)),
+        Err(err) => {
+            tracing::error!("snapshot delta serialization failed: {err}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "serialization failed"})),
+            )
+        }
+    }
+}
+
 pub async fn metrics_handler(
     State(app_state): State<AppState>,
 ) -> Json<VecDeque<sim_core::MetricsSnapshot>> {
@@ -128,7 +226,8 @@ pub async fn metrics_handler(
 pub async fn save_handler(
     State(app_state): State<AppState>,
 ) -> (StatusCode, Json<serde_json::Value>) {
-    let run_dir = match &app_state.run_dir {
+    let sim = app_state.sim.lock();
+    let run_dir = match &sim.run_dir {
         Some(dir) => dir.clone(),
         None => {
             return (
@@ -138,7 +237,6 @@ pub async fn save_handler(
         }
     };
 
-    let sim = app_state.sim.lock();
     let tick = sim.game_state.meta.tick;
     let body = match serde_json::to_string_pretty(&sim.game_state) {
         Ok(json) => json,
@@ -176,6 +274,108 @@ pub async fn save_handler(
     )
 }
 
+/// Snapshot the running sim (state, RNG position, next command id) in
+/// memory so it can be rewound to with `restore_handler`.
+pub async fn checkpoint_handler(State(app_state): State<AppState>) -> Json<serde_json::Value> {
+    let mut sim = app_state.sim.lock();
+    let tick = sim.game_state.meta.tick;
+    let game_state = sim.game_state.clone();
+    let rng = sim_core::RngCheckpoint::capture(&sim.rng);
+    let next_command_id = sim.next_command_id;
+    let checkpoint_id = sim.checkpoints.push(game_state, rng, next_command_id);
+    Json(serde_json::json!({"checkpoint_id": checkpoint_id, "tick": tick}))
+}
+
+async fn runs_handler(State(app_state): State<AppState>) -> (StatusCode, Json<serde_json::Value>) {
+    match crate::runs::list(&app_state.runs) {
+        Ok(runs) => (StatusCode::OK, Json(runs)),
+        Err(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("{err:#}")})),
+        ),
+    }
+}
+
+async fn run_handler(
+    State(app_state): State<AppState>,
+    Path(run_id): Path<String>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    match crate::runs::inspect(&app_state.runs, &run_id) {
+        Ok(Some(run)) => (StatusCode::OK, Json(run)),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("no run {run_id}")})),
+        ),
+        Err(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("{err:#}")})),
+        ),
+    }
+}
+
+/// Content loading and run setup touch the disk, so the launch runs on the
+/// blocking pool.
+async fn launch_run_handler(
+    State(app_state): State<AppState>,
+    Json(request): Json<crate::runs::LaunchRequest>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let launched =
+        tokio::task::spawn_blocking(move || crate::runs::launch(&app_state, request)).await;
+    match launched {
+        Ok(Ok(run)) => (StatusCode::CREATED, Json(run)),
+        Ok(Err(crate::runs::LaunchError::Content(err))) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("{err:#}")})),
+        ),
+        Ok(Err(crate::runs::LaunchError::Setup(err))) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("{err:#}")})),
+        ),
+        Err(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("launch task failed: {err}")})),
+        ),
+    }
+}
+
+/// Rewind the running sim to a checkpoint. Queued commands are dropped,
+/// the autopilot restarts fresh, and metrics, scores and the timeline
+/// forget everything after the checkpoint's tick.
+pub async fn restore_handler(
+    State(app_state): State<AppState>,
+    Path(checkpoint_id): Path<u64>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let mut sim = app_state.sim.lock();
+    let Some(checkpoint) = sim.checkpoints.get(checkpoint_id).cloned() else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("no checkpoint {checkpoint_id}")})),
+        );
+    };
+    let tick = checkpoint.tick;
+    sim.game_state = checkpoint.game_state;
+    sim.rng = checkpoint.rng.restore();
+    sim.next_command_id = checkpoint.next_command_id;
+    sim.autopilot = sim_control::AutopilotController::with_profile(sim.autopilot.profile());
+    sim.metrics_history.retain(|snapshot| snapshot.tick <= tick);
+    sim.score_history.retain(|score| score.tick <= tick);
+    sim.timeline.rewind(tick);
+    sim.reporter.rewind(tick);
+    if let Some(saver) = sim.autosave.as_mut() {
+        saver.reset(tick);
+    }
+    app_state.command_queue.lock().clear();
+    if let Some(journal) = &app_state.command_journal {
+        if let Err(err) = journal.lock().clear(tick) {
+            tracing::error!("command journal write failed: {err:#}");
+        }
+    }
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({"checkpoint_id": checkpoint_id, "tick": tick})),
+    )
+}
+
 async fn alerts_handler(State(app_state): State<AppState>) -> Json<serde_json::Value> {
     let sim = app_state.sim.lock();
     let active_ids: Vec<String> = sim
@@ -263,6 +463,301 @@ async fn perf_handler(State(app_state): State<AppState>) -> Json<serde_json::Val
     }))
 }
 
+#[derive(serde::Deserialize)]
+pub struct TimelineQuery {
+    /// Only return segments ending after this tick.
+    #[serde(default)]
+    since_tick: u64,
+}
+
+/// Compacted event timeline: per-segment event aggregates plus the ticks
+/// that have a keyframe available.
+async fn timeline_handler(
+    State(app_state): State<AppState>,
+    Query(query): Query<TimelineQuery>,
+) -> Json<serde_json::Value> {
+    let sim = app_state.sim.lock();
+    let segments: Vec<&crate::timeline::TimelineSegment> = sim
+        .timeline
+        .segments
+        .iter()
+        .filter(|segment| segment.end_tick > query.since_tick)
+        .collect();
+    let keyframe_ticks: Vec<u64> = sim.timeline.keyframes.iter().map(|k| k.tick).collect();
+    Json(serde_json::json!({
+        "segment_ticks": sim.timeline.segment_ticks(),
+        "segments": segments,
+        "keyframe_ticks": keyframe_ticks,
+    }))
+}
+
+#[derive(serde::Deserialize)]
+pub struct KeyframeQuery {
+    tick: u64,
+}
+
+/// Latest keyframe at or before `tick`, for seeking a replay.
+async fn timeline_keyframe_handler(
+    State(app_state): State<AppState>,
+    Query(query): Query<KeyframeQuery>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let sim = app_state.sim.lock();
+    match sim.timeline.keyframe_at(query.tick) {
+        Some(frame) => (StatusCode::OK, Json(serde_json::json!(frame))),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "error": format!("no keyframe at or before tick {}", query.tick),
+            })),
+        ),
+    }
+}
+
+#[derive(serde::Deserialize)]
+pub struct EventHistoryQuery {
+    from_tick: Option<u64>,
+    /// `Event` variant name, e.g. `OreMined`.
+    kind: Option<String>,
+    ship_id: Option<String>,
+    limit: Option<usize>,
+}
+
+/// Persisted event history from `events.jsonl` (and rotated segments),
+/// oldest first, e.g. `?from_tick=500&kind=ShipArrived&ship_id=ship_0001`.
+/// 503 when the run has no directory.
+async fn events_handler(
+    State(app_state): State<AppState>,
+    Query(query): Query<EventHistoryQuery>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let files = match &app_state.sim.lock().event_store {
+        Some(store) => store.files(),
+        None => {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": "no run directory (started with --no-metrics?)"})),
+            );
+        }
+    };
+    let filter = crate::event_store::EventFilter {
+        from_tick: query.from_tick,
+        kind: query.kind,
+        ship_id: query.ship_id,
+    };
+    let limit = query
+        .limit
+        .unwrap_or(crate::event_store::DEFAULT_QUERY_LIMIT)
+        .min(crate::event_store::MAX_QUERY_LIMIT);
+    let result =
+        tokio::task::spawn_blocking(move || crate::event_store::query(&files, &filter, limit))
+            .await;
+    match result {
+        Ok(Ok(history)) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "count": history.events.len(),
+                "truncated": history.truncated,
+                "events": history.events,
+            })),
+        ),
+        Ok(Err(err)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("{err:#}")})),
+        ),
+        Err(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("event query failed: {err}")})),
+        ),
+    }
+}
+
+#[derive(serde::Deserialize)]
+pub struct SnapshotQuery {
+    /// Filter expression, e.g. `ships[owner=principal_autopilot & task=Idle]`.
+    q: String,
+}
+
+/// Entities of one snapshot collection matching a `sim_core::query`
+/// expression. 400 with the parse error for a malformed query.
+async fn query_handler(
+    State(app_state): State<AppState>,
+    Query(query): Query<SnapshotQuery>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let parsed = match sim_core::query::Query::parse(&query.q) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({
+                    "error": err.message,
+                    "offset": err.offset,
+                })),
+            )
+        }
+    };
+    let sim = app_state.sim.lock();
+    let results = parsed.run(&sim.game_state);
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "tick": sim.game_state.meta.tick,
+            "count": results.len(),
+            "results": results,
+        })),
+    )
+}
+
+/// Items per page when a collection request sets no `limit`.
+const DEFAULT_PAGE_LIMIT: usize = 100;
+
+/// Most items one collection page may hold.
+const MAX_PAGE_LIMIT: usize = 1_000;
+
+#[derive(serde::Deserialize)]
+pub struct CollectionParams {
+    /// Body the entity orbits.
+    node: Option<String>,
+    owner: Option<String>,
+    /// Asteroids whose composition has (or has not) been deep-scanned.
+    has_composition: Option<bool>,
+    /// Asteroids of at least this many kg.
+    min_mass: Option<f64>,
+    /// A field of the collection, `-field` for descending. Id order by
+    /// default.
+    sort: Option<String>,
+    #[serde(default)]
+    offset: usize,
+    limit: Option<usize>,
+}
+
+/// One page of ships, stations or asteroids, e.g.
+/// `/api/v1/asteroids?node=belt_inner&has_composition=true&min_mass=1000&sort=-mass_kg&offset=50&limit=50`.
+/// `total` counts every match before paging. 400 for a filter or sort
+/// field the collection does not have.
+fn collection_handler(
+    collection: Collection,
+    State(app_state): State<AppState>,
+    Query(params): Query<CollectionParams>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    use sim_core::query::{Query, SortKey};
+
+    let conditions = [
+        ("node", "=", params.node),
+        ("owner", "=", params.owner),
+        (
+            "scanned",
+            "=",
+            params.has_composition.map(|b| b.to_string()),
+        ),
+        ("mass_kg", ">=", params.min_mass.map(|kg| kg.to_string())),
+    ];
+    let query = conditions
+        .into_iter()
+        .filter_map(|(field, op, value)| value.map(|value| (field, op, value)))
+        .try_fold(Query::all(collection), |query, (field, op, value)| {
+            query.and(field, op, &value)
+        });
+    let sort = params
+        .sort
+        .as_deref()
+        .map(|sort| SortKey::parse(collection, sort))
+        .transpose();
+    let (query, sort) = match (query, sort) {
+        (Ok(query), Ok(sort)) => (query, sort),
+        (Err(err), _) | (_, Err(err)) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": err.message })),
+            );
+        }
+    };
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_PAGE_LIMIT)
+        .min(MAX_PAGE_LIMIT);
+
+    let sim = app_state.sim.lock();
+    let mut results = query.run(&sim.game_state);
+    let total = results.len();
+    results.sort_and_page(sort, params.offset, limit);
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "tick": sim.game_state.meta.tick,
+            "total": total,
+            "offset": params.offset,
+            "limit": limit,
+            "items": results,
+        })),
+    )
+}
+
+#[derive(serde::Deserialize)]
+pub struct AsteroidQueryParams {
+    /// `value` (needs `element`), `mass` (default) or `distance` (needs `near`).
+    sort: Option<String>,
+    element: Option<String>,
+    /// Body whose centre distances are measured from.
+    near: Option<String>,
+    body: Option<String>,
+    #[serde(default)]
+    composition: sim_core::asteroid_ranking::CompositionFilter,
+    limit: Option<usize>,
+}
+
+/// Ranked asteroids from `sim_core::asteroid_ranking::asteroid_candidates`, e.g.
+/// `?sort=value&element=Fe&near=belt_inner&limit=10`. 400 for an unknown
+/// sort, a sort missing its parameter, or an unknown `near` body.
+async fn asteroid_query_handler(
+    State(app_state): State<AppState>,
+    Query(params): Query<AsteroidQueryParams>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    use sim_core::asteroid_ranking::{asteroid_candidates, AsteroidQuery, AsteroidSort};
+
+    let bad_request = |error: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": error })),
+        )
+    };
+    let sort = match (params.sort.as_deref(), params.element) {
+        (Some("value"), Some(element)) => AsteroidSort::ElementValue(element),
+        (Some("value"), None) => return bad_request("sort=value needs element".to_string()),
+        (Some("mass") | None, _) => AsteroidSort::Mass,
+        (Some("distance"), _) if params.near.is_none() => {
+            return bad_request("sort=distance needs near".to_string())
+        }
+        (Some("distance"), _) => AsteroidSort::Distance,
+        (Some(other), _) => return bad_request(format!("unknown sort '{other}'")),
+    };
+    let sim = app_state.sim.lock();
+    let near = match params.near.map(BodyId) {
+        Some(body) if !sim.game_state.body_cache.contains_key(&body) => {
+            return bad_request(format!("unknown body '{}'", body.0));
+        }
+        near => near.map(|parent_body| sim_core::Position {
+            parent_body,
+            radius_au_um: sim_core::RadiusAuMicro(0),
+            angle_mdeg: sim_core::AngleMilliDeg(0),
+        }),
+    };
+    let query = AsteroidQuery {
+        body: params.body.map(BodyId),
+        composition: params.composition,
+        near,
+        sort,
+        limit: params.limit,
+    };
+    let results = asteroid_candidates(&sim.game_state, &query);
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "tick": sim.game_state.meta.tick,
+            "count": results.len(),
+            "results": results,
+        })),
+    )
+}
+
 async fn score_handler(
     State(app_state): State<AppState>,
 ) -> (StatusCode, [(header::HeaderName, &'static str); 1], String) {
@@ -291,32 +786,215 @@ async fn score_handler(
     }
 }
 
-pub async fn pause_handler(State(app_state): State<AppState>) -> Json<serde_json::Value> {
-    app_state.paused.store(true, Ordering::Relaxed);
-    Json(serde_json::json!({"paused": true}))
+/// The optional `reason` of a pause request body.
+fn pause_reason(body: Option<&serde_json::Value>) -> Result<Option<String>, String> {
+    match body.and_then(|body| body.get("reason")) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(reason)) => Ok(Some(reason.clone())),
+        Some(_) => Err("reason must be a string".to_string()),
+    }
+}
+
+/// Pause now. The body is optional: `{ reason? }`.
+pub async fn pause_handler(
+    State(app_state): State<AppState>,
+    body: Option<Json<serde_json::Value>>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let reason = match pause_reason(body.as_deref()) {
+        Ok(reason) => reason,
+        Err(error) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": error})),
+            )
+        }
+    };
+    let mut sim = app_state.sim.lock();
+    crate::pause::pause(
+        &mut sim,
+        &app_state.paused,
+        &app_state.event_tx,
+        reason,
+        false,
+    );
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({"paused": true, "reason": sim.pauses.reason()})),
+    )
+}
+
+/// Pause when the sim reaches `at_tick`: `{ at_tick, reason? }`. A tick
+/// already past is rejected; the current tick pauses before it runs.
+pub async fn pause_schedule_handler(
+    State(app_state): State<AppState>,
+    Json(body): Json<serde_json::Value>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let bad_request = |error: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": error})),
+        )
+    };
+    let Some(at_tick) = body.get("at_tick").and_then(serde_json::Value::as_u64) else {
+        return bad_request("missing or invalid at_tick".to_string());
+    };
+    let reason = match pause_reason(Some(&body)) {
+        Ok(reason) => reason,
+        Err(error) => return bad_request(error),
+    };
+    let mut sim = app_state.sim.lock();
+    let tick = sim.game_state.meta.tick;
+    if at_tick < tick {
+        return bad_request(format!(
+            "at_tick {at_tick} is before the current tick {tick}"
+        ));
+    }
+    sim.pauses
+        .schedule(crate::pause::ScheduledPause { at_tick, reason });
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({"scheduled_pauses": sim.pauses.pending()})),
+    )
 }
 
 pub async fn resume_handler(State(app_state): State<AppState>) -> Json<serde_json::Value> {
-    app_state.paused.store(false, Ordering::Relaxed);
+    let mut sim = app_state.sim.lock();
+    crate::pause::resume(&mut sim, &app_state.paused, &app_state.event_tx);
     Json(serde_json::json!({"paused": false}))
 }
 
+/// Most ticks one `POST /api/v1/fast_forward` may run.
+pub const MAX_FAST_FORWARD_TICKS: u64 = 100_000;
+
+#[derive(serde::Deserialize)]
+pub struct FastForwardRequest {
+    pub ticks: u64,
+}
+
+/// Run ticks unpaced while the run is paused, leaving `ticks_per_sec`
+/// alone. Ticking happens on the blocking pool.
+async fn fast_forward_handler(
+    State(app_state): State<AppState>,
+    Json(request): Json<FastForwardRequest>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    if !(1..=MAX_FAST_FORWARD_TICKS).contains(&request.ticks) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": format!("ticks must be between 1 and {MAX_FAST_FORWARD_TICKS}")
+            })),
+        );
+    }
+    if !app_state.paused.load(Ordering::Relaxed) {
+        return (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({"error": "pause the run before fast-forwarding"})),
+        );
+    }
+    let from_tick = app_state.sim.lock().game_state.meta.tick;
+    let state = app_state.clone();
+    let ran = tokio::task::spawn_blocking(move || {
+        crate::tick_loop::fast_forward(
+            &state.sim,
+            &state.command_queue,
+            &state.event_tx,
+            &state.paused,
+            request.ticks,
+            state.runs.max_ticks,
+        )
+    })
+    .await;
+    let Ok((ticks_run, stopped_by)) = ran else {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": "fast-forward task failed"})),
+        );
+    };
+    let sim = app_state.sim.lock();
+    let metrics = sim_core::compute_metrics(&sim.game_state, &sim.content);
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "from_tick": from_tick,
+            "tick": sim.game_state.meta.tick,
+            "ticks_run": ticks_run,
+            "stopped_by": stopped_by,
+            "metrics": metrics,
+        })),
+    )
+}
+
+/// Parse the `command` of a submission body, honouring its optional
+/// `command_schema_version`.
+fn parse_command(body: &serde_json::Value) -> Result<sim_core::Command, String> {
+    // Clients may pin the command schema they were written against. Older
+    // versions are served through serde aliases; newer ones cannot be.
+    if let Some(version) = body.get("command_schema_version") {
+        let supported = sim_core::COMMAND_SCHEMA_VERSION;
+        if version.as_u64().is_none_or(|v| v > u64::from(supported)) {
+            return Err(format!(
+                "unsupported command_schema_version {version} (server supports up to {supported})"
+            ));
+        }
+    }
+    serde_json::from_value(body["command"].clone()).map_err(|err| format!("invalid command: {err}"))
+}
+
+/// Journal `envelopes`, then queue them. Nothing is queued when the journal
+/// write fails, so every acknowledged command is on disk.
+fn enqueue(
+    app_state: &AppState,
+    envelopes: Vec<CommandEnvelope>,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    if let Some(journal) = &app_state.command_journal {
+        if let Err(err) = journal.lock().append(&envelopes) {
+            tracing::error!("command journal write failed: {err:#}");
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": format!("command journal: {err:#}")})),
+            ));
+        }
+    }
+    app_state.command_queue.lock().extend(envelopes);
+    Ok(())
+}
+
+/// The principal single-command routes act for: the API key's, or the
+/// player when the daemon has no keys.
+fn acting_principal(authenticated: Option<Extension<Authenticated>>) -> PrincipalId {
+    authenticated.map_or_else(
+        || PrincipalId("principal_player".to_string()),
+        |Extension(Authenticated(principal))| principal,
+    )
+}
+
+/// Queue one command for the next tick, issued by the acting principal.
+/// With API keys it passes the same ownership check as a batch entry; a
+/// keyless daemon is single-player, so the UI may command every station.
 pub async fn command_handler(
     State(app_state): State<AppState>,
+    authenticated: Option<Extension<Authenticated>>,
     Json(body): Json<serde_json::Value>,
 ) -> (StatusCode, Json<serde_json::Value>) {
-    let command: sim_core::Command = match serde_json::from_value(body["command"].clone()) {
+    let command = match parse_command(&body) {
         Ok(cmd) => cmd,
-        Err(err) => {
+        Err(error) => {
             return (
                 StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({"error": format!("invalid command: {err}")})),
+                Json(serde_json::json!({ "error": error })),
             );
         }
     };
+    let keyed = authenticated.is_some();
+    let issued_by = acting_principal(authenticated);
 
     let (command_id, tick) = {
         let mut sim = app_state.sim.lock();
+        if keyed {
+            if let Err((status, error)) = check_ownership(&command, &issued_by, &sim.game_state) {
+                return (status, Json(serde_json::json!({ "error": error })));
+            }
+        }
         let id_num = sim.next_command_id;
         sim.next_command_id += 1;
         let tick = sim.game_state.meta.tick;
@@ -325,17 +1003,240 @@ pub async fn command_handler(
 
     let envelope = CommandEnvelope {
         id: command_id,
-        issued_by: PrincipalId("principal_player".to_string()),
+        issued_by,
         issued_tick: tick,
         execute_at_tick: tick,
         command,
     };
 
-    app_state.command_queue.lock().push(envelope);
+    if let Err(response) = enqueue(&app_state, vec![envelope]) {
+        return response;
+    }
 
     (
         StatusCode::OK,
-        Json(serde_json::json!({"command_id": command_id.0})),
+        Json(serde_json::json!({
+            "command_id": command_id.0,
+            "command_schema_version": sim_core::COMMAND_SCHEMA_VERSION,
+        })),
+    )
+}
+
+/// Queue one command envelope or an array of them, alongside the
+/// autopilot's commands. Each entry is
+/// `{ issued_by, command, execute_at_tick?, command_schema_version? }`; the
+/// server assigns `id` and `issued_tick`, ignoring any sent.
+/// `execute_at_tick` defaults to the next tick and may not be earlier; a
+/// later one waits in the queue (and the command journal) until then. With
+/// API keys, `issued_by` defaults to the key's principal and may not name
+/// another. A command aimed at a ship or station must be issued by its
+/// owner. The batch is checked in full before anything is queued, so a
+/// rejected entry (reported with its `index`) queues nothing.
+pub async fn commands_handler(
+    State(app_state): State<AppState>,
+    authenticated: Option<Extension<Authenticated>>,
+    Json(body): Json<serde_json::Value>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let authenticated = authenticated.map(|Extension(Authenticated(principal))| principal);
+    let entries = match body {
+        serde_json::Value::Array(entries) => entries,
+        single => vec![single],
+    };
+    let mut sim = app_state.sim.lock();
+    let mut submissions = Vec::with_capacity(entries.len());
+    for (index, entry) in entries.iter().enumerate() {
+        match validate_submission(entry, &sim.game_state, authenticated.as_ref()) {
+            Ok(submission) => submissions.push(submission),
+            Err((status, error)) => {
+                return (
+                    status,
+                    Json(serde_json::json!({ "error": error, "index": index })),
+                );
+            }
+        }
+    }
+
+    let tick = sim.game_state.meta.tick;
+    let mut command_ids = Vec::with_capacity(submissions.len());
+    let mut envelopes = Vec::with_capacity(submissions.len());
+    for (issued_by, command, execute_at_tick) in submissions {
+        let id = CommandId(sim.next_command_id);
+        sim.next_command_id += 1;
+        command_ids.push(id.0);
+        envelopes.push(CommandEnvelope {
+            id,
+            issued_by,
+            issued_tick: tick,
+            execute_at_tick,
+            command,
+        });
+    }
+    if let Err(response) = enqueue(&app_state, envelopes) {
+        return response;
+    }
+
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "command_ids": command_ids,
+            "tick": tick,
+            "command_schema_version": sim_core::COMMAND_SCHEMA_VERSION,
+        })),
+    )
+}
+
+fn validate_submission(
+    entry: &serde_json::Value,
+    game_state: &sim_core::GameState,
+    authenticated: Option<&PrincipalId>,
+) -> Result<(PrincipalId, sim_core::Command, u64), (StatusCode, String)> {
+    let issued_by: PrincipalId = match (entry.get("issued_by"), authenticated) {
+        (None | Some(serde_json::Value::Null), Some(principal)) => principal.clone(),
+        (value, _) => serde_json::from_value(value.cloned().unwrap_or_default())
+            .map_err(|err| (StatusCode::BAD_REQUEST, format!("invalid issued_by: {err}")))?,
+    };
+    if let Some(principal) = authenticated.filter(|principal| **principal != issued_by) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            format!(
+                "API key for {} cannot issue as {}",
+                principal.0, issued_by.0
+            ),
+        ));
+    }
+    let command = parse_command(entry).map_err(|error| (StatusCode::BAD_REQUEST, error))?;
+    let tick = game_state.meta.tick;
+    let execute_at_tick = match entry.get("execute_at_tick") {
+        None | Some(serde_json::Value::Null) => tick,
+        Some(value) => match value.as_u64() {
+            Some(at) if at >= tick => at,
+            _ => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!("execute_at_tick {value} is before the current tick {tick}"),
+                ))
+            }
+        },
+    };
+    check_ownership(&command, &issued_by, game_state)?;
+    Ok((issued_by, command, execute_at_tick))
+}
+
+/// Refuse a command aimed at a ship or station `issued_by` does not own,
+/// including the station behind an import order or project it cancels.
+fn check_ownership(
+    command: &sim_core::Command,
+    issued_by: &PrincipalId,
+    game_state: &sim_core::GameState,
+) -> Result<(), (StatusCode, String)> {
+    let forbidden = |target: &dyn std::fmt::Display| {
+        (
+            StatusCode::FORBIDDEN,
+            format!("{} does not own {target}", issued_by.0),
+        )
+    };
+    let mut ship_ids: Vec<&sim_core::ShipId> = command.ship_id().into_iter().collect();
+    if let sim_core::Command::TransferCargo {
+        counterparty: sim_core::CargoHolder::Ship(ship_id),
+        ..
+    } = command
+    {
+        ship_ids.push(ship_id);
+    }
+    for ship_id in ship_ids {
+        let Some(ship) = game_state.ships.get(ship_id) else {
+            return Err((StatusCode::BAD_REQUEST, format!("unknown ship {ship_id}")));
+        };
+        if ship.owner != *issued_by {
+            return Err(forbidden(ship_id));
+        }
+    }
+
+    let mut station_ids = command.station_ids();
+    match command {
+        sim_core::Command::CancelImportOrder { order_id } => station_ids.extend(
+            game_state
+                .import_orders
+                .iter()
+                .filter(|order| order.id == *order_id)
+                .map(|order| &order.station_id),
+        ),
+        sim_core::Command::CancelProject { project_id } => station_ids.extend(
+            game_state
+                .projects
+                .iter()
+                .filter(|project| project.id == *project_id)
+                .map(|project| &project.station_id),
+        ),
+        _ => {}
+    }
+    for station_id in station_ids {
+        let Some(station) = game_state.stations.get(station_id) else {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("unknown station {station_id}"),
+            ));
+        };
+        if station.owner != *issued_by {
+            return Err(forbidden(station_id));
+        }
+    }
+    Ok(())
+}
+
+/// Why a station module is not running: the `IdleReason` chain from
+/// `sim_core::why_not_running`, empty when nothing blocks it.
+pub async fn module_diagnosis_handler(
+    State(app_state): State<AppState>,
+    Path((station_id, module_id)): Path<(String, String)>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let sim = app_state.sim.lock();
+    let station_id = sim_core::StationId(station_id);
+    let module_id = sim_core::ModuleInstanceId(module_id);
+    let Some(reasons) =
+        sim_core::why_not_running(&sim.game_state, &sim.content, &station_id, &module_id)
+    else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "error": format!("no module {module_id} at station {station_id}"),
+            })),
+        );
+    };
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "tick": sim.game_state.meta.tick,
+            "station_id": station_id,
+            "module_id": module_id,
+            "running": reasons.is_empty(),
+            "reasons": reasons,
+        })),
+    )
+}
+
+/// A ship's cargo with hold usage by partition.
+pub async fn ship_manifest_handler(
+    State(app_state): State<AppState>,
+    Path(ship_id): Path<String>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let sim = app_state.sim.lock();
+    let ship_id = ShipId(ship_id);
+    let Some(ship) = sim.game_state.ships.get(&ship_id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("no ship {ship_id}")})),
+        );
+    };
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "tick": sim.game_state.meta.tick,
+            "ship_id": ship_id,
+            "hull_id": ship.hull_id,
+            "items": ship.inventory,
+            "hold": sim_core::hold_usage(ship, &sim.content),
+        })),
     )
 }
 
@@ -344,6 +1245,36 @@ pub async fn pricing_handler(State(app_state): State<AppState>) -> Json<sim_core
     Json(sim.content.pricing.clone())
 }
 
+/// Current trade prices: each priced item's base price, its market
+/// multiplier and the resulting unit price.
+pub async fn market_handler(State(app_state): State<AppState>) -> Json<serde_json::Value> {
+    let sim = app_state.sim.lock();
+    let market = &sim.game_state.market;
+    let items: BTreeMap<&String, serde_json::Value> = sim
+        .content
+        .pricing
+        .items
+        .iter()
+        .map(|(key, entry)| {
+            let item = market.items.get(key);
+            (
+                key,
+                serde_json::json!({
+                    "base_price_per_unit": entry.base_price_per_unit,
+                    "current_price_per_unit":
+                        sim_core::market::current_price(&sim.content, market, key),
+                    "multiplier": market.multiplier(key),
+                    "net_volume": item.map_or(0.0, |item| item.net_volume),
+                }),
+            )
+        })
+        .collect();
+    Json(serde_json::json!({
+        "tick": sim.game_state.meta.tick,
+        "items": items,
+    }))
+}
+
 /// Return the currently active `StrategyConfig` from `GameState`. Used by
 /// the MCP advisor (VIO-484) and any UI/automation client tuning strategy.
 pub async fn strategy_get_handler(
@@ -359,6 +1290,7 @@ pub async fn strategy_get_handler(
 /// tick coalesce to the last one.
 pub async fn strategy_post_handler(
     State(app_state): State<AppState>,
+    authenticated: Option<Extension<Authenticated>>,
     Json(config): Json<sim_core::StrategyConfig>,
 ) -> (StatusCode, Json<serde_json::Value>) {
     let (command_id, tick) = {
@@ -371,13 +1303,15 @@ pub async fn strategy_post_handler(
 
     let envelope = CommandEnvelope {
         id: command_id,
-        issued_by: PrincipalId("principal_player".to_string()),
+        issued_by: acting_principal(authenticated),
         issued_tick: tick,
         execute_at_tick: tick,
         command: sim_core::Command::SetStrategyConfig { config },
     };
 
-    app_state.command_queue.lock().push(envelope);
+    if let Err(response) = enqueue(&app_state, vec![envelope]) {
+        return response;
+    }
 
     (
         StatusCode::OK,
@@ -499,6 +1433,7 @@ pub async fn content_handler(State(app_state): State<AppState>) -> Json<ContentR
         recipes: sim.content.recipes.clone(),
         event_defs,
         hulls: sim.content.hulls.clone(),
+        ship_classes: sim.content.ship_classes.clone(),
         frames: sim.content.frames.clone(),
     })
 }
@@ -512,6 +1447,8 @@ pub struct ContentResponse {
     pub recipes: std::collections::BTreeMap<sim_core::RecipeId, sim_core::RecipeDef>,
     pub event_defs: Vec<EventDefInfo>,
     pub hulls: std::collections::BTreeMap<sim_core::HullId, sim_core::HullDef>,
+    /// Ship class catalog. Empty when `content/ship_classes.json` is missing.
+    pub ship_classes: std::collections::BTreeMap<String, sim_core::ShipClassDef>,
     /// Station frame catalog. Empty when `content/frame_defs.json` is missing.
     pub frames: std::collections::BTreeMap<sim_core::FrameId, sim_core::FrameDef>,
 }
@@ -564,6 +1501,21 @@ pub async fn speed_handler(
     )
 }
 
+#[derive(serde::Deserialize)]
+pub struct EventLevelRequest {
+    pub level: sim_core::EventLevel,
+}
+
+/// Change which events the run keeps from the next tick on. Events below
+/// the level are not streamed, logged or written to `events.jsonl`.
+pub async fn event_level_handler(
+    State(app_state): State<AppState>,
+    Json(request): Json<EventLevelRequest>,
+) -> Json<serde_json::Value> {
+    app_state.sim.lock().event_level = request.level;
+    Json(serde_json::json!({"event_level": request.level}))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -580,8 +1532,13 @@ mod tests {
             .to_string_lossy()
             .to_string();
         let content = sim_world::load_content(&content_dir).expect("load content");
-        let (game_state, rng) =
-            sim_world::load_or_build_state(&content, Some(1), None).expect("build state");
+        let (game_state, rng) = sim_world::load_or_build_state(
+            &content,
+            Some(1),
+            None,
+            sim_world::OrphanedModulePolicy::default(),
+        )
+        .expect("build state");
         let (event_tx, _) = broadcast::channel(16);
         let sim = Arc::new(Mutex::new(SimState {
             game_state,
@@ -596,14 +1553,27 @@ mod tests {
             timings_history: VecDeque::new(),
             score_history: VecDeque::new(),
             last_threshold: String::new(),
+            timeline: crate::timeline::TimelineCompactor::new(0, 0, 0),
+            event_level: sim_core::EventLevel::Debug,
+            checkpoints: crate::checkpoint::CheckpointStore::default(),
+            reporter: sim_world::RunReporter::default(),
+            state_hashes: None,
+            event_log: crate::event_log::EventLog::default(),
+            pauses: crate::pause::PauseSchedule::default(),
+            event_store: None,
+            snapshot_delta: crate::snapshot_delta::DeltaTracker::default(),
+            run_dir: None,
+            autosave: None,
         }));
         AppState {
             sim,
             command_queue: Arc::new(Mutex::new(Vec::new())),
             event_tx,
             ticks_per_sec: Arc::new(AtomicU64::new(0)),
-            run_dir: None,
             paused: Arc::new(AtomicBool::new(false)),
+            command_journal: None,
+            api_keys: None,
+            runs: std::sync::Arc::default(),
         }
     }
 
@@ -643,7 +1613,7 @@ mod tests {
         };
 
         let (status, Json(_body)) =
-            strategy_post_handler(State(state.clone()), Json(new_config.clone())).await;
+            strategy_post_handler(State(state.clone()), None, Json(new_config.clone())).await;
         assert_eq!(status, StatusCode::OK);
 
         let queue = state.command_queue.lock();
@@ -659,24 +1629,100 @@ mod tests {
     }
 }
 
+#[derive(serde::Deserialize)]
+pub struct StreamQuery {
+    /// Resume after this cursor, for clients that cannot set `Last-Event-ID`.
+    last_event_id: Option<u64>,
+    /// Drop events below this level for this subscriber only.
+    level: Option<sim_core::EventLevel>,
+}
+
+/// Whether a subscriber filtering at `level` wants `event`.
+fn wanted(level: Option<sim_core::EventLevel>, event: &sim_core::Event) -> bool {
+    level.is_none_or(|level| event.level() >= level)
+}
+
+/// The cursor a reconnecting SSE client asks to resume after.
+fn resume_cursor(headers: &HeaderMap, query: &StreamQuery) -> Option<u64> {
+    headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .or(query.last_event_id)
+}
+
+/// SSE stream of event batches and heartbeats. Each batch frame's `id` is
+/// the cursor of its last event (see `event_log`). A client reconnecting
+/// with `Last-Event-ID` (or `?last_event_id=`) first gets the retained
+/// events after that cursor, preceded by a `{"gap": true, ..}` frame if some
+/// were already evicted.
 pub async fn stream_handler(
     State(app_state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<StreamQuery>,
 ) -> Sse<impl futures_core::Stream<Item = Result<Event, Infallible>>> {
+    let resume_from = resume_cursor(&headers, &query);
+    let level = query.level;
+    // Subscribe before reading the log so no batch falls between the two;
+    // batches already in the backlog are skipped by cursor.
     let mut rx = app_state.event_tx.subscribe();
     let sim = app_state.sim.clone();
+    let (backlog, mut through) = {
+        let sim = sim.lock();
+        match resume_from {
+            Some(cursor) => (Some(sim.event_log.since(cursor)), cursor),
+            None => (None, sim.event_log.next_cursor() - 1),
+        }
+    };
 
     let stream = async_stream::stream! {
+        let mut pending: Vec<EventEnvelope> = Vec::new();
+        if let Some(backlog) = backlog {
+            if backlog.gap {
+                let gap = serde_json::json!({"gap": true, "last_event_id": through});
+                yield Ok(Event::default().data(gap.to_string()));
+            }
+            if let Some((cursor, _)) = backlog.events.last() {
+                through = *cursor;
+                let events: Vec<&EventEnvelope> = backlog
+                    .events
+                    .iter()
+                    .map(|(_, event)| event)
+                    .filter(|event| wanted(level, &event.event))
+                    .collect();
+                if !events.is_empty() {
+                    let data = serde_json::to_string(&events).unwrap_or_default();
+                    yield Ok(Event::default().id(through.to_string()).data(data));
+                }
+            }
+        }
         let mut heartbeat = tokio::time::interval(Duration::from_millis(200));
         heartbeat.tick().await; // discard the immediate first tick
         let mut flush = tokio::time::interval(Duration::from_millis(50));
         flush.tick().await; // discard the immediate first tick
-        let mut pending: Vec<EventEnvelope> = Vec::new();
         loop {
             tokio::select! {
                 result = rx.recv() => {
                     match result {
-                        Ok(events) => pending.extend(events),
-                        Err(broadcast::error::RecvError::Lagged(_)) => {}
+                        Ok(batch) => {
+                            for (cursor, event) in (batch.first_cursor..).zip(batch.events) {
+                                if cursor > through {
+                                    through = cursor;
+                                    if wanted(level, &event.event) {
+                                        pending.push(event);
+                                    }
+                                }
+                            }
+                        }
+                        // Fell behind the channel: catch up from the log.
+                        Err(broadcast::error::RecvError::Lagged(_)) => {
+                            for (cursor, event) in sim.lock().event_log.since(through).events {
+                                through = cursor;
+                                if wanted(level, &event.event) {
+                                    pending.push(event);
+                                }
+                            }
+                        }
                         Err(broadcast::error::RecvError::Closed) => break,
                     }
                 }
@@ -684,7 +1730,7 @@ pub async fn stream_handler(
                     if !pending.is_empty() {
                         let data = serde_json::to_string(&pending).unwrap_or_default();
                         pending.clear();
-                        yield Ok(Event::default().data(data));
+                        yield Ok(Event::default().id(through.to_string()).data(data));
                     }
                 }
                 _ = heartbeat.tick() => {
@@ -702,3 +1748,130 @@ pub async fn stream_handler(
             .text("ping"),
     )
 }
+
+/// `event: tick` frames for `(cursor, event)` pairs, one per run of events
+/// from the same tick. Each frame's `id` is the cursor of its last event.
+fn tick_frames(events: &[(u64, serde_json::Value)]) -> impl Iterator<Item = Event> + '_ {
+    events
+        .chunk_by(|(_, a), (_, b)| a["tick"] == b["tick"])
+        .map(|run| {
+            let (last, _) = run[run.len() - 1];
+            let events: Vec<&serde_json::Value> = run.iter().map(|(_, event)| event).collect();
+            let data = serde_json::json!({"tick": run[0].1["tick"], "events": events});
+            Event::default()
+                .event("tick")
+                .id(last.to_string())
+                .data(data.to_string())
+        })
+}
+
+fn cursor_values(
+    events: impl IntoIterator<Item = (u64, EventEnvelope)>,
+) -> Vec<(u64, serde_json::Value)> {
+    events
+        .into_iter()
+        .map(|(cursor, event)| (cursor, serde_json::to_value(event).unwrap_or_default()))
+        .collect()
+}
+
+/// [`wanted`] for an event read back from `events.jsonl`.
+fn wanted_value(level: Option<sim_core::EventLevel>, value: &serde_json::Value) -> bool {
+    level.is_none()
+        || serde_json::from_value::<sim_core::Event>(value["event"].clone())
+            .is_ok_and(|event| wanted(level, &event))
+}
+
+/// SSE stream with one `event: tick` frame per tick, `{"tick", "events"}`,
+/// for clients that want the broadcast as plain HTTP. A client
+/// reconnecting with `Last-Event-ID` (or `?last_event_id=`) gets the events
+/// after that cursor first: from the in-memory log, or from the run's
+/// `events.jsonl` history once they have left it. Only events lost from
+/// both are reported, by an `event: gap` frame.
+pub async fn event_stream_handler(
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<StreamQuery>,
+) -> Sse<impl futures_core::Stream<Item = Result<Event, Infallible>>> {
+    let resume_from = resume_cursor(&headers, &query);
+    let level = query.level;
+    let mut rx = app_state.event_tx.subscribe();
+    let sim = app_state.sim.clone();
+    let (backlog, history, mut through) = {
+        let sim = sim.lock();
+        match resume_from {
+            Some(cursor) => {
+                let backlog = sim.event_log.since(cursor);
+                let history = backlog
+                    .gap
+                    .then(|| {
+                        sim.event_store
+                            .as_ref()
+                            .map(crate::event_store::EventStore::files)
+                    })
+                    .flatten();
+                (Some(backlog), history, cursor)
+            }
+            None => (None, None, sim.event_log.next_cursor() - 1),
+        }
+    };
+
+    let stream = async_stream::stream! {
+        if let Some(backlog) = backlog {
+            let retained_from = backlog.events.first().map_or(through + 1, |(cursor, _)| *cursor);
+            let mut replay = Vec::new();
+            if let Some(files) = history {
+                let after = through;
+                let read = tokio::task::spawn_blocking(move || {
+                    crate::event_store::read_cursors(&files, after, retained_from - 1)
+                })
+                .await;
+                match read {
+                    Ok(Ok(events)) => replay = events,
+                    Ok(Err(err)) => tracing::warn!("event history replay failed: {err:#}"),
+                    Err(err) => tracing::warn!("event history replay failed: {err}"),
+                }
+            }
+            let recovered = replay.last().map_or(through, |(cursor, _)| *cursor);
+            if backlog.gap && recovered + 1 < retained_from {
+                let gap = serde_json::json!({"last_event_id": recovered});
+                yield Ok(Event::default().event("gap").data(gap.to_string()));
+            }
+            replay.extend(cursor_values(backlog.events));
+            if let Some((cursor, _)) = replay.last() {
+                through = *cursor;
+            }
+            replay.retain(|(_, event)| wanted_value(level, event));
+            for frame in tick_frames(&replay) {
+                yield Ok(frame);
+            }
+        }
+        loop {
+            let events: Vec<(u64, EventEnvelope)> = match rx.recv().await {
+                Ok(batch) => (batch.first_cursor..).zip(batch.events).collect(),
+                // Fell behind the channel: catch up from the log.
+                Err(broadcast::error::RecvError::Lagged(_)) => {
+                    sim.lock().event_log.since(through).events
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            let fresh: Vec<_> = events.into_iter().filter(|(cursor, _)| *cursor > through).collect();
+            if let Some((cursor, _)) = fresh.last() {
+                through = *cursor;
+            }
+            let fresh = cursor_values(
+                fresh
+                    .into_iter()
+                    .filter(|(_, envelope)| wanted(level, &envelope.event)),
+            );
+            for frame in tick_frames(&fresh) {
+                yield Ok(frame);
+            }
+        }
+    };
+
+    Sse::new(stream).keep_alive(
+        axum::response::sse::KeepAlive::new()
+            .interval(Duration::from_secs(15))
+            .text("ping"),
+    )
+}
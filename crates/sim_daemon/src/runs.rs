@@ -0,0 +1,345 @@
+//! Run management for `/api/v1/runs`.
+//!
+//! The daemon lists the run and batch directories under `runs/`, serves a
+//! run's `run_info.json`, `report.json` and latest metrics row, and can
+//! launch a fresh run in place of the current one. A launched run inherits
+//! the daemon's recording flags (run directory, timeline, state hashes,
+//! event history) and autopilot profile; only the seed, content and
+//! notebook fields come from the request. The tick loop keeps going, so
+//! the new run starts paused or running as the old one was, and
+//! `--max-ticks` still ends the loop for good once reached.
+
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use sim_world::{RunKind, RunSetupBuilder};
+
+use crate::state::AppState;
+
+/// Daemon settings a launched run inherits.
+#[derive(Debug, Clone)]
+pub struct RunLauncher {
+    /// Directory listed by `GET /api/v1/runs`. Recorded runs are always
+    /// created under `runs/`.
+    pub root: PathBuf,
+    /// Base content directory when the request names none.
+    pub content_dir: String,
+    /// Whether launched runs get a run directory (off with `--no-metrics`).
+    pub record: bool,
+    pub max_ticks: Option<u64>,
+    pub timeline_every: u64,
+    pub keyframe_every: u64,
+    pub hash_every: Option<u64>,
+    pub events_rotate_mb: u64,
+    pub metrics_format: sim_world::MetricsFormat,
+}
+
+impl Default for RunLauncher {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::from("runs"),
+            content_dir: "./content".to_string(),
+            record: false,
+            max_ticks: None,
+            timeline_every: 0,
+            keyframe_every: 0,
+            hash_every: None,
+            events_rotate_mb: 0,
+            metrics_format: sim_world::MetricsFormat::Csv,
+        }
+    }
+}
+
+/// Body of `POST /api/v1/runs`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LaunchRequest {
+    /// World seed; the default seed when omitted.
+    pub seed: Option<u64>,
+    /// Replaces the daemon's `--content-dir`.
+    pub content_dir: Option<String>,
+    /// Packs layered over the content directory, in order.
+    #[serde(default)]
+    pub content_packs: Vec<String>,
+    pub hypothesis: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Why a launch failed.
+#[derive(Debug)]
+pub enum LaunchError {
+    /// The requested content could not be loaded.
+    Content(anyhow::Error),
+    /// Setting up the new run failed.
+    Setup(anyhow::Error),
+}
+
+/// `{ runs: [{ run_id, kind, seeds, experiment }] }` for the directories
+/// under the launcher's root. A missing root lists nothing.
+pub fn list(launcher: &RunLauncher) -> Result<Value> {
+    let listings = if launcher.root.is_dir() {
+        sim_world::list_runs(&launcher.root)?
+    } else {
+        Vec::new()
+    };
+    let runs: Vec<Value> = listings
+        .into_iter()
+        .map(|listing| {
+            serde_json::json!({
+                "run_id": dir_name(&listing.dir),
+                "kind": match listing.kind {
+                    RunKind::Run => "run",
+                    RunKind::Batch => "batch",
+                },
+                "name": listing.name,
+                "seeds": listing.seeds,
+                "experiment": listing.experiment,
+            })
+        })
+        .collect();
+    Ok(serde_json::json!({ "runs": runs }))
+}
+
+fn dir_name(dir: &Path) -> String {
+    dir.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// `{ run_id, run_info, batch_summary, report, metrics }` for one
+/// directory under the root, or `None` when there is no such run. Ids
+/// that are not a plain directory name are treated as missing.
+pub fn inspect(launcher: &RunLauncher, run_id: &str) -> Result<Option<Value>> {
+    let plain = !run_id.is_empty() && !run_id.starts_with('.') && !run_id.contains(['/', '\\']);
+    let dir = launcher.root.join(run_id);
+    if !plain || !dir.is_dir() {
+        return Ok(None);
+    }
+    let run_info = read_json(&dir.join("run_info.json"))?;
+    let batch_summary = read_json(&dir.join("batch_summary.json"))?;
+    if run_info.is_null() && batch_summary.is_null() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::json!({
+        "run_id": run_id,
+        "run_info": run_info,
+        "batch_summary": batch_summary,
+        "report": read_json(&dir.join("report.json"))?,
+        "metrics": latest_metrics(&dir)?,
+    })))
+}
+
+/// The file's JSON, or null when it does not exist.
+fn read_json(path: &Path) -> Result<Value> {
+    if !path.exists() {
+        return Ok(Value::Null);
+    }
+    let text =
+        std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    serde_json::from_str(&text).with_context(|| format!("parsing {}", path.display()))
+}
+
+/// `{ files, latest }` for the run's metrics CSVs, where `latest` maps the
+/// header to the last row written. Null when the run has no metrics.
+fn latest_metrics(dir: &Path) -> Result<Value> {
+    let mut files: Vec<String> = std::fs::read_dir(dir)
+        .with_context(|| format!("reading {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| dir_name(&entry.path())))
+        .filter(|name| {
+            name.starts_with("metrics_")
+                && Path::new(name)
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+        })
+        .collect();
+    files.sort();
+    let Some(last) = files.last() else {
+        return Ok(Value::Null);
+    };
+    let path = dir.join(last);
+    let file = std::fs::File::open(&path).with_context(|| format!("reading {}", path.display()))?;
+    let mut lines = BufReader::new(file).lines();
+    let header = lines.next().transpose()?.unwrap_or_default();
+    let latest = match lines.last().transpose()? {
+        Some(row) => Value::Object(
+            header
+                .split(',')
+                .zip(row.split(','))
+                .map(|(column, cell)| {
+                    let value = cell
+                        .parse::<f64>()
+                        .ok()
+                        .and_then(serde_json::Number::from_f64)
+                        .map_or_else(|| Value::String(cell.to_string()), Value::Number);
+                    (column.to_string(), value)
+                })
+                .collect(),
+        ),
+        None => Value::Null,
+    };
+    Ok(serde_json::json!({ "files": files, "latest": latest }))
+}
+
+/// Log the content and state warnings from building a [`sim_world::RunSetup`].
+pub fn log_load_warnings(warnings: &[String]) {
+    for warning in warnings {
+        tracing::warn!("load: {warning}");
+    }
+}
+
+/// Build a new run from `request` and swap it in for the current one.
+/// Returns `{ run_id, run_dir, seed, content_version }`.
+pub fn launch(app_state: &AppState, request: LaunchRequest) -> Result<Value, LaunchError> {
+    let launcher = &app_state.runs;
+    let content_dir = request
+        .content_dir
+        .unwrap_or_else(|| launcher.content_dir.clone());
+    let content_dirs: Vec<&str> = std::iter::once(content_dir.as_str())
+        .chain(request.content_packs.iter().map(String::as_str))
+        .collect();
+    let (metrics_every, controller) = {
+        let sim = app_state.sim.lock();
+        (sim.metrics_every, sim.autopilot.profile())
+    };
+
+    let mut builder = RunSetupBuilder::from_content_packs(&content_dirs)
+        .map_err(LaunchError::Content)?
+        .seed(request.seed);
+    if launcher.record {
+        builder = builder
+            .metrics(
+                metrics_every,
+                serde_json::json!({
+                    "runner": "sim_daemon",
+                    "max_ticks": launcher.max_ticks,
+                    "controller": controller.name(),
+                    "metrics_format": launcher.metrics_format,
+                    "content_packs": request.content_packs,
+                }),
+            )
+            .metrics_format(launcher.metrics_format)
+            .experiment(
+                sim_world::ExperimentMeta {
+                    hypothesis: request.hypothesis,
+                    notes: request.notes,
+                    content_commit: None,
+                }
+                .with_detected_commit(&content_dir),
+            );
+    }
+    let setup = builder.build().map_err(LaunchError::Setup)?;
+    log_load_warnings(&setup.warnings);
+    let run_dir = setup.run_dir.as_deref();
+    let tick = setup.game_state.meta.tick;
+    let timeline = crate::timeline::TimelineCompactor::for_run(
+        launcher.timeline_every,
+        launcher.keyframe_every,
+        tick,
+        run_dir,
+    )
+    .context("opening timeline files")
+    .map_err(LaunchError::Setup)?;
+    let state_hashes = match (launcher.hash_every, run_dir) {
+        (Some(every), Some(dir)) => {
+            Some(sim_world::StateHashWriter::create(dir, every).map_err(LaunchError::Setup)?)
+        }
+        _ => None,
+    };
+    let event_store = run_dir
+        .map(|dir| crate::event_store::EventStore::create(dir, launcher.events_rotate_mb))
+        .transpose()
+        .map_err(LaunchError::Setup)?;
+    let journal = match (&app_state.command_journal, run_dir) {
+        (Some(_), Some(dir)) => {
+            Some(crate::journal::CommandJournal::create(dir).map_err(LaunchError::Setup)?)
+        }
+        _ => None,
+    };
+
+    let summary = serde_json::json!({
+        "run_id": setup.run_dir.as_deref().map(dir_name),
+        "run_dir": setup.run_dir,
+        "seed": setup.game_state.meta.seed,
+        "content_version": setup.content.content_version,
+    });
+    let mut sim = app_state.sim.lock();
+    sim.alert_engine = launcher.record.then(|| {
+        crate::alerts::AlertEngine::new(&setup.content.alert_rules, setup.content.techs.len())
+    });
+    sim.reporter = sim_world::RunReporter::new(&setup.game_state);
+    sim.game_state = setup.game_state;
+    sim.content = setup.content;
+    sim.rng = setup.rng;
+    sim.run_dir = setup.run_dir;
+    sim.metrics_writer = setup.metrics_writer;
+    sim.autopilot = sim_control::AutopilotController::with_profile(controller);
+    sim.next_command_id = 0;
+    sim.metrics_history.clear();
+    sim.timings_history.clear();
+    sim.score_history.clear();
+    sim.last_threshold.clear();
+    sim.timeline = timeline;
+    sim.checkpoints = crate::checkpoint::CheckpointStore::default();
+    sim.state_hashes = state_hashes;
+    sim.event_store = event_store;
+    sim.snapshot_delta = crate::snapshot_delta::DeltaTracker::default();
+    if let Some(saver) = sim.autosave.as_mut() {
+        saver.reset(tick);
+    }
+    app_state.command_queue.lock().clear();
+    if let (Some(shared), Some(journal)) = (&app_state.command_journal, journal) {
+        *shared.lock() = journal;
+    }
+    drop(sim);
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inspect_reads_run_files_and_latest_metrics() {
+        let root = tempfile::tempdir().unwrap();
+        let launcher = RunLauncher {
+            root: root.path().to_path_buf(),
+            ..RunLauncher::default()
+        };
+        let dir = root.path().join("20260101_000000_seed7");
+        std::fs::create_dir(&dir).unwrap();
+        std::fs::write(
+            dir.join("run_info.json"),
+            r#"{"run_id": "20260101_000000_seed7", "seed": 7}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("metrics_000.csv"),
+            "tick,station_count\n60,1\n120,2\n",
+        )
+        .unwrap();
+        std::fs::create_dir(root.path().join("scratch")).unwrap();
+
+        let listed = list(&launcher).unwrap();
+        assert_eq!(listed["runs"].as_array().unwrap().len(), 1);
+        assert_eq!(listed["runs"][0]["run_id"], "20260101_000000_seed7");
+        assert_eq!(listed["runs"][0]["seeds"], 7);
+
+        let run = inspect(&launcher, "20260101_000000_seed7")
+            .unwrap()
+            .unwrap();
+        assert_eq!(run["run_info"]["seed"], 7);
+        assert_eq!(run["report"], Value::Null);
+        assert_eq!(
+            run["metrics"]["files"],
+            serde_json::json!(["metrics_000.csv"])
+        );
+        assert_eq!(run["metrics"]["latest"]["tick"], 120.0);
+
+        assert!(inspect(&launcher, "scratch").unwrap().is_none());
+        assert!(inspect(&launcher, "..").unwrap().is_none());
+        assert!(inspect(&launcher, "../etc").unwrap().is_none());
+    }
+}
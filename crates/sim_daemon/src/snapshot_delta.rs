@@ -0,0 +1,195 @@
+//! Incremental snapshots for `GET /api/v1/snapshot/delta?since_tick=`.
+//!
+//! Full snapshots are large, so polling clients can ask for what changed
+//! instead. The tracker keeps a hash of every ship, station and asteroid
+//! and of the research state, refreshed whenever a delta is requested, and
+//! stamps each with the tick its current hash was first seen. A delta
+//! returns the entities stamped after `since_tick` and the ids seen removed
+//! after it. Stamps can be later than the real change, never earlier, so a
+//! delta may repeat unchanged entities but does not miss changed ones.
+//!
+//! Asking for a tick before the tracker's first observation (or before a
+//! checkpoint restore rewound the sim) cannot list removals, so that delta
+//! carries every entity and `complete: true`: the client should replace its
+//! copy rather than merge into it.
+
+use std::collections::BTreeMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use serde_json::{Map, Value};
+use sim_core::GameState;
+
+#[derive(Debug, Default)]
+pub struct DeltaTracker {
+    /// Tick of the first observation since the tracker started or the sim
+    /// rewound.
+    baseline_tick: Option<u64>,
+    last_tick: u64,
+    sections: BTreeMap<&'static str, Section>,
+}
+
+#[derive(Debug, Default)]
+struct Section {
+    /// Hash of each entity's JSON and the tick that hash was first seen.
+    entries: BTreeMap<String, (u64, u64)>,
+    /// Ids no longer in the state, with the tick they were seen gone.
+    removed: BTreeMap<String, u64>,
+}
+
+/// What one section contributes to a delta.
+struct SectionDelta {
+    changed: Map<String, Value>,
+    removed: Vec<String>,
+}
+
+impl Section {
+    /// Record `entities` as of `tick` and report the changes after `since`.
+    fn observe(&mut self, tick: u64, entities: Map<String, Value>, since: u64) -> SectionDelta {
+        let gone: Vec<String> = self
+            .entries
+            .keys()
+            .filter(|id| !entities.contains_key(*id))
+            .cloned()
+            .collect();
+        for id in gone {
+            self.entries.remove(&id);
+            self.removed.insert(id, tick);
+        }
+        let mut changed = Map::new();
+        for (id, entity) in entities {
+            let hash = hash_json(&entity);
+            let stamp = match self.entries.get_mut(&id) {
+                Some(entry) if entry.0 == hash => entry.1,
+                Some(entry) => {
+                    *entry = (hash, tick);
+                    tick
+                }
+                None => {
+                    self.removed.remove(&id);
+                    self.entries.insert(id.clone(), (hash, tick));
+                    tick
+                }
+            };
+            if stamp > since {
+                changed.insert(id, entity);
+            }
+        }
+        let removed = self
+            .removed
+            .iter()
+            .filter(|(_, removed_at)| **removed_at > since)
+            .map(|(id, _)| id.clone())
+            .collect();
+        SectionDelta { changed, removed }
+    }
+}
+
+fn hash_json(value: &Value) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.to_string().hash(&mut hasher);
+    hasher.finish()
+}
+
+impl DeltaTracker {
+    /// Observe `state` and return what changed after `since_tick`, as
+    /// `{ tick, since_tick, complete, ships, stations, asteroids, removed,
+    /// research }`. `research` is null when it has not changed.
+    pub fn delta(&mut self, state: &GameState, since_tick: u64) -> serde_json::Result<Value> {
+        let tick = state.meta.tick;
+        if tick < self.last_tick {
+            *self = Self::default();
+        }
+        self.last_tick = tick;
+        let baseline = *self.baseline_tick.get_or_insert(tick);
+        // A fresh tracker stamps everything with `baseline`, so asking for
+        // an earlier tick already returns every entity.
+        let complete = since_tick < baseline;
+
+        let mut delta = Map::new();
+        let mut removed = Map::new();
+        let sections = [
+            ("ships", serde_json::to_value(&state.ships)?),
+            ("stations", serde_json::to_value(&state.stations)?),
+            ("asteroids", serde_json::to_value(&state.asteroids)?),
+        ];
+        for (name, value) in sections {
+            let Value::Object(entities) = value else {
+                continue;
+            };
+            let section = self.sections.entry(name).or_default();
+            let SectionDelta {
+                changed,
+                removed: gone,
+            } = section.observe(tick, entities, since_tick);
+            delta.insert(name.to_string(), Value::Object(changed));
+            removed.insert(name.to_string(), gone.into());
+        }
+        let research = Map::from_iter([(
+            "research".to_string(),
+            serde_json::to_value(&state.research)?,
+        )]);
+        let mut research = self
+            .sections
+            .entry("research")
+            .or_default()
+            .observe(tick, research, since_tick)
+            .changed;
+
+        delta.insert("tick".to_string(), tick.into());
+        delta.insert("since_tick".to_string(), since_tick.into());
+        delta.insert("complete".to_string(), complete.into());
+        delta.insert("removed".to_string(), Value::Object(removed));
+        delta.insert(
+            "research".to_string(),
+            research.remove("research").unwrap_or(Value::Null),
+        );
+        Ok(Value::Object(delta))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim_core::test_fixtures::{base_content, base_state};
+
+    #[test]
+    fn delta_lists_changed_and_removed_entities() {
+        let content = base_content();
+        let mut state = base_state(&content);
+        let mut tracker = DeltaTracker::default();
+        let ship_id = state.ships.keys().next().unwrap().clone();
+
+        state.meta.tick = 3;
+        let first = tracker.delta(&state, 0).unwrap();
+        assert_eq!(first["complete"], true);
+        assert!(first["ships"].get(&ship_id.0).is_some());
+
+        state.meta.tick = 5;
+        let quiet = tracker.delta(&state, 3).unwrap();
+        assert_eq!(quiet["complete"], false);
+        assert_eq!(quiet["ships"], serde_json::json!({}));
+        assert_eq!(quiet["research"], Value::Null);
+
+        state.meta.tick = 6;
+        state.ships.get_mut(&ship_id).unwrap().propellant_kg -= 1.0;
+        let station_id = state.stations.keys().next().unwrap().clone();
+        state.stations.remove(&station_id);
+        let changed = tracker.delta(&state, 5).unwrap();
+        assert!(changed["ships"].get(&ship_id.0).is_some());
+        assert_eq!(
+            changed["removed"]["stations"],
+            serde_json::json!([station_id.0])
+        );
+        assert_eq!(
+            tracker.delta(&state, 6).unwrap()["ships"],
+            serde_json::json!({})
+        );
+
+        state.meta.tick = 2;
+        let rewound = tracker.delta(&state, 1).unwrap();
+        assert_eq!(
+            rewound["complete"], true,
+            "a restore invalidates earlier stamps"
+        );
+    }
+}
@@ -2,8 +2,7 @@ use parking_lot::Mutex;
 use rand_chacha::ChaCha8Rng;
 use sim_control::AutopilotController;
 use sim_core::{
-    CommandEnvelope, EventEnvelope, GameContent, GameState, MetricsFileWriter, MetricsSnapshot,
-    RunScore, TickTimings,
+    CommandEnvelope, EventEnvelope, GameContent, GameState, MetricsSnapshot, RunScore, TickTimings,
 };
 use std::collections::VecDeque;
 use std::path::PathBuf;
@@ -12,13 +11,13 @@ use std::sync::Arc;
 use tokio::sync::broadcast;
 
 /// Maximum number of metrics snapshots kept in memory.
-pub(crate) const MAX_METRICS_HISTORY: usize = 10_000;
+pub const MAX_METRICS_HISTORY: usize = 10_000;
 
 /// Maximum number of tick timings kept in the rolling buffer.
-pub(crate) const MAX_TIMINGS_HISTORY: usize = 1_000;
+pub const MAX_TIMINGS_HISTORY: usize = 1_000;
 
 /// Maximum number of score snapshots kept in the rolling buffer.
-pub(crate) const MAX_SCORE_HISTORY: usize = 10_000;
+pub const MAX_SCORE_HISTORY: usize = 10_000;
 
 pub struct SimState {
     pub game_state: GameState,
@@ -28,11 +27,32 @@ pub struct SimState {
     pub next_command_id: u64,
     pub metrics_every: u64,
     pub metrics_history: VecDeque<MetricsSnapshot>,
-    pub metrics_writer: Option<MetricsFileWriter>,
+    pub metrics_writer: Option<sim_world::MetricsWriter>,
     pub alert_engine: Option<crate::alerts::AlertEngine>,
     pub timings_history: VecDeque<TickTimings>,
     pub score_history: VecDeque<RunScore>,
     pub last_threshold: String,
+    pub timeline: crate::timeline::TimelineCompactor,
+    /// Events below this level are dropped after the timeline sees them.
+    pub event_level: sim_core::EventLevel,
+    pub checkpoints: crate::checkpoint::CheckpointStore,
+    /// Written as the run's `report.json`/`report.md` when `--max-ticks` ends it.
+    pub reporter: sim_world::RunReporter,
+    /// `state_hashes.csv` writer when `--hash-every` is set.
+    pub state_hashes: Option<sim_world::StateHashWriter>,
+    /// Recent streamed events, replayed to SSE clients that reconnect.
+    pub event_log: crate::event_log::EventLog,
+    /// Why the run is paused and the pauses scheduled ahead.
+    pub pauses: crate::pause::PauseSchedule,
+    /// `events.jsonl` history when the run has a directory.
+    pub event_store: Option<crate::event_store::EventStore>,
+    /// Entity hashes behind `/api/v1/snapshot/delta`.
+    pub snapshot_delta: crate::snapshot_delta::DeltaTracker,
+    /// The run's directory under `runs/`; `None` with `--no-metrics`.
+    pub run_dir: Option<PathBuf>,
+    /// `autosave.json` schedule when `--autosave-every`/`--autosave-secs`
+    /// is set and the run has a directory.
+    pub autosave: Option<crate::autosave::Autosaver>,
 }
 
 impl SimState {
@@ -41,13 +61,49 @@ impl SimState {
             self.metrics_history.pop_front();
         }
         if let Some(ref mut writer) = self.metrics_writer {
-            if let Err(err) = writer.write_row(&snapshot) {
-                tracing::warn!("metrics CSV write failed: {err}");
+            if let Err(err) = writer.write_row(&snapshot, &self.game_state, &self.content) {
+                tracing::warn!("metrics write failed: {err:#}");
             }
         }
         self.metrics_history.push_back(snapshot);
     }
 
+    /// Append the state hash if this tick is on the `--hash-every` interval.
+    /// Flushed per row so the file is complete if the daemon is killed.
+    pub fn record_state_hash(&mut self) {
+        if let Some(ref mut writer) = self.state_hashes {
+            if let Err(err) = writer
+                .observe(&self.game_state)
+                .and_then(|_| writer.flush())
+            {
+                tracing::warn!("state hash write failed: {err:#}");
+            }
+        }
+    }
+
+    /// Write `autosave.json` if the autosave interval has passed. Failures
+    /// are logged; the run goes on.
+    pub fn autosave_if_due(&mut self) {
+        let (Some(saver), Some(dir)) = (self.autosave.as_mut(), self.run_dir.as_deref()) else {
+            return;
+        };
+        if let Err(err) = saver.save_if_due(dir, &self.game_state, &self.rng, self.next_command_id)
+        {
+            tracing::warn!("autosave failed: {err:#}");
+        }
+    }
+
+    /// Persist streamed events to the run's history, then add them to the
+    /// resume log.
+    pub fn log_events(&mut self, events: Vec<EventEnvelope>) -> crate::event_log::EventBatch {
+        if let Some(ref mut store) = self.event_store {
+            if let Err(err) = store.append(&events) {
+                tracing::warn!("event history write failed: {err:#}");
+            }
+        }
+        self.event_log.push(events)
+    }
+
     pub fn push_timings(&mut self, timings: TickTimings) {
         if self.timings_history.len() >= MAX_TIMINGS_HISTORY {
             self.timings_history.pop_front();
@@ -65,7 +121,8 @@ impl SimState {
 
 pub type SharedSim = Arc<Mutex<SimState>>;
 pub type CommandQueue = Arc<Mutex<Vec<CommandEnvelope>>>;
-pub type EventTx = broadcast::Sender<Vec<EventEnvelope>>;
+pub type EventTx = broadcast::Sender<crate::event_log::EventBatch>;
+pub type SharedJournal = Arc<Mutex<crate::journal::CommandJournal>>;
 
 #[derive(Clone)]
 pub struct AppState {
@@ -73,6 +130,11 @@ pub struct AppState {
     pub command_queue: CommandQueue,
     pub event_tx: EventTx,
     pub ticks_per_sec: Arc<AtomicU64>,
-    pub run_dir: Option<PathBuf>,
     pub paused: Arc<AtomicBool>,
+    /// Write-ahead log of external commands, in the run directory.
+    pub command_journal: Option<SharedJournal>,
+    /// Keys required on POST routes; `None` leaves them open.
+    pub api_keys: Option<Arc<crate::auth::ApiKeys>>,
+    /// Settings for runs launched through `/api/v1/runs`.
+    pub runs: Arc<crate::runs::RunLauncher>,
 }
@@ -1,6 +1,8 @@
+use crate::event_log::EventBatch;
 use crate::state::{CommandQueue, EventTx, SharedSim, SimState};
 use sim_control::CommandSource;
 use sim_core::TickTimings;
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -34,6 +36,11 @@ pub async fn run_tick_loop(
             perf_window_ticks = 0;
         }
 
+        // A scheduled pause that has come due stops the run before this tick.
+        if crate::pause::pause_if_due(&mut sim.lock(), &paused, &event_tx) {
+            continue;
+        }
+
         // --- Pacing ---
         let rate = f64::from_bits(ticks_per_sec.load(Ordering::Relaxed));
         if rate > 0.0 {
@@ -66,12 +73,23 @@ pub async fn run_tick_loop(
         }
 
         // --- Execute one tick ---
-        let (events, done) = execute_tick(&sim, &command_queue, max_ticks);
+        // Checked under the sim lock: `/pause` and `/resume` flip `paused`
+        // holding it, so a fast-forward and this loop never both tick.
+        let (batch, done) = {
+            let mut guard = sim.lock();
+            if paused.load(Ordering::Relaxed) {
+                continue;
+            }
+            // A fast-forward ran the run to its end and wrapped it up.
+            if reached(&guard, max_ticks) {
+                return;
+            }
+            execute_tick_locked(&mut guard, &command_queue, max_ticks)
+        };
 
         // Only broadcast events when SSE clients are connected.
-        // Avoids cloning the Vec into the broadcast channel when nobody's listening.
         if event_tx.receiver_count() > 0 {
-            let _ = event_tx.send(events);
+            let _ = event_tx.send(batch);
         }
 
         // --- Performance logging ---
@@ -92,14 +110,99 @@ pub async fn run_tick_loop(
             break;
         }
     }
+
+    finish_run(&sim);
+}
+
+fn reached(sim: &SimState, max_ticks: Option<u64>) -> bool {
+    max_ticks.is_some_and(|max| sim.game_state.meta.tick >= max)
+}
+
+/// Wrap up a run that reached `--max-ticks`: close the metrics files and
+/// write the report. Called once, by whichever of the tick loop or a
+/// fast-forward ran the last tick.
+fn finish_run(sim: &SharedSim) {
+    let metrics_writer = sim.lock().metrics_writer.take();
+    if let Some(Err(err)) = metrics_writer.map(sim_world::MetricsWriter::finish) {
+        tracing::warn!("finishing metrics files failed: {err:#}");
+    }
+    let report_dir = sim.lock().run_dir.clone();
+    if let Some(dir) = report_dir {
+        write_report(sim, &dir);
+    }
 }
 
-fn execute_tick(
+/// Write the end-of-run report. Failures are logged; the run is over either way.
+fn write_report(sim: &SharedSim, dir: &Path) {
+    let mut guard = sim.lock();
+    let reporter = std::mem::take(&mut guard.reporter);
+    let report = reporter.finish(&guard.game_state, &guard.content);
+    match report.write(dir) {
+        Ok(()) => tracing::info!("Run report written to {}", dir.join("report.md").display()),
+        Err(err) => tracing::warn!("writing run report failed: {err:#}"),
+    }
+}
+
+/// Why a fast-forward stopped short of its tick count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FastForwardStop {
+    ScheduledPause,
+    MaxTicks,
+    /// The run was resumed; the tick loop carries on from here.
+    Resumed,
+}
+
+/// Run up to `ticks` ticks back to back while the tick loop is paused,
+/// broadcasting each tick's events as the loop would. Stops early where a
+/// scheduled pause comes due, at `max_ticks` (ending the run as the loop
+/// would) or once the run is resumed. Returns the ticks run.
+pub fn fast_forward(
     sim: &SharedSim,
     command_queue: &CommandQueue,
+    event_tx: &EventTx,
+    paused: &AtomicBool,
+    ticks: u64,
     max_ticks: Option<u64>,
-) -> (Vec<sim_core::EventEnvelope>, bool) {
-    let mut guard = sim.lock();
+) -> (u64, Option<FastForwardStop>) {
+    for ran in 0..ticks {
+        let (batch, done) = {
+            let mut guard = sim.lock();
+            if !paused.load(Ordering::Relaxed) {
+                return (ran, Some(FastForwardStop::Resumed));
+            }
+            if crate::pause::pause_if_due(&mut guard, paused, event_tx) {
+                return (ran, Some(FastForwardStop::ScheduledPause));
+            }
+            if reached(&guard, max_ticks) {
+                return (ran, Some(FastForwardStop::MaxTicks));
+            }
+            execute_tick_locked(&mut guard, command_queue, max_ticks)
+        };
+        if event_tx.receiver_count() > 0 {
+            let _ = event_tx.send(batch);
+        }
+        if done {
+            finish_run(sim);
+            return (ran + 1, Some(FastForwardStop::MaxTicks));
+        }
+    }
+    (ticks, None)
+}
+
+pub fn execute_tick(
+    sim: &SharedSim,
+    command_queue: &CommandQueue,
+    max_ticks: Option<u64>,
+) -> (EventBatch, bool) {
+    execute_tick_locked(&mut sim.lock(), command_queue, max_ticks)
+}
+
+fn execute_tick_locked(
+    guard: &mut SimState,
+    command_queue: &CommandQueue,
+    max_ticks: Option<u64>,
+) -> (EventBatch, bool) {
     let SimState {
         ref game_state,
         ref content,
@@ -108,8 +211,9 @@ fn execute_tick(
         ref mut next_command_id,
         ..
     } = *guard;
-    let mut player_commands: Vec<sim_core::CommandEnvelope> =
-        command_queue.lock().drain(..).collect();
+    // Commands scheduled for a later tick are handed over now; the sim holds
+    // them in `pending_commands`, so they are saved with the state.
+    let mut player_commands = std::mem::take(&mut *command_queue.lock());
     let autopilot_commands = autopilot.generate_commands(game_state, content, next_command_id);
     player_commands.extend(autopilot_commands);
     let commands = player_commands;
@@ -133,6 +237,7 @@ fn execute_tick(
         let snapshot = sim_core::compute_metrics(&guard.game_state, &guard.content);
 
         if needs_metrics {
+            guard.reporter.observe_metrics(&snapshot);
             guard.push_metrics(snapshot.clone());
 
             let SimState {
@@ -170,8 +275,21 @@ fn execute_tick(
         }
     }
 
-    let done = max_ticks.is_some_and(|max| guard.game_state.meta.tick >= max);
-    (events, done)
+    let SimState {
+        ref game_state,
+        ref mut timeline,
+        ..
+    } = *guard;
+    timeline.record(&events, game_state);
+    guard.reporter.observe_events(&events);
+    guard.record_state_hash();
+    guard.autosave_if_due();
+    let event_level = guard.event_level;
+    events.retain(|envelope| envelope.event.level() >= event_level);
+    let batch = guard.log_events(events);
+
+    let done = reached(guard, max_ticks);
+    (batch, done)
 }
 
 #[cfg(test)]
@@ -182,7 +300,6 @@ mod tests {
     use rand::SeedableRng;
     use rand_chacha::ChaCha8Rng;
     use sim_core::test_fixtures::base_content;
-    use sim_core::EventEnvelope;
     use sim_world::build_initial_state;
     use std::collections::VecDeque;
     use tokio::sync::broadcast;
@@ -191,7 +308,7 @@ mod tests {
         let content = base_content();
         let mut rng = ChaCha8Rng::seed_from_u64(0);
         let game_state = build_initial_state(&content, 0, &mut rng);
-        let (event_tx, _) = broadcast::channel::<Vec<EventEnvelope>>(256);
+        let (event_tx, _) = broadcast::channel(256);
         let sim = Arc::new(Mutex::new(SimState {
             game_state,
             content,
@@ -205,6 +322,17 @@ mod tests {
             timings_history: VecDeque::new(),
             score_history: VecDeque::new(),
             last_threshold: String::new(),
+            timeline: crate::timeline::TimelineCompactor::new(0, 0, 0),
+            event_level: sim_core::EventLevel::Debug,
+            checkpoints: crate::checkpoint::CheckpointStore::default(),
+            reporter: sim_world::RunReporter::default(),
+            state_hashes: None,
+            event_log: crate::event_log::EventLog::default(),
+            pauses: crate::pause::PauseSchedule::default(),
+            event_store: None,
+            snapshot_delta: crate::snapshot_delta::DeltaTracker::default(),
+            run_dir: None,
+            autosave: None,
         }));
         let command_queue = Arc::new(Mutex::new(Vec::new()));
         let paused = Arc::new(AtomicBool::new(false));
@@ -284,6 +412,112 @@ mod tests {
         assert_eq!(sim.lock().game_state.meta.tick, 5);
     }
 
+    #[tokio::test]
+    async fn test_tick_loop_stops_at_scheduled_pause() {
+        let (sim, command_queue, event_tx, paused) = make_test_sim();
+        sim.lock().pauses.schedule(crate::pause::ScheduledPause {
+            at_tick: 3,
+            reason: Some("maintenance".to_string()),
+        });
+
+        let handle = tokio::spawn(run_tick_loop(
+            sim.clone(),
+            command_queue,
+            event_tx.clone(),
+            Arc::new(AtomicU64::new(0.0_f64.to_bits())),
+            Some(5),
+            paused.clone(),
+        ));
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(paused.load(Ordering::Relaxed));
+        {
+            let guard = sim.lock();
+            assert_eq!(guard.game_state.meta.tick, 3);
+            assert_eq!(guard.pauses.reason(), Some("maintenance"));
+            assert!(guard.pauses.pending().is_empty());
+        }
+
+        crate::pause::resume(&mut sim.lock(), &paused, &event_tx);
+        handle.await.unwrap();
+        assert_eq!(sim.lock().game_state.meta.tick, 5);
+    }
+
+    #[test]
+    fn test_fast_forward_stops_at_scheduled_pause() {
+        let (sim, command_queue, event_tx, paused) = make_test_sim();
+        paused.store(true, Ordering::Relaxed);
+        assert_eq!(
+            fast_forward(&sim, &command_queue, &event_tx, &paused, 4, None),
+            (4, None)
+        );
+        sim.lock().pauses.schedule(crate::pause::ScheduledPause {
+            at_tick: 6,
+            reason: Some("review".to_string()),
+        });
+        assert_eq!(
+            fast_forward(&sim, &command_queue, &event_tx, &paused, 10, None),
+            (2, Some(FastForwardStop::ScheduledPause))
+        );
+        assert_eq!(sim.lock().pauses.reason(), Some("review"));
+        assert_eq!(
+            fast_forward(&sim, &command_queue, &event_tx, &paused, 10, Some(9)),
+            (3, Some(FastForwardStop::MaxTicks))
+        );
+        assert_eq!(sim.lock().game_state.meta.tick, 9);
+        assert!(paused.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_fast_forward_stops_once_resumed() {
+        let (sim, command_queue, event_tx, paused) = make_test_sim();
+        assert_eq!(
+            fast_forward(&sim, &command_queue, &event_tx, &paused, 4, None),
+            (0, Some(FastForwardStop::Resumed))
+        );
+        assert_eq!(sim.lock().game_state.meta.tick, 0);
+    }
+
+    #[tokio::test]
+    async fn test_fast_forward_to_max_ticks_ends_the_run() {
+        let (sim, command_queue, event_tx, paused) = make_test_sim();
+        let dir = tempfile::tempdir().unwrap();
+        sim.lock().run_dir = Some(dir.path().to_path_buf());
+        paused.store(true, Ordering::Relaxed);
+        assert_eq!(
+            fast_forward(&sim, &command_queue, &event_tx, &paused, 10, Some(4)),
+            (4, Some(FastForwardStop::MaxTicks))
+        );
+        assert!(dir.path().join("report.json").exists());
+        assert!(dir.path().join("report.md").exists());
+
+        // Resumed, the loop finds the run already over and runs nothing.
+        paused.store(false, Ordering::Relaxed);
+        run_tick_loop(
+            sim.clone(),
+            command_queue,
+            event_tx,
+            Arc::new(AtomicU64::new(0.0_f64.to_bits())),
+            Some(4),
+            paused,
+        )
+        .await;
+        assert_eq!(sim.lock().game_state.meta.tick, 4);
+    }
+
+    #[test]
+    fn test_execute_tick_drops_events_below_level() {
+        let (sim, command_queue, _, _) = make_test_sim();
+        sim.lock().event_level = sim_core::EventLevel::Info;
+        for _ in 0..200 {
+            let (batch, _) = execute_tick(&sim, &command_queue, None);
+            assert!(batch
+                .events
+                .iter()
+                .all(|e| e.event.level() == sim_core::EventLevel::Info));
+        }
+    }
+
     #[tokio::test]
     async fn test_tick_loop_collects_metrics() {
         let (sim, command_queue, event_tx, paused) = make_test_sim();
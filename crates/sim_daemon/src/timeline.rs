@@ -0,0 +1,298 @@
+//! Compacted event timeline for scrubbing through long runs.
+//!
+//! The raw per-tick event stream is too large for a UI to replay. The
+//! compactor folds events into fixed-width tick segments that keep only
+//! per-kind counts and summed `_kg` quantities ("12 `RefineryRan`, 4,800 kg
+//! ore consumed"), and snapshots the full `GameState` every few segments as
+//! a keyframe to seek to. When the run has a directory, segments and
+//! keyframes are also appended to `timeline.jsonl` and `keyframes.jsonl`.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use serde::Serialize;
+use sim_core::{EventEnvelope, GameState};
+
+/// Ticks folded into one segment.
+pub const DEFAULT_SEGMENT_TICKS: u64 = 100;
+
+/// A keyframe is taken at the end of every Nth segment.
+pub const DEFAULT_KEYFRAME_EVERY: u64 = 10;
+
+/// Maximum number of segments kept in memory.
+pub(crate) const MAX_TIMELINE_SEGMENTS: usize = 10_000;
+
+/// Maximum number of keyframes kept in memory. Each is a full state.
+pub(crate) const MAX_TIMELINE_KEYFRAMES: usize = 50;
+
+/// Fields that report a level rather than a flow; summing them across
+/// events is meaningless.
+const LEVEL_FIELDS: &[&str] = &["asteroid_remaining_kg", "jettisoned_kg", "threshold_kg"];
+
+/// All events of one kind within a segment.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EventAggregate {
+    pub count: u64,
+    /// Sum of each numeric `*_kg` payload field across the events.
+    pub totals_kg: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineSegment {
+    /// First tick covered (inclusive).
+    pub start_tick: u64,
+    /// Last tick covered (exclusive).
+    pub end_tick: u64,
+    pub event_count: u64,
+    /// Keyed by `Event` variant name.
+    pub aggregates: BTreeMap<String, EventAggregate>,
+    /// True when a keyframe was taken at `end_tick`.
+    pub keyframe: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Keyframe {
+    pub tick: u64,
+    pub state: serde_json::Value,
+}
+
+struct TimelineFiles {
+    segments: BufWriter<File>,
+    keyframes: BufWriter<File>,
+}
+
+pub struct TimelineCompactor {
+    segment_ticks: u64,
+    keyframe_every: u64,
+    open: BTreeMap<String, EventAggregate>,
+    open_start: u64,
+    open_events: u64,
+    segments_closed: u64,
+    pub(crate) segments: VecDeque<TimelineSegment>,
+    pub(crate) keyframes: VecDeque<Keyframe>,
+    files: Option<TimelineFiles>,
+}
+
+impl TimelineCompactor {
+    /// `segment_ticks == 0` disables compaction entirely.
+    pub fn new(segment_ticks: u64, keyframe_every: u64, start_tick: u64) -> Self {
+        Self {
+            segment_ticks,
+            keyframe_every,
+            open: BTreeMap::new(),
+            open_start: start_tick,
+            open_events: 0,
+            segments_closed: 0,
+            segments: VecDeque::new(),
+            keyframes: VecDeque::new(),
+            files: None,
+        }
+    }
+
+    /// Compactor for a daemon run, writing to `run_dir` when there is one
+    /// and compaction is enabled.
+    pub fn for_run(
+        segment_ticks: u64,
+        keyframe_every: u64,
+        start_tick: u64,
+        run_dir: Option<&Path>,
+    ) -> std::io::Result<Self> {
+        let timeline = Self::new(segment_ticks, keyframe_every, start_tick);
+        match run_dir {
+            Some(dir) if segment_ticks > 0 => timeline.with_output_dir(dir),
+            _ => Ok(timeline),
+        }
+    }
+
+    /// Also append segments and keyframes to JSONL files in `dir`.
+    fn with_output_dir(mut self, dir: &Path) -> std::io::Result<Self> {
+        self.files = Some(TimelineFiles {
+            segments: BufWriter::new(File::create(dir.join("timeline.jsonl"))?),
+            keyframes: BufWriter::new(File::create(dir.join("keyframes.jsonl"))?),
+        });
+        Ok(self)
+    }
+
+    pub fn segment_ticks(&self) -> u64 {
+        self.segment_ticks
+    }
+
+    /// Fold one tick's events in; closes the segment when `state` has
+    /// reached its end.
+    pub fn record(&mut self, events: &[EventEnvelope], state: &GameState) {
+        if self.segment_ticks == 0 {
+            return;
+        }
+        for envelope in events {
+            self.add_event(envelope);
+        }
+        let tick = state.meta.tick;
+        if tick >= self.open_start + self.segment_ticks {
+            self.close_segment(tick, state);
+        }
+    }
+
+    fn add_event(&mut self, envelope: &EventEnvelope) {
+        self.open_events += 1;
+        let Ok(serde_json::Value::Object(map)) = serde_json::to_value(&envelope.event) else {
+            return;
+        };
+        let Some((kind, payload)) = map.into_iter().next() else {
+            return;
+        };
+        let aggregate = self.open.entry(kind).or_default();
+        aggregate.count += 1;
+        let serde_json::Value::Object(fields) = payload else {
+            return;
+        };
+        for (name, value) in fields {
+            if !name.ends_with("_kg") || LEVEL_FIELDS.contains(&name.as_str()) {
+                continue;
+            }
+            if let Some(kg) = value.as_f64() {
+                *aggregate.totals_kg.entry(name).or_insert(0.0) += kg;
+            }
+        }
+    }
+
+    fn close_segment(&mut self, end_tick: u64, state: &GameState) {
+        self.segments_closed += 1;
+        let keyframe =
+            self.keyframe_every > 0 && self.segments_closed.is_multiple_of(self.keyframe_every);
+        let segment = TimelineSegment {
+            start_tick: self.open_start,
+            end_tick,
+            event_count: std::mem::take(&mut self.open_events),
+            aggregates: std::mem::take(&mut self.open),
+            keyframe,
+        };
+        self.open_start = end_tick;
+        self.write_line(|files| &mut files.segments, &segment);
+        if self.segments.len() >= MAX_TIMELINE_SEGMENTS {
+            self.segments.pop_front();
+        }
+        self.segments.push_back(segment);
+
+        if keyframe {
+            match serde_json::to_value(state) {
+                Ok(state) => {
+                    let frame = Keyframe {
+                        tick: end_tick,
+                        state,
+                    };
+                    self.write_line(|files| &mut files.keyframes, &frame);
+                    if self.keyframes.len() >= MAX_TIMELINE_KEYFRAMES {
+                        self.keyframes.pop_front();
+                    }
+                    self.keyframes.push_back(frame);
+                }
+                Err(err) => tracing::warn!("timeline keyframe serialization failed: {err}"),
+            }
+        }
+    }
+
+    fn write_line<T: Serialize>(
+        &mut self,
+        pick: impl FnOnce(&mut TimelineFiles) -> &mut BufWriter<File>,
+        value: &T,
+    ) {
+        let Some(files) = self.files.as_mut() else {
+            return;
+        };
+        let writer = pick(files);
+        let result = serde_json::to_writer(&mut *writer, value)
+            .map_err(std::io::Error::from)
+            .and_then(|()| writer.write_all(b"\n"))
+            .and_then(|()| writer.flush());
+        if let Err(err) = result {
+            tracing::warn!("timeline write failed: {err}");
+        }
+    }
+
+    /// Forget segments and keyframes after `tick`, for a checkpoint restore.
+    /// The open segment restarts at `tick`. Files on disk are append-only
+    /// and keep the abandoned ticks.
+    pub fn rewind(&mut self, tick: u64) {
+        let before = self.segments.len();
+        self.segments.retain(|segment| segment.end_tick <= tick);
+        self.segments_closed = self
+            .segments_closed
+            .saturating_sub((before - self.segments.len()) as u64);
+        self.keyframes.retain(|frame| frame.tick <= tick);
+        self.open.clear();
+        self.open_events = 0;
+        self.open_start = tick;
+    }
+
+    /// Latest keyframe at or before `tick`.
+    pub fn keyframe_at(&self, tick: u64) -> Option<&Keyframe> {
+        self.keyframes.iter().rev().find(|frame| frame.tick <= tick)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim_core::test_fixtures::{base_content, base_state};
+    use sim_core::{Event, EventId, ModuleInstanceId, StationId};
+
+    fn refinery_ran(tick: u64, ore_kg: f32) -> EventEnvelope {
+        EventEnvelope {
+            id: EventId(tick),
+            tick,
+            event: Event::RefineryRan {
+                station_id: StationId("station_earth_orbit".to_string()),
+                module_id: ModuleInstanceId("module_refinery".to_string()),
+                ore_consumed_kg: ore_kg,
+                material_produced_kg: ore_kg / 2.0,
+                material_quality: 0.8,
+                slag_produced_kg: ore_kg / 2.0,
+                material_element: "Fe".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn segments_aggregate_events_and_take_keyframes() {
+        let content = base_content();
+        let mut state = base_state(&content);
+        let mut timeline = TimelineCompactor::new(2, 2, 0);
+
+        for tick in 0..4 {
+            state.meta.tick = tick + 1;
+            timeline.record(&[refinery_ran(tick, 100.0)], &state);
+        }
+
+        assert_eq!(timeline.segments.len(), 2);
+        let first = &timeline.segments[0];
+        assert_eq!((first.start_tick, first.end_tick), (0, 2));
+        assert_eq!(first.event_count, 2);
+        let refinery = &first.aggregates["RefineryRan"];
+        assert_eq!(refinery.count, 2);
+        assert!((refinery.totals_kg["ore_consumed_kg"] - 200.0).abs() < 1e-6);
+        assert!(!first.keyframe);
+        assert!(timeline.segments[1].keyframe);
+        assert_eq!(timeline.keyframe_at(10).map(|frame| frame.tick), Some(4));
+        assert!(timeline.keyframe_at(3).is_none());
+    }
+
+    #[test]
+    fn output_dir_receives_jsonl_lines() {
+        let content = base_content();
+        let mut state = base_state(&content);
+        let dir = tempfile::tempdir().unwrap();
+        let mut timeline = TimelineCompactor::new(1, 1, 0)
+            .with_output_dir(dir.path())
+            .unwrap();
+
+        state.meta.tick = 1;
+        timeline.record(&[refinery_ran(0, 50.0)], &state);
+
+        let segments = std::fs::read_to_string(dir.path().join("timeline.jsonl")).unwrap();
+        let keyframes = std::fs::read_to_string(dir.path().join("keyframes.jsonl")).unwrap();
+        assert_eq!(segments.lines().count(), 1);
+        assert_eq!(keyframes.lines().count(), 1);
+    }
+}
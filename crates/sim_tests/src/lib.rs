@@ -0,0 +1,196 @@
+//! `sim_tests` — end-to-end scenarios across the workspace crates.
+//!
+//! Unit tests cover each crate against `test_fixtures`; the suites in
+//! `tests/` instead run the real `content/` directory through world build,
+//! autopilot, the daemon API, save/load and command replay, so a change in
+//! one crate that breaks another (a new command that no longer serializes
+//! into snapshots, say) fails here. This file holds the shared harness.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use rand_chacha::ChaCha8Rng;
+use sim_control::{AutopilotController, CommandSource};
+use sim_core::{CommandEnvelope, EventEnvelope, GameContent, GameState};
+use sim_daemon::state::{AppState, SimState};
+use sim_daemon::timeline::{TimelineCompactor, DEFAULT_KEYFRAME_EVERY, DEFAULT_SEGMENT_TICKS};
+use sim_world::OrphanedModulePolicy;
+
+/// The workspace `content/` directory.
+pub fn content_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../content")
+}
+
+/// Load and validate the real game content.
+pub fn real_content() -> GameContent {
+    let dir = content_dir();
+    let content = sim_world::load_content(dir.to_str().expect("content path is UTF-8"))
+        .expect("content directory loads");
+    sim_world::validate_content(&content).expect("content validates");
+    content
+}
+
+/// A freshly generated world and its run RNG, built the same way
+/// `sim_cli run --seed` and `sim_daemon run --seed` do.
+pub fn new_world(content: &GameContent, seed: u64) -> (GameState, ChaCha8Rng) {
+    sim_world::load_or_build_state(content, Some(seed), None, OrphanedModulePolicy::default())
+        .expect("world builds")
+}
+
+/// An autopilot run that records every tick's commands for replay.
+pub struct RecordedRun {
+    pub state: GameState,
+    pub rng: ChaCha8Rng,
+    pub autopilot: AutopilotController,
+    pub next_command_id: u64,
+    /// Commands applied on each tick, in order.
+    pub command_log: Vec<Vec<CommandEnvelope>>,
+    pub events: Vec<EventEnvelope>,
+}
+
+impl RecordedRun {
+    pub fn new(content: &GameContent, seed: u64) -> Self {
+        let (state, rng) = new_world(content, seed);
+        Self {
+            state,
+            rng,
+            autopilot: AutopilotController::new(),
+            next_command_id: 0,
+            command_log: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Advance `ticks` ticks under the autopilot.
+    pub fn run(&mut self, content: &GameContent, ticks: u64) {
+        for _ in 0..ticks {
+            let commands =
+                self.autopilot
+                    .generate_commands(&self.state, content, &mut self.next_command_id);
+            let events = sim_core::tick(&mut self.state, &commands, content, &mut self.rng, None);
+            self.events.extend(events);
+            self.command_log.push(commands);
+        }
+    }
+}
+
+/// Re-run a recorded command log from a fresh world, without the autopilot.
+pub fn replay(content: &GameContent, seed: u64, command_log: &[Vec<CommandEnvelope>]) -> GameState {
+    let (mut state, mut rng) = new_world(content, seed);
+    for commands in command_log {
+        sim_core::tick(&mut state, commands, content, &mut rng, None);
+    }
+    state
+}
+
+/// Serialized form used to compare states. Runtime caches are skipped by
+/// serde already; station power readouts are recomputed every tick and not
+/// restored on load, so they are dropped too, and the unlocked-tech set is
+/// sorted since `HashSet` order differs between instances.
+pub fn state_json(state: &GameState) -> serde_json::Value {
+    let mut value = serde_json::to_value(state).expect("state serializes");
+    if let Some(unlocked) = value["research"]["unlocked"].as_array_mut() {
+        unlocked.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+    }
+    if let Some(stations) = value["stations"].as_object_mut() {
+        for station in stations.values_mut() {
+            if let Some(station) = station.as_object_mut() {
+                station.remove("power");
+            }
+        }
+    }
+    value
+}
+
+/// JSON paths at which two states differ (per [`state_json`]), for
+/// readable assertion failures on large states.
+pub fn state_diff(a: &GameState, b: &GameState) -> Vec<String> {
+    let mut out = Vec::new();
+    diff_values(&state_json(a), &state_json(b), "$", &mut out);
+    out
+}
+
+fn diff_values(
+    left: &serde_json::Value,
+    right: &serde_json::Value,
+    path: &str,
+    out: &mut Vec<String>,
+) {
+    use serde_json::Value;
+    match (left, right) {
+        (Value::Object(left_map), Value::Object(right_map)) => {
+            let right_only = right_map.keys().filter(|k| !left_map.contains_key(*k));
+            for key in left_map.keys().chain(right_only) {
+                let child = format!("{path}.{key}");
+                match (left_map.get(key), right_map.get(key)) {
+                    (Some(l), Some(r)) => diff_values(l, r, &child, out),
+                    _ => out.push(child),
+                }
+            }
+        }
+        (Value::Array(left_items), Value::Array(right_items))
+            if left_items.len() == right_items.len() =>
+        {
+            for (i, (l, r)) in left_items.iter().zip(right_items).enumerate() {
+                diff_values(l, r, &format!("{path}[{i}]"), out);
+            }
+        }
+        _ if left != right => out.push(format!("{path}: {left} != {right}")),
+        _ => {}
+    }
+}
+
+/// Daemon state over a fresh world, paused, with an optional run directory
+/// for saves.
+pub fn daemon_state(content: GameContent, seed: u64, run_dir: Option<PathBuf>) -> AppState {
+    let (game_state, rng) = new_world(&content, seed);
+    let (event_tx, _) = tokio::sync::broadcast::channel(64);
+    AppState {
+        sim: Arc::new(Mutex::new(SimState {
+            game_state,
+            content,
+            rng,
+            autopilot: AutopilotController::new(),
+            next_command_id: 0,
+            metrics_every: 60,
+            metrics_history: VecDeque::new(),
+            metrics_writer: None,
+            alert_engine: None,
+            timings_history: VecDeque::new(),
+            score_history: VecDeque::new(),
+            last_threshold: String::new(),
+            timeline: TimelineCompactor::new(DEFAULT_SEGMENT_TICKS, DEFAULT_KEYFRAME_EVERY, 0),
+            event_level: sim_core::EventLevel::Debug,
+            checkpoints: sim_daemon::checkpoint::CheckpointStore::default(),
+            reporter: sim_world::RunReporter::default(),
+            state_hashes: None,
+            event_log: sim_daemon::event_log::EventLog::default(),
+            pauses: sim_daemon::pause::PauseSchedule::default(),
+            event_store: None,
+            snapshot_delta: sim_daemon::snapshot_delta::DeltaTracker::default(),
+            run_dir,
+            autosave: None,
+        })),
+        command_queue: Arc::new(Mutex::new(Vec::new())),
+        event_tx,
+        ticks_per_sec: Arc::new(AtomicU64::new(0.0_f64.to_bits())),
+        paused: Arc::new(AtomicBool::new(true)),
+        command_journal: None,
+        api_keys: None,
+        runs: Arc::default(),
+    }
+}
+
+/// Step the daemon's simulation `ticks` times, as its tick loop would.
+pub fn step_daemon(app_state: &AppState, ticks: u64) -> Vec<EventEnvelope> {
+    let mut events = Vec::new();
+    for _ in 0..ticks {
+        let (batch, _) =
+            sim_daemon::tick_loop::execute_tick(&app_state.sim, &app_state.command_queue, None);
+        events.extend(batch.events);
+    }
+    events
+}
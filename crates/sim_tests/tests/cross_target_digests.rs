@@ -0,0 +1,60 @@
+//! Cross-target determinism harness for the `fixed-point` feature.
+//!
+//! Runs the real content under the autopilot and checks the state digest
+//! every `EVERY` ticks against `fixtures/fixed_point_digests.csv`, recorded
+//! on another machine. CI runs this on each target; the first mismatching
+//! row names the tick where this target diverged. After an intended change
+//! to sim behaviour or content, re-record with `BLESS_DIGESTS=1`.
+#![cfg(feature = "fixed-point")]
+
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use sim_tests::{real_content, RecordedRun};
+
+const SEED: u64 = 7;
+const TICKS: u64 = 1_000;
+const EVERY: u64 = 100;
+
+fn digests_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/fixed_point_digests.csv")
+}
+
+/// `tick,state_hash` rows, as in a run's `state_hashes.csv`.
+fn record_digests() -> String {
+    let content = real_content();
+    let mut run = RecordedRun::new(&content, SEED);
+    let mut csv = String::from("tick,state_hash\n");
+    while run.state.meta.tick < TICKS {
+        run.run(&content, EVERY);
+        writeln!(
+            csv,
+            "{},{:016x}",
+            run.state.meta.tick,
+            run.state.stable_hash()
+        )
+        .unwrap();
+    }
+    csv
+}
+
+#[test]
+fn digests_match_the_recorded_target() {
+    let actual = record_digests();
+    let path = digests_path();
+    if std::env::var_os("BLESS_DIGESTS").is_some() {
+        std::fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("reading {}: {err}", path.display()));
+    for (expected_row, actual_row) in expected.lines().zip(actual.lines()) {
+        assert_eq!(
+            expected_row,
+            actual_row,
+            "digest diverged from {}; rerun with BLESS_DIGESTS=1 if the change is intended",
+            path.display()
+        );
+    }
+    assert_eq!(expected.lines().count(), actual.lines().count());
+}
@@ -0,0 +1,81 @@
+//! World build → autopilot → save/load → replay, on the real content.
+
+use sim_core::GameState;
+use sim_tests::{real_content, replay, state_diff, RecordedRun};
+use sim_world::OrphanedModulePolicy;
+
+const SEED: u64 = 7;
+const TICKS: u64 = 500;
+
+#[test]
+fn autopilot_run_makes_progress_and_keeps_state_valid() {
+    let content = real_content();
+    let mut run = RecordedRun::new(&content, SEED);
+    run.run(&content, TICKS);
+
+    assert_eq!(run.state.current_tick(), TICKS);
+    assert!(
+        run.command_log.iter().any(|commands| !commands.is_empty()),
+        "autopilot issued no commands in {TICKS} ticks"
+    );
+    assert!(!run.events.is_empty());
+    sim_world::validate_state(&run.state, &content).unwrap();
+}
+
+#[test]
+fn saved_state_loads_back_identically() {
+    let content = real_content();
+    let mut run = RecordedRun::new(&content, SEED);
+    run.run(&content, TICKS);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("save.json");
+    std::fs::write(&path, serde_json::to_string_pretty(&run.state).unwrap()).unwrap();
+    let (mut loaded, mut rng) = sim_world::load_or_build_state(
+        &content,
+        None,
+        Some(path.to_str().unwrap()),
+        OrphanedModulePolicy::Error,
+    )
+    .unwrap();
+
+    let diff = state_diff(&loaded, &run.state);
+    assert!(
+        diff.is_empty(),
+        "loaded state differs: {:#?}",
+        &diff[..diff.len().min(20)]
+    );
+    // The loaded save must keep running under the autopilot.
+    let mut autopilot = sim_control::AutopilotController::new();
+    let mut next_command_id = run.next_command_id;
+    for _ in 0..50 {
+        let commands = sim_control::CommandSource::generate_commands(
+            &mut autopilot,
+            &loaded,
+            &content,
+            &mut next_command_id,
+        );
+        sim_core::tick(&mut loaded, &commands, &content, &mut rng, None);
+    }
+    assert_eq!(loaded.current_tick(), TICKS + 50);
+    sim_world::validate_state(&loaded, &content).unwrap();
+}
+
+#[test]
+fn recorded_commands_replay_to_the_same_state() {
+    let content = real_content();
+    let mut run = RecordedRun::new(&content, SEED);
+    run.run(&content, TICKS);
+
+    // Round-trip the log through JSON, as an external replay file would.
+    let log_json = serde_json::to_string(&run.command_log).unwrap();
+    let log: Vec<Vec<sim_core::CommandEnvelope>> = serde_json::from_str(&log_json).unwrap();
+    let replayed: GameState = replay(&content, SEED, &log);
+
+    let diff = state_diff(&replayed, &run.state);
+    assert!(
+        diff.is_empty(),
+        "replay diverged: {:#?}",
+        &diff[..diff.len().min(20)]
+    );
+}
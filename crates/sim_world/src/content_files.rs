@@ -0,0 +1,279 @@
+//! Content file lookup and parsing.
+//!
+//! Each content file is named by its JSON filename (`constants.json`), but
+//! may be authored as `.toml`, `.yaml` or `.yml` with the same stem; the
+//! format is picked from whichever one exists. Having more than one is an
+//! error. TOML cannot hold a top-level array, so list files written in TOML
+//! put their entries under a single `items` key (`[[items]]` tables).
+//!
+//! JSON stays the canonical format: the shipped `content/` directory,
+//! `content_hash` and everything the sim writes out are JSON regardless of
+//! how a file was authored.
+//!
+//! Content can be layered from several directories ("packs"), base first.
+//! Each file is merged across the packs that have it before it is typed:
+//! objects merge key by key, arrays of objects that all carry an `id`
+//! merge by id (a def with a known id is merged into it, a new id is
+//! appended), and anything else in a later pack replaces the earlier value.
+//! So a pack can override one field of one tech, or add a module def,
+//! without copying the rest of the file. Required files only need to exist
+//! in one pack.
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Accepted extensions, in the order they are reported.
+const EXTENSIONS: [&str; 4] = ["json", "toml", "yaml", "yml"];
+
+/// Find the file for `filename` (a `*.json` name) in `dir`, in any
+/// accepted format. `None` if no variant exists.
+fn find(dir: &Path, filename: &str) -> Result<Option<PathBuf>> {
+    let stem = filename.strip_suffix(".json").unwrap_or(filename);
+    let found: Vec<PathBuf> = EXTENSIONS
+        .iter()
+        .map(|extension| dir.join(format!("{stem}.{extension}")))
+        .filter(|path| path.is_file())
+        .collect();
+    match found.as_slice() {
+        [] => Ok(None),
+        [path] => Ok(Some(path.clone())),
+        _ => bail!(
+            "content file {stem} exists in several formats ({}); keep one",
+            found
+                .iter()
+                .filter_map(|path| path.file_name()?.to_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Wrapper for TOML list files; see the module docs.
+#[derive(Deserialize)]
+struct TomlItems<T> {
+    items: T,
+}
+
+fn parse<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let name = path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+    let text = std::fs::read_to_string(path).with_context(|| format!("reading {name}"))?;
+    let parsed = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => {
+            let table: toml::Table =
+                toml::from_str(&text).with_context(|| format!("parsing {name}"))?;
+            let is_list = table.len() == 1 && table.get("items").is_some_and(toml::Value::is_array);
+            if is_list {
+                toml::from_str::<TomlItems<T>>(&text).map(|file| file.items)
+            } else {
+                toml::from_str(&text)
+            }
+            .map_err(anyhow::Error::from)
+        }
+        Some("yaml" | "yml") => serde_yaml::from_str(&text).map_err(anyhow::Error::from),
+        _ => serde_json::from_str(&text).map_err(anyhow::Error::from),
+    };
+    parsed.with_context(|| format!("parsing {name}"))
+}
+
+/// Merge `filename` across `dirs`, or `None` if no pack has it.
+fn load_layered<T: DeserializeOwned>(dirs: &[&Path], filename: &str) -> Result<Option<T>> {
+    // A single pack is typed straight from its file, so errors point at it.
+    if let [dir] = dirs {
+        return find(dir, filename)?.map(|path| parse(&path)).transpose();
+    }
+    let mut merged: Option<Value> = None;
+    for dir in dirs {
+        let Some(path) = find(dir, filename)? else {
+            continue;
+        };
+        let layer: Value = parse(&path)?;
+        match &mut merged {
+            Some(base) => merge(base, layer),
+            None => merged = Some(layer),
+        }
+    }
+    merged
+        .map(|value| {
+            serde_json::from_value(value)
+                .with_context(|| format!("parsing {filename} merged from content packs"))
+        })
+        .transpose()
+}
+
+/// Merge `layer` into `base`; see the module docs.
+fn merge(base: &mut Value, layer: Value) {
+    match (base, layer) {
+        (Value::Object(base), Value::Object(layer)) => {
+            for (key, value) in layer {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (Value::Array(base), Value::Array(layer)) if all_have_ids(base) && all_have_ids(&layer) => {
+            for def in layer {
+                match base.iter_mut().find(|existing| existing["id"] == def["id"]) {
+                    Some(existing) => merge(existing, def),
+                    None => base.push(def),
+                }
+            }
+        }
+        (base, layer) => *base = layer,
+    }
+}
+
+fn all_have_ids(items: &[Value]) -> bool {
+    items
+        .iter()
+        .all(|item| item.get("id").is_some_and(Value::is_string))
+}
+
+/// Load a content file that must exist in at least one of `dirs`.
+pub(crate) fn load_required<T: DeserializeOwned>(dirs: &[&Path], filename: &str) -> Result<T> {
+    match load_layered(dirs, filename)? {
+        Some(value) => Ok(value),
+        None => bail!(
+            "reading {filename}: not found in {} (also tried .toml, .yaml, .yml)",
+            dirs.iter()
+                .map(|dir| dir.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Load a content file that may be absent from every pack.
+pub(crate) fn load_optional<T: DeserializeOwned>(
+    dirs: &[&Path],
+    filename: &str,
+) -> Result<Option<T>> {
+    load_layered(dirs, filename)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Def {
+        id: String,
+        mass_kg: f32,
+    }
+
+    #[test]
+    fn toml_list_files_use_items() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("defs.toml"),
+            "[[items]]\nid = \"a\"\nmass_kg = 1.5\n\n[[items]]\nid = \"b\"\nmass_kg = 2.0\n",
+        )
+        .unwrap();
+
+        let defs: Vec<Def> = load_required(&[dir.path()], "defs.json").unwrap();
+
+        assert_eq!(defs.len(), 2);
+        assert_eq!(defs[1].id, "b");
+    }
+
+    #[test]
+    fn toml_table_with_items_map_is_not_unwrapped() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("table.toml"), "[items]\nfe = 1.0\n").unwrap();
+
+        let table: BTreeMap<String, BTreeMap<String, f64>> =
+            load_required(&[dir.path()], "table.json").unwrap();
+
+        assert!((table["items"]["fe"] - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn yaml_file_is_found_by_json_name() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("defs.yml"), "- id: a\n  mass_kg: 3.0\n").unwrap();
+
+        let defs: Option<Vec<Def>> = load_optional(&[dir.path()], "defs.json").unwrap();
+
+        assert_eq!(
+            defs,
+            Some(vec![Def {
+                id: "a".to_string(),
+                mass_kg: 3.0,
+            }])
+        );
+    }
+
+    #[test]
+    fn same_file_in_two_formats_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("defs.json"), "[]").unwrap();
+        std::fs::write(dir.path().join("defs.yaml"), "[]").unwrap();
+
+        let err = load_optional::<Vec<Def>>(&[dir.path()], "defs.json").unwrap_err();
+
+        assert!(err.to_string().contains("defs.json, defs.yaml"), "{err}");
+    }
+
+    #[test]
+    fn missing_optional_file_is_none() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let defs: Option<Vec<Def>> = load_optional(&[dir.path()], "defs.json").unwrap();
+
+        assert!(defs.is_none());
+    }
+
+    #[test]
+    fn later_pack_merges_defs_by_id() {
+        let base = tempfile::tempdir().unwrap();
+        let pack = tempfile::tempdir().unwrap();
+        std::fs::write(
+            base.path().join("defs.json"),
+            r#"[{"id": "a", "mass_kg": 1.0}, {"id": "b", "mass_kg": 2.0}]"#,
+        )
+        .unwrap();
+        std::fs::write(
+            pack.path().join("defs.yaml"),
+            "- id: b\n  mass_kg: 20.0\n- id: c\n  mass_kg: 3.0\n",
+        )
+        .unwrap();
+
+        let defs: Vec<Def> = load_required(&[base.path(), pack.path()], "defs.json").unwrap();
+
+        let masses: Vec<(&str, f32)> = defs.iter().map(|d| (d.id.as_str(), d.mass_kg)).collect();
+        assert_eq!(masses, [("a", 1.0), ("b", 20.0), ("c", 3.0)]);
+    }
+
+    #[test]
+    fn later_pack_overrides_single_object_fields() {
+        let base = tempfile::tempdir().unwrap();
+        let pack = tempfile::tempdir().unwrap();
+        std::fs::write(
+            base.path().join("table.json"),
+            r#"{"items": {"fe": {"price": 1.0, "weight": 2.0}}, "tags": [1, 2]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            pack.path().join("table.json"),
+            r#"{"items": {"fe": {"price": 5.0}}, "tags": [3]}"#,
+        )
+        .unwrap();
+
+        let table: serde_json::Value =
+            load_required(&[base.path(), pack.path()], "table.json").unwrap();
+
+        assert_eq!(
+            table,
+            serde_json::json!({"items": {"fe": {"price": 5.0, "weight": 2.0}}, "tags": [3]})
+        );
+    }
+}
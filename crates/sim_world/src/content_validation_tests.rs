@@ -10,7 +10,7 @@
 use crate::load_content;
 use sim_core::{
     ComponentId, GameContent, GameState, InputFilter, InventoryItem, ModuleBehaviorDef, OutputSpec,
-    RecipeId, TechEffect, TechId,
+    RecipeDef, RecipeId, TechEffect, TechId,
 };
 use std::collections::HashSet;
 use std::sync::OnceLock;
@@ -152,7 +152,7 @@ fn module_power_consumption_is_non_negative() {
 fn storage_capacity_is_positive() {
     let content = load_test_content();
     for module_def in content.module_defs.values() {
-        if let ModuleBehaviorDef::Storage { capacity_m3 } = &module_def.behavior {
+        if let ModuleBehaviorDef::Storage { capacity_m3, .. } = &module_def.behavior {
             assert!(
                 *capacity_m3 > 0.0,
                 "module '{}' storage has non-positive capacity_m3: {}",
@@ -264,9 +264,9 @@ fn constants_probabilities_are_valid() {
     let content = load_test_content();
     let c = &content.constants;
     assert!(
-        (0.0..=1.0).contains(&c.survey_tag_detection_probability),
-        "survey_tag_detection_probability {} out of range [0, 1]",
-        c.survey_tag_detection_probability
+        (0.0..=1.0).contains(&c.base_sensor_quality),
+        "base_sensor_quality {} out of range [0, 1]",
+        c.base_sensor_quality
     );
 }
 
@@ -598,6 +598,75 @@ fn at_least_one_asteroid_template_exists() {
     );
 }
 
+#[test]
+fn every_zone_body_has_a_belt() {
+    // With belts.json present the global top-up is off, so a zone body
+    // without a belt would never see another scan site.
+    let content = load_test_content();
+    for body in content
+        .solar_system
+        .bodies
+        .iter()
+        .filter(|b| b.zone.is_some())
+    {
+        assert!(
+            content.belts.iter().any(|belt| belt.body == body.id),
+            "zone body '{}' has no belt",
+            body.id.0
+        );
+    }
+}
+
+#[test]
+fn every_template_volatile_besides_helium_feeds_a_propellant_chain() {
+    // Ice mined from volatile templates must be extractable from ore and
+    // crackable into LH2, or it is dead weight in the hold.
+    let content = load_test_content();
+    let processor_recipes: Vec<&RecipeDef> = content
+        .module_defs
+        .values()
+        .filter_map(|def| match &def.behavior {
+            ModuleBehaviorDef::Processor(processor) => Some(&processor.recipes),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|id| content.recipes.get(id))
+        .collect();
+    let outputs = |recipe: &RecipeDef, element: &str| {
+        recipe.outputs.iter().any(
+            |output| matches!(output, OutputSpec::Material { element: out, .. } if out == element),
+        )
+    };
+
+    for template in &content.asteroid_templates {
+        for element_id in template.composition_ranges.keys() {
+            let volatile = content
+                .elements
+                .iter()
+                .any(|e| e.id == *element_id && e.volatile);
+            if !volatile || element_id == "He" {
+                continue;
+            }
+            assert!(
+                processor_recipes
+                    .iter()
+                    .any(|recipe| outputs(recipe, element_id)),
+                "template '{}' volatile '{element_id}' has no extraction recipe",
+                template.id
+            );
+            assert!(
+                processor_recipes.iter().any(|recipe| {
+                    recipe.inputs.iter().any(
+                        |input| matches!(&input.filter, InputFilter::Element(e) if e == element_id),
+                    ) && outputs(recipe, "LH2")
+                }),
+                "template '{}' volatile '{element_id}' is never turned into LH2",
+                template.id
+            );
+        }
+    }
+}
+
 #[test]
 fn at_least_one_mineable_ore_element_exists() {
     // "ore" is the hardcoded element ID that the mining system produces when ships mine asteroids.
@@ -0,0 +1,212 @@
+//! Experiment notebook metadata for runs and benchmark batches.
+//!
+//! A hypothesis, free-form notes and the git commit of the content
+//! directory can be attached to a run (`sim_cli run`, `sim_daemon run`) or a
+//! `sim_bench` batch. They are written into `run_info.json` /
+//! `batch_summary.json` under `"experiment"` and read back by
+//! [`list_runs`] for `sim_cli runs list`.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Notebook fields attached to a run or batch. All optional.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExperimentMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hypothesis: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Git commit that last touched the content directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_commit: Option<String>,
+}
+
+impl ExperimentMeta {
+    pub fn is_empty(&self) -> bool {
+        self.hypothesis.is_none() && self.notes.is_none() && self.content_commit.is_none()
+    }
+
+    /// Fill `content_commit` from git when it was not given explicitly.
+    #[must_use]
+    pub fn with_detected_commit(mut self, content_dir: &str) -> Self {
+        if self.content_commit.is_none() {
+            self.content_commit = detect_content_commit(content_dir);
+        }
+        self
+    }
+
+    /// Fields set here win over `base`; unset fields fall back to it. Used
+    /// to let CLI flags override a scenario's `experiment` block.
+    #[must_use]
+    pub fn or(self, base: Self) -> Self {
+        Self {
+            hypothesis: self.hypothesis.or(base.hypothesis),
+            notes: self.notes.or(base.notes),
+            content_commit: self.content_commit.or(base.content_commit),
+        }
+    }
+}
+
+/// The last git commit touching `content_dir`, or `None` outside a git
+/// checkout. Appends `-dirty` when the directory has uncommitted changes.
+pub fn detect_content_commit(content_dir: &str) -> Option<String> {
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .arg("-C")
+            .arg(content_dir)
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let commit = git(&["log", "-1", "--format=%h", "--", "."]).filter(|c| !c.is_empty())?;
+    let dirty = git(&["status", "--porcelain", "--", "."]).is_some_and(|s| !s.is_empty());
+    Some(if dirty {
+        format!("{commit}-dirty")
+    } else {
+        commit
+    })
+}
+
+/// What a run directory holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunKind {
+    /// A single `sim_cli` / `sim_daemon` run (`run_info.json`).
+    Run,
+    /// A `sim_bench` batch (`batch_summary.json`).
+    Batch,
+}
+
+/// One entry of [`list_runs`].
+#[derive(Debug, Clone)]
+pub struct RunListing {
+    pub dir: PathBuf,
+    pub kind: RunKind,
+    /// `run_id` for runs, `scenario_name` for batches.
+    pub name: String,
+    /// Seed for runs; seed count for batches.
+    pub seeds: u64,
+    pub experiment: ExperimentMeta,
+}
+
+/// List run and batch directories directly under `root`, sorted by
+/// directory name (run ids and batch names start with a timestamp).
+/// Directories with neither `batch_summary.json` nor `run_info.json` are
+/// skipped.
+pub fn list_runs(root: &Path) -> Result<Vec<RunListing>> {
+    let mut listings = Vec::new();
+    let entries = std::fs::read_dir(root).with_context(|| format!("reading {}", root.display()))?;
+    for entry in entries {
+        let dir = entry?.path();
+        if !dir.is_dir() {
+            continue;
+        }
+        if let Some(listing) = read_listing(&dir)? {
+            listings.push(listing);
+        }
+    }
+    listings.sort_by(|a, b| a.dir.cmp(&b.dir));
+    Ok(listings)
+}
+
+fn read_listing(dir: &Path) -> Result<Option<RunListing>> {
+    let (kind, path) = if dir.join("batch_summary.json").exists() {
+        (RunKind::Batch, dir.join("batch_summary.json"))
+    } else if dir.join("run_info.json").exists() {
+        (RunKind::Run, dir.join("run_info.json"))
+    } else {
+        return Ok(None);
+    };
+    let json =
+        std::fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+    let value: serde_json::Value =
+        serde_json::from_str(&json).with_context(|| format!("parsing {}", path.display()))?;
+    let (name_key, seeds_key) = match kind {
+        RunKind::Run => ("run_id", "seed"),
+        RunKind::Batch => ("scenario_name", "seed_count"),
+    };
+    let experiment = value
+        .get("experiment")
+        .cloned()
+        .map(serde_json::from_value)
+        .transpose()
+        .with_context(|| format!("parsing experiment in {}", path.display()))?
+        .unwrap_or_default();
+    Ok(Some(RunListing {
+        dir: dir.to_path_buf(),
+        kind,
+        name: value[name_key].as_str().unwrap_or_default().to_string(),
+        seeds: value[seeds_key].as_u64().unwrap_or_default(),
+        experiment,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_meta_serializes_to_empty_object() {
+        let meta = ExperimentMeta::default();
+        assert!(meta.is_empty());
+        assert_eq!(serde_json::to_string(&meta).unwrap(), "{}");
+    }
+
+    #[test]
+    fn flags_override_scenario_fields() {
+        let flags = ExperimentMeta {
+            notes: Some("rerun".to_string()),
+            ..ExperimentMeta::default()
+        };
+        let scenario = ExperimentMeta {
+            hypothesis: Some("more labs".to_string()),
+            notes: Some("first try".to_string()),
+            content_commit: None,
+        };
+        let merged = flags.or(scenario);
+        assert_eq!(merged.hypothesis.as_deref(), Some("more labs"));
+        assert_eq!(merged.notes.as_deref(), Some("rerun"));
+    }
+
+    #[test]
+    fn list_runs_reads_runs_and_batches() {
+        let root = tempfile::tempdir().unwrap();
+        let run_dir = root.path().join("20260101_000000_seed7");
+        std::fs::create_dir(&run_dir).unwrap();
+        let experiment = ExperimentMeta {
+            hypothesis: Some("cheaper refining".to_string()),
+            ..ExperimentMeta::default()
+        };
+        crate::write_run_info(
+            &run_dir,
+            "20260101_000000_seed7",
+            7,
+            "v1",
+            60,
+            serde_json::Value::Null,
+            &experiment,
+        )
+        .unwrap();
+        let batch_dir = root.path().join("smoke_20260102_000000");
+        std::fs::create_dir(&batch_dir).unwrap();
+        std::fs::write(
+            batch_dir.join("batch_summary.json"),
+            r#"{"scenario_name": "smoke", "seed_count": 5}"#,
+        )
+        .unwrap();
+        std::fs::create_dir(root.path().join("scratch")).unwrap();
+
+        let listings = list_runs(root.path()).unwrap();
+
+        assert_eq!(listings.len(), 2);
+        assert_eq!(listings[0].kind, RunKind::Run);
+        assert_eq!(listings[0].seeds, 7);
+        assert_eq!(listings[0].experiment, experiment);
+        assert_eq!(listings[1].kind, RunKind::Batch);
+        assert_eq!(listings[1].name, "smoke");
+        assert_eq!(listings[1].seeds, 5);
+        assert!(listings[1].experiment.is_empty());
+    }
+}
@@ -7,18 +7,36 @@ use rand_chacha::ChaCha8Rng;
 use serde::Deserialize;
 use sim_core::{
     AHashMap, AlertRuleDef, AsteroidTemplateDef, ComponentId, Constants, Counters, ElementDef,
-    GameContent, GameState, InputFilter, InventoryItem, MetaState, MetricsFileWriter,
-    ModuleBehaviorDef, ModuleDef, ModuleItemId, OutputSpec, PowerState, PricingTable, PrincipalId,
-    QualityFormula, ResearchState, ScanSite, ShipId, ShipState, SiteId, SolarSystemDef, StationId,
-    StationState, TechDef, TechId, YieldFormula,
+    GameContent, GameState, InputFilter, InventoryItem, MetaState, ModuleBehaviorDef, ModuleDef,
+    ModuleItemId, OutputSpec, PowerState, PricingTable, PrincipalId, QualityFormula, ResearchState,
+    ScanSite, ShipId, ShipState, SiteId, SolarSystemDef, StationId, StationState, TechDef, TechId,
+    YieldFormula,
 };
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+mod content_files;
+mod experiment;
+mod lints;
+mod metrics_output;
+mod parquet_writer;
+pub use experiment::{detect_content_commit, list_runs, ExperimentMeta, RunKind, RunListing};
+pub use lints::lint_content;
+pub use metrics_output::{MetricsFormat, MetricsWriter, METRICS_PARQUET_FILE};
+pub use parquet_writer::ParquetMetricsWriter;
+pub mod report;
+pub use report::{RunReport, RunReporter};
+mod state_hashes;
+pub use state_hashes::{StateHashWriter, STATE_HASHES_FILE};
+mod validation;
+pub use validation::ValidationReport;
+
 #[derive(Deserialize)]
 struct TechsFile {
     content_version: String,
     techs: Vec<TechDef>,
+    #[serde(default)]
+    starting_unlocked: Vec<TechId>,
 }
 
 #[derive(Deserialize)]
@@ -31,71 +49,335 @@ struct ElementsFile {
     elements: Vec<ElementDef>,
 }
 
-/// Validates cross-references in loaded content, panicking on any authoring error.
+/// Validates cross-references in loaded content, collecting every authoring
+/// error and warning into a [`ValidationReport`].
 ///
 /// Catches mistakes like: referencing an unknown element in a recipe, a tech
-/// prereq that doesn't exist, or a solar-system edge pointing at an unknown node.
-pub fn validate_content(content: &GameContent) {
-    validate_constants(content);
+/// prereq that doesn't exist or forms a cycle, or a solar-system edge
+/// pointing at an unknown node. Returns `Err` if any error was found,
+/// otherwise the warnings.
+pub fn validate_content(content: &GameContent) -> Result<Vec<String>, ValidationReport> {
+    content_report(content).into_result()
+}
+
+/// Every error and warning [`validate_content`] checks for, as a report.
+pub fn content_report(content: &GameContent) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    validate_constants(content, &mut report);
     let element_ids: HashSet<&str> = content.elements.iter().map(|e| e.id.as_str()).collect();
-    validate_elements(&element_ids);
-    validate_techs(content, &element_ids);
-    validate_solar_system(content);
-    validate_orbital_bodies(content);
-    validate_asteroid_templates(content, &element_ids);
-    validate_module_recipes(content, &element_ids);
-    validate_hull_defs(content);
-    validate_autopilot(content, &element_ids);
-    validate_crew_roles(content);
+    validate_elements(&element_ids, &mut report);
+    validate_techs(content, &element_ids, &mut report);
+    validate_solar_system(content, &mut report);
+    validate_orbital_bodies(content, &mut report);
+    validate_belts(content, &mut report);
+    validate_asteroid_templates(content, &element_ids, &mut report);
+    validate_module_recipes(content, &element_ids, &mut report);
+    validate_habitats(content, &element_ids, &mut report);
+    validate_drone_bays(content, &mut report);
+    validate_hull_defs(content, &mut report);
+    validate_autopilot(content, &element_ids, &mut report);
+    validate_crew_roles(content, &mut report);
     let satellite_types: HashSet<&str> = content
         .satellite_defs
         .values()
         .map(|s| s.satellite_type.as_str())
         .collect();
-    validate_satellite_type_refs(content, &satellite_types);
-    validate_scoring(content);
+    validate_satellite_type_refs(content, &satellite_types, &mut report);
+    validate_scoring(content, &mut report);
     let module_behavior_types: HashSet<&'static str> = content
         .module_defs
         .values()
         .map(|m| m.behavior.type_name())
         .collect();
-    validate_milestones(&content.milestones, &module_behavior_types);
-    validate_milestone_satellite_refs(&content.milestones, &satellite_types);
+    validate_milestones(&content.milestones, &module_behavior_types, &mut report);
+    validate_milestone_satellite_refs(&content.milestones, &satellite_types, &mut report);
+    validate_station_layouts(content, &mut report);
+    validate_project_defs(content, &element_ids, &mut report);
+    validate_module_upgrades(content, &element_ids, &mut report);
+    for error in sim_core::sim_events::event_def_errors(&content.events) {
+        report.error(error);
+    }
+    report
 }
 
-fn validate_constants(content: &GameContent) {
-    assert!(
-        content.constants.minutes_per_tick > 0,
-        "minutes_per_tick must be > 0"
-    );
+/// Projects must take time, draw known elements, and gate on known techs.
+fn validate_project_defs(
+    content: &GameContent,
+    element_ids: &HashSet<&str>,
+    report: &mut ValidationReport,
+) {
+    for project in content.project_defs.values() {
+        report.check(project.duration_ticks > 0, || {
+            format!(
+                "project '{}' has duration_ticks 0 (must be >= 1)",
+                project.id
+            )
+        });
+        for (element, kg) in &project.materials {
+            report.check(element_ids.contains(element.as_str()), || {
+                format!(
+                    "project '{}' material '{element}' is not a known element",
+                    project.id
+                )
+            });
+            report.check(*kg > 0.0, || {
+                format!(
+                    "project '{}' material '{element}' has non-positive kg {kg}",
+                    project.id
+                )
+            });
+        }
+        if let Some(ref tech) = project.required_tech {
+            report.check(content.techs.iter().any(|t| t.id == *tech), || {
+                format!(
+                    "project '{}' requires unknown tech '{}'",
+                    project.id, tech.0
+                )
+            });
+        }
+    }
 }
 
-fn validate_elements(element_ids: &HashSet<&str>) {
-    assert!(
-        element_ids.contains("ore"),
-        "required element 'ore' is missing from content.elements"
-    );
-    assert!(
-        element_ids.contains("slag"),
-        "required element 'slag' is missing from content.elements"
-    );
+/// Upgrade chains must lead to known module defs without looping, and
+/// draw known elements and components.
+fn validate_module_upgrades(
+    content: &GameContent,
+    element_ids: &HashSet<&str>,
+    report: &mut ValidationReport,
+) {
+    for module in content.module_defs.values() {
+        let Some(upgrade) = &module.upgrade_to else {
+            continue;
+        };
+        report.check(
+            content.module_defs.contains_key(&upgrade.module_def_id),
+            || {
+                format!(
+                    "module '{}' upgrades to unknown module '{}'",
+                    module.id, upgrade.module_def_id
+                )
+            },
+        );
+        for (element, kg) in &upgrade.materials {
+            report.check(element_ids.contains(element.as_str()), || {
+                format!(
+                    "module '{}' upgrade material '{element}' is not a known element",
+                    module.id
+                )
+            });
+            report.check(*kg > 0.0, || {
+                format!(
+                    "module '{}' upgrade material '{element}' has non-positive kg {kg}",
+                    module.id
+                )
+            });
+        }
+        for component_id in upgrade.components.keys() {
+            report.check(
+                content
+                    .component_defs
+                    .iter()
+                    .any(|component| component.id == component_id.0),
+                || {
+                    format!(
+                        "module '{}' upgrade component '{component_id}' is not a known component",
+                        module.id
+                    )
+                },
+            );
+        }
+        // Walk the chain; a def seen twice means it loops.
+        let mut seen = HashSet::from([module.id.as_str()]);
+        let mut next = Some(upgrade.module_def_id.as_str());
+        while let Some(def_id) = next {
+            if !seen.insert(def_id) {
+                report.error(format!("module '{}' upgrade chain loops", module.id));
+                break;
+            }
+            next = content
+                .module_defs
+                .get(def_id)
+                .and_then(|def| def.upgrade_to.as_ref())
+                .map(|upgrade| upgrade.module_def_id.as_str());
+        }
+    }
+}
+
+/// Frame slot adjacency must point at real slots, and adjacency rules must
+/// name known behavior types.
+fn validate_station_layouts(content: &GameContent, report: &mut ValidationReport) {
+    for frame in content.frames.values() {
+        for (idx, slot) in frame.slots.iter().enumerate() {
+            for &neighbor in &slot.adjacent {
+                report.check(neighbor < frame.slots.len() && neighbor != idx, || {
+                    format!(
+                        "frame '{}' slot '{}' lists invalid adjacent slot index {neighbor}",
+                        frame.id, slot.label
+                    )
+                });
+            }
+        }
+    }
+    for rule in &content.adjacency_rules {
+        for type_name in [&rule.module_type, &rule.neighbor_type] {
+            report.check(
+                sim_core::ModuleBehaviorDef::TYPE_NAMES.contains(&type_name.as_str()),
+                || {
+                    format!(
+                        "adjacency rule {} -> {} references unknown behavior type '{type_name}'",
+                        rule.module_type, rule.neighbor_type
+                    )
+                },
+            );
+        }
+    }
+}
+
+fn validate_constants(content: &GameContent, report: &mut ValidationReport) {
+    let c = &content.constants;
+    report.check(c.minutes_per_tick > 0, || {
+        "minutes_per_tick must be > 0".to_string()
+    });
+    for (name, value) in [
+        ("starting_loan", c.starting_loan),
+        ("loan_interest_rate_per_day", c.loan_interest_rate_per_day),
+        ("loan_repayment_amount", c.loan_repayment_amount),
+    ] {
+        report.check(value.is_finite() && value >= 0.0, || {
+            format!("{name} must be finite and >= 0, got {value}")
+        });
+    }
+    report.check(c.loan_default_after_missed > 0, || {
+        "loan_default_after_missed must be > 0".to_string()
+    });
+}
+
+fn validate_elements(element_ids: &HashSet<&str>, report: &mut ValidationReport) {
+    report.check(element_ids.contains("ore"), || {
+        "required element 'ore' is missing from content.elements".to_string()
+    });
+    report.check(element_ids.contains("slag"), || {
+        "required element 'slag' is missing from content.elements".to_string()
+    });
+}
+
+/// Starting techs must exist and carry their whole prereq chain, or the
+/// world would start with techs the research graph could never reach.
+pub fn check_starting_unlocked(content: &GameContent) -> Result<()> {
+    let starting: HashSet<&TechId> = content.starting_unlocked.iter().collect();
+    for tech_id in &content.starting_unlocked {
+        let Some(tech) = content.techs.iter().find(|t| t.id == *tech_id) else {
+            anyhow::bail!(
+                "starting_unlocked tech '{}' is not a known tech id",
+                tech_id.0
+            );
+        };
+        if let Some(prereq) = tech.prereqs.iter().find(|p| !starting.contains(p)) {
+            anyhow::bail!(
+                "starting_unlocked tech '{}' requires '{}', which is not also starting_unlocked",
+                tech_id.0,
+                prereq.0,
+            );
+        }
+    }
+    Ok(())
 }
 
-fn validate_techs(content: &GameContent, _element_ids: &HashSet<&str>) {
+/// Techs that can never unlock, in id order, each with the prereq chain
+/// that blocks it: from the tech, through the first prereq at each step that
+/// can never unlock either, to a tech already on the chain (a cycle) or an unknown id
+/// (marked `(unknown)`).
+fn blocked_tech_chains(content: &GameContent) -> Vec<Vec<String>> {
+    let techs: std::collections::BTreeMap<&str, &TechDef> =
+        content.techs.iter().map(|t| (t.id.0.as_str(), t)).collect();
+
+    // Unlockable from the empty set: every prereq is itself unlockable.
+    let mut unlockable: HashSet<&str> = HashSet::new();
+    loop {
+        let before = unlockable.len();
+        for (id, tech) in &techs {
+            if tech
+                .prereqs
+                .iter()
+                .all(|prereq| unlockable.contains(prereq.0.as_str()))
+            {
+                unlockable.insert(id);
+            }
+        }
+        if unlockable.len() == before {
+            break;
+        }
+    }
+
+    let mut chains = Vec::new();
+    for (&id, &tech) in &techs {
+        if unlockable.contains(id) {
+            continue;
+        }
+        let mut chain = vec![id.to_string()];
+        let mut current = tech;
+        while let Some(prereq) = current
+            .prereqs
+            .iter()
+            .map(|prereq| prereq.0.as_str())
+            .find(|prereq| !unlockable.contains(prereq))
+        {
+            let Some(&next) = techs.get(prereq) else {
+                chain.push(format!("{prereq} (unknown)"));
+                break;
+            };
+            let seen = chain.iter().any(|step| step == prereq);
+            chain.push(prereq.to_string());
+            if seen {
+                break;
+            }
+            current = next;
+        }
+        chains.push(chain);
+    }
+    chains
+}
+
+/// Report techs that can never unlock. A cycle is reported once, from its
+/// lowest id; a tech blocked only by its own unknown prereq is already
+/// reported as such.
+fn validate_tech_reachability(content: &GameContent, report: &mut ValidationReport) {
+    for chain in blocked_tech_chains(content) {
+        let tech = &chain[0];
+        let last = &chain[chain.len() - 1];
+        if chain.len() == 2 && last.ends_with("(unknown)") {
+            continue;
+        }
+        if last == tech {
+            if chain.iter().all(|step| step >= tech) {
+                report.error(format!("tech prereq cycle: {}", chain.join(" -> ")));
+            }
+            continue;
+        }
+        report.error(format!(
+            "tech '{tech}' can never unlock: {}",
+            chain.join(" -> ")
+        ));
+    }
+}
+
+fn validate_techs(
+    content: &GameContent,
+    _element_ids: &HashSet<&str>,
+    report: &mut ValidationReport,
+) {
     let tech_ids: HashSet<&TechId> = content.techs.iter().map(|t| &t.id).collect();
     for tech in &content.techs {
-        assert!(
-            tech.tier > 0,
-            "tech '{}' has tier 0 (must be >= 1)",
-            tech.id.0
-        );
+        report.check(tech.tier > 0, || {
+            format!("tech '{}' has tier 0 (must be >= 1)", tech.id.0)
+        });
         for prereq in &tech.prereqs {
-            assert!(
-                tech_ids.contains(prereq),
-                "tech '{}' prereq '{}' is not a known tech id",
-                tech.id.0,
-                prereq.0,
-            );
+            report.check(tech_ids.contains(prereq), || {
+                format!(
+                    "tech '{}' prereq '{}' is not a known tech id",
+                    tech.id.0, prereq.0
+                )
+            });
         }
         for effect in &tech.effects {
             if let sim_core::TechEffect::StatModifier {
@@ -104,70 +386,71 @@ fn validate_techs(content: &GameContent, _element_ids: &HashSet<&str>) {
                 value,
             } = effect
             {
-                assert!(
-                    value.abs() < 100.0,
-                    "tech '{}' has StatModifier with unreasonable value {} (expected -100..100)",
-                    tech.id.0,
-                    value,
-                );
+                report.check(value.abs() < 100.0, || {
+                    format!(
+                        "tech '{}' has StatModifier with unreasonable value {} (expected -100..100)",
+                        tech.id.0,
+                        value,
+                    )
+                });
+            } else if let sim_core::TechEffect::WaiveRoyalty { item_id } = effect {
+                report.check(content.pricing.items.contains_key(item_id), || {
+                    format!(
+                        "tech '{}' waives royalty for '{item_id}', which has no pricing entry",
+                        tech.id.0
+                    )
+                });
             }
         }
     }
 
+    if let Err(err) = check_starting_unlocked(content) {
+        report.error(err.to_string());
+    }
+    validate_tech_reachability(content, report);
+
     // Cross-reference: required_tech on rockets, satellites, modules, recipes, hulls
     for rocket in content.rocket_defs.values() {
         if let Some(ref tech) = rocket.required_tech {
-            assert!(
-                tech_ids.contains(tech),
-                "rocket '{}' requires unknown tech '{}'",
-                rocket.id,
-                tech.0,
-            );
+            report.check(tech_ids.contains(tech), || {
+                format!("rocket '{}' requires unknown tech '{}'", rocket.id, tech.0)
+            });
         }
     }
     for sat in content.satellite_defs.values() {
         if let Some(ref tech) = sat.required_tech {
-            assert!(
-                tech_ids.contains(tech),
-                "satellite '{}' requires unknown tech '{}'",
-                sat.id,
-                tech.0,
-            );
+            report.check(tech_ids.contains(tech), || {
+                format!("satellite '{}' requires unknown tech '{}'", sat.id, tech.0)
+            });
         }
     }
     for module in content.module_defs.values() {
         if let Some(ref tech) = module.required_tech {
-            assert!(
-                tech_ids.contains(tech),
-                "module '{}' requires unknown tech '{}'",
-                module.id,
-                tech.0,
-            );
+            report.check(tech_ids.contains(tech), || {
+                format!("module '{}' requires unknown tech '{}'", module.id, tech.0)
+            });
         }
     }
     for recipe in content.recipes.values() {
         if let Some(ref tech) = recipe.required_tech {
-            assert!(
-                tech_ids.contains(tech),
-                "recipe '{}' requires unknown tech '{}'",
-                recipe.id.0,
-                tech.0,
-            );
+            report.check(tech_ids.contains(tech), || {
+                format!(
+                    "recipe '{}' requires unknown tech '{}'",
+                    recipe.id.0, tech.0
+                )
+            });
         }
     }
     for hull in content.hulls.values() {
         if let Some(ref tech) = hull.required_tech {
-            assert!(
-                tech_ids.contains(tech),
-                "hull '{}' requires unknown tech '{}'",
-                hull.id.0,
-                tech.0,
-            );
+            report.check(tech_ids.contains(tech), || {
+                format!("hull '{}' requires unknown tech '{}'", hull.id.0, tech.0)
+            });
         }
     }
 }
 
-fn validate_solar_system(content: &GameContent) {
+fn validate_solar_system(content: &GameContent, report: &mut ValidationReport) {
     let node_ids: HashSet<&str> = content
         .solar_system
         .nodes
@@ -175,67 +458,136 @@ fn validate_solar_system(content: &GameContent) {
         .map(|n| n.id.0.as_str())
         .collect();
     for (from, to) in &content.solar_system.edges {
-        assert!(
-            node_ids.contains(from.0.as_str()),
-            "solar system edge references unknown node '{}'",
-            from.0,
-        );
-        assert!(
-            node_ids.contains(to.0.as_str()),
-            "solar system edge references unknown node '{}'",
-            to.0,
+        report.check(node_ids.contains(from.0.as_str()), || {
+            format!("solar system edge references unknown node '{}'", from.0)
+        });
+        report.check(node_ids.contains(to.0.as_str()), || {
+            format!("solar system edge references unknown node '{}'", to.0)
+        });
+    }
+    // Edges are travelled both ways; every node should connect to the first.
+    let Some(start) = content.solar_system.nodes.first() else {
+        return;
+    };
+    let mut reached: HashSet<&str> = HashSet::from([start.id.0.as_str()]);
+    let mut frontier = vec![start.id.0.as_str()];
+    while let Some(node) = frontier.pop() {
+        for (from, to) in &content.solar_system.edges {
+            let next = if from.0 == node {
+                to.0.as_str()
+            } else if to.0 == node {
+                from.0.as_str()
+            } else {
+                continue;
+            };
+            if reached.insert(next) {
+                frontier.push(next);
+            }
+        }
+    }
+    for node in &content.solar_system.nodes {
+        if !reached.contains(node.id.0.as_str()) {
+            report.warning(format!(
+                "solar system node '{}' is unreachable from '{}'",
+                node.id.0, start.id.0
+            ));
+        }
+    }
+}
+
+/// Belts must sit on distinct zone bodies, draw from known templates, and
+/// start within their own cap.
+fn validate_belts(content: &GameContent, report: &mut ValidationReport) {
+    let mut seen = HashSet::new();
+    for belt in &content.belts {
+        let body = &belt.body.0;
+        report.check(seen.insert(body.as_str()), || {
+            format!("duplicate belt for body '{body}'")
+        });
+        report.check(
+            content
+                .solar_system
+                .bodies
+                .iter()
+                .any(|b| b.id == belt.body && b.zone.is_some()),
+            || format!("belt references body '{body}' which is not a zone body"),
         );
+        report.check(belt.initial_budget <= belt.max_budget, || {
+            format!(
+                "belt '{body}' initial_budget {} exceeds max_budget {}",
+                belt.initial_budget, belt.max_budget
+            )
+        });
+        for template in &belt.templates {
+            report.check(
+                content.asteroid_templates.iter().any(|t| t.id == *template),
+                || format!("belt '{body}' references unknown template '{template}'"),
+            );
+        }
     }
 }
 
-fn validate_orbital_bodies(content: &GameContent) {
+fn validate_orbital_bodies(content: &GameContent, report: &mut ValidationReport) {
     let body_ids: HashSet<&str> = content
         .solar_system
         .bodies
         .iter()
         .map(|b| b.id.0.as_str())
         .collect();
-    assert!(
-        body_ids.len() == content.solar_system.bodies.len(),
-        "duplicate body id in orbital body tree"
-    );
+    report.check(body_ids.len() == content.solar_system.bodies.len(), || {
+        "duplicate body id in orbital body tree".to_string()
+    });
     for body in &content.solar_system.bodies {
         if let Some(ref parent) = body.parent {
-            assert!(
-                body_ids.contains(parent.0.as_str()),
-                "orbital body '{}' references unknown parent '{}'",
-                body.id.0,
-                parent.0,
-            );
+            report.check(body_ids.contains(parent.0.as_str()), || {
+                format!(
+                    "orbital body '{}' references unknown parent '{}'",
+                    body.id.0, parent.0
+                )
+            });
         }
         if let Some(ref zone) = body.zone {
-            assert!(
-                zone.radius_max_au_um > zone.radius_min_au_um,
-                "orbital body '{}' zone has radius_max <= radius_min",
-                body.id.0,
-            );
-            assert!(
+            report.check(zone.radius_max_au_um > zone.radius_min_au_um, || {
+                format!(
+                    "orbital body '{}' zone has radius_max <= radius_min",
+                    body.id.0
+                )
+            });
+            report.check(
                 zone.angle_span_mdeg > 0 && zone.angle_span_mdeg <= sim_core::FULL_CIRCLE,
-                "orbital body '{}' zone has invalid angle_span (must be 1..=360000)",
-                body.id.0,
-            );
-            assert!(
-                zone.scan_site_weight > 0,
-                "orbital body '{}' zone has scan_site_weight of 0",
-                body.id.0,
+                || {
+                    format!(
+                        "orbital body '{}' zone has invalid angle_span (must be 1..=360000)",
+                        body.id.0
+                    )
+                },
             );
+            report.check(zone.scan_site_weight > 0, || {
+                format!(
+                    "orbital body '{}' zone has scan_site_weight of 0",
+                    body.id.0
+                )
+            });
         }
+        report.check((0.0..=1.0).contains(&body.security), || {
+            format!(
+                "orbital body '{}' security {} must be in 0.0..=1.0",
+                body.id.0, body.security
+            )
+        });
     }
     // Verify no cycles: every body's ancestor chain must terminate at a root.
     for body in &content.solar_system.bodies {
         let mut visited = HashSet::new();
         let mut current_id = body.parent.as_ref();
         while let Some(pid) = current_id {
-            assert!(
-                visited.insert(pid.0.as_str()),
-                "cycle detected in orbital body tree at '{}'",
-                pid.0,
-            );
+            if !visited.insert(pid.0.as_str()) {
+                report.error(format!(
+                    "cycle detected in orbital body tree at '{}'",
+                    pid.0
+                ));
+                break;
+            }
             current_id = content
                 .solar_system
                 .bodies
@@ -246,47 +598,67 @@ fn validate_orbital_bodies(content: &GameContent) {
     }
 }
 
-fn validate_asteroid_templates(content: &GameContent, element_ids: &HashSet<&str>) {
+fn validate_asteroid_templates(
+    content: &GameContent,
+    element_ids: &HashSet<&str>,
+    report: &mut ValidationReport,
+) {
     for template in &content.asteroid_templates {
         for element_id in template.composition_ranges.keys() {
-            assert!(
-                element_ids.contains(element_id.as_str()),
-                "asteroid template '{}' composition key '{}' is not a known element",
-                template.id,
-                element_id,
-            );
+            report.check(element_ids.contains(element_id.as_str()), || {
+                format!(
+                    "asteroid template '{}' composition key '{}' is not a known element",
+                    template.id, element_id
+                )
+            });
+            // Whatever a template yields must be sellable.
+            report.check(content.pricing.items.contains_key(element_id), || {
+                format!(
+                    "asteroid template '{}' element '{}' has no pricing entry",
+                    template.id, element_id
+                )
+            });
         }
     }
 }
 
-fn validate_module_recipes(content: &GameContent, element_ids: &HashSet<&str>) {
+fn validate_module_recipes(
+    content: &GameContent,
+    element_ids: &HashSet<&str>,
+    report: &mut ValidationReport,
+) {
     for module_def in content.module_defs.values() {
         if let ModuleBehaviorDef::Processor(processor) = &module_def.behavior {
             for recipe_id in &processor.recipes {
-                let recipe = content.recipes.get(recipe_id).unwrap_or_else(|| {
-                    panic!(
+                let Some(recipe) = content.recipes.get(recipe_id) else {
+                    report.error(format!(
                         "module '{}' references unknown recipe '{}'",
                         module_def.id, recipe_id
-                    );
-                });
-                validate_recipe_elements(content, element_ids, &module_def.id, recipe);
+                    ));
+                    continue;
+                };
+                validate_recipe_elements(content, element_ids, &module_def.id, recipe, report);
             }
         }
         if let ModuleBehaviorDef::Assembler(assembler) = &module_def.behavior {
             for recipe_id in &assembler.recipes {
-                let recipe = content.recipes.get(recipe_id).unwrap_or_else(|| {
-                    panic!(
+                let Some(recipe) = content.recipes.get(recipe_id) else {
+                    report.error(format!(
                         "module '{}' references unknown recipe '{}'",
                         module_def.id, recipe_id
-                    );
-                });
+                    ));
+                    continue;
+                };
                 for input in &recipe.inputs {
                     if let InputFilter::Element(element_id) = &input.filter {
-                        assert!(
-                            element_ids.contains(element_id.as_str()),
-                            "module '{}' assembler recipe '{}' input element '{}' is not a known element",
-                            module_def.id, recipe_id, element_id,
-                        );
+                        report.check(element_ids.contains(element_id.as_str()), || {
+                            format!(
+                                "module '{}' assembler recipe '{}' input element '{}' is not a known element",
+                                module_def.id,
+                                recipe_id,
+                                element_id,
+                            )
+                        });
                     }
                 }
             }
@@ -294,21 +666,73 @@ fn validate_module_recipes(content: &GameContent, element_ids: &HashSet<&str>) {
     }
 }
 
+/// Habitats must house someone and draw known elements at non-negative
+/// rates.
+fn validate_habitats(
+    content: &GameContent,
+    element_ids: &HashSet<&str>,
+    report: &mut ValidationReport,
+) {
+    for module_def in content.module_defs.values() {
+        let ModuleBehaviorDef::Habitat(habitat) = &module_def.behavior else {
+            continue;
+        };
+        report.check(habitat.crew_capacity > 0, || {
+            format!("habitat '{}' has zero crew_capacity", module_def.id)
+        });
+        for (element_id, kg) in &habitat.consumables_kg_per_crew_hour {
+            report.check(element_ids.contains(element_id.as_str()), || {
+                format!(
+                    "habitat '{}' consumable '{}' is not a known element",
+                    module_def.id, element_id
+                )
+            });
+            report.check(*kg >= 0.0, || {
+                format!(
+                    "habitat '{}' consumable '{}' has negative rate {kg}",
+                    module_def.id, element_id
+                )
+            });
+        }
+    }
+}
+
+/// Drone bays must run on an interval and cut a positive amount of ore.
+fn validate_drone_bays(content: &GameContent, report: &mut ValidationReport) {
+    for module_def in content.module_defs.values() {
+        let ModuleBehaviorDef::DroneBay(bay) = &module_def.behavior else {
+            continue;
+        };
+        report.check(bay.mining_interval_minutes > 0, || {
+            format!(
+                "drone bay '{}' has zero mining_interval_minutes",
+                module_def.id
+            )
+        });
+        report.check(bay.kg_per_run > 0.0, || {
+            format!(
+                "drone bay '{}' has non-positive kg_per_run {}",
+                module_def.id, bay.kg_per_run
+            )
+        });
+    }
+}
+
 fn validate_recipe_elements(
     content: &GameContent,
     element_ids: &HashSet<&str>,
     module_id: &str,
     recipe: &sim_core::RecipeDef,
+    report: &mut ValidationReport,
 ) {
     for input in &recipe.inputs {
         if let InputFilter::Element(element_id) = &input.filter {
-            assert!(
-                element_ids.contains(element_id.as_str()),
-                "module '{}' recipe '{}' input element '{}' is not a known element",
-                module_id,
-                recipe.id,
-                element_id,
-            );
+            report.check(element_ids.contains(element_id.as_str()), || {
+                format!(
+                    "module '{}' recipe '{}' input element '{}' is not a known element",
+                    module_id, recipe.id, element_id
+                )
+            });
         }
     }
     for output in &recipe.outputs {
@@ -318,49 +742,114 @@ fn validate_recipe_elements(
                 yield_formula,
                 quality_formula,
             } => {
-                assert!(
-                    element_ids.contains(element.as_str()),
-                    "module '{}' recipe '{}' output element '{}' is not a known element",
-                    module_id,
-                    recipe.id,
-                    element,
-                );
+                report.check(element_ids.contains(element.as_str()), || {
+                    format!(
+                        "module '{}' recipe '{}' output element '{}' is not a known element",
+                        module_id, recipe.id, element
+                    )
+                });
                 if let YieldFormula::ElementFraction { element: fe } = yield_formula {
-                    assert!(
-                        element_ids.contains(fe.as_str()),
-                        "module '{}' recipe '{}' YieldFormula element '{}' is not a known element",
-                        module_id,
-                        recipe.id,
-                        fe,
-                    );
+                    report.check(element_ids.contains(fe.as_str()), || {
+                        format!(
+                            "module '{}' recipe '{}' YieldFormula element '{}' is not a known element",
+                            module_id,
+                            recipe.id,
+                            fe,
+                        )
+                    });
                 }
                 if let QualityFormula::ElementFractionTimesMultiplier { element: fe, .. } =
                     quality_formula
                 {
-                    assert!(
-                        element_ids.contains(fe.as_str()),
-                        "module '{}' recipe '{}' QualityFormula element '{}' is not a known element",
-                        module_id,
-                        recipe.id,
-                        fe,
-                    );
+                    report.check(element_ids.contains(fe.as_str()), || {
+                        format!(
+                            "module '{}' recipe '{}' QualityFormula element '{}' is not a known element",
+                            module_id,
+                            recipe.id,
+                            fe,
+                        )
+                    });
                 }
             }
             OutputSpec::Slag { .. } | OutputSpec::Component { .. } => {}
-            OutputSpec::Ship { hull_id } => {
-                assert!(
-                    content.hulls.contains_key(hull_id),
-                    "module '{}' recipe '{}' OutputSpec::Ship references unknown hull_id '{}'",
-                    module_id,
-                    recipe.id,
-                    hull_id.0,
-                );
+            OutputSpec::Ship {
+                hull_id, class_id, ..
+            } => {
+                report.check(content.hulls.contains_key(hull_id), || {
+                    format!(
+                        "module '{}' recipe '{}' OutputSpec::Ship references unknown hull_id '{}'",
+                        module_id, recipe.id, hull_id.0
+                    )
+                });
+                if let Some(class_id) = class_id {
+                    report.check(content.ship_classes.contains_key(class_id), || {
+                        format!(
+                            "module '{}' recipe '{}' OutputSpec::Ship references unknown class_id '{}'",
+                            module_id, recipe.id, class_id
+                        )
+                    });
+                }
             }
         }
     }
 }
 
-fn validate_hull_defs(content: &GameContent) {
+/// Class overrides must be non-negative and the speed multiplier positive,
+/// or ships of the class could never arrive anywhere.
+fn validate_ship_class(class: &sim_core::ShipClassDef, report: &mut ValidationReport) {
+    report.check(
+        class.speed_multiplier.is_finite() && class.speed_multiplier > 0.0,
+        || {
+            format!(
+                "ship class '{}' speed_multiplier must be positive, got {}",
+                class.id, class.speed_multiplier
+            )
+        },
+    );
+    report.check(
+        class.mining_rate_multiplier.is_finite() && class.mining_rate_multiplier >= 0.0,
+        || {
+            format!(
+                "ship class '{}' mining_rate_multiplier must be non-negative, got {}",
+                class.id, class.mining_rate_multiplier
+            )
+        },
+    );
+    for (field, value) in [
+        ("cargo_capacity_m3", class.cargo_capacity_m3),
+        ("propellant_capacity_kg", class.propellant_capacity_kg),
+    ] {
+        if let Some(value) = value {
+            report.check(value.is_finite() && value >= 0.0, || {
+                format!(
+                    "ship class '{}' {field} must be non-negative, got {value}",
+                    class.id
+                )
+            });
+        }
+    }
+}
+
+/// Partition volumes must be non-negative and fit in the hull's hold.
+fn validate_hold_partitions(hull: &sim_core::HullDef, report: &mut ValidationReport) {
+    for (partition, capacity_m3) in &hull.hold_partitions {
+        report.check(capacity_m3.is_finite() && *capacity_m3 >= 0.0, || {
+            format!(
+                "hull '{}' hold partition {partition:?} has invalid capacity {capacity_m3}",
+                hull.id
+            )
+        });
+    }
+    let reserved_m3: f32 = hull.hold_partitions.values().sum();
+    report.check(reserved_m3 <= hull.cargo_capacity_m3, || {
+        format!(
+            "hull '{}' reserves {reserved_m3} m3 of hold partitions but holds only {} m3",
+            hull.id, hull.cargo_capacity_m3
+        )
+    });
+}
+
+fn validate_hull_defs(content: &GameContent, report: &mut ValidationReport) {
     // Collect all slot types defined across hulls and frames. Modules are
     // fitted to either a ship hull or a station frame, so both sources
     // legitimately satisfy `compatible_slots`.
@@ -381,14 +870,21 @@ fn validate_hull_defs(content: &GameContent) {
     for module_def in content.module_defs.values() {
         for slot_type in &module_def.compatible_slots {
             if !known_slot_types.contains(slot_type) {
-                eprintln!(
-                    "WARNING: module '{}' has compatible_slot '{}' not found in any hull or frame",
+                report.warning(format!(
+                    "module '{}' has compatible_slot '{}' not found in any hull or frame",
                     module_def.id, slot_type
-                );
+                ));
             }
         }
     }
 
+    for hull in content.hulls.values() {
+        validate_hold_partitions(hull, report);
+    }
+    for class in content.ship_classes.values() {
+        validate_ship_class(class, report);
+    }
+
     // Warn about hull slot types with no compatible modules
     for hull in content.hulls.values() {
         for slot in &hull.slots {
@@ -397,10 +893,10 @@ fn validate_hull_defs(content: &GameContent) {
                 .values()
                 .any(|m| m.compatible_slots.contains(&slot.slot_type));
             if !has_compatible {
-                eprintln!(
-                    "WARNING: hull '{}' slot '{}' (type '{}') has no compatible modules",
+                report.warning(format!(
+                    "hull '{}' slot '{}' (type '{}') has no compatible modules",
                     hull.id, slot.label, slot.slot_type
-                );
+                ));
             }
         }
     }
@@ -413,55 +909,61 @@ fn validate_hull_defs(content: &GameContent) {
                 .values()
                 .any(|m| m.compatible_slots.contains(&slot.slot_type));
             if !has_compatible {
-                eprintln!(
-                    "WARNING: frame '{}' slot '{}' (type '{}') has no compatible modules",
+                report.warning(format!(
+                    "frame '{}' slot '{}' (type '{}') has no compatible modules",
                     frame.id, slot.label, slot.slot_type
-                );
+                ));
             }
         }
     }
 
     // Validate fitting templates reference valid hulls, modules, and compatible slots
     for (hull_id, fittings) in &content.fitting_templates {
-        let hull = content.hulls.get(hull_id);
-        assert!(
-            hull.is_some(),
-            "fitting_templates references unknown hull '{hull_id}'"
-        );
-        let hull = hull.unwrap();
+        let Some(hull) = content.hulls.get(hull_id) else {
+            report.error(format!(
+                "fitting_templates references unknown hull '{hull_id}'"
+            ));
+            continue;
+        };
         for fitting in fittings {
-            assert!(
-                fitting.slot_index < hull.slots.len(),
-                "fitting_templates hull '{}' slot_index {} out of range (hull has {} slots)",
-                hull_id,
-                fitting.slot_index,
-                hull.slots.len()
-            );
-            let module_def = content.module_defs.get(&fitting.module_def_id.0);
-            assert!(
-                module_def.is_some(),
-                "fitting_templates hull '{}' references unknown module '{}'",
-                hull_id,
-                fitting.module_def_id
-            );
-            let module_def = module_def.unwrap();
+            if fitting.slot_index >= hull.slots.len() {
+                report.error(format!(
+                    "fitting_templates hull '{}' slot_index {} out of range (hull has {} slots)",
+                    hull_id,
+                    fitting.slot_index,
+                    hull.slots.len()
+                ));
+                continue;
+            }
+            let Some(module_def) = content.module_defs.get(&fitting.module_def_id.0) else {
+                report.error(format!(
+                    "fitting_templates hull '{}' references unknown module '{}'",
+                    hull_id, fitting.module_def_id
+                ));
+                continue;
+            };
             let slot_type = &hull.slots[fitting.slot_index].slot_type;
-            assert!(
-                module_def.compatible_slots.contains(slot_type),
-                "fitting_templates hull '{}' slot {} (type '{}') incompatible with module '{}' (compatible: {:?})",
-                hull_id,
-                fitting.slot_index,
-                slot_type,
-                fitting.module_def_id,
-                module_def.compatible_slots
-            );
+            report.check(module_def.compatible_slots.contains(slot_type), || {
+                format!(
+                    "fitting_templates hull '{}' slot {} (type '{}') incompatible with module '{}' (compatible: {:?})",
+                    hull_id,
+                    fitting.slot_index,
+                    slot_type,
+                    fitting.module_def_id,
+                    module_def.compatible_slots,
+                )
+            });
         }
     }
 }
 
 /// Validate autopilot config cross-references against content.
 /// Only checks non-empty fields — empty means "not configured" (test fixtures).
-fn validate_autopilot(content: &GameContent, element_ids: &HashSet<&str>) {
+fn validate_autopilot(
+    content: &GameContent,
+    element_ids: &HashSet<&str>,
+    report: &mut ValidationReport,
+) {
     let ap = &content.autopilot;
     let tech_ids: HashSet<&str> = content.techs.iter().map(|t| t.id.0.as_str()).collect();
     let comp_ids: HashSet<&str> = content
@@ -481,220 +983,391 @@ fn validate_autopilot(content: &GameContent, element_ids: &HashSet<&str>) {
                 .module_defs
                 .values()
                 .any(|def| def.roles.iter().any(|r| r == role_name));
-            assert!(
-                has_module,
-                "autopilot.{field_name} '{role_name}' has no matching modules in module_defs"
-            );
+            report.check(has_module, || {
+                format!(
+                    "autopilot.{field_name} '{role_name}' has no matching modules in module_defs"
+                )
+            });
         }
     }
-    if !ap.volatile_element.is_empty() {
-        assert!(
-            element_ids.contains(ap.volatile_element.as_str()),
-            "autopilot.volatile_element '{}' not in elements",
-            ap.volatile_element
-        );
-    }
-    if !ap.propellant_element.is_empty() {
-        assert!(
-            element_ids.contains(ap.propellant_element.as_str()),
-            "autopilot.propellant_element '{}' not in elements",
-            ap.propellant_element
-        );
-    }
-    if !ap.primary_mining_element.is_empty() {
-        assert!(
-            element_ids.contains(ap.primary_mining_element.as_str()),
-            "autopilot.primary_mining_element '{}' not in elements",
-            ap.primary_mining_element
-        );
-    }
-    if !ap.deep_scan_tech.is_empty() {
-        assert!(
-            tech_ids.contains(ap.deep_scan_tech.as_str()),
-            "autopilot.deep_scan_tech '{}' not in techs",
-            ap.deep_scan_tech
-        );
-    }
-    if !ap.ship_construction_tech.is_empty() {
-        assert!(
-            tech_ids.contains(ap.ship_construction_tech.as_str()),
-            "autopilot.ship_construction_tech '{}' not in techs",
-            ap.ship_construction_tech
-        );
-    }
-    if !ap.shipyard_import_component.is_empty() {
-        assert!(
-            comp_ids.contains(ap.shipyard_import_component.as_str()),
-            "autopilot.shipyard_import_component '{}' not in component_defs",
-            ap.shipyard_import_component
-        );
-    }
-    if !ap.export_component.component_id.is_empty() {
-        assert!(
-            comp_ids.contains(ap.export_component.component_id.as_str()),
-            "autopilot.export_component.component_id '{}' not in component_defs",
-            ap.export_component.component_id
-        );
+    // Configured ids must name something in their catalog.
+    for (field_name, id, known, catalog) in [
+        (
+            "volatile_element",
+            &ap.volatile_element,
+            element_ids,
+            "elements",
+        ),
+        (
+            "propellant_element",
+            &ap.propellant_element,
+            element_ids,
+            "elements",
+        ),
+        (
+            "primary_mining_element",
+            &ap.primary_mining_element,
+            element_ids,
+            "elements",
+        ),
+        ("deep_scan_tech", &ap.deep_scan_tech, &tech_ids, "techs"),
+        (
+            "ship_construction_tech",
+            &ap.ship_construction_tech,
+            &tech_ids,
+            "techs",
+        ),
+        (
+            "shipyard_import_component",
+            &ap.shipyard_import_component,
+            &comp_ids,
+            "component_defs",
+        ),
+        (
+            "export_component.component_id",
+            &ap.export_component.component_id,
+            &comp_ids,
+            "component_defs",
+        ),
+    ] {
+        if !id.is_empty() {
+            report.check(known.contains(id.as_str()), || {
+                format!("autopilot.{field_name} '{id}' not in {catalog}")
+            });
+        }
     }
     for entry in &ap.export_elements {
-        assert!(
-            element_ids.contains(entry.element.as_str()),
-            "autopilot.export_elements element '{}' not in elements",
-            entry.element
-        );
+        report.check(element_ids.contains(entry.element.as_str()), || {
+            format!(
+                "autopilot.export_elements element '{}' not in elements",
+                entry.element
+            )
+        });
     }
     let valid_tasks: HashSet<&str> = ["Deposit", "Mine", "DeepScan", "Survey"]
         .into_iter()
         .collect();
     for task in &ap.task_priority {
-        assert!(
-            valid_tasks.contains(task.as_str()),
-            "autopilot.task_priority contains unknown task type '{task}'. \
+        report.check(valid_tasks.contains(task.as_str()), || {
+            format!(
+                "autopilot.task_priority contains unknown task type '{task}'. \
              Valid values: Deposit, Mine, DeepScan, Survey"
-        );
+            )
+        });
     }
 }
 
-fn validate_crew_roles(content: &GameContent) {
+fn validate_crew_roles(content: &GameContent, report: &mut ValidationReport) {
     for (role_id, def) in &content.crew_roles {
         if def.recruitment_cost <= 0.0 {
-            eprintln!(
-                "warning: crew role '{}' has non-positive recruitment_cost ({})",
+            report.warning(format!(
+                "crew role '{}' has non-positive recruitment_cost ({})",
                 role_id, def.recruitment_cost
-            );
+            ));
         }
     }
     // Validate module crew_requirement references valid roles
     for (module_id, module_def) in &content.module_defs {
         for role in module_def.crew_requirement.keys() {
-            assert!(
-                content.crew_roles.contains_key(role),
-                "module '{module_id}' crew_requirement references unknown crew role '{role}'"
-            );
+            report.check(content.crew_roles.contains_key(role), || {
+                format!(
+                    "module '{module_id}' crew_requirement references unknown crew role '{role}'"
+                )
+            });
         }
     }
 }
 
-fn validate_scoring(content: &GameContent) {
+fn validate_scoring(content: &GameContent, report: &mut ValidationReport) {
     // Skip validation for test fixtures that use ScoringConfig::default() (empty dimensions).
     // Real content from scoring.json will have dimensions populated.
     if content.scoring.dimensions.is_empty() && content.scoring.thresholds.is_empty() {
         return;
     }
     if let Err(err) = sim_core::validate_scoring_config(&content.scoring) {
-        panic!("invalid scoring config: {err}");
+        report.error(format!("invalid scoring config: {err}"));
     }
 }
 
-pub fn validate_state(state: &GameState, content: &GameContent) {
+/// Checks that inventories in `state` only hold elements known to `content`.
+pub fn validate_state(
+    state: &GameState,
+    content: &GameContent,
+) -> Result<Vec<String>, ValidationReport> {
+    let mut report = ValidationReport::default();
     let element_ids: HashSet<&str> = content.elements.iter().map(|e| e.id.as_str()).collect();
     for station in state.stations.values() {
         for item in &station.core.inventory {
             if let InventoryItem::Material { element, .. } = item {
-                assert!(
-                    element_ids.contains(element.as_str()),
-                    "station '{}' inventory material element '{}' is not a known element",
-                    station.id.0,
-                    element
-                );
+                report.check(element_ids.contains(element.as_str()), || {
+                    format!(
+                        "station '{}' inventory material element '{}' is not a known element",
+                        station.id.0, element
+                    )
+                });
             }
         }
     }
     for ship in state.ships.values() {
         for item in &ship.inventory {
             if let InventoryItem::Material { element, .. } = item {
-                assert!(
-                    element_ids.contains(element.as_str()),
-                    "ship '{}' inventory material element '{}' is not a known element",
-                    ship.id.0,
-                    element
+                report.check(element_ids.contains(element.as_str()), || {
+                    format!(
+                        "ship '{}' inventory material element '{}' is not a known element",
+                        ship.id.0, element
+                    )
+                });
+            }
+        }
+    }
+    report.into_result()
+}
+
+/// What to do with modules whose `def_id` no longer exists in content.
+///
+/// Saves outlive content: when a `ModuleDef` is removed or renamed, modules
+/// built from it would otherwise sit in the station doing nothing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OrphanedModulePolicy {
+    /// Refuse to load the save.
+    Error,
+    /// Remove the module and credit its listed price back to the balance.
+    Scrap,
+    /// Keep the module but disable it. The autopilot never re-enables
+    /// modules without a def.
+    #[default]
+    Quarantine,
+}
+
+impl std::str::FromStr for OrphanedModulePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(Self::Error),
+            "scrap" => Ok(Self::Scrap),
+            "quarantine" => Ok(Self::Quarantine),
+            other => Err(format!(
+                "unknown orphaned-module policy '{other}' (expected error, scrap, or quarantine)"
+            )),
+        }
+    }
+}
+
+/// A facility module whose `def_id` is missing from content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrphanedModule {
+    /// Station or ground facility id.
+    pub facility_id: String,
+    pub module_id: sim_core::ModuleInstanceId,
+    pub def_id: String,
+    /// Balance credited when scrapped. Zero under other policies, or when
+    /// the def has no pricing entry either.
+    pub refund: f64,
+}
+
+/// Lists modules on stations and ground facilities that reference an
+/// unknown module def, in facility then module order.
+pub fn find_orphaned_modules(state: &GameState, content: &GameContent) -> Vec<OrphanedModule> {
+    let stations = state
+        .stations
+        .values()
+        .map(|station| (station.id.0.as_str(), &station.core));
+    let ground = state
+        .ground_facilities
+        .values()
+        .map(|facility| (facility.id.0.as_str(), &facility.core));
+    stations
+        .chain(ground)
+        .flat_map(|(facility_id, core)| {
+            core.modules
+                .iter()
+                .filter(|module| !content.module_defs.contains_key(&module.def_id))
+                .map(move |module| OrphanedModule {
+                    facility_id: facility_id.to_string(),
+                    module_id: module.id.clone(),
+                    def_id: module.def_id.clone(),
+                    refund: 0.0,
+                })
+        })
+        .collect()
+}
+
+/// Detects orphaned modules and applies `policy` to them. Returns the
+/// affected modules so the caller can report them.
+///
+/// Module indices must be rebuilt afterwards, since scrapping shifts
+/// module positions.
+pub fn resolve_orphaned_modules(
+    state: &mut GameState,
+    content: &GameContent,
+    policy: OrphanedModulePolicy,
+) -> Result<Vec<OrphanedModule>> {
+    let mut orphans = find_orphaned_modules(state, content);
+    if orphans.is_empty() {
+        return Ok(orphans);
+    }
+    match policy {
+        OrphanedModulePolicy::Error => {
+            let listing: Vec<String> = orphans
+                .iter()
+                .map(|o| format!("{}/{} ('{}')", o.facility_id, o.module_id.0, o.def_id))
+                .collect();
+            anyhow::bail!(
+                "save references {} module(s) with unknown defs: {}",
+                orphans.len(),
+                listing.join(", ")
+            );
+        }
+        OrphanedModulePolicy::Scrap => {
+            for orphan in &mut orphans {
+                orphan.refund = content
+                    .pricing
+                    .items
+                    .get(&orphan.def_id)
+                    .map_or(0.0, |entry| entry.base_price_per_unit);
+                state.balance += orphan.refund;
+            }
+            let is_known =
+                |module: &sim_core::ModuleState| content.module_defs.contains_key(&module.def_id);
+            for station in state.stations.values_mut() {
+                station.core.modules.retain(is_known);
+            }
+            for facility in state.ground_facilities.values_mut() {
+                facility.core.modules.retain(is_known);
+            }
+        }
+        OrphanedModulePolicy::Quarantine => {
+            let cores = state
+                .stations
+                .values_mut()
+                .map(|station| &mut station.core)
+                .chain(
+                    state
+                        .ground_facilities
+                        .values_mut()
+                        .map(|facility| &mut facility.core),
                 );
+            for core in cores {
+                for module in &mut core.modules {
+                    if !content.module_defs.contains_key(&module.def_id) {
+                        module.enabled = false;
+                    }
+                }
             }
         }
     }
+    Ok(orphans)
+}
+
+fn orphan_warnings(orphans: &[OrphanedModule], policy: OrphanedModulePolicy) -> Vec<String> {
+    let action = match policy {
+        OrphanedModulePolicy::Error => "rejected",
+        OrphanedModulePolicy::Scrap => "scrapped",
+        OrphanedModulePolicy::Quarantine => "quarantined (disabled)",
+    };
+    orphans
+        .iter()
+        .map(|orphan| {
+            format!(
+                "module '{}' on '{}' has unknown def '{}'; {action}{}",
+                orphan.module_id.0,
+                orphan.facility_id,
+                orphan.def_id,
+                if orphan.refund > 0.0 {
+                    format!(", refunded {:.0}", orphan.refund)
+                } else {
+                    String::new()
+                },
+            )
+        })
+        .collect()
 }
 
 fn load_hull_defs(
-    dir: &Path,
+    dirs: &[&Path],
+    report: &mut ValidationReport,
 ) -> Result<std::collections::BTreeMap<sim_core::HullId, sim_core::HullDef>> {
-    match std::fs::read_to_string(dir.join("hull_defs.json")) {
-        Ok(text) => {
-            let defs: Vec<sim_core::HullDef> =
-                serde_json::from_str(&text).context("parsing hull_defs.json")?;
-            let mut map = std::collections::BTreeMap::new();
-            for def in defs {
-                let id = def.id.clone();
-                assert!(
-                    map.insert(id.clone(), def).is_none(),
-                    "duplicate hull ID: {id}"
-                );
-            }
-            Ok(map)
+    let defs: Vec<sim_core::HullDef> = load_optional(dirs, "hull_defs.json")?;
+    let mut map = std::collections::BTreeMap::new();
+    for def in defs {
+        let id = def.id.clone();
+        if map.insert(id.clone(), def).is_some() {
+            report.error(format!("duplicate hull ID: {id}"));
         }
-        Err(_) => Ok(std::collections::BTreeMap::new()),
     }
+    Ok(map)
+}
+
+/// Load ship class definitions from `content/ship_classes.json`.
+/// Returns an empty map if the file does not exist; duplicate class IDs
+/// are reported.
+fn load_ship_classes(
+    dirs: &[&Path],
+    report: &mut ValidationReport,
+) -> Result<std::collections::BTreeMap<String, sim_core::ShipClassDef>> {
+    let defs: Vec<sim_core::ShipClassDef> = load_optional(dirs, "ship_classes.json")?;
+    let mut map = std::collections::BTreeMap::new();
+    for def in defs {
+        let id = def.id.clone();
+        if map.insert(id.clone(), def).is_some() {
+            report.error(format!("duplicate ship class ID: {id}"));
+        }
+    }
+    Ok(map)
 }
 
 /// Load station frame definitions from `content/frame_defs.json`.
-/// Returns an empty map if the file does not exist.
-/// Panics on duplicate frame IDs.
+/// Returns an empty map if the file does not exist; duplicate frame IDs
+/// are reported.
 fn load_frame_defs(
-    dir: &Path,
+    dirs: &[&Path],
+    report: &mut ValidationReport,
 ) -> Result<std::collections::BTreeMap<sim_core::FrameId, sim_core::FrameDef>> {
-    match std::fs::read_to_string(dir.join("frame_defs.json")) {
-        Ok(text) => {
-            let defs: Vec<sim_core::FrameDef> =
-                serde_json::from_str(&text).context("parsing frame_defs.json")?;
-            let mut map = std::collections::BTreeMap::new();
-            for def in defs {
-                let id = def.id.clone();
-                assert!(
-                    map.insert(id.clone(), def).is_none(),
-                    "duplicate frame ID: {id}"
-                );
-            }
-            Ok(map)
+    let defs: Vec<sim_core::FrameDef> = load_optional(dirs, "frame_defs.json")?;
+    let mut map = std::collections::BTreeMap::new();
+    for def in defs {
+        let id = def.id.clone();
+        if map.insert(id.clone(), def).is_some() {
+            report.error(format!("duplicate frame ID: {id}"));
         }
-        Err(_) => Ok(std::collections::BTreeMap::new()),
     }
+    Ok(map)
 }
 
 fn load_fitting_templates(
-    dir: &Path,
+    dirs: &[&Path],
 ) -> Result<std::collections::BTreeMap<sim_core::HullId, Vec<sim_core::FittedModule>>> {
-    match std::fs::read_to_string(dir.join("fitting_templates.json")) {
-        Ok(text) => {
-            let map: std::collections::BTreeMap<String, Vec<sim_core::FittedModule>> =
-                serde_json::from_str(&text).context("parsing fitting_templates.json")?;
-            Ok(map
-                .into_iter()
-                .map(|(key, value)| (sim_core::HullId(key), value))
-                .collect())
-        }
-        Err(_) => Ok(std::collections::BTreeMap::new()),
-    }
+    let map: std::collections::BTreeMap<String, Vec<sim_core::FittedModule>> =
+        load_optional(dirs, "fitting_templates.json")?;
+    Ok(map
+        .into_iter()
+        .map(|(key, value)| (sim_core::HullId(key), value))
+        .collect())
 }
 
 /// Validate that satellite type strings referenced by autopilot config and
 /// scoring signals exist in `satellite_defs`. This catches typos and content
 /// drift at load time rather than silently returning zero counts.
-fn validate_satellite_type_refs(content: &sim_core::GameContent, satellite_types: &HashSet<&str>) {
+fn validate_satellite_type_refs(
+    content: &sim_core::GameContent,
+    satellite_types: &HashSet<&str>,
+    report: &mut ValidationReport,
+) {
     // Only validate if there are satellite defs at all (test fixtures may be empty).
     if satellite_types.is_empty() {
         return;
     }
     // autopilot.comm_satellite_type
     let comm = content.autopilot.comm_satellite_type.as_str();
-    assert!(
-        satellite_types.contains(comm),
-        "autopilot.comm_satellite_type '{comm}' not found in satellite_defs"
-    );
+    report.check(satellite_types.contains(comm), || {
+        format!("autopilot.comm_satellite_type '{comm}' not found in satellite_defs")
+    });
     // autopilot.nav_satellite_type
     let nav = content.autopilot.nav_satellite_type.as_str();
-    assert!(
-        satellite_types.contains(nav),
-        "autopilot.nav_satellite_type '{nav}' not found in satellite_defs"
-    );
+    report.check(satellite_types.contains(nav), || {
+        format!("autopilot.nav_satellite_type '{nav}' not found in satellite_defs")
+    });
     // scoring signal sources with satellites_of_type: prefix
     for dim in &content.scoring.dimensions {
         for signal in &dim.signals {
@@ -702,11 +1375,13 @@ fn validate_satellite_type_refs(content: &sim_core::GameContent, satellite_types
                 .source
                 .strip_prefix(sim_core::SATELLITES_OF_TYPE_PREFIX)
             {
-                assert!(
-                    satellite_types.contains(sat_type),
-                    "scoring dimension '{}' signal '{}' references unknown satellite type '{sat_type}'",
-                    dim.id, signal.source
-                );
+                report.check(satellite_types.contains(sat_type), || {
+                    format!(
+                        "scoring dimension '{}' signal '{}' references unknown satellite type '{sat_type}'",
+                        dim.id,
+                        signal.source,
+                    )
+                });
             }
         }
     }
@@ -717,6 +1392,7 @@ fn validate_satellite_type_refs(content: &sim_core::GameContent, satellite_types
 fn validate_milestone_satellite_refs(
     milestones: &[sim_core::MilestoneDef],
     satellite_types: &HashSet<&str>,
+    report: &mut ValidationReport,
 ) {
     if satellite_types.is_empty() {
         return;
@@ -725,11 +1401,12 @@ fn validate_milestone_satellite_refs(
         for cond in &m.conditions {
             if let sim_core::MilestoneCondition::CounterAbove { counter, .. } = cond {
                 if let Some(sat_type) = counter.strip_prefix(sim_core::SATELLITES_OF_TYPE_PREFIX) {
-                    assert!(
-                        satellite_types.contains(sat_type),
-                        "milestone '{}' counter '{counter}' references unknown satellite type '{sat_type}'",
-                        m.id
-                    );
+                    report.check(satellite_types.contains(sat_type), || {
+                        format!(
+                            "milestone '{}' counter '{counter}' references unknown satellite type '{sat_type}'",
+                            m.id,
+                        )
+                    });
                 }
             }
         }
@@ -738,7 +1415,7 @@ fn validate_milestone_satellite_refs(
 
 /// Validate milestone definitions: unique IDs, valid chained references,
 /// known counter names, and known metric field names. Typos in content must
-/// panic at load time rather than silently preventing milestones from firing.
+/// fail at load time rather than silently preventing milestones from firing.
 ///
 /// `module_behavior_types` is the set of module behavior type names present
 /// in `content.module_defs` (e.g. `"processor"`, `"assembler"`) — used to
@@ -746,14 +1423,13 @@ fn validate_milestone_satellite_refs(
 fn validate_milestones(
     milestones: &[sim_core::MilestoneDef],
     module_behavior_types: &HashSet<&'static str>,
+    report: &mut ValidationReport,
 ) {
     let mut seen_ids = std::collections::HashSet::new();
     for m in milestones {
-        assert!(
-            seen_ids.insert(m.id.as_str()),
-            "duplicate milestone id '{}'",
-            m.id
-        );
+        report.check(seen_ids.insert(m.id.as_str()), || {
+            format!("duplicate milestone id '{}'", m.id)
+        });
     }
     // Build the set of known metric field names from MetricsSnapshot's descriptors.
     let metric_fields: HashSet<&'static str> = sim_core::MetricsSnapshot::fixed_field_descriptors()
@@ -766,11 +1442,12 @@ fn validate_milestones(
         for cond in &m.conditions {
             match cond {
                 sim_core::MilestoneCondition::MilestoneCompleted { milestone_id } => {
-                    assert!(
-                        seen_ids.contains(milestone_id.as_str()),
-                        "milestone '{}' references unknown prerequisite milestone '{milestone_id}'",
-                        m.id
-                    );
+                    report.check(seen_ids.contains(milestone_id.as_str()), || {
+                        format!(
+                            "milestone '{}' references unknown prerequisite milestone '{milestone_id}'",
+                            m.id,
+                        )
+                    });
                 }
                 sim_core::MilestoneCondition::CounterAbove { counter, .. } => {
                     // Dynamic satellites_of_type:<type> counters are validated by
@@ -778,12 +1455,13 @@ fn validate_milestones(
                     if counter.starts_with(sim_core::SATELLITES_OF_TYPE_PREFIX) {
                         continue;
                     }
-                    assert!(
-                        known_counters.contains(counter.as_str()),
-                        "milestone '{}' references unknown counter '{counter}' \
+                    report.check(known_counters.contains(counter.as_str()), || {
+                        format!(
+                            "milestone '{}' references unknown counter '{counter}' \
                          (check spelling or add to KNOWN_COUNTERS in milestone.rs)",
-                        m.id
-                    );
+                            m.id
+                        )
+                    });
                 }
                 sim_core::MilestoneCondition::MetricAbove { field, .. } => {
                     // Check fixed fields FIRST to mirror runtime resolution order
@@ -801,11 +1479,11 @@ fn validate_milestones(
                     if is_per_module_metric_field(field, module_behavior_types) {
                         continue;
                     }
-                    panic!(
+                    report.error(format!(
                         "milestone '{}' references unknown metric field '{field}' \
                          (check spelling against MetricsSnapshot::fixed_field_descriptors)",
                         m.id
-                    );
+                    ));
                 }
             }
         }
@@ -825,86 +1503,93 @@ fn is_per_module_metric_field(field: &str, module_behavior_types: &HashSet<&'sta
         && module_behavior_types.contains(type_name)
 }
 
-/// Load an optional JSON file, returning `T::default()` if the file is missing.
-fn load_optional_json<T: serde::de::DeserializeOwned + Default>(
-    dir: &Path,
+/// Load an optional content file, returning `T::default()` if it is missing.
+fn load_optional<T: serde::de::DeserializeOwned + Default>(
+    dirs: &[&Path],
     filename: &str,
 ) -> Result<T> {
-    match std::fs::read_to_string(dir.join(filename)) {
-        Ok(text) => serde_json::from_str(&text).with_context(|| format!("parsing {filename}")),
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
-        Err(e) => Err(anyhow::anyhow!("reading {filename}: {e}")),
-    }
+    Ok(content_files::load_optional(dirs, filename)?.unwrap_or_default())
 }
 
 /// Load crew role definitions from `crew_roles.json`.
-/// Returns an empty map if the file is missing.
+/// Returns an empty map if the file is missing; duplicate ids are reported.
 fn load_crew_roles(
-    dir: &Path,
+    dirs: &[&Path],
+    report: &mut ValidationReport,
 ) -> Result<std::collections::BTreeMap<sim_core::CrewRole, sim_core::CrewRoleDef>> {
-    let defs: Vec<sim_core::CrewRoleDef> = load_optional_json(dir, "crew_roles.json")?;
+    let defs: Vec<sim_core::CrewRoleDef> = load_optional(dirs, "crew_roles.json")?;
     let mut seen = std::collections::HashSet::new();
     for def in &defs {
-        assert!(seen.insert(&def.id), "duplicate crew role id '{}'", def.id);
-        if def.recruitment_cost <= 0.0 {
-            eprintln!(
-                "warning: crew role '{}' has non-positive recruitment_cost ({})",
-                def.id, def.recruitment_cost
-            );
-        }
+        report.check(seen.insert(&def.id), || {
+            format!("duplicate crew role id '{}'", def.id)
+        });
     }
     Ok(defs.into_iter().map(|d| (d.id.clone(), d)).collect())
 }
 
 fn load_satellite_defs(
-    dir: &Path,
+    dirs: &[&Path],
+    report: &mut ValidationReport,
 ) -> Result<std::collections::BTreeMap<String, sim_core::SatelliteDef>> {
-    let defs: Vec<sim_core::SatelliteDef> = load_optional_json(dir, "satellite_defs.json")?;
+    let defs: Vec<sim_core::SatelliteDef> = load_optional(dirs, "satellite_defs.json")?;
     let mut seen = std::collections::HashSet::new();
     for def in &defs {
-        assert!(
-            seen.insert(&def.id),
-            "duplicate satellite def id '{}'",
-            def.id
-        );
+        report.check(seen.insert(&def.id), || {
+            format!("duplicate satellite def id '{}'", def.id)
+        });
+    }
+    Ok(defs.into_iter().map(|d| (d.id.clone(), d)).collect())
+}
+
+fn load_project_defs(
+    dirs: &[&Path],
+    report: &mut ValidationReport,
+) -> Result<std::collections::BTreeMap<String, sim_core::ProjectDef>> {
+    let defs: Vec<sim_core::ProjectDef> = load_optional(dirs, "project_defs.json")?;
+    let mut seen = std::collections::HashSet::new();
+    for def in &defs {
+        report.check(seen.insert(&def.id), || {
+            format!("duplicate project def id '{}'", def.id)
+        });
     }
     Ok(defs.into_iter().map(|d| (d.id.clone(), d)).collect())
 }
 
-fn load_rocket_defs(dir: &Path) -> Result<std::collections::BTreeMap<String, sim_core::RocketDef>> {
-    let defs: Vec<sim_core::RocketDef> = load_optional_json(dir, "rockets.json")?;
+fn load_rocket_defs(
+    dirs: &[&Path],
+    report: &mut ValidationReport,
+) -> Result<std::collections::BTreeMap<String, sim_core::RocketDef>> {
+    let defs: Vec<sim_core::RocketDef> = load_optional(dirs, "rockets.json")?;
     let mut seen = std::collections::HashSet::new();
     for def in &defs {
-        assert!(seen.insert(&def.id), "duplicate rocket def id '{}'", def.id);
+        report.check(seen.insert(&def.id), || {
+            format!("duplicate rocket def id '{}'", def.id)
+        });
     }
     Ok(defs.into_iter().map(|d| (d.id.clone(), d)).collect())
 }
 
-/// Load recipe definitions from `recipes.json`, validating unique IDs.
+/// Load recipe definitions from `recipes.json`, reporting duplicate IDs.
 fn load_recipes(
-    dir: &Path,
+    dirs: &[&Path],
+    report: &mut ValidationReport,
 ) -> Result<std::collections::BTreeMap<sim_core::RecipeId, sim_core::RecipeDef>> {
-    let recipes: Vec<sim_core::RecipeDef> = serde_json::from_str(
-        &std::fs::read_to_string(dir.join("recipes.json")).context("reading recipes.json")?,
-    )
-    .context("parsing recipes.json")?;
+    let recipes: Vec<sim_core::RecipeDef> = content_files::load_required(dirs, "recipes.json")?;
     let mut seen = std::collections::HashSet::new();
     for recipe in &recipes {
-        assert!(
-            seen.insert(&recipe.id),
-            "duplicate recipe id '{}'",
-            recipe.id
-        );
+        report.check(seen.insert(&recipe.id), || {
+            format!("duplicate recipe id '{}'", recipe.id)
+        });
     }
     Ok(recipes.into_iter().map(|r| (r.id.clone(), r)).collect())
 }
 
-/// Load required JSON files (constants, techs, solar system, etc.) that must
-/// exist. Returns parsed values to feed into `GameContent`. Extracted from
+/// Load required content files (constants, techs, solar system, etc.) that
+/// must exist. Returns parsed values to feed into `GameContent`. Extracted from
 /// `load_content` to keep function size under the clippy line limit.
 #[allow(clippy::type_complexity)]
-fn load_required_json(
-    dir: &Path,
+fn load_required_files(
+    dirs: &[&Path],
 ) -> Result<(
     Constants,
     TechsFile,
@@ -917,54 +1602,22 @@ fn load_required_json(
     Vec<AlertRuleDef>,
     Vec<sim_core::sim_events::SimEventDef>,
 )> {
-    let constants: Constants = serde_json::from_str(
-        &std::fs::read_to_string(dir.join("constants.json")).context("reading constants.json")?,
-    )
-    .context("parsing constants.json")?;
-    let techs_file: TechsFile = serde_json::from_str(
-        &std::fs::read_to_string(dir.join("techs.json")).context("reading techs.json")?,
-    )
-    .context("parsing techs.json")?;
-    let solar_system: SolarSystemDef = serde_json::from_str(
-        &std::fs::read_to_string(dir.join("solar_system.json"))
-            .context("reading solar_system.json")?,
-    )
-    .context("parsing solar_system.json")?;
-    let templates_file: AsteroidTemplatesFile = serde_json::from_str(
-        &std::fs::read_to_string(dir.join("asteroid_templates.json"))
-            .context("reading asteroid_templates.json")?,
-    )
-    .context("parsing asteroid_templates.json")?;
-    let elements_file: ElementsFile = serde_json::from_str(
-        &std::fs::read_to_string(dir.join("elements.json")).context("reading elements.json")?,
-    )
-    .context("parsing elements.json")?;
+    let constants: Constants = content_files::load_required(dirs, "constants.json")?;
+    let techs_file: TechsFile = content_files::load_required(dirs, "techs.json")?;
+    let solar_system: SolarSystemDef = content_files::load_required(dirs, "solar_system.json")?;
+    let templates_file: AsteroidTemplatesFile =
+        content_files::load_required(dirs, "asteroid_templates.json")?;
+    let elements_file: ElementsFile = content_files::load_required(dirs, "elements.json")?;
     let module_defs: AHashMap<String, ModuleDef> = {
-        let defs: Vec<ModuleDef> = serde_json::from_str(
-            &std::fs::read_to_string(dir.join("module_defs.json"))
-                .context("reading module_defs.json")?,
-        )
-        .context("parsing module_defs.json")?;
+        let defs: Vec<ModuleDef> = content_files::load_required(dirs, "module_defs.json")?;
         defs.into_iter().map(|d| (d.id.clone(), d)).collect()
     };
-    let component_defs: Vec<sim_core::ComponentDef> = serde_json::from_str(
-        &std::fs::read_to_string(dir.join("component_defs.json"))
-            .context("reading component_defs.json")?,
-    )
-    .context("parsing component_defs.json")?;
-    let pricing: PricingTable = serde_json::from_str(
-        &std::fs::read_to_string(dir.join("pricing.json")).context("reading pricing.json")?,
-    )
-    .context("parsing pricing.json")?;
-    let alert_rules: Vec<AlertRuleDef> = match std::fs::read_to_string(dir.join("alerts.json")) {
-        Ok(text) => serde_json::from_str(&text).context("parsing alerts.json")?,
-        Err(_) => Vec::new(),
-    };
+    let component_defs: Vec<sim_core::ComponentDef> =
+        content_files::load_required(dirs, "component_defs.json")?;
+    let pricing: PricingTable = content_files::load_required(dirs, "pricing.json")?;
+    let alert_rules: Vec<AlertRuleDef> = load_optional(dirs, "alerts.json")?;
     let mut sim_events: Vec<sim_core::sim_events::SimEventDef> =
-        match std::fs::read_to_string(dir.join("events.json")) {
-            Ok(text) => serde_json::from_str(&text).context("parsing events.json")?,
-            Err(_) => Vec::new(),
-        };
+        load_optional(dirs, "events.json")?;
     for event in &mut sim_events {
         event.resolve_weight();
     }
@@ -983,7 +1636,38 @@ fn load_required_json(
 }
 
 pub fn load_content(content_dir: &str) -> Result<GameContent> {
-    let dir = Path::new(content_dir);
+    load_content_packs(&[content_dir])
+}
+
+/// Load content layered from several directories, base first. Later packs
+/// override or extend earlier ones per file, merging defs by id (see
+/// `content_files`); required files need only be in one pack. Validation
+/// warnings are dropped; see [`load_content_packs_with_warnings`].
+pub fn load_content_packs(content_dirs: &[&str]) -> Result<GameContent> {
+    load_content_packs_with_warnings(content_dirs).map(|(content, _)| content)
+}
+
+/// [`load_content_packs`], also returning the validation warnings for the
+/// caller to show.
+pub fn load_content_packs_with_warnings(
+    content_dirs: &[&str],
+) -> Result<(GameContent, Vec<String>)> {
+    let (content, mut report) = load_content_packs_unvalidated(content_dirs)?;
+    report.merge(content_report(&content));
+    let warnings = report.into_result().context("invalid content")?;
+    Ok((content, warnings))
+}
+
+/// [`load_content_packs`] without [`validate_content`], for tools that
+/// report the problems themselves. Only unreadable or unparsable files fail;
+/// the report holds what loading itself found (duplicate ids), to merge
+/// with [`content_report`].
+pub fn load_content_packs_unvalidated(
+    content_dirs: &[&str],
+) -> Result<(GameContent, ValidationReport)> {
+    anyhow::ensure!(!content_dirs.is_empty(), "no content directory given");
+    let dirs: Vec<&Path> = content_dirs.iter().map(Path::new).collect();
+    let dirs = dirs.as_slice();
     let (
         constants,
         techs_file,
@@ -995,27 +1679,33 @@ pub fn load_content(content_dir: &str) -> Result<GameContent> {
         pricing,
         alert_rules,
         sim_events,
-    ) = load_required_json(dir)?;
-    let hulls = load_hull_defs(dir)?;
-    let frames = load_frame_defs(dir)?;
-    let fitting_templates = load_fitting_templates(dir)?;
-    let initial_station: sim_core::InitialStationDef =
-        load_optional_json(dir, "initial_station.json")?;
+    ) = load_required_files(dirs)?;
+    let mut report = ValidationReport::default();
+    let hulls = load_hull_defs(dirs, &mut report)?;
+    let ship_classes = load_ship_classes(dirs, &mut report)?;
+    let belts: Vec<sim_core::BeltDef> = load_optional(dirs, "belts.json")?;
+    let frames = load_frame_defs(dirs, &mut report)?;
+    let adjacency_rules: Vec<sim_core::AdjacencyRuleDef> =
+        load_optional(dirs, "adjacency_rules.json")?;
+    let fitting_templates = load_fitting_templates(dirs)?;
+    let initial_station: sim_core::InitialStationDef = load_optional(dirs, "initial_station.json")?;
     let initial_stations: Vec<sim_core::StationSetupDef> =
-        load_optional_json(dir, "initial_stations.json")?;
-    let autopilot: sim_core::AutopilotConfig = load_optional_json(dir, "autopilot.json")?;
-    let default_strategy: sim_core::StrategyConfig = load_optional_json(dir, "strategy.json")?;
+        load_optional(dirs, "initial_stations.json")?;
+    let autopilot: sim_core::AutopilotConfig = load_optional(dirs, "autopilot.json")?;
+    let default_strategy: sim_core::StrategyConfig = load_optional(dirs, "strategy.json")?;
     let phase_presets: std::collections::BTreeMap<sim_core::GamePhase, sim_core::PriorityWeights> =
-        load_optional_json(dir, "strategy_phase_presets.json")?;
-    let scoring: sim_core::ScoringConfig = load_optional_json(dir, "scoring.json")?;
-    let milestones: Vec<sim_core::MilestoneDef> = load_optional_json(dir, "milestones.json")?;
-    let crew_roles = load_crew_roles(dir)?;
-    let recipe_map = load_recipes(dir)?;
-    let rocket_defs = load_rocket_defs(dir)?;
-    let satellite_defs = load_satellite_defs(dir)?;
+        load_optional(dirs, "strategy_phase_presets.json")?;
+    let scoring: sim_core::ScoringConfig = load_optional(dirs, "scoring.json")?;
+    let milestones: Vec<sim_core::MilestoneDef> = load_optional(dirs, "milestones.json")?;
+    let crew_roles = load_crew_roles(dirs, &mut report)?;
+    let recipe_map = load_recipes(dirs, &mut report)?;
+    let rocket_defs = load_rocket_defs(dirs, &mut report)?;
+    let satellite_defs = load_satellite_defs(dirs, &mut report)?;
+    let project_defs = load_project_defs(dirs, &mut report)?;
     let mut content = GameContent {
         content_version: techs_file.content_version,
         techs: techs_file.techs,
+        starting_unlocked: techs_file.starting_unlocked,
         solar_system,
         asteroid_templates: templates_file.templates,
         elements: elements_file.elements,
@@ -1027,7 +1717,10 @@ pub fn load_content(content_dir: &str) -> Result<GameContent> {
         alert_rules,
         events: sim_events,
         hulls,
+        ship_classes,
+        belts,
         frames,
+        adjacency_rules,
         fitting_templates,
         initial_station,
         initial_stations,
@@ -1039,14 +1732,14 @@ pub fn load_content(content_dir: &str) -> Result<GameContent> {
         milestones,
         rocket_defs,
         satellite_defs,
+        project_defs,
+        subsystems: sim_core::Subsystems::default(),
         density_map: AHashMap::default(),
     };
     content.constants.derive_tick_values();
     sim_core::derive_module_tick_values(&mut content.module_defs, &content.constants);
     content.init_caches();
-    sim_core::sim_events::validate_event_defs(&content.events);
-    validate_content(&content);
-    Ok(content)
+    Ok((content, report))
 }
 
 fn build_initial_ship(
@@ -1081,9 +1774,13 @@ fn build_initial_ship(
         crew: std::collections::BTreeMap::new(),
         leaders: Vec::new(),
         home_station: Some(home_station.clone()),
+        name: String::new(),
+        class_id: None,
+        role: None,
+        wear: sim_core::WearState::default(),
     };
     if content.hulls.contains_key(&hull_id) {
-        sim_core::recompute_ship_stats(&mut ship, content);
+        sim_core::internals::recompute_ship_stats(&mut ship, content);
         ship.propellant_kg = ship.propellant_capacity_kg;
     }
     (ship_id, ship)
@@ -1110,6 +1807,7 @@ fn build_station_from_setup(
         inventory.push(InventoryItem::Module {
             item_id: ModuleItemId(format!("module_item_{:04}", module_id_offset + index + 1)),
             module_def_id: module_def_id.clone(),
+            wear: 0.0,
         });
     }
     for mat in &setup.initial.materials {
@@ -1146,14 +1844,20 @@ fn build_station_from_setup(
             thermal_links: Vec::new(),
             power: PowerState::default(),
             cached_inventory_volume_m3: None,
-            module_type_index: sim_core::ModuleTypeIndex::default(),
+            module_type_index: sim_core::internals::ModuleTypeIndex::default(),
             module_id_index: std::collections::HashMap::new(),
-            power_budget_cache: sim_core::PowerBudgetCache::default(),
+            power_budget_cache: sim_core::internals::PowerBudgetCache::default(),
+            production_windows: std::collections::BTreeMap::new(),
+            life_support_failing: false,
         },
         frame_id,
         leaders: Vec::new(),
+        owner: PrincipalId("principal_autopilot".to_string()),
+        expansions: sim_core::StationExpansions::default(),
+        power_policy: sim_core::PowerPolicy::default(),
+        history: std::collections::VecDeque::new(),
     };
-    sim_core::recompute_station_stats(&mut station, content);
+    sim_core::internals::recompute_station_stats(&mut station, content);
 
     let ships: Vec<(ShipId, ShipState)> = setup
         .ships
@@ -1246,7 +1950,7 @@ pub fn build_initial_state(content: &GameContent, seed: u64, rng: &mut impl Rng)
             let zone_class = body.zone.as_ref().expect("zone body").resource_class;
             let template = sim_core::pick_template_biased(templates, zone_class, rng);
             let position = sim_core::random_position_in_zone(body, rng);
-            let uuid = sim_core::generate_uuid(rng);
+            let uuid = sim_core::internals::generate_uuid(rng);
             scan_sites.push(ScanSite {
                 id: SiteId(format!("site_{uuid}")),
                 position,
@@ -1254,7 +1958,7 @@ pub fn build_initial_state(content: &GameContent, seed: u64, rng: &mut impl Rng)
             });
         }
     }
-    GameState {
+    let mut state = GameState {
         meta: MetaState {
             tick: 0,
             seed,
@@ -1272,8 +1976,13 @@ pub fn build_initial_state(content: &GameContent, seed: u64, rng: &mut impl Rng)
             data_pool: AHashMap::default(),
             evidence: AHashMap::default(),
             action_counts: AHashMap::default(),
+            queue: Vec::new(),
+        },
+        balance: c.starting_loan,
+        loan: sim_core::LoanState {
+            outstanding: c.starting_loan,
+            ..sim_core::LoanState::default()
         },
-        balance: 1_000_000_000.0,
         export_revenue_total: 0.0,
         export_count: 0,
         counters: Counters {
@@ -1291,7 +2000,42 @@ pub fn build_initial_state(content: &GameContent, seed: u64, rng: &mut impl Rng)
         transfer_count: 0,
         progression: sim_core::ProgressionState::default(),
         strategy_config: content.default_strategy.clone(),
+        import_orders: Vec::new(),
+        freight_orders: Vec::new(),
+        freight_routes: Vec::new(),
+        market: sim_core::MarketState::default(),
+        contracts: Vec::new(),
         body_cache: sim_core::build_body_cache(&content.solar_system.bodies),
+        jettisoned_slag_kg: std::collections::BTreeMap::new(),
+        solar_storms: std::collections::BTreeMap::new(),
+        claims: std::collections::BTreeMap::new(),
+        hotspots: sim_core::internals::HotspotWindow::default(),
+        projects: Vec::new(),
+        station_builds: Vec::new(),
+        belts: std::collections::BTreeMap::new(),
+        pending_commands: Vec::new(),
+    };
+    grant_starting_techs(&mut state, content);
+    clear_disabled_subsystems(&mut state, content);
+    state
+}
+
+/// Remove world features belonging to subsystems the content disables:
+/// with mining off, the run has no scan sites or asteroids.
+pub fn clear_disabled_subsystems(state: &mut GameState, content: &GameContent) {
+    if !content.subsystems.enabled(sim_core::Subsystem::Mining) {
+        state.scan_sites.clear();
+        state.asteroids.clear();
+    }
+}
+
+/// Unlock the content's `starting_unlocked` techs, applying their stat
+/// modifiers as if they had been researched.
+fn grant_starting_techs(state: &mut GameState, content: &GameContent) {
+    for tech_id in &content.starting_unlocked {
+        if let Some(tech) = content.techs.iter().find(|t| t.id == *tech_id) {
+            sim_core::grant_tech(state, tech);
+        }
     }
 }
 
@@ -1354,11 +2098,12 @@ pub fn auto_assign_initial_crew(state: &mut GameState, content: &GameContent) {
                 .module_defs
                 .get(&station.core.modules[module_index].def_id)
             {
-                station.core.modules[module_index].efficiency = sim_core::compute_module_efficiency(
-                    &station.core.modules[module_index],
-                    def,
-                    &content.constants,
-                );
+                station.core.modules[module_index].efficiency =
+                    sim_core::internals::compute_module_efficiency(
+                        &station.core.modules[module_index],
+                        def,
+                        &content.constants,
+                    );
             }
         }
     }
@@ -1384,7 +2129,8 @@ pub fn create_run_dir(run_id: &str) -> Result<std::path::PathBuf> {
 
 /// Writes `run_info.json` into the run directory.
 ///
-/// `runner_args` is an arbitrary JSON value containing runner-specific CLI arguments.
+/// `runner_args` is an arbitrary JSON value containing runner-specific CLI
+/// arguments. `experiment` is written under `"experiment"` unless empty.
 #[allow(clippy::needless_pass_by_value)]
 pub fn write_run_info(
     dir: &std::path::Path,
@@ -1393,14 +2139,18 @@ pub fn write_run_info(
     content_version: &str,
     metrics_every: u64,
     runner_args: serde_json::Value,
+    experiment: &ExperimentMeta,
 ) -> Result<()> {
-    let info = serde_json::json!({
+    let mut info = serde_json::json!({
         "run_id": run_id,
         "seed": seed,
         "content_version": content_version,
         "metrics_every": metrics_every,
         "args": runner_args,
     });
+    if !experiment.is_empty() {
+        info["experiment"] = serde_json::to_value(experiment).context("serializing experiment")?;
+    }
     let path = dir.join("run_info.json");
     let file =
         std::fs::File::create(&path).with_context(|| format!("creating {}", path.display()))?;
@@ -1409,45 +2159,130 @@ pub fn write_run_info(
     Ok(())
 }
 
+/// Stable fingerprint of loaded content, as 16 hex digits.
+///
+/// Hashes the canonical JSON form (object keys sorted) with FNV-1a, so the
+/// result does not depend on hash-map iteration order and matches across
+/// machines that load identical content files.
+pub fn content_hash(content: &GameContent) -> Result<String> {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
+    let canonical = serde_json::to_value(content).context("serializing content")?;
+    let bytes = serde_json::to_vec(&canonical).context("serializing content")?;
+    let hash = bytes.iter().fold(FNV_OFFSET, |acc, byte| {
+        (acc ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    });
+    Ok(format!("{hash:016x}"))
+}
+
+/// Label for the world-generation child seed (see [`worldgen_rng`]).
+pub const WORLDGEN_SEED_LABEL: &str = "worldgen";
+
+/// RNG for world generation, derived from the run seed so that the layout
+/// of a seed does not depend on how many draws any other subsystem makes.
+pub fn worldgen_rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(sim_core::derive_seed(seed, WORLDGEN_SEED_LABEL))
+}
+
+/// Parses saved state JSON, upgrading it from an older schema version
+/// through the `sim_core::migrations` registry. Saves from a newer engine
+/// are refused.
+pub fn parse_state(json: &str) -> Result<GameState> {
+    parse_state_with_warnings(json).map(|(state, _)| state)
+}
+
+/// [`parse_state`] that also hands back load warnings (e.g. a schema
+/// migration) for the caller to show.
+pub fn parse_state_with_warnings(json: &str) -> Result<(GameState, Vec<String>)> {
+    let value: serde_json::Value = serde_json::from_str(json).context("parsing JSON")?;
+    parse_state_value_with_warnings(value)
+}
+
+/// [`parse_state`] for state already parsed as JSON, e.g. embedded in a
+/// larger document.
+pub fn parse_state_value(value: serde_json::Value) -> Result<GameState> {
+    parse_state_value_with_warnings(value).map(|(state, _)| state)
+}
+
+/// [`parse_state_value`] that also hands back load warnings.
+pub fn parse_state_value_with_warnings(
+    mut value: serde_json::Value,
+) -> Result<(GameState, Vec<String>)> {
+    let from = sim_core::migrations::migrate_state_json(&mut value)?;
+    let mut warnings = Vec::new();
+    if from != sim_core::CURRENT_SCHEMA_VERSION {
+        warnings.push(format!(
+            "migrated state from schema version {from} to {}",
+            sim_core::CURRENT_SCHEMA_VERSION
+        ));
+    }
+    let state = serde_json::from_value(value).context("parsing state")?;
+    Ok((state, warnings))
+}
+
+/// Ready a saved state for `content`: handles orphaned modules per
+/// `orphan_policy`, rebuilds the derived caches a save does not carry, and
+/// validates the result. Returns a warning per orphaned module handled.
+pub fn prepare_loaded_state(
+    mut loaded: GameState,
+    content: &GameContent,
+    orphan_policy: OrphanedModulePolicy,
+) -> Result<(GameState, Vec<String>)> {
+    let orphans = resolve_orphaned_modules(&mut loaded, content, orphan_policy)?;
+    let warnings = orphan_warnings(&orphans, orphan_policy);
+
+    loaded.body_cache = sim_core::build_body_cache(&content.solar_system.bodies);
+    for station in loaded.stations.values_mut() {
+        station.rebuild_module_index(content);
+        station.init_module_efficiency(content);
+    }
+    clear_disabled_subsystems(&mut loaded, content);
+    validate_state(&loaded, content).context("validating state")?;
+    Ok((loaded, warnings))
+}
+
 /// Loads state from a JSON file or builds initial state from content.
 ///
-/// Returns the game state and a seeded RNG.
+/// Returns the game state and the run RNG, seeded directly from the run
+/// seed and untouched by world generation. Modules in a loaded save whose
+/// def is missing from content are handled per `orphan_policy`.
 pub fn load_or_build_state(
     content: &GameContent,
     seed: Option<u64>,
     state_file: Option<&str>,
+    orphan_policy: OrphanedModulePolicy,
 ) -> Result<(GameState, ChaCha8Rng)> {
+    load_or_build_state_with_warnings(content, seed, state_file, orphan_policy)
+        .map(|(state, rng, _)| (state, rng))
+}
+
+/// [`load_or_build_state`] that also hands back the warnings from loading
+/// a state file (schema migration, orphaned modules).
+pub fn load_or_build_state_with_warnings(
+    content: &GameContent,
+    seed: Option<u64>,
+    state_file: Option<&str>,
+    orphan_policy: OrphanedModulePolicy,
+) -> Result<(GameState, ChaCha8Rng, Vec<String>)> {
     if let Some(path) = state_file {
         let json =
             std::fs::read_to_string(path).with_context(|| format!("reading state file: {path}"))?;
-        let mut loaded: GameState =
-            serde_json::from_str(&json).with_context(|| format!("parsing state file: {path}"))?;
-
-        // Validate schema version
-        let expected = sim_core::CURRENT_SCHEMA_VERSION;
-        let actual = loaded.meta.schema_version;
-        anyhow::ensure!(
-            actual == expected,
-            "Save file '{path}' has schema version {actual}, but the engine expects version {expected}. \
-             This save is incompatible with the current engine."
-        );
-
-        loaded.body_cache = sim_core::build_body_cache(&content.solar_system.bodies);
-        for station in loaded.stations.values_mut() {
-            station.rebuild_module_index(content);
-            station.init_module_efficiency(content);
-        }
+        let (loaded, mut warnings) = parse_state_with_warnings(&json)
+            .with_context(|| format!("loading state file: {path}"))?;
+        let (loaded, orphans) = prepare_loaded_state(loaded, content, orphan_policy)
+            .with_context(|| format!("loading state file: {path}"))?;
+        warnings.extend(orphans);
         let rng = ChaCha8Rng::seed_from_u64(loaded.meta.seed);
-        validate_state(&loaded, content);
-        Ok((loaded, rng))
+        Ok((loaded, rng, warnings))
     } else {
         let resolved_seed = seed.unwrap_or_else(rand::random);
-        let mut rng = ChaCha8Rng::seed_from_u64(resolved_seed);
-        let mut state = build_initial_state(content, resolved_seed, &mut rng);
+        let mut state =
+            build_initial_state(content, resolved_seed, &mut worldgen_rng(resolved_seed));
+        let rng = ChaCha8Rng::seed_from_u64(resolved_seed);
         for station in state.stations.values_mut() {
             station.rebuild_module_index(content);
         }
-        Ok((state, rng))
+        Ok((state, rng, Vec::new()))
     }
 }
 
@@ -1461,31 +2296,41 @@ pub struct RunSetup {
     pub game_state: GameState,
     pub rng: ChaCha8Rng,
     pub run_dir: Option<PathBuf>,
-    pub metrics_writer: Option<MetricsFileWriter>,
+    pub metrics_writer: Option<MetricsWriter>,
+    /// Warnings from loading content (validation) and state (schema
+    /// migration, orphaned modules), for the binary to show.
+    pub warnings: Vec<String>,
 }
 
 /// Builder for [`RunSetup`]. Loads content, builds/loads state, optionally
 /// creates a run directory and metrics writer.
 pub struct RunSetupBuilder {
     content: GameContent,
+    content_warnings: Vec<String>,
     seed: Option<u64>,
     state_file: Option<String>,
+    resume: Option<(GameState, ChaCha8Rng)>,
+    orphan_policy: OrphanedModulePolicy,
     enable_metrics: bool,
     metrics_every: u64,
+    metrics_format: MetricsFormat,
     runner_args: serde_json::Value,
+    experiment: ExperimentMeta,
 }
 
 impl RunSetupBuilder {
     /// Start a builder by loading content from `content_dir`.
     pub fn from_content_dir(content_dir: &str) -> Result<Self> {
-        let content = load_content(content_dir)?;
+        Self::from_content_packs(&[content_dir])
+    }
+
+    /// Start a builder from content layered over several directories, base
+    /// first; see [`load_content_packs`].
+    pub fn from_content_packs(content_dirs: &[&str]) -> Result<Self> {
+        let (content, warnings) = load_content_packs_with_warnings(content_dirs)?;
         Ok(Self {
-            content,
-            seed: None,
-            state_file: None,
-            enable_metrics: false,
-            metrics_every: 60,
-            runner_args: serde_json::Value::Null,
+            content_warnings: warnings,
+            ..Self::from_content(content)
         })
     }
 
@@ -1494,11 +2339,16 @@ impl RunSetupBuilder {
     pub fn from_content(content: GameContent) -> Self {
         Self {
             content,
+            content_warnings: Vec::new(),
             seed: None,
             state_file: None,
+            resume: None,
+            orphan_policy: OrphanedModulePolicy::default(),
             enable_metrics: false,
             metrics_every: 60,
+            metrics_format: MetricsFormat::default(),
             runner_args: serde_json::Value::Null,
+            experiment: ExperimentMeta::default(),
         }
     }
 
@@ -1516,7 +2366,23 @@ impl RunSetupBuilder {
         self
     }
 
-    /// Enable run-directory creation, `run_info.json`, and metrics CSV writing.
+    /// Continue a saved state with its RNG where it left off, e.g. from a
+    /// daemon autosave. Takes precedence over [`seed`](Self::seed) and
+    /// [`state_file`](Self::state_file).
+    #[must_use]
+    pub fn resume_from(mut self, game_state: GameState, rng: ChaCha8Rng) -> Self {
+        self.resume = Some((game_state, rng));
+        self
+    }
+
+    /// How to treat saved modules whose def is missing from content.
+    #[must_use]
+    pub fn orphan_policy(mut self, policy: OrphanedModulePolicy) -> Self {
+        self.orphan_policy = policy;
+        self
+    }
+
+    /// Enable run-directory creation, `run_info.json`, and metrics file writing.
     #[must_use]
     pub fn metrics(mut self, metrics_every: u64, runner_args: serde_json::Value) -> Self {
         self.enable_metrics = true;
@@ -1525,10 +2391,37 @@ impl RunSetupBuilder {
         self
     }
 
+    /// Which metrics files the run directory gets (CSV by default).
+    #[must_use]
+    pub fn metrics_format(mut self, format: MetricsFormat) -> Self {
+        self.metrics_format = format;
+        self
+    }
+
+    /// Attach experiment notebook metadata, written to `run_info.json`.
+    #[must_use]
+    pub fn experiment(mut self, experiment: ExperimentMeta) -> Self {
+        self.experiment = experiment;
+        self
+    }
+
     /// Consume the builder and produce a [`RunSetup`].
     pub fn build(self) -> Result<RunSetup> {
-        let (game_state, rng) =
-            load_or_build_state(&self.content, self.seed, self.state_file.as_deref())?;
+        let mut warnings = self.content_warnings;
+        let (game_state, rng, state_warnings) = match self.resume {
+            Some((saved, rng)) => {
+                let (state, orphans) =
+                    prepare_loaded_state(saved, &self.content, self.orphan_policy)?;
+                (state, rng, orphans)
+            }
+            None => load_or_build_state_with_warnings(
+                &self.content,
+                self.seed,
+                self.state_file.as_deref(),
+                self.orphan_policy,
+            )?,
+        };
+        warnings.extend(state_warnings);
 
         let (run_dir, metrics_writer) = if self.enable_metrics {
             let run_id = generate_run_id(game_state.meta.seed);
@@ -1540,11 +2433,9 @@ impl RunSetupBuilder {
                 &self.content.content_version,
                 self.metrics_every,
                 self.runner_args,
+                &self.experiment,
             )?;
-            let element_ids = sim_core::content_element_ids(&self.content);
-            let behavior_types = sim_core::content_behavior_types(&self.content);
-            let writer = MetricsFileWriter::new(dir.clone(), element_ids, behavior_types)
-                .with_context(|| format!("opening metrics CSV in {}", dir.display()))?;
+            let writer = MetricsWriter::new(&dir, self.metrics_format, &self.content)?;
             (Some(dir), Some(writer))
         } else {
             (None, None)
@@ -1556,6 +2447,7 @@ impl RunSetupBuilder {
             rng,
             run_dir,
             metrics_writer,
+            warnings,
         })
     }
 }
@@ -1572,31 +2464,102 @@ mod tests {
     };
     use std::collections::HashMap;
 
+    /// Asserts that validation failed with an error containing `expected`.
+    fn assert_reported(result: Result<Vec<String>, ValidationReport>, expected: &str) {
+        let report = result.expect_err("validation should fail");
+        assert!(
+            report.errors.iter().any(|error| error.contains(expected)),
+            "no error containing {expected:?} in {report}"
+        );
+    }
+
     #[test]
     fn test_valid_content_passes_validation() {
         let content = minimal_content();
-        validate_content(&content); // should not panic
+        validate_content(&content).unwrap();
+    }
+
+    #[test]
+    fn test_tech_prereq_unknown_is_reported() {
+        let mut content = minimal_content();
+        content.techs.push(TechDef {
+            id: TechId("tech_a".to_string()),
+            name: "A".to_string(),
+            tier: 1,
+            prereqs: vec![TechId("tech_nonexistent".to_string())],
+            domain_requirements: HashMap::new(),
+            accepted_data: vec![],
+            effects: vec![],
+        });
+        assert_reported(validate_content(&content), "is not a known tech id");
+    }
+
+    fn tech(id: &str, prereqs: &[&str], effects: Vec<sim_core::TechEffect>) -> TechDef {
+        TechDef {
+            id: TechId(id.to_string()),
+            name: id.to_string(),
+            tier: 1,
+            prereqs: prereqs.iter().map(|p| TechId((*p).to_string())).collect(),
+            domain_requirements: HashMap::new(),
+            accepted_data: vec![],
+            effects,
+        }
+    }
+
+    #[test]
+    fn test_starting_unlocked_without_prereq_is_reported() {
+        let mut content = minimal_content();
+        content.techs.push(tech("tech_a", &[], vec![]));
+        content.techs.push(tech("tech_b", &["tech_a"], vec![]));
+        content.starting_unlocked = vec![TechId("tech_b".to_string())];
+        assert_reported(
+            validate_content(&content),
+            "which is not also starting_unlocked",
+        );
+    }
+
+    #[test]
+    fn test_build_initial_state_grants_starting_techs() {
+        let mut content = base_content();
+        content.techs.push(tech(
+            "tech_power_boost",
+            &[],
+            vec![sim_core::TechEffect::StatModifier {
+                stat: sim_core::modifiers::StatId::PowerOutput,
+                op: sim_core::modifiers::ModifierOp::PctAdditive,
+                value: 0.1,
+            }],
+        ));
+        content.starting_unlocked = vec![TechId("tech_power_boost".to_string())];
+
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let state = build_initial_state(&content, 1, &mut rng);
+
+        assert!(state
+            .research
+            .unlocked
+            .contains(&TechId("tech_power_boost".to_string())));
+        let boosted = state
+            .modifiers
+            .resolve(sim_core::modifiers::StatId::PowerOutput, 100.0);
+        assert!((boosted - 110.0).abs() < 1e-9, "got {boosted}");
     }
 
-    #[test]
-    #[should_panic(expected = "is not a known tech id")]
-    fn test_tech_prereq_unknown_panics() {
-        let mut content = minimal_content();
-        content.techs.push(TechDef {
-            id: TechId("tech_a".to_string()),
-            name: "A".to_string(),
-            tier: 1,
-            prereqs: vec![TechId("tech_nonexistent".to_string())],
-            domain_requirements: HashMap::new(),
-            accepted_data: vec![],
-            effects: vec![],
-        });
-        validate_content(&content);
+    #[test]
+    fn test_build_initial_state_lends_the_starting_balance() {
+        let mut content = base_content();
+        content.constants.starting_loan = 2_500_000.0;
+
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let state = build_initial_state(&content, 1, &mut rng);
+
+        assert!((state.balance - 2_500_000.0).abs() < f64::EPSILON);
+        assert!((state.loan.outstanding - 2_500_000.0).abs() < f64::EPSILON);
+        assert_eq!(state.loan.missed_payments, 0);
     }
 
     #[test]
-    #[should_panic(expected = "unknown node")]
-    fn test_solar_system_edge_unknown_node_panics() {
+    fn test_solar_system_edge_unknown_node_is_reported() {
         let mut content = minimal_content();
         content.solar_system.nodes.push(NodeDef {
             id: NodeId("node_a".to_string()),
@@ -1607,12 +2570,86 @@ mod tests {
             NodeId("node_a".to_string()),
             NodeId("node_missing".to_string()),
         ));
-        validate_content(&content);
+        assert_reported(validate_content(&content), "unknown node");
+    }
+
+    #[test]
+    fn test_unreachable_solar_system_node_is_a_warning() {
+        let mut content = minimal_content();
+        for id in ["node_a", "node_b", "node_island"] {
+            content.solar_system.nodes.push(NodeDef {
+                id: NodeId(id.to_string()),
+                name: id.to_string(),
+                solar_intensity: 1.0,
+            });
+        }
+        content
+            .solar_system
+            .edges
+            .push((NodeId("node_b".to_string()), NodeId("node_a".to_string())));
+
+        let mut report = ValidationReport::default();
+        validate_solar_system(&content, &mut report);
+
+        assert!(!report.has_errors());
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].contains("'node_island' is unreachable"));
+    }
+
+    #[test]
+    fn test_tech_prereq_cycle_is_reported() {
+        let mut content = minimal_content();
+        content.techs.push(tech("tech_a", &["tech_c"], vec![]));
+        content.techs.push(tech("tech_b", &["tech_a"], vec![]));
+        content.techs.push(tech("tech_c", &["tech_b"], vec![]));
+        assert_reported(
+            validate_content(&content),
+            "tech prereq cycle: tech_a -> tech_c -> tech_b -> tech_a",
+        );
+    }
+
+    #[test]
+    fn test_techs_behind_a_cycle_or_unknown_prereq_are_reported_with_their_chain() {
+        let mut content = minimal_content();
+        content.techs.push(tech("tech_a", &["tech_b"], vec![]));
+        content.techs.push(tech("tech_b", &["tech_a"], vec![]));
+        content.techs.push(tech("tech_c", &["tech_a"], vec![]));
+        content.techs.push(tech("tech_d", &["tech_e"], vec![]));
+        content
+            .techs
+            .push(tech("tech_e", &["tech_missing"], vec![]));
+        content.techs.push(tech("tech_f", &[], vec![]));
+
+        let report = validate_content(&content).unwrap_err();
+
+        let expected = [
+            "tech 'tech_e' prereq 'tech_missing' is not a known tech id",
+            "tech prereq cycle: tech_a -> tech_b -> tech_a",
+            "tech 'tech_c' can never unlock: tech_c -> tech_a -> tech_b -> tech_a",
+            "tech 'tech_d' can never unlock: tech_d -> tech_e -> tech_missing (unknown)",
+        ];
+        for message in expected {
+            assert!(report.errors.iter().any(|e| e == message), "{report}");
+        }
+        assert_eq!(report.errors.len(), expected.len(), "{report}");
+    }
+
+    #[test]
+    fn test_validation_collects_every_error() {
+        let mut content = minimal_content();
+        content.elements.retain(|e| e.id != "ore" && e.id != "slag");
+        content
+            .techs
+            .push(tech("tech_a", &["tech_missing"], vec![]));
+
+        let report = validate_content(&content).unwrap_err();
+
+        assert_eq!(report.errors.len(), 3, "{report}");
+        assert!(report.to_string().starts_with("3 error(s)"));
     }
 
     #[test]
-    #[should_panic(expected = "not a known element")]
-    fn test_asteroid_template_unknown_element_panics() {
+    fn test_asteroid_template_unknown_element_is_reported() {
         let mut content = minimal_content();
         content.asteroid_templates.push(AsteroidTemplateDef {
             id: "tmpl_test".to_string(),
@@ -1620,12 +2657,23 @@ mod tests {
             composition_ranges: HashMap::from([("NoSuchElement".to_string(), (0.5_f32, 0.5_f32))]),
             preferred_class: None,
         });
-        validate_content(&content);
+        assert_reported(validate_content(&content), "not a known element");
+    }
+
+    #[test]
+    fn test_asteroid_template_unpriced_element_is_reported() {
+        let mut content = minimal_content();
+        content.asteroid_templates.push(AsteroidTemplateDef {
+            id: "tmpl_test".to_string(),
+            anomaly_tags: vec![],
+            composition_ranges: HashMap::from([("Fe".to_string(), (0.5_f32, 0.5_f32))]),
+            preferred_class: None,
+        });
+        assert_reported(validate_content(&content), "has no pricing entry");
     }
 
     #[test]
-    #[should_panic(expected = "not a known element")]
-    fn test_recipe_output_unknown_element_panics() {
+    fn test_recipe_output_unknown_element_is_reported() {
         let mut content = minimal_content();
         let recipe = RecipeDef {
             id: sim_core::RecipeId("recipe_test".to_string()),
@@ -1658,7 +2706,7 @@ mod tests {
                 }))
                 .build(),
         );
-        validate_content(&content);
+        assert_reported(validate_content(&content), "not a known element");
     }
 
     #[test]
@@ -1674,12 +2722,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_run_rng_is_independent_of_worldgen() {
+        let content = base_content();
+        let (state, mut run_rng) =
+            load_or_build_state(&content, Some(42), None, OrphanedModulePolicy::default()).unwrap();
+
+        let expected = build_initial_state(&content, 42, &mut worldgen_rng(42));
+        let site_ids = |s: &GameState| {
+            s.scan_sites
+                .iter()
+                .map(|x| x.id.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(site_ids(&state), site_ids(&expected));
+        // World generation must not consume draws from the run stream.
+        let mut fresh = ChaCha8Rng::seed_from_u64(42);
+        assert_eq!(run_rng.gen::<u64>(), fresh.gen::<u64>());
+    }
+
     #[test]
     fn test_load_content_missing_file() {
         let result = load_content("/tmp/nonexistent_dir_12345");
         assert!(result.is_err());
     }
 
+    #[test]
+    fn content_pack_overrides_one_tech_and_one_price() {
+        let pack = tempfile::tempdir().unwrap();
+        std::fs::write(
+            pack.path().join("techs.json"),
+            r#"{"techs": [{"id": "tech_deep_scan_v1", "tier": 3}]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            pack.path().join("pricing.toml"),
+            "[items.ore]\nbase_price_per_unit = 7.0\n",
+        )
+        .unwrap();
+        let base = load_content("../../content").unwrap();
+
+        let content =
+            load_content_packs(&["../../content", pack.path().to_str().unwrap()]).unwrap();
+
+        let tech = |content: &GameContent| {
+            content
+                .techs
+                .iter()
+                .find(|tech| tech.id.0 == "tech_deep_scan_v1")
+                .cloned()
+                .unwrap()
+        };
+        assert_eq!(tech(&content).tier, 3);
+        assert_eq!(tech(&content).name, tech(&base).name);
+        assert_eq!(content.techs.len(), base.techs.len());
+        let ore = &content.pricing.items["ore"];
+        assert!((ore.base_price_per_unit - 7.0).abs() < f64::EPSILON);
+        assert_eq!(ore.importable, base.pricing.items["ore"].importable);
+    }
+
     #[test]
     fn test_build_initial_state_has_ship_and_station() {
         let content = base_content();
@@ -1756,24 +2857,27 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "required element 'ore' is missing")]
-    fn test_missing_ore_element_panics() {
+    fn test_missing_ore_element_is_reported() {
         let mut content = minimal_content();
         content.elements.retain(|e| e.id != "ore");
-        validate_content(&content);
+        assert_reported(
+            validate_content(&content),
+            "required element 'ore' is missing",
+        );
     }
 
     #[test]
-    #[should_panic(expected = "required element 'slag' is missing")]
-    fn test_missing_slag_element_panics() {
+    fn test_missing_slag_element_is_reported() {
         let mut content = minimal_content();
         content.elements.retain(|e| e.id != "slag");
-        validate_content(&content);
+        assert_reported(
+            validate_content(&content),
+            "required element 'slag' is missing",
+        );
     }
 
     #[test]
-    #[should_panic(expected = "not a known element")]
-    fn test_assembler_recipe_unknown_element_panics() {
+    fn test_assembler_recipe_unknown_element_is_reported() {
         let mut content = minimal_content();
         let recipe = RecipeDef {
             id: sim_core::RecipeId("recipe_asm_test".to_string()),
@@ -1800,15 +2904,15 @@ mod tests {
                     assembly_interval_ticks: 10,
                     max_stock: std::collections::HashMap::new(),
                     recipes: vec![sim_core::RecipeId("recipe_asm_test".to_string())],
+                    parallel_slots: 1,
                 }))
                 .build(),
         );
-        validate_content(&content);
+        assert_reported(validate_content(&content), "not a known element");
     }
 
     #[test]
-    #[should_panic(expected = "duplicate body id")]
-    fn test_body_tree_duplicate_id_panics() {
+    fn test_body_tree_duplicate_id_is_reported() {
         let mut content = minimal_content();
         let body = sim_core::OrbitalBodyDef {
             id: sim_core::BodyId("dup".to_string()),
@@ -1819,15 +2923,15 @@ mod tests {
             angle_mdeg: 0,
             solar_intensity: 1.0,
             zone: None,
+            security: 1.0,
         };
         content.solar_system.bodies.push(body.clone());
         content.solar_system.bodies.push(body);
-        validate_content(&content);
+        assert_reported(validate_content(&content), "duplicate body id");
     }
 
     #[test]
-    #[should_panic(expected = "unknown parent")]
-    fn test_body_tree_unknown_parent_panics() {
+    fn test_body_tree_unknown_parent_is_reported() {
         let mut content = minimal_content();
         content.solar_system.bodies.push(sim_core::OrbitalBodyDef {
             id: sim_core::BodyId("orphan".to_string()),
@@ -1838,13 +2942,30 @@ mod tests {
             angle_mdeg: 0,
             solar_intensity: 1.0,
             zone: None,
+            security: 1.0,
+        });
+        assert_reported(validate_content(&content), "unknown parent");
+    }
+
+    #[test]
+    fn test_body_security_out_of_range_is_reported() {
+        let mut content = minimal_content();
+        content.solar_system.bodies.push(sim_core::OrbitalBodyDef {
+            id: sim_core::BodyId("lawless".to_string()),
+            name: "Lawless".to_string(),
+            parent: None,
+            body_type: sim_core::BodyType::Planet,
+            radius_au_um: 1_000_000,
+            angle_mdeg: 0,
+            solar_intensity: 1.0,
+            zone: None,
+            security: -0.5,
         });
-        validate_content(&content);
+        assert_reported(validate_content(&content), "security -0.5 must be in");
     }
 
     #[test]
-    #[should_panic(expected = "radius_max <= radius_min")]
-    fn test_body_tree_inverted_zone_radius_panics() {
+    fn test_body_tree_inverted_zone_radius_is_reported() {
         let mut content = minimal_content();
         content.solar_system.bodies.push(sim_core::OrbitalBodyDef {
             id: sim_core::BodyId("bad_zone".to_string()),
@@ -1863,13 +2984,13 @@ mod tests {
                 scan_site_weight: 1,
                 implicit_comm_tier: None,
             }),
+            security: 1.0,
         });
-        validate_content(&content);
+        assert_reported(validate_content(&content), "radius_max <= radius_min");
     }
 
     #[test]
-    #[should_panic(expected = "invalid angle_span")]
-    fn test_body_tree_oversized_angle_span_panics() {
+    fn test_body_tree_oversized_angle_span_is_reported() {
         let mut content = minimal_content();
         content.solar_system.bodies.push(sim_core::OrbitalBodyDef {
             id: sim_core::BodyId("wide_zone".to_string()),
@@ -1888,8 +3009,42 @@ mod tests {
                 scan_site_weight: 1,
                 implicit_comm_tier: None,
             }),
+            security: 1.0,
         });
-        validate_content(&content);
+        assert_reported(validate_content(&content), "invalid angle_span");
+    }
+
+    fn test_belt(body: &str) -> sim_core::BeltDef {
+        sim_core::BeltDef {
+            body: sim_core::BodyId(body.to_string()),
+            initial_budget: 5,
+            max_budget: 10,
+            respawn_interval_minutes: 60,
+            respawn_amount: 1,
+            max_sites: 3,
+            templates: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_belt_on_body_without_zone_is_reported() {
+        let mut content = minimal_content();
+        content.belts.push(test_belt("nowhere"));
+        assert_reported(validate_content(&content), "is not a zone body");
+    }
+
+    #[test]
+    fn test_belt_budget_and_template_errors_are_reported() {
+        let mut content = minimal_content();
+        let mut belt = test_belt("nowhere");
+        belt.initial_budget = 11;
+        belt.templates = vec!["tmpl_ghost".to_string()];
+        content.belts.push(belt.clone());
+        content.belts.push(belt);
+        let result = validate_content(&content);
+        assert_reported(result.clone(), "exceeds max_budget 10");
+        assert_reported(result.clone(), "unknown template 'tmpl_ghost'");
+        assert_reported(result, "duplicate belt for body 'nowhere'");
     }
 
     #[test]
@@ -2015,15 +3170,14 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "not a known element")]
-    fn test_state_with_unknown_material_element_panics() {
+    fn test_state_with_unknown_material_element_is_reported() {
         let content = minimal_content();
         let station_id = StationId("station_test".to_string());
         let state = GameState {
             meta: MetaState {
                 tick: 0,
                 seed: 42,
-                schema_version: 1,
+                schema_version: sim_core::CURRENT_SCHEMA_VERSION,
                 content_version: "test".to_string(),
             },
             scan_sites: vec![],
@@ -2049,12 +3203,18 @@ mod tests {
                         thermal_links: Vec::new(),
                         power: PowerState::default(),
                         cached_inventory_volume_m3: None,
-                        module_type_index: sim_core::ModuleTypeIndex::default(),
+                        module_type_index: sim_core::internals::ModuleTypeIndex::default(),
                         module_id_index: std::collections::HashMap::new(),
-                        power_budget_cache: sim_core::PowerBudgetCache::default(),
+                        power_budget_cache: sim_core::internals::PowerBudgetCache::default(),
+                        production_windows: std::collections::BTreeMap::new(),
+                        life_support_failing: false,
                     },
                     leaders: Vec::new(),
                     frame_id: None,
+                    owner: PrincipalId("principal_autopilot".to_string()),
+                    expansions: sim_core::StationExpansions::default(),
+                    power_policy: sim_core::PowerPolicy::default(),
+                    history: std::collections::VecDeque::new(),
                 },
             )]
             .into_iter()
@@ -2066,8 +3226,10 @@ mod tests {
                 data_pool: AHashMap::default(),
                 evidence: AHashMap::default(),
                 action_counts: AHashMap::default(),
+                queue: Vec::new(),
             },
             balance: 0.0,
+            loan: sim_core::LoanState::default(),
             export_revenue_total: 0.0,
             export_count: 0,
             counters: Counters {
@@ -2085,9 +3247,22 @@ mod tests {
             transfer_count: 0,
             progression: sim_core::ProgressionState::default(),
             strategy_config: Default::default(),
+            import_orders: Vec::new(),
+            freight_orders: Vec::new(),
+            freight_routes: Vec::new(),
+            market: sim_core::MarketState::default(),
+            contracts: Vec::new(),
             body_cache: AHashMap::default(),
+            jettisoned_slag_kg: std::collections::BTreeMap::new(),
+            solar_storms: std::collections::BTreeMap::new(),
+            claims: std::collections::BTreeMap::new(),
+            hotspots: sim_core::internals::HotspotWindow::default(),
+            projects: Vec::new(),
+            station_builds: Vec::new(),
+            belts: std::collections::BTreeMap::new(),
+            pending_commands: Vec::new(),
         };
-        validate_state(&state, &content);
+        assert_reported(validate_state(&state, &content), "not a known element");
     }
 
     #[test]
@@ -2104,7 +3279,12 @@ mod tests {
         let json = serde_json::to_string_pretty(&state).unwrap();
         std::fs::write(&path, &json).unwrap();
 
-        let result = load_or_build_state(&content, None, Some(path.to_str().unwrap()));
+        let result = load_or_build_state(
+            &content,
+            None,
+            Some(path.to_str().unwrap()),
+            OrphanedModulePolicy::Error,
+        );
         assert!(
             result.is_ok(),
             "current schema version should load: {result:?}"
@@ -2125,9 +3305,14 @@ mod tests {
         let json = serde_json::to_string_pretty(&state).unwrap();
         std::fs::write(&path, &json).unwrap();
 
-        let result = load_or_build_state(&content, None, Some(path.to_str().unwrap()));
+        let result = load_or_build_state(
+            &content,
+            None,
+            Some(path.to_str().unwrap()),
+            OrphanedModulePolicy::Error,
+        );
         assert!(result.is_err(), "mismatched schema version should error");
-        let err_msg = result.unwrap_err().to_string();
+        let err_msg = format!("{:#}", result.unwrap_err());
         assert!(
             err_msg.contains("schema version 999"),
             "error should mention actual version: {err_msg}"
@@ -2135,6 +3320,112 @@ mod tests {
         std::fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn older_schema_version_is_migrated_on_load() {
+        let content = base_content();
+        let state = sim_core::test_fixtures::base_state(&content);
+        let mut value = serde_json::to_value(&state).unwrap();
+        value["meta"]["schema_version"] = serde_json::json!(1);
+
+        let (loaded, warnings) = parse_state_with_warnings(&value.to_string()).unwrap();
+        assert_eq!(loaded.meta.schema_version, sim_core::CURRENT_SCHEMA_VERSION);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("schema version 1"), "{warnings:?}");
+    }
+
+    fn state_with_orphan(content: &GameContent) -> GameState {
+        let mut state = sim_core::test_fixtures::base_state(content);
+        let station = state
+            .stations
+            .get_mut(&sim_core::test_fixtures::test_station_id())
+            .unwrap();
+        station
+            .core
+            .modules
+            .push(sim_core::test_fixtures::test_module(
+                "module_retired",
+                sim_core::ModuleKindState::Storage,
+            ));
+        state
+    }
+
+    #[test]
+    fn orphaned_modules_error_policy_rejects_save() {
+        let content = base_content();
+        let mut state = state_with_orphan(&content);
+        let err = resolve_orphaned_modules(&mut state, &content, OrphanedModulePolicy::Error)
+            .unwrap_err();
+        assert!(err.to_string().contains("module_retired"), "{err}");
+    }
+
+    #[test]
+    fn orphaned_modules_scrap_policy_removes_and_refunds() {
+        let mut content = base_content();
+        content.pricing.items.insert(
+            "module_retired".to_string(),
+            sim_core::PricingEntry {
+                base_price_per_unit: 1_000.0,
+                importable: false,
+                exportable: false,
+                category: "module".to_string(),
+                royalty_per_unit: 0.0,
+                elasticity: 0.0,
+            },
+        );
+        let mut state = state_with_orphan(&content);
+        let modules_before = state.stations.values().next().unwrap().core.modules.len();
+
+        let orphans =
+            resolve_orphaned_modules(&mut state, &content, OrphanedModulePolicy::Scrap).unwrap();
+
+        assert_eq!(orphans.len(), 1);
+        assert!((orphans[0].refund - 1_000.0).abs() < f64::EPSILON);
+        assert!((state.balance - 1_000.0).abs() < f64::EPSILON);
+        let station = state.stations.values().next().unwrap();
+        assert_eq!(station.core.modules.len(), modules_before - 1);
+        assert!(find_orphaned_modules(&state, &content).is_empty());
+    }
+
+    #[test]
+    fn orphaned_modules_quarantine_policy_disables_in_place() {
+        let content = base_content();
+        let mut state = state_with_orphan(&content);
+
+        let orphans =
+            resolve_orphaned_modules(&mut state, &content, OrphanedModulePolicy::Quarantine)
+                .unwrap();
+
+        assert_eq!(orphans.len(), 1);
+        let station = state.stations.values().next().unwrap();
+        let module = station
+            .core
+            .modules
+            .iter()
+            .find(|m| m.def_id == "module_retired")
+            .expect("quarantined module is kept");
+        assert!(!module.enabled);
+        assert!(state.balance.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn orphaned_modules_are_returned_as_run_setup_warnings() {
+        let content = base_content();
+        let state = state_with_orphan(&content);
+        let setup = RunSetupBuilder::from_content(content)
+            .resume_from(state, ChaCha8Rng::seed_from_u64(1))
+            .orphan_policy(OrphanedModulePolicy::Quarantine)
+            .build()
+            .unwrap();
+
+        assert_eq!(setup.warnings.len(), 1, "{:?}", setup.warnings);
+        assert!(
+            setup.warnings[0].contains("module_retired")
+                && setup.warnings[0].contains("quarantined"),
+            "{:?}",
+            setup.warnings
+        );
+    }
+
     #[test]
     fn run_setup_builder_without_metrics() {
         let setup = RunSetupBuilder::from_content_dir("../../content")
@@ -2180,6 +3471,27 @@ mod tests {
         assert!(setup.run_dir.is_none());
     }
 
+    #[test]
+    fn run_setup_builder_resumes_state_and_rng() {
+        use rand::Rng;
+        let content = load_content("../../content").unwrap();
+        let mut first = RunSetupBuilder::from_content(content.clone())
+            .seed(Some(7))
+            .build()
+            .unwrap();
+        first.game_state.meta.tick = 30;
+        let _: u64 = first.rng.gen();
+
+        let mut resumed = RunSetupBuilder::from_content(content)
+            .seed(Some(8))
+            .resume_from(first.game_state, first.rng.clone())
+            .build()
+            .unwrap();
+        assert_eq!(resumed.game_state.meta.tick, 30);
+        assert_eq!(resumed.game_state.meta.seed, 7);
+        assert_eq!(resumed.rng.gen::<u64>(), first.rng.gen::<u64>());
+    }
+
     /// Extract sorted module def IDs from a station's inventory.
     fn station_module_ids(station: &StationState) -> Vec<String> {
         let mut modules: Vec<String> = station
@@ -2241,7 +3553,7 @@ mod tests {
     #[test]
     fn events_json_loads_and_validates() {
         let content = load_content("../../content").unwrap();
-        assert_eq!(content.events.len(), 6, "Expected 6 event definitions");
+        assert_eq!(content.events.len(), 7, "Expected 7 event definitions");
 
         // Verify all event IDs are present
         let ids: Vec<&str> = content.events.iter().map(|e| e.id.0.as_str()).collect();
@@ -2251,6 +3563,7 @@ mod tests {
         assert!(ids.contains(&"evt_solar_flare"));
         assert!(ids.contains(&"evt_micrometeorite"));
         assert!(ids.contains(&"evt_supply_cache"));
+        assert!(ids.contains(&"evt_pirate_raid"));
 
         // Verify weights were resolved
         for event in &content.events {
@@ -2284,7 +3597,7 @@ mod tests {
             }
         ]"#;
         std::fs::write(dir.path().join("hull_defs.json"), hull_json).unwrap();
-        let hulls = load_hull_defs(dir.path()).unwrap();
+        let hulls = load_hull_defs(&[dir.path()], &mut ValidationReport::default()).unwrap();
         assert_eq!(hulls.len(), 1);
         let hull = &hulls[&sim_core::HullId("hull_test".to_string())];
         assert_eq!(hull.name, "Test Hull");
@@ -2297,8 +3610,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "duplicate hull ID")]
-    fn test_load_hull_defs_duplicate_panics() {
+    fn test_load_hull_defs_duplicate_is_reported() {
         let dir = tempfile::tempdir().unwrap();
         let hull_json = r#"[
             {
@@ -2321,17 +3633,102 @@ mod tests {
             }
         ]"#;
         std::fs::write(dir.path().join("hull_defs.json"), hull_json).unwrap();
-        let _ = load_hull_defs(dir.path()).unwrap();
+        let mut report = ValidationReport::default();
+        load_hull_defs(&[dir.path()], &mut report).unwrap();
+        assert_eq!(
+            report.errors,
+            vec!["duplicate hull ID: hull_dup".to_string()]
+        );
     }
 
     #[test]
     fn test_load_hull_defs_missing_file_returns_empty() {
         let dir = tempfile::tempdir().unwrap();
         // No hull_defs.json written
-        let hulls = load_hull_defs(dir.path()).unwrap();
+        let hulls = load_hull_defs(&[dir.path()], &mut ValidationReport::default()).unwrap();
         assert!(hulls.is_empty());
     }
 
+    #[test]
+    fn test_duplicate_ship_class_fails_the_load_with_a_report_error() {
+        let dir = tempfile::tempdir().unwrap();
+        for entry in std::fs::read_dir("../../content").unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_file() {
+                std::fs::copy(&path, dir.path().join(path.file_name().unwrap())).unwrap();
+            }
+        }
+        let classes_path = dir.path().join("ship_classes.json");
+        let mut classes: Vec<serde_json::Value> =
+            serde_json::from_str(&std::fs::read_to_string(&classes_path).unwrap()).unwrap();
+        classes.push(classes[0].clone());
+        std::fs::write(&classes_path, serde_json::to_string(&classes).unwrap()).unwrap();
+        let dir = dir.path().to_str().unwrap();
+
+        let (_, report) = load_content_packs_unvalidated(&[dir]).unwrap();
+        assert_eq!(
+            report.errors,
+            vec!["duplicate ship class ID: class_prospector".to_string()]
+        );
+        let err = load_content_packs(&[dir]).unwrap_err();
+        assert!(format!("{err:#}").contains("duplicate ship class ID"));
+    }
+
+    #[test]
+    fn test_into_result_hands_back_warnings() {
+        let mut report = ValidationReport::default();
+        report.warning("suspicious");
+        assert_eq!(
+            report.clone().into_result(),
+            Ok(vec!["suspicious".to_string()])
+        );
+        report.error("broken");
+        assert!(report.into_result().is_err());
+    }
+
+    #[test]
+    fn test_content_authored_in_toml_and_yaml_loads_identically() {
+        let dir = tempfile::tempdir().unwrap();
+        for entry in std::fs::read_dir("../../content").unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_file() {
+                std::fs::copy(&path, dir.path().join(path.file_name().unwrap())).unwrap();
+            }
+        }
+        let read_json = |name: &str| -> serde_json::Value {
+            let path = dir.path().join(name);
+            let value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+            std::fs::remove_file(path).unwrap();
+            value
+        };
+        let constants = read_json("constants.json");
+        std::fs::write(
+            dir.path().join("constants.toml"),
+            toml::to_string(&constants).unwrap(),
+        )
+        .unwrap();
+        let recipes = serde_json::json!({ "items": read_json("recipes.json") });
+        std::fs::write(
+            dir.path().join("recipes.toml"),
+            toml::to_string(&recipes).unwrap(),
+        )
+        .unwrap();
+        let techs = read_json("techs.json");
+        std::fs::write(
+            dir.path().join("techs.yaml"),
+            serde_yaml::to_string(&techs).unwrap(),
+        )
+        .unwrap();
+
+        let converted = load_content(dir.path().to_str().unwrap()).unwrap();
+        let original = load_content("../../content").unwrap();
+
+        assert_eq!(
+            content_hash(&converted).unwrap(),
+            content_hash(&original).unwrap()
+        );
+    }
+
     #[test]
     fn test_load_frame_defs_roundtrip() {
         let dir = tempfile::tempdir().unwrap();
@@ -2356,7 +3753,7 @@ mod tests {
             }
         ]"#;
         std::fs::write(dir.path().join("frame_defs.json"), frame_json).unwrap();
-        let frames = load_frame_defs(dir.path()).unwrap();
+        let frames = load_frame_defs(&[dir.path()], &mut ValidationReport::default()).unwrap();
         assert_eq!(frames.len(), 1);
         let frame = &frames[&sim_core::FrameId("frame_test".to_string())];
         assert_eq!(frame.name, "Test Frame");
@@ -2366,8 +3763,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "duplicate frame ID")]
-    fn test_load_frame_defs_duplicate_panics() {
+    fn test_load_frame_defs_duplicate_is_reported() {
         let dir = tempfile::tempdir().unwrap();
         let frame_json = r#"[
             {
@@ -2386,14 +3782,19 @@ mod tests {
             }
         ]"#;
         std::fs::write(dir.path().join("frame_defs.json"), frame_json).unwrap();
-        let _ = load_frame_defs(dir.path()).unwrap();
+        let mut report = ValidationReport::default();
+        load_frame_defs(&[dir.path()], &mut report).unwrap();
+        assert_eq!(
+            report.errors,
+            vec!["duplicate frame ID: frame_dup".to_string()]
+        );
     }
 
     #[test]
     fn test_load_frame_defs_missing_file_returns_empty() {
         let dir = tempfile::tempdir().unwrap();
         // No frame_defs.json written — graceful fallback to empty.
-        let frames = load_frame_defs(dir.path()).unwrap();
+        let frames = load_frame_defs(&[dir.path()], &mut ValidationReport::default()).unwrap();
         assert!(frames.is_empty());
     }
 
@@ -2443,10 +3844,12 @@ mod tests {
     }
 
     #[test]
-    fn test_validate_hull_defs_no_panic_on_empty() {
+    fn test_validate_hull_defs_accepts_empty() {
         let content = base_content();
-        // Should not panic — empty hulls is valid
-        validate_hull_defs(&content);
+        // Empty hulls is valid
+        let mut report = ValidationReport::default();
+        validate_hull_defs(&content, &mut report);
+        assert!(!report.has_errors());
     }
 
     #[test]
@@ -2503,8 +3906,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "references unknown module")]
-    fn test_fitting_template_bad_module_panics() {
+    fn test_fitting_template_bad_module_is_reported() {
         let mut content = base_content();
         content.hulls.insert(
             sim_core::HullId("hull_test".to_string()),
@@ -2518,10 +3920,13 @@ mod tests {
                 slots: vec![sim_core::SlotDef {
                     slot_type: sim_core::SlotType("utility".to_string()),
                     label: "Utility 1".to_string(),
+                    adjacent: Vec::new(),
                 }],
                 bonuses: vec![],
                 required_tech: None,
                 tags: vec![],
+                hold_partitions: std::collections::BTreeMap::new(),
+                replacement_value: 0.0,
             },
         );
         content.fitting_templates.insert(
@@ -2531,12 +3936,141 @@ mod tests {
                 module_def_id: sim_core::ModuleDefId("nonexistent_module".to_string()),
             }],
         );
-        validate_content(&content);
+        assert_reported(validate_content(&content), "references unknown module");
+    }
+
+    fn upgrade(module_def_id: &str) -> sim_core::ModuleUpgradeDef {
+        sim_core::ModuleUpgradeDef {
+            module_def_id: module_def_id.to_string(),
+            materials: std::collections::BTreeMap::new(),
+            components: std::collections::BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_upgrade_to_unknown_module_is_reported() {
+        let mut content = base_content();
+        content.module_defs.insert(
+            "mod_mk1".to_string(),
+            ModuleDefBuilder::new("mod_mk1")
+                .upgrade_to(upgrade("mod_mk2"))
+                .build(),
+        );
+        assert_reported(
+            validate_content(&content),
+            "upgrades to unknown module 'mod_mk2'",
+        );
+    }
+
+    #[test]
+    fn test_looping_upgrade_chain_is_reported() {
+        let mut content = base_content();
+        for (id, next) in [("mod_mk1", "mod_mk2"), ("mod_mk2", "mod_mk1")] {
+            content.module_defs.insert(
+                id.to_string(),
+                ModuleDefBuilder::new(id).upgrade_to(upgrade(next)).build(),
+            );
+        }
+        assert_reported(validate_content(&content), "upgrade chain loops");
+    }
+
+    #[test]
+    fn test_habitat_with_unknown_consumable_is_reported() {
+        let mut content = base_content();
+        content.module_defs.insert(
+            "module_habitat".to_string(),
+            ModuleDefBuilder::new("module_habitat")
+                .behavior(ModuleBehaviorDef::Habitat(sim_core::HabitatDef {
+                    crew_capacity: 4,
+                    consumables_kg_per_crew_hour: [("Food".to_string(), 0.1)].into(),
+                }))
+                .build(),
+        );
+        assert_reported(
+            validate_content(&content),
+            "habitat 'module_habitat' consumable 'Food' is not a known element",
+        );
+    }
+
+    #[test]
+    fn test_drone_bay_without_yield_is_reported() {
+        let mut content = base_content();
+        content.module_defs.insert(
+            "module_drone_bay".to_string(),
+            ModuleDefBuilder::new("module_drone_bay")
+                .behavior(ModuleBehaviorDef::DroneBay(sim_core::DroneBayDef {
+                    mining_interval_minutes: 60,
+                    mining_interval_ticks: 1,
+                    range_hops: 1,
+                    kg_per_run: 0.0,
+                }))
+                .build(),
+        );
+        assert_reported(
+            validate_content(&content),
+            "drone bay 'module_drone_bay' has non-positive kg_per_run 0",
+        );
+    }
+
+    #[test]
+    fn test_hold_partitions_over_capacity_are_reported() {
+        let mut content = base_content();
+        content.hulls.insert(
+            sim_core::HullId("hull_test".to_string()),
+            sim_core::HullDef {
+                id: sim_core::HullId("hull_test".to_string()),
+                name: "Test".to_string(),
+                mass_kg: 1000.0,
+                cargo_capacity_m3: 10.0,
+                base_speed_ticks_per_au: 100,
+                base_propellant_capacity_kg: 5000.0,
+                slots: vec![],
+                bonuses: vec![],
+                required_tech: None,
+                tags: vec![],
+                hold_partitions: [
+                    (sim_core::HoldPartition::OreBay, 8.0),
+                    (sim_core::HoldPartition::ComponentLocker, 4.0),
+                ]
+                .into(),
+                replacement_value: 0.0,
+            },
+        );
+        assert_reported(validate_content(&content), "reserves 12 m3");
+    }
+
+    #[test]
+    fn test_ship_class_with_zero_speed_is_reported() {
+        let mut content = base_content();
+        content.ship_classes.insert(
+            "class_test".to_string(),
+            sim_core::ShipClassDef {
+                id: "class_test".to_string(),
+                name: "Test".to_string(),
+                cargo_capacity_m3: None,
+                propellant_capacity_kg: None,
+                speed_multiplier: 0.0,
+                mining_rate_multiplier: 1.0,
+            },
+        );
+        assert_reported(
+            validate_content(&content),
+            "ship class 'class_test' speed_multiplier must be positive, got 0",
+        );
+    }
+
+    #[test]
+    fn test_negative_loan_repayment_is_reported() {
+        let mut content = base_content();
+        content.constants.loan_repayment_amount = -5.0;
+        assert_reported(
+            validate_content(&content),
+            "loan_repayment_amount must be finite and >= 0",
+        );
     }
 
     #[test]
-    #[should_panic(expected = "slot_index")]
-    fn test_fitting_template_bad_slot_index_panics() {
+    fn test_fitting_template_bad_slot_index_is_reported() {
         let mut content = base_content();
         content.hulls.insert(
             sim_core::HullId("hull_test".to_string()),
@@ -2550,10 +4084,13 @@ mod tests {
                 slots: vec![sim_core::SlotDef {
                     slot_type: sim_core::SlotType("utility".to_string()),
                     label: "Utility 1".to_string(),
+                    adjacent: Vec::new(),
                 }],
                 bonuses: vec![],
                 required_tech: None,
                 tags: vec![],
+                hold_partitions: std::collections::BTreeMap::new(),
+                replacement_value: 0.0,
             },
         );
         // Add a valid module def
@@ -2574,7 +4111,7 @@ mod tests {
                 module_def_id: sim_core::ModuleDefId("mod_valid".to_string()),
             }],
         );
-        validate_content(&content);
+        assert_reported(validate_content(&content), "slot_index");
     }
 
     #[test]
@@ -2590,8 +4127,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "invalid scoring config")]
-    fn scoring_config_bad_weights_panics() {
+    fn scoring_config_bad_weights_is_reported() {
         let mut content = minimal_content();
         content.scoring = sim_core::ScoringConfig {
             dimensions: vec![
@@ -2617,7 +4153,7 @@ mod tests {
             computation_interval_ticks: 24,
             scale_factor: 2500.0,
         };
-        validate_content(&content);
+        assert_reported(validate_content(&content), "invalid scoring config");
     }
 
     /// Helper: build minimal content with one satellite def of the given type.
@@ -2639,17 +4175,18 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "autopilot.comm_satellite_type 'communication' not found")]
-    fn validate_satellite_refs_missing_comm_type_panics() {
+    fn validate_satellite_refs_missing_comm_type_is_reported() {
         // Only a "survey" satellite def exists, but autopilot defaults to
         // comm_satellite_type = "communication" → should panic.
         let content = minimal_content_with_satellite("survey");
-        validate_content(&content);
+        assert_reported(
+            validate_content(&content),
+            "autopilot.comm_satellite_type 'communication' not found",
+        );
     }
 
     #[test]
-    #[should_panic(expected = "references unknown satellite type 'bogus'")]
-    fn validate_scoring_signal_unknown_satellite_type_panics() {
+    fn validate_scoring_signal_unknown_satellite_type_is_reported() {
         let mut content = minimal_content_with_satellite("communication");
         // Add a nav satellite so comm/nav validation passes.
         content.satellite_defs.insert(
@@ -2688,12 +4225,14 @@ mod tests {
             computation_interval_ticks: 24,
             scale_factor: 2500.0,
         };
-        validate_content(&content);
+        assert_reported(
+            validate_content(&content),
+            "references unknown satellite type 'bogus'",
+        );
     }
 
     #[test]
-    #[should_panic(expected = "references unknown satellite type 'bogus'")]
-    fn validate_milestone_counter_unknown_satellite_type_panics() {
+    fn validate_milestone_counter_unknown_satellite_type_is_reported() {
         let mut content = minimal_content_with_satellite("communication");
         content.satellite_defs.insert(
             "sat_nav_test".to_string(),
@@ -2725,7 +4264,10 @@ mod tests {
             },
             phase_advance: None,
         }];
-        validate_content(&content);
+        assert_reported(
+            validate_content(&content),
+            "references unknown satellite type 'bogus'",
+        );
     }
 
     #[test]
@@ -2813,11 +4355,10 @@ mod tests {
                 phase_advance: None,
             },
         ];
-        validate_milestones(&milestones, &test_behavior_types()); // should not panic
+        milestone_report(&milestones, &test_behavior_types()).unwrap();
     }
 
     #[test]
-    #[should_panic(expected = "duplicate milestone id")]
     fn validate_milestones_rejects_duplicate_ids() {
         let milestones = vec![
             sim_core::MilestoneDef {
@@ -2849,11 +4390,13 @@ mod tests {
                 phase_advance: None,
             },
         ];
-        validate_milestones(&milestones, &test_behavior_types());
+        assert_reported(
+            milestone_report(&milestones, &test_behavior_types()),
+            "duplicate milestone id",
+        );
     }
 
     #[test]
-    #[should_panic(expected = "unknown prerequisite milestone")]
     fn validate_milestones_rejects_unknown_prereq() {
         let milestones = vec![sim_core::MilestoneDef {
             id: "m1".to_string(),
@@ -2871,7 +4414,19 @@ mod tests {
             },
             phase_advance: None,
         }];
-        validate_milestones(&milestones, &test_behavior_types());
+        assert_reported(
+            milestone_report(&milestones, &test_behavior_types()),
+            "unknown prerequisite milestone",
+        );
+    }
+
+    fn milestone_report(
+        milestones: &[sim_core::MilestoneDef],
+        module_behavior_types: &HashSet<&'static str>,
+    ) -> Result<Vec<String>, ValidationReport> {
+        let mut report = ValidationReport::default();
+        validate_milestones(milestones, module_behavior_types, &mut report);
+        report.into_result()
     }
 
     /// Test helper: set of module behavior type names that exercise the
@@ -2909,7 +4464,6 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "unknown counter 'satelites_deployed'")]
     fn validate_milestones_rejects_counter_typo() {
         // Intentional typo: "satelites" with one L
         let milestones = vec![milestone_with_condition(
@@ -2919,11 +4473,13 @@ mod tests {
                 threshold: 1.0,
             },
         )];
-        validate_milestones(&milestones, &test_behavior_types());
+        assert_reported(
+            milestone_report(&milestones, &test_behavior_types()),
+            "unknown counter 'satelites_deployed'",
+        );
     }
 
     #[test]
-    #[should_panic(expected = "unknown metric field 'total_or_kg'")]
     fn validate_milestones_rejects_metric_field_typo() {
         let milestones = vec![milestone_with_condition(
             "typo_milestone",
@@ -2932,11 +4488,13 @@ mod tests {
                 threshold: 100.0,
             },
         )];
-        validate_milestones(&milestones, &test_behavior_types());
+        assert_reported(
+            milestone_report(&milestones, &test_behavior_types()),
+            "unknown metric field 'total_or_kg'",
+        );
     }
 
     #[test]
-    #[should_panic(expected = "unknown metric field 'sateelites_active'")]
     fn validate_milestones_rejects_metric_field_typo_ending_in_active() {
         // Regression guard: typos ending in _active/_stalled/_starved must
         // NOT be silently accepted as per-module metric fields. The real
@@ -2949,7 +4507,10 @@ mod tests {
                 threshold: 1.0,
             },
         )];
-        validate_milestones(&milestones, &test_behavior_types());
+        assert_reported(
+            milestone_report(&milestones, &test_behavior_types()),
+            "unknown metric field 'sateelites_active'",
+        );
     }
 
     #[test]
@@ -2986,7 +4547,7 @@ mod tests {
                 },
             ),
         ];
-        validate_milestones(&milestones, &test_behavior_types()); // should not panic
+        milestone_report(&milestones, &test_behavior_types()).unwrap();
     }
 
     #[test]
@@ -0,0 +1,236 @@
+//! Content lints: defs that load fine but can never matter in play.
+//!
+//! [`validate_content`](crate::validate_content) rejects broken content;
+//! these checks find content that is consistent but dead, so they only
+//! warn. `sim_cli validate` runs both.
+
+use std::collections::HashSet;
+
+use sim_core::{GameContent, ModuleBehaviorDef, OutputSpec, ResearchDomain, YieldFormula};
+
+use crate::ValidationReport;
+
+/// Warnings for modules nothing provides, recipes that yield nothing and
+/// techs whose research no obtainable lab produces.
+pub fn lint_content(content: &GameContent) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let obtainable = obtainable_modules(content);
+    lint_unobtainable_modules(content, &obtainable, &mut report);
+    lint_zero_yield_recipes(content, &mut report);
+    lint_unresearchable_techs(content, &obtainable, &mut report);
+    report
+}
+
+/// Module defs a world can come to hold: starting station modules,
+/// importable modules, fitting-template modules and upgrade targets of any
+/// of those.
+fn obtainable_modules(content: &GameContent) -> HashSet<&str> {
+    let starting = if content.initial_stations.is_empty() {
+        content.initial_station.modules.iter().collect::<Vec<_>>()
+    } else {
+        content
+            .initial_stations
+            .iter()
+            .flat_map(|station| &station.initial.modules)
+            .collect()
+    };
+    let mut obtainable: HashSet<&str> = starting
+        .into_iter()
+        .map(String::as_str)
+        .chain(
+            content
+                .module_defs
+                .keys()
+                .filter(|id| {
+                    content
+                        .pricing
+                        .items
+                        .get(id.as_str())
+                        .is_some_and(|entry| entry.importable)
+                })
+                .map(String::as_str),
+        )
+        .chain(
+            content
+                .fitting_templates
+                .values()
+                .flatten()
+                .map(|fitted| fitted.module_def_id.0.as_str()),
+        )
+        .collect();
+    let mut frontier: Vec<&str> = obtainable.iter().copied().collect();
+    while let Some(id) = frontier.pop() {
+        let Some(upgrade) = content
+            .module_defs
+            .get(id)
+            .and_then(|m| m.upgrade_to.as_ref())
+        else {
+            continue;
+        };
+        if obtainable.insert(upgrade.module_def_id.as_str()) {
+            frontier.push(upgrade.module_def_id.as_str());
+        }
+    }
+    obtainable
+}
+
+fn lint_unobtainable_modules(
+    content: &GameContent,
+    obtainable: &HashSet<&str>,
+    report: &mut ValidationReport,
+) {
+    let mut ids: Vec<&String> = content.module_defs.keys().collect();
+    ids.sort();
+    for id in ids {
+        if !obtainable.contains(id.as_str()) {
+            report.warning(format!(
+                "module '{id}' is never producible: not in a starting station, \
+                 not importable, not fitted by a hull template and not an upgrade target"
+            ));
+        }
+    }
+}
+
+fn lint_zero_yield_recipes(content: &GameContent, report: &mut ValidationReport) {
+    for recipe in content.recipes.values() {
+        let zero = |output: &OutputSpec| match output {
+            OutputSpec::Material { yield_formula, .. } | OutputSpec::Slag { yield_formula } => {
+                matches!(yield_formula, YieldFormula::FixedFraction(fraction) if *fraction <= 0.0)
+            }
+            OutputSpec::Component { .. } | OutputSpec::Ship { .. } => false,
+        };
+        let reason = if recipe.outputs.is_empty() {
+            Some("it has no outputs".to_string())
+        } else if recipe.efficiency <= 0.0 {
+            Some(format!("its efficiency is {}", recipe.efficiency))
+        } else if recipe.outputs.iter().all(zero) {
+            Some("every output has a fixed yield of 0".to_string())
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            report.warning(format!("recipe '{}' yields nothing: {reason}", recipe.id.0));
+        }
+    }
+}
+
+/// Research points only come from labs, so a tech that needs points in a
+/// domain no obtainable lab researches stays locked unless it starts
+/// unlocked.
+fn lint_unresearchable_techs(
+    content: &GameContent,
+    obtainable: &HashSet<&str>,
+    report: &mut ValidationReport,
+) {
+    let researched: HashSet<&ResearchDomain> = content
+        .module_defs
+        .values()
+        .filter(|module| obtainable.contains(module.id.as_str()))
+        .filter_map(|module| match &module.behavior {
+            ModuleBehaviorDef::Lab(lab) if lab.research_points_per_run > 0.0 => Some(&lab.domain),
+            _ => None,
+        })
+        .collect();
+    for tech in &content.techs {
+        if content.starting_unlocked.contains(&tech.id) {
+            continue;
+        }
+        let mut missing: Vec<&str> = tech
+            .domain_requirements
+            .iter()
+            .filter(|(domain, points)| **points > 0.0 && !researched.contains(domain))
+            .map(|(domain, _)| domain.0.as_str())
+            .collect();
+        if missing.is_empty() {
+            continue;
+        }
+        missing.sort_unstable();
+        report.warning(format!(
+            "tech '{}' is unreachable: no obtainable lab researches {}",
+            tech.id.0,
+            missing.join(", ")
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim_core::test_fixtures::{
+        base_content, insert_recipe, test_iron_recipe, ModuleDefBuilder,
+    };
+    use sim_core::{LabDef, PricingEntry, TechDef, TechId};
+
+    #[test]
+    fn lints_flag_dead_modules_recipes_and_techs() {
+        let mut content = base_content();
+        content.module_defs.clear();
+        content.initial_station.modules = vec!["module_lab".to_string()];
+        content.module_defs.insert(
+            "module_lab".to_string(),
+            ModuleDefBuilder::new("module_lab")
+                .behavior(ModuleBehaviorDef::Lab(LabDef {
+                    domain: ResearchDomain("survey".to_string()),
+                    data_consumption_per_run: 1.0,
+                    research_points_per_run: 1.0,
+                    accepted_data: vec![],
+                    research_interval_minutes: 1,
+                    research_interval_ticks: 1,
+                }))
+                .build(),
+        );
+        content.module_defs.insert(
+            "module_imported".to_string(),
+            ModuleDefBuilder::new("module_imported").build(),
+        );
+        content.pricing.items.insert(
+            "module_imported".to_string(),
+            PricingEntry {
+                base_price_per_unit: 1.0,
+                importable: true,
+                exportable: false,
+                category: "module".to_string(),
+                royalty_per_unit: 0.0,
+                elasticity: 0.0,
+            },
+        );
+        content.module_defs.insert(
+            "module_orphan".to_string(),
+            ModuleDefBuilder::new("module_orphan").build(),
+        );
+        let mut recipe = test_iron_recipe();
+        recipe.efficiency = 0.0;
+        let recipe_id = insert_recipe(&mut content, recipe);
+        content.techs = vec![
+            TechDef {
+                id: TechId("tech_survey".to_string()),
+                name: "Survey".to_string(),
+                tier: 1,
+                prereqs: vec![],
+                domain_requirements: [(ResearchDomain("survey".to_string()), 10.0)].into(),
+                accepted_data: vec![],
+                effects: vec![],
+            },
+            TechDef {
+                id: TechId("tech_physics".to_string()),
+                name: "Physics".to_string(),
+                tier: 1,
+                prereqs: vec![],
+                domain_requirements: [(ResearchDomain("physics".to_string()), 10.0)].into(),
+                accepted_data: vec![],
+                effects: vec![],
+            },
+        ];
+
+        let report = lint_content(&content);
+        assert!(report.errors.is_empty());
+        assert_eq!(report.warnings.len(), 3, "{report}");
+        assert!(
+            report.warnings[0].contains("module 'module_orphan'"),
+            "{report}"
+        );
+        assert!(report.warnings[1].contains(&format!("recipe '{}'", recipe_id.0)));
+        assert!(report.warnings[2].contains("tech 'tech_physics'"));
+        assert!(report.warnings[2].contains("physics"));
+    }
+}
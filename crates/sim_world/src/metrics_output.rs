@@ -0,0 +1,216 @@
+//! Metrics output for a run directory: CSV (`metrics_NNN.csv`), Parquet
+//! (`metrics.parquet`) or both, picked with `--metrics-format`.
+//!
+//! The Parquet file has the CSV's columns plus the run score at each sample.
+//! Its footer is written when the writer is finished or dropped, so a process
+//! killed mid-run leaves an unreadable Parquet file; CSV rows survive.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sim_core::{GameContent, GameState, MetricsFileWriter, MetricsSnapshot};
+
+use crate::parquet_writer::ParquetMetricsWriter;
+
+pub const METRICS_PARQUET_FILE: &str = "metrics.parquet";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricsFormat {
+    #[default]
+    Csv,
+    Parquet,
+    Both,
+}
+
+impl MetricsFormat {
+    pub const ALL: [Self; 3] = [Self::Csv, Self::Parquet, Self::Both];
+
+    /// Name accepted by `--metrics-format`.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Parquet => "parquet",
+            Self::Both => "both",
+        }
+    }
+
+    fn csv(self) -> bool {
+        matches!(self, Self::Csv | Self::Both)
+    }
+
+    fn parquet(self) -> bool {
+        matches!(self, Self::Parquet | Self::Both)
+    }
+}
+
+impl std::fmt::Display for MetricsFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for MetricsFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.name() == s)
+            .ok_or_else(|| format!("unknown metrics format '{s}' (expected csv, parquet, or both)"))
+    }
+}
+
+/// Writes each metrics sample of a run in the chosen formats.
+pub struct MetricsWriter {
+    csv: Option<MetricsFileWriter>,
+    parquet: Option<ParquetMetricsWriter>,
+}
+
+impl MetricsWriter {
+    /// Open the writers for `format` in `dir`, with per-element and
+    /// per-module-type columns for `content`.
+    pub fn new(dir: &Path, format: MetricsFormat, content: &GameContent) -> Result<Self> {
+        let element_ids = sim_core::content_element_ids(content);
+        let behavior_types = sim_core::content_behavior_types(content);
+        let csv = if format.csv() {
+            let writer = MetricsFileWriter::new(
+                dir.to_path_buf(),
+                element_ids.clone(),
+                behavior_types.clone(),
+            )
+            .with_context(|| format!("opening metrics CSV in {}", dir.display()))?;
+            Some(writer)
+        } else {
+            None
+        };
+        let parquet = if format.parquet() {
+            let writer = ParquetMetricsWriter::new(
+                &dir.join(METRICS_PARQUET_FILE),
+                element_ids,
+                behavior_types,
+            )
+            .with_context(|| format!("opening metrics Parquet in {}", dir.display()))?;
+            Some(writer)
+        } else {
+            None
+        };
+        Ok(Self { csv, parquet })
+    }
+
+    /// Append one sample. The run score is only computed for Parquet.
+    pub fn write_row(
+        &mut self,
+        snapshot: &MetricsSnapshot,
+        state: &GameState,
+        content: &GameContent,
+    ) -> Result<()> {
+        if let Some(csv) = &mut self.csv {
+            csv.write_row(snapshot).context("writing metrics CSV row")?;
+        }
+        if let Some(parquet) = &mut self.parquet {
+            let score = sim_core::compute_run_score(snapshot, state, content);
+            parquet
+                .write_row(snapshot, &score)
+                .context("writing metrics Parquet row")?;
+        }
+        Ok(())
+    }
+
+    /// Flush buffered CSV rows. Parquet rows are written in batches and on
+    /// [`finish`](Self::finish).
+    pub fn flush(&mut self) -> Result<()> {
+        if let Some(csv) = &mut self.csv {
+            csv.flush().context("flushing metrics CSV")?;
+        }
+        Ok(())
+    }
+
+    /// Flush everything and write the Parquet footer.
+    pub fn finish(mut self) -> Result<()> {
+        self.flush()?;
+        if let Some(parquet) = self.parquet.take() {
+            parquet.finish().context("finishing metrics Parquet")?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for MetricsWriter {
+    /// A run that ends without [`finish`](Self::finish) (daemon run switch,
+    /// early error) still gets a readable Parquet file.
+    fn drop(&mut self) {
+        if let Some(parquet) = self.parquet.take() {
+            let _ = parquet.finish();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim_core::test_fixtures::{base_content, base_state};
+
+    #[test]
+    fn formats_round_trip_through_their_names() {
+        for format in MetricsFormat::ALL {
+            assert_eq!(format.name().parse(), Ok(format));
+            let json = serde_json::to_string(&format).unwrap();
+            assert_eq!(json, format!("\"{}\"", format.name()));
+        }
+        assert!("arrow".parse::<MetricsFormat>().is_err());
+    }
+
+    #[test]
+    fn writer_creates_the_files_of_its_format() {
+        let content = base_content();
+        let state = base_state(&content);
+        let snapshot = sim_core::compute_metrics(&state, &content);
+        for (format, has_csv, has_parquet) in [
+            (MetricsFormat::Csv, true, false),
+            (MetricsFormat::Parquet, false, true),
+            (MetricsFormat::Both, true, true),
+        ] {
+            let dir = tempfile::tempdir().unwrap();
+            let mut writer = MetricsWriter::new(dir.path(), format, &content).unwrap();
+            writer.write_row(&snapshot, &state, &content).unwrap();
+            writer.finish().unwrap();
+            assert_eq!(
+                dir.path().join("metrics_000.csv").exists(),
+                has_csv,
+                "{format}"
+            );
+            let parquet_path = dir.path().join(METRICS_PARQUET_FILE);
+            assert_eq!(parquet_path.exists(), has_parquet, "{format}");
+            if has_parquet {
+                let file = std::fs::File::open(parquet_path).unwrap();
+                let reader =
+                    parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder::try_new(file)
+                        .unwrap()
+                        .build()
+                        .unwrap();
+                let rows: usize = reader.map(|batch| batch.unwrap().num_rows()).sum();
+                assert_eq!(rows, 1);
+            }
+        }
+    }
+
+    #[test]
+    fn dropping_the_writer_finishes_parquet() {
+        let content = base_content();
+        let state = base_state(&content);
+        let snapshot = sim_core::compute_metrics(&state, &content);
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let mut writer =
+                MetricsWriter::new(dir.path(), MetricsFormat::Parquet, &content).unwrap();
+            writer.write_row(&snapshot, &state, &content).unwrap();
+        }
+        let file = std::fs::File::open(dir.path().join(METRICS_PARQUET_FILE)).unwrap();
+        assert!(
+            parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder::try_new(file).is_ok()
+        );
+    }
+}
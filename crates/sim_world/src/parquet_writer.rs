@@ -465,6 +465,9 @@ mod tests {
             thruster_count: 2,
             export_revenue_total: 50_000.0 + index as f64 * 500.0,
             export_count: 10 + index as u32,
+            loan_outstanding: 0.0,
+            loan_missed_payments: 0,
+            loan_defaulted: 0,
             power_generated_kw: 100.0,
             power_consumed_kw: 75.0,
             power_deficit_kw: 0.0,
@@ -480,6 +483,7 @@ mod tests {
             transfer_count: 0,
             milestones_completed: 0,
             game_phase: 0,
+            power_brownout_kw: 0.0,
         }
     }
 
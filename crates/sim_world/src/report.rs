@@ -0,0 +1,496 @@
+//! End-of-run reports.
+//!
+//! [`RunReporter`] watches a single run — every tick's events and each
+//! metrics sample — and at the end writes `report.json` and `report.md`
+//! into the run directory: tech timeline, production totals, economy
+//! trajectory, alerts fired and collapse status, plus the run's metrics
+//! aggregated the same way `sim_bench` aggregates across seeds
+//! ([`build_aggregated_metrics`]). `sim_cli run` and `sim_daemon run
+//! --max-ticks` write one when they finish with a run directory.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sim_core::{Event, EventEnvelope, GameContent, GameState, MetricsSnapshot};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// Bumped when the `report.json` shape changes.
+pub const REPORT_VERSION: u32 = 1;
+
+/// Mean, min, max and population standard deviation of a sample.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct MetricStats {
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+    pub stddev: f64,
+}
+
+pub fn summarize(values: &[f64]) -> MetricStats {
+    let count = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / count;
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count;
+    MetricStats {
+        mean,
+        min,
+        max,
+        stddev: variance.sqrt(),
+    }
+}
+
+/// Build aggregated metrics in the contract format:
+/// `{ "key": { "mean": ..., "min": ..., "max": ..., "stddev": ... }, ... }`
+///
+/// Auto-generates entries for all fixed scalar fields (except `tick` and `metrics_version`)
+/// using [`MetricsSnapshot::fixed_field_values`]. New fields added to `MetricsSnapshot`
+/// automatically appear in the aggregated output.
+pub fn build_aggregated_metrics(snapshots: &[&MetricsSnapshot]) -> serde_json::Value {
+    let descriptors = MetricsSnapshot::fixed_field_descriptors();
+    // Pre-extract field values once per snapshot to avoid O(fields * snapshots) allocations.
+    let all_values: Vec<Vec<(&str, sim_core::MetricValue)>> =
+        snapshots.iter().map(|s| s.fixed_field_values()).collect();
+
+    let mut map = serde_json::Map::new();
+    for (index, (name, _)) in descriptors.iter().enumerate() {
+        if matches!(*name, "tick" | "metrics_version") {
+            continue;
+        }
+        let values: Vec<f64> = all_values.iter().map(|fv| fv[index].1.as_f64()).collect();
+        let stats = summarize(&values);
+        map.insert(
+            name.to_string(),
+            serde_json::json!({
+                "mean": stats.mean,
+                "min": stats.min,
+                "max": stats.max,
+                "stddev": stats.stddev,
+            }),
+        );
+    }
+    serde_json::Value::Object(map)
+}
+
+/// Detect collapse: the starting loan has defaulted (bankruptcy), or
+/// processor starved > 0 AND `fleet_idle` == `fleet_total`.
+pub fn detect_collapse(snapshot: &MetricsSnapshot) -> (bool, Option<String>) {
+    if snapshot.loan_defaulted > 0 {
+        return (true, Some("loan_default".to_string()));
+    }
+    let processor_starved = snapshot
+        .per_module_metrics
+        .get("processor")
+        .map_or(0, |m| m.starved);
+    let collapsed = processor_starved > 0 && snapshot.fleet_idle == snapshot.fleet_total;
+    if collapsed {
+        (true, Some("refinery_starved + fleet_idle".to_string()))
+    } else {
+        (false, None)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RunReport {
+    pub report_version: u32,
+    pub seed: u64,
+    pub tick_start: u64,
+    pub tick_end: u64,
+    pub tech_timeline: Vec<TechUnlock>,
+    /// Per-module totals from `ProductionReport` events, keyed by module id.
+    pub production: BTreeMap<String, ProductionTotals>,
+    pub final_inventory: InventoryTotals,
+    pub economy: Vec<EconomyPoint>,
+    /// Keyed by alert id.
+    pub alerts: BTreeMap<String, AlertSummary>,
+    pub collapse: CollapseStatus,
+    /// [`build_aggregated_metrics`] over the run's metrics samples.
+    pub metrics: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TechUnlock {
+    pub tick: u64,
+    pub tech_id: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ProductionTotals {
+    pub runs: u64,
+    pub input_kg: f64,
+    pub output_kg: f64,
+    pub output_units: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct InventoryTotals {
+    pub ore_kg: f64,
+    pub material_kg: f64,
+    pub slag_kg: f64,
+    pub per_element_material_kg: BTreeMap<String, f64>,
+    pub export_revenue_total: f64,
+    pub export_count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EconomyPoint {
+    pub tick: u64,
+    pub balance: f64,
+    pub export_revenue_total: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AlertSummary {
+    pub count: u64,
+    pub first_tick: u64,
+    pub last_tick: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CollapseStatus {
+    pub occurred: bool,
+    /// First metrics sample that showed collapse.
+    pub tick: Option<u64>,
+    pub reason: Option<String>,
+}
+
+/// Accumulates a [`RunReport`] over one run.
+#[derive(Debug, Default)]
+pub struct RunReporter {
+    tick_start: u64,
+    tech_timeline: Vec<TechUnlock>,
+    /// `(tick, module_id, totals)` per `ProductionReport`.
+    production: Vec<(u64, String, ProductionTotals)>,
+    /// `(tick, alert_id)` per `AlertRaised`.
+    alerts: Vec<(u64, String)>,
+    samples: Vec<MetricsSnapshot>,
+}
+
+impl RunReporter {
+    pub fn new(state: &GameState) -> Self {
+        Self {
+            tick_start: state.meta.tick,
+            ..Self::default()
+        }
+    }
+
+    /// Record a tick's events. Pass them before any level filtering.
+    pub fn observe_events(&mut self, events: &[EventEnvelope]) {
+        for envelope in events {
+            match &envelope.event {
+                Event::TechUnlocked { tech_id } => self.tech_timeline.push(TechUnlock {
+                    tick: envelope.tick,
+                    tech_id: tech_id.0.clone(),
+                }),
+                Event::ProductionReport {
+                    module_id,
+                    runs,
+                    input_kg,
+                    output_kg,
+                    output_units,
+                    ..
+                } => self.production.push((
+                    envelope.tick,
+                    module_id.0.clone(),
+                    ProductionTotals {
+                        runs: u64::from(*runs),
+                        input_kg: f64::from(*input_kg),
+                        output_kg: f64::from(*output_kg),
+                        output_units: u64::from(*output_units),
+                    },
+                )),
+                Event::AlertRaised { alert_id, .. } => {
+                    self.alerts.push((envelope.tick, alert_id.clone()));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Record a metrics sample.
+    pub fn observe_metrics(&mut self, snapshot: &MetricsSnapshot) {
+        self.samples.push(snapshot.clone());
+    }
+
+    /// Forget everything recorded after `tick`, for a run rewound to a
+    /// checkpoint.
+    pub fn rewind(&mut self, tick: u64) {
+        self.tech_timeline.retain(|unlock| unlock.tick <= tick);
+        self.production.retain(|(at, ..)| *at <= tick);
+        self.alerts.retain(|(at, _)| *at <= tick);
+        self.samples.retain(|sample| sample.tick <= tick);
+    }
+
+    /// Finish the report from the final state. The final state is sampled
+    /// too unless the last sample already covers its tick.
+    pub fn finish(mut self, state: &GameState, content: &GameContent) -> RunReport {
+        if self
+            .samples
+            .last()
+            .is_none_or(|s| s.tick != state.meta.tick)
+        {
+            let snapshot = sim_core::compute_metrics(state, content);
+            self.observe_metrics(&snapshot);
+        }
+        let refs: Vec<&MetricsSnapshot> = self.samples.iter().collect();
+        let metrics = build_aggregated_metrics(&refs);
+        let economy = self
+            .samples
+            .iter()
+            .map(|s| EconomyPoint {
+                tick: s.tick,
+                balance: s.balance,
+                export_revenue_total: s.export_revenue_total,
+            })
+            .collect();
+        RunReport {
+            report_version: REPORT_VERSION,
+            seed: state.meta.seed,
+            tick_start: self.tick_start,
+            tick_end: state.meta.tick,
+            tech_timeline: self.tech_timeline,
+            production: sum_production(self.production),
+            final_inventory: self
+                .samples
+                .last()
+                .map(InventoryTotals::from_snapshot)
+                .unwrap_or_default(),
+            economy,
+            alerts: summarize_alerts(self.alerts),
+            collapse: first_collapse(&self.samples),
+            metrics,
+        }
+    }
+}
+
+impl InventoryTotals {
+    fn from_snapshot(snapshot: &MetricsSnapshot) -> Self {
+        Self {
+            ore_kg: f64::from(snapshot.total_ore_kg),
+            material_kg: f64::from(snapshot.total_material_kg),
+            slag_kg: f64::from(snapshot.total_slag_kg),
+            per_element_material_kg: snapshot
+                .per_element_material_kg
+                .iter()
+                .map(|(element, kg)| (element.clone(), f64::from(*kg)))
+                .collect(),
+            export_revenue_total: snapshot.export_revenue_total,
+            export_count: snapshot.export_count,
+        }
+    }
+}
+
+fn sum_production(
+    records: Vec<(u64, String, ProductionTotals)>,
+) -> BTreeMap<String, ProductionTotals> {
+    let mut production: BTreeMap<String, ProductionTotals> = BTreeMap::new();
+    for (_, module_id, report) in records {
+        let totals = production.entry(module_id).or_default();
+        totals.runs += report.runs;
+        totals.input_kg += report.input_kg;
+        totals.output_kg += report.output_kg;
+        totals.output_units += report.output_units;
+    }
+    production
+}
+
+fn summarize_alerts(records: Vec<(u64, String)>) -> BTreeMap<String, AlertSummary> {
+    let mut alerts: BTreeMap<String, AlertSummary> = BTreeMap::new();
+    for (tick, alert_id) in records {
+        alerts
+            .entry(alert_id)
+            .and_modify(|summary| {
+                summary.count += 1;
+                summary.last_tick = tick;
+            })
+            .or_insert(AlertSummary {
+                count: 1,
+                first_tick: tick,
+                last_tick: tick,
+            });
+    }
+    alerts
+}
+
+fn first_collapse(samples: &[MetricsSnapshot]) -> CollapseStatus {
+    samples
+        .iter()
+        .find_map(|sample| match detect_collapse(sample) {
+            (true, reason) => Some(CollapseStatus {
+                occurred: true,
+                tick: Some(sample.tick),
+                reason,
+            }),
+            (false, _) => None,
+        })
+        .unwrap_or_default()
+}
+
+impl RunReport {
+    /// Write `report.json` and `report.md` into `dir`.
+    pub fn write(&self, dir: &Path) -> Result<()> {
+        let json_path = dir.join("report.json");
+        let json = serde_json::to_string_pretty(self).context("serializing report")?;
+        std::fs::write(&json_path, json)
+            .with_context(|| format!("writing {}", json_path.display()))?;
+        let md_path = dir.join("report.md");
+        std::fs::write(&md_path, self.to_markdown())
+            .with_context(|| format!("writing {}", md_path.display()))?;
+        Ok(())
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        let _ = writeln!(md, "# Run report\n");
+        let _ = writeln!(
+            md,
+            "Seed {}, ticks {}–{}.\n",
+            self.seed, self.tick_start, self.tick_end
+        );
+
+        let _ = writeln!(md, "## Collapse\n");
+        match (&self.collapse.tick, &self.collapse.reason) {
+            (Some(tick), Some(reason)) => {
+                let _ = writeln!(md, "Collapsed at tick {tick}: {reason}.\n");
+            }
+            _ => {
+                let _ = writeln!(md, "No collapse.\n");
+            }
+        }
+
+        let _ = writeln!(md, "## Tech timeline\n");
+        if self.tech_timeline.is_empty() {
+            let _ = writeln!(md, "No techs unlocked.\n");
+        } else {
+            let _ = writeln!(md, "| Tick | Tech |\n|---:|---|");
+            for unlock in &self.tech_timeline {
+                let _ = writeln!(md, "| {} | {} |", unlock.tick, unlock.tech_id);
+            }
+            md.push('\n');
+        }
+
+        let _ = writeln!(md, "## Production\n");
+        if self.production.is_empty() {
+            let _ = writeln!(md, "No production reported.\n");
+        } else {
+            let _ = writeln!(
+                md,
+                "| Module | Runs | Input kg | Output kg | Units |\n|---|---:|---:|---:|---:|"
+            );
+            for (module_id, totals) in &self.production {
+                let _ = writeln!(
+                    md,
+                    "| {module_id} | {} | {:.1} | {:.1} | {} |",
+                    totals.runs, totals.input_kg, totals.output_kg, totals.output_units
+                );
+            }
+            md.push('\n');
+        }
+        let inventory = &self.final_inventory;
+        let _ = writeln!(
+            md,
+            "Final inventory: {:.1} kg ore, {:.1} kg material, {:.1} kg slag. \
+             Exports: {} for ${:.0}.\n",
+            inventory.ore_kg,
+            inventory.material_kg,
+            inventory.slag_kg,
+            inventory.export_count,
+            inventory.export_revenue_total
+        );
+
+        let _ = writeln!(md, "## Economy\n");
+        let _ = writeln!(md, "| Tick | Balance | Export revenue |\n|---:|---:|---:|");
+        for point in &self.economy {
+            let _ = writeln!(
+                md,
+                "| {} | {:.0} | {:.0} |",
+                point.tick, point.balance, point.export_revenue_total
+            );
+        }
+        md.push('\n');
+
+        let _ = writeln!(md, "## Alerts\n");
+        if self.alerts.is_empty() {
+            let _ = writeln!(md, "No alerts fired.");
+        } else {
+            let _ = writeln!(
+                md,
+                "| Alert | Count | First tick | Last tick |\n|---|---:|---:|---:|"
+            );
+            for (alert_id, summary) in &self.alerts {
+                let _ = writeln!(
+                    md,
+                    "| {alert_id} | {} | {} | {} |",
+                    summary.count, summary.first_tick, summary.last_tick
+                );
+            }
+        }
+        md
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim_core::test_fixtures::{base_content, base_state};
+    use sim_core::{AlertSeverity, EventId, TechId};
+
+    fn envelope(tick: u64, event: Event) -> EventEnvelope {
+        EventEnvelope {
+            id: EventId(tick),
+            tick,
+            event,
+        }
+    }
+
+    #[test]
+    fn report_collects_techs_alerts_and_final_sample() {
+        let content = base_content();
+        let mut state = base_state(&content);
+        let mut reporter = RunReporter::new(&state);
+        reporter.observe_events(&[
+            envelope(
+                3,
+                Event::TechUnlocked {
+                    tech_id: TechId("tech_a".to_string()),
+                },
+            ),
+            envelope(
+                4,
+                Event::AlertRaised {
+                    alert_id: "LOW_FUEL".to_string(),
+                    severity: AlertSeverity::Warning,
+                    message: String::new(),
+                    suggested_action: String::new(),
+                },
+            ),
+            envelope(
+                9,
+                Event::AlertRaised {
+                    alert_id: "LOW_FUEL".to_string(),
+                    severity: AlertSeverity::Warning,
+                    message: String::new(),
+                    suggested_action: String::new(),
+                },
+            ),
+        ]);
+        state.meta.tick = 10;
+
+        let report = reporter.finish(&state, &content);
+
+        assert_eq!(report.tick_end, 10);
+        assert_eq!(report.tech_timeline[0].tech_id, "tech_a");
+        assert_eq!(
+            report.alerts["LOW_FUEL"],
+            AlertSummary {
+                count: 2,
+                first_tick: 4,
+                last_tick: 9,
+            }
+        );
+        assert_eq!(report.economy.len(), 1);
+        assert_eq!(report.economy[0].tick, 10);
+        assert!(report.to_markdown().contains("| 3 | tech_a |"));
+    }
+}
@@ -0,0 +1,71 @@
+//! Collected content and state diagnostics.
+//!
+//! `validate_content` and `validate_state` run every check and gather what
+//! they find into a [`ValidationReport`] instead of stopping at the first
+//! problem, so an author sees every broken reference in one pass.
+
+use std::fmt;
+
+/// Problems found by a validation pass. Errors make content or a state
+/// unusable; warnings are suspicious but loadable.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct ValidationReport {
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl ValidationReport {
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.errors.push(message.into());
+    }
+
+    pub fn warning(&mut self, message: impl Into<String>) {
+        self.warnings.push(message.into());
+    }
+
+    /// Record `message()` as an error unless `ok`.
+    pub(crate) fn check(&mut self, ok: bool, message: impl FnOnce() -> String) {
+        if !ok {
+            self.error(message());
+        }
+    }
+
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Append `other`'s errors and warnings to this report.
+    pub fn merge(&mut self, other: Self) {
+        self.errors.extend(other.errors);
+        self.warnings.extend(other.warnings);
+    }
+
+    /// `Err(self)` if there are any errors, otherwise the warnings, for the
+    /// caller to show or drop.
+    pub fn into_result(self) -> Result<Vec<String>, Self> {
+        if self.has_errors() {
+            return Err(self);
+        }
+        Ok(self.warnings)
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} error(s), {} warning(s)",
+            self.errors.len(),
+            self.warnings.len()
+        )?;
+        for error in &self.errors {
+            write!(f, "\n  error: {error}")?;
+        }
+        for warning in &self.warnings {
+            write!(f, "\n  warning: {warning}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationReport {}
@@ -66,6 +66,7 @@ def suggest_strategy_params(trial: Any) -> dict[str, Any]:
     params["strategy.lh2_threshold_kg"] = trial.suggest_float("lh2_threshold_kg", 1000.0, 20000.0)
     params["strategy.lh2_abundant_multiplier"] = trial.suggest_float("lh2_abundant_multiplier", 1.5, 5.0)
     params["strategy.refinery_threshold_kg"] = trial.suggest_float("refinery_threshold_kg", 500.0, 5000.0)
+    params["strategy.refinery_batch_ticks"] = trial.suggest_int("refinery_batch_ticks", 0, 96)
     params["strategy.slag_jettison_pct"] = trial.suggest_float("slag_jettison_pct", 0.5, 0.95)
     params["strategy.export_batch_size_kg"] = trial.suggest_float("export_batch_size_kg", 100.0, 2000.0)
     params["strategy.export_min_revenue"] = trial.suggest_float("export_min_revenue", 100.0, 5000.0)
@@ -205,6 +206,7 @@ def export_best_config(study: Any, export_path: str) -> None:
         "lh2_threshold_kg",
         "lh2_abundant_multiplier",
         "refinery_threshold_kg",
+        "refinery_batch_ticks",
         "slag_jettison_pct",
         "export_batch_size_kg",
         "export_min_revenue",
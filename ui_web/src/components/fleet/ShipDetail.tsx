@@ -87,6 +87,9 @@ export function ShipDetail({ ship, hulls, displayTick }: ShipDetailProps) {
             {taskType === 'DeepScan' && 'DeepScan' in task.kind && (
               <div className="text-dim">asteroid: {task.kind.DeepScan.asteroid}</div>
             )}
+            {taskType === 'Resurvey' && 'Resurvey' in task.kind && (
+              <div className="text-dim">asteroid: {task.kind.Resurvey.asteroid}</div>
+            )}
           </div>
         )}
       </div>
@@ -29,6 +29,7 @@ export const ELEMENT_COLORS: Record<string, string> = {
   Si: '#8a9aaf',
   He: '#c4a038',
   H2O: '#4a90d9',
+  NH3: '#9ad0c2',
   LH2: '#5cc8e8',
   LOX: '#e08a6a',
 };
@@ -75,6 +76,7 @@ export const BODY_COLORS: Record<string, string> = {
 // --- Ship task types ---
 export const SHIP_TASK_COLORS: Record<string, string> = {
   Survey: '#5b9bd5',
+  Resurvey: '#5b9bd5',
   DeepScan: '#7b68ee',
   Mine: '#d4a44c',
   Deposit: '#4caf7d',
@@ -127,6 +127,41 @@ describe('applyEvents', () => {
     });
   });
 
+  describe('DroneOreMined', () => {
+    it('adds the ore to the station and cuts the asteroid', () => {
+      const station = makeStation();
+      const asteroid = makeAsteroid({ mass_kg: 500 });
+      const oreLot = makeOreLot({ kg: 100 });
+
+      const events = [{
+        id: 1,
+        tick: 10,
+        event: {
+          DroneOreMined: {
+            station_id: 'station_001',
+            module_id: 'module_drone_bay_0001',
+            asteroid_id: 'ast_001',
+            ore_lot: oreLot,
+            asteroid_remaining_kg: 400,
+          },
+        },
+      }];
+
+      const result = applyEvents(
+        { ast_001: asteroid },
+        {},
+        { station_001: station },
+        emptyResearch,
+        [],
+        defaultBalance,
+        events,
+      );
+
+      expect(result.stations['station_001'].inventory).toEqual([oreLot]);
+      expect(result.asteroids['ast_001'].mass_kg).toBe(400);
+    });
+  });
+
   describe('OreDeposited', () => {
     it('clears ship inventory and adds items to station', () => {
       const oreLot = makeOreLot();
@@ -608,6 +643,63 @@ describe('applyEvents', () => {
       expect(item.kind).toBe('Module');
       expect(item.item_id).toBe('item_001');
       expect(item.module_def_id).toBe('module_refinery');
+      expect(item.wear).toBe(0.2);
+    });
+  });
+
+  describe('ModuleScrapped', () => {
+    it('removes module from station and adds recovered material', () => {
+      const station = makeStation({
+        modules: [{
+          id: 'mod_proc', def_id: 'module_refinery', enabled: true,
+          kind_state: { Processor: { threshold_kg: 0, ticks_since_last_run: 0, stalled: false } },
+          wear: { wear: 0.2 },
+        }],
+      });
+
+      const events = [{
+        id: 1, tick: 10,
+        event: {
+          ModuleScrapped: {
+            station_id: 'station_001', module_id: 'mod_proc', module_def_id: 'module_refinery',
+            element: 'Fe', kg: 400,
+          },
+        },
+      }];
+
+      const result = applyEvents({}, {}, { station_001: station }, emptyResearch, [], defaultBalance, events);
+      expect(result.stations['station_001'].modules).toHaveLength(0);
+      const item = result.stations['station_001'].inventory[0] as MaterialItem;
+      expect(item.kind).toBe('Material');
+      expect(item.element).toBe('Fe');
+      expect(item.kg).toBe(400);
+    });
+  });
+
+  describe('ModuleUpgraded', () => {
+    it('swaps the module def and wear in place', () => {
+      const station = makeStation({
+        modules: [{
+          id: 'mod_proc', def_id: 'module_refinery', enabled: true,
+          kind_state: { Processor: { threshold_kg: 0, ticks_since_last_run: 0, stalled: false } },
+          wear: { wear: 0.4 },
+        }],
+      });
+
+      const events = [{
+        id: 1, tick: 10,
+        event: {
+          ModuleUpgraded: {
+            station_id: 'station_001', module_id: 'mod_proc', from_def_id: 'module_refinery',
+            to_def_id: 'module_refinery_mk2', wear: 0.2,
+          },
+        },
+      }];
+
+      const result = applyEvents({}, {}, { station_001: station }, emptyResearch, [], defaultBalance, events);
+      const module = result.stations['station_001'].modules[0];
+      expect(module.def_id).toBe('module_refinery_mk2');
+      expect(module.wear.wear).toBe(0.2);
     });
   });
 
@@ -1317,6 +1409,43 @@ describe('applyEvents', () => {
     });
   });
 
+  describe('ShipRepaired', () => {
+    it('sets the ship wear to the repaired level', () => {
+      const ships = { ship_0001: makeShip({ wear: { wear: 0.5 } }) };
+      const events = [{
+        id: 1, tick: 10,
+        event: {
+          ShipRepaired: {
+            ship_id: 'ship_0001', station_id: 'station_001',
+            wear_before: 0.5, wear_after: 0.1, repair_kits_used: 2,
+          },
+        },
+      }];
+
+      const result = applyEvents({}, ships, {}, emptyResearch, [], defaultBalance, events);
+      expect(result.ships['ship_0001'].wear).toEqual({ wear: 0.1 });
+    });
+  });
+
+  describe('ShipLost', () => {
+    it('removes the ship and credits the insurance payout', () => {
+      const ships = { ship_0001: makeShip(), ship_0002: makeShip({ id: 'ship_0002' }) };
+      const events = [{
+        id: 1, tick: 10,
+        event: {
+          ShipLost: {
+            ship_id: 'ship_0001', owner: 'principal_autopilot',
+            cause: 'solar_storm', insurance_payout: 1_000_000,
+          },
+        },
+      }];
+
+      const result = applyEvents({}, ships, {}, emptyResearch, [], defaultBalance, events);
+      expect(Object.keys(result.ships)).toEqual(['ship_0002']);
+      expect(result.balance).toBe(defaultBalance + 1_000_000);
+    });
+  });
+
   describe('ShipConstructed', () => {
     it('adds a new ship to state', () => {
       const events = [{
@@ -1,6 +1,6 @@
 import type { z } from 'zod';
 
-import type { AsteroidState, ComponentItem, InventoryItem, MaterialItem, ModuleKindState, OverheatZone, ResearchState, ScanSite, ShipState, SimEvent, SlagItem, StationState, TaskState, TradeItemSpec } from '../types';
+import type { AssemblerState, AsteroidState, ComponentItem, InventoryItem, MaterialItem, ModuleKindState, OverheatZone, ResearchState, ScanSite, ShipState, SimEvent, SlagItem, StationState, TaskState, TradeItemSpec } from '../types';
 import { getEventKey } from '../utils';
 
 import { eventSchemas } from './eventSchemas';
@@ -36,8 +36,11 @@ function buildTaskStub(taskKind: string, target: string | null, tick: number): T
   const kindMap: Record<string, Record<string, unknown>> = {
     Survey: target ? { Survey: { site: target } } : { Idle: {} },
     DeepScan: target ? { DeepScan: { asteroid: target } } : { Idle: {} },
+    Resurvey: target ? { Resurvey: { asteroid: target } } : { Idle: {} },
     Mine: target ? { Mine: { asteroid: target, duration_ticks: 0 } } : { Idle: {} },
     Deposit: target ? { Deposit: { station: target, blocked: false } } : { Idle: {} },
+    DockForRepair: target ? { DockForRepair: { station: target } } : { Idle: {} },
+    Escort: target ? { Escort: { ship: target } } : { Idle: {} },
     Transit: target
       ? {
         Transit: {
@@ -112,18 +115,23 @@ function handleAsteroidDiscovered(state: SimState, event: EventPayload<'Asteroid
   };
 }
 
-function handleOreMined(state: SimState, event: EventPayload<'OreMined'>): SimState {
-  let { asteroids, ships } = state;
-  if (event.asteroid_remaining_kg <= 0) {
-    asteroids = Object.fromEntries(
-      Object.entries(asteroids).filter(([id]) => id !== event.asteroid_id)
-    );
-  } else if (asteroids[event.asteroid_id]) {
-    asteroids = {
-      ...asteroids,
-      [event.asteroid_id]: { ...asteroids[event.asteroid_id], mass_kg: event.asteroid_remaining_kg },
-    };
+function cutAsteroid(
+  asteroids: SimState['asteroids'],
+  asteroidId: string,
+  remainingKg: number,
+): SimState['asteroids'] {
+  if (remainingKg <= 0) {
+    return Object.fromEntries(Object.entries(asteroids).filter(([id]) => id !== asteroidId));
+  }
+  if (asteroids[asteroidId]) {
+    return { ...asteroids, [asteroidId]: { ...asteroids[asteroidId], mass_kg: remainingKg } };
   }
+  return asteroids;
+}
+
+function handleOreMined(state: SimState, event: EventPayload<'OreMined'>): SimState {
+  const asteroids = cutAsteroid(state.asteroids, event.asteroid_id, event.asteroid_remaining_kg);
+  let { ships } = state;
   if (ships[event.ship_id]) {
     ships = {
       ...ships,
@@ -136,6 +144,21 @@ function handleOreMined(state: SimState, event: EventPayload<'OreMined'>): SimSt
   return { ...state, asteroids, ships };
 }
 
+function handleDroneOreMined(state: SimState, event: EventPayload<'DroneOreMined'>): SimState {
+  const asteroids = cutAsteroid(state.asteroids, event.asteroid_id, event.asteroid_remaining_kg);
+  let { stations } = state;
+  if (stations[event.station_id]) {
+    stations = {
+      ...stations,
+      [event.station_id]: {
+        ...stations[event.station_id],
+        inventory: [...stations[event.station_id].inventory, event.ore_lot],
+      },
+    };
+  }
+  return { ...state, asteroids, stations };
+}
+
 function handleOreDeposited(state: SimState, event: EventPayload<'OreDeposited'>): SimState {
   let { ships, stations } = state;
   if (ships[event.ship_id]) {
@@ -289,7 +312,9 @@ function handleModuleUninstalled(state: SimState, event: EventPayload<'ModuleUni
   const removed = station.modules.find((m) => m.id === event.module_id);
   const updatedModules = station.modules.filter((m) => m.id !== event.module_id);
   const updatedInventory = removed
-    ? [...station.inventory, { kind: 'Module' as const, item_id: event.module_item_id, module_def_id: removed.def_id }]
+    ? [...station.inventory, {
+      kind: 'Module' as const, item_id: event.module_item_id, module_def_id: removed.def_id, wear: removed.wear.wear,
+    }]
     : station.inventory;
   return {
     ...state,
@@ -300,6 +325,39 @@ function handleModuleUninstalled(state: SimState, event: EventPayload<'ModuleUni
   };
 }
 
+function handleModuleUpgraded(state: SimState, event: EventPayload<'ModuleUpgraded'>): SimState {
+  return mapStationModule(state, event.station_id, event.module_id, (m) => ({
+    ...m,
+    def_id: event.to_def_id,
+    wear: { wear: event.wear },
+  }));
+}
+
+function handleModuleScrapped(state: SimState, event: EventPayload<'ModuleScrapped'>): SimState {
+  if (!state.stations[event.station_id]) {return state;}
+  const station = state.stations[event.station_id];
+  const updatedModules = station.modules.filter((m) => m.id !== event.module_id);
+  const updatedInventory = [...station.inventory];
+  if (event.kg > 0) {
+    const matIndex = updatedInventory.findIndex(
+      (i) => i.kind === 'Material' && i.element === event.element && i.quality === 1.0,
+    );
+    if (matIndex >= 0) {
+      const existing = updatedInventory[matIndex] as MaterialItem;
+      updatedInventory[matIndex] = { ...existing, kg: existing.kg + event.kg };
+    } else {
+      updatedInventory.push({ kind: 'Material', element: event.element, kg: event.kg, quality: 1.0 });
+    }
+  }
+  return {
+    ...state,
+    stations: {
+      ...state.stations,
+      [event.station_id]: { ...station, modules: updatedModules, inventory: updatedInventory },
+    },
+  };
+}
+
 function handleModuleToggled(state: SimState, event: EventPayload<'ModuleToggled'>): SimState {
   return mapStationModule(state, event.station_id, event.module_id, (m) => ({ ...m, enabled: event.enabled }));
 }
@@ -428,6 +486,21 @@ function handleModuleAutoDisabled(state: SimState, event: EventPayload<'ModuleAu
   return mapStationModule(state, event.station_id, event.module_id, (m) => ({ ...m, enabled: false }));
 }
 
+/** Set an assembler slot's stall/cap flag. Slot 0 is the module's own flags. */
+function setAssemblerSlotFlag(
+  asm: AssemblerState,
+  slot: number,
+  flag: 'stalled' | 'capped',
+  value: boolean,
+): AssemblerState {
+  if (slot === 0) {return { ...asm, [flag]: value };}
+  const extra = [...(asm.extra_slots ?? [])];
+  const idx = slot - 1;
+  if (!extra[idx]) {return asm;}
+  extra[idx] = { ...extra[idx], [flag]: value };
+  return { ...asm, extra_slots: extra };
+}
+
 function handleModuleStalled(state: SimState, event: EventPayload<'ModuleStalled'>): SimState {
   return mapStationModule(state, event.station_id, event.module_id, (m) => {
     const ks = m.kind_state;
@@ -435,7 +508,7 @@ function handleModuleStalled(state: SimState, event: EventPayload<'ModuleStalled
       return { ...m, kind_state: { Processor: { ...ks.Processor, stalled: true } } };
     }
     if (typeof ks === 'object' && 'Assembler' in ks) {
-      return { ...m, kind_state: { Assembler: { ...ks.Assembler, stalled: true } } };
+      return { ...m, kind_state: { Assembler: setAssemblerSlotFlag(ks.Assembler, event.slot ?? 0, 'stalled', true) } };
     }
     return m;
   });
@@ -448,7 +521,7 @@ function handleModuleResumed(state: SimState, event: EventPayload<'ModuleResumed
       return { ...m, kind_state: { Processor: { ...ks.Processor, stalled: false } } };
     }
     if (typeof ks === 'object' && 'Assembler' in ks) {
-      return { ...m, kind_state: { Assembler: { ...ks.Assembler, stalled: false } } };
+      return { ...m, kind_state: { Assembler: setAssemblerSlotFlag(ks.Assembler, event.slot ?? 0, 'stalled', false) } };
     }
     return m;
   });
@@ -458,7 +531,7 @@ function handleAssemblerCapped(state: SimState, event: EventPayload<'AssemblerCa
   return mapStationModule(state, event.station_id, event.module_id, (m) => {
     const ks = m.kind_state;
     if (typeof ks === 'object' && 'Assembler' in ks) {
-      return { ...m, kind_state: { Assembler: { ...ks.Assembler, capped: true } } };
+      return { ...m, kind_state: { Assembler: setAssemblerSlotFlag(ks.Assembler, event.slot ?? 0, 'capped', true) } };
     }
     return m;
   });
@@ -468,7 +541,7 @@ function handleAssemblerUncapped(state: SimState, event: EventPayload<'Assembler
   return mapStationModule(state, event.station_id, event.module_id, (m) => {
     const ks = m.kind_state;
     if (typeof ks === 'object' && 'Assembler' in ks) {
-      return { ...m, kind_state: { Assembler: { ...ks.Assembler, capped: false } } };
+      return { ...m, kind_state: { Assembler: setAssemblerSlotFlag(ks.Assembler, event.slot ?? 0, 'capped', false) } };
     }
     return m;
   });
@@ -533,6 +606,18 @@ function handleMaintenanceRan(state: SimState, event: EventPayload<'MaintenanceR
   };
 }
 
+function handleShipRepaired(state: SimState, event: EventPayload<'ShipRepaired'>): SimState {
+  const ship = state.ships[event.ship_id];
+  if (!ship) {return state;}
+  return {
+    ...state,
+    ships: {
+      ...state.ships,
+      [event.ship_id]: { ...ship, wear: { wear: event.wear_after } },
+    },
+  };
+}
+
 function handleLabRan(state: SimState, event: EventPayload<'LabRan'>): SimState {
   return mapStationModule(state, event.station_id, event.module_id, (m) => {
     const ks = m.kind_state;
@@ -588,7 +673,12 @@ function handleCompositionMapped(state: SimState, event: EventPayload<'Compositi
       ...state.asteroids,
       [event.asteroid_id]: {
         ...state.asteroids[event.asteroid_id],
-        knowledge: { ...state.asteroids[event.asteroid_id].knowledge, composition: event.composition },
+        knowledge: {
+          ...state.asteroids[event.asteroid_id].knowledge,
+          composition: event.composition,
+          composition_sigma: event.composition_sigma,
+          deep_scan_count: event.deep_scan_count,
+        },
       },
     },
   };
@@ -623,17 +713,27 @@ function handleShipConstructed(state: SimState, event: EventPayload<'ShipConstru
       [event.ship_id]: {
         id: event.ship_id,
         position: event.position,
-        owner: 'principal_autopilot',
+        owner: event.owner,
         inventory: [],
         cargo_capacity_m3: event.cargo_capacity_m3,
         task: null,
         hull_id: event.hull_id,
         fitted_modules: event.fitted_modules ?? [],
+        name: event.name || undefined,
+        class_id: event.class_id ?? undefined,
+        role: event.role ?? undefined,
       },
     },
   };
 }
 
+function handleShipLost(state: SimState, event: EventPayload<'ShipLost'>): SimState {
+  const ships = Object.fromEntries(
+    Object.entries(state.ships).filter(([id]) => id !== event.ship_id),
+  );
+  return { ...state, ships, balance: state.balance + event.insurance_payout };
+}
+
 function handleShipModuleFitted(state: SimState, event: EventPayload<'ShipModuleFitted'>): SimState {
   const ship = state.ships[event.ship_id];
   if (!ship) {return state;}
@@ -670,22 +770,49 @@ function handleShipModuleUnfitted(state: SimState, event: EventPayload<'ShipModu
 }
 
 function handleItemImported(state: SimState, event: EventPayload<'ItemImported'>): SimState {
-  let updatedState = { ...state, balance: event.balance_after };
-  if (!state.stations[event.station_id]) {return updatedState;}
-  const station = state.stations[event.station_id];
-  const newItem = tradeItemToInventory(event.item_spec);
+  return addImportToStation(
+    { ...state, balance: event.balance_after },
+    event.station_id,
+    event.item_spec,
+  );
+}
+
+function handleImportOrderPlaced(state: SimState, event: EventPayload<'ImportOrderPlaced'>): SimState {
+  return { ...state, balance: event.balance_after };
+}
+
+function handleImportDelivered(state: SimState, event: EventPayload<'ImportDelivered'>): SimState {
+  return addImportToStation(state, event.station_id, event.item_spec);
+}
+
+function handleImportOrderCancelled(state: SimState, event: EventPayload<'ImportOrderCancelled'>): SimState {
+  return { ...state, balance: event.balance_after };
+}
+
+function handleRoyaltyPaid(state: SimState, event: EventPayload<'RoyaltyPaid'>): SimState {
+  return { ...state, balance: event.balance_after };
+}
+
+function addImportToStation(
+  state: SimState,
+  stationId: string,
+  itemSpec: EventPayload<'ItemImported'>['item_spec'],
+): SimState {
+  if (!state.stations[stationId]) {return state;}
+  const station = state.stations[stationId];
+  const newItem = tradeItemToInventory(itemSpec);
   if (!newItem) {
     // Crew import: add to station.crew roster
-    if ('Crew' in event.item_spec) {
-      const { role, count } = event.item_spec.Crew;
+    if ('Crew' in itemSpec) {
+      const { role, count } = itemSpec.Crew;
       const crew = { ...(station.crew ?? {}) };
       crew[role] = (crew[role] ?? 0) + count;
       return {
-        ...updatedState,
-        stations: { ...updatedState.stations, [event.station_id]: { ...station, crew } },
+        ...state,
+        stations: { ...state.stations, [stationId]: { ...station, crew } },
       };
     }
-    return updatedState;
+    return state;
   }
   const stationInv = [...station.inventory];
   let merged = false;
@@ -709,21 +836,44 @@ function handleItemImported(state: SimState, event: EventPayload<'ItemImported'>
     }
   }
   if (!merged) {stationInv.push(newItem);}
-  updatedState = {
-    ...updatedState,
+  return {
+    ...state,
     stations: {
       ...state.stations,
-      [event.station_id]: { ...station, inventory: stationInv },
+      [stationId]: { ...station, inventory: stationInv },
     },
   };
-  return updatedState;
 }
 
 function handleItemExported(state: SimState, event: EventPayload<'ItemExported'>): SimState {
-  const itemSpec = event.item_spec;
-  let updatedState = { ...state, balance: event.balance_after };
-  if (!state.stations[event.station_id]) {return updatedState;}
-  const station = state.stations[event.station_id];
+  return removeTradeItemFromStation(
+    { ...state, balance: event.balance_after },
+    event.station_id,
+    event.item_spec,
+  );
+}
+
+function handleFreightScheduled(state: SimState, event: EventPayload<'FreightScheduled'>): SimState {
+  return removeTradeItemFromStation(
+    { ...state, balance: event.balance_after },
+    event.from_station,
+    event.item_spec,
+  );
+}
+
+function handleFreightDelivered(state: SimState, event: EventPayload<'FreightDelivered'>): SimState {
+  // Route runs deliver through the ship's OreDeposited.
+  if (event.route_id !== undefined) {return state;}
+  return addImportToStation(state, event.to_station, event.item_spec);
+}
+
+function removeTradeItemFromStation(
+  state: SimState,
+  stationId: string,
+  itemSpec: EventPayload<'ItemExported'>['item_spec'],
+): SimState {
+  if (!state.stations[stationId]) {return state;}
+  const station = state.stations[stationId];
   let stationInv = [...station.inventory];
   if ('Material' in itemSpec) {
     const { element, kg } = itemSpec.Material;
@@ -762,14 +912,13 @@ function handleItemExported(state: SimState, event: EventPayload<'ItemExported'>
     );
     if (moduleIndex >= 0) {stationInv.splice(moduleIndex, 1);}
   }
-  updatedState = {
-    ...updatedState,
+  return {
+    ...state,
     stations: {
       ...state.stations,
-      [event.station_id]: { ...station, inventory: stationInv },
+      [stationId]: { ...station, inventory: stationInv },
     },
   };
-  return updatedState;
 }
 
 function handleSlagJettisoned(state: SimState, event: EventPayload<'SlagJettisoned'>): SimState {
@@ -784,6 +933,21 @@ function handleSlagJettisoned(state: SimState, event: EventPayload<'SlagJettison
   };
 }
 
+function handleDebrisStrike(state: SimState, event: EventPayload<'DebrisStrike'>): SimState {
+  const ship = state.ships[event.ship_id];
+  if (!ship) {return state;}
+  return {
+    ...state,
+    ships: {
+      ...state.ships,
+      [event.ship_id]: {
+        ...ship,
+        propellant_kg: Math.max(0, (ship.propellant_kg ?? 0) - event.propellant_lost_kg),
+      },
+    },
+  };
+}
+
 function handleBoiloffLoss(state: SimState, event: EventPayload<'BoiloffLoss'>): SimState {
   if (!state.stations[event.station_id]) {return state;}
   const station = state.stations[event.station_id];
@@ -958,11 +1122,16 @@ type AnyEventHandler = (state: SimState, event: any, tick: number) => SimState;
 const EVENT_HANDLERS: Record<string, AnyEventHandler> = {
   AsteroidDiscovered: handleAsteroidDiscovered,
   OreMined: handleOreMined,
+  DroneOreMined: handleDroneOreMined,
   OreDeposited: handleOreDeposited,
   ItemsPickedUp: handleItemsPickedUp,
+  // Split ore lots get fresh ids, so inventories refresh from the next snapshot.
+  CargoTransferred: noOp,
   ModuleInstalled: handleModuleInstalled,
   ModuleNoCompatibleSlot: noOp,
   ModuleUninstalled: handleModuleUninstalled,
+  ModuleScrapped: handleModuleScrapped,
+  ModuleUpgraded: handleModuleUpgraded,
   StationConstructionStarted: noOp,
   StationModuleDelivered: noOp,
   ModuleToggled: handleModuleToggled,
@@ -974,11 +1143,13 @@ const EVENT_HANDLERS: Record<string, AnyEventHandler> = {
   ModuleStalled: handleModuleStalled,
   ModuleResumed: handleModuleResumed,
   ModuleAwaitingTech: noOp,
+  ModuleIdleDiagnosis: noOp, // debug-only; fetched on demand from the diagnosis endpoint
   AssemblerCapped: handleAssemblerCapped,
   AssemblerUncapped: handleAssemblerUncapped,
   DepositBlocked: handleDepositBlocked,
   DepositUnblocked: handleDepositUnblocked,
   MaintenanceRan: handleMaintenanceRan,
+  ShipRepaired: handleShipRepaired,
   LabRan: handleLabRan,
   LabStarved: handleLabStarved,
   LabResumed: handleLabResumed,
@@ -987,9 +1158,28 @@ const EVENT_HANDLERS: Record<string, AnyEventHandler> = {
   TechUnlocked: handleTechUnlocked,
   ScanSiteSpawned: handleScanSiteSpawned,
   ShipConstructed: handleShipConstructed,
+  ShipLost: handleShipLost,
   ItemImported: handleItemImported,
   ItemExported: handleItemExported,
+  ImportOrderPlaced: handleImportOrderPlaced,
+  ImportDelivered: handleImportDelivered,
+  ImportOrderCancelled: handleImportOrderCancelled,
+  RoyaltyPaid: handleRoyaltyPaid,
+  FreightScheduled: handleFreightScheduled,
+  FreightDelivered: handleFreightDelivered,
+  PriceChanged: noOp, // prices are read from /api/v1/market
+  FreightRouteCreated: noOp,
   SlagJettisoned: handleSlagJettisoned,
+  SlagPollutionWarning: noOp,
+  StormStarted: noOp, // interrupted ships refresh from the next snapshot
+  StormEnded: noOp,
+  ControllerFault: noOp,
+  SimPaused: noOp,
+  SimResumed: noOp,
+  TickHotspots: noOp,
+  DebrisStrike: handleDebrisStrike,
+  CargoStolen: noOp, // per-lot losses refresh from the next snapshot
+  ThreatRepelled: noOp,
   PowerStateUpdated: handlePowerStateUpdated,
   InsufficientFunds: noOp,
   AlertRaised: noOp,
@@ -1005,19 +1195,42 @@ const EVENT_HANDLERS: Record<string, AnyEventHandler> = {
   OverheatCleared: handleOverheatCleared,
   OverheatDamage: handleOverheatDamage,
   BoiloffLoss: handleBoiloffLoss,
+  ProductionReport: noOp, // summary of runs already applied via RefineryRan/AssemblerRan
+  OreSpoiled: noOp, // ore lots refresh from the next snapshot
+  ProjectStarted: noOp, // projects and station capacity refresh from the next snapshot
+  ProjectStalled: noOp,
+  ProjectCompleted: noOp,
+  ProjectCancelled: noOp,
+  StationBuildStarted: noOp, // the new station arrives with StationDeployed
   RecipeSelectionReset: noOp,
+  AssemblerJobCompleted: noOp, // assembler queues refresh from the next snapshot
   SimEventFired: noOp, // SE-05 will add proper handler
   SimEventExpired: noOp, // SE-05 will add proper handler
   ShipModuleFitted: handleShipModuleFitted,
   ShipModuleUnfitted: handleShipModuleUnfitted,
   PropellantConsumed: noOp,
   InsufficientPropellant: noOp,
+  TaskOutOfRange: noOp,
+  BodyClaimed: noOp, // claims and balance refresh from the next snapshot
+  ClaimRejected: noOp,
+  ClaimReleased: noOp,
+  ClaimExpired: noOp,
+  CommandCancelled: noOp,
+  CommandRejected: noOp,
+  ContractOffered: noOp, // contracts and balance refresh from the next snapshot
+  ContractAccepted: noOp,
+  ContractCompleted: noOp,
+  ContractFailed: noOp,
+  MiningRightsDenied: noOp,
   RefuelComplete: noOp,
   RefuelAborted: noOp,
   CrewAssigned: handleCrewAssigned,
   CrewUnassigned: handleCrewUnassigned,
   ModuleUnderstaffed: handleModuleUnderstaffed,
   ModuleFullyStaffed: handleModuleFullyStaffed,
+  // Life-support penalties arrive as ModuleEfficiencyChanged.
+  LifeSupportFailed: noOp,
+  LifeSupportRestored: noOp,
   ThermalLinkCreated: noOp,
   ThermalLinkRemoved: noOp,
   MoltenTransferred: noOp,
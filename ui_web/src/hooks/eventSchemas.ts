@@ -9,10 +9,20 @@ const tradeItemSpecSchema = z.union([
   z.object({ Crew: z.object({ role: z.string(), count: z.number() }) }),
 ]);
 
+const contractKindSchema = z.union([
+  z.object({ Deliver: z.object({ element: z.string(), kg: z.number() }) }),
+  z.object({ Survey: z.object({ count: z.number() }) }),
+]);
+
 const powerStateSchema = z.object({
   generated_kw: z.number(),
   consumed_kw: z.number(),
   deficit_kw: z.number(),
+  idle_kw: z.number().default(0),
+  active_kw: z.number().default(0),
+  stalled_kw: z.number().default(0),
+  brownout_kw: z.number().default(0),
+  deferred_kw: z.number().default(0),
   battery_discharge_kw: z.number(),
   battery_charge_kw: z.number(),
   battery_stored_kwh: z.number(),
@@ -79,6 +89,15 @@ export const eventSchemas = {
     asteroid_id: z.string(),
     ore_lot: inventoryItemSchema,
     asteroid_remaining_kg: z.number(),
+    layer: z.enum(['Surface', 'Core']).nullable().optional(),
+  }),
+
+  DroneOreMined: z.object({
+    station_id: z.string(),
+    module_id: z.string(),
+    asteroid_id: z.string(),
+    ore_lot: inventoryItemSchema,
+    asteroid_remaining_kg: z.number(),
   }),
 
   OreDeposited: z.object({
@@ -96,6 +115,17 @@ export const eventSchemas = {
     items: z.array(inventoryItemSchema),
   }),
 
+  /** TransferCargo finished; `items` is what actually moved. */
+  CargoTransferred: z.object({
+    ship_id: z.string(),
+    counterparty: z.union([
+      z.object({ Ship: z.string() }),
+      z.object({ Station: z.string() }),
+    ]),
+    direction: z.enum(['Load', 'Unload']),
+    items: z.array(inventoryItemSchema),
+  }),
+
   ModuleInstalled: z.object({
     station_id: z.string(),
     module_id: z.string(),
@@ -112,6 +142,22 @@ export const eventSchemas = {
     module_item_id: z.string(),
   }),
 
+  ModuleScrapped: z.object({
+    station_id: z.string(),
+    module_id: z.string(),
+    module_def_id: z.string(),
+    element: z.string(),
+    kg: z.number(),
+  }),
+
+  ModuleUpgraded: z.object({
+    station_id: z.string(),
+    module_id: z.string(),
+    from_def_id: z.string(),
+    to_def_id: z.string(),
+    wear: z.number(),
+  }),
+
   /** Emitted when InstallModule fails on a framed station because no
    *  compatible unoccupied slot is available. The module stays in inventory. */
   ModuleNoCompatibleSlot: z.object({
@@ -150,6 +196,7 @@ export const eventSchemas = {
     component_produced_id: z.string(),
     component_produced_count: z.number(),
     component_quality: z.number(),
+    slot: z.number().default(0),
   }),
 
   WearAccumulated: z.object({
@@ -168,21 +215,32 @@ export const eventSchemas = {
     station_id: z.string(),
     module_id: z.string(),
     shortfall_m3: z.number(),
+    slot: z.number().default(0),
   }),
 
   ModuleResumed: z.object({
     station_id: z.string(),
     module_id: z.string(),
+    slot: z.number().default(0),
+  }),
+
+  AssemblerJobCompleted: z.object({
+    station_id: z.string(),
+    module_id: z.string(),
+    recipe_id: z.string(),
+    count: z.number(),
   }),
 
   AssemblerCapped: z.object({
     station_id: z.string(),
     module_id: z.string(),
+    slot: z.number().default(0),
   }),
 
   AssemblerUncapped: z.object({
     station_id: z.string(),
     module_id: z.string(),
+    slot: z.number().default(0),
   }),
 
   DepositBlocked: z.object({
@@ -204,6 +262,14 @@ export const eventSchemas = {
     repair_kits_remaining: z.number(),
   }),
 
+  ShipRepaired: z.object({
+    ship_id: z.string(),
+    station_id: z.string(),
+    wear_before: z.number(),
+    wear_after: z.number(),
+    repair_kits_used: z.number(),
+  }),
+
   LabRan: z.object({
     station_id: z.string(),
     module_id: z.string(),
@@ -231,6 +297,17 @@ export const eventSchemas = {
   CompositionMapped: z.object({
     asteroid_id: z.string(),
     composition: z.record(z.string(), z.number()),
+    layers: z
+      .object({
+        surface_fraction: z.number(),
+        initial_mass_kg: z.number(),
+        surface: z.record(z.string(), z.number()),
+        core: z.record(z.string(), z.number()),
+      })
+      .nullable()
+      .optional(),
+    composition_sigma: z.number().optional(),
+    deep_scan_count: z.number().optional(),
   }),
 
   TechUnlocked: z.object({
@@ -250,6 +327,17 @@ export const eventSchemas = {
     cargo_capacity_m3: z.number(),
     hull_id: z.string(),
     fitted_modules: z.array(z.object({ slot_index: z.number(), module_def_id: z.string() })).optional(),
+    owner: z.string().default('principal_autopilot'),
+    name: z.string().default(''),
+    class_id: z.string().nullable().optional(),
+    role: z.string().nullable().optional(),
+  }),
+
+  ShipLost: z.object({
+    ship_id: z.string(),
+    owner: z.string(),
+    cause: z.enum(['solar_storm', 'hull_failure', 'sim_event']),
+    insurance_payout: z.number(),
   }),
 
   ShipModuleFitted: z.object({
@@ -280,11 +368,209 @@ export const eventSchemas = {
     balance_after: z.number(),
   }),
 
+  ImportOrderPlaced: z.object({
+    order_id: z.number(),
+    station_id: z.string(),
+    item_spec: tradeItemSpecSchema,
+    cost: z.number(),
+    balance_after: z.number(),
+    arrival_tick: z.number(),
+  }),
+
+  ImportDelivered: z.object({
+    order_id: z.number(),
+    station_id: z.string(),
+    item_spec: tradeItemSpecSchema,
+  }),
+
+  ImportOrderCancelled: z.object({
+    order_id: z.number(),
+    station_id: z.string(),
+    item_spec: tradeItemSpecSchema,
+    refund: z.number(),
+    balance_after: z.number(),
+  }),
+
+  RoyaltyPaid: z.object({
+    station_id: z.string(),
+    module_id: z.string(),
+    item_id: z.string(),
+    units: z.number(),
+    amount: z.number(),
+    balance_after: z.number(),
+  }),
+
+  FreightScheduled: z.object({
+    order_id: z.number(),
+    from_station: z.string(),
+    to_station: z.string(),
+    item_spec: tradeItemSpecSchema,
+    cost: z.number(),
+    balance_after: z.number(),
+    arrival_tick: z.number(),
+  }),
+
+  FreightDelivered: z.object({
+    order_id: z.number(),
+    from_station: z.string(),
+    to_station: z.string(),
+    item_spec: tradeItemSpecSchema,
+    route_id: z.number().optional(),
+    ship_id: z.string().optional(),
+  }),
+
+  PriceChanged: z.object({
+    item_id: z.string(),
+    old_price: z.number(),
+    new_price: z.number(),
+  }),
+
+  FreightRouteCreated: z.object({
+    route_id: z.number(),
+    owner: z.string(),
+    from_station: z.string(),
+    to_station: z.string(),
+    item_spec: tradeItemSpecSchema,
+    interval_ticks: z.number(),
+  }),
+
   SlagJettisoned: z.object({
     station_id: z.string(),
     kg: z.number(),
   }),
 
+  SlagPollutionWarning: z.object({
+    body_id: z.string(),
+    jettisoned_kg: z.number(),
+    threshold_kg: z.number(),
+  }),
+
+  StormStarted: z.object({
+    body_id: z.string(),
+    ends_tick: z.number(),
+    interrupted_ships: z.array(z.string()),
+  }),
+
+  StormEnded: z.object({
+    body_id: z.string(),
+  }),
+
+  TaskOutOfRange: z.object({
+    ship_id: z.string(),
+    target_body: z.string(),
+    hops: z.number(),
+    range_hops: z.number(),
+    refused: z.boolean(),
+  }),
+
+  BodyClaimed: z.object({
+    body_id: z.string(),
+    owner: z.string(),
+    basis: z.enum(['credits', 'station']),
+    cost: z.number(),
+    expires_tick: z.number().nullable(),
+    contested_from: z.string().nullable(),
+  }),
+
+  ClaimRejected: z.object({
+    body_id: z.string(),
+    principal: z.string(),
+    reason: z.string(),
+  }),
+
+  ClaimReleased: z.object({
+    body_id: z.string(),
+    owner: z.string(),
+  }),
+
+  ClaimExpired: z.object({
+    body_id: z.string(),
+    owner: z.string(),
+  }),
+
+  CommandCancelled: z.object({
+    command_id: z.number(),
+    issued_by: z.string(),
+  }),
+
+  CommandRejected: z.object({
+    command_id: z.number(),
+    reason: z.string(),
+  }),
+
+  ContractOffered: z.object({
+    contract_id: z.number(),
+    kind: contractKindSchema,
+    reward: z.number(),
+    penalty: z.number(),
+    deadline_tick: z.number(),
+  }),
+
+  ContractAccepted: z.object({
+    contract_id: z.number(),
+    holder: z.string(),
+  }),
+
+  ContractCompleted: z.object({
+    contract_id: z.number(),
+    holder: z.string(),
+    reward: z.number(),
+  }),
+
+  ContractFailed: z.object({
+    contract_id: z.number(),
+    holder: z.string(),
+    penalty: z.number(),
+    abandoned: z.boolean(),
+  }),
+
+  MiningRightsDenied: z.object({
+    ship_id: z.string(),
+    body_id: z.string(),
+    holder: z.string(),
+  }),
+
+  ControllerFault: z.object({
+    controller: z.string(),
+    message: z.string(),
+  }),
+
+  SimPaused: z.object({
+    reason: z.string().nullable(),
+    scheduled: z.boolean(),
+  }),
+
+  SimResumed: z.object({}),
+
+  TickHotspots: z.object({
+    window_ticks: z.number(),
+    hotspots: z.array(
+      z.object({
+        station_id: z.string(),
+        phase: z.string(),
+        total_us: z.number(),
+      }),
+    ),
+  }),
+
+  DebrisStrike: z.object({
+    ship_id: z.string(),
+    body_id: z.string(),
+    propellant_lost_kg: z.number(),
+  }),
+
+  CargoStolen: z.object({
+    ship_id: z.string(),
+    body_id: z.string(),
+    kg_stolen: z.number(),
+  }),
+
+  ThreatRepelled: z.object({
+    ship_id: z.string(),
+    body_id: z.string(),
+    escorts: z.array(z.string()),
+  }),
+
   PowerStateUpdated: z.object({
     station_id: z.string(),
     power: powerStateSchema,
@@ -319,6 +605,12 @@ export const eventSchemas = {
     tech_id: z.string(),
   }),
 
+  ModuleIdleDiagnosis: z.object({
+    station_id: z.string(),
+    module_id: z.string(),
+    reasons: z.array(z.union([z.string(), z.record(z.string(), z.unknown())])),
+  }),
+
   InsufficientFunds: z.object({
     station_id: z.string(),
     action: z.string(),
@@ -383,6 +675,57 @@ export const eventSchemas = {
     kg_lost: z.number(),
   }),
 
+  ProductionReport: z.object({
+    station_id: z.string(),
+    module_id: z.string(),
+    start_tick: z.number(),
+    end_tick: z.number(),
+    runs: z.number(),
+    input_kg: z.number(),
+    output_kg: z.number(),
+    output_units: z.number(),
+    average_quality: z.number(),
+  }),
+
+  OreSpoiled: z.object({
+    ship_id: z.string().nullable().optional(),
+    station_id: z.string().nullable().optional(),
+    kg_lost: z.number(),
+  }),
+
+  ProjectStarted: z.object({
+    project_id: z.number(),
+    station_id: z.string(),
+    project_def_id: z.string(),
+  }),
+
+  ProjectStalled: z.object({
+    project_id: z.number(),
+    station_id: z.string(),
+    element: z.string(),
+  }),
+
+  ProjectCompleted: z.object({
+    project_id: z.number(),
+    station_id: z.string(),
+    project_def_id: z.string(),
+    outcome: z.record(z.string(), z.unknown()),
+  }),
+
+  ProjectCancelled: z.object({
+    project_id: z.number(),
+    station_id: z.string(),
+    project_def_id: z.string(),
+    refunded_kg: z.number(),
+  }),
+
+  StationBuildStarted: z.object({
+    source_station: z.string(),
+    frame_id: z.string(),
+    position: positionSchema,
+    complete_tick: z.number(),
+  }),
+
   RecipeSelectionReset: z.object({
     station_id: z.string(),
     module_id: z.string(),
@@ -424,6 +767,17 @@ export const eventSchemas = {
     module_id: z.string(),
   }),
 
+  LifeSupportFailed: z.object({
+    station_id: z.string(),
+    crew: z.number(),
+    crew_capacity: z.number(),
+    short_element: z.string().nullable(),
+  }),
+
+  LifeSupportRestored: z.object({
+    station_id: z.string(),
+  }),
+
   ThermalLinkCreated: z.object({
     station_id: z.string(),
     from_module_id: z.string(),
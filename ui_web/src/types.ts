@@ -5,8 +5,11 @@ export interface MetaInfo {
   content_version: string
   ticks_per_sec: number
   paused: boolean
+  pause_reason?: string | null
+  scheduled_pauses?: { at_tick: number; reason: string | null }[]
   minutes_per_tick: number
   trade_unlock_tick: number
+  command_schema_version?: number
 }
 
 export interface TaskState {
@@ -14,8 +17,11 @@ export interface TaskState {
     | { Idle: Record<string, never> }
     | { Survey: { site: string } }
     | { DeepScan: { asteroid: string } }
+    | { Resurvey: { asteroid: string } }
     | { Mine: { asteroid: string; duration_ticks: number } }
     | { Deposit: { station: string; blocked: boolean } }
+    | { DockForRepair: { station: string } }
+    | { Escort: { ship: string } }
     | { Transit: { destination: Position; total_ticks: number; then: Record<string, unknown> } }
   started_tick: number
   eta_tick: number
@@ -65,6 +71,8 @@ export interface ModuleItem {
   kind: 'Module'
   item_id: string
   module_def_id: string
+  /** Wear carried over from an uninstalled module. */
+  wear?: number
 }
 
 export type InventoryItem = OreItem | SlagItem | MaterialItem | ComponentItem | ModuleItem
@@ -81,12 +89,36 @@ export interface MaintenanceState {
   ticks_since_last_run: number
 }
 
+export interface AssemblerJob {
+  recipe_id: string
+  progress: number
+  material_element: string
+  material_consumed_kg: number
+}
+
+export interface AssemblerQueueEntry {
+  recipe_id: string
+  count: number
+  started: number
+  completed: number
+}
+
+export interface AssemblerSlotState {
+  ticks_since_last_run: number
+  stalled: boolean
+  capped: boolean
+  job?: AssemblerJob | null
+}
+
 export interface AssemblerState {
   ticks_since_last_run: number
   stalled: boolean
   capped: boolean
   cap_override: Record<string, number>
   selected_recipe?: string | null
+  job?: AssemblerJob | null
+  extra_slots?: AssemblerSlotState[]
+  queue?: AssemblerQueueEntry[]
 }
 
 export interface LabState {
@@ -114,8 +146,15 @@ export type ModuleKindState =
   | { Radiator: Record<string, never> }
   | { ThermalContainer: ThermalContainerState }
   | { LaunchPad: LaunchPadState }
+  | { DroneBay: DroneBayState }
   | 'Storage'
   | 'Equipment'
+  | 'Habitat'
+
+export interface DroneBayState {
+  ticks_since_last_run: number
+  stalled: boolean
+}
 
 export interface LaunchPadState {
   available: boolean
@@ -177,14 +216,39 @@ export interface ShipState {
   fitted_modules?: FittedModule[]
   propellant_kg?: number
   propellant_capacity_kg?: number
+  wear?: WearState
   crew?: Record<string, number>
   leaders?: string[]
+  name?: string
+  class_id?: string
+  role?: string
+}
+
+export type HoldPartition = 'ore_bay' | 'component_locker'
+
+export interface PartitionUsage {
+  /** `null` is the space shared by cargo of unreserved partitions. */
+  partition: HoldPartition | null
+  capacity_m3: number
+  used_m3: number
+}
+
+export interface HoldUsage {
+  capacity_m3: number
+  used_m3: number
+  /** Empty for an undivided hold. */
+  partitions: PartitionUsage[]
 }
 
 export interface PowerState {
   generated_kw: number
   consumed_kw: number
   deficit_kw: number
+  idle_kw?: number
+  active_kw?: number
+  stalled_kw?: number
+  brownout_kw?: number
+  deferred_kw?: number
   battery_discharge_kw: number
   battery_charge_kw: number
   battery_stored_kwh: number
@@ -204,12 +268,19 @@ export interface StationState {
    *  contributes frame bonuses via the modifier pipeline. Missing on legacy
    *  frameless stations. */
   frame_id?: string
+  owner?: string
+  /** Recent significant events at this station, oldest first. Omitted
+   *  when empty. */
+  history?: SimEvent[]
 }
 
 export interface AsteroidKnowledge {
   // Each entry: ["IronRich", 0.85]
   tag_beliefs: [string, number][]
   composition: Record<string, number> | null
+  /** Noise left in `composition` after combining every deep scan. */
+  composition_sigma?: number | null
+  deep_scan_count?: number
 }
 
 export interface AsteroidState {
@@ -235,6 +306,7 @@ export interface ResearchState {
   data_pool: Record<string, number>
   evidence: Record<string, DomainProgress>
   action_counts: Record<string, number>
+  queue?: string[]
 }
 
 export interface AbsolutePos {
@@ -263,6 +335,7 @@ export interface OrbitalBodyDef {
   angle_mdeg: number
   solar_intensity: number
   zone: ZoneDef | null
+  security?: number
 }
 
 export interface SolarSystemConfig {
@@ -273,15 +346,23 @@ export interface SolarSystemConfig {
   docking_range_au_um: number
 }
 
+export interface LoanState {
+  outstanding: number
+  missed_payments: number
+  defaulted: boolean
+}
+
 export interface SimSnapshot {
   meta: MetaInfo
   balance: number
+  loan?: LoanState
   scan_sites: ScanSite[]
   asteroids: Record<string, AsteroidState>
   ships: Record<string, ShipState>
   stations: Record<string, StationState>
   research: ResearchState
   body_absolutes: Record<string, AbsolutePos>
+  ship_holds?: Record<string, HoldUsage>
 }
 
 export interface SimEvent {
@@ -397,6 +478,7 @@ export interface ItemFlowStats {
 export interface SlotDef {
   slot_type: string
   label: string
+  adjacent?: number[]
 }
 
 export interface HullDef {
@@ -406,11 +488,23 @@ export interface HullDef {
   cargo_capacity_m3: number
   base_speed_ticks_per_au: number
   base_propellant_capacity_kg: number
+  replacement_value?: number
   slots: SlotDef[]
   bonuses: unknown[]
   tags: string[]
 }
 
+/** Construction variant of a hull. Mirrors the Rust `ShipClassDef` struct;
+ *  unset capacities keep the hull's. */
+export interface ShipClassDef {
+  id: string
+  name: string
+  cargo_capacity_m3?: number
+  propellant_capacity_kg?: number
+  speed_multiplier: number
+  mining_rate_multiplier: number
+}
+
 /** Station frame definition (SF-01+). Determines slot layout and frame
  *  bonuses. Mirrors the Rust `FrameDef` struct. */
 export interface FrameDef {
@@ -431,6 +525,9 @@ export interface ContentResponse {
   minutes_per_tick: number
   recipes: Record<string, RecipeDef>
   hulls: Record<string, HullDef>
+  /** Ship class catalog. Empty when the content drop has no
+   *  `ship_classes.json`. */
+  ship_classes: Record<string, ShipClassDef>
   /** Station frame catalog (SF-07). Empty when the content drop has no
    *  `frame_defs.json`. */
   frames: Record<string, FrameDef>